@@ -1,10 +1,13 @@
+use crate::bech32m;
 use crate::format::BitcoinFormat;
 use crate::network::BitcoinNetwork;
 use crate::private_key::BitcoinPrivateKey;
 use crate::public_key::BitcoinPublicKey;
+use crate::taproot::{tweak_public_key, TaprootScriptTree};
 use crate::witness_program::WitnessProgram;
 use wagyu_model::no_std::*;
 use wagyu_model::{
+    batch_hash::{checksum_batch, hash160_batch},
     crypto::{checksum, hash160},
     Address, AddressError, PrivateKey,
 };
@@ -43,6 +46,7 @@ impl<N: BitcoinNetwork> Address for BitcoinAddress<N> {
             }
             BitcoinFormat::P2SH_P2WPKH => Self::p2sh_p2wpkh(&public_key),
             BitcoinFormat::Bech32 => Self::bech32(&public_key),
+            BitcoinFormat::P2TR => Self::p2tr(&public_key),
         }
     }
 
@@ -58,6 +62,7 @@ impl<N: BitcoinNetwork> Address for BitcoinAddress<N> {
             }
             BitcoinFormat::P2SH_P2WPKH => Self::p2sh_p2wpkh(public_key),
             BitcoinFormat::Bech32 => Self::bech32(public_key),
+            BitcoinFormat::P2TR => Self::p2tr(public_key),
         }
     }
 }
@@ -123,7 +128,80 @@ impl<N: BitcoinNetwork> BitcoinAddress<N> {
 
     /// Returns a Bech32 address from a given Bitcoin public key.
     pub fn bech32(public_key: &<Self as Address>::PublicKey) -> Result<Self, AddressError> {
-        let redeem_script = Self::create_redeem_script(public_key);
+        Self::bech32_redeem_script(&Self::create_redeem_script(public_key))
+    }
+
+    /// Returns the addresses of the given Bitcoin public keys in the given format, with the hashes of the
+    /// public keys, and the checksums of the addresses, computed as batches. A P2TR address is a tweak of its
+    /// public key instead of a hash of it, so P2TR addresses are derived one public key at a time.
+    pub fn from_public_keys(
+        public_keys: &[<Self as Address>::PublicKey],
+        format: &BitcoinFormat,
+    ) -> Result<Vec<Self>, AddressError> {
+        let compressed = public_keys
+            .iter()
+            .map(|public_key| public_key.to_secp256k1_public_key().serialize_compressed().to_vec())
+            .collect::<Vec<_>>();
+        match format {
+            BitcoinFormat::P2PKH => {
+                let public_keys = public_keys
+                    .iter()
+                    .zip(compressed)
+                    .map(|(public_key, compressed)| match public_key.is_compressed() {
+                        true => compressed,
+                        false => public_key.to_secp256k1_public_key().serialize().to_vec(),
+                    })
+                    .collect::<Vec<_>>();
+                Ok(Self::base58_check_batch(format, &hash160_batch(&public_keys)))
+            }
+            BitcoinFormat::P2SH_P2WPKH => {
+                let redeem_scripts = hash160_batch(&compressed)
+                    .iter()
+                    .map(|hash| Self::p2wpkh_redeem_script(hash))
+                    .collect::<Vec<_>>();
+                Ok(Self::base58_check_batch(format, &hash160_batch(&redeem_scripts)))
+            }
+            BitcoinFormat::Bech32 => hash160_batch(&compressed)
+                .iter()
+                .map(|hash| Self::bech32_redeem_script(&Self::p2wpkh_redeem_script(hash)))
+                .collect(),
+            _ => public_keys
+                .iter()
+                .map(|public_key| Self::from_public_key(public_key, format))
+                .collect(),
+        }
+    }
+
+    /// Returns the Base58Check addresses of the given format of the given hashes, with their checksums
+    /// computed as a batch.
+    fn base58_check_batch(format: &BitcoinFormat, hashes: &[[u8; 20]]) -> Vec<Self> {
+        let payloads = hashes
+            .iter()
+            .map(|hash| {
+                let mut payload = [0u8; 21];
+                payload[0] = N::to_address_prefix(format)[0];
+                payload[1..].copy_from_slice(hash);
+                payload
+            })
+            .collect::<Vec<_>>();
+        payloads
+            .iter()
+            .zip(checksum_batch(&payloads))
+            .map(|(payload, sum)| {
+                let mut address = [0u8; 25];
+                address[0..21].copy_from_slice(payload);
+                address[21..25].copy_from_slice(&sum[0..4]);
+                Self {
+                    address: address.to_base58(),
+                    format: format.clone(),
+                    _network: PhantomData,
+                }
+            })
+            .collect()
+    }
+
+    /// Returns a Bech32 address from a given P2WPKH redeem script.
+    fn bech32_redeem_script(redeem_script: &[u8; 22]) -> Result<Self, AddressError> {
         let version = u5::try_from_u8(redeem_script[0])?;
 
         let mut data = vec![version];
@@ -138,6 +216,33 @@ impl<N: BitcoinNetwork> BitcoinAddress<N> {
         })
     }
 
+    /// Returns a P2TR address from a given Bitcoin public key, with no script tree (BIP-86).
+    pub fn p2tr(public_key: &<Self as Address>::PublicKey) -> Result<Self, AddressError> {
+        Self::p2tr_output_key(&tweak_public_key(&public_key.to_secp256k1_public_key(), None)?)
+    }
+
+    /// Returns a P2TR address from a given Bitcoin public key, the internal key of a given script tree.
+    pub fn p2tr_script_tree(
+        public_key: &<Self as Address>::PublicKey,
+        script_tree: &TaprootScriptTree,
+    ) -> Result<Self, AddressError> {
+        let merkle_root = Some(&script_tree.merkle_root);
+        Self::p2tr_output_key(&tweak_public_key(&public_key.to_secp256k1_public_key(), merkle_root)?)
+    }
+
+    /// Returns a P2TR address from a given x-only output key.
+    fn p2tr_output_key(output_key: &[u8; 32]) -> Result<Self, AddressError> {
+        let mut data = vec![1u8];
+        data.extend(output_key.to_base32().iter().map(|value| value.to_u8()));
+
+        let hrp = String::from_utf8(N::to_address_prefix(&BitcoinFormat::P2TR))?;
+        Ok(Self {
+            address: bech32m::encode(&hrp, &data),
+            format: BitcoinFormat::P2TR,
+            _network: PhantomData,
+        })
+    }
+
     /// Returns the format of the Bitcoin address.
     pub fn format(&self) -> BitcoinFormat {
         self.format.clone()
@@ -145,9 +250,14 @@ impl<N: BitcoinNetwork> BitcoinAddress<N> {
 
     /// Returns a redeem script for a given Bitcoin public key.
     fn create_redeem_script(public_key: &<Self as Address>::PublicKey) -> [u8; 22] {
+        Self::p2wpkh_redeem_script(&hash160(&public_key.to_secp256k1_public_key().serialize_compressed()))
+    }
+
+    /// Returns a redeem script for a given hash of a Bitcoin public key.
+    fn p2wpkh_redeem_script(hash: &[u8]) -> [u8; 22] {
         let mut redeem = [0u8; 22];
         redeem[1] = 0x14;
-        redeem[2..].copy_from_slice(&hash160(&public_key.to_secp256k1_public_key().serialize_compressed()));
+        redeem[2..].copy_from_slice(hash);
         redeem
     }
 }
@@ -172,7 +282,41 @@ impl<N: BitcoinNetwork> FromStr for BitcoinAddress<N> {
 
         if let Ok(format) = BitcoinFormat::from_address_prefix(prefix.as_bytes()) {
             if BitcoinFormat::Bech32 == format {
-                let bech32 = Bech32::from_str(&address)?;
+                // Witness version 1 and later addresses are encoded in Bech32m
+                if let Ok((_, data)) = bech32m::decode(address) {
+                    if data.is_empty() || data[0] == 0 {
+                        return Err(AddressError::InvalidAddress(address.to_owned()));
+                    }
+
+                    let data = data
+                        .iter()
+                        .map(|value| u5::try_from_u8(*value))
+                        .collect::<Result<Vec<u5>, _>>()?;
+                    let mut program = Vec::from_base32(&data[1..])?;
+                    let mut witness_program = vec![data[0].to_u8(), program.len() as u8];
+                    witness_program.append(&mut program);
+
+                    // Check that the witness program is a valid taproot output.
+                    let witness_program = WitnessProgram::new(witness_program.as_slice())?;
+                    if witness_program.version != 1 || witness_program.program.len() != 32 {
+                        return Err(AddressError::InvalidAddress(address.to_owned()));
+                    }
+                    // Check that the address prefix corresponds to the correct network.
+                    let _ = N::from_address_prefix(prefix.as_bytes())?;
+
+                    return Ok(Self {
+                        address: address.to_owned(),
+                        format: BitcoinFormat::P2TR,
+                        _network: PhantomData,
+                    });
+                }
+
+                let bech32 = Bech32::from_str(address).map_err(|error| match bech32m::locate_errors(address) {
+                    positions if !positions.is_empty() => {
+                        AddressError::InvalidBech32Checksum(address.to_owned(), positions)
+                    }
+                    _ => AddressError::from(error),
+                })?;
                 if bech32.data().is_empty() {
                     return Err(AddressError::InvalidAddress(address.to_owned()));
                 }
@@ -244,6 +388,21 @@ mod tests {
         assert_eq!(expected_address, address.to_string());
     }
 
+    fn test_from_public_keys<N: BitcoinNetwork>(keypairs: &[(&str, &str)], format: &BitcoinFormat) {
+        let public_keys = keypairs
+            .iter()
+            .map(|(private_key, _)| {
+                BitcoinPublicKey::from_private_key(&BitcoinPrivateKey::<N>::from_str(private_key).unwrap())
+            })
+            .collect::<Vec<_>>();
+        let addresses = BitcoinAddress::from_public_keys(&public_keys, format).unwrap();
+        let expected_addresses = keypairs.iter().map(|(_, address)| *address).collect::<Vec<_>>();
+        assert_eq!(
+            expected_addresses,
+            addresses.iter().map(|address| address.to_string()).collect::<Vec<_>>()
+        );
+    }
+
     fn test_from_str<N: BitcoinNetwork>(expected_address: &str, expected_format: &BitcoinFormat) {
         let address = BitcoinAddress::<N>::from_str(expected_address).unwrap();
         assert_eq!(expected_address, address.to_string());
@@ -299,6 +458,11 @@ mod tests {
             });
         }
 
+        #[test]
+        fn from_public_keys() {
+            test_from_public_keys::<N>(&KEYPAIRS, &BitcoinFormat::P2PKH);
+        }
+
         #[test]
         fn from_str() {
             KEYPAIRS.iter().for_each(|(_, address)| {
@@ -360,6 +524,11 @@ mod tests {
             });
         }
 
+        #[test]
+        fn from_public_keys() {
+            test_from_public_keys::<N>(&KEYPAIRS, &BitcoinFormat::P2PKH);
+        }
+
         #[test]
         fn from_str() {
             KEYPAIRS.iter().for_each(|(_, address)| {
@@ -454,6 +623,11 @@ mod tests {
             });
         }
 
+        #[test]
+        fn from_public_keys() {
+            test_from_public_keys::<N>(&KEYPAIRS, &BitcoinFormat::P2PKH);
+        }
+
         #[test]
         fn from_str() {
             KEYPAIRS.iter().for_each(|(_, address)| {
@@ -515,6 +689,11 @@ mod tests {
             });
         }
 
+        #[test]
+        fn from_public_keys() {
+            test_from_public_keys::<N>(&KEYPAIRS, &BitcoinFormat::P2PKH);
+        }
+
         #[test]
         fn from_str() {
             KEYPAIRS.iter().for_each(|(_, address)| {
@@ -576,6 +755,11 @@ mod tests {
             });
         }
 
+        #[test]
+        fn from_public_keys() {
+            test_from_public_keys::<N>(&KEYPAIRS, &BitcoinFormat::P2SH_P2WPKH);
+        }
+
         #[test]
         fn from_str() {
             KEYPAIRS.iter().for_each(|(_, address)| {
@@ -670,6 +854,11 @@ mod tests {
             });
         }
 
+        #[test]
+        fn from_public_keys() {
+            test_from_public_keys::<N>(&KEYPAIRS, &BitcoinFormat::P2SH_P2WPKH);
+        }
+
         #[test]
         fn from_str() {
             KEYPAIRS.iter().for_each(|(_, address)| {
@@ -732,6 +921,14 @@ mod tests {
             });
         }
 
+        #[test]
+        fn from_mistyped_address() {
+            match BitcoinAddress::<N>::from_str(INVALID[0]) {
+                Err(AddressError::InvalidBech32Checksum(_, positions)) => assert_eq!(vec![42], positions),
+                result => panic!("expected an invalid bech32 checksum, found {:?}", result),
+            }
+        }
+
         #[test]
         fn from_private_key() {
             KEYPAIRS.iter().for_each(|(private_key, address)| {
@@ -749,6 +946,11 @@ mod tests {
             });
         }
 
+        #[test]
+        fn from_public_keys() {
+            test_from_public_keys::<N>(&KEYPAIRS, &BitcoinFormat::Bech32);
+        }
+
         #[test]
         fn from_str() {
             KEYPAIRS.iter().for_each(|(_, address)| {
@@ -823,6 +1025,11 @@ mod tests {
             });
         }
 
+        #[test]
+        fn from_public_keys() {
+            test_from_public_keys::<N>(&KEYPAIRS, &BitcoinFormat::Bech32);
+        }
+
         #[test]
         fn from_str() {
             KEYPAIRS.iter().for_each(|(_, address)| {
@@ -873,4 +1080,78 @@ mod tests {
             });
         }
     }
+
+    mod p2tr_mainnet {
+        use super::*;
+        use crate::{BitcoinDerivationPath, BitcoinExtendedPrivateKey};
+        use wagyu_model::ExtendedPrivateKey;
+
+        type N = Mainnet;
+
+        // The master extended private key of "abandon abandon ... about"
+        const MASTER_EXTENDED_PRIVATE_KEY: &str = "xprv9s21ZrQH143K3GJpoapnV8SFfukcVBSfeCficPSGfubmSFDxo1kuHnLisriDvSnRRuL2Qrg5ggqHKNVpxR86QEC8w35uxmGoggxtQTPvfUu";
+
+        // The first receiving addresses of BIP-44, BIP-49, BIP-84, and BIP-86 (https://github.com/bitcoin/bips/blob/master/bip-0086.mediawiki#test-vectors)
+        const PATHPAIRS: [(&str, BitcoinFormat, &str); 4] = [
+            (
+                "m/44'/0'/0'/0/0",
+                BitcoinFormat::P2PKH,
+                "1LqBGSKuX5yYUonjxT5qGfpUsXKYYWeabA",
+            ),
+            (
+                "m/49'/0'/0'/0/0",
+                BitcoinFormat::P2SH_P2WPKH,
+                "37VucYSaXLCAsxYyAPfbSi9eh4iEcbShgf",
+            ),
+            (
+                "m/84'/0'/0'/0/0",
+                BitcoinFormat::Bech32,
+                "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu",
+            ),
+            (
+                "m/86'/0'/0'/0/0",
+                BitcoinFormat::P2TR,
+                "bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr",
+            ),
+        ];
+
+        const INVALID: [&str; 2] = [
+            "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kemeawh", // bech32m checksum for witness version 0
+            "tb1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vq47zagq", // testnet prefix
+        ];
+
+        #[test]
+        fn from_extended_private_key() {
+            let master_extended_private_key =
+                BitcoinExtendedPrivateKey::<N>::from_str(MASTER_EXTENDED_PRIVATE_KEY).unwrap();
+            PATHPAIRS.iter().for_each(|(path, expected_format, expected_address)| {
+                let path = BitcoinDerivationPath::from_str(path).unwrap();
+                let extended_private_key = master_extended_private_key.derive(&path).unwrap();
+                assert_eq!(*expected_format, extended_private_key.format());
+
+                // The extended private key round trips through its version bytes
+                let encoded = extended_private_key.to_string();
+                assert_eq!(
+                    encoded,
+                    BitcoinExtendedPrivateKey::<N>::from_str(&encoded).unwrap().to_string()
+                );
+
+                let address = extended_private_key.to_address(&extended_private_key.format()).unwrap();
+                assert_eq!(*expected_address, address.to_string());
+            });
+        }
+
+        #[test]
+        fn from_str() {
+            let (_, _, address) = PATHPAIRS[3];
+            test_from_str::<N>(address, &BitcoinFormat::P2TR);
+        }
+
+        #[test]
+        fn from_invalid_address() {
+            INVALID.iter().for_each(|invalid_address| {
+                assert!(BitcoinAddress::<N>::from_str(invalid_address).is_err());
+            });
+        }
+    }
 }
@@ -0,0 +1,212 @@
+//!
+//! Bech32m
+//!
+//! This module contains the Bech32m encoding of witness version 1 and later addresses,
+//! which differs from Bech32 by the constant of its checksum, and the location of
+//! a mistyped character in either encoding.
+//! https://github.com/bitcoin/bips/blob/master/bip-0350.mediawiki
+//!
+
+use wagyu_model::no_std::*;
+use wagyu_model::AddressError;
+
+/// The Bech32m character set
+const CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/// The constant of the Bech32 checksum
+const BECH32_CONST: u32 = 1;
+
+/// The constant of the Bech32m checksum
+const BECH32M_CONST: u32 = 0x2bc8_30a3;
+
+/// Returns the BCH checksum of the given 5-bit values.
+fn polymod(values: &[u8]) -> u32 {
+    const GENERATOR: [u32; 5] = [0x3b6a_57b2, 0x2650_8e6d, 0x1ea1_19fa, 0x3d42_33dd, 0x2a14_62b3];
+    let mut checksum = 1u32;
+    for value in values {
+        let top = checksum >> 25;
+        checksum = ((checksum & 0x01ff_ffff) << 5) ^ u32::from(*value);
+        for (index, generator) in GENERATOR.iter().enumerate() {
+            if (top >> index) & 1 == 1 {
+                checksum ^= generator;
+            }
+        }
+    }
+    checksum
+}
+
+/// Returns the human-readable part expanded for the checksum.
+fn expand(hrp: &str) -> Vec<u8> {
+    let mut values: Vec<u8> = hrp.bytes().map(|byte| byte >> 5).collect();
+    values.push(0);
+    values.extend(hrp.bytes().map(|byte| byte & 0x1f));
+    values
+}
+
+/// Returns the Bech32m encoding of the given human-readable part and 5-bit values.
+pub fn encode(hrp: &str, data: &[u8]) -> String {
+    let mut values = expand(hrp);
+    values.extend_from_slice(data);
+    values.extend_from_slice(&[0u8; 6]);
+    let checksum = polymod(&values) ^ BECH32M_CONST;
+
+    let mut encoded = format!("{}1", hrp);
+    for value in data {
+        encoded.push(CHARSET[*value as usize] as char);
+    }
+    for index in 0..6 {
+        encoded.push(CHARSET[((checksum >> (5 * (5 - index))) & 0x1f) as usize] as char);
+    }
+    encoded
+}
+
+/// Returns the human-readable part and 5-bit values of the given Bech32m encoding.
+pub fn decode(encoded: &str) -> Result<(String, Vec<u8>), AddressError> {
+    let invalid = || AddressError::InvalidAddress(encoded.into());
+    if encoded.to_lowercase() != encoded && encoded.to_uppercase() != encoded {
+        return Err(invalid());
+    }
+
+    let encoded_lowercase = encoded.to_lowercase();
+    let separator = encoded_lowercase.rfind('1').ok_or_else(invalid)?;
+    let (hrp, data) = (&encoded_lowercase[..separator], &encoded_lowercase[separator + 1..]);
+    if hrp.is_empty() || data.len() < 6 {
+        return Err(invalid());
+    }
+
+    let data = data
+        .bytes()
+        .map(|byte| {
+            CHARSET
+                .iter()
+                .position(|character| *character == byte)
+                .map(|value| value as u8)
+        })
+        .collect::<Option<Vec<u8>>>()
+        .ok_or_else(invalid)?;
+
+    let mut values = expand(hrp);
+    values.extend_from_slice(&data);
+    match polymod(&values) == BECH32M_CONST {
+        true => Ok((hrp.to_string(), data[..data.len() - 6].to_vec())),
+        false => Err(invalid()),
+    }
+}
+
+/// Returns the positions (from 1) of the characters of the given Bech32 or Bech32m string
+/// that are probably mistyped, or an empty vector if none can be located.
+///
+/// A single mistyped character is located with the BCH code of the checksum: as the checksum is
+/// linear, the residue of the string is the residue of the error alone, which matches the residue
+/// of exactly one error value at one position for each checksum constant.
+pub fn locate_errors(encoded: &str) -> Vec<usize> {
+    let encoded = encoded.to_lowercase();
+    let separator = match encoded.rfind('1') {
+        Some(separator) => separator,
+        None => return vec![],
+    };
+    let (hrp, data) = (&encoded[..separator], &encoded[separator + 1..]);
+
+    // A character outside of the character set is mistyped
+    let invalid: Vec<usize> = data
+        .bytes()
+        .enumerate()
+        .filter(|(_, byte)| !CHARSET.contains(byte))
+        .map(|(index, _)| separator + index + 2)
+        .collect();
+    if !invalid.is_empty() {
+        return invalid;
+    }
+
+    let mut values = expand(hrp);
+    let offset = values.len();
+    values.extend(
+        data.bytes()
+            .map(|byte| CHARSET.iter().position(|c| *c == byte).unwrap_or(0) as u8),
+    );
+    let residue = polymod(&values);
+
+    let mut zeros = vec![0u8; values.len()];
+    let zero = polymod(&zeros);
+
+    let mut positions = vec![];
+    for constant in [BECH32_CONST, BECH32M_CONST].iter() {
+        // The checksum is valid, so no character is mistyped
+        if residue == *constant {
+            return vec![];
+        }
+        for index in 0..data.len() {
+            for error in 1..32u8 {
+                zeros[offset + index] = error;
+                if polymod(&zeros) ^ zero == residue ^ constant {
+                    positions.push(separator + index + 2);
+                }
+            }
+            zeros[offset + index] = 0;
+        }
+    }
+    positions.sort_unstable();
+    positions.dedup();
+    positions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn valid_checksums() {
+        // https://github.com/bitcoin/bips/blob/master/bip-0350.mediawiki#test-vectors-for-bech32m
+        let encodings = [
+            "A1LQFN3A",
+            "a1lqfn3a",
+            "an83characterlonghumanreadablepartthatcontainsthetheexcludedcharactersbioandnumber11sg7hg6",
+            "abcdef1l7aum6echk45nj3s0wdvt2fg8x9yrzpqzd3ryx",
+            "split1checkupstagehandshakeupstreamerranterredcaperredlc445v",
+            "?1v759aa",
+        ];
+        for encoding in encodings.iter() {
+            let (hrp, data) = decode(encoding).unwrap();
+            assert_eq!(encoding.to_lowercase(), encode(&hrp, &data));
+        }
+    }
+
+    #[test]
+    fn invalid_checksums() {
+        let encodings = [
+            // Bech32 checksum
+            "a12uel5l",
+            // Mixed case
+            "A1lqfn3a",
+            // Invalid character
+            "abcdef1l7aum6echk45nj2s0wdvt2fg8x9yrzpqzd3ryx",
+            // Short checksum
+            "qyrz8wqd2c9m",
+        ];
+        for encoding in encodings.iter() {
+            assert!(decode(encoding).is_err());
+        }
+    }
+
+    #[test]
+    fn locate_mistyped_characters() {
+        // (encoding, positions)
+        let encodings = [
+            // Valid Bech32 and Bech32m checksums
+            ("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4", vec![]),
+            ("bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr", vec![]),
+            // Mistyped characters of a Bech32 checksum
+            ("bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t5", vec![42]),
+            ("bc1qw508d6qejxtdg4y5r3zarvaryOc5xw7kv8f3t4", vec![30]),
+            ("bc1qw508d6qejxtdg4y5r3zarvory0c5xw7kv8f3t4", vec![27]),
+            // A mistyped character of a Bech32m checksum
+            (
+                "bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcq",
+                vec![62],
+            ),
+        ];
+        for (encoding, positions) in encodings.iter() {
+            assert_eq!(*positions, locate_errors(encoding));
+        }
+    }
+}
@@ -16,6 +16,12 @@ pub enum BitcoinDerivationPath<N: BitcoinNetwork> {
     /// BIP49 - m/49'/{0', 1'}/{account}'/{change}/{index} - SegWit Pay-to-Witness-Public-Key Hash
     /// https://github.com/bitcoin/bips/blob/master/bip-0049.mediawiki
     BIP49([ChildIndex; 3]),
+    /// BIP84 - m/84'/{0', 1'}/{account}'/{change}/{index} - Native SegWit Pay-to-Witness-Public-Key Hash
+    /// https://github.com/bitcoin/bips/blob/master/bip-0084.mediawiki
+    BIP84([ChildIndex; 3]),
+    /// BIP86 - m/86'/{0', 1'}/{account}'/{change}/{index} - Pay-to-Taproot
+    /// https://github.com/bitcoin/bips/blob/master/bip-0086.mediawiki
+    BIP86([ChildIndex; 3]),
 }
 
 impl<N: BitcoinNetwork> DerivationPath for BitcoinDerivationPath<N> {
@@ -50,6 +56,30 @@ impl<N: BitcoinNetwork> DerivationPath for BitcoinDerivationPath<N> {
                     false => Err(DerivationPathError::ExpectedBIP49Path),
                 }
             }
+            BitcoinDerivationPath::BIP84(path) => {
+                match path[0].is_hardened() && path[1].is_normal() && path[2].is_normal() {
+                    true => Ok(vec![
+                        ChildIndex::Hardened(84),
+                        N::HD_COIN_TYPE,
+                        path[0],
+                        path[1],
+                        path[2],
+                    ]),
+                    false => Err(DerivationPathError::ExpectedBIP84Path),
+                }
+            }
+            BitcoinDerivationPath::BIP86(path) => {
+                match path[0].is_hardened() && path[1].is_normal() && path[2].is_normal() {
+                    true => Ok(vec![
+                        ChildIndex::Hardened(86),
+                        N::HD_COIN_TYPE,
+                        path[0],
+                        path[1],
+                        path[2],
+                    ]),
+                    false => Err(DerivationPathError::ExpectedBIP86Path),
+                }
+            }
         }
     }
 
@@ -74,7 +104,25 @@ impl<N: BitcoinNetwork> DerivationPath for BitcoinDerivationPath<N> {
             {
                 return Ok(BitcoinDerivationPath::BIP49([path[2], path[3], path[4]]));
             }
-            // Path length 5 - BIP32 (non-BIP44 & non-BIP49 compliant)
+            // Path length 5 - BIP84
+            if path[0] == ChildIndex::Hardened(84)
+                && path[1] == N::HD_COIN_TYPE
+                && path[2].is_hardened()
+                && path[3].is_normal()
+                && path[4].is_normal()
+            {
+                return Ok(BitcoinDerivationPath::BIP84([path[2], path[3], path[4]]));
+            }
+            // Path length 5 - BIP86
+            if path[0] == ChildIndex::Hardened(86)
+                && path[1] == N::HD_COIN_TYPE
+                && path[2].is_hardened()
+                && path[3].is_normal()
+                && path[4].is_normal()
+            {
+                return Ok(BitcoinDerivationPath::BIP86([path[2], path[3], path[4]]));
+            }
+            // Path length 5 - BIP32 (non-BIP44, non-BIP49, non-BIP84 & non-BIP86 compliant)
             return Ok(BitcoinDerivationPath::BIP32(path.to_vec(), PhantomData));
         } else {
             // Path length 0 - BIP32 root key
@@ -592,4 +640,36 @@ mod tests {
             Err(DerivationPathError::InvalidChildNumber(2147483648))
         );
     }
+
+    #[test]
+    fn non_hardened_only() {
+        use super::*;
+
+        type N = Mainnet;
+
+        assert_eq!(
+            BitcoinDerivationPath::<N>::from_str("m/0/1")
+                .unwrap()
+                .non_hardened_only(),
+            Ok(vec![ChildIndex::normal(0).unwrap(), ChildIndex::normal(1).unwrap()])
+        );
+        assert_eq!(
+            BitcoinDerivationPath::<N>::from_str("m").unwrap().non_hardened_only(),
+            Ok(vec![])
+        );
+        assert_eq!(
+            BitcoinDerivationPath::<N>::from_str("m/44'/0'/0'/0/0")
+                .unwrap()
+                .non_hardened_only(),
+            Err(DerivationPathError::HardenedChildFromPublicKey(
+                "m/44'/0'/0'/0/0".into()
+            ))
+        );
+        assert_eq!(
+            BitcoinDerivationPath::<N>::from_str("m/0/1h")
+                .unwrap()
+                .non_hardened_only(),
+            Err(DerivationPathError::HardenedChildFromPublicKey("m/0/1'".into()))
+        );
+    }
 }
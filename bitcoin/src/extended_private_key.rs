@@ -6,7 +6,7 @@ use crate::network::BitcoinNetwork;
 use crate::private_key::BitcoinPrivateKey;
 use crate::public_key::BitcoinPublicKey;
 use wagyu_model::{
-    crypto::{checksum, hash160},
+    crypto::{checksum, constant_time_eq, hash160},
     AddressError, ChildIndex, DerivationPath, ExtendedPrivateKey, ExtendedPrivateKeyError, ExtendedPublicKey,
     PrivateKey,
 };
@@ -20,7 +20,7 @@ use sha2::Sha512;
 type HmacSha512 = Hmac<Sha512>;
 
 /// Represents a Bitcoin extended private key
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Eq)]
 pub struct BitcoinExtendedPrivateKey<N: BitcoinNetwork> {
     /// The address format
     pub(super) format: BitcoinFormat,
@@ -108,6 +108,8 @@ impl<N: BitcoinNetwork> ExtendedPrivateKey for BitcoinExtendedPrivateKey<N> {
 
             let format = match path {
                 BitcoinDerivationPath::BIP49(_) => BitcoinFormat::P2SH_P2WPKH,
+                BitcoinDerivationPath::BIP84(_) => BitcoinFormat::Bech32,
+                BitcoinDerivationPath::BIP86(_) => BitcoinFormat::P2TR,
                 _ => extended_private_key.format.clone(),
             };
 
@@ -150,6 +152,15 @@ impl<N: BitcoinNetwork> BitcoinExtendedPrivateKey<N> {
     pub fn format(&self) -> BitcoinFormat {
         self.format.clone()
     }
+
+    /// Returns the Bitcoin extended private key with the given format,
+    /// which sets its SLIP-132 version bytes and the format of its derived addresses.
+    pub fn with_format(&self, format: &BitcoinFormat) -> Self {
+        Self {
+            format: format.clone(),
+            ..self.clone()
+        }
+    }
 }
 
 impl<N: BitcoinNetwork> FromStr for BitcoinExtendedPrivateKey<N> {
@@ -196,6 +207,20 @@ impl<N: BitcoinNetwork> FromStr for BitcoinExtendedPrivateKey<N> {
     }
 }
 
+impl<N: BitcoinNetwork> PartialEq for BitcoinExtendedPrivateKey<N> {
+    /// Returns `true` if the extended private keys are equal, comparing their private keys and chain codes
+    /// in constant time.
+    fn eq(&self, other: &Self) -> bool {
+        let secrets_eq =
+            (self.private_key == other.private_key) & constant_time_eq(&self.chain_code, &other.chain_code);
+        secrets_eq
+            && self.format == other.format
+            && self.depth == other.depth
+            && self.parent_fingerprint == other.parent_fingerprint
+            && self.child_index == other.child_index
+    }
+}
+
 impl<N: BitcoinNetwork> Display for BitcoinExtendedPrivateKey<N> {
     /// BIP32 serialization format
     /// https://github.com/bitcoin/bips/blob/master/bip-0032.mediawiki#serialization-format
@@ -830,6 +855,44 @@ mod tests {
         }
     }
 
+    mod slip132_mainnet {
+        use super::*;
+
+        type N = Mainnet;
+
+        // (extended_private_key, format), from the BIP49 and BIP84 test vectors
+        const KEYPAIRS: [(&str, BitcoinFormat); 2] = [
+            (
+                "yprvAM7sCKyfKPFyPT1kyKsHWEoWqJPKTAwgyRrVZLe3RaX6dd5EK1NE87WYsi3yGisqGsJoQ21v7WUercQsUdST2HeV91poWQY5mGrk4yWnuy5",
+                BitcoinFormat::P2SH_P2WPKH,
+            ),
+            (
+                "zprvAWgYBBk7JR8Gjrh4UJQ2uJdG1r3WNRRfURiABBE3RvMXYSrRJL62XuezvGdPvG6GFBZduosCc1YP5wixPox7zhZLfiUm8aunE96BBa4Kei5",
+                BitcoinFormat::Bech32,
+            ),
+        ];
+
+        #[test]
+        fn from_str() {
+            KEYPAIRS.iter().for_each(|(extended_private_key, format)| {
+                let extended_private_key = BitcoinExtendedPrivateKey::<N>::from_str(extended_private_key).unwrap();
+                assert_eq!(*format, extended_private_key.format());
+            });
+        }
+
+        #[test]
+        fn with_format() {
+            KEYPAIRS.iter().for_each(|(expected_extended_private_key, format)| {
+                let extended_private_key =
+                    BitcoinExtendedPrivateKey::<N>::from_str(expected_extended_private_key).unwrap();
+                let xprv = extended_private_key.with_format(&BitcoinFormat::P2PKH);
+                assert!(xprv.to_string().starts_with("xprv"));
+                assert_eq!(extended_private_key.to_private_key(), xprv.to_private_key());
+                assert_eq!(*expected_extended_private_key, xprv.with_format(format).to_string());
+            });
+        }
+    }
+
     mod test_invalid {
         use super::*;
 
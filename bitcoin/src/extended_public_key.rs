@@ -61,18 +61,16 @@ impl<N: BitcoinNetwork> ExtendedPublicKey for BitcoinExtendedPublicKey<N> {
 
         let mut extended_public_key = self.clone();
 
-        for index in path.to_vec()?.into_iter() {
-            let public_key_serialized = &self.public_key.to_secp256k1_public_key().serialize_compressed()[..];
-
-            let mut mac = HmacSha512::new_varkey(&self.chain_code)?;
-            match index {
-                // HMAC-SHA512(Key = cpar, Data = serP(Kpar) || ser32(i))
-                ChildIndex::Normal(_) => mac.input(public_key_serialized),
-                // Return failure
-                ChildIndex::Hardened(_) => {
-                    return Err(ExtendedPublicKeyError::InvalidChildNumber(1 << 31, u32::from(index)))
-                }
-            }
+        // Hardened child indices cannot be derived from an extended public key
+        for index in path.non_hardened_only()?.into_iter() {
+            let public_key_serialized = &extended_public_key
+                .public_key
+                .to_secp256k1_public_key()
+                .serialize_compressed()[..];
+
+            let mut mac = HmacSha512::new_varkey(&extended_public_key.chain_code)?;
+            // HMAC-SHA512(Key = cpar, Data = serP(Kpar) || ser32(i))
+            mac.input(public_key_serialized);
             // Append the child index in big-endian format
             mac.input(&u32::from(index).to_be_bytes());
             let hmac = mac.result().code();
@@ -80,7 +78,7 @@ impl<N: BitcoinNetwork> ExtendedPublicKey for BitcoinExtendedPublicKey<N> {
             let mut chain_code = [0u8; 32];
             chain_code[0..32].copy_from_slice(&hmac[32..]);
 
-            let mut public_key = self.public_key.to_secp256k1_public_key();
+            let mut public_key = extended_public_key.public_key.to_secp256k1_public_key();
             public_key.tweak_add_assign(&SecretKey::parse_slice(&hmac[..32])?)?;
             let public_key = Self::PublicKey::from_secp256k1_public_key(public_key, true);
 
@@ -112,10 +110,27 @@ impl<N: BitcoinNetwork> ExtendedPublicKey for BitcoinExtendedPublicKey<N> {
 }
 
 impl<N: BitcoinNetwork> BitcoinExtendedPublicKey<N> {
-    /// Returns the format of the Bitcoin extended private key.
+    /// Returns the format of the Bitcoin extended public key.
     pub fn format(&self) -> BitcoinFormat {
         self.format.clone()
     }
+
+    /// Returns the Bitcoin extended public key with the given format,
+    /// which sets its SLIP-132 version bytes and the format of its derived addresses.
+    pub fn with_format(&self, format: &BitcoinFormat) -> Self {
+        Self {
+            format: format.clone(),
+            ..self.clone()
+        }
+    }
+
+    /// Returns the fingerprint of the extended public key,
+    /// the first 32 bits of its key identifier (hash160(ECDSA_public_key)).
+    pub fn to_fingerprint(&self) -> [u8; 4] {
+        let mut fingerprint = [0u8; 4];
+        fingerprint.copy_from_slice(&hash160(&self.public_key.to_secp256k1_public_key().serialize_compressed())[0..4]);
+        fingerprint
+    }
 }
 
 impl<N: BitcoinNetwork> FromStr for BitcoinExtendedPublicKey<N> {
@@ -441,6 +456,19 @@ mod tests {
             });
         }
 
+        #[test]
+        fn derive_public() {
+            // m/0'/1/2' to m/0'/1/2'/2/1000000000, with each child derived from its parent public key
+            let (_, _, _, _, _, _, _, extended_public_key) = KEYPAIRS[3];
+            let (_, _, _, _, _, _, _, expected_extended_public_key) = KEYPAIRS[5];
+            let extended_public_key = BitcoinExtendedPublicKey::<N>::from_str(extended_public_key).unwrap();
+            let path = BitcoinDerivationPath::from_str("m/2/1000000000").unwrap();
+            assert_eq!(
+                expected_extended_public_key,
+                extended_public_key.derive(&path).unwrap().to_string()
+            );
+        }
+
         #[test]
         fn from_str() {
             KEYPAIRS.iter().for_each(
@@ -462,6 +490,45 @@ mod tests {
                 test_to_string::<N>(extended_public_key);
             });
         }
+
+        #[test]
+        fn to_fingerprint() {
+            // Each key of a test vector is the parent of the next key
+            KEYPAIRS.chunks(6).for_each(|keypairs| {
+                keypairs.windows(2).for_each(|pair| {
+                    let (_, _, _, _, _, _, _, extended_public_key) = pair[0];
+                    let (_, _, _, _, _, expected_fingerprint, _, _) = pair[1];
+                    let extended_public_key = BitcoinExtendedPublicKey::<N>::from_str(extended_public_key).unwrap();
+                    assert_eq!(expected_fingerprint, hex::encode(extended_public_key.to_fingerprint()));
+                });
+            });
+        }
+    }
+
+    mod slip132_mainnet {
+        use super::*;
+
+        type N = Mainnet;
+
+        // (extended_public_key, format), from the BIP49 test vectors
+        const KEYPAIRS: [(&str, BitcoinFormat); 1] = [(
+            "ypub6a7DbqWZ9kpGbw6E5MQHsNkFPLDordfYLen6Mj3eyv45WRQNrYgUfuq2izWkWnBbFsv7vHcPbs2gzJwEYqRobMGiJVFqSAm9w8jiKXYNV92",
+            BitcoinFormat::P2SH_P2WPKH,
+        )];
+
+        #[test]
+        fn with_format() {
+            KEYPAIRS.iter().for_each(|(expected_extended_public_key, format)| {
+                let extended_public_key =
+                    BitcoinExtendedPublicKey::<N>::from_str(expected_extended_public_key).unwrap();
+                assert_eq!(*format, extended_public_key.format());
+
+                let zpub = extended_public_key.with_format(&BitcoinFormat::Bech32);
+                assert!(zpub.to_string().starts_with("zpub"));
+                assert_eq!(extended_public_key.to_public_key(), zpub.to_public_key());
+                assert_eq!(*expected_extended_public_key, zpub.with_format(format).to_string());
+            });
+        }
     }
 
     mod test_invalid {
@@ -493,6 +560,14 @@ mod tests {
             let _result = BitcoinExtendedPublicKey::<N>::from_str(INVALID_EXTENDED_PUBLIC_KEY_CHECKSUM).unwrap();
         }
 
+        #[test]
+        #[should_panic(expected = "HardenedChildFromPublicKey(\"m/0/1'\")")]
+        fn derive_hardened() {
+            let extended_public_key = BitcoinExtendedPublicKey::<N>::from_str(VALID_EXTENDED_PUBLIC_KEY).unwrap();
+            let path = BitcoinDerivationPath::from_str("m/0/1'").unwrap();
+            let _result = extended_public_key.derive(&path).unwrap();
+        }
+
         #[test]
         #[should_panic(expected = "InvalidByteLength(81)")]
         fn from_str_short() {
@@ -17,6 +17,8 @@ pub enum BitcoinFormat {
     P2SH_P2WPKH,
     /// Bech32, e.g. bc1pw508d6qejxtdg4y5r3zarvary0c5xw7kw508d6qejxtdg4y5r3zarvary0c5xw7k7grplx
     Bech32,
+    /// Pay-to-Taproot, e.g. bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr
+    P2TR,
 }
 
 impl Format for BitcoinFormat {}
@@ -46,6 +48,7 @@ impl BitcoinFormat {
         match prefix[0..4] {
             [0x04, 0x88, 0xAD, 0xE4] | [0x04, 0x35, 0x83, 0x94] => Ok(BitcoinFormat::P2PKH),
             [0x04, 0x9D, 0x78, 0x78] | [0x04, 0x4A, 0x4E, 0x28] => Ok(BitcoinFormat::P2SH_P2WPKH),
+            [0x04, 0xB2, 0x43, 0x0C] | [0x04, 0x5F, 0x18, 0xBC] => Ok(BitcoinFormat::Bech32),
             _ => Err(ExtendedPrivateKeyError::InvalidVersionBytes(prefix.to_vec())),
         }
     }
@@ -56,6 +59,7 @@ impl BitcoinFormat {
         match prefix[0..4] {
             [0x04, 0x88, 0xB2, 0x1E] | [0x04, 0x35, 0x87, 0xCF] => Ok(BitcoinFormat::P2PKH),
             [0x04, 0x9D, 0x7C, 0xB2] | [0x04, 0x4A, 0x52, 0x62] => Ok(BitcoinFormat::P2SH_P2WPKH),
+            [0x04, 0xB2, 0x47, 0x46] | [0x04, 0x5F, 0x1C, 0xF6] => Ok(BitcoinFormat::Bech32),
             _ => Err(ExtendedPublicKeyError::InvalidVersionBytes(prefix.to_vec())),
         }
     }
@@ -68,6 +72,7 @@ impl fmt::Display for BitcoinFormat {
             BitcoinFormat::P2WSH => write!(f, "p2wsh"),
             BitcoinFormat::P2SH_P2WPKH => write!(f, "p2sh_p2wpkh"),
             BitcoinFormat::Bech32 => write!(f, "bech32"),
+            BitcoinFormat::P2TR => write!(f, "p2tr"),
         }
     }
 }
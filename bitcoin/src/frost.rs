@@ -0,0 +1,937 @@
+//!
+//! FROST
+//!
+//! This module contains FROST threshold Schnorr signatures, which are BIP-340 signatures of taproot outputs.
+//! https://eprint.iacr.org/2020/852
+//!
+//! The participants generate a group key with a distributed key generation (Pedersen DKG with proofs
+//! of knowledge), in which no participant learns the group private key:
+//! - round 1: each participant commits to a random polynomial of degree threshold - 1, and broadcasts
+//!   its public package (the commitments, and a proof of knowledge of its constant term).
+//! - round 2: each participant sends each other participant a secret share, the evaluation of its
+//!   polynomial at their identifier.
+//! - round 3: each participant verifies its secret shares against the commitments, and derives its
+//!   key package (its signing share, the group public key, and the verification shares of all participants).
+//!
+//! Any threshold of the participants sign in two rounds: each signer shares its public nonce, and then
+//! its partial signature, which are aggregated into a single Schnorr signature. The group public key is
+//! the internal key of a P2TR address, and the signatures are valid for its output key.
+//!
+//! The secret shares of round 2 must be sent over confidential channels, and a secret nonce must never be
+//! used for more than one partial signature.
+//!
+//! The signatures are BIP-340 signatures of a taproot output key, so the binding factors and the challenge are
+//! tagged hashes, and the group commitment is x-only. This is not the FROST(secp256k1, SHA-256) ciphersuite of
+//! RFC 9591, whose signatures are not valid taproot signatures, so its test vectors do not apply.
+//!
+
+use crate::address::BitcoinAddress;
+use crate::musig::{
+    add, has_even_y, multiply, multiply_generator, negate, parity, to_array, to_point, to_scalar, to_scalar_checked,
+    x_only,
+};
+use crate::network::BitcoinNetwork;
+use crate::public_key::BitcoinPublicKey;
+use crate::taproot::tagged_hash;
+use wagyu_model::no_std::*;
+use wagyu_model::AddressError;
+
+use core::{fmt, str::FromStr};
+use rand::Rng;
+use secp256k1::{curve::Scalar, PublicKey};
+
+#[derive(Debug, Fail)]
+pub enum FrostError {
+    #[fail(display = "duplicate participant {}", _0)]
+    DuplicateParticipant(u8),
+
+    #[fail(display = "{} signers are fewer than the threshold of {}", _0, _1)]
+    InsufficientSigners(usize, u8),
+
+    #[fail(display = "invalid {}", _0)]
+    InvalidEncoding(&'static str),
+
+    #[fail(display = "invalid group public key")]
+    InvalidGroupPublicKey,
+
+    #[fail(display = "invalid nonce of participant {}", _0)]
+    InvalidNonce(u8),
+
+    #[fail(display = "invalid partial signature of participant {}", _0)]
+    InvalidPartialSignature(u8),
+
+    #[fail(display = "invalid participant {} of {} participants", _0, _1)]
+    InvalidParticipant(u8, u8),
+
+    #[fail(display = "invalid proof of knowledge of participant {}", _0)]
+    InvalidProof(u8),
+
+    #[fail(display = "invalid secret share from participant {}", _0)]
+    InvalidShare(u8),
+
+    #[fail(display = "the secret nonce is not of a signer of the key package")]
+    MismatchedSecretNonce,
+
+    #[fail(display = "missing public package of participant {}", _0)]
+    MissingPackage(u8),
+
+    #[fail(display = "missing partial signature of participant {}", _0)]
+    MissingPartialSignature(u8),
+
+    #[fail(display = "missing secret share from participant {}", _0)]
+    MissingShare(u8),
+
+    #[fail(display = "unsupported threshold: {} of {} participants", _0, _1)]
+    UnsupportedThreshold(u8, u8),
+}
+
+/// Represents the secret package of a participant of a key generation, its random polynomial
+#[derive(Debug, Clone)]
+pub struct FrostSecretPackage {
+    /// The identifier of the participant, from 1 to the number of participants
+    pub identifier: u8,
+    /// The number of signers required to sign
+    pub threshold: u8,
+    /// The number of participants
+    pub participants: u8,
+    /// The coefficients of the polynomial, of which the constant term is the contribution to the group private key
+    coefficients: Vec<Scalar>,
+}
+
+impl FrostSecretPackage {
+    /// Returns the secret package and public package of the given participant of a threshold-of-participants
+    /// key generation (round 1).
+    pub fn new<R: Rng>(
+        rng: &mut R,
+        identifier: u8,
+        threshold: u8,
+        participants: u8,
+    ) -> Result<(Self, FrostPublicPackage), FrostError> {
+        if threshold < 2 || threshold > participants {
+            return Err(FrostError::UnsupportedThreshold(threshold, participants));
+        }
+        if identifier == 0 || identifier > participants {
+            return Err(FrostError::InvalidParticipant(identifier, participants));
+        }
+
+        let secret_package = Self {
+            identifier,
+            threshold,
+            participants,
+            coefficients: (0..threshold).map(|_| random_scalar(rng)).collect(),
+        };
+        let commitments = secret_package.commitments()?;
+
+        // The proof of knowledge of the constant term is a Schnorr signature of the identifier
+        let k = random_scalar(rng);
+        let proof_nonce = multiply_generator(&k).ok_or(FrostError::InvalidProof(identifier))?;
+        let c = dkg_challenge(identifier, &commitments[0], &proof_nonce);
+        let proof = k + c * secret_package.coefficients[0].clone();
+
+        Ok((
+            secret_package,
+            FrostPublicPackage {
+                identifier,
+                commitments,
+                proof_nonce,
+                proof,
+            },
+        ))
+    }
+
+    /// Returns the secret shares of the other participants, after verifying their public packages (round 2).
+    pub fn to_shares(&self, packages: &[FrostPublicPackage]) -> Result<Vec<FrostShare>, FrostError> {
+        self.check_packages(packages)?;
+        Ok((1..=self.participants)
+            .filter(|recipient| *recipient != self.identifier)
+            .map(|recipient| FrostShare {
+                sender: self.identifier,
+                recipient,
+                share: self.evaluate(recipient),
+            })
+            .collect())
+    }
+
+    /// Returns the key package of the participant from the public packages of the other participants, and
+    /// the secret shares they sent to it (round 3).
+    pub fn to_key_package(
+        &self,
+        packages: &[FrostPublicPackage],
+        shares: &[FrostShare],
+    ) -> Result<FrostKeyPackage, FrostError> {
+        let packages = self.check_packages(packages)?;
+
+        let mut signing_share = self.evaluate(self.identifier);
+        for package in &packages {
+            let share = shares
+                .iter()
+                .find(|share| share.sender == package.identifier && share.recipient == self.identifier)
+                .ok_or(FrostError::MissingShare(package.identifier))?;
+            let expected = evaluate_commitments(&package.commitments, self.identifier);
+            if multiply_generator(&share.share).map(|point| point.serialize_compressed())
+                != expected.map(|point| point.serialize_compressed())
+            {
+                return Err(FrostError::InvalidShare(package.identifier));
+            }
+            signing_share += share.share.clone();
+        }
+
+        // The group public key and verification shares are the sums over the polynomials of all participants
+        let mut commitments = packages
+            .iter()
+            .map(|package| package.commitments.clone())
+            .collect::<Vec<Vec<PublicKey>>>();
+        commitments.push(self.commitments()?);
+
+        let group_public_key = add(&commitments.iter().map(|c| Some(c[0].clone())).collect::<Vec<_>>())
+            .ok_or(FrostError::InvalidGroupPublicKey)?;
+        let verification_shares = (1..=self.participants)
+            .map(|identifier| {
+                add(&commitments
+                    .iter()
+                    .map(|c| evaluate_commitments(c, identifier))
+                    .collect::<Vec<_>>())
+                .ok_or(FrostError::InvalidGroupPublicKey)
+            })
+            .collect::<Result<Vec<PublicKey>, FrostError>>()?;
+
+        Ok(FrostKeyPackage {
+            identifier: self.identifier,
+            threshold: self.threshold,
+            signing_share,
+            group_public_key,
+            verification_shares,
+        })
+    }
+
+    /// Returns the commitments to the coefficients of the polynomial.
+    fn commitments(&self) -> Result<Vec<PublicKey>, FrostError> {
+        self.coefficients
+            .iter()
+            .map(|coefficient| multiply_generator(coefficient).ok_or(FrostError::InvalidProof(self.identifier)))
+            .collect()
+    }
+
+    /// Returns the evaluation of the polynomial at the given identifier.
+    fn evaluate(&self, identifier: u8) -> Scalar {
+        let x = Scalar::from_int(identifier as u32);
+        self.coefficients
+            .iter()
+            .rev()
+            .fold(Scalar::from_int(0), |sum, coefficient| {
+                sum * x.clone() + coefficient.clone()
+            })
+    }
+
+    /// Returns the public packages of the other participants, sorted by identifier, after verifying that
+    /// each participant has a single valid package. The package of this participant is ignored.
+    fn check_packages(&self, packages: &[FrostPublicPackage]) -> Result<Vec<FrostPublicPackage>, FrostError> {
+        let mut others = Vec::new();
+        for package in packages.iter().filter(|package| package.identifier != self.identifier) {
+            if package.identifier == 0 || package.identifier > self.participants {
+                return Err(FrostError::InvalidParticipant(package.identifier, self.participants));
+            }
+            if others
+                .iter()
+                .any(|other: &FrostPublicPackage| other.identifier == package.identifier)
+            {
+                return Err(FrostError::DuplicateParticipant(package.identifier));
+            }
+            if package.commitments.len() != self.threshold as usize || !package.verify() {
+                return Err(FrostError::InvalidProof(package.identifier));
+            }
+            others.push(package.clone());
+        }
+        others.sort_by_key(|package| package.identifier);
+
+        match (1..=self.participants)
+            .find(|identifier| *identifier != self.identifier && others.iter().all(|p| p.identifier != *identifier))
+        {
+            Some(identifier) => Err(FrostError::MissingPackage(identifier)),
+            None => Ok(others),
+        }
+    }
+}
+
+impl FromStr for FrostSecretPackage {
+    type Err = FrostError;
+
+    fn from_str(secret_package: &str) -> Result<Self, Self::Err> {
+        let error = FrostError::InvalidEncoding("secret package");
+        let bytes = hex::decode(secret_package).map_err(|_| FrostError::InvalidEncoding("secret package"))?;
+        if bytes.len() < 3 || bytes.len() != 3 + 32 * bytes[1] as usize {
+            return Err(error);
+        }
+        let (identifier, threshold, participants) = (bytes[0], bytes[1], bytes[2]);
+        if threshold < 2 || threshold > participants || identifier == 0 || identifier > participants {
+            return Err(error);
+        }
+
+        let coefficients = bytes[3..]
+            .chunks(32)
+            .map(|coefficient| to_scalar_checked(coefficient).ok_or(FrostError::InvalidEncoding("secret package")))
+            .collect::<Result<Vec<Scalar>, FrostError>>()?;
+        Ok(Self {
+            identifier,
+            threshold,
+            participants,
+            coefficients,
+        })
+    }
+}
+
+impl fmt::Display for FrostSecretPackage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut bytes = vec![self.identifier, self.threshold, self.participants];
+        self.coefficients
+            .iter()
+            .for_each(|coefficient| bytes.extend_from_slice(&coefficient.b32()));
+        write!(f, "{}", hex::encode(bytes))
+    }
+}
+
+/// Represents the public package of a participant of a key generation, broadcast to the other participants
+#[derive(Debug, Clone)]
+pub struct FrostPublicPackage {
+    /// The identifier of the participant
+    pub identifier: u8,
+    /// The commitments to the coefficients of the polynomial of the participant
+    pub commitments: Vec<PublicKey>,
+    /// The nonce of the proof of knowledge
+    proof_nonce: PublicKey,
+    /// The proof of knowledge of the constant term of the polynomial
+    proof: Scalar,
+}
+
+impl FrostPublicPackage {
+    /// Returns whether the proof of knowledge of the constant term of the polynomial is valid.
+    pub fn verify(&self) -> bool {
+        let commitment = match self.commitments.first() {
+            Some(commitment) => commitment,
+            None => return false,
+        };
+        let c = dkg_challenge(self.identifier, commitment, &self.proof_nonce);
+        let expected = add(&[Some(self.proof_nonce.clone()), multiply(commitment, &c)]);
+        multiply_generator(&self.proof).map(|point| point.serialize_compressed())
+            == expected.map(|point| point.serialize_compressed())
+    }
+}
+
+impl FromStr for FrostPublicPackage {
+    type Err = FrostError;
+
+    fn from_str(public_package: &str) -> Result<Self, Self::Err> {
+        let error = || FrostError::InvalidEncoding("public package");
+        let bytes = hex::decode(public_package).map_err(|_| error())?;
+        if bytes.len() < 2 || bytes.len() != 2 + 33 * bytes[1] as usize + 65 || bytes[1] == 0 {
+            return Err(error());
+        }
+
+        let end = 2 + 33 * bytes[1] as usize;
+        let commitments = bytes[2..end]
+            .chunks(33)
+            .map(|commitment| to_point(commitment).ok_or_else(error))
+            .collect::<Result<Vec<PublicKey>, FrostError>>()?;
+        Ok(Self {
+            identifier: bytes[0],
+            commitments,
+            proof_nonce: to_point(&bytes[end..end + 33]).ok_or_else(error)?,
+            proof: to_scalar_checked(&bytes[end + 33..]).ok_or_else(error)?,
+        })
+    }
+}
+
+impl fmt::Display for FrostPublicPackage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut bytes = vec![self.identifier, self.commitments.len() as u8];
+        self.commitments
+            .iter()
+            .for_each(|commitment| bytes.extend_from_slice(&commitment.serialize_compressed()));
+        bytes.extend_from_slice(&self.proof_nonce.serialize_compressed());
+        bytes.extend_from_slice(&self.proof.b32());
+        write!(f, "{}", hex::encode(bytes))
+    }
+}
+
+/// Represents a secret share from a participant of a key generation to another participant,
+/// sent over a confidential channel
+#[derive(Debug, Clone)]
+pub struct FrostShare {
+    /// The identifier of the participant that sends the share
+    pub sender: u8,
+    /// The identifier of the participant that receives the share
+    pub recipient: u8,
+    /// The evaluation of the polynomial of the sender at the identifier of the recipient
+    share: Scalar,
+}
+
+impl FromStr for FrostShare {
+    type Err = FrostError;
+
+    fn from_str(share: &str) -> Result<Self, Self::Err> {
+        let bytes = hex::decode(share).map_err(|_| FrostError::InvalidEncoding("secret share"))?;
+        match bytes.len() {
+            34 => Ok(Self {
+                sender: bytes[0],
+                recipient: bytes[1],
+                share: to_scalar_checked(&bytes[2..]).ok_or(FrostError::InvalidEncoding("secret share"))?,
+            }),
+            _ => Err(FrostError::InvalidEncoding("secret share")),
+        }
+    }
+}
+
+impl fmt::Display for FrostShare {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let bytes = [&[self.sender, self.recipient][..], &self.share.b32()[..]].concat();
+        write!(f, "{}", hex::encode(bytes))
+    }
+}
+
+/// Represents the key package of a participant, with which it signs for the group public key
+#[derive(Debug, Clone)]
+pub struct FrostKeyPackage {
+    /// The identifier of the participant
+    pub identifier: u8,
+    /// The number of signers required to sign
+    pub threshold: u8,
+    /// The signing share of the participant, its share of the group private key
+    signing_share: Scalar,
+    /// The group public key
+    pub group_public_key: PublicKey,
+    /// The verification shares (the public keys of the signing shares) of the participants, by identifier
+    pub verification_shares: Vec<PublicKey>,
+}
+
+impl FrostKeyPackage {
+    /// Returns the number of participants.
+    pub fn participants(&self) -> u8 {
+        self.verification_shares.len() as u8
+    }
+
+    /// Returns the verification share of the participant.
+    pub fn to_verification_share(&self) -> PublicKey {
+        self.verification_shares[self.identifier as usize - 1].clone()
+    }
+
+    /// Returns the x-only group public key.
+    pub fn to_x_only_public_key(&self) -> [u8; 32] {
+        x_only(&self.group_public_key)
+    }
+
+    /// Returns the P2TR address with the group public key as its internal key, and no script tree (BIP-86).
+    pub fn to_address<N: BitcoinNetwork>(&self) -> Result<BitcoinAddress<N>, AddressError> {
+        BitcoinAddress::p2tr(&BitcoinPublicKey::from_secp256k1_public_key(
+            self.group_public_key.clone(),
+            true,
+        ))
+    }
+
+    /// Returns the secret nonce and public nonce of the participant, for a single signature (signing round 1).
+    pub fn nonce_gen<R: Rng>(&self, rng: &mut R) -> Result<([u8; 65], [u8; 67]), FrostError> {
+        let mut random = [0u8; 32];
+        rng.fill(&mut random);
+        let input = [&random[..], &self.signing_share.b32()[..]].concat();
+
+        let mut secret_nonce = [0u8; 65];
+        let mut public_nonce = [0u8; 67];
+        secret_nonce[0] = self.identifier;
+        public_nonce[0] = self.identifier;
+        for i in 0..2 {
+            let k = to_scalar(&tagged_hash("FROST/nonce", &[&input[..], &[i as u8]].concat()));
+            let point = multiply_generator(&k).ok_or(FrostError::InvalidNonce(self.identifier))?;
+            secret_nonce[1 + i * 32..1 + (i + 1) * 32].copy_from_slice(&k.b32());
+            public_nonce[1 + i * 33..1 + (i + 1) * 33].copy_from_slice(&point.serialize_compressed());
+        }
+        Ok((secret_nonce, public_nonce))
+    }
+}
+
+impl FromStr for FrostKeyPackage {
+    type Err = FrostError;
+
+    fn from_str(key_package: &str) -> Result<Self, Self::Err> {
+        let error = || FrostError::InvalidEncoding("key package");
+        let bytes = hex::decode(key_package).map_err(|_| error())?;
+        if bytes.len() < 3 || bytes.len() != 3 + 32 + 33 + 33 * bytes[2] as usize {
+            return Err(error());
+        }
+        let (identifier, threshold, participants) = (bytes[0], bytes[1], bytes[2]);
+        if threshold < 2 || threshold > participants || identifier == 0 || identifier > participants {
+            return Err(error());
+        }
+
+        let verification_shares = bytes[68..]
+            .chunks(33)
+            .map(|share| to_point(share).ok_or_else(error))
+            .collect::<Result<Vec<PublicKey>, FrostError>>()?;
+        Ok(Self {
+            identifier,
+            threshold,
+            signing_share: to_scalar_checked(&bytes[3..35]).ok_or_else(error)?,
+            group_public_key: to_point(&bytes[35..68]).ok_or_else(error)?,
+            verification_shares,
+        })
+    }
+}
+
+impl fmt::Display for FrostKeyPackage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut bytes = vec![self.identifier, self.threshold, self.participants()];
+        bytes.extend_from_slice(&self.signing_share.b32());
+        bytes.extend_from_slice(&self.group_public_key.serialize_compressed());
+        self.verification_shares
+            .iter()
+            .for_each(|share| bytes.extend_from_slice(&share.serialize_compressed()));
+        write!(f, "{}", hex::encode(bytes))
+    }
+}
+
+/// Represents a signer of a signing session, with its public nonce and binding factor
+#[derive(Debug, Clone)]
+struct FrostSigner {
+    identifier: u8,
+    hiding: PublicKey,
+    binding: PublicKey,
+    binding_factor: Scalar,
+}
+
+/// Represents a signing session of a message by a threshold of the participants of a group public key
+#[derive(Debug, Clone)]
+pub struct FrostSession {
+    /// The signers of the session, sorted by identifier
+    signers: Vec<FrostSigner>,
+    /// The verification shares of the participants
+    verification_shares: Vec<PublicKey>,
+    /// The taproot output key of the group public key
+    output_key: PublicKey,
+    /// The negation of the group public key in the output key
+    gacc: Scalar,
+    /// The taproot tweak of the output key
+    tacc: Scalar,
+    /// The final nonce
+    r: PublicKey,
+    /// The challenge of the signature
+    c: Scalar,
+}
+
+impl FrostSession {
+    /// Returns the signing session of the given message by the signers of the given public nonces, for the output
+    /// key of the P2TR address of the group public key of the key package (signing round 2).
+    pub fn new(key_package: &FrostKeyPackage, public_nonces: &[[u8; 67]], message: &[u8]) -> Result<Self, FrostError> {
+        let participants = key_package.participants();
+        let mut signers = Vec::<FrostSigner>::with_capacity(public_nonces.len());
+        for nonce in public_nonces {
+            let identifier = nonce[0];
+            if identifier == 0 || identifier > participants {
+                return Err(FrostError::InvalidParticipant(identifier, participants));
+            }
+            if signers.iter().any(|signer| signer.identifier == identifier) {
+                return Err(FrostError::DuplicateParticipant(identifier));
+            }
+            signers.push(FrostSigner {
+                identifier,
+                hiding: to_point(&nonce[1..34]).ok_or(FrostError::InvalidNonce(identifier))?,
+                binding: to_point(&nonce[34..]).ok_or(FrostError::InvalidNonce(identifier))?,
+                binding_factor: Scalar::from_int(0),
+            });
+        }
+        if signers.len() < key_package.threshold as usize {
+            return Err(FrostError::InsufficientSigners(signers.len(), key_package.threshold));
+        }
+        signers.sort_by_key(|signer| signer.identifier);
+
+        // The taproot tweak of a key path only output (BIP-86)
+        let group_public_key = &key_package.group_public_key;
+        let gacc = parity(group_public_key);
+        let tacc = to_scalar(&tagged_hash("TapTweak", &x_only(group_public_key)));
+        let output_key = add(&[multiply(group_public_key, &gacc), multiply_generator(&tacc)])
+            .ok_or(FrostError::InvalidGroupPublicKey)?;
+        let q = x_only(&output_key);
+
+        // Each binding factor commits to the output key, the public nonces of the signers, and the message
+        let mut list = Vec::with_capacity(signers.len() * 67);
+        for signer in &signers {
+            list.push(signer.identifier);
+            list.extend_from_slice(&signer.hiding.serialize_compressed());
+            list.extend_from_slice(&signer.binding.serialize_compressed());
+        }
+        let list = tagged_hash("FROST/commitments", &list);
+        for signer in signers.iter_mut() {
+            signer.binding_factor = to_scalar(&tagged_hash(
+                "FROST/binding",
+                &[&q[..], &list[..], &[signer.identifier], message].concat(),
+            ));
+        }
+
+        let nonces = signers
+            .iter()
+            .map(|signer| {
+                add(&[
+                    Some(signer.hiding.clone()),
+                    multiply(&signer.binding, &signer.binding_factor),
+                ])
+            })
+            .collect::<Vec<Option<PublicKey>>>();
+        let r = add(&nonces).ok_or(FrostError::InvalidNonce(signers[0].identifier))?;
+        let c = to_scalar(&tagged_hash(
+            "BIP0340/challenge",
+            &[&x_only(&r)[..], &q[..], message].concat(),
+        ));
+
+        Ok(Self {
+            signers,
+            verification_shares: key_package.verification_shares.clone(),
+            output_key,
+            gacc,
+            tacc,
+            r,
+            c,
+        })
+    }
+
+    /// Returns the x-only output key, for which the aggregate signature is valid.
+    pub fn to_x_only_public_key(&self) -> [u8; 32] {
+        x_only(&self.output_key)
+    }
+
+    /// Returns the partial signature of the participant of the key package, with its secret nonce.
+    pub fn sign(&self, key_package: &FrostKeyPackage, secret_nonce: &[u8; 65]) -> Result<[u8; 33], FrostError> {
+        let signer = self
+            .signers
+            .iter()
+            .find(|signer| signer.identifier == secret_nonce[0] && signer.identifier == key_package.identifier)
+            .ok_or(FrostError::MismatchedSecretNonce)?;
+        let d = to_scalar_checked(&secret_nonce[1..33]).ok_or(FrostError::MismatchedSecretNonce)?;
+        let e = to_scalar_checked(&secret_nonce[33..]).ok_or(FrostError::MismatchedSecretNonce)?;
+        let matches = |k: &Scalar, point: &PublicKey| {
+            multiply_generator(k).map(|k| k.serialize_compressed()) == Some(point.serialize_compressed())
+        };
+        if !matches(&d, &signer.hiding) || !matches(&e, &signer.binding) {
+            return Err(FrostError::MismatchedSecretNonce);
+        }
+
+        let k = d + signer.binding_factor.clone() * e;
+        let k = match has_even_y(&self.r) {
+            true => k,
+            false => -k,
+        };
+        let z = k + self.share_coefficient(signer.identifier) * key_package.signing_share.clone();
+
+        let mut partial_signature = [0u8; 33];
+        partial_signature[0] = signer.identifier;
+        partial_signature[1..].copy_from_slice(&z.b32());
+
+        // The partial signature is verified, to catch a faulty computation before it is shared
+        match self.verify_partial_signature(&partial_signature) {
+            true => Ok(partial_signature),
+            false => Err(FrostError::InvalidPartialSignature(signer.identifier)),
+        }
+    }
+
+    /// Returns whether the given partial signature is valid for the public nonce and verification share of its signer.
+    pub fn verify_partial_signature(&self, partial_signature: &[u8; 33]) -> bool {
+        let signer = match self
+            .signers
+            .iter()
+            .find(|signer| signer.identifier == partial_signature[0])
+        {
+            Some(signer) => signer,
+            None => return false,
+        };
+        let z = match to_scalar_checked(&partial_signature[1..]) {
+            Some(z) => z,
+            None => return false,
+        };
+
+        let nonce = add(&[
+            Some(signer.hiding.clone()),
+            multiply(&signer.binding, &signer.binding_factor),
+        ]);
+        let nonce = match has_even_y(&self.r) {
+            true => nonce,
+            false => negate(nonce),
+        };
+        let verification_share = &self.verification_shares[signer.identifier as usize - 1];
+
+        // z⋅G = R + c⋅λ⋅Y
+        let expected = add(&[
+            nonce,
+            multiply(verification_share, &self.share_coefficient(signer.identifier)),
+        ]);
+        multiply_generator(&z).map(|point| point.serialize_compressed())
+            == expected.map(|point| point.serialize_compressed())
+    }
+
+    /// Returns the Schnorr signature of the aggregate of the partial signatures of the signers.
+    pub fn aggregate(&self, partial_signatures: &[[u8; 33]]) -> Result<[u8; 64], FrostError> {
+        let mut z = Scalar::from_int(0);
+        for signer in &self.signers {
+            let partial_signature = partial_signatures
+                .iter()
+                .find(|partial_signature| partial_signature[0] == signer.identifier)
+                .ok_or(FrostError::MissingPartialSignature(signer.identifier))?;
+            if !self.verify_partial_signature(partial_signature) {
+                return Err(FrostError::InvalidPartialSignature(signer.identifier));
+            }
+            z += to_scalar(&to_array(&partial_signature[1..]));
+        }
+        z += self.c.clone() * parity(&self.output_key) * self.tacc.clone();
+
+        let mut signature = [0u8; 64];
+        signature[..32].copy_from_slice(&x_only(&self.r));
+        signature[32..].copy_from_slice(&z.b32());
+        Ok(signature)
+    }
+
+    /// Returns the coefficient of the signing share of the given signer in its partial signature,
+    /// its Lagrange coefficient with the challenge and the negations of the output key.
+    fn share_coefficient(&self, identifier: u8) -> Scalar {
+        let lambda = self
+            .signers
+            .iter()
+            .filter(|signer| signer.identifier != identifier)
+            .fold(Scalar::from_int(1), |lambda, signer| {
+                let j = Scalar::from_int(signer.identifier as u32);
+                let denominator = j.clone() + -Scalar::from_int(identifier as u32);
+                lambda * j * denominator.inv()
+            });
+        self.c.clone() * lambda * parity(&self.output_key) * self.gacc.clone()
+    }
+}
+
+/// Returns the challenge of the proof of knowledge of the given participant of a key generation.
+fn dkg_challenge(identifier: u8, commitment: &PublicKey, nonce: &PublicKey) -> Scalar {
+    to_scalar(&tagged_hash(
+        "FROST/dkg",
+        &[
+            &[identifier][..],
+            &commitment.serialize_compressed()[..],
+            &nonce.serialize_compressed()[..],
+        ]
+        .concat(),
+    ))
+}
+
+/// Returns the evaluation of the polynomial of the given commitments at the given identifier,
+/// the public key of the secret share of the identifier.
+fn evaluate_commitments(commitments: &[PublicKey], identifier: u8) -> Option<PublicKey> {
+    let x = Scalar::from_int(identifier as u32);
+    let mut power = Scalar::from_int(1);
+    let mut terms = Vec::with_capacity(commitments.len());
+    for commitment in commitments {
+        terms.push(multiply(commitment, &power));
+        power *= x.clone();
+    }
+    add(&terms)
+}
+
+/// Returns a random non-zero scalar.
+fn random_scalar<R: Rng>(rng: &mut R) -> Scalar {
+    loop {
+        let mut bytes = [0u8; 32];
+        rng.fill(&mut bytes);
+        if let Some(scalar) = to_scalar_checked(&bytes).filter(|scalar| !scalar.is_zero()) {
+            return scalar;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::musig::verify_schnorr;
+    use crate::network::Mainnet;
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    fn key_generation<R: Rng>(rng: &mut R, threshold: u8, participants: u8) -> Vec<FrostKeyPackage> {
+        let round1 = (1..=participants)
+            .map(|identifier| FrostSecretPackage::new(rng, identifier, threshold, participants).unwrap())
+            .collect::<Vec<(FrostSecretPackage, FrostPublicPackage)>>();
+        let packages = round1.iter().map(|(_, package)| package.clone()).collect::<Vec<_>>();
+        let shares = round1
+            .iter()
+            .flat_map(|(secret_package, _)| secret_package.to_shares(&packages).unwrap())
+            .collect::<Vec<FrostShare>>();
+        round1
+            .iter()
+            .map(|(secret_package, _)| secret_package.to_key_package(&packages, &shares).unwrap())
+            .collect()
+    }
+
+    fn sign<R: Rng>(rng: &mut R, key_packages: &[&FrostKeyPackage], message: &[u8]) -> ([u8; 32], [u8; 64]) {
+        let nonces = key_packages
+            .iter()
+            .map(|key_package| key_package.nonce_gen(rng).unwrap())
+            .collect::<Vec<([u8; 65], [u8; 67])>>();
+        let public_nonces = nonces.iter().map(|nonce| nonce.1).collect::<Vec<[u8; 67]>>();
+        let session = FrostSession::new(key_packages[0], &public_nonces, message).unwrap();
+        let partial_signatures = key_packages
+            .iter()
+            .zip(&nonces)
+            .map(|(key_package, nonce)| session.sign(key_package, &nonce.0).unwrap())
+            .collect::<Vec<[u8; 33]>>();
+        (
+            session.to_x_only_public_key(),
+            session.aggregate(&partial_signatures).unwrap(),
+        )
+    }
+
+    fn test_threshold(threshold: u8, participants: u8) {
+        let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+        let key_packages = key_generation(rng, threshold, participants);
+
+        let address = key_packages[0].to_address::<Mainnet>().unwrap();
+        for key_package in &key_packages {
+            assert_eq!(address, key_package.to_address::<Mainnet>().unwrap());
+        }
+
+        // Every subset of a threshold of consecutive participants signs for the output key of the address
+        let message = [0x42u8; 32];
+        for start in 0..participants as usize {
+            let signers = (0..threshold as usize)
+                .map(|i| &key_packages[(start + i) % participants as usize])
+                .collect::<Vec<&FrostKeyPackage>>();
+            let (output_key, signature) = sign(rng, &signers, &message);
+            assert!(verify_schnorr(&output_key, &message, &signature));
+            assert!(!verify_schnorr(&output_key, &[0x24u8; 32], &signature));
+        }
+    }
+
+    #[test]
+    fn threshold_2_of_2() {
+        test_threshold(2, 2);
+    }
+
+    #[test]
+    fn threshold_2_of_3() {
+        test_threshold(2, 3);
+    }
+
+    #[test]
+    fn threshold_3_of_5() {
+        test_threshold(3, 5);
+    }
+
+    /// A regression vector of a 2-of-3 key generation and a signature by participants 1 and 3, from a seeded
+    /// random number generator. The output key (BIP-86 tweak of the group public key) and the signature (BIP-340)
+    /// are verified independently of this module, with the reference implementation of BIP-340.
+    #[test]
+    fn known_answer() {
+        let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+        let key_packages = key_generation(rng, 2, 3);
+        let message = [0x42u8; 32];
+        let (output_key, signature) = sign(rng, &[&key_packages[0], &key_packages[2]], &message);
+
+        assert_eq!(
+            "c3564c193bdf11d1fa2ee32c253a05831f9d02f0636de814aa577b96dd76c974",
+            hex::encode(key_packages[0].to_x_only_public_key())
+        );
+        assert_eq!(
+            "2ebbe3ec9c6bbde106b03284b59785b7cdd836b169846da4da2af545ac0449d6",
+            hex::encode(output_key)
+        );
+        assert_eq!(
+            "9b9415bd8a179b462a19296f0db1e76bd4759b859ca613d1f737254b5c670db9\
+             16bbbf7fcd81fb1ecf307d90a784bdf460cb1b32f5559d188b08c02e18fef7d2",
+            hex::encode(&signature[..])
+        );
+    }
+
+    #[test]
+    fn to_string_from_str() {
+        let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+        let (secret_package, public_package) = FrostSecretPackage::new(rng, 1, 2, 3).unwrap();
+        let secret_string = secret_package.to_string();
+        let public_string = public_package.to_string();
+        assert_eq!(
+            secret_string,
+            FrostSecretPackage::from_str(&secret_string).unwrap().to_string()
+        );
+        assert_eq!(
+            public_string,
+            FrostPublicPackage::from_str(&public_string).unwrap().to_string()
+        );
+
+        let key_package = &key_generation(rng, 2, 3)[1];
+        let key_string = key_package.to_string();
+        assert_eq!(key_string, FrostKeyPackage::from_str(&key_string).unwrap().to_string());
+    }
+
+    #[test]
+    fn invalid_key_generation() {
+        let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+        assert!(FrostSecretPackage::new(rng, 1, 1, 3).is_err());
+        assert!(FrostSecretPackage::new(rng, 1, 4, 3).is_err());
+        assert!(FrostSecretPackage::new(rng, 4, 2, 3).is_err());
+
+        let round1 = (1..=3)
+            .map(|identifier| FrostSecretPackage::new(rng, identifier, 2, 3).unwrap())
+            .collect::<Vec<(FrostSecretPackage, FrostPublicPackage)>>();
+        let mut packages = round1.iter().map(|(_, package)| package.clone()).collect::<Vec<_>>();
+        let mut shares = round1
+            .iter()
+            .flat_map(|(secret_package, _)| secret_package.to_shares(&packages).unwrap())
+            .collect::<Vec<FrostShare>>();
+
+        // A tampered secret share is detected by its recipient
+        let index = shares.iter().position(|share| share.recipient == 1).unwrap();
+        shares[index].share += Scalar::from_int(1);
+        match round1[0].0.to_key_package(&packages, &shares) {
+            Err(FrostError::InvalidShare(_)) => {}
+            result => panic!("unexpected result {:?}", result),
+        }
+
+        // A public package with an invalid proof of knowledge, or a missing public package, is rejected
+        packages[1].proof += Scalar::from_int(1);
+        match round1[0].0.to_shares(&packages) {
+            Err(FrostError::InvalidProof(2)) => {}
+            result => panic!("unexpected result {:?}", result),
+        }
+        match round1[0].0.to_shares(&packages[..2]) {
+            Err(FrostError::InvalidProof(2)) => {}
+            result => panic!("unexpected result {:?}", result),
+        }
+        match round1[0].0.to_shares(&[packages[0].clone(), packages[2].clone()]) {
+            Err(FrostError::MissingPackage(2)) => {}
+            result => panic!("unexpected result {:?}", result),
+        }
+    }
+
+    #[test]
+    fn invalid_signing() {
+        let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+        let key_packages = key_generation(rng, 2, 3);
+        let message = [0x42u8; 32];
+
+        let (_, public_nonce) = key_packages[0].nonce_gen(rng).unwrap();
+        match FrostSession::new(&key_packages[0], &[public_nonce], &message) {
+            Err(FrostError::InsufficientSigners(1, 2)) => {}
+            result => panic!("unexpected result {:?}", result),
+        }
+        match FrostSession::new(&key_packages[0], &[public_nonce, public_nonce], &message) {
+            Err(FrostError::DuplicateParticipant(1)) => {}
+            result => panic!("unexpected result {:?}", result),
+        }
+
+        // A signer outside of the session cannot sign, and a forged partial signature is rejected
+        let nonces = [
+            key_packages[0].nonce_gen(rng).unwrap(),
+            key_packages[1].nonce_gen(rng).unwrap(),
+        ];
+        let session = FrostSession::new(&key_packages[0], &[nonces[0].1, nonces[1].1], &message).unwrap();
+        let (other_secret_nonce, _) = key_packages[2].nonce_gen(rng).unwrap();
+        match session.sign(&key_packages[2], &other_secret_nonce) {
+            Err(FrostError::MismatchedSecretNonce) => {}
+            result => panic!("unexpected result {:?}", result),
+        }
+        let partial_signature = session.sign(&key_packages[0], &nonces[0].0).unwrap();
+        let mut forged = session.sign(&key_packages[1], &nonces[1].0).unwrap();
+        forged[32] ^= 0x01;
+        match session.aggregate(&[partial_signature, forged]) {
+            Err(FrostError::InvalidPartialSignature(2)) => {}
+            result => panic!("unexpected result {:?}", result),
+        }
+    }
+}
@@ -0,0 +1,164 @@
+//!
+//! HTLC
+//!
+//! This module contains the hash time-locked contracts (HTLCs) of atomic swaps, witness scripts
+//! spendable by a recipient with the preimage of a SHA-256 hash, or by a refund public key after
+//! a timelock (BIP-199).
+//!
+
+use crate::address::BitcoinAddress;
+use crate::network::BitcoinNetwork;
+use crate::public_key::BitcoinPublicKey;
+use crate::timelock::{push_number, BitcoinTimelock};
+use crate::transaction::Opcode;
+use wagyu_model::no_std::*;
+use wagyu_model::{AddressError, TransactionError};
+
+/// The size of the preimage of an HTLC in bytes, which is enforced so that a preimage
+/// is also valid on the other chain of a swap
+pub const HTLC_PREIMAGE_SIZE: u32 = 32;
+
+/// Represents a Bitcoin hash time-locked contract
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitcoinHtlc<N: BitcoinNetwork> {
+    /// The public key that claims the contract with the preimage of the hash
+    pub recipient: BitcoinPublicKey<N>,
+    /// The public key that refunds the contract after the timelock
+    pub refund: BitcoinPublicKey<N>,
+    /// The SHA-256 hash of the preimage
+    pub hash: [u8; 32],
+    /// The timelock of the refund
+    pub timelock: BitcoinTimelock,
+}
+
+impl<N: BitcoinNetwork> BitcoinHtlc<N> {
+    /// Returns the contract of the given recipient and refund public keys, SHA-256 hash, and timelock.
+    pub fn new(
+        recipient: BitcoinPublicKey<N>,
+        refund: BitcoinPublicKey<N>,
+        hash: &[u8],
+        timelock: BitcoinTimelock,
+    ) -> Result<Self, TransactionError> {
+        if hash.len() != 32 {
+            return Err(TransactionError::Message(format!(
+                "invalid hash: {} bytes, expected a SHA-256 hash of 32 bytes",
+                hash.len()
+            )));
+        }
+
+        let mut sha256 = [0u8; 32];
+        sha256.copy_from_slice(hash);
+        Ok(Self {
+            recipient,
+            refund,
+            hash: sha256,
+            timelock,
+        })
+    }
+
+    /// Returns the witness script of the contract,
+    /// `OP_IF OP_SIZE <32> OP_EQUALVERIFY OP_SHA256 <hash> OP_EQUALVERIFY <recipient>
+    /// OP_ELSE <locktime> OP_CHECKLOCKTIMEVERIFY OP_DROP <refund> OP_ENDIF OP_CHECKSIG`,
+    /// with OP_CHECKSEQUENCEVERIFY for a relative timelock.
+    pub fn to_witness_script(&self) -> Vec<u8> {
+        let (value, opcode) = match self.timelock {
+            BitcoinTimelock::Absolute(lock_time) => (lock_time, Opcode::OP_CHECKLOCKTIMEVERIFY),
+            BitcoinTimelock::Relative(sequence) => (sequence, Opcode::OP_CHECKSEQUENCEVERIFY),
+        };
+
+        // Witness scripts only accept compressed public keys
+        let recipient = self.recipient.to_secp256k1_public_key().serialize_compressed();
+        let refund = self.refund.to_secp256k1_public_key().serialize_compressed();
+
+        let mut script = vec![Opcode::OP_IF as u8, Opcode::OP_SIZE as u8];
+        script.extend(push_number(HTLC_PREIMAGE_SIZE));
+        script.push(Opcode::OP_EQUALVERIFY as u8);
+        script.push(Opcode::OP_SHA256 as u8);
+        script.push(self.hash.len() as u8);
+        script.extend(self.hash.iter());
+        script.push(Opcode::OP_EQUALVERIFY as u8);
+        script.push(recipient.len() as u8);
+        script.extend(recipient.iter());
+        script.push(Opcode::OP_ELSE as u8);
+        script.extend(push_number(value));
+        script.push(opcode as u8);
+        script.push(Opcode::OP_DROP as u8);
+        script.push(refund.len() as u8);
+        script.extend(refund.iter());
+        script.push(Opcode::OP_ENDIF as u8);
+        script.push(Opcode::OP_CHECKSIG as u8);
+        script
+    }
+
+    /// Returns the P2WSH address of the witness script of the contract.
+    pub fn to_address(&self) -> Result<BitcoinAddress<N>, AddressError> {
+        BitcoinAddress::<N>::p2wsh(&self.to_witness_script())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::*;
+    use core::str::FromStr;
+
+    const RECIPIENT: &str = "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+    const REFUND: &str = "02c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5";
+    const HASH: &str = "66687aadf862bd776c8fc18b8e9f8e20089714856ee233b3902a591d0d5f2925";
+
+    fn test_witness_script<N: BitcoinNetwork>(timelock: BitcoinTimelock, expected_witness_script: &str) {
+        let recipient = BitcoinPublicKey::<N>::from_str(RECIPIENT).unwrap();
+        let refund = BitcoinPublicKey::<N>::from_str(REFUND).unwrap();
+        let htlc = BitcoinHtlc::new(recipient, refund, &hex::decode(HASH).unwrap(), timelock).unwrap();
+
+        let witness_script = htlc.to_witness_script();
+        assert_eq!(expected_witness_script, hex::encode(&witness_script));
+        assert_eq!(
+            BitcoinAddress::<N>::p2wsh(&witness_script).unwrap(),
+            htlc.to_address().unwrap()
+        );
+    }
+
+    #[test]
+    fn absolute() {
+        test_witness_script::<Mainnet>(
+            BitcoinTimelock::absolute(800_000).unwrap(),
+            &[
+                "6382012088a820",
+                HASH,
+                "8821",
+                RECIPIENT,
+                "670300350cb17521",
+                REFUND,
+                "68ac",
+            ]
+            .concat(),
+        );
+    }
+
+    #[test]
+    fn relative() {
+        test_witness_script::<Testnet>(
+            BitcoinTimelock::relative_blocks(144).unwrap(),
+            &[
+                "6382012088a820",
+                HASH,
+                "8821",
+                RECIPIENT,
+                "67029000b27521",
+                REFUND,
+                "68ac",
+            ]
+            .concat(),
+        );
+    }
+
+    #[test]
+    fn invalid_hash() {
+        let recipient = BitcoinPublicKey::<Mainnet>::from_str(RECIPIENT).unwrap();
+        let refund = BitcoinPublicKey::<Mainnet>::from_str(REFUND).unwrap();
+        let timelock = BitcoinTimelock::absolute(800_000).unwrap();
+        assert!(BitcoinHtlc::new(recipient.clone(), refund.clone(), &[0u8; 20], timelock).is_err());
+        assert!(BitcoinHtlc::new(recipient, refund, &[], timelock).is_err());
+    }
+}
@@ -12,6 +12,8 @@ extern crate failure;
 pub mod address;
 pub use self::address::*;
 
+pub mod bech32m;
+
 pub mod amount;
 pub use self::amount::*;
 
@@ -27,9 +29,21 @@ pub use self::extended_public_key::*;
 pub mod format;
 pub use self::format::*;
 
+pub mod frost;
+pub use self::frost::*;
+
+pub mod htlc;
+pub use self::htlc::*;
+
+pub mod miniscript;
+pub use self::miniscript::*;
+
 pub mod mnemonic;
 pub use self::mnemonic::*;
 
+pub mod musig;
+pub use self::musig::*;
+
 pub mod network;
 pub use self::network::*;
 
@@ -39,6 +53,15 @@ pub use self::private_key::*;
 pub mod public_key;
 pub use self::public_key::*;
 
+pub mod signed_message;
+pub use self::signed_message::*;
+
+pub mod taproot;
+pub use self::taproot::*;
+
+pub mod timelock;
+pub use self::timelock::*;
+
 pub mod transaction;
 pub use self::transaction::*;
 
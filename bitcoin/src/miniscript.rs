@@ -0,0 +1,614 @@
+//!
+//! Miniscript
+//!
+//! This module contains a compiler of spending policies to Miniscript for P2WSH outputs,
+//! and the analysis of the compiled scripts.
+//! https://bitcoin.sipa.be/miniscript/
+//!
+//! A policy is composed of `pk(KEY)`, `after(N)`, `older(N)`, `sha256(H)`, `hash256(H)`,
+//! `ripemd160(H)`, `hash160(H)`, `and(X,Y)`, `or(X,Y)`, and `thresh(k,X,Y,...)`. The probabilities
+//! of `or` branches (`or(9@X,1@Y)`) are accepted, but ignored, as the compilation is deterministic
+//! rather than cost-optimal:
+//! - `and(X,Y)` compiles to `and_v(v:X,Y)`.
+//! - `or(X,Y)` compiles to `or_i(X,Y)`.
+//! - `thresh(k,pk(A),pk(B),...)` compiles to `multi(k,A,B,...)`.
+//! - Any other `thresh(k,X,Y,...)` compiles to `thresh(k,X,a:Y,...)`, with each subpolicy wrapped
+//!   with `n:` to be a unit, and with `l:` to be dissatisfiable, as needed.
+//!
+
+use crate::address::BitcoinAddress;
+use crate::network::BitcoinNetwork;
+use crate::timelock::push_number;
+use crate::transaction::{variable_length_integer, Opcode};
+use wagyu_model::no_std::*;
+use wagyu_model::{AddressError, TransactionError};
+
+use core::{cmp::Ordering, str::FromStr};
+
+/// The maximum size of a standard P2WSH witness script in bytes
+pub const MAX_STANDARD_P2WSH_SCRIPT_SIZE: usize = 3600;
+/// The maximum number of public keys of a `multi` fragment
+pub const MAX_MULTISIG_PUBLIC_KEYS: usize = 20;
+/// The maximum size of a signature in a witness, with its sighash byte and length prefix
+const MAX_SIGNATURE_SIZE: usize = 74;
+
+#[derive(Debug, Fail)]
+pub enum MiniscriptError {
+    #[fail(display = "{}", _0)]
+    AddressError(AddressError),
+
+    #[fail(display = "invalid hash {}: expected {} bytes", _0, _1)]
+    InvalidHash(String, usize),
+
+    #[fail(display = "invalid public key: {}", _0)]
+    InvalidKey(String),
+
+    #[fail(display = "invalid policy: {}", _0)]
+    InvalidPolicy(String),
+
+    #[fail(display = "invalid script size: {} bytes exceeds {} bytes", _0, _1)]
+    InvalidScriptSize(usize, usize),
+
+    #[fail(display = "invalid threshold: {}-of-{}", _0, _1)]
+    InvalidThreshold(usize, usize),
+
+    #[fail(display = "invalid timelock: {}", _0)]
+    InvalidTimelock(String),
+
+    #[fail(display = "{}", _0)]
+    TransactionError(TransactionError),
+}
+
+impl From<AddressError> for MiniscriptError {
+    fn from(error: AddressError) -> Self {
+        MiniscriptError::AddressError(error)
+    }
+}
+
+impl From<TransactionError> for MiniscriptError {
+    fn from(error: TransactionError) -> Self {
+        MiniscriptError::TransactionError(error)
+    }
+}
+
+/// Represents the size of a witness stack, with its number of elements and their size in bytes
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub struct WitnessSize {
+    pub elements: usize,
+    pub bytes: usize,
+}
+
+impl WitnessSize {
+    fn new(elements: usize, bytes: usize) -> Self {
+        Self { elements, bytes }
+    }
+
+    fn add(self, other: Self) -> Self {
+        Self::new(self.elements + other.elements, self.bytes + other.bytes)
+    }
+
+    fn max(self, other: Self) -> Self {
+        match self.bytes.cmp(&other.bytes) {
+            Ordering::Less => other,
+            _ => self,
+        }
+    }
+}
+
+/// Represents a compiled fragment of a policy, of the Miniscript type B
+#[derive(Debug, Clone)]
+struct Fragment {
+    /// The Miniscript of the fragment
+    miniscript: String,
+    /// The script of the fragment
+    script: Vec<u8>,
+    /// The public key of a `pk` fragment, with its name in the policy
+    key: Option<(String, Vec<u8>)>,
+    /// Whether the fragment can be dissatisfied (the `d` property)
+    dissatisfiable: bool,
+    /// Whether the fragment leaves exactly 1 on the stack when satisfied (the `u` property)
+    unit: bool,
+    /// Whether every satisfaction of the fragment requires a signature (the `s` property)
+    signed: bool,
+    /// The maximum size of a satisfaction of the fragment
+    satisfaction: WitnessSize,
+    /// The maximum size of a dissatisfaction of the fragment, if it is dissatisfiable
+    dissatisfaction: Option<WitnessSize>,
+}
+
+impl Fragment {
+    /// Returns the `v:` wrapper of the fragment, with its final opcode replaced by its VERIFY variant if any.
+    fn verify(&self) -> (String, Vec<u8>) {
+        let mut script = self.script.clone();
+        let last = script.len() - 1;
+        match script[last] {
+            opcode if opcode == Opcode::OP_CHECKSIG as u8 => script[last] = Opcode::OP_CHECKSIGVERIFY as u8,
+            opcode if opcode == Opcode::OP_CHECKMULTISIG as u8 => script[last] = Opcode::OP_CHECKMULTISIGVERIFY as u8,
+            opcode if opcode == Opcode::OP_EQUAL as u8 => script[last] = Opcode::OP_EQUALVERIFY as u8,
+            _ => script.push(Opcode::OP_VERIFY as u8),
+        };
+        (format!("v:{}", self.miniscript), script)
+    }
+
+    /// Returns the `n:` wrapper of the fragment, `[X] 0NOTEQUAL`, which is a unit.
+    fn unit(self) -> Self {
+        let mut script = self.script;
+        script.push(Opcode::OP_0NOTEQUAL as u8);
+        Self {
+            miniscript: wrap("n", &self.miniscript),
+            script,
+            key: None,
+            unit: true,
+            ..self
+        }
+    }
+
+    /// Returns the `l:` wrapper of the fragment, `IF 0 ELSE [X] ENDIF`, which is dissatisfiable.
+    fn dissatisfiable(self) -> Self {
+        let script = [
+            vec![Opcode::OP_IF as u8, Opcode::OP_0 as u8, Opcode::OP_ELSE as u8],
+            self.script,
+            vec![Opcode::OP_ENDIF as u8],
+        ]
+        .concat();
+        Self {
+            miniscript: wrap("l", &self.miniscript),
+            script,
+            key: None,
+            dissatisfiable: true,
+            satisfaction: self.satisfaction.add(WitnessSize::new(1, 1)),
+            dissatisfaction: Some(WitnessSize::new(1, 2)),
+            ..self
+        }
+    }
+
+    /// Returns the `a:` wrapper of the fragment, `TOALTSTACK [X] FROMALTSTACK`, of the Miniscript type W.
+    fn alt(self) -> Self {
+        let script = [
+            vec![Opcode::OP_TOALTSTACK as u8],
+            self.script,
+            vec![Opcode::OP_FROMALTSTACK as u8],
+        ]
+        .concat();
+        Self {
+            miniscript: wrap("a", &self.miniscript),
+            script,
+            key: None,
+            ..self
+        }
+    }
+}
+
+/// Returns the given Miniscript with the given wrapper, merging consecutive wrappers (such as `a:n:X` to `an:X`).
+fn wrap(wrapper: &str, miniscript: &str) -> String {
+    match miniscript.find(|c: char| !c.is_ascii_lowercase()) {
+        Some(i) if miniscript[i..].starts_with(':') => format!("{}{}", wrapper, miniscript),
+        _ => format!("{}:{}", wrapper, miniscript),
+    }
+}
+
+/// Represents a Miniscript compiled from a policy, for a P2WSH output
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitcoinMiniscript {
+    /// The Miniscript of the policy
+    pub miniscript: String,
+    /// The witness script of the Miniscript
+    pub script: Vec<u8>,
+    /// Whether every satisfaction requires a signature
+    pub requires_signature: bool,
+    /// The maximum size of a satisfaction, excluding the witness script
+    pub max_satisfaction: WitnessSize,
+}
+
+impl BitcoinMiniscript {
+    /// Returns the Miniscript of the given policy, with its public keys in hex, or named in the given keys.
+    pub fn compile(policy: &str, keys: &[(String, Vec<u8>)]) -> Result<Self, MiniscriptError> {
+        let fragment = compile(policy, keys)?;
+        if fragment.script.len() > MAX_STANDARD_P2WSH_SCRIPT_SIZE {
+            return Err(MiniscriptError::InvalidScriptSize(
+                fragment.script.len(),
+                MAX_STANDARD_P2WSH_SCRIPT_SIZE,
+            ));
+        }
+
+        Ok(Self {
+            miniscript: fragment.miniscript,
+            script: fragment.script,
+            requires_signature: fragment.signed,
+            max_satisfaction: fragment.satisfaction,
+        })
+    }
+
+    /// Returns the P2WSH address of the witness script.
+    pub fn to_address<N: BitcoinNetwork>(&self) -> Result<BitcoinAddress<N>, AddressError> {
+        BitcoinAddress::<N>::p2wsh(&self.script)
+    }
+
+    /// Returns the maximum weight of the witness of an input spending the P2WSH address,
+    /// with its number of elements, its satisfaction, and its witness script.
+    pub fn max_satisfaction_weight(&self) -> Result<usize, MiniscriptError> {
+        let elements = variable_length_integer(self.max_satisfaction.elements as u64 + 1)?;
+        let script = variable_length_integer(self.script.len() as u64)?;
+        Ok(elements.len() + self.max_satisfaction.bytes + script.len() + self.script.len())
+    }
+}
+
+/// Returns the name and the arguments of the given policy, split on their top-level commas.
+fn split(policy: &str) -> Result<(&str, Vec<&str>), MiniscriptError> {
+    let invalid = || MiniscriptError::InvalidPolicy(policy.into());
+    let open = policy.find('(').ok_or_else(invalid)?;
+    if !policy.ends_with(')') {
+        return Err(invalid());
+    }
+
+    let mut arguments = vec![];
+    let (mut depth, mut start) = (0, open + 1);
+    for (i, c) in policy.char_indices().skip(open + 1) {
+        match c {
+            '(' => depth += 1,
+            ')' if depth == 0 && i != policy.len() - 1 => return Err(invalid()),
+            ')' if depth == 0 => arguments.push(policy[start..i].trim()),
+            ')' => depth -= 1,
+            ',' if depth == 0 => {
+                arguments.push(policy[start..i].trim());
+                start = i + 1;
+            }
+            _ => (),
+        }
+    }
+    match arguments.iter().any(|argument| argument.is_empty()) {
+        true => Err(invalid()),
+        false => Ok((policy[..open].trim(), arguments)),
+    }
+}
+
+/// Returns the compiled fragment of the given policy.
+fn compile(policy: &str, keys: &[(String, Vec<u8>)]) -> Result<Fragment, MiniscriptError> {
+    let (name, arguments) = split(policy.trim())?;
+    match (name, arguments.as_slice()) {
+        ("pk", [key]) => {
+            let public_key = match keys.iter().find(|(name, _)| name == key) {
+                Some((_, public_key)) => public_key.clone(),
+                None => hex::decode(key).map_err(|_| MiniscriptError::InvalidKey(key.to_string()))?,
+            };
+            // Witness scripts only accept compressed public keys
+            if public_key.len() != 33 || secp256k1::PublicKey::parse_slice(&public_key, None).is_err() {
+                return Err(MiniscriptError::InvalidKey(key.to_string()));
+            }
+
+            let script = [
+                vec![public_key.len() as u8],
+                public_key.clone(),
+                vec![Opcode::OP_CHECKSIG as u8],
+            ]
+            .concat();
+            Ok(Fragment {
+                miniscript: format!("pk({})", key),
+                script,
+                key: Some((key.to_string(), public_key)),
+                dissatisfiable: true,
+                unit: true,
+                signed: true,
+                satisfaction: WitnessSize::new(1, MAX_SIGNATURE_SIZE),
+                dissatisfaction: Some(WitnessSize::new(1, 1)),
+            })
+        }
+        ("after", [value]) | ("older", [value]) => {
+            let locktime = match u32::from_str(value) {
+                Ok(locktime) if (1..0x8000_0000).contains(&locktime) => locktime,
+                _ => return Err(MiniscriptError::InvalidTimelock(value.to_string())),
+            };
+            let opcode = match name {
+                "after" => Opcode::OP_CHECKLOCKTIMEVERIFY,
+                _ => Opcode::OP_CHECKSEQUENCEVERIFY,
+            };
+
+            let mut script = push_number(locktime);
+            script.push(opcode as u8);
+            Ok(Fragment {
+                miniscript: format!("{}({})", name, locktime),
+                script,
+                key: None,
+                dissatisfiable: false,
+                unit: false,
+                signed: false,
+                satisfaction: WitnessSize::default(),
+                dissatisfaction: None,
+            })
+        }
+        ("sha256", [hash]) | ("hash256", [hash]) | ("ripemd160", [hash]) | ("hash160", [hash]) => {
+            let (opcode, length) = match name {
+                "sha256" => (Opcode::OP_SHA256, 32),
+                "hash256" => (Opcode::OP_HASH256, 32),
+                "ripemd160" => (Opcode::OP_RIPEMD160, 20),
+                _ => (Opcode::OP_HASH160, 20),
+            };
+            let digest = match hex::decode(hash) {
+                Ok(digest) if digest.len() == length => digest,
+                _ => return Err(MiniscriptError::InvalidHash(hash.to_string(), length)),
+            };
+
+            // The preimage is 32 bytes, so that it cannot be malleated
+            let script = [
+                vec![
+                    Opcode::OP_SIZE as u8,
+                    0x01,
+                    0x20,
+                    Opcode::OP_EQUALVERIFY as u8,
+                    opcode as u8,
+                ],
+                vec![length as u8],
+                digest,
+                vec![Opcode::OP_EQUAL as u8],
+            ]
+            .concat();
+            Ok(Fragment {
+                miniscript: format!("{}({})", name, hash),
+                script,
+                key: None,
+                dissatisfiable: true,
+                unit: true,
+                signed: false,
+                satisfaction: WitnessSize::new(1, 33),
+                dissatisfaction: Some(WitnessSize::new(1, 33)),
+            })
+        }
+        ("and", [x, y]) => {
+            let (x, y) = (compile(x, keys)?, compile(y, keys)?);
+            let (miniscript, script) = x.verify();
+            Ok(Fragment {
+                miniscript: format!("and_v({},{})", miniscript, y.miniscript),
+                script: [script, y.script].concat(),
+                key: None,
+                dissatisfiable: false,
+                unit: y.unit,
+                signed: x.signed || y.signed,
+                satisfaction: x.satisfaction.add(y.satisfaction),
+                dissatisfaction: None,
+            })
+        }
+        ("or", [x, y]) => {
+            // The probabilities of the branches are ignored
+            let branch = |policy: &str| match policy.find('@') {
+                Some(i) if policy[..i].trim().chars().all(|c| c.is_ascii_digit()) => policy[i + 1..].to_string(),
+                _ => policy.to_string(),
+            };
+            let (x, y) = (compile(&branch(x), keys)?, compile(&branch(y), keys)?);
+
+            let dissatisfaction = match (x.dissatisfaction, y.dissatisfaction) {
+                (Some(x), Some(y)) => Some(x.add(WitnessSize::new(1, 2)).max(y.add(WitnessSize::new(1, 1)))),
+                (Some(x), None) => Some(x.add(WitnessSize::new(1, 2))),
+                (None, Some(y)) => Some(y.add(WitnessSize::new(1, 1))),
+                (None, None) => None,
+            };
+            Ok(Fragment {
+                miniscript: format!("or_i({},{})", x.miniscript, y.miniscript),
+                script: [
+                    vec![Opcode::OP_IF as u8],
+                    x.script,
+                    vec![Opcode::OP_ELSE as u8],
+                    y.script,
+                    vec![Opcode::OP_ENDIF as u8],
+                ]
+                .concat(),
+                key: None,
+                dissatisfiable: x.dissatisfiable || y.dissatisfiable,
+                unit: x.unit && y.unit,
+                signed: x.signed && y.signed,
+                satisfaction: x
+                    .satisfaction
+                    .add(WitnessSize::new(1, 2))
+                    .max(y.satisfaction.add(WitnessSize::new(1, 1))),
+                dissatisfaction,
+            })
+        }
+        ("thresh", [threshold, subpolicies @ ..]) => {
+            let threshold = usize::from_str(threshold)
+                .map_err(|_| MiniscriptError::InvalidPolicy(format!("invalid threshold {}", threshold)))?;
+            if threshold == 0 || threshold > subpolicies.len() {
+                return Err(MiniscriptError::InvalidThreshold(threshold, subpolicies.len()));
+            }
+            let subpolicies = subpolicies
+                .iter()
+                .map(|subpolicy| compile(subpolicy, keys))
+                .collect::<Result<Vec<_>, _>>()?;
+
+            match subpolicies.iter().all(|subpolicy| subpolicy.key.is_some()) {
+                true => multi(threshold, &subpolicies),
+                false => thresh(threshold, subpolicies),
+            }
+        }
+        _ => Err(MiniscriptError::InvalidPolicy(policy.into())),
+    }
+}
+
+/// Returns the `multi(k,A,B,...)` fragment of the given threshold of the given `pk` fragments,
+/// `<k> <A> <B> ... <n> CHECKMULTISIG`.
+fn multi(threshold: usize, subpolicies: &[Fragment]) -> Result<Fragment, MiniscriptError> {
+    if subpolicies.len() > MAX_MULTISIG_PUBLIC_KEYS {
+        return Err(MiniscriptError::InvalidThreshold(threshold, subpolicies.len()));
+    }
+
+    let mut names = vec![];
+    let mut script = push_number(threshold as u32);
+    for (name, public_key) in subpolicies.iter().filter_map(|subpolicy| subpolicy.key.as_ref()) {
+        names.push(name.as_str());
+        script.push(public_key.len() as u8);
+        script.extend(public_key);
+    }
+    script.extend(push_number(subpolicies.len() as u32));
+    script.push(Opcode::OP_CHECKMULTISIG as u8);
+
+    // The satisfaction is preceded by an empty element, which is popped by CHECKMULTISIG
+    Ok(Fragment {
+        miniscript: format!("multi({},{})", threshold, names.join(",")),
+        script,
+        key: None,
+        dissatisfiable: true,
+        unit: true,
+        signed: true,
+        satisfaction: WitnessSize::new(threshold + 1, threshold * MAX_SIGNATURE_SIZE + 1),
+        dissatisfaction: Some(WitnessSize::new(threshold + 1, threshold + 1)),
+    })
+}
+
+/// Returns the `thresh(k,X,a:Y,...)` fragment of the given threshold of the given fragments,
+/// `[X] [a:Y] ADD ... <k> EQUAL`, with each fragment wrapped to be a dissatisfiable unit.
+fn thresh(threshold: usize, subpolicies: Vec<Fragment>) -> Result<Fragment, MiniscriptError> {
+    let unsigned = subpolicies.iter().filter(|subpolicy| !subpolicy.signed).count();
+
+    let mut miniscripts = vec![];
+    let mut script = vec![];
+    let mut costs = vec![];
+    let mut dissatisfaction = WitnessSize::default();
+    for (i, mut subpolicy) in subpolicies.into_iter().enumerate() {
+        if !subpolicy.unit {
+            subpolicy = subpolicy.unit();
+        }
+        if !subpolicy.dissatisfiable {
+            subpolicy = subpolicy.dissatisfiable();
+        }
+        if i > 0 {
+            subpolicy = subpolicy.alt();
+        }
+
+        let subpolicy_dissatisfaction = subpolicy.dissatisfaction.unwrap_or_default();
+        dissatisfaction = dissatisfaction.add(subpolicy_dissatisfaction);
+        costs.push((subpolicy.satisfaction, subpolicy_dissatisfaction));
+
+        miniscripts.push(subpolicy.miniscript);
+        script.extend(subpolicy.script);
+        if i > 0 {
+            script.push(Opcode::OP_ADD as u8);
+        }
+    }
+    script.extend(push_number(threshold as u32));
+    script.push(Opcode::OP_EQUAL as u8);
+
+    // The largest satisfaction satisfies the subpolicies with the largest satisfactions over their dissatisfactions
+    costs.sort_by_key(|(satisfaction, dissatisfaction)| {
+        core::cmp::Reverse(satisfaction.bytes as isize - dissatisfaction.bytes as isize)
+    });
+    let satisfaction = costs.iter().enumerate().fold(
+        WitnessSize::default(),
+        |size, (i, (satisfaction, dissatisfaction))| match i < threshold {
+            true => size.add(*satisfaction),
+            false => size.add(*dissatisfaction),
+        },
+    );
+
+    Ok(Fragment {
+        miniscript: format!("thresh({},{})", threshold, miniscripts.join(",")),
+        script,
+        key: None,
+        dissatisfiable: true,
+        unit: true,
+        signed: unsigned < threshold,
+        satisfaction,
+        dissatisfaction: Some(dissatisfaction),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::*;
+
+    const KEY_A: &str = "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+    const KEY_B: &str = "02c6047f9441ed7d6d3045406e95c07cd85c778e4b8cef3ca7abac09b95c709ee5";
+    const KEY_C: &str = "02f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9";
+
+    fn keys() -> Vec<(String, Vec<u8>)> {
+        vec![
+            ("A".into(), hex::decode(KEY_A).unwrap()),
+            ("B".into(), hex::decode(KEY_B).unwrap()),
+            ("C".into(), hex::decode(KEY_C).unwrap()),
+        ]
+    }
+
+    fn test_compile(policy: &str, expected_miniscript: &str, expected_script: &str, expected_weight: usize) {
+        let miniscript = BitcoinMiniscript::compile(policy, &keys()).unwrap();
+        assert_eq!(expected_miniscript, miniscript.miniscript);
+        assert_eq!(expected_script, hex::encode(&miniscript.script));
+        assert_eq!(expected_weight, miniscript.max_satisfaction_weight().unwrap());
+        assert_eq!(
+            BitcoinAddress::<Mainnet>::p2wsh(&miniscript.script).unwrap(),
+            miniscript.to_address::<Mainnet>().unwrap()
+        );
+    }
+
+    #[test]
+    fn pk() {
+        // <A> CHECKSIG, with a witness of the signature and the 35 byte script
+        test_compile("pk(A)", "pk(A)", &format!("21{}ac", KEY_A), 1 + 74 + 1 + 35);
+        test_compile(
+            &format!("pk({})", KEY_B),
+            &format!("pk({})", KEY_B),
+            &format!("21{}ac", KEY_B),
+            111,
+        );
+    }
+
+    #[test]
+    fn and_or_after() {
+        // <A> CHECKSIGVERIFY IF <B> CHECKSIG ELSE <1000> CHECKLOCKTIMEVERIFY ENDIF
+        test_compile(
+            "and(pk(A),or(pk(B),after(1000)))",
+            "and_v(v:pk(A),or_i(pk(B),after(1000)))",
+            &format!("21{}ad6321{}ac6702e803b168", KEY_A, KEY_B),
+            1 + (74 + 74 + 2) + 1 + 77,
+        );
+        // The probabilities of the branches are ignored
+        let miniscript = BitcoinMiniscript::compile("or(99@pk(A),1@older(144))", &keys()).unwrap();
+        assert_eq!("or_i(pk(A),older(144))", miniscript.miniscript);
+        assert!(!miniscript.requires_signature);
+    }
+
+    #[test]
+    fn hash() {
+        let hash = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+        test_compile(
+            &format!("and(sha256({}),pk(A))", hash),
+            &format!("and_v(v:sha256({}),pk(A))", hash),
+            &format!("82012088a820{}8821{}ac", hash, KEY_A),
+            1 + (33 + 74) + 1 + 74,
+        );
+        assert!(BitcoinMiniscript::compile("sha256(00)", &keys()).is_err());
+    }
+
+    #[test]
+    fn multi() {
+        // 2 <A> <B> <C> 3 CHECKMULTISIG, with a witness of an empty element and 2 signatures
+        test_compile(
+            "thresh(2,pk(A),pk(B),pk(C))",
+            "multi(2,A,B,C)",
+            &format!("5221{}21{}21{}53ae", KEY_A, KEY_B, KEY_C),
+            1 + (1 + 2 * 74) + 1 + 105,
+        );
+    }
+
+    #[test]
+    fn thresh() {
+        // <A> CHECKSIG TOALTSTACK IF 0 ELSE <B> CHECKSIGVERIFY <10> CSV ... ENDIF FROMALTSTACK ADD 2 EQUAL
+        let miniscript = BitcoinMiniscript::compile("thresh(2,pk(A),pk(B),older(10))", &keys()).unwrap();
+        assert_eq!("thresh(2,pk(A),a:pk(B),aln:older(10))", miniscript.miniscript);
+        assert_eq!(
+            format!("21{}ac6b21{}ac6c936b6300675ab292686c935287", KEY_A, KEY_B),
+            hex::encode(&miniscript.script)
+        );
+        assert!(miniscript.requires_signature);
+
+        let miniscript = BitcoinMiniscript::compile("thresh(1,pk(A),older(10))", &keys()).unwrap();
+        assert!(!miniscript.requires_signature);
+    }
+
+    #[test]
+    fn invalid() {
+        assert!(BitcoinMiniscript::compile("pk(D)", &keys()).is_err());
+        assert!(BitcoinMiniscript::compile("and(pk(A))", &keys()).is_err());
+        assert!(BitcoinMiniscript::compile("or(pk(A),pk(B)", &keys()).is_err());
+        assert!(BitcoinMiniscript::compile("thresh(3,pk(A),pk(B))", &keys()).is_err());
+        assert!(BitcoinMiniscript::compile("after(0)", &keys()).is_err());
+        assert!(BitcoinMiniscript::compile("multi(1,A)", &keys()).is_err());
+    }
+}
@@ -7,6 +7,7 @@ use crate::private_key::BitcoinPrivateKey;
 use crate::public_key::BitcoinPublicKey;
 use crate::wordlist::BitcoinWordlist;
 use wagyu_model::no_std::*;
+use wagyu_model::utilities::unicode::nfkd;
 use wagyu_model::{ExtendedPrivateKey, Mnemonic, MnemonicCount, MnemonicError, MnemonicExtended};
 
 use bitvec::prelude::*;
@@ -33,19 +34,11 @@ pub struct BitcoinMnemonic<N: BitcoinNetwork, W: BitcoinWordlist> {
 impl<N: BitcoinNetwork, W: BitcoinWordlist> MnemonicCount for BitcoinMnemonic<N, W> {
     /// Returns a new mnemonic given the word count.
     fn new_with_count<R: Rng>(rng: &mut R, word_count: u8) -> Result<Self, MnemonicError> {
-        let length: usize = match word_count {
-            12 => 16,
-            15 => 20,
-            18 => 24,
-            21 => 28,
-            24 => 32,
-            wc => return Err(MnemonicError::InvalidWordCount(wc)),
-        };
-
-        let entropy: [u8; 32] = rng.gen();
+        let mut entropy = vec![0u8; Self::entropy_length(word_count as usize)?];
+        rng.fill(&mut entropy[..]);
 
         Ok(Self {
-            entropy: entropy[0..length].to_vec(),
+            entropy,
             _network: PhantomData,
             _wordlist: PhantomData,
         })
@@ -70,20 +63,15 @@ impl<N: BitcoinNetwork, W: BitcoinWordlist> Mnemonic for BitcoinMnemonic<N, W> {
 
     /// Returns the mnemonic for the given phrase.
     fn from_phrase(phrase: &str) -> Result<Self, MnemonicError> {
-        let mnemonic = phrase.split(" ").collect::<Vec<&str>>();
-
-        let length = match mnemonic.len() {
-            12 => 128,
-            15 => 160,
-            18 => 192,
-            21 => 224,
-            24 => 256,
-            wc => return Err(MnemonicError::InvalidWordCount(wc as u8)),
-        };
+        // Decomposes the phrase, and separates its words by any spaces, including ideographic spaces.
+        let normalized = nfkd(phrase);
+        let words = normalized.split_whitespace().collect::<Vec<&str>>();
+
+        let length = Self::entropy_length(words.len())? * 8;
 
         let mut entropy: BitVec<Msb0, u8> = BitVec::new();
 
-        for word in mnemonic {
+        for word in &words {
             let index = W::get_index(word)?;
             let index_u8: [u8; 2] = (index as u16).to_be_bytes();
             let index_slice = &BitVec::from_slice(&index_u8)[5..];
@@ -98,7 +86,7 @@ impl<N: BitcoinNetwork, W: BitcoinWordlist> Mnemonic for BitcoinMnemonic<N, W> {
         };
 
         // Ensures the checksum word matches the checksum word in the given phrase.
-        match phrase == mnemonic.to_phrase()? {
+        match words.join(W::SEPARATOR) == mnemonic.to_phrase()? {
             true => Ok(mnemonic),
             false => Err(MnemonicError::InvalidPhrase(phrase.into())),
         }
@@ -143,7 +131,7 @@ impl<N: BitcoinNetwork, W: BitcoinWordlist> Mnemonic for BitcoinMnemonic<N, W> {
             })
             .collect::<Vec<&str>>();
 
-        Ok(phrase.join(" "))
+        Ok(phrase.join(W::SEPARATOR))
     }
 
     /// Returns the private key of the corresponding mnemonic.
@@ -181,16 +169,46 @@ impl<N: BitcoinNetwork, W: BitcoinWordlist> MnemonicExtended for BitcoinMnemonic
 }
 
 impl<N: BitcoinNetwork, W: BitcoinWordlist> BitcoinMnemonic<N, W> {
+    /// Returns the mnemonic for the given entropy, or an error if its length is not that of a phrase.
+    pub fn from_entropy(entropy: &[u8]) -> Result<Self, MnemonicError> {
+        let mnemonic = Self {
+            entropy: entropy.to_vec(),
+            _network: PhantomData,
+            _wordlist: PhantomData,
+        };
+        mnemonic.to_phrase()?;
+        Ok(mnemonic)
+    }
+
+    /// Returns the entropy of the corresponding mnemonic.
+    pub fn to_entropy(&self) -> Vec<u8> {
+        self.entropy.clone()
+    }
+
+    /// Returns the checksum of the corresponding mnemonic, the first ENT / 32 bits of the SHA256 hash
+    /// of its entropy, as a string of binary digits.
+    pub fn to_checksum(&self) -> Result<String, MnemonicError> {
+        match self.entropy.len() {
+            16 | 20 | 24 | 28 | 32 => {
+                let hash = Sha256::digest(&self.entropy);
+                Ok(format!("{:08b}", hash[0])[..self.entropy.len() / 4].into())
+            }
+            entropy_len => Err(MnemonicError::InvalidEntropyLength(entropy_len)),
+        }
+    }
+
     /// Compares the given phrase against the phrase extracted from its entropy.
     pub fn verify_phrase(phrase: &str) -> bool {
         Self::from_phrase(phrase).is_ok()
     }
 
     /// Returns a seed using the given password and mnemonic.
-    fn to_seed(&self, password: Option<&str>) -> Result<Vec<u8>, MnemonicError> {
+    pub fn to_seed(&self, password: Option<&str>) -> Result<Vec<u8>, MnemonicError> {
         let mut seed = vec![0u8; PBKDF2_BYTES];
-        let salt = format!("mnemonic{}", password.unwrap_or(""));
-        pbkdf2::<Hmac<Sha512>>(&self.to_phrase()?.as_bytes(), salt.as_bytes(), PBKDF2_ROUNDS, &mut seed);
+        // Decomposes the phrase and password, which separates the words of the phrase by spaces.
+        let phrase = nfkd(&self.to_phrase()?);
+        let salt = nfkd(&format!("mnemonic{}", password.unwrap_or("")));
+        pbkdf2::<Hmac<Sha512>>(phrase.as_bytes(), salt.as_bytes(), PBKDF2_ROUNDS, &mut seed);
         Ok(seed)
     }
 }
@@ -248,6 +266,12 @@ mod tests {
         assert_eq!(expected_phrase, mnemonic.to_phrase().unwrap());
     }
 
+    fn test_from_entropy<N: BitcoinNetwork, W: BitcoinWordlist>(entropy: &Vec<u8>, expected_phrase: &str) {
+        let mnemonic = BitcoinMnemonic::<N, W>::from_entropy(entropy).unwrap();
+        assert_eq!(expected_phrase, mnemonic.to_phrase().unwrap());
+        assert_eq!(entropy, &mnemonic.to_entropy());
+    }
+
     fn test_verify_phrase<N: BitcoinNetwork, W: BitcoinWordlist>(phrase: &str) {
         assert!(BitcoinMnemonic::<N, W>::verify_phrase(phrase));
     }
@@ -448,6 +472,18 @@ mod tests {
             })
         }
 
+        #[test]
+        fn new_entropy_length() {
+            let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+            [(12, 16), (15, 20), (18, 24), (21, 28), (24, 32)]
+                .iter()
+                .for_each(|(word_count, length)| {
+                    let mnemonic = BitcoinMnemonic::<N, W>::new_with_count(rng, *word_count).unwrap();
+                    assert_eq!(*length, mnemonic.entropy.len());
+                    assert_eq!(*word_count as usize, mnemonic.to_phrase().unwrap().split(' ').count());
+                })
+        }
+
         #[test]
         fn from_phrase() {
             KEYPAIRS.iter().for_each(|(entropy_str, phrase, _, _)| {
@@ -464,6 +500,28 @@ mod tests {
             })
         }
 
+        #[test]
+        fn from_entropy() {
+            KEYPAIRS.iter().for_each(|(entropy_str, phrase, _, _)| {
+                let entropy = hex::decode(entropy_str).unwrap();
+                test_from_entropy::<N, W>(&entropy, phrase);
+            })
+        }
+
+        #[test]
+        fn to_checksum() {
+            KEYPAIRS.iter().for_each(|(entropy_str, phrase, _, _)| {
+                let entropy = hex::decode(entropy_str).unwrap();
+                let mnemonic = BitcoinMnemonic::<N, W>::from_entropy(&entropy).unwrap();
+                // The checksum is the low bits of the index of the final word
+                let checksum = mnemonic.to_checksum().unwrap();
+                let index = W::get_index(phrase.rsplit(' ').next().unwrap()).unwrap();
+                let bits = format!("{:011b}", index);
+                assert_eq!(entropy.len() / 4, checksum.len());
+                assert_eq!(&bits[11 - checksum.len()..], checksum);
+            });
+        }
+
         #[test]
         fn verify_phrase() {
             KEYPAIRS.iter().for_each(|(_, phrase, _, _)| {
@@ -506,6 +564,84 @@ mod tests {
         }
     }
 
+    /// Test vectors of the Japanese wordlist, with a password of composed kana
+    mod japanese {
+        use super::*;
+
+        type N = Mainnet;
+        type W = Japanese;
+
+        const PASSWORD: &str = "ガバヴァぱばぐゞちぢ十人十色";
+        const COMPOSED_PHRASE: &str =
+            "そとづら あまど おおう あこがれる いくぶん けいけん あたえる いよく そとづら あまど おおう あかちゃん";
+
+        // (entropy, phrase, seed)
+        const KEYPAIRS: [(&str, &str, &str); 5] = [
+            (
+                "00000000000000000000000000000000",
+                "あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あいこくしん　あおぞら",
+                "1d6131e271308e9504ed020a1aca8878f341029c73e6af3e6d888f45f5cc58dde8db14108cf92ca4aba0bf45d5614553a83860df9a0aac5e81d0384305bd9c11"
+            ),
+            (
+                "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+                "そつう　れきだい　ほんやく　わかす　りくつ　ばいか　ろせん　やちん　そつう　れきだい　ほんやく　わかめ",
+                "c67884cf1f1bcd854bfa4d96e4520f972f9cd67d4948641ec3f686121b8b1b974acbbbc791fce4c6b7a4d87b991fb4e96cc083f4b1a6a0087b22baff6db1d190"
+            ),
+            (
+                "80808080808080808080808080808080",
+                "そとづら　あまど　おおう　あこがれる　いくぶん　けいけん　あたえる　いよく　そとづら　あまど　おおう　あかちゃん",
+                "e72047e2d168a84102689f94c4d188d1922016cd8d8676ff315b1cbf2f28550fb3a7d69502c8005b06fe714bb7f54928ad9dacd11a81806ef96647513f94a52c"
+            ),
+            (
+                "ffffffffffffffffffffffffffffffff",
+                "われる　われる　われる　われる　われる　われる　われる　われる　われる　われる　われる　ろんぶん",
+                "9d5e8772d6cc6c830f183d307a1987b4fe038eb550a58fb1be01557c47f884c670f17a31a223d6673c66deab185557d80d852c0c8c647d8fa07eb33d743bc545"
+            ),
+            (
+                "9e885d952ad362caeb4efe34a8e91bd2",
+                "ておくれ　げざん　しねま　こりる　きぼう　しねん　ななおし　ほんやく　きない　けむり　けまり　てんない",
+                "c6191a501f8860a44b280d53f3fb4cd5f54d2e59f39e249424996eb4ec8479593b4c4467b144730d6d4ed1e95139ff4853a5a7daef877d6ff03a4c7a4895d7f6"
+            )
+        ];
+
+        #[test]
+        fn from_phrase() {
+            KEYPAIRS.iter().for_each(|(entropy_str, phrase, _)| {
+                let entropy: Vec<u8> = hex::decode(entropy_str).unwrap();
+                test_from_phrase::<N, W>(&entropy, phrase);
+            })
+        }
+
+        #[test]
+        fn from_phrase_composed() {
+            let (entropy_str, phrase, _) = KEYPAIRS[2];
+            let mnemonic = BitcoinMnemonic::<N, W>::from_phrase(COMPOSED_PHRASE).unwrap();
+            assert_eq!(hex::decode(entropy_str).unwrap(), mnemonic.to_entropy());
+            assert_eq!(phrase, mnemonic.to_phrase().unwrap());
+        }
+
+        #[test]
+        fn to_phrase() {
+            KEYPAIRS.iter().for_each(|(entropy_str, phrase, _)| {
+                let entropy: Vec<u8> = hex::decode(entropy_str).unwrap();
+                test_to_phrase::<N, W>(phrase, &entropy);
+            })
+        }
+
+        #[test]
+        fn to_seed() {
+            KEYPAIRS.iter().for_each(|(entropy_str, _, expected_seed)| {
+                let entropy: Vec<u8> = hex::decode(entropy_str).unwrap();
+                let mnemonic = BitcoinMnemonic::<N, W> {
+                    entropy,
+                    _network: PhantomData,
+                    _wordlist: PhantomData,
+                };
+                test_to_seed::<N, W>(expected_seed, Some(PASSWORD), mnemonic);
+            });
+        }
+    }
+
     mod test_invalid {
         use super::*;
 
@@ -547,6 +683,12 @@ mod tests {
             let _mnemonic = BitcoinMnemonic::<N, W>::from_phrase(INVALID_PHRASE_CHECKSUM).unwrap();
         }
 
+        #[test]
+        #[should_panic(expected = "InvalidEntropyLength(15)")]
+        fn from_entropy_invalid_length() {
+            let _mnemonic = BitcoinMnemonic::<N, W>::from_entropy(&[0u8; 15]).unwrap();
+        }
+
         #[test]
         fn verify_invalid_phrase() {
             assert!(!BitcoinMnemonic::<N, W>::verify_phrase(INVALID_PHRASE_LENGTH));
@@ -0,0 +1,739 @@
+//!
+//! MuSig
+//!
+//! This module contains the MuSig2 multi-signature scheme for BIP-340 Schnorr signatures.
+//! https://github.com/bitcoin/bips/blob/master/bip-0327.mediawiki
+//!
+//! The public keys of the signers are aggregated into a single x-only public key, which is
+//! indistinguishable from the key of a single signer, such as the internal key of a P2TR output.
+//! Signing takes two rounds:
+//! - each signer generates a secret nonce, and shares its public nonce with the other signers.
+//! - each signer signs with its secret nonce and the aggregate of the public nonces, and the
+//!   partial signatures are aggregated into a single Schnorr signature.
+//!
+//! A secret nonce must never be used for more than one partial signature, as the private key of
+//! its signer is otherwise revealed by the partial signatures.
+//!
+
+use crate::address::BitcoinAddress;
+use crate::network::BitcoinNetwork;
+use crate::public_key::BitcoinPublicKey;
+use crate::taproot::tagged_hash;
+use wagyu_model::no_std::*;
+use wagyu_model::AddressError;
+
+use core::convert::TryFrom;
+use rand::Rng;
+use secp256k1::{curve::Scalar, PublicKey, SecretKey};
+
+#[derive(Debug, Fail)]
+pub enum MuSigError {
+    #[fail(display = "invalid aggregate nonce")]
+    InvalidAggregateNonce,
+
+    #[fail(display = "invalid aggregate public key")]
+    InvalidAggregatePublicKey,
+
+    #[fail(display = "invalid partial signature of signer {}", _0)]
+    InvalidPartialSignature(usize),
+
+    #[fail(display = "invalid private key")]
+    InvalidPrivateKey,
+
+    #[fail(display = "invalid public key of signer {}", _0)]
+    InvalidPublicKey(usize),
+
+    #[fail(display = "invalid public nonce of signer {}", _0)]
+    InvalidPublicNonce(usize),
+
+    #[fail(display = "invalid secret nonce")]
+    InvalidSecretNonce,
+
+    #[fail(display = "invalid tweak")]
+    InvalidTweak,
+
+    #[fail(display = "the secret nonce was not generated for the public key of the private key")]
+    MismatchedSecretNonce,
+
+    #[fail(display = "the public key of the private key is not a public key of the signers")]
+    UnknownSigner,
+}
+
+/// Returns the public keys sorted in lexicographical order (KeySort),
+/// for an aggregate public key that is independent of the order of its signers.
+pub fn key_sort(public_keys: &[[u8; 33]]) -> Vec<[u8; 33]> {
+    let mut public_keys = public_keys.to_vec();
+    public_keys.sort_unstable();
+    public_keys
+}
+
+/// Returns the secret nonce and public nonce of the signer of the given public key (NonceGen).
+/// The nonce is derived from fresh randomness, which is strengthened by the private key,
+/// aggregate public key, and message, if specified.
+pub fn nonce_gen<R: Rng>(
+    rng: &mut R,
+    private_key: Option<&[u8; 32]>,
+    public_key: &[u8; 33],
+    aggregate_public_key: Option<&[u8; 32]>,
+    message: Option<&[u8]>,
+) -> Result<([u8; 97], [u8; 66]), MuSigError> {
+    let mut random = [0u8; 32];
+    rng.fill(&mut random);
+    if let Some(private_key) = private_key {
+        let mask = tagged_hash("MuSig/aux", &random);
+        random = private_key_xor(private_key, &mask);
+    }
+    nonce_gen_internal(&random, public_key, aggregate_public_key, message)
+}
+
+/// Returns the aggregate nonce of the public nonces of the signers (NonceAgg).
+pub fn nonce_agg(public_nonces: &[[u8; 66]]) -> Result<[u8; 66], MuSigError> {
+    let mut aggregate_nonce = [0u8; 66];
+    for j in 0..2 {
+        let points = public_nonces
+            .iter()
+            .enumerate()
+            .map(|(i, nonce)| {
+                to_point(&nonce[j * 33..(j + 1) * 33])
+                    .map(Some)
+                    .ok_or(MuSigError::InvalidPublicNonce(i))
+            })
+            .collect::<Result<Vec<Option<PublicKey>>, MuSigError>>()?;
+        aggregate_nonce[j * 33..(j + 1) * 33].copy_from_slice(&to_bytes_ext(&add(&points)));
+    }
+    Ok(aggregate_nonce)
+}
+
+/// Returns the BIP-340 Schnorr signature of the message by the given private key, with the given auxiliary
+/// random data. The signature is valid for the x-only public key of the private key.
+pub fn sign_schnorr(private_key: &[u8; 32], message: &[u8], aux_rand: &[u8; 32]) -> Result<[u8; 64], MuSigError> {
+    let d = match to_scalar_checked(private_key) {
+        Some(d) if !d.is_zero() => d,
+        _ => return Err(MuSigError::InvalidPrivateKey),
+    };
+    let point = multiply_generator(&d).ok_or(MuSigError::InvalidPrivateKey)?;
+    let d = d * parity(&point);
+    let public_key = x_only(&point);
+
+    // The nonce is derived from the private key masked with the auxiliary random data
+    let masked_key = private_key_xor(&d.b32(), &tagged_hash("BIP0340/aux", aux_rand));
+    let k = to_scalar(&tagged_hash(
+        "BIP0340/nonce",
+        &[&masked_key[..], &public_key[..], message].concat(),
+    ));
+    let r = multiply_generator(&k).ok_or(MuSigError::InvalidSecretNonce)?;
+    let k = k * parity(&r);
+
+    let e = to_scalar(&tagged_hash(
+        "BIP0340/challenge",
+        &[&x_only(&r)[..], &public_key[..], message].concat(),
+    ));
+    let mut signature = [0u8; 64];
+    signature[..32].copy_from_slice(&x_only(&r));
+    signature[32..].copy_from_slice(&(k + e * d).b32());
+    Ok(signature)
+}
+
+/// Returns whether the given signature is a valid BIP-340 Schnorr signature of the message
+/// by the x-only public key.
+pub fn verify_schnorr(public_key: &[u8; 32], message: &[u8], signature: &[u8; 64]) -> bool {
+    let point = match to_point(&[&[0x02], &public_key[..]].concat()) {
+        Some(point) => point,
+        None => return false,
+    };
+    let s = match to_scalar_checked(&signature[32..]) {
+        Some(s) => s,
+        None => return false,
+    };
+    let e = to_scalar(&tagged_hash(
+        "BIP0340/challenge",
+        &[&signature[..32], &public_key[..], message].concat(),
+    ));
+
+    // R = s⋅G - e⋅P must have an even y coordinate and the x coordinate of the signature
+    match add(&[multiply_generator(&s), negate(multiply(&point, &e))]) {
+        Some(r) => has_even_y(&r) && x_only(&r)[..] == signature[..32],
+        None => false,
+    }
+}
+
+/// Represents the aggregate public key of the signers, and the tweaks applied to it
+#[derive(Debug, Clone)]
+pub struct MuSigKeyAggContext {
+    /// The compressed public keys of the signers, in the order of aggregation
+    pub public_keys: Vec<[u8; 33]>,
+    /// The (tweaked) aggregate public key
+    point: PublicKey,
+    /// The accumulated negation of the tweaks
+    gacc: Scalar,
+    /// The accumulated tweak
+    tacc: Scalar,
+}
+
+impl MuSigKeyAggContext {
+    /// Returns the aggregate public key of the given public keys, in the given order (KeyAgg).
+    pub fn new(public_keys: &[[u8; 33]]) -> Result<Self, MuSigError> {
+        let terms = public_keys
+            .iter()
+            .enumerate()
+            .map(|(i, public_key)| match to_point(public_key) {
+                Some(point) => Ok(multiply(&point, &key_agg_coefficient(public_keys, public_key))),
+                None => Err(MuSigError::InvalidPublicKey(i)),
+            })
+            .collect::<Result<Vec<Option<PublicKey>>, MuSigError>>()?;
+
+        Ok(Self {
+            public_keys: public_keys.to_vec(),
+            point: add(&terms).ok_or(MuSigError::InvalidAggregatePublicKey)?,
+            gacc: Scalar::from_int(1),
+            tacc: Scalar::from_int(0),
+        })
+    }
+
+    /// Applies the given tweak to the aggregate public key, as an x-only tweak (of a taproot output)
+    /// or a plain tweak (of a BIP-32 derivation).
+    pub fn apply_tweak(&mut self, tweak: &[u8; 32], x_only: bool) -> Result<(), MuSigError> {
+        let g = match x_only {
+            true => parity(&self.point),
+            false => Scalar::from_int(1),
+        };
+        let t = to_scalar_checked(tweak).ok_or(MuSigError::InvalidTweak)?;
+
+        self.point = add(&[multiply(&self.point, &g), multiply_generator(&t)]).ok_or(MuSigError::InvalidTweak)?;
+        self.gacc = g.clone() * self.gacc.clone();
+        self.tacc = t + g * self.tacc.clone();
+        Ok(())
+    }
+
+    /// Applies the taproot tweak of a key path only output (BIP-86), after which the aggregate public key
+    /// is the output key of its P2TR address.
+    pub fn apply_taproot_tweak(&mut self) -> Result<(), MuSigError> {
+        let tweak = tagged_hash("TapTweak", &self.to_x_only_public_key());
+        self.apply_tweak(&tweak, true)
+    }
+
+    /// Returns the aggregate public key.
+    pub fn to_public_key(&self) -> PublicKey {
+        self.point.clone()
+    }
+
+    /// Returns the x-only aggregate public key.
+    pub fn to_x_only_public_key(&self) -> [u8; 32] {
+        x_only(&self.point)
+    }
+
+    /// Returns the P2TR address with the aggregate public key as its internal key, and no script tree (BIP-86).
+    pub fn to_address<N: BitcoinNetwork>(&self) -> Result<BitcoinAddress<N>, AddressError> {
+        BitcoinAddress::p2tr(&BitcoinPublicKey::from_secp256k1_public_key(self.point.clone(), true))
+    }
+}
+
+/// Represents a signing session of a message with an aggregate public key and an aggregate nonce
+#[derive(Debug, Clone)]
+pub struct MuSigSession {
+    /// The aggregate public key of the signers
+    key_agg: MuSigKeyAggContext,
+    /// The nonce coefficient
+    b: Scalar,
+    /// The final nonce
+    r: PublicKey,
+    /// The challenge of the signature
+    e: Scalar,
+}
+
+impl MuSigSession {
+    /// Returns the signing session of the given message with the aggregate public key and aggregate nonce.
+    pub fn new(key_agg: &MuSigKeyAggContext, aggregate_nonce: &[u8; 66], message: &[u8]) -> Result<Self, MuSigError> {
+        let q = key_agg.to_x_only_public_key();
+        let b = to_scalar(&tagged_hash(
+            "MuSig/noncecoef",
+            &[&aggregate_nonce[..], &q[..], message].concat(),
+        ));
+
+        let r1 = to_point_ext(&aggregate_nonce[..33]).ok_or(MuSigError::InvalidAggregateNonce)?;
+        let r2 = to_point_ext(&aggregate_nonce[33..]).ok_or(MuSigError::InvalidAggregateNonce)?;
+        // The final nonce is the generator if the nonces cancel out, as no signer can cause this
+        let r = match add(&[r1, r2.and_then(|r2| multiply(&r2, &b))]) {
+            Some(r) => r,
+            None => multiply_generator(&Scalar::from_int(1)).ok_or(MuSigError::InvalidAggregateNonce)?,
+        };
+        let e = to_scalar(&tagged_hash(
+            "BIP0340/challenge",
+            &[&x_only(&r)[..], &q[..], message].concat(),
+        ));
+
+        Ok(Self {
+            key_agg: key_agg.clone(),
+            b,
+            r,
+            e,
+        })
+    }
+
+    /// Returns the partial signature of the signer of the given private key, with its secret nonce (Sign).
+    pub fn sign(&self, secret_nonce: &[u8; 97], private_key: &[u8; 32]) -> Result<[u8; 32], MuSigError> {
+        let k1 = to_scalar_checked(&secret_nonce[..32])
+            .filter(|k| !k.is_zero())
+            .ok_or(MuSigError::InvalidSecretNonce)?;
+        let k2 = to_scalar_checked(&secret_nonce[32..64])
+            .filter(|k| !k.is_zero())
+            .ok_or(MuSigError::InvalidSecretNonce)?;
+        let (k1, k2) = match has_even_y(&self.r) {
+            true => (k1, k2),
+            false => (-k1, -k2),
+        };
+
+        let d = to_scalar_checked(private_key).ok_or(MuSigError::InvalidPrivateKey)?;
+        let public_key = multiply_generator(&d)
+            .ok_or(MuSigError::InvalidPrivateKey)?
+            .serialize_compressed();
+        if secret_nonce[64..] != public_key[..] {
+            return Err(MuSigError::MismatchedSecretNonce);
+        }
+        let a = self.key_agg_coefficient(&public_key)?;
+        let d = parity(&self.key_agg.point) * self.key_agg.gacc.clone() * d;
+
+        let s = k1 + self.b.clone() * k2 + self.e.clone() * a * d;
+        let partial_signature = s.b32();
+
+        // The partial signature is verified, to catch a faulty computation before it is shared
+        let mut public_nonce = [0u8; 66];
+        for (i, k) in secret_nonce[..64].chunks(32).enumerate() {
+            let point = multiply_generator(&to_scalar(&to_array(k))).ok_or(MuSigError::InvalidSecretNonce)?;
+            public_nonce[i * 33..(i + 1) * 33].copy_from_slice(&point.serialize_compressed());
+        }
+        match self.verify_partial_signature(&partial_signature, &public_nonce, &public_key) {
+            true => Ok(partial_signature),
+            false => Err(MuSigError::InvalidPartialSignature(self.signer_index(&public_key)?)),
+        }
+    }
+
+    /// Returns whether the given partial signature is valid for the public nonce and public key of its signer
+    /// (PartialSigVerify).
+    pub fn verify_partial_signature(
+        &self,
+        partial_signature: &[u8; 32],
+        public_nonce: &[u8; 66],
+        public_key: &[u8; 33],
+    ) -> bool {
+        let (s, r1, r2, point, a) = match (
+            to_scalar_checked(partial_signature),
+            to_point(&public_nonce[..33]),
+            to_point(&public_nonce[33..]),
+            to_point(public_key),
+            self.key_agg_coefficient(public_key),
+        ) {
+            (Some(s), Some(r1), Some(r2), Some(point), Ok(a)) => (s, r1, r2, point, a),
+            _ => return false,
+        };
+
+        let nonce = add(&[Some(r1), multiply(&r2, &self.b)]);
+        let nonce = match has_even_y(&self.r) {
+            true => nonce,
+            false => negate(nonce),
+        };
+        let g = parity(&self.key_agg.point) * self.key_agg.gacc.clone();
+        let point = multiply(&point, &g);
+
+        // s⋅G = R + e⋅a⋅P
+        let expected = add(&[nonce, point.and_then(|point| multiply(&point, &(self.e.clone() * a)))]);
+        to_bytes_ext(&multiply_generator(&s))[..] == to_bytes_ext(&expected)[..]
+    }
+
+    /// Returns the Schnorr signature of the aggregate of the partial signatures of the signers (PartialSigAgg).
+    pub fn aggregate(&self, partial_signatures: &[[u8; 32]]) -> Result<[u8; 64], MuSigError> {
+        let mut s = Scalar::from_int(0);
+        for (i, partial_signature) in partial_signatures.iter().enumerate() {
+            s += to_scalar_checked(partial_signature).ok_or(MuSigError::InvalidPartialSignature(i))?;
+        }
+        s += self.e.clone() * parity(&self.key_agg.point) * self.key_agg.tacc.clone();
+
+        let mut signature = [0u8; 64];
+        signature[..32].copy_from_slice(&x_only(&self.r));
+        signature[32..].copy_from_slice(&s.b32());
+        Ok(signature)
+    }
+
+    /// Returns the index of the given public key among the signers.
+    fn signer_index(&self, public_key: &[u8; 33]) -> Result<usize, MuSigError> {
+        self.key_agg
+            .public_keys
+            .iter()
+            .position(|key| key == public_key)
+            .ok_or(MuSigError::UnknownSigner)
+    }
+
+    /// Returns the key aggregation coefficient of the given public key of a signer.
+    fn key_agg_coefficient(&self, public_key: &[u8; 33]) -> Result<Scalar, MuSigError> {
+        self.signer_index(public_key)?;
+        Ok(key_agg_coefficient(&self.key_agg.public_keys, public_key))
+    }
+}
+
+/// Returns the nonces derived from the given randomness (NonceGen), with no extra input.
+fn nonce_gen_internal(
+    random: &[u8; 32],
+    public_key: &[u8; 33],
+    aggregate_public_key: Option<&[u8; 32]>,
+    message: Option<&[u8]>,
+) -> Result<([u8; 97], [u8; 66]), MuSigError> {
+    let aggregate_public_key = aggregate_public_key.map(|key| &key[..]).unwrap_or(&[]);
+
+    let mut input = random.to_vec();
+    input.push(public_key.len() as u8);
+    input.extend_from_slice(public_key);
+    input.push(aggregate_public_key.len() as u8);
+    input.extend_from_slice(aggregate_public_key);
+    match message {
+        Some(message) => {
+            input.push(1);
+            input.extend_from_slice(&(message.len() as u64).to_be_bytes());
+            input.extend_from_slice(message);
+        }
+        None => input.push(0),
+    }
+    input.extend_from_slice(&0u32.to_be_bytes());
+
+    let mut secret_nonce = [0u8; 97];
+    let mut public_nonce = [0u8; 66];
+    for i in 0..2 {
+        let k = to_scalar(&tagged_hash("MuSig/nonce", &[&input[..], &[i as u8]].concat()));
+        let point = multiply_generator(&k).ok_or(MuSigError::InvalidSecretNonce)?;
+        secret_nonce[i * 32..(i + 1) * 32].copy_from_slice(&k.b32());
+        public_nonce[i * 33..(i + 1) * 33].copy_from_slice(&point.serialize_compressed());
+    }
+    secret_nonce[64..].copy_from_slice(public_key);
+    Ok((secret_nonce, public_nonce))
+}
+
+/// Returns the key aggregation coefficient of the given public key, which is 1 for the second distinct
+/// public key of the signers.
+fn key_agg_coefficient(public_keys: &[[u8; 33]], public_key: &[u8; 33]) -> Scalar {
+    if public_keys.iter().find(|key| *key != &public_keys[0]) == Some(public_key) {
+        return Scalar::from_int(1);
+    }
+    let list = tagged_hash("KeyAgg list", &public_keys.concat());
+    to_scalar(&tagged_hash(
+        "KeyAgg coefficient",
+        &[&list[..], &public_key[..]].concat(),
+    ))
+}
+
+/// Returns the bytes of the given private key, XORed with the given mask.
+fn private_key_xor(private_key: &[u8; 32], mask: &[u8; 32]) -> [u8; 32] {
+    let mut bytes = [0u8; 32];
+    bytes
+        .iter_mut()
+        .enumerate()
+        .for_each(|(i, byte)| *byte = private_key[i] ^ mask[i]);
+    bytes
+}
+
+/// Returns the given 32 bytes as an array.
+pub(crate) fn to_array(bytes: &[u8]) -> [u8; 32] {
+    let mut array = [0u8; 32];
+    array.copy_from_slice(bytes);
+    array
+}
+
+/// Returns the integer of the given bytes, modulo the curve order.
+pub(crate) fn to_scalar(bytes: &[u8; 32]) -> Scalar {
+    let mut scalar = Scalar::default();
+    let _ = scalar.set_b32(bytes);
+    scalar
+}
+
+/// Returns the integer of the given 32 bytes, or `None` if it is not less than the curve order.
+pub(crate) fn to_scalar_checked(bytes: &[u8]) -> Option<Scalar> {
+    if bytes.len() != 32 {
+        return None;
+    }
+    let mut scalar = Scalar::default();
+    match bool::from(scalar.set_b32(&to_array(bytes))) {
+        true => None,
+        false => Some(scalar),
+    }
+}
+
+/// Returns 1 if the given point has an even y coordinate, and -1 otherwise.
+pub(crate) fn parity(point: &PublicKey) -> Scalar {
+    match has_even_y(point) {
+        true => Scalar::from_int(1),
+        false => -Scalar::from_int(1),
+    }
+}
+
+/// Returns whether the given point has an even y coordinate.
+pub(crate) fn has_even_y(point: &PublicKey) -> bool {
+    point.serialize_compressed()[0] == 0x02
+}
+
+/// Returns the x coordinate of the given point.
+pub(crate) fn x_only(point: &PublicKey) -> [u8; 32] {
+    to_array(&point.serialize_compressed()[1..])
+}
+
+/// Returns the point of the given compressed public key, or `None` if it is invalid.
+pub(crate) fn to_point(bytes: &[u8]) -> Option<PublicKey> {
+    let mut compressed = [0u8; 33];
+    match bytes.len() {
+        33 => compressed.copy_from_slice(bytes),
+        _ => return None,
+    };
+    PublicKey::parse_compressed(&compressed).ok()
+}
+
+/// Returns the point of the given compressed public key, with 33 zero bytes as the point at infinity (`None`),
+/// or `None` if it is invalid.
+fn to_point_ext(bytes: &[u8]) -> Option<Option<PublicKey>> {
+    match bytes.iter().all(|byte| *byte == 0) {
+        true => Some(None),
+        false => to_point(bytes).map(Some),
+    }
+}
+
+/// Returns the compressed bytes of the given point, with 33 zero bytes as the point at infinity (`None`).
+fn to_bytes_ext(point: &Option<PublicKey>) -> [u8; 33] {
+    match point {
+        Some(point) => point.serialize_compressed(),
+        None => [0u8; 33],
+    }
+}
+
+/// Returns the sum of the given points, with `None` as the point at infinity.
+pub(crate) fn add(points: &[Option<PublicKey>]) -> Option<PublicKey> {
+    PublicKey::combine(&points.iter().flatten().cloned().collect::<Vec<PublicKey>>()).ok()
+}
+
+/// Returns the negation of the given point, with `None` as the point at infinity.
+pub(crate) fn negate(point: Option<PublicKey>) -> Option<PublicKey> {
+    let mut compressed = point?.serialize_compressed();
+    compressed[0] ^= 0x01;
+    PublicKey::parse_compressed(&compressed).ok()
+}
+
+/// Returns the given scalar multiple of the point, with `None` as the point at infinity.
+pub(crate) fn multiply(point: &PublicKey, scalar: &Scalar) -> Option<PublicKey> {
+    let scalar = SecretKey::try_from(scalar.clone()).ok()?;
+    let mut point = point.clone();
+    point.tweak_mul_assign(&scalar).ok()?;
+    Some(point)
+}
+
+/// Returns the given scalar multiple of the generator, with `None` as the point at infinity.
+pub(crate) fn multiply_generator(scalar: &Scalar) -> Option<PublicKey> {
+    let scalar = SecretKey::try_from(scalar.clone()).ok()?;
+    Some(PublicKey::from_secret_key(&scalar))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::Mainnet;
+    use rand::SeedableRng;
+    use rand_xorshift::XorShiftRng;
+
+    fn to_public_key(public_key: &str) -> [u8; 33] {
+        let mut bytes = [0u8; 33];
+        bytes.copy_from_slice(&hex::decode(public_key).unwrap());
+        bytes
+    }
+
+    // https://github.com/bitcoin/bips/blob/master/bip-0327/vectors/key_agg_vectors.json
+    const PUBLIC_KEYS: [&str; 3] = [
+        "02F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9",
+        "03DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+        "023590A94E768F8E1815C2F24B4D80A8E3149316C3518CE7B7AD338368D038CA66",
+    ];
+
+    #[test]
+    fn key_agg() {
+        let vectors: [(&[usize], &str); 4] = [
+            (
+                &[0, 1, 2],
+                "90539EEDE565F5D054F32CC0C220126889ED1E5D193BAF15AEF344FE59D4610C",
+            ),
+            (
+                &[2, 1, 0],
+                "6204DE8B083426DC6EAF9502D27024D53FC826BF7D2012148A0575435DF54B2B",
+            ),
+            (
+                &[0, 0, 0],
+                "B436E3BAD62B8CD409969A224731C193D051162D8C5AE8B109306127DA3AA935",
+            ),
+            (
+                &[0, 0, 1, 1],
+                "69BC22BFA5D106306E48A20679DE1D7389386124D07571D0D872686028C26A3E",
+            ),
+        ];
+        for (indices, expected) in vectors.iter() {
+            let public_keys = indices
+                .iter()
+                .map(|i| to_public_key(PUBLIC_KEYS[*i]))
+                .collect::<Vec<[u8; 33]>>();
+            let key_agg = MuSigKeyAggContext::new(&public_keys).unwrap();
+            assert_eq!(expected.to_lowercase(), hex::encode(key_agg.to_x_only_public_key()));
+        }
+    }
+
+    #[test]
+    fn key_agg_invalid() {
+        let public_keys = [
+            to_public_key(PUBLIC_KEYS[0]),
+            to_public_key("020000000000000000000000000000000000000000000000000000000000000005"),
+        ];
+        match MuSigKeyAggContext::new(&public_keys) {
+            Err(MuSigError::InvalidPublicKey(1)) => {}
+            result => panic!("unexpected result {:?}", result),
+        }
+    }
+
+    #[test]
+    fn key_sort_order() {
+        let public_keys = PUBLIC_KEYS
+            .iter()
+            .map(|key| to_public_key(key))
+            .collect::<Vec<[u8; 33]>>();
+        let reversed = public_keys.iter().rev().cloned().collect::<Vec<[u8; 33]>>();
+        assert_eq!(key_sort(&public_keys), key_sort(&reversed));
+    }
+
+    #[test]
+    fn verify_schnorr_vector() {
+        // https://github.com/bitcoin/bips/blob/master/bip-0340/test-vectors.csv
+        let mut public_key = [0u8; 32];
+        public_key
+            .copy_from_slice(&hex::decode("F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9").unwrap());
+        let mut signature = [0u8; 64];
+        signature.copy_from_slice(&hex::decode("E907831F80848D1069A5371B402410364BDF1C5F8307B0084C55F1CE2DCA821525F66A4A85EA8B71E482A74F382D2CE5EBEEE8FDB2172F477DF4900D310536C0").unwrap());
+        assert!(verify_schnorr(&public_key, &[0u8; 32], &signature));
+
+        signature[63] ^= 0x01;
+        assert!(!verify_schnorr(&public_key, &[0u8; 32], &signature));
+    }
+
+    #[test]
+    fn sign_schnorr_vectors() {
+        // https://github.com/bitcoin/bips/blob/master/bip-0340/test-vectors.csv (vectors 0 to 3)
+        let vectors = [
+            (
+                "0000000000000000000000000000000000000000000000000000000000000003",
+                "0000000000000000000000000000000000000000000000000000000000000000",
+                "0000000000000000000000000000000000000000000000000000000000000000",
+                "E907831F80848D1069A5371B402410364BDF1C5F8307B0084C55F1CE2DCA821525F66A4A85EA8B71E482A74F382D2CE5EBEEE8FDB2172F477DF4900D310536C0",
+            ),
+            (
+                "B7E151628AED2A6ABF7158809CF4F3C762E7160F38B4DA56A784D9045190CFEF",
+                "0000000000000000000000000000000000000000000000000000000000000001",
+                "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+                "6896BD60EEAE296DB48A229FF71DFE071BDE413E6D43F917DC8DCF8C78DE33418906D11AC976ABCCB20B091292BFF4EA897EFCB639EA871CFA95F6DE339E4B0A",
+            ),
+            (
+                "C90FDAA22168C234C4C6628B80DC1CD129024E088A67CC74020BBEA63B14E5C9",
+                "C87AA53824B4D7AE2EB035A2B5BBBCCC080E76CDC6D1692C4B0B62D798E6D906",
+                "7E2D58D8B3BCDF1ABADEC7829054F90DDA9805AAB56C77333024B9D0A508B75C",
+                "5831AAEED7B44BB74E5EAB94BA9D4294C49BCF2A60728D8B4C200F50DD313C1BAB745879A5AD954A72C45A91C3A51D3C7ADEA98D82F8481E0E1E03674A6F3FB7",
+            ),
+            (
+                "0B432B2677937381AEF05BB02A66ECD012773062CF3FA2549E44F58ED2401710",
+                "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF",
+                "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF",
+                "7EB0509757E246F19449885651611CB965ECC1A187DD51B64FDA1EDC9637D5EC97582B9CB13DB3933705B32BA982AF5AF25FD78881EBB32771FC5922EFC66EA3",
+            ),
+        ];
+        for (private_key, aux_rand, message, expected) in vectors.iter() {
+            let private_key = to_array(&hex::decode(private_key).unwrap());
+            let aux_rand = to_array(&hex::decode(aux_rand).unwrap());
+            let message = hex::decode(message).unwrap();
+            let signature = sign_schnorr(&private_key, &message, &aux_rand).unwrap();
+            assert_eq!(expected.to_lowercase(), hex::encode(&signature[..]));
+
+            let public_key = x_only(&multiply_generator(&to_scalar(&private_key)).unwrap());
+            assert!(verify_schnorr(&public_key, &message, &signature));
+        }
+        assert!(sign_schnorr(&[0u8; 32], &[0u8; 32], &[0u8; 32]).is_err());
+    }
+
+    #[test]
+    fn sign_and_aggregate() {
+        let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+        let message = [0x42u8; 32];
+
+        for signers in 2..5 {
+            let private_keys = (0..signers)
+                .map(|_| SecretKey::random(rng).serialize())
+                .collect::<Vec<[u8; 32]>>();
+            let public_keys = private_keys
+                .iter()
+                .map(|key| PublicKey::from_secret_key(&SecretKey::parse(key).unwrap()).serialize_compressed())
+                .collect::<Vec<[u8; 33]>>();
+            let mut key_agg = MuSigKeyAggContext::new(&key_sort(&public_keys)).unwrap();
+            let address = key_agg.to_address::<Mainnet>().unwrap();
+            key_agg.apply_taproot_tweak().unwrap();
+
+            let nonces = private_keys
+                .iter()
+                .zip(&public_keys)
+                .map(|(private_key, public_key)| {
+                    nonce_gen(rng, Some(private_key), public_key, None, Some(&message)).unwrap()
+                })
+                .collect::<Vec<([u8; 97], [u8; 66])>>();
+            let public_nonces = nonces.iter().map(|nonce| nonce.1).collect::<Vec<[u8; 66]>>();
+            let session = MuSigSession::new(&key_agg, &nonce_agg(&public_nonces).unwrap(), &message).unwrap();
+
+            let partial_signatures = private_keys
+                .iter()
+                .zip(&nonces)
+                .map(|(private_key, nonce)| session.sign(&nonce.0, private_key).unwrap())
+                .collect::<Vec<[u8; 32]>>();
+            for i in 0..signers {
+                assert!(session.verify_partial_signature(&partial_signatures[i], &public_nonces[i], &public_keys[i]));
+                let other = (i + 1) % signers;
+                assert!(!session.verify_partial_signature(
+                    &partial_signatures[i],
+                    &public_nonces[other],
+                    &public_keys[other]
+                ));
+            }
+
+            // The signature is valid for the output key of the P2TR address
+            let signature = session.aggregate(&partial_signatures).unwrap();
+            let output_key = key_agg.to_x_only_public_key();
+            assert!(verify_schnorr(&output_key, &message, &signature));
+            assert!(address.to_string().starts_with("bc1p"));
+            assert!(!verify_schnorr(&output_key, &[0x24u8; 32], &signature));
+        }
+    }
+
+    #[test]
+    fn sign_invalid() {
+        let rng = &mut XorShiftRng::seed_from_u64(1231275789u64);
+        let private_keys = [SecretKey::random(rng).serialize(), SecretKey::random(rng).serialize()];
+        let public_keys = private_keys
+            .iter()
+            .map(|key| PublicKey::from_secret_key(&SecretKey::parse(key).unwrap()).serialize_compressed())
+            .collect::<Vec<[u8; 33]>>();
+        let key_agg = MuSigKeyAggContext::new(&public_keys).unwrap();
+
+        let (secret_nonce, public_nonce) = nonce_gen(rng, None, &public_keys[0], None, None).unwrap();
+        let (_, other_nonce) = nonce_gen(rng, None, &public_keys[1], None, None).unwrap();
+        let session = MuSigSession::new(&key_agg, &nonce_agg(&[public_nonce, other_nonce]).unwrap(), &[]).unwrap();
+
+        match session.sign(&secret_nonce, &private_keys[1]) {
+            Err(MuSigError::MismatchedSecretNonce) => {}
+            result => panic!("unexpected result {:?}", result),
+        }
+        let stranger = SecretKey::random(rng).serialize();
+        let mut stranger_nonce = secret_nonce;
+        stranger_nonce[64..]
+            .copy_from_slice(&PublicKey::from_secret_key(&SecretKey::parse(&stranger).unwrap()).serialize_compressed());
+        match session.sign(&stranger_nonce, &stranger) {
+            Err(MuSigError::UnknownSigner) => {}
+            result => panic!("unexpected result {:?}", result),
+        }
+        match nonce_agg(&[public_nonce, [0u8; 66]]) {
+            Err(MuSigError::InvalidPublicNonce(1)) => {}
+            result => panic!("unexpected result {:?}", result),
+        }
+    }
+}
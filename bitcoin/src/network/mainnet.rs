@@ -25,6 +25,7 @@ impl BitcoinNetwork for Mainnet {
             BitcoinFormat::P2WSH => vec![0x00],
             BitcoinFormat::P2SH_P2WPKH => vec![0x05],
             BitcoinFormat::Bech32 => vec![0x62, 0x63],
+            BitcoinFormat::P2TR => vec![0x62, 0x63],
         }
     }
 
@@ -55,6 +56,8 @@ impl BitcoinNetwork for Mainnet {
         match format {
             BitcoinFormat::P2PKH => Ok(vec![0x04, 0x88, 0xAD, 0xE4]), // xprv
             BitcoinFormat::P2SH_P2WPKH => Ok(vec![0x04, 0x9D, 0x78, 0x78]), // yprv
+            BitcoinFormat::Bech32 => Ok(vec![0x04, 0xB2, 0x43, 0x0C]), // zprv
+            BitcoinFormat::P2TR => Ok(vec![0x04, 0x88, 0xAD, 0xE4]),  // BIP-86 uses the BIP-32 version bytes
             _ => Err(ExtendedPrivateKeyError::UnsupportedFormat(format.to_string())),
         }
     }
@@ -63,7 +66,7 @@ impl BitcoinNetwork for Mainnet {
     /// https://github.com/satoshilabs/slips/blob/master/slip-0132.md
     fn from_extended_private_key_version_bytes(prefix: &[u8]) -> Result<Self, ExtendedPrivateKeyError> {
         match prefix[0..4] {
-            [0x04, 0x88, 0xAD, 0xE4] | [0x04, 0x9D, 0x78, 0x78] => Ok(Self),
+            [0x04, 0x88, 0xAD, 0xE4] | [0x04, 0x9D, 0x78, 0x78] | [0x04, 0xB2, 0x43, 0x0C] => Ok(Self),
             _ => Err(ExtendedPrivateKeyError::InvalidVersionBytes(prefix.to_vec())),
         }
     }
@@ -74,6 +77,8 @@ impl BitcoinNetwork for Mainnet {
         match format {
             BitcoinFormat::P2PKH => Ok(vec![0x04, 0x88, 0xB2, 0x1E]), // xpub
             BitcoinFormat::P2SH_P2WPKH => Ok(vec![0x04, 0x9D, 0x7C, 0xB2]), // ypub
+            BitcoinFormat::Bech32 => Ok(vec![0x04, 0xB2, 0x47, 0x46]), // zpub
+            BitcoinFormat::P2TR => Ok(vec![0x04, 0x88, 0xB2, 0x1E]),  // BIP-86 uses the BIP-32 version bytes
             _ => Err(ExtendedPublicKeyError::UnsupportedFormat(format.to_string())),
         }
     }
@@ -82,7 +87,7 @@ impl BitcoinNetwork for Mainnet {
     /// https://github.com/satoshilabs/slips/blob/master/slip-0132.md
     fn from_extended_public_key_version_bytes(prefix: &[u8]) -> Result<Self, ExtendedPublicKeyError> {
         match prefix[0..4] {
-            [0x04, 0x88, 0xB2, 0x1E] | [0x04, 0x9D, 0x7C, 0xB2] => Ok(Self),
+            [0x04, 0x88, 0xB2, 0x1E] | [0x04, 0x9D, 0x7C, 0xB2] | [0x04, 0xB2, 0x47, 0x46] => Ok(Self),
             _ => Err(ExtendedPublicKeyError::InvalidVersionBytes(prefix.to_vec())),
         }
     }
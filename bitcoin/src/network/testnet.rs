@@ -25,6 +25,7 @@ impl BitcoinNetwork for Testnet {
             BitcoinFormat::P2WSH => vec![0x00],
             BitcoinFormat::P2SH_P2WPKH => vec![0xC4],
             BitcoinFormat::Bech32 => vec![0x74, 0x62],
+            BitcoinFormat::P2TR => vec![0x74, 0x62],
         }
     }
 
@@ -55,6 +56,8 @@ impl BitcoinNetwork for Testnet {
         match format {
             BitcoinFormat::P2PKH => Ok(vec![0x04, 0x35, 0x83, 0x94]), // tpriv
             BitcoinFormat::P2SH_P2WPKH => Ok(vec![0x04, 0x4A, 0x4E, 0x28]), // upriv
+            BitcoinFormat::Bech32 => Ok(vec![0x04, 0x5F, 0x18, 0xBC]), // vprv
+            BitcoinFormat::P2TR => Ok(vec![0x04, 0x35, 0x83, 0x94]),  // BIP-86 uses the BIP-32 version bytes
             _ => Err(ExtendedPrivateKeyError::UnsupportedFormat(format.to_string())),
         }
     }
@@ -63,7 +66,7 @@ impl BitcoinNetwork for Testnet {
     /// https://github.com/satoshilabs/slips/blob/master/slip-0132.md
     fn from_extended_private_key_version_bytes(prefix: &[u8]) -> Result<Self, ExtendedPrivateKeyError> {
         match prefix[0..4] {
-            [0x04, 0x35, 0x83, 0x94] | [0x04, 0x4A, 0x4E, 0x28] => Ok(Self),
+            [0x04, 0x35, 0x83, 0x94] | [0x04, 0x4A, 0x4E, 0x28] | [0x04, 0x5F, 0x18, 0xBC] => Ok(Self),
             _ => Err(ExtendedPrivateKeyError::InvalidVersionBytes(prefix.to_vec())),
         }
     }
@@ -74,6 +77,8 @@ impl BitcoinNetwork for Testnet {
         match format {
             BitcoinFormat::P2PKH => Ok(vec![0x04, 0x35, 0x87, 0xCF]), // tpub
             BitcoinFormat::P2SH_P2WPKH => Ok(vec![0x04, 0x4A, 0x52, 0x62]), // upub
+            BitcoinFormat::Bech32 => Ok(vec![0x04, 0x5F, 0x1C, 0xF6]), // vpub
+            BitcoinFormat::P2TR => Ok(vec![0x04, 0x35, 0x87, 0xCF]),  // BIP-86 uses the BIP-32 version bytes
             _ => Err(ExtendedPublicKeyError::UnsupportedFormat(format.to_string())),
         }
     }
@@ -82,7 +87,7 @@ impl BitcoinNetwork for Testnet {
     /// https://github.com/satoshilabs/slips/blob/master/slip-0132.md
     fn from_extended_public_key_version_bytes(prefix: &[u8]) -> Result<Self, ExtendedPublicKeyError> {
         match prefix[0..4] {
-            [0x04, 0x35, 0x87, 0xCF] | [0x04, 0x4A, 0x52, 0x62] => Ok(Self),
+            [0x04, 0x35, 0x87, 0xCF] | [0x04, 0x4A, 0x52, 0x62] | [0x04, 0x5F, 0x1C, 0xF6] => Ok(Self),
             _ => Err(ExtendedPublicKeyError::InvalidVersionBytes(prefix.to_vec())),
         }
     }
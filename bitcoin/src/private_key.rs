@@ -2,7 +2,10 @@ use crate::address::BitcoinAddress;
 use crate::format::BitcoinFormat;
 use crate::network::BitcoinNetwork;
 use crate::public_key::BitcoinPublicKey;
-use wagyu_model::{crypto::checksum, Address, AddressError, PrivateKey, PrivateKeyError, PublicKey};
+use wagyu_model::{
+    crypto::{checksum, constant_time_eq},
+    Address, AddressError, PrivateKey, PrivateKeyError, PublicKey,
+};
 
 use base58::{FromBase58, ToBase58};
 use core::{fmt, fmt::Display, marker::PhantomData, str::FromStr};
@@ -10,7 +13,7 @@ use rand::Rng;
 use secp256k1;
 
 /// Represents a Bitcoin private key
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Eq)]
 pub struct BitcoinPrivateKey<N: BitcoinNetwork> {
     /// The ECDSA private key
     secret_key: secp256k1::SecretKey,
@@ -96,6 +99,14 @@ impl<N: BitcoinNetwork> FromStr for BitcoinPrivateKey<N> {
     }
 }
 
+impl<N: BitcoinNetwork> PartialEq for BitcoinPrivateKey<N> {
+    /// Returns `true` if the private keys are equal, comparing their secret keys in constant time.
+    fn eq(&self, other: &Self) -> bool {
+        constant_time_eq(&self.secret_key.serialize(), &other.secret_key.serialize())
+            && self.compressed == other.compressed
+    }
+}
+
 impl<N: BitcoinNetwork> Display for BitcoinPrivateKey<N> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let mut wif = [0u8; 38];
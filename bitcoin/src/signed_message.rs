@@ -0,0 +1,640 @@
+//!
+//! SignedMessage
+//!
+//! This module contains the BIP-322 generic signed message format for Bitcoin.
+//! https://github.com/bitcoin/bips/blob/master/bip-0322.mediawiki
+//!
+//! A message is signed by proving that the signer could spend a virtual output:
+//! - `to_spend` is an unsignable transaction that commits to the tagged hash of the message
+//!   in its input script and pays 0 satoshis to the script public key of the signing address.
+//! - `to_sign` spends output 0 of `to_spend` with a single OP_RETURN output, and is signed
+//!   exactly as a transaction spending the address would be.
+//!
+//! The "simple" proof is the consensus-encoded witness stack of the `to_sign` input,
+//! and is available for native SegWit (P2WPKH) and Taproot (P2TR) addresses.
+//! The "full" proof is the entire signed `to_sign` transaction, and is available for
+//! P2PKH, P2SH-P2WPKH, P2WPKH, and P2TR addresses.
+//!
+//! A P2TR address is signed by its key path with no script tree (BIP-86), with a BIP-340 Schnorr
+//! signature of the BIP-341 signature hash. Proofs are signed with SIGHASH_DEFAULT and no auxiliary
+//! randomness, and verified with SIGHASH_DEFAULT or SIGHASH_ALL.
+//!
+
+use crate::address::BitcoinAddress;
+use crate::amount::BitcoinAmount;
+use crate::format::BitcoinFormat;
+use crate::musig::{sign_schnorr, to_array, verify_schnorr, MuSigError};
+use crate::network::BitcoinNetwork;
+use crate::private_key::BitcoinPrivateKey;
+use crate::public_key::BitcoinPublicKey;
+use crate::taproot::{tagged_hash, tweak_private_key};
+use crate::transaction::{
+    create_script_pub_key, read_variable_length_integer, variable_length_integer, BitcoinTransaction,
+    BitcoinTransactionInput, BitcoinTransactionOutput, BitcoinTransactionParameters, SignatureHash,
+};
+use wagyu_model::crypto::hash160;
+use wagyu_model::no_std::*;
+use wagyu_model::{AddressError, AmountError, PrivateKey, PublicKey, Transaction, TransactionError};
+
+use core::str::FromStr;
+use secp256k1;
+use sha2::{Digest, Sha256};
+
+/// The tag of the BIP-340 tagged hash used to commit to the message
+const MESSAGE_TAG: &[u8] = b"BIP0322-signed-message";
+
+/// The OP_RETURN opcode used as the sole output script of `to_sign`
+const OP_RETURN: u8 = 0x6a;
+
+#[derive(Debug, Fail)]
+pub enum SignedMessageError {
+    #[fail(display = "{}: {}", _0, _1)]
+    Crate(&'static str, String),
+
+    #[fail(display = "invalid signed message proof: {}", _0)]
+    InvalidProof(String),
+
+    #[fail(display = "{}", _0)]
+    TransactionError(TransactionError),
+
+    #[fail(display = "unsupported signed message format: {}", _0)]
+    UnsupportedFormat(String),
+}
+
+impl From<AddressError> for SignedMessageError {
+    fn from(error: AddressError) -> Self {
+        SignedMessageError::TransactionError(TransactionError::AddressError(error))
+    }
+}
+
+impl From<AmountError> for SignedMessageError {
+    fn from(error: AmountError) -> Self {
+        SignedMessageError::TransactionError(TransactionError::AmountError(error))
+    }
+}
+
+impl From<hex::FromHexError> for SignedMessageError {
+    fn from(error: hex::FromHexError) -> Self {
+        SignedMessageError::Crate("hex", format!("{:?}", error))
+    }
+}
+
+impl From<MuSigError> for SignedMessageError {
+    fn from(error: MuSigError) -> Self {
+        SignedMessageError::Crate("musig", format!("{:?}", error))
+    }
+}
+
+impl From<secp256k1::Error> for SignedMessageError {
+    fn from(error: secp256k1::Error) -> Self {
+        SignedMessageError::Crate("libsecp256k1", format!("{:?}", error))
+    }
+}
+
+impl From<TransactionError> for SignedMessageError {
+    fn from(error: TransactionError) -> Self {
+        SignedMessageError::TransactionError(error)
+    }
+}
+
+/// Returns the BIP-322 message hash, `SHA256(SHA256(tag) || SHA256(tag) || message)`,
+/// with the tag `BIP0322-signed-message`.
+pub fn signed_message_hash(message: &[u8]) -> [u8; 32] {
+    let tag = Sha256::digest(MESSAGE_TAG);
+
+    let mut hash = [0u8; 32];
+    hash.copy_from_slice(&Sha256::digest(&[&tag[..], &tag[..], message].concat()));
+    hash
+}
+
+/// Represents a BIP-322 signed message for a Bitcoin address
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BitcoinSignedMessage<N: BitcoinNetwork> {
+    /// The address of the signer
+    pub address: BitcoinAddress<N>,
+    /// The message to be signed
+    pub message: Vec<u8>,
+}
+
+impl<N: BitcoinNetwork> BitcoinSignedMessage<N> {
+    /// Returns a new signed message for the given address and message.
+    pub fn new(address: &BitcoinAddress<N>, message: &[u8]) -> Result<Self, SignedMessageError> {
+        match address.format() {
+            BitcoinFormat::P2PKH | BitcoinFormat::P2SH_P2WPKH | BitcoinFormat::Bech32 | BitcoinFormat::P2TR => {
+                Ok(Self {
+                    address: address.clone(),
+                    message: message.to_vec(),
+                })
+            }
+            format => Err(SignedMessageError::UnsupportedFormat(format.to_string())),
+        }
+    }
+
+    /// Returns the virtual `to_spend` transaction committing to the message.
+    pub fn to_spend(&self) -> Result<BitcoinTransaction<N>, SignedMessageError> {
+        // The input script is `OP_0 PUSH32[message_hash]`
+        let mut script_sig = vec![0x00, 0x20];
+        script_sig.extend(&signed_message_hash(&self.message));
+
+        let mut input = BitcoinTransactionInput::<N>::new(
+            vec![0u8; 32],
+            0xffffffff,
+            None,
+            None,
+            None,
+            None,
+            Some(vec![0u8; 4]),
+            SignatureHash::SIGHASH_ALL,
+        )?;
+        input.script_sig = script_sig;
+        input.is_signed = true;
+
+        let output = BitcoinTransactionOutput {
+            amount: BitcoinAmount::from_satoshi(0)?,
+            script_pub_key: create_script_pub_key::<N>(&self.address)?,
+        };
+
+        Ok(BitcoinTransaction::<N>::new(&BitcoinTransactionParameters::<N> {
+            version: 0,
+            inputs: vec![input],
+            outputs: vec![output],
+            lock_time: 0,
+            segwit_flag: false,
+        })?)
+    }
+
+    /// Returns the unsigned virtual `to_sign` transaction spending `to_spend`,
+    /// given the public key of the signer.
+    pub fn to_sign(&self, public_key: &BitcoinPublicKey<N>) -> Result<BitcoinTransaction<N>, SignedMessageError> {
+        let redeem_script = match self.address.format() {
+            BitcoinFormat::P2SH_P2WPKH => {
+                let mut redeem_script = vec![0x00, 0x14];
+                redeem_script.extend(&hash160(&public_key.to_secp256k1_public_key().serialize_compressed()));
+                Some(redeem_script)
+            }
+            _ => None,
+        };
+        self.to_sign_with_redeem_script(redeem_script)
+    }
+
+    /// Returns the unsigned virtual `to_sign` transaction spending `to_spend`,
+    /// given the redeem script of a P2SH-P2WPKH address.
+    fn to_sign_with_redeem_script(
+        &self,
+        redeem_script: Option<Vec<u8>>,
+    ) -> Result<BitcoinTransaction<N>, SignedMessageError> {
+        let to_spend_id = hex::decode(self.to_spend()?.to_transaction_id()?.to_string())?;
+
+        let input = match self.address.format() {
+            // The outpoint of a P2TR input is set directly, as it is rejected by the outpoints of signable inputs
+            BitcoinFormat::P2TR => {
+                let mut input = BitcoinTransactionInput::<N>::new(
+                    to_spend_id,
+                    0,
+                    None,
+                    None,
+                    None,
+                    None,
+                    Some(vec![0u8; 4]),
+                    SignatureHash::SIGHASH_ALL,
+                )?;
+                input.outpoint.address = Some(self.address.clone());
+                input.outpoint.amount = Some(BitcoinAmount::from_satoshi(0)?);
+                input.outpoint.script_pub_key = Some(create_script_pub_key::<N>(&self.address)?);
+                input
+            }
+            _ => BitcoinTransactionInput::<N>::new(
+                to_spend_id,
+                0,
+                Some(self.address.clone()),
+                Some(BitcoinAmount::from_satoshi(0)?),
+                redeem_script,
+                None,
+                Some(vec![0u8; 4]),
+                SignatureHash::SIGHASH_ALL,
+            )?,
+        };
+
+        let output = BitcoinTransactionOutput {
+            amount: BitcoinAmount::from_satoshi(0)?,
+            script_pub_key: vec![OP_RETURN],
+        };
+
+        Ok(BitcoinTransaction::<N>::new(&BitcoinTransactionParameters::<N> {
+            version: 0,
+            inputs: vec![input],
+            outputs: vec![output],
+            lock_time: 0,
+            segwit_flag: false,
+        })?)
+    }
+
+    /// Returns the "full" proof, the serialized signed `to_sign` transaction.
+    pub fn sign_full(&self, private_key: &BitcoinPrivateKey<N>) -> Result<Vec<u8>, SignedMessageError> {
+        if private_key.to_address(&self.address.format())? != self.address {
+            return Err(SignedMessageError::InvalidProof(
+                "private key does not correspond to the address".into(),
+            ));
+        }
+
+        let to_sign = self.to_sign(&private_key.to_public_key())?;
+        let to_sign = match self.address.format() {
+            BitcoinFormat::P2TR => self.sign_taproot(&to_sign, private_key)?,
+            _ => to_sign.sign(private_key)?,
+        };
+        Ok(to_sign.to_transaction_bytes()?)
+    }
+
+    /// Returns `to_sign` with its input signed by the key path of the P2TR address,
+    /// with a SIGHASH_DEFAULT signature.
+    fn sign_taproot(
+        &self,
+        to_sign: &BitcoinTransaction<N>,
+        private_key: &BitcoinPrivateKey<N>,
+    ) -> Result<BitcoinTransaction<N>, SignedMessageError> {
+        let sighash = tagged_hash("TapSighash", &to_sign.taproot_hash_preimage(0, None)?);
+        let tweaked_key = tweak_private_key(&private_key.to_secp256k1_secret_key(), None)?;
+        let signature = sign_schnorr(&tweaked_key.serialize(), &sighash, &[0u8; 32])?;
+
+        let mut parameters = to_sign.parameters().clone();
+        parameters.inputs[0].witnesses = vec![[&[signature.len() as u8][..], &signature[..]].concat()];
+        parameters.inputs[0].is_signed = true;
+        parameters.segwit_flag = true;
+        Ok(BitcoinTransaction::<N>::new(&parameters)?)
+    }
+
+    /// Returns the "simple" proof, the consensus-encoded witness stack of the signed `to_sign` input.
+    pub fn sign_simple(&self, private_key: &BitcoinPrivateKey<N>) -> Result<Vec<u8>, SignedMessageError> {
+        self.check_simple_format()?;
+
+        let to_sign = BitcoinTransaction::<N>::from_transaction_bytes(&self.sign_full(private_key)?)?;
+        let witnesses = &to_sign.parameters().inputs[0].witnesses;
+
+        let mut proof = variable_length_integer(witnesses.len() as u64)?;
+        witnesses.iter().for_each(|witness| proof.extend(witness));
+        Ok(proof)
+    }
+
+    /// Returns `true` if the given "full" proof is a valid signature of the message by the address.
+    pub fn verify_full(&self, proof: &[u8]) -> Result<bool, SignedMessageError> {
+        let transaction = BitcoinTransaction::<N>::from_transaction_bytes(&proof.to_vec())?;
+        let parameters = transaction.parameters();
+
+        if parameters.version != 0 || parameters.lock_time != 0 {
+            return Err(SignedMessageError::InvalidProof("invalid version or lock time".into()));
+        }
+        if parameters.inputs.len() != 1 || parameters.outputs.len() != 1 {
+            return Err(SignedMessageError::InvalidProof(
+                "expected one input and one output".into(),
+            ));
+        }
+        let (input, output) = (&parameters.inputs[0], &parameters.outputs[0]);
+        if output.amount.0 != 0 || output.script_pub_key != vec![OP_RETURN] {
+            return Err(SignedMessageError::InvalidProof(
+                "expected a single OP_RETURN output".into(),
+            ));
+        }
+
+        let witnesses = input
+            .witnesses
+            .iter()
+            .map(|witness| read_pushes(witness))
+            .collect::<Result<Vec<_>, _>>()?
+            .concat();
+
+        // The witness stack of a P2TR input is the signature alone
+        if self.address.format() == BitcoinFormat::P2TR {
+            let to_sign = self.to_sign_with_redeem_script(None)?;
+            check_to_sign_input(input, &to_sign)?;
+            return match witnesses.len() {
+                1 => self.verify_taproot_signature(&to_sign, &witnesses[0]),
+                _ => Err(SignedMessageError::InvalidProof("invalid witness stack".into())),
+            };
+        }
+
+        let (signature, public_key) = match self.address.format() {
+            BitcoinFormat::P2PKH => {
+                // The input script is `PUSH[signature] PUSH[public_key]`
+                let script_sig = &input.script_sig;
+                let elements = read_pushes(script_sig)?;
+                match elements.len() {
+                    2 => (elements[0].clone(), elements[1].clone()),
+                    _ => return Err(SignedMessageError::InvalidProof("invalid input script".into())),
+                }
+            }
+            _ => match witnesses.len() {
+                2 => (witnesses[0].clone(), witnesses[1].clone()),
+                _ => return Err(SignedMessageError::InvalidProof("invalid witness stack".into())),
+            },
+        };
+
+        let public_key = to_public_key::<N>(&public_key)?;
+        let to_sign = self.to_sign(&public_key)?;
+        check_to_sign_input(input, &to_sign)?;
+
+        let expected_input = &to_sign.parameters().inputs[0];
+        if self.address.format() == BitcoinFormat::P2SH_P2WPKH {
+            let redeem_script = expected_input.outpoint.redeem_script.clone().unwrap_or_default();
+            if input.script_sig != [variable_length_integer(redeem_script.len() as u64)?, redeem_script].concat() {
+                return Err(SignedMessageError::InvalidProof("invalid input script".into()));
+            }
+        }
+
+        self.verify_signature(&to_sign, &signature, &public_key)
+    }
+
+    /// Returns `true` if the given "simple" proof is a valid signature of the message by the address.
+    pub fn verify_simple(&self, proof: &[u8]) -> Result<bool, SignedMessageError> {
+        self.check_simple_format()?;
+
+        // The witness stack is the signature and public key of a P2WPKH address, or the signature of a P2TR address
+        let expected_count = match self.address.format() {
+            BitcoinFormat::P2TR => 1,
+            _ => 2,
+        };
+        let mut reader = proof;
+        let count = read_variable_length_integer(&mut reader)?;
+        let stack = read_pushes(reader)?;
+        if count != expected_count || stack.len() != expected_count {
+            return Err(SignedMessageError::InvalidProof("invalid witness stack".into()));
+        }
+
+        if self.address.format() == BitcoinFormat::P2TR {
+            return self.verify_taproot_signature(&self.to_sign_with_redeem_script(None)?, &stack[0]);
+        }
+
+        let public_key = to_public_key::<N>(&stack[1])?;
+        let to_sign = self.to_sign(&public_key)?;
+        self.verify_signature(&to_sign, &stack[0], &public_key)
+    }
+
+    /// Returns an error if the address does not have simple proofs.
+    fn check_simple_format(&self) -> Result<(), SignedMessageError> {
+        match self.address.format() {
+            BitcoinFormat::Bech32 | BitcoinFormat::P2TR => Ok(()),
+            _ => Err(SignedMessageError::UnsupportedFormat(format!(
+                "simple proofs require a {} or {} address",
+                BitcoinFormat::Bech32,
+                BitcoinFormat::P2TR
+            ))),
+        }
+    }
+
+    /// Returns `true` if the given Schnorr signature, with an optional trailing SIGHASH_ALL byte,
+    /// signs input 0 of `to_sign` with the output key of the P2TR address.
+    fn verify_taproot_signature(
+        &self,
+        to_sign: &BitcoinTransaction<N>,
+        signature: &[u8],
+    ) -> Result<bool, SignedMessageError> {
+        let sighash = match signature.len() {
+            64 => None,
+            65 if signature[64] == SignatureHash::SIGHASH_ALL as u8 => Some(SignatureHash::SIGHASH_ALL),
+            _ => return Ok(false),
+        };
+        let message = tagged_hash("TapSighash", &to_sign.taproot_hash_preimage(0, sighash)?);
+
+        // The script public key is `OP_1 PUSH32[output_key]`
+        let output_key = to_array(&create_script_pub_key::<N>(&self.address)?[2..]);
+        let mut schnorr_signature = [0u8; 64];
+        schnorr_signature.copy_from_slice(&signature[..64]);
+        Ok(verify_schnorr(&output_key, &message, &schnorr_signature))
+    }
+
+    /// Returns `true` if the given DER signature with a trailing sighash byte signs
+    /// input 0 of `to_sign` with the given public key of the address.
+    fn verify_signature(
+        &self,
+        to_sign: &BitcoinTransaction<N>,
+        signature: &[u8],
+        public_key: &BitcoinPublicKey<N>,
+    ) -> Result<bool, SignedMessageError> {
+        match signature.split_last() {
+            Some((&sighash, _)) if sighash == SignatureHash::SIGHASH_ALL as u8 => (),
+            _ => return Ok(false),
+        };
+
+        if public_key.to_address(&self.address.format())? != self.address {
+            return Ok(false);
+        }
+
+        let preimage = match self.address.format() {
+            BitcoinFormat::P2PKH => to_sign.p2pkh_hash_preimage(0, SignatureHash::SIGHASH_ALL)?,
+            _ => to_sign.segwit_hash_preimage(0, SignatureHash::SIGHASH_ALL)?,
+        };
+        let transaction_hash = Sha256::digest(&Sha256::digest(&preimage));
+
+        let signature = secp256k1::Signature::parse_der(&signature[..signature.len() - 1])?;
+        Ok(secp256k1::verify(
+            &secp256k1::Message::parse_slice(&transaction_hash)?,
+            &signature,
+            &public_key.to_secp256k1_public_key(),
+        ))
+    }
+}
+
+/// Returns an error if the given input of a proof does not spend `to_spend` as the input of `to_sign`.
+fn check_to_sign_input<N: BitcoinNetwork>(
+    input: &BitcoinTransactionInput<N>,
+    to_sign: &BitcoinTransaction<N>,
+) -> Result<(), SignedMessageError> {
+    let expected_input = &to_sign.parameters().inputs[0];
+    match input.outpoint.reverse_transaction_id == expected_input.outpoint.reverse_transaction_id
+        && input.outpoint.index == expected_input.outpoint.index
+        && input.sequence == expected_input.sequence
+    {
+        true => Ok(()),
+        false => Err(SignedMessageError::InvalidProof("input does not spend to_spend".into())),
+    }
+}
+
+/// Returns the elements of a sequence of length-prefixed data pushes.
+fn read_pushes(mut reader: &[u8]) -> Result<Vec<Vec<u8>>, SignedMessageError> {
+    let mut elements = vec![];
+    while !reader.is_empty() {
+        let length = read_variable_length_integer(&mut reader)?;
+        if length > reader.len() {
+            return Err(SignedMessageError::InvalidProof("truncated data push".into()));
+        }
+        let (element, remainder) = reader.split_at(length);
+        elements.push(element.to_vec());
+        reader = remainder;
+    }
+    Ok(elements)
+}
+
+/// Returns the Bitcoin public key of the given serialized secp256k1 public key.
+fn to_public_key<N: BitcoinNetwork>(public_key: &[u8]) -> Result<BitcoinPublicKey<N>, SignedMessageError> {
+    let compressed = public_key.len() == 33;
+    let public_key = secp256k1::PublicKey::parse_slice(public_key, None)?;
+    Ok(BitcoinPublicKey::<N>::from_secp256k1_public_key(public_key, compressed))
+}
+
+impl<N: BitcoinNetwork> FromStr for BitcoinSignedMessage<N> {
+    type Err = SignedMessageError;
+
+    /// Returns a signed message for the given `address:message` string.
+    fn from_str(signed_message: &str) -> Result<Self, Self::Err> {
+        let mut parts = signed_message.splitn(2, ':');
+        match (parts.next(), parts.next()) {
+            (Some(address), Some(message)) => Self::new(&BitcoinAddress::<N>::from_str(address)?, message.as_bytes()),
+            _ => Err(SignedMessageError::InvalidProof("expected `address:message`".into())),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Mainnet;
+
+    type N = Mainnet;
+
+    // https://github.com/bitcoin/bips/blob/master/bip-0322.mediawiki#test-vectors
+    const PRIVATE_KEY: &str = "L3VFeEujGtevx9w18HD1fhRbCH67Az2dpCymeRE1SoPK6XQtaN2k";
+    const ADDRESS: &str = "bc1q9vza2e8x573nczrlzms0wvx3gsqjx7vavgkx0l";
+    const TAPROOT_ADDRESS: &str = "bc1ppv609nr0vr25u07u95waq5lucwfm6tde4nydujnu8npg4q75mr5sxq8lt3";
+
+    fn signed_message(address: &str, message: &str) -> BitcoinSignedMessage<N> {
+        BitcoinSignedMessage::<N>::new(&BitcoinAddress::<N>::from_str(address).unwrap(), message.as_bytes()).unwrap()
+    }
+
+    #[test]
+    fn message_hash() {
+        assert_eq!(
+            "c90c269c4f8fcbe6880f72a721ddfbf1914268a794cbb21cfafee13770ae19f1",
+            hex::encode(signed_message_hash(b""))
+        );
+        assert_eq!(
+            "f0eb03b1a75ac6d9847f55c624a99169b5dccba2a31f5b23bea77ba270de0a7a",
+            hex::encode(signed_message_hash(b"Hello World"))
+        );
+    }
+
+    #[test]
+    fn virtual_transactions() {
+        let private_key = BitcoinPrivateKey::<N>::from_str(PRIVATE_KEY).unwrap();
+        let public_key = private_key.to_public_key();
+
+        let vectors = [
+            (
+                "",
+                "c5680aa69bb8d860bf82d4e9cd3504b55dde018de765a91bb566283c545a99a7",
+                "1e9654e951a5ba44c8604c4de6c67fd78a27e81dcadcfe1edf638ba3aaebaed6",
+            ),
+            (
+                "Hello World",
+                "b79d196740ad5217771c1098fc4a4b51e0535c32236c71f1ea4d61a2d603352b",
+                "88737ae86f2077145f93cc4b153ae9a1cb8d56afa511988c149c5c8c9d93bddf",
+            ),
+        ];
+
+        for (message, to_spend_id, to_sign_id) in vectors.iter() {
+            let signed_message = signed_message(ADDRESS, message);
+            let to_spend = signed_message.to_spend().unwrap();
+            let to_sign = signed_message.to_sign(&public_key).unwrap();
+            assert_eq!(*to_spend_id, to_spend.to_transaction_id().unwrap().to_string());
+            assert_eq!(*to_sign_id, to_sign.to_transaction_id().unwrap().to_string());
+        }
+    }
+
+    #[test]
+    fn verify_simple() {
+        let vectors = [
+            ("", "024730440220336801010aaf657d79662cac98a990a43ac6f376af2c84f8f76401ccb9d0231602201693a4e683db4a91944ca5cb11527840366daf583a2c695fccf8e93483b52e34012102c7f12003196442943d8588e01aee840423cc54fc1521526a3b85c2b0cbd58872"),
+            ("Hello World", "0247304402206517c8637a7bfc3a154edcba6196d64bbd5b73955cb7da7d1626bcdde466c364022022bf10d19fc0bb69b4596e306b362acaa835293cf693bb176f7324b531f5afec012102c7f12003196442943d8588e01aee840423cc54fc1521526a3b85c2b0cbd58872"),
+        ];
+
+        for (message, proof) in vectors.iter() {
+            let proof = hex::decode(proof).unwrap();
+            assert!(signed_message(ADDRESS, message).verify_simple(&proof).unwrap());
+            assert!(!signed_message(ADDRESS, "Hello World!").verify_simple(&proof).unwrap());
+        }
+    }
+
+    #[test]
+    fn verify_simple_taproot() {
+        let private_key = BitcoinPrivateKey::<N>::from_str(PRIVATE_KEY).unwrap();
+        assert_eq!(
+            TAPROOT_ADDRESS,
+            private_key.to_address(&BitcoinFormat::P2TR).unwrap().to_string()
+        );
+
+        // A SIGHASH_ALL signature of "Hello World"
+        let proof = hex::decode("0141ddebd3eb25012ffa82937d9f25f9644e047bb2f472ab6c5089bbb53588ada2884cb5bcc53911f32d8dcf9548733b694d120db6a4e485194559e8d8fe668d269f01").unwrap();
+        assert!(signed_message(TAPROOT_ADDRESS, "Hello World")
+            .verify_simple(&proof)
+            .unwrap());
+        assert!(!signed_message(TAPROOT_ADDRESS, "Hello World!")
+            .verify_simple(&proof)
+            .unwrap());
+
+        // The same signature with any other sighash byte is invalid
+        let mut proof = proof;
+        proof[66] = SignatureHash::SIGHASH_NONE as u8;
+        assert!(!signed_message(TAPROOT_ADDRESS, "Hello World")
+            .verify_simple(&proof)
+            .unwrap());
+    }
+
+    #[test]
+    fn sign_simple_taproot() {
+        let private_key = BitcoinPrivateKey::<N>::from_str(PRIVATE_KEY).unwrap();
+        let signed_message = signed_message(TAPROOT_ADDRESS, "Hello World");
+        let proof = signed_message.sign_simple(&private_key).unwrap();
+        assert_eq!(66, proof.len());
+        assert_eq!(proof, signed_message.sign_simple(&private_key).unwrap());
+        assert!(signed_message.verify_simple(&proof).unwrap());
+        assert!(signed_message
+            .verify_full(&signed_message.sign_full(&private_key).unwrap())
+            .unwrap());
+    }
+
+    #[test]
+    fn sign_simple() {
+        let private_key = BitcoinPrivateKey::<N>::from_str(PRIVATE_KEY).unwrap();
+        let signed_message = signed_message(ADDRESS, "Hello World");
+        let proof = signed_message.sign_simple(&private_key).unwrap();
+        assert!(signed_message.verify_simple(&proof).unwrap());
+        assert!(signed_message
+            .verify_full(&signed_message.sign_full(&private_key).unwrap())
+            .unwrap());
+    }
+
+    #[test]
+    fn sign_full() {
+        let private_key = BitcoinPrivateKey::<N>::from_str(PRIVATE_KEY).unwrap();
+        for format in [
+            BitcoinFormat::P2PKH,
+            BitcoinFormat::P2SH_P2WPKH,
+            BitcoinFormat::Bech32,
+            BitcoinFormat::P2TR,
+        ]
+        .iter()
+        {
+            let address = private_key.to_address(format).unwrap();
+            let signed_message = BitcoinSignedMessage::<N>::new(&address, b"Hello World").unwrap();
+            let proof = signed_message.sign_full(&private_key).unwrap();
+            assert!(signed_message.verify_full(&proof).unwrap());
+
+            let other_message = BitcoinSignedMessage::<N>::new(&address, b"Hello World!").unwrap();
+            assert!(other_message.verify_full(&proof).is_err());
+        }
+    }
+
+    #[test]
+    fn sign_with_mismatched_key() {
+        let private_key =
+            BitcoinPrivateKey::<N>::from_str("KwdMAjGmerYanjeui5SHS7JkmpZvVipYvB2LJGU1ZxJwYvP98617").unwrap();
+        assert!(signed_message(ADDRESS, "Hello World")
+            .sign_simple(&private_key)
+            .is_err());
+        assert!(BitcoinSignedMessage::<N>::new(
+            &private_key.to_address(&BitcoinFormat::P2PKH).unwrap(),
+            b"Hello World"
+        )
+        .unwrap()
+        .sign_simple(&private_key)
+        .is_err());
+    }
+}
@@ -0,0 +1,285 @@
+//!
+//! Taproot
+//!
+//! This module contains the key tweaking of Pay-to-Taproot (P2TR) outputs, and their script trees
+//! of tapscript leaves (BIP-342) with the control blocks that spend them.
+//! https://github.com/bitcoin/bips/blob/master/bip-0341.mediawiki
+//!
+
+use crate::transaction::variable_length_integer;
+use wagyu_model::no_std::*;
+use wagyu_model::TransactionError;
+
+use core::convert::TryFrom;
+use secp256k1::curve::Scalar;
+use sha2::{Digest, Sha256};
+
+/// The leaf version of a tapscript leaf (BIP-342)
+pub const TAPSCRIPT_LEAF_VERSION: u8 = 0xc0;
+
+#[derive(Debug, Fail)]
+pub enum TaprootError {
+    #[fail(display = "{}: {}", _0, _1)]
+    Crate(&'static str, String),
+
+    #[fail(display = "the script tree has no leaves")]
+    EmptyScriptTree,
+
+    #[fail(display = "{}", _0)]
+    TransactionError(TransactionError),
+}
+
+impl From<secp256k1::Error> for TaprootError {
+    fn from(error: secp256k1::Error) -> Self {
+        TaprootError::Crate("libsecp256k1", format!("{:?}", error))
+    }
+}
+
+impl From<TransactionError> for TaprootError {
+    fn from(error: TransactionError) -> Self {
+        TaprootError::TransactionError(error)
+    }
+}
+
+/// Represents a script tree of tapscript leaves, paired in order, level by level, into a balanced tree
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TaprootScriptTree {
+    /// The scripts of the leaves
+    pub scripts: Vec<Vec<u8>>,
+    /// The leaf hashes of the scripts
+    pub leaf_hashes: Vec<[u8; 32]>,
+    /// The Merkle paths of the leaves, from the sibling of each leaf to the children of the root
+    pub merkle_paths: Vec<Vec<[u8; 32]>>,
+    /// The Merkle root of the tree
+    pub merkle_root: [u8; 32],
+}
+
+impl TaprootScriptTree {
+    /// Returns the script tree of the given scripts, as tapscript leaves.
+    pub fn new(scripts: &[Vec<u8>]) -> Result<Self, TaprootError> {
+        if scripts.is_empty() {
+            return Err(TaprootError::EmptyScriptTree);
+        }
+
+        let leaf_hashes = scripts
+            .iter()
+            .map(|script| tap_leaf_hash(TAPSCRIPT_LEAF_VERSION, script))
+            .collect::<Result<Vec<[u8; 32]>, TaprootError>>()?;
+        let mut merkle_paths = vec![vec![]; scripts.len()];
+
+        // Each node is its hash and the indices of its leaves, and an odd node is carried to the next level
+        let mut nodes = leaf_hashes
+            .iter()
+            .enumerate()
+            .map(|(index, hash)| (*hash, vec![index]))
+            .collect::<Vec<([u8; 32], Vec<usize>)>>();
+        while nodes.len() > 1 {
+            let mut parents = Vec::new();
+            for pair in nodes.chunks(2) {
+                match pair {
+                    [(left, left_leaves), (right, right_leaves)] => {
+                        left_leaves.iter().for_each(|leaf| merkle_paths[*leaf].push(*right));
+                        right_leaves.iter().for_each(|leaf| merkle_paths[*leaf].push(*left));
+                        parents.push((
+                            tap_branch_hash(left, right),
+                            [&left_leaves[..], &right_leaves[..]].concat(),
+                        ));
+                    }
+                    _ => parents.push(pair[0].clone()),
+                }
+            }
+            nodes = parents;
+        }
+
+        Ok(Self {
+            scripts: scripts.to_vec(),
+            leaf_hashes,
+            merkle_paths,
+            merkle_root: nodes[0].0,
+        })
+    }
+
+    /// Returns the control blocks spending each leaf of the script tree of the given internal key,
+    /// of its leaf version and the parity of the output key, the x-only internal key, and its Merkle path.
+    pub fn to_control_blocks(&self, public_key: &secp256k1::PublicKey) -> Result<Vec<Vec<u8>>, TaprootError> {
+        let (_, odd) = tweak(public_key, Some(&self.merkle_root))?;
+        let internal_key = &public_key.serialize_compressed()[1..];
+        Ok(self
+            .merkle_paths
+            .iter()
+            .map(|merkle_path| {
+                let mut control_block = vec![TAPSCRIPT_LEAF_VERSION | odd as u8];
+                control_block.extend_from_slice(internal_key);
+                merkle_path
+                    .iter()
+                    .for_each(|hash| control_block.extend_from_slice(hash));
+                control_block
+            })
+            .collect())
+    }
+}
+
+/// Returns the BIP-340 tagged hash of the given message, `SHA256(SHA256(tag) || SHA256(tag) || message)`.
+pub fn tagged_hash(tag: &str, message: &[u8]) -> [u8; 32] {
+    let tag = Sha256::digest(tag.as_bytes());
+    let mut hasher = Sha256::new();
+    hasher.input(tag);
+    hasher.input(tag);
+    hasher.input(message);
+
+    let mut hash = [0u8; 32];
+    hash.copy_from_slice(&hasher.result());
+    hash
+}
+
+/// Returns the leaf hash of the given script with the given leaf version.
+pub fn tap_leaf_hash(leaf_version: u8, script: &[u8]) -> Result<[u8; 32], TaprootError> {
+    let mut message = vec![leaf_version];
+    message.extend(variable_length_integer(script.len() as u64)?);
+    message.extend_from_slice(script);
+    Ok(tagged_hash("TapLeaf", &message))
+}
+
+/// Returns the branch hash of the given child nodes, which are sorted so that the hash is independent of their order.
+pub fn tap_branch_hash(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    let (left, right) = if left <= right { (left, right) } else { (right, left) };
+    tagged_hash("TapBranch", &[&left[..], &right[..]].concat())
+}
+
+/// Returns the x-only output key of the given public key, tweaked with the given script tree root,
+/// or with no script tree for a key path only output (BIP-86).
+pub fn tweak_public_key(
+    public_key: &secp256k1::PublicKey,
+    merkle_root: Option<&[u8; 32]>,
+) -> Result<[u8; 32], secp256k1::Error> {
+    Ok(tweak(public_key, merkle_root)?.0)
+}
+
+/// Returns the private key of the output key of the given private key, tweaked with the given script tree root,
+/// or with no script tree for a key path only output (BIP-86). The private key is negated when its public key
+/// has an odd y coordinate, as the internal key is x-only, so the tweaked private key signs for the output key.
+pub fn tweak_private_key(
+    private_key: &secp256k1::SecretKey,
+    merkle_root: Option<&[u8; 32]>,
+) -> Result<secp256k1::SecretKey, secp256k1::Error> {
+    let public_key = secp256k1::PublicKey::from_secret_key(private_key);
+    let scalar: Scalar = private_key.clone().into();
+    let scalar = match public_key.serialize_compressed()[0] == 0x03 {
+        true => -scalar,
+        false => scalar,
+    };
+
+    let mut tweaked_key = secp256k1::SecretKey::try_from(scalar)?;
+    tweaked_key.tweak_add_assign(&tap_tweak(&public_key, merkle_root)?)?;
+    Ok(tweaked_key)
+}
+
+/// Returns the tweak of the given public key, which commits to its x-only internal key,
+/// followed by the script tree root if any.
+fn tap_tweak(
+    public_key: &secp256k1::PublicKey,
+    merkle_root: Option<&[u8; 32]>,
+) -> Result<secp256k1::SecretKey, secp256k1::Error> {
+    let mut message = [0u8; 64];
+    message[..32].copy_from_slice(&public_key.serialize_compressed()[1..]);
+    let length = match merkle_root {
+        Some(merkle_root) => {
+            message[32..].copy_from_slice(merkle_root);
+            64
+        }
+        None => 32,
+    };
+    secp256k1::SecretKey::parse(&tagged_hash("TapTweak", &message[..length]))
+}
+
+/// Returns the x-only output key of the given public key, tweaked with the given script tree root,
+/// and whether the output key has an odd y coordinate.
+fn tweak(
+    public_key: &secp256k1::PublicKey,
+    merkle_root: Option<&[u8; 32]>,
+) -> Result<([u8; 32], bool), secp256k1::Error> {
+    // The internal key is the x-only public key, which implicitly has an even y coordinate
+    let mut internal_key = public_key.serialize_compressed();
+    internal_key[0] = 0x02;
+    let tweak = tap_tweak(public_key, merkle_root)?;
+
+    let mut output_key = secp256k1::PublicKey::parse_compressed(&internal_key)?;
+    output_key.tweak_add_assign(&tweak)?;
+
+    let output_key = output_key.serialize_compressed();
+    let mut x_only = [0u8; 32];
+    x_only.copy_from_slice(&output_key[1..]);
+    Ok((x_only, output_key[0] == 0x03))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tweak_key_path_only() {
+        // https://github.com/bitcoin/bips/blob/master/bip-0086.mediawiki#test-vectors
+        let internal_key = hex::decode("03cc8a4bc64d897bddc5fbc2f670f7a8ba0b386779106cf1223c6fc5d7cd6fc115").unwrap();
+        let public_key = secp256k1::PublicKey::parse_slice(&internal_key, None).unwrap();
+        assert_eq!(
+            "a60869f0dbcf1dc659c9cecbaf8050135ea9e8cdc487053f1dc6880949dc684c",
+            hex::encode(tweak_public_key(&public_key, None).unwrap())
+        );
+    }
+
+    #[test]
+    fn tweak_single_leaf() {
+        // https://github.com/bitcoin/bips/blob/master/bip-0341/wallet-test-vectors.json
+        let internal_key = hex::decode("02187791b6f712a8ea41c8ecdd0ee77fab3e85263b37e1ec18a3651926b3a6cf27").unwrap();
+        let public_key = secp256k1::PublicKey::parse_slice(&internal_key, None).unwrap();
+        let script = hex::decode("20d85a959b0290bf19bb89ed43c916be835475d013da4b362117393e25a48229b8ac").unwrap();
+        let tree = TaprootScriptTree::new(&[script]).unwrap();
+        assert_eq!(
+            "5b75adecf53548f3ec6ad7d78383bf84cc57b55a3127c72b9a2481752dd88b21",
+            hex::encode(tree.merkle_root)
+        );
+        assert_eq!(
+            "147c9c57132f6e7ecddba9800bb0c4449251c92a1e60371ee77557b6620f3ea3",
+            hex::encode(tweak_public_key(&public_key, Some(&tree.merkle_root)).unwrap())
+        );
+        assert_eq!(
+            "c1187791b6f712a8ea41c8ecdd0ee77fab3e85263b37e1ec18a3651926b3a6cf27",
+            hex::encode(&tree.to_control_blocks(&public_key).unwrap()[0])
+        );
+    }
+
+    #[test]
+    fn tweak_private_key_path_only() {
+        // https://github.com/bitcoin/bips/blob/master/bip-0341/wallet-test-vectors.json (keyPathSpending, input 0)
+        let private_key = hex::decode("6b973d88838f27366ed61c9ad6367663045cb456e28335c109e30717ae0c6baa").unwrap();
+        let private_key = secp256k1::SecretKey::parse_slice(&private_key).unwrap();
+        let tweaked_key = tweak_private_key(&private_key, None).unwrap();
+        assert_eq!(
+            "2405b971772ad26915c8dcdf10f238753a9b837e5f8e6a86fd7c0cce5b7296d9",
+            hex::encode(tweaked_key.serialize())
+        );
+
+        let public_key = secp256k1::PublicKey::from_secret_key(&private_key);
+        let output_key = secp256k1::PublicKey::from_secret_key(&tweaked_key).serialize_compressed();
+        assert_eq!(tweak_public_key(&public_key, None).unwrap()[..], output_key[1..]);
+    }
+
+    #[test]
+    fn merkle_paths() {
+        let scripts = (0..5u8).map(|i| vec![0x51 + i]).collect::<Vec<Vec<u8>>>();
+        let tree = TaprootScriptTree::new(&scripts).unwrap();
+
+        // Each leaf and its Merkle path hash to the Merkle root
+        for (leaf_hash, merkle_path) in tree.leaf_hashes.iter().zip(&tree.merkle_paths) {
+            let root = merkle_path
+                .iter()
+                .fold(*leaf_hash, |node, sibling| tap_branch_hash(&node, sibling));
+            assert_eq!(tree.merkle_root, root);
+        }
+        assert_eq!(
+            vec![3, 3, 3, 3, 1],
+            tree.merkle_paths.iter().map(Vec::len).collect::<Vec<_>>()
+        );
+        assert!(TaprootScriptTree::new(&[]).is_err());
+    }
+}
@@ -0,0 +1,222 @@
+//!
+//! Timelock
+//!
+//! This module contains the time-locked witness scripts of a public key, spendable after
+//! an absolute locktime with OP_CHECKLOCKTIMEVERIFY (BIP-65), or after a relative locktime
+//! with OP_CHECKSEQUENCEVERIFY (BIP-68, BIP-112).
+//!
+
+use crate::address::BitcoinAddress;
+use crate::network::BitcoinNetwork;
+use crate::public_key::BitcoinPublicKey;
+use crate::transaction::Opcode;
+use wagyu_model::no_std::*;
+use wagyu_model::{AddressError, TransactionError};
+
+use core::fmt;
+
+/// The locktime threshold, below which a locktime is a block height, and above which a Unix timestamp
+pub const LOCKTIME_THRESHOLD: u32 = 500_000_000;
+/// The sequence flag of a relative locktime in units of 512 seconds, rather than blocks
+pub const SEQUENCE_LOCKTIME_TYPE_FLAG: u32 = 1 << 22;
+/// The granularity of a relative locktime in seconds
+pub const SEQUENCE_LOCKTIME_GRANULARITY: u32 = 512;
+/// The sequence of an input of a transaction with an absolute locktime (enables the locktime, but not RBF)
+pub const SEQUENCE_LOCKTIME_ENABLED: u32 = 0xfffffffe;
+
+/// Represents a Bitcoin timelock
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum BitcoinTimelock {
+    /// An absolute locktime, as the `lock_time` of the spending transaction
+    Absolute(u32),
+    /// A relative locktime, as the `sequence` of the spending input
+    Relative(u32),
+}
+
+impl BitcoinTimelock {
+    /// Returns an absolute timelock of the given block height, or of the given Unix timestamp
+    /// if it is at least `LOCKTIME_THRESHOLD`.
+    pub fn absolute(lock_time: u32) -> Result<Self, TransactionError> {
+        match lock_time {
+            0 => Err(TransactionError::Message("invalid locktime: 0 is not locked".into())),
+            _ => Ok(BitcoinTimelock::Absolute(lock_time)),
+        }
+    }
+
+    /// Returns a relative timelock of the given number of blocks.
+    pub fn relative_blocks(blocks: u32) -> Result<Self, TransactionError> {
+        match blocks {
+            1..=0xffff => Ok(BitcoinTimelock::Relative(blocks)),
+            _ => Err(TransactionError::Message(format!(
+                "invalid relative locktime: {} blocks, expected 1 to 65535 blocks",
+                blocks
+            ))),
+        }
+    }
+
+    /// Returns a relative timelock of the given number of seconds, which must be a multiple of 512.
+    pub fn relative_seconds(seconds: u32) -> Result<Self, TransactionError> {
+        let units = seconds / SEQUENCE_LOCKTIME_GRANULARITY;
+        match (seconds % SEQUENCE_LOCKTIME_GRANULARITY, units) {
+            (0, 1..=0xffff) => Ok(BitcoinTimelock::Relative(SEQUENCE_LOCKTIME_TYPE_FLAG | units)),
+            _ => Err(TransactionError::Message(format!(
+                "invalid relative locktime: {} seconds, expected a multiple of {} seconds up to {} seconds",
+                seconds,
+                SEQUENCE_LOCKTIME_GRANULARITY,
+                0xffff * SEQUENCE_LOCKTIME_GRANULARITY
+            ))),
+        }
+    }
+
+    /// Returns the `lock_time` of a transaction spending the timelock.
+    pub fn lock_time(&self) -> u32 {
+        match self {
+            BitcoinTimelock::Absolute(lock_time) => *lock_time,
+            BitcoinTimelock::Relative(_) => 0,
+        }
+    }
+
+    /// Returns the `sequence` of an input spending the timelock.
+    pub fn sequence(&self) -> u32 {
+        match self {
+            BitcoinTimelock::Absolute(_) => SEQUENCE_LOCKTIME_ENABLED,
+            BitcoinTimelock::Relative(sequence) => *sequence,
+        }
+    }
+
+    /// Returns the minimum `version` of a transaction spending the timelock,
+    /// as relative locktimes are only enforced from version 2 (BIP-68).
+    pub fn version(&self) -> u32 {
+        match self {
+            BitcoinTimelock::Absolute(_) => 1,
+            BitcoinTimelock::Relative(_) => 2,
+        }
+    }
+
+    /// Returns the witness script of the given public key, locked until the timelock,
+    /// `<locktime> OP_CHECKLOCKTIMEVERIFY OP_DROP <public key> OP_CHECKSIG` for an absolute timelock, and
+    /// `<sequence> OP_CHECKSEQUENCEVERIFY OP_DROP <public key> OP_CHECKSIG` for a relative timelock.
+    pub fn to_witness_script<N: BitcoinNetwork>(&self, public_key: &BitcoinPublicKey<N>) -> Vec<u8> {
+        let (value, opcode) = match self {
+            BitcoinTimelock::Absolute(lock_time) => (*lock_time, Opcode::OP_CHECKLOCKTIMEVERIFY),
+            BitcoinTimelock::Relative(sequence) => (*sequence, Opcode::OP_CHECKSEQUENCEVERIFY),
+        };
+
+        // Witness scripts only accept compressed public keys
+        let public_key = public_key.to_secp256k1_public_key().serialize_compressed();
+
+        let mut script = push_number(value);
+        script.push(opcode as u8);
+        script.push(Opcode::OP_DROP as u8);
+        script.push(public_key.len() as u8);
+        script.extend(public_key.iter());
+        script.push(Opcode::OP_CHECKSIG as u8);
+        script
+    }
+
+    /// Returns the P2WSH address of the witness script of the given public key, locked until the timelock.
+    pub fn to_address<N: BitcoinNetwork>(
+        &self,
+        public_key: &BitcoinPublicKey<N>,
+    ) -> Result<BitcoinAddress<N>, AddressError> {
+        BitcoinAddress::<N>::p2wsh(&self.to_witness_script(public_key))
+    }
+}
+
+impl fmt::Display for BitcoinTimelock {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            BitcoinTimelock::Absolute(height) if *height < LOCKTIME_THRESHOLD => write!(f, "block {}", height),
+            BitcoinTimelock::Absolute(timestamp) => write!(f, "timestamp {}", timestamp),
+            BitcoinTimelock::Relative(sequence) if sequence & SEQUENCE_LOCKTIME_TYPE_FLAG != 0 => {
+                write!(f, "{} seconds", (sequence & 0xffff) * SEQUENCE_LOCKTIME_GRANULARITY)
+            }
+            BitcoinTimelock::Relative(blocks) => write!(f, "{} blocks", blocks),
+        }
+    }
+}
+
+/// Returns the minimal push of the given positive number, as an opcode for 1 to 16,
+/// or as its little-endian script number encoding otherwise.
+pub(crate) fn push_number(value: u32) -> Vec<u8> {
+    if (1..=16).contains(&value) {
+        return vec![Opcode::OP_1 as u8 + (value - 1) as u8];
+    }
+
+    let mut number = value.to_le_bytes().to_vec();
+    while number.len() > 1 && number[number.len() - 1] == 0 && number[number.len() - 2] & 0x80 == 0 {
+        number.pop();
+    }
+    // The most significant bit of a script number is its sign
+    if number[number.len() - 1] & 0x80 != 0 {
+        number.push(0x00);
+    }
+
+    [vec![number.len() as u8], number].concat()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::network::*;
+    use core::str::FromStr;
+
+    const PUBLIC_KEY: &str = "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+
+    fn test_witness_script<N: BitcoinNetwork>(timelock: BitcoinTimelock, expected_witness_script: &str) {
+        let public_key = BitcoinPublicKey::<N>::from_str(PUBLIC_KEY).unwrap();
+        let witness_script = timelock.to_witness_script(&public_key);
+        assert_eq!(expected_witness_script, hex::encode(&witness_script));
+
+        let address = timelock.to_address(&public_key).unwrap();
+        assert_eq!(BitcoinAddress::<N>::p2wsh(&witness_script).unwrap(), address);
+    }
+
+    #[test]
+    fn push_number() {
+        assert_eq!("51", hex::encode(super::push_number(1)));
+        assert_eq!("60", hex::encode(super::push_number(16)));
+        assert_eq!("0111", hex::encode(super::push_number(17)));
+        assert_eq!("02007f", hex::encode(super::push_number(0x7f00)));
+        assert_eq!("029000", hex::encode(super::push_number(144)));
+        assert_eq!("0300350c", hex::encode(super::push_number(800_000)));
+        assert_eq!("0400ffff00", hex::encode(super::push_number(0xffff00)));
+        assert_eq!("050000008000", hex::encode(super::push_number(0x8000_0000)));
+    }
+
+    #[test]
+    fn absolute() {
+        test_witness_script::<Mainnet>(
+            BitcoinTimelock::absolute(800_000).unwrap(),
+            "0300350cb175210279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798ac",
+        );
+        let timelock = BitcoinTimelock::absolute(1_700_000_000).unwrap();
+        assert_eq!(1_700_000_000, timelock.lock_time());
+        assert_eq!(SEQUENCE_LOCKTIME_ENABLED, timelock.sequence());
+        assert_eq!("timestamp 1700000000", timelock.to_string());
+        assert!(BitcoinTimelock::absolute(0).is_err());
+    }
+
+    #[test]
+    fn relative() {
+        test_witness_script::<Testnet>(
+            BitcoinTimelock::relative_blocks(144).unwrap(),
+            "029000b275210279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798ac",
+        );
+        test_witness_script::<Mainnet>(
+            BitcoinTimelock::relative_seconds(86_016).unwrap(),
+            "03a80040b275210279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798ac",
+        );
+        let timelock = BitcoinTimelock::relative_blocks(16).unwrap();
+        assert_eq!(0, timelock.lock_time());
+        assert_eq!(16, timelock.sequence());
+        assert_eq!(2, timelock.version());
+        assert_eq!("16 blocks", timelock.to_string());
+        assert_eq!(
+            "86016 seconds",
+            BitcoinTimelock::relative_seconds(86_016).unwrap().to_string()
+        );
+        assert!(BitcoinTimelock::relative_blocks(0x10000).is_err());
+        assert!(BitcoinTimelock::relative_seconds(1000).is_err());
+    }
+}
@@ -1,5 +1,6 @@
 use crate::address::BitcoinAddress;
 use crate::amount::BitcoinAmount;
+use crate::bech32m;
 use crate::format::BitcoinFormat;
 use crate::network::BitcoinNetwork;
 use crate::private_key::BitcoinPrivateKey;
@@ -9,12 +10,16 @@ use wagyu_model::no_std::{io::Read, *};
 use wagyu_model::{PrivateKey, Transaction, TransactionError, TransactionId};
 
 use base58::FromBase58;
-use bech32::{Bech32, FromBase32};
+use bech32::{u5, Bech32, FromBase32};
 use core::{fmt, str::FromStr};
 use secp256k1;
 use serde::Serialize;
 use sha2::{Digest, Sha256};
 
+/// The maximum size of the data of a standard OP_RETURN output in bytes (Bitcoin Core's `-datacarriersize`,
+/// without the OP_RETURN opcode and the push of the data)
+pub const MAX_OP_RETURN_SIZE: usize = 80;
+
 /// Returns the variable length integer of the given value.
 /// https://en.bitcoin.it/wiki/Protocol_documentation#Variable_length_integer
 pub fn variable_length_integer(value: u64) -> Result<Vec<u8>, TransactionError> {
@@ -132,6 +137,18 @@ pub fn create_script_pub_key<N: BitcoinNetwork>(address: &BitcoinAddress<N>) ->
             let mut program_bytes = vec![v[0].to_u8(), program.len() as u8];
             program_bytes.extend(program);
 
+            Ok(WitnessProgram::new(&program_bytes)?.to_scriptpubkey())
+        }
+        BitcoinFormat::P2TR => {
+            let (_, data) = bech32m::decode(&address.to_string())?;
+            let program = data
+                .iter()
+                .map(|value| u5::try_from_u8(*value))
+                .collect::<Result<Vec<u5>, _>>()?;
+            let program = Vec::from_base32(&program[1..])?;
+            let mut program_bytes = vec![data[0], program.len() as u8];
+            program_bytes.extend(program);
+
             Ok(WitnessProgram::new(&program_bytes)?.to_scriptpubkey())
         }
     }
@@ -194,21 +211,65 @@ impl SignatureHash {
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
 #[allow(non_camel_case_types)]
 pub enum Opcode {
+    OP_0 = 0x00,
+    OP_PUSHDATA1 = 0x4c,
+    OP_1 = 0x51,
+    OP_IF = 0x63,
+    OP_ELSE = 0x67,
+    OP_ENDIF = 0x68,
+    OP_VERIFY = 0x69,
+    OP_RETURN = 0x6a,
+    OP_TOALTSTACK = 0x6b,
+    OP_FROMALTSTACK = 0x6c,
+    OP_DROP = 0x75,
     OP_DUP = 0x76,
-    OP_HASH160 = 0xa9,
-    OP_CHECKSIG = 0xac,
+    OP_SIZE = 0x82,
     OP_EQUAL = 0x87,
     OP_EQUALVERIFY = 0x88,
+    OP_0NOTEQUAL = 0x92,
+    OP_ADD = 0x93,
+    OP_RIPEMD160 = 0xa6,
+    OP_SHA256 = 0xa8,
+    OP_HASH160 = 0xa9,
+    OP_HASH256 = 0xaa,
+    OP_CHECKSIG = 0xac,
+    OP_CHECKSIGVERIFY = 0xad,
+    OP_CHECKMULTISIG = 0xae,
+    OP_CHECKMULTISIGVERIFY = 0xaf,
+    OP_CHECKLOCKTIMEVERIFY = 0xb1,
+    OP_CHECKSEQUENCEVERIFY = 0xb2,
 }
 
 impl fmt::Display for Opcode {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
+            Opcode::OP_0 => write!(f, "OP_0"),
+            Opcode::OP_PUSHDATA1 => write!(f, "OP_PUSHDATA1"),
+            Opcode::OP_1 => write!(f, "OP_1"),
+            Opcode::OP_IF => write!(f, "OP_IF"),
+            Opcode::OP_ELSE => write!(f, "OP_ELSE"),
+            Opcode::OP_ENDIF => write!(f, "OP_ENDIF"),
+            Opcode::OP_VERIFY => write!(f, "OP_VERIFY"),
+            Opcode::OP_RETURN => write!(f, "OP_RETURN"),
+            Opcode::OP_TOALTSTACK => write!(f, "OP_TOALTSTACK"),
+            Opcode::OP_FROMALTSTACK => write!(f, "OP_FROMALTSTACK"),
+            Opcode::OP_DROP => write!(f, "OP_DROP"),
             Opcode::OP_DUP => write!(f, "OP_DUP"),
-            Opcode::OP_HASH160 => write!(f, "OP_HASH160"),
-            Opcode::OP_CHECKSIG => write!(f, "OP_CHECKSIG"),
+            Opcode::OP_SIZE => write!(f, "OP_SIZE"),
             Opcode::OP_EQUAL => write!(f, "OP_EQUAL"),
             Opcode::OP_EQUALVERIFY => write!(f, "OP_EQUALVERIFY"),
+            Opcode::OP_0NOTEQUAL => write!(f, "OP_0NOTEQUAL"),
+            Opcode::OP_ADD => write!(f, "OP_ADD"),
+            Opcode::OP_RIPEMD160 => write!(f, "OP_RIPEMD160"),
+            Opcode::OP_SHA256 => write!(f, "OP_SHA256"),
+            Opcode::OP_HASH160 => write!(f, "OP_HASH160"),
+            Opcode::OP_HASH256 => write!(f, "OP_HASH256"),
+            Opcode::OP_CHECKSIG => write!(f, "OP_CHECKSIG"),
+            Opcode::OP_CHECKSIGVERIFY => write!(f, "OP_CHECKSIGVERIFY"),
+            Opcode::OP_CHECKMULTISIG => write!(f, "OP_CHECKMULTISIG"),
+            Opcode::OP_CHECKMULTISIGVERIFY => write!(f, "OP_CHECKMULTISIGVERIFY"),
+            Opcode::OP_CHECKLOCKTIMEVERIFY => write!(f, "OP_CHECKLOCKTIMEVERIFY"),
+            Opcode::OP_CHECKSEQUENCEVERIFY => write!(f, "OP_CHECKSEQUENCEVERIFY"),
         }
     }
 }
@@ -276,6 +337,8 @@ impl<N: BitcoinNetwork> Outpoint<N> {
                         true => return Err(TransactionError::InvalidInputs("Bech32".into())),
                         false => None,
                     },
+                    // Spending a taproot output requires a Schnorr signature, which is not supported
+                    BitcoinFormat::P2TR => return Err(TransactionError::InvalidInputs("P2TR".into())),
                 };
 
                 (Some(script_pub_key), redeem_script)
@@ -417,6 +480,7 @@ impl<N: BitcoinNetwork> BitcoinTransactionInput<N> {
                     Some(address) => match address.format() {
                         BitcoinFormat::Bech32 => input.extend(vec![0x00]),
                         BitcoinFormat::P2WSH => input.extend(vec![0x00]),
+                        BitcoinFormat::P2TR => input.extend(vec![0x00]),
                         _ => {
                             let script_pub_key = match &self.outpoint.script_pub_key {
                                 Some(script) => script,
@@ -461,6 +525,29 @@ impl BitcoinTransactionOutput {
         })
     }
 
+    /// Returns a Bitcoin transaction output of zero amount, with the given data after an OP_RETURN,
+    /// which provably cannot be spent. The data must be at most `MAX_OP_RETURN_SIZE` bytes to be relayed.
+    pub fn op_return(data: &[u8]) -> Result<Self, TransactionError> {
+        let mut script_pub_key = vec![Opcode::OP_RETURN as u8];
+        match data.len() {
+            0 => (),
+            1..=75 => script_pub_key.push(data.len() as u8),
+            76..=MAX_OP_RETURN_SIZE => script_pub_key.extend(&[Opcode::OP_PUSHDATA1 as u8, data.len() as u8]),
+            size => {
+                return Err(TransactionError::Message(format!(
+                    "invalid OP_RETURN data: {} bytes exceeds {} bytes",
+                    size, MAX_OP_RETURN_SIZE
+                )))
+            }
+        };
+        script_pub_key.extend(data);
+
+        Ok(Self {
+            amount: BitcoinAmount(0),
+            script_pub_key,
+        })
+    }
+
     /// Read and output a Bitcoin transaction output
     pub fn read<R: Read>(mut reader: &mut R) -> Result<Self, TransactionError> {
         let mut amount = [0u8; 8];
@@ -710,6 +797,7 @@ impl<N: BitcoinNetwork> Transaction for BitcoinTransaction<N> {
                             .append(&mut vec![signature.clone(), public_key]);
                         transaction.parameters.inputs[vin].is_signed = true;
                     }
+                    BitcoinFormat::P2TR => return Err(TransactionError::InvalidInputs("P2TR".into())),
                 };
             }
         }
@@ -779,6 +867,46 @@ impl<N: BitcoinNetwork> Transaction for BitcoinTransaction<N> {
 }
 
 impl<N: BitcoinNetwork> BitcoinTransaction<N> {
+    /// Returns the transaction parameters.
+    pub fn parameters(&self) -> &BitcoinTransactionParameters<N> {
+        &self.parameters
+    }
+
+    /// Returns whether the transaction signals that it is replaceable (BIP-125),
+    /// with the sequence of any of its inputs below 0xfffffffe.
+    /// https://github.com/bitcoin/bips/blob/master/bip-0125.mediawiki
+    pub fn signals_rbf(&self) -> bool {
+        self.parameters
+            .inputs
+            .iter()
+            .any(|input| match input.sequence.as_slice() {
+                [a, b, c, d] => u32::from_le_bytes([*a, *b, *c, *d]) < 0xfffffffe,
+                _ => false,
+            })
+    }
+
+    /// Returns the weight of the transaction (BIP-141), as 3 times its size without witnesses plus its size.
+    pub fn weight(&self) -> Result<usize, TransactionError> {
+        Ok(3 * self.to_transaction_bytes_without_witness()?.len() + self.to_transaction_bytes()?.len())
+    }
+
+    /// Returns the virtual size of the transaction, as its weight divided by 4 (rounded up).
+    pub fn vsize(&self) -> Result<usize, TransactionError> {
+        Ok(self.weight()?.div_ceil(4))
+    }
+
+    /// Returns the transaction without the scripts and witnesses of its inputs, to be signed again.
+    pub fn to_unsigned(&self) -> Self {
+        let mut transaction = self.clone();
+        transaction.parameters.segwit_flag = false;
+        for input in &mut transaction.parameters.inputs {
+            input.script_sig = vec![];
+            input.witnesses = vec![];
+            input.is_signed = false;
+        }
+        transaction
+    }
+
     /// Return the P2PKH hash preimage of the raw transaction.
     pub fn p2pkh_hash_preimage(&self, vin: usize, sighash: SignatureHash) -> Result<Vec<u8>, TransactionError> {
         let mut preimage = self.parameters.version.to_le_bytes().to_vec();
@@ -832,6 +960,7 @@ impl<N: BitcoinNetwork> BitcoinTransaction<N> {
                 None => return Err(TransactionError::InvalidInputs("P2SH_P2WPKH".into())),
             },
             BitcoinFormat::P2PKH => return Err(TransactionError::UnsupportedPreimage("P2PKH".into())),
+            BitcoinFormat::P2TR => return Err(TransactionError::UnsupportedPreimage("P2TR".into())),
         };
 
         let mut script_code = vec![];
@@ -869,6 +998,63 @@ impl<N: BitcoinNetwork> BitcoinTransaction<N> {
         Ok(preimage)
     }
 
+    /// Return the Taproot key path hash preimage of the raw transaction, the epoch followed by the signature message,
+    /// with `None` as SIGHASH_DEFAULT. Only SIGHASH_DEFAULT and SIGHASH_ALL are supported.
+    /// https://github.com/bitcoin/bips/blob/master/bip-0341.mediawiki#common-signature-message
+    pub fn taproot_hash_preimage(
+        &self,
+        vin: usize,
+        sighash: Option<SignatureHash>,
+    ) -> Result<Vec<u8>, TransactionError> {
+        let hash_type = match sighash {
+            None => 0x00u8,
+            Some(SignatureHash::SIGHASH_ALL) => SignatureHash::SIGHASH_ALL as u8,
+            Some(sighash) => return Err(TransactionError::UnsupportedPreimage(format!("P2TR {:?}", sighash))),
+        };
+
+        let mut prev_outputs = vec![];
+        let mut amounts = vec![];
+        let mut script_pub_keys = vec![];
+        let mut sequences = vec![];
+        let mut outputs = vec![];
+
+        // The signature message commits to the amounts and script public keys of all inputs
+        for input in &self.parameters.inputs {
+            prev_outputs.extend(&input.outpoint.reverse_transaction_id);
+            prev_outputs.extend(&input.outpoint.index.to_le_bytes());
+            match &input.outpoint.amount {
+                Some(amount) => amounts.extend(&amount.0.to_le_bytes()),
+                None => return Err(TransactionError::MissingOutpointAmount),
+            };
+            match &input.outpoint.script_pub_key {
+                Some(script) => {
+                    script_pub_keys.extend(variable_length_integer(script.len() as u64)?);
+                    script_pub_keys.extend(script);
+                }
+                None => return Err(TransactionError::MissingOutpointScriptPublicKey),
+            };
+            sequences.extend(&input.sequence);
+        }
+
+        for output in &self.parameters.outputs {
+            outputs.extend(&output.serialize()?);
+        }
+
+        let mut preimage = vec![0x00, hash_type];
+        preimage.extend(&self.parameters.version.to_le_bytes());
+        preimage.extend(&self.parameters.lock_time.to_le_bytes());
+        preimage.extend(Sha256::digest(&prev_outputs));
+        preimage.extend(Sha256::digest(&amounts));
+        preimage.extend(Sha256::digest(&script_pub_keys));
+        preimage.extend(Sha256::digest(&sequences));
+        preimage.extend(Sha256::digest(&outputs));
+        // The spend type of a key path spend with no annex
+        preimage.push(0x00);
+        preimage.extend(&(vin as u32).to_le_bytes());
+
+        Ok(preimage)
+    }
+
     /// Returns the transaction with the traditional serialization (no witness).
     fn to_transaction_bytes_without_witness(&self) -> Result<Vec<u8>, TransactionError> {
         let mut transaction = self.parameters.version.to_le_bytes().to_vec();
@@ -1939,6 +2125,73 @@ mod tests {
         }
     }
 
+    mod test_fee_functions {
+        use super::*;
+        type N = Mainnet;
+
+        // A P2SH_P2WPKH input with a 107 byte witness, to a P2PKH output and a P2WPKH output
+        const TRANSACTION: &str = "01000000000101b62af4592179f55b13611bbd3bf023dd0698e71be7b3aee797ba7ab2af6f76a50000000017160014b5ccbe3c5a285af4afada113a8619827fb30b2eeffffffff02b8880000000000001976a91442cd2c7460acc561c96b11c4aa96d0346b84db7f88acb888000000000000160014c4242ad7193d651d51e04f0b63f9646c447bd404024730440220449ca32ff3f8da3c17c1813dac91010cb1fea7a77b2f63065184b8318e1b9ed70220315da34cfeae62c26557c40f5ac5cde46b2801349e6677fc96597b4bfee04b0b012102973e9145ca85357b06de3009a12db171d70bae8a648dc8188e49723a2a46459100000000";
+
+        #[test]
+        fn test_weight() {
+            let transaction =
+                BitcoinTransaction::<N>::from_transaction_bytes(&hex::decode(TRANSACTION).unwrap()).unwrap();
+            assert_eq!(3 * 139 + 248, transaction.weight().unwrap());
+            assert_eq!(167, transaction.vsize().unwrap());
+        }
+
+        #[test]
+        fn test_signals_rbf() {
+            let transaction =
+                BitcoinTransaction::<N>::from_transaction_bytes(&hex::decode(TRANSACTION).unwrap()).unwrap();
+            assert!(!transaction.signals_rbf());
+
+            let replaceable = TRANSACTION.replace("2eeffffffff02", "2eefdffffff02");
+            let transaction =
+                BitcoinTransaction::<N>::from_transaction_bytes(&hex::decode(replaceable).unwrap()).unwrap();
+            assert!(transaction.signals_rbf());
+        }
+
+        #[test]
+        fn test_to_unsigned() {
+            let transaction =
+                BitcoinTransaction::<N>::from_transaction_bytes(&hex::decode(TRANSACTION).unwrap()).unwrap();
+            let unsigned = transaction.to_unsigned();
+            assert!(unsigned.parameters().inputs.iter().all(|input| !input.is_signed));
+            assert_eq!(transaction.parameters().outputs, unsigned.parameters().outputs);
+            assert_eq!(
+                "0100000001b62af4592179f55b13611bbd3bf023dd0698e71be7b3aee797ba7ab2af6f76a50000000000ffffffff02b8880000000000001976a91442cd2c7460acc561c96b11c4aa96d0346b84db7f88acb888000000000000160014c4242ad7193d651d51e04f0b63f9646c447bd40400000000",
+                hex::encode(unsigned.to_transaction_bytes().unwrap())
+            );
+        }
+    }
+
+    mod test_op_return {
+        use super::*;
+
+        #[test]
+        fn test_op_return() {
+            let output = BitcoinTransactionOutput::op_return(b"wagyu").unwrap();
+            assert_eq!(BitcoinAmount(0), output.amount);
+            assert_eq!("6a057761677975", hex::encode(&output.script_pub_key));
+            assert_eq!(
+                "6a",
+                hex::encode(&BitcoinTransactionOutput::op_return(&[]).unwrap().script_pub_key)
+            );
+
+            let output = BitcoinTransactionOutput::op_return(&[0xff; 76]).unwrap();
+            assert_eq!("6a4c4c", hex::encode(&output.script_pub_key[..3]));
+            assert_eq!(
+                MAX_OP_RETURN_SIZE + 3,
+                BitcoinTransactionOutput::op_return(&[0; 80])
+                    .unwrap()
+                    .script_pub_key
+                    .len()
+            );
+            assert!(BitcoinTransactionOutput::op_return(&[0; 81]).is_err());
+        }
+    }
+
     mod test_helper_functions {
         use super::*;
 
@@ -9,6 +9,9 @@ impl Wordlist for Japanese {}
 impl BitcoinWordlist for Japanese {
     /// The wordlist in original form.
     const WORDLIST: &'static str = JAPANESE;
+
+    /// The separator of the words of a phrase, an ideographic space.
+    const SEPARATOR: &'static str = "\u{3000}";
 }
 
 #[cfg(test)]
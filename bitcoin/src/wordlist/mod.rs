@@ -30,6 +30,9 @@ pub trait BitcoinWordlist: Wordlist {
     /// The wordlist in original form.
     const WORDLIST: &'static str;
 
+    /// The separator of the words of a phrase.
+    const SEPARATOR: &'static str = " ";
+
     /// Returns the word of a given index from the word list.
     fn get(index: usize) -> Result<String, WordlistError> {
         if index >= 2048 {
@@ -0,0 +1,264 @@
+//! # API
+//!
+//! Functions that take and return only strings and byte slices, so that they can be
+//! exported to JavaScript with `wasm-bindgen` as is. Errors are returned as their messages.
+
+use crate::bitcoin::{
+    wordlist::English as BitcoinEnglish, BitcoinDerivationPath, BitcoinFormat, BitcoinMnemonic, BitcoinNetwork,
+    BitcoinPrivateKey, Mainnet as BitcoinMainnet, Testnet as BitcoinTestnet,
+};
+use crate::entropy::rng_from_seed;
+use crate::ethereum::{
+    wordlist::English as EthereumEnglish, EthereumDerivationPath, EthereumFormat, EthereumMnemonic, EthereumNetwork,
+    EthereumPrivateKey, EthereumTransaction, Goerli, Kovan, Mainnet as EthereumMainnet, Rinkeby, Ropsten,
+};
+use crate::model::{
+    no_std::{format, String, ToString},
+    ExtendedPrivateKey, ExtendedPublicKey, Mnemonic, MnemonicCount, MnemonicExtended, Network, PrivateKey, PublicKey,
+    Transaction,
+};
+
+use core::str::FromStr;
+
+/// Returns a new English BIP39 mnemonic of the given word count,
+/// generated from the given 32 bytes of entropy.
+pub fn generate_mnemonic(seed: &[u8], word_count: u8) -> Result<String, String> {
+    if seed.len() != 32 {
+        return Err("expected 32 bytes of entropy".into());
+    }
+    let mut bytes = [0u8; 32];
+    bytes.copy_from_slice(seed);
+
+    let mnemonic =
+        BitcoinMnemonic::<BitcoinMainnet, BitcoinEnglish>::new_with_count(&mut rng_from_seed(bytes), word_count)
+            .map_err(|error| error.to_string())?;
+    mnemonic.to_phrase().map_err(|error| error.to_string())
+}
+
+/// Returns the Bitcoin address of the given English mnemonic and password (if any),
+/// derived at the given path, in the given format (`legacy`, `segwit`, or `bech32`)
+/// on the given network (`mainnet` or `testnet`).
+pub fn bitcoin_address(
+    mnemonic: &str,
+    password: Option<&str>,
+    path: &str,
+    format: &str,
+    network: &str,
+) -> Result<String, String> {
+    let format = bitcoin_format(format)?;
+    match network {
+        "mainnet" => derive_bitcoin_address::<BitcoinMainnet>(mnemonic, password, path, &format),
+        "testnet" => derive_bitcoin_address::<BitcoinTestnet>(mnemonic, password, path, &format),
+        network => Err(format!("unsupported network: {}", network)),
+    }
+}
+
+/// Returns the Bitcoin private key (in WIF) of the given English mnemonic and password (if any),
+/// derived at the given path on the given network (`mainnet` or `testnet`).
+pub fn bitcoin_private_key(
+    mnemonic: &str,
+    password: Option<&str>,
+    path: &str,
+    network: &str,
+) -> Result<String, String> {
+    match network {
+        "mainnet" => Ok(derive_bitcoin_private_key::<BitcoinMainnet>(mnemonic, password, path)?.to_string()),
+        "testnet" => Ok(derive_bitcoin_private_key::<BitcoinTestnet>(mnemonic, password, path)?.to_string()),
+        network => Err(format!("unsupported network: {}", network)),
+    }
+}
+
+/// Returns the Bitcoin address of the given private key (in WIF),
+/// in the given format (`legacy`, `segwit`, or `bech32`) on the network of the private key.
+pub fn bitcoin_private_key_address(private_key: &str, format: &str) -> Result<String, String> {
+    let format = bitcoin_format(format)?;
+    let address = match BitcoinPrivateKey::<BitcoinMainnet>::from_str(private_key) {
+        Ok(private_key) => private_key.to_address(&format).map(|address| address.to_string()),
+        Err(_) => BitcoinPrivateKey::<BitcoinTestnet>::from_str(private_key)
+            .map_err(|error| error.to_string())?
+            .to_address(&format)
+            .map(|address| address.to_string()),
+    };
+    address.map_err(|error| error.to_string())
+}
+
+/// Returns the Ethereum address of the given English mnemonic and password (if any),
+/// derived at the given path.
+pub fn ethereum_address(mnemonic: &str, password: Option<&str>, path: &str) -> Result<String, String> {
+    let mnemonic = EthereumMnemonic::<EthereumMainnet, EthereumEnglish>::from_phrase(mnemonic)
+        .map_err(|error| error.to_string())?;
+    let path = EthereumDerivationPath::from_str(path).map_err(|error| error.to_string())?;
+    let extended_private_key = mnemonic
+        .to_extended_private_key(password)
+        .and_then(|key| Ok(key.derive(&path)?))
+        .map_err(|error| error.to_string())?;
+
+    let public_key = extended_private_key.to_extended_public_key().to_public_key();
+    let address = public_key
+        .to_address(&EthereumFormat::Standard)
+        .map_err(|error| error.to_string())?;
+    Ok(address.to_string())
+}
+
+/// Returns the Ethereum private key (in hex) of the given English mnemonic and password (if any),
+/// derived at the given path.
+pub fn ethereum_private_key(mnemonic: &str, password: Option<&str>, path: &str) -> Result<String, String> {
+    let mnemonic = EthereumMnemonic::<EthereumMainnet, EthereumEnglish>::from_phrase(mnemonic)
+        .map_err(|error| error.to_string())?;
+    let path = EthereumDerivationPath::from_str(path).map_err(|error| error.to_string())?;
+    let extended_private_key = mnemonic
+        .to_extended_private_key(password)
+        .and_then(|key| Ok(key.derive(&path)?))
+        .map_err(|error| error.to_string())?;
+    Ok(extended_private_key.to_private_key().to_string())
+}
+
+/// Returns the Ethereum address of the given private key (in hex).
+pub fn ethereum_private_key_address(private_key: &str) -> Result<String, String> {
+    let private_key = EthereumPrivateKey::from_str(private_key).map_err(|error| error.to_string())?;
+    let address = private_key
+        .to_address(&EthereumFormat::Standard)
+        .map_err(|error| error.to_string())?;
+    Ok(address.to_string())
+}
+
+/// Returns the signed transaction hex of the given unsigned Ethereum transaction hex,
+/// signed with the given private key for the given network (e.g. `mainnet` or `ropsten`).
+pub fn ethereum_sign_transaction(transaction_hex: &str, private_key: &str, network: &str) -> Result<String, String> {
+    match network {
+        EthereumMainnet::NAME => sign_ethereum_transaction::<EthereumMainnet>(transaction_hex, private_key),
+        Goerli::NAME => sign_ethereum_transaction::<Goerli>(transaction_hex, private_key),
+        Kovan::NAME => sign_ethereum_transaction::<Kovan>(transaction_hex, private_key),
+        Rinkeby::NAME => sign_ethereum_transaction::<Rinkeby>(transaction_hex, private_key),
+        Ropsten::NAME => sign_ethereum_transaction::<Ropsten>(transaction_hex, private_key),
+        network => Err(format!("unsupported network: {}", network)),
+    }
+}
+
+fn bitcoin_format(format: &str) -> Result<BitcoinFormat, String> {
+    match format {
+        "legacy" | "p2pkh" => Ok(BitcoinFormat::P2PKH),
+        "segwit" | "p2sh_p2wpkh" => Ok(BitcoinFormat::P2SH_P2WPKH),
+        "bech32" => Ok(BitcoinFormat::Bech32),
+        format => Err(format!("unsupported format: {}", format)),
+    }
+}
+
+fn derive_bitcoin_address<N: BitcoinNetwork>(
+    mnemonic: &str,
+    password: Option<&str>,
+    path: &str,
+    format: &BitcoinFormat,
+) -> Result<String, String> {
+    let private_key = derive_bitcoin_private_key::<N>(mnemonic, password, path)?;
+    let address = private_key.to_address(format).map_err(|error| error.to_string())?;
+    Ok(address.to_string())
+}
+
+fn derive_bitcoin_private_key<N: BitcoinNetwork>(
+    mnemonic: &str,
+    password: Option<&str>,
+    path: &str,
+) -> Result<BitcoinPrivateKey<N>, String> {
+    let mnemonic = BitcoinMnemonic::<N, BitcoinEnglish>::from_phrase(mnemonic).map_err(|error| error.to_string())?;
+    let path = BitcoinDerivationPath::from_str(path).map_err(|error| error.to_string())?;
+    let extended_private_key = mnemonic
+        .to_extended_private_key(password)
+        .and_then(|key| Ok(key.derive(&path)?))
+        .map_err(|error| error.to_string())?;
+    Ok(extended_private_key.to_private_key())
+}
+
+fn sign_ethereum_transaction<N: EthereumNetwork>(transaction_hex: &str, private_key: &str) -> Result<String, String> {
+    let transaction_hex = transaction_hex.trim_start_matches("0x");
+    let transaction_bytes = hex::decode(transaction_hex).map_err(|error| error.to_string())?;
+    let private_key = EthereumPrivateKey::from_str(private_key).map_err(|error| error.to_string())?;
+
+    let transaction = EthereumTransaction::<N>::from_transaction_bytes(&transaction_bytes)
+        .and_then(|transaction| transaction.sign(&private_key))
+        .map_err(|error| error.to_string())?;
+    let signed_bytes = transaction.to_transaction_bytes().map_err(|error| error.to_string())?;
+    Ok(format!("0x{}", hex::encode(signed_bytes)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MNEMONIC: &str =
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    #[test]
+    fn generate() {
+        let mnemonic = generate_mnemonic(&[1u8; 32], 24).unwrap();
+        assert_eq!(24, mnemonic.split(' ').count());
+        assert_eq!(mnemonic, generate_mnemonic(&[1u8; 32], 24).unwrap());
+        assert!(generate_mnemonic(&[1u8; 16], 24).is_err());
+        assert!(generate_mnemonic(&[1u8; 32], 13).is_err());
+    }
+
+    #[test]
+    fn bitcoin() {
+        assert_eq!(
+            "1LqBGSKuX5yYUonjxT5qGfpUsXKYYWeabA",
+            bitcoin_address(MNEMONIC, None, "m/44'/0'/0'/0/0", "legacy", "mainnet").unwrap()
+        );
+        assert_eq!(
+            "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu",
+            bitcoin_address(MNEMONIC, None, "m/84'/0'/0'/0/0", "bech32", "mainnet").unwrap()
+        );
+        assert!(bitcoin_address(MNEMONIC, None, "m/44'/0'/0'/0/0", "legacy", "regtest").is_err());
+    }
+
+    #[test]
+    fn bitcoin_private_keys() {
+        let private_key = bitcoin_private_key(MNEMONIC, None, "m/84'/0'/0'/0/0", "mainnet").unwrap();
+        assert_eq!("KyZpNDKnfs94vbrwhJneDi77V6jF64PWPF8x5cdJb8ifgg2DUc9d", private_key);
+        assert_eq!(
+            "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu",
+            bitcoin_private_key_address(&private_key, "bech32").unwrap()
+        );
+
+        let private_key = bitcoin_private_key(MNEMONIC, None, "m/84'/1'/0'/0/0", "testnet").unwrap();
+        assert!(bitcoin_private_key_address(&private_key, "bech32")
+            .unwrap()
+            .starts_with("tb1"));
+        assert!(bitcoin_private_key_address("invalid", "bech32").is_err());
+    }
+
+    #[test]
+    fn ethereum_private_keys() {
+        let private_key = ethereum_private_key(MNEMONIC, None, "m/44'/60'/0'/0/0").unwrap();
+        assert_eq!(
+            "0x9858EfFD232B4033E47d90003D41EC34EcaEda94",
+            ethereum_private_key_address(&private_key).unwrap()
+        );
+    }
+
+    #[test]
+    fn ethereum() {
+        assert_eq!(
+            "0x9858EfFD232B4033E47d90003D41EC34EcaEda94",
+            ethereum_address(MNEMONIC, None, "m/44'/60'/0'/0/0").unwrap()
+        );
+        assert!(ethereum_address("abandon", None, "m/44'/60'/0'/0/0").is_err());
+    }
+
+    #[test]
+    fn ethereum_sign() {
+        let transaction = "0xdf800182520894e5c7ab7a563af4e3f12b78e3396ca1e4f2d0b58e0180018080";
+        let private_key = "f89f23eaeac18252fedf81bb8318d3c111d48c19b0680dcf6e0a8d5136caf287";
+
+        let signed = ethereum_sign_transaction(transaction, private_key, "mainnet").unwrap();
+        assert!(signed.starts_with("0xf8"));
+        assert_eq!(
+            signed,
+            ethereum_sign_transaction(&transaction[2..], private_key, "mainnet").unwrap()
+        );
+        assert_ne!(
+            signed,
+            ethereum_sign_transaction(transaction, private_key, "ropsten").unwrap()
+        );
+        assert!(ethereum_sign_transaction(transaction, private_key, "regtest").is_err());
+    }
+}
@@ -0,0 +1,34 @@
+use rand::SeedableRng;
+use rand_chacha::ChaCha20Rng;
+
+/// Returns a ChaCha20 RNG seeded with the given 32 bytes of entropy,
+/// such as the output of `crypto.getRandomValues` in a browser.
+///
+/// The seed must be uniformly random and used once, as it determines every key generated from the RNG.
+pub fn rng_from_seed(seed: [u8; 32]) -> ChaCha20Rng {
+    ChaCha20Rng::from_seed(seed)
+}
+
+/// Returns the operating system RNG.
+///
+/// On `wasm32-unknown-unknown`, `getrandom` draws entropy from `crypto.getRandomValues` only if its
+/// `wasm-bindgen` feature is enabled by the final crate, and otherwise fails on every call.
+#[cfg(feature = "getrandom")]
+pub fn os_rng() -> rand::rngs::OsRng {
+    rand::rngs::OsRng
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    #[test]
+    fn rng_from_seed_is_deterministic() {
+        let first: [u8; 32] = rng_from_seed([7u8; 32]).gen();
+        let second: [u8; 32] = rng_from_seed([7u8; 32]).gen();
+        let other: [u8; 32] = rng_from_seed([8u8; 32]).gen();
+        assert_eq!(first, second);
+        assert_ne!(first, other);
+    }
+}
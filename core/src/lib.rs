@@ -0,0 +1,20 @@
+//! # Wagyu Core
+//!
+//! The cryptography and model layer of wagyu for Bitcoin and Ethereum, without the command-line tool.
+//!
+//! Built without the default `std` feature, the core is `no_std` (with `alloc`), and compiles
+//! to `wasm32-unknown-unknown` for browser and offline web use. The core draws no entropy
+//! of its own: callers pass an RNG, seed one from browser entropy with [`rng_from_seed`],
+//! or enable the `getrandom` feature to use the operating system RNG.
+#![cfg_attr(not(feature = "std"), no_std)]
+#![warn(unused_extern_crates, dead_code)]
+#![forbid(unsafe_code)]
+
+pub use wagyu_bitcoin as bitcoin;
+pub use wagyu_ethereum as ethereum;
+pub use wagyu_model as model;
+
+pub mod api;
+
+pub mod entropy;
+pub use self::entropy::*;
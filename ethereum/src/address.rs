@@ -1,13 +1,18 @@
 use crate::format::EthereumFormat;
 use crate::private_key::EthereumPrivateKey;
 use crate::public_key::EthereumPublicKey;
+use wagyu_model::no_std::*;
 use wagyu_model::{to_hex_string, Address, AddressError, PrivateKey};
 
 use core::{convert::TryFrom, fmt, str::FromStr};
 use regex::Regex;
+use rlp::RlpStream;
 use serde::Serialize;
 use tiny_keccak::keccak256;
 
+/// The creation code of the proxy of a CREATE3 deployment, which deploys its calldata with CREATE
+pub const CREATE3_PROXY_INIT_CODE: &str = "67363d3d37363d34f03d5260086018f3";
+
 /// Represents an Ethereum address
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Hash)]
 pub struct EthereumAddress(String);
@@ -47,6 +52,63 @@ impl EthereumAddress {
 
         EthereumAddress(checksum_address)
     }
+
+    /// Returns the address of a contract deployed with CREATE by the given sender with the given nonce,
+    /// `keccak256(rlp([sender, nonce]))[12..]`.
+    pub fn create(sender: &Self, nonce: u64) -> Result<Self, AddressError> {
+        let mut stream = RlpStream::new_list(2);
+        stream.append(&sender.to_bytes()?.to_vec());
+        stream.append(&nonce);
+        Self::from_str(&hex::encode(&keccak256(&stream.out())[12..]))
+    }
+
+    /// Returns the address of a contract deployed with CREATE2 by the given deployer with the given salt
+    /// and hash of the init code, `keccak256(0xff || deployer || salt || keccak256(init_code))[12..]` (EIP-1014).
+    pub fn create2(deployer: &Self, salt: &[u8; 32], init_code_hash: &[u8; 32]) -> Result<Self, AddressError> {
+        let mut preimage = vec![0xff];
+        preimage.extend_from_slice(&deployer.to_bytes()?);
+        preimage.extend_from_slice(salt);
+        preimage.extend_from_slice(init_code_hash);
+        Self::from_str(&hex::encode(&keccak256(&preimage)[12..]))
+    }
+
+    /// Returns the address of a contract deployed with CREATE3 by the given factory with the given salt.
+    /// The factory deploys a proxy with CREATE2, which deploys the contract with CREATE as its first
+    /// transaction, so the address is independent of the init code of the contract.
+    pub fn create3(factory: &Self, salt: &[u8; 32]) -> Result<Self, AddressError> {
+        let proxy = Self::create2(factory, salt, &keccak256(&hex::decode(CREATE3_PROXY_INIT_CODE)?))?;
+        Self::create(&proxy, 1)
+    }
+
+    /// Returns the init code of an EIP-1167 minimal proxy, which delegates all calls to the given implementation.
+    pub fn minimal_proxy_init_code(implementation: &Self) -> Result<Vec<u8>, AddressError> {
+        let mut init_code = hex::decode("3d602d80600a3d3981f3363d3d373d3d3d363d73")?;
+        init_code.extend_from_slice(&implementation.to_bytes()?);
+        init_code.extend_from_slice(&hex::decode("5af43d82803e903d91602b57fd5bf3")?);
+        Ok(init_code)
+    }
+
+    /// Returns the address of an EIP-1167 minimal proxy of the given implementation,
+    /// deployed with CREATE2 by the given deployer with the given salt.
+    pub fn minimal_proxy(deployer: &Self, salt: &[u8; 32], implementation: &Self) -> Result<Self, AddressError> {
+        Self::create2(
+            deployer,
+            salt,
+            &keccak256(&Self::minimal_proxy_init_code(implementation)?),
+        )
+    }
+
+    /// Returns the 20 bytes of the address.
+    pub fn to_bytes(&self) -> Result<[u8; 20], AddressError> {
+        let address = hex::decode(&self.0[2..])?;
+        if address.len() != 20 {
+            return Err(AddressError::InvalidByteLength(address.len()));
+        }
+
+        let mut bytes = [0u8; 20];
+        bytes.copy_from_slice(&address);
+        Ok(bytes)
+    }
 }
 
 impl<'a> TryFrom<&'a str> for EthereumAddress {
@@ -172,6 +234,116 @@ mod tests {
         }
     }
 
+    mod contract_address {
+        use super::*;
+
+        fn address(address: &str) -> EthereumAddress {
+            EthereumAddress::from_str(address).unwrap()
+        }
+
+        fn bytes32(bytes: &str) -> [u8; 32] {
+            let mut bytes32 = [0u8; 32];
+            bytes32.copy_from_slice(&hex::decode(bytes).unwrap());
+            bytes32
+        }
+
+        #[test]
+        fn create() {
+            let sender = address("0x6ac7ea33f8831ea9dcc53393aaa88b25a785dbf0");
+            [
+                (0, "0xcd234a471b72ba2f1ccf0a70fcaba648a5eecd8d"),
+                (1, "0x343c43a37d37dff08ae8c4a11544c718abb4fcf8"),
+                (1000, "0xb9cdb7f5e62043c1e4eb7a6d76ef8ee246d364ec"),
+            ]
+            .iter()
+            .for_each(|(nonce, expected_address)| {
+                assert_eq!(
+                    address(expected_address),
+                    EthereumAddress::create(&sender, *nonce).unwrap()
+                );
+            });
+        }
+
+        // https://eips.ethereum.org/EIPS/eip-1014
+        #[test]
+        fn create2() {
+            [
+                (
+                    "0x0000000000000000000000000000000000000000",
+                    "0000000000000000000000000000000000000000000000000000000000000000",
+                    "00",
+                    "0x4D1A2e2bB4F88F0250f26Ffff098B0b30B26BF38",
+                ),
+                (
+                    "0xdeadbeef00000000000000000000000000000000",
+                    "0000000000000000000000000000000000000000000000000000000000000000",
+                    "00",
+                    "0xB928f69Bb1D91Cd65274e3c79d8986362984fDA3",
+                ),
+                (
+                    "0xdeadbeef00000000000000000000000000000000",
+                    "000000000000000000000000feed000000000000000000000000000000000000",
+                    "00",
+                    "0xD04116cDd17beBE565EB2422F2497E06cC1C9833",
+                ),
+                (
+                    "0x0000000000000000000000000000000000000000",
+                    "0000000000000000000000000000000000000000000000000000000000000000",
+                    "deadbeef",
+                    "0x70f2b2914A2a4b783FaEFb75f459A580616Fcb5e",
+                ),
+            ]
+            .iter()
+            .for_each(|(deployer, salt, init_code, expected_address)| {
+                let init_code_hash = keccak256(&hex::decode(init_code).unwrap());
+                let create2 = EthereumAddress::create2(&address(deployer), &bytes32(salt), &init_code_hash).unwrap();
+                assert_eq!(*expected_address, create2.to_string());
+            });
+        }
+
+        #[test]
+        fn create3() {
+            // The hash of the proxy init code of solmate CREATE3
+            assert_eq!(
+                "21c35dbe1b344a2488cf3321d6ce542f8e9f305544ff09e4993a62319a497c1f",
+                hex::encode(keccak256(&hex::decode(CREATE3_PROXY_INIT_CODE).unwrap()))
+            );
+
+            let factory = address("0x9fe46736679d2d9a65f0992f2272de9f3c7fa6e0");
+            let salt = [1u8; 32];
+            assert_eq!(
+                address("0x334ceb6d931cb307c81670b22c3510c911d42ef7"),
+                EthereumAddress::create3(&factory, &salt).unwrap()
+            );
+        }
+
+        #[test]
+        fn minimal_proxy() {
+            let deployer = address("0x9fe46736679d2d9a65f0992f2272de9f3c7fa6e0");
+            let implementation = address("0xbebebebebebebebebebebebebebebebebebebebe");
+            let salt = [1u8; 32];
+
+            let init_code = EthereumAddress::minimal_proxy_init_code(&implementation).unwrap();
+            assert_eq!(55, init_code.len());
+            assert_eq!(
+                "60a146579875aca032c9566ded6fc12bea362d66b7f2aa0f440db7bd8a90a4fe",
+                hex::encode(keccak256(&init_code))
+            );
+            assert_eq!(
+                address("0x6dd29eecc07f745f2ad620820b9a1fff122a804a"),
+                EthereumAddress::minimal_proxy(&deployer, &salt, &implementation).unwrap()
+            );
+        }
+
+        #[test]
+        fn invalid_address() {
+            let invalid = EthereumAddress("0xzz46736679d2d9a65f0992f2272de9f3c7fa6e0".into());
+            assert!(invalid.to_bytes().is_err());
+            assert!(EthereumAddress::create(&invalid, 0).is_err());
+            assert!(EthereumAddress::create3(&invalid, &[0u8; 32]).is_err());
+        }
+    }
+
     #[test]
     fn test_checksum_address_invalid() {
         // Mismatched keypair
@@ -1,3 +1,4 @@
+use wagyu_model::no_std::*;
 use wagyu_model::{Amount, AmountError};
 
 use core::fmt;
@@ -1,5 +1,6 @@
 use crate::network::EthereumNetwork;
 use wagyu_model::derivation_path::{ChildIndex, DerivationPath, DerivationPathError};
+use wagyu_model::no_std::*;
 
 use core::convert::TryFrom;
 use core::{fmt, marker::PhantomData, str::FromStr};
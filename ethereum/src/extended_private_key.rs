@@ -6,7 +6,7 @@ use crate::network::EthereumNetwork;
 use crate::private_key::EthereumPrivateKey;
 use crate::public_key::EthereumPublicKey;
 use wagyu_model::{
-    crypto::{checksum, hash160},
+    crypto::{checksum, constant_time_eq, hash160},
     AddressError, ChildIndex, DerivationPath, ExtendedPrivateKey, ExtendedPrivateKeyError, ExtendedPublicKey,
     PrivateKey,
 };
@@ -20,7 +20,7 @@ use sha2::Sha512;
 type HmacSha512 = Hmac<Sha512>;
 
 /// Represents a Ethereum Extended Private Key
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone)]
 pub struct EthereumExtendedPrivateKey<N> {
     /// The depth of key derivation, e.g. 0x00 for master nodes, 0x01 for level-1 derived keys, ...
     pub(super) depth: u8,
@@ -150,7 +150,7 @@ impl<N: EthereumNetwork> FromStr for EthereumExtendedPrivateKey<N> {
         }
 
         // Ethereum xkeys are mainnet only
-        if &data[0..4] != [0x04u8, 0x88, 0xAD, 0xE4] {
+        if data[0..4] != N::EXTENDED_PRIVATE_KEY_VERSION_BYTES {
             return Err(ExtendedPrivateKeyError::InvalidVersionBytes(data[0..4].to_vec()));
         };
 
@@ -185,12 +185,27 @@ impl<N: EthereumNetwork> FromStr for EthereumExtendedPrivateKey<N> {
     }
 }
 
+impl<N: EthereumNetwork> PartialEq for EthereumExtendedPrivateKey<N> {
+    /// Returns `true` if the extended private keys are equal, comparing their private keys and chain codes
+    /// in constant time.
+    fn eq(&self, other: &Self) -> bool {
+        let secrets_eq =
+            (self.private_key == other.private_key) & constant_time_eq(&self.chain_code, &other.chain_code);
+        secrets_eq
+            && self.depth == other.depth
+            && self.parent_fingerprint == other.parent_fingerprint
+            && self.child_index == other.child_index
+    }
+}
+
+impl<N: EthereumNetwork> Eq for EthereumExtendedPrivateKey<N> {}
+
 impl<N: EthereumNetwork> Display for EthereumExtendedPrivateKey<N> {
     /// BIP32 serialization format:
     /// https://github.com/ethereum/bips/blob/master/bip-0032.mediawiki#serialization-format
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
         let mut result = [0u8; 82];
-        result[0..4].copy_from_slice(&[0x04, 0x88, 0xAD, 0xE4][..]);
+        result[0..4].copy_from_slice(&N::EXTENDED_PRIVATE_KEY_VERSION_BYTES);
         result[4] = self.depth as u8;
         result[5..9].copy_from_slice(&self.parent_fingerprint[..]);
         result[9..13].copy_from_slice(&u32::from(self.child_index).to_be_bytes());
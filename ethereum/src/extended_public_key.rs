@@ -62,18 +62,16 @@ impl<N: EthereumNetwork> ExtendedPublicKey for EthereumExtendedPublicKey<N> {
 
         let mut extended_public_key = self.clone();
 
-        for index in path.to_vec()?.into_iter() {
-            let public_key_serialized = &self.public_key.to_secp256k1_public_key().serialize()[..];
-
-            let mut mac = HmacSha512::new_varkey(&self.chain_code)?;
-            match index {
-                // HMAC-SHA512(Key = cpar, Data = serP(Kpar) || ser32(i))
-                ChildIndex::Normal(_) => mac.input(public_key_serialized),
-                // Return failure
-                ChildIndex::Hardened(_) => {
-                    return Err(ExtendedPublicKeyError::InvalidChildNumber(1 << 31, u32::from(index)))
-                }
-            }
+        // Hardened child indices cannot be derived from an extended public key
+        for index in path.non_hardened_only()?.into_iter() {
+            let public_key_serialized = &extended_public_key
+                .public_key
+                .to_secp256k1_public_key()
+                .serialize_compressed()[..];
+
+            let mut mac = HmacSha512::new_varkey(&extended_public_key.chain_code)?;
+            // HMAC-SHA512(Key = cpar, Data = serP(Kpar) || ser32(i))
+            mac.input(public_key_serialized);
             // Append the child index in big-endian format
             mac.input(&u32::from(index).to_be_bytes());
             let hmac = mac.result().code();
@@ -81,7 +79,7 @@ impl<N: EthereumNetwork> ExtendedPublicKey for EthereumExtendedPublicKey<N> {
             let mut chain_code = [0u8; 32];
             chain_code[0..32].copy_from_slice(&hmac[32..]);
 
-            let mut public_key = self.public_key.to_secp256k1_public_key();
+            let mut public_key = extended_public_key.public_key.to_secp256k1_public_key();
             public_key.tweak_add_assign(&SecretKey::parse_slice(&hmac[..32])?)?;
             let public_key = Self::PublicKey::from_secp256k1_public_key(public_key);
 
@@ -112,6 +110,16 @@ impl<N: EthereumNetwork> ExtendedPublicKey for EthereumExtendedPublicKey<N> {
     }
 }
 
+impl<N: EthereumNetwork> EthereumExtendedPublicKey<N> {
+    /// Returns the fingerprint of the extended public key,
+    /// the first 32 bits of its key identifier (hash160(ECDSA_public_key)).
+    pub fn to_fingerprint(&self) -> [u8; 4] {
+        let mut fingerprint = [0u8; 4];
+        fingerprint.copy_from_slice(&hash160(&self.public_key.to_secp256k1_public_key().serialize_compressed())[0..4]);
+        fingerprint
+    }
+}
+
 impl<N: EthereumNetwork> FromStr for EthereumExtendedPublicKey<N> {
     type Err = ExtendedPublicKeyError;
 
@@ -121,7 +129,7 @@ impl<N: EthereumNetwork> FromStr for EthereumExtendedPublicKey<N> {
             return Err(ExtendedPublicKeyError::InvalidByteLength(data.len()));
         }
 
-        if &data[0..4] != [0x04u8, 0x88, 0xB2, 0x1E] {
+        if data[0..4] != N::EXTENDED_PUBLIC_KEY_VERSION_BYTES {
             return Err(ExtendedPublicKeyError::InvalidVersionBytes(data[0..4].to_vec()));
         };
 
@@ -163,7 +171,7 @@ impl<N: EthereumNetwork> fmt::Display for EthereumExtendedPublicKey<N> {
     /// https://github.com/ethereum/bips/blob/master/bip-0032.mediawiki#serialization-format
     fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
         let mut result = [0u8; 82];
-        result[0..4].copy_from_slice(&[0x04u8, 0x88, 0xB2, 0x1E][..]);
+        result[0..4].copy_from_slice(&N::EXTENDED_PUBLIC_KEY_VERSION_BYTES);
         result[4] = self.depth as u8;
         result[5..9].copy_from_slice(&self.parent_fingerprint[..]);
         result[9..13].copy_from_slice(&u32::from(self.child_index).to_be_bytes());
@@ -436,6 +444,19 @@ mod tests {
             });
         }
 
+        #[test]
+        fn derive_public() {
+            // m/0'/1/2' to m/0'/1/2'/2/1000000000, with each child derived from its parent public key
+            let (_, _, _, _, _, _, _, extended_public_key) = KEYPAIRS[3];
+            let (_, _, _, _, _, _, _, expected_extended_public_key) = KEYPAIRS[5];
+            let extended_public_key = EthereumExtendedPublicKey::<N>::from_str(extended_public_key).unwrap();
+            let path = EthereumDerivationPath::from_str("m/2/1000000000").unwrap();
+            assert_eq!(
+                expected_extended_public_key,
+                extended_public_key.derive(&path).unwrap().to_string()
+            );
+        }
+
         #[test]
         fn from_str() {
             KEYPAIRS.iter().for_each(
@@ -457,6 +478,19 @@ mod tests {
                 test_to_string::<N>(extended_public_key);
             });
         }
+
+        #[test]
+        fn to_fingerprint() {
+            // Each key of a test vector is the parent of the next key
+            KEYPAIRS.chunks(6).for_each(|keypairs| {
+                keypairs.windows(2).for_each(|pair| {
+                    let (_, _, _, _, _, _, _, extended_public_key) = pair[0];
+                    let (_, _, _, _, _, expected_fingerprint, _, _) = pair[1];
+                    let extended_public_key = EthereumExtendedPublicKey::<N>::from_str(extended_public_key).unwrap();
+                    assert_eq!(expected_fingerprint, hex::encode(extended_public_key.to_fingerprint()));
+                });
+            });
+        }
     }
 
     mod test_invalid {
@@ -6,6 +6,9 @@
 #![warn(unused_extern_crates, dead_code)]
 #![forbid(unsafe_code)]
 
+#[macro_use]
+extern crate failure;
+
 pub mod address;
 pub use self::address::*;
 
@@ -30,14 +33,26 @@ pub use self::mnemonic::*;
 pub mod network;
 pub use self::network::*;
 
+pub mod node_record;
+pub use self::node_record::*;
+
 pub mod private_key;
 pub use self::private_key::*;
 
 pub mod public_key;
 pub use self::public_key::*;
 
+pub mod signature;
+pub use self::signature::*;
+
 pub mod transaction;
 pub use self::transaction::*;
 
+pub mod typed_data;
+pub use self::typed_data::*;
+
+pub mod user_operation;
+pub use self::user_operation::*;
+
 pub mod wordlist;
 pub use self::wordlist::*;
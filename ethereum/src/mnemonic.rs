@@ -6,6 +6,8 @@ use crate::network::EthereumNetwork;
 use crate::private_key::EthereumPrivateKey;
 use crate::public_key::EthereumPublicKey;
 use crate::wordlist::EthereumWordlist;
+use wagyu_model::no_std::*;
+use wagyu_model::utilities::unicode::nfkd;
 use wagyu_model::{ExtendedPrivateKey, Mnemonic, MnemonicCount, MnemonicError, MnemonicExtended};
 
 use bitvec::prelude::*;
@@ -32,19 +34,11 @@ pub struct EthereumMnemonic<N: EthereumNetwork, W: EthereumWordlist> {
 impl<N: EthereumNetwork, W: EthereumWordlist> MnemonicCount for EthereumMnemonic<N, W> {
     /// Returns a new mnemonic given the word count.
     fn new_with_count<R: Rng>(rng: &mut R, word_count: u8) -> Result<Self, MnemonicError> {
-        let length: usize = match word_count {
-            12 => 16,
-            15 => 20,
-            18 => 24,
-            21 => 28,
-            24 => 32,
-            wc => return Err(MnemonicError::InvalidWordCount(wc)),
-        };
-
-        let entropy: [u8; 32] = rng.gen();
+        let mut entropy = vec![0u8; Self::entropy_length(word_count as usize)?];
+        rng.fill(&mut entropy[..]);
 
         Ok(Self {
-            entropy: entropy[0..length].to_vec(),
+            entropy,
             _network: PhantomData,
             _wordlist: PhantomData,
         })
@@ -69,20 +63,15 @@ impl<N: EthereumNetwork, W: EthereumWordlist> Mnemonic for EthereumMnemonic<N, W
 
     /// Returns the mnemonic for the given phrase.
     fn from_phrase(phrase: &str) -> Result<Self, MnemonicError> {
-        let mnemonic = phrase.split(" ").collect::<Vec<&str>>();
-
-        let length = match mnemonic.len() {
-            12 => 128,
-            15 => 160,
-            18 => 192,
-            21 => 224,
-            24 => 256,
-            wc => return Err(MnemonicError::InvalidWordCount(wc as u8)),
-        };
+        // Decomposes the phrase, and separates its words by any spaces, including ideographic spaces.
+        let normalized = nfkd(phrase);
+        let words = normalized.split_whitespace().collect::<Vec<&str>>();
+
+        let length = Self::entropy_length(words.len())? * 8;
 
         let mut entropy: BitVec<Msb0, u8> = BitVec::new();
 
-        for word in mnemonic {
+        for word in &words {
             let index = W::get_index(word)?;
             let index_u8: [u8; 2] = (index as u16).to_be_bytes();
             let index_slice = &BitVec::from_slice(&index_u8)[5..];
@@ -97,7 +86,7 @@ impl<N: EthereumNetwork, W: EthereumWordlist> Mnemonic for EthereumMnemonic<N, W
         };
 
         // Ensures the checksum word matches the checksum word in the given phrase.
-        match phrase == mnemonic.to_phrase()? {
+        match words.join(W::SEPARATOR) == mnemonic.to_phrase()? {
             true => Ok(mnemonic),
             false => Err(MnemonicError::InvalidPhrase(phrase.into())),
         }
@@ -142,7 +131,7 @@ impl<N: EthereumNetwork, W: EthereumWordlist> Mnemonic for EthereumMnemonic<N, W
             })
             .collect::<Vec<&str>>();
 
-        Ok(phrase.join(" "))
+        Ok(phrase.join(W::SEPARATOR))
     }
 
     /// Returns the private key of the corresponding mnemonic.
@@ -188,8 +177,10 @@ impl<N: EthereumNetwork, W: EthereumWordlist> EthereumMnemonic<N, W> {
     /// Returns a seed using the given password and mnemonic.
     fn to_seed(&self, password: Option<&str>) -> Result<Vec<u8>, MnemonicError> {
         let mut seed = vec![0u8; PBKDF2_BYTES];
-        let salt = format!("mnemonic{}", password.unwrap_or(""));
-        pbkdf2::<Hmac<Sha512>>(&self.to_phrase()?.as_bytes(), salt.as_bytes(), PBKDF2_ROUNDS, &mut seed);
+        // Decomposes the phrase and password, which separates the words of the phrase by spaces.
+        let phrase = nfkd(&self.to_phrase()?);
+        let salt = nfkd(&format!("mnemonic{}", password.unwrap_or("")));
+        pbkdf2::<Hmac<Sha512>>(phrase.as_bytes(), salt.as_bytes(), PBKDF2_ROUNDS, &mut seed);
         Ok(seed)
     }
 }
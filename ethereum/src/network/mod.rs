@@ -21,4 +21,8 @@ pub trait EthereumNetwork: Network {
     const NETWORK_ID: u32;
     const HD_PURPOSE: ChildIndex = ChildIndex::Hardened(44);
     const HD_COIN_TYPE: ChildIndex;
+    /// The version bytes of an extended private key (xprv), as Ethereum extended keys are mainnet only
+    const EXTENDED_PRIVATE_KEY_VERSION_BYTES: [u8; 4] = [0x04, 0x88, 0xAD, 0xE4];
+    /// The version bytes of an extended public key (xpub), as Ethereum extended keys are mainnet only
+    const EXTENDED_PUBLIC_KEY_VERSION_BYTES: [u8; 4] = [0x04, 0x88, 0xB2, 0x1E];
 }
@@ -0,0 +1,180 @@
+//!
+//! NodeRecord
+//!
+//! This module contains the identities of an Ethereum node derived from its secp256k1 key,
+//! the node ID of devp2p (enode), the Ethereum Node Record of discv5 (https://eips.ethereum.org/EIPS/eip-778),
+//! and the peer ID of libp2p (https://github.com/libp2p/specs/blob/master/peer-ids/peer-ids.md).
+//!
+
+use crate::private_key::EthereumPrivateKey;
+use crate::public_key::EthereumPublicKey;
+use crate::signature::{EthereumSignature, SignatureError};
+use wagyu_model::no_std::*;
+use wagyu_model::PrivateKey;
+
+use base58::ToBase58;
+use rlp::RlpStream;
+use tiny_keccak::keccak256;
+
+/// The identity scheme of a node record
+pub const NODE_RECORD_IDENTITY_SCHEME: &str = "v4";
+
+/// Represents an Ethereum Node Record (ENR) signed with the "v4" identity scheme
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EthereumNodeRecord {
+    /// The sequence number of the record, increased on each update
+    pub seq: u64,
+    /// The IPv4 address of the node
+    pub ip: Option<[u8; 4]>,
+    /// The TCP port of the node (devp2p)
+    pub tcp: Option<u16>,
+    /// The UDP port of the node (discovery)
+    pub udp: Option<u16>,
+    /// The public key of the node
+    pub public_key: EthereumPublicKey,
+    /// The signature of the content of the record, `r || s`
+    pub signature: [u8; 64],
+}
+
+impl EthereumNodeRecord {
+    /// Returns the record of the given sequence number and endpoint, signed with the given private key.
+    pub fn new(
+        private_key: &EthereumPrivateKey,
+        seq: u64,
+        ip: Option<[u8; 4]>,
+        tcp: Option<u16>,
+        udp: Option<u16>,
+    ) -> Result<Self, SignatureError> {
+        let mut record = Self {
+            seq,
+            ip,
+            tcp,
+            udp,
+            public_key: private_key.to_public_key(),
+            signature: [0u8; 64],
+        };
+
+        let signature = EthereumSignature::sign(&keccak256(&record.to_content(None)), private_key)?;
+        record.signature[..32].copy_from_slice(&signature.r);
+        record.signature[32..].copy_from_slice(&signature.s);
+        Ok(record)
+    }
+
+    /// Returns the RLP encoding of the record, `[signature, seq, k, v, ...]`.
+    pub fn to_rlp(&self) -> Vec<u8> {
+        self.to_content(Some(&self.signature))
+    }
+
+    /// Returns the node ID of the record, the keccak256 hash of the uncompressed public key.
+    pub fn to_node_id(&self) -> [u8; 32] {
+        keccak256(&self.to_enode_id())
+    }
+
+    /// Returns the devp2p node ID of the node, the uncompressed public key without its prefix,
+    /// as in `enode://<id>@<ip>:<port>`.
+    pub fn to_enode_id(&self) -> [u8; 64] {
+        let mut enode_id = [0u8; 64];
+        enode_id.copy_from_slice(&self.public_key.to_secp256k1_public_key().serialize()[1..]);
+        enode_id
+    }
+
+    /// Returns the libp2p peer ID of the node, the base58 identity multihash
+    /// of the protobuf encoding of its compressed public key.
+    pub fn to_peer_id(&self) -> String {
+        // The protobuf encoding of the key type (secp256k1) and the key data
+        let mut public_key = vec![0x08, 0x02, 0x12, 0x21];
+        public_key.extend_from_slice(&self.public_key.to_secp256k1_public_key().serialize_compressed());
+
+        // The identity multihash, for public keys of at most 42 bytes
+        let mut peer_id = vec![0x00, public_key.len() as u8];
+        peer_id.extend_from_slice(&public_key);
+        peer_id.to_base58()
+    }
+
+    /// Returns the RLP encoding of the content of the record, `[seq, k, v, ...]`,
+    /// preceded by the given signature, with its key-value pairs sorted by key.
+    fn to_content(&self, signature: Option<&[u8; 64]>) -> Vec<u8> {
+        // The "id" and "secp256k1" pairs are always present
+        let pairs = 2 + self.ip.is_some() as usize + self.tcp.is_some() as usize + self.udp.is_some() as usize;
+
+        let mut stream = RlpStream::new_list(1 + 2 * pairs + signature.is_some() as usize);
+        if let Some(signature) = signature {
+            stream.append(&signature.to_vec());
+        }
+        stream.append(&self.seq);
+        stream.append(&"id");
+        stream.append(&NODE_RECORD_IDENTITY_SCHEME);
+        if let Some(ip) = self.ip {
+            stream.append(&"ip");
+            stream.append(&ip.to_vec());
+        }
+        stream.append(&"secp256k1");
+        stream.append(
+            &self
+                .public_key
+                .to_secp256k1_public_key()
+                .serialize_compressed()
+                .to_vec(),
+        );
+        if let Some(tcp) = self.tcp {
+            stream.append(&"tcp");
+            stream.append(&tcp);
+        }
+        if let Some(udp) = self.udp {
+            stream.append(&"udp");
+            stream.append(&udp);
+        }
+        stream.out()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use base58::FromBase58;
+    use core::str::FromStr;
+
+    // https://eips.ethereum.org/EIPS/eip-778
+    const PRIVATE_KEY: &str = "b71c71a67e1177ad4e901695e1b4b9ee17ae16c6668d313eac2f96dbcda3f291";
+
+    #[test]
+    fn new() {
+        let private_key = EthereumPrivateKey::from_str(PRIVATE_KEY).unwrap();
+        let record = EthereumNodeRecord::new(&private_key, 1, Some([127, 0, 0, 1]), None, Some(30303)).unwrap();
+
+        assert_eq!(
+            "f884b8407098ad865b00a582051940cb9cf36836572411a47278783077011599ed5cd16b76f2635f4e234738f30813a89eb9137e3e3df5266e3a1f11df72ecf1145ccb9c01826964827634826970847f00000189736563703235366b31a103ca634cae0d49acb401d8a4c6b6fe8c55b70d115bf400769cc1400f3258cd31388375647082765f",
+            hex::encode(record.to_rlp())
+        );
+        assert_eq!(
+            "a448f24c6d18e575453db13171562b71999873db5b286df957af199ec94617f7",
+            hex::encode(record.to_node_id())
+        );
+    }
+
+    #[test]
+    fn to_peer_id() {
+        let private_key = EthereumPrivateKey::from_str(PRIVATE_KEY).unwrap();
+        let record = EthereumNodeRecord::new(&private_key, 1, None, None, None).unwrap();
+
+        let peer_id = record.to_peer_id();
+        assert!(peer_id.starts_with("16Uiu2"));
+        assert_eq!(
+            "ca634cae0d49acb401d8a4c6b6fe8c55b70d115bf400769cc1400f3258cd3138",
+            hex::encode(&peer_id.from_base58().unwrap()[7..])
+        );
+    }
+
+    #[test]
+    fn signature() {
+        let private_key = EthereumPrivateKey::from_str(PRIVATE_KEY).unwrap();
+        let record = EthereumNodeRecord::new(&private_key, 7, Some([10, 0, 0, 1]), Some(30303), Some(30301)).unwrap();
+
+        // The signature is over the content of the record, excluding the signature
+        let hash = keccak256(&record.to_content(None));
+        let signature = EthereumSignature::sign(&hash, &private_key).unwrap();
+        assert_eq!(signature.r, record.signature[..32]);
+        assert_eq!(signature.s, record.signature[32..]);
+        assert_eq!(record.public_key, signature.recover(&hash).unwrap());
+    }
+}
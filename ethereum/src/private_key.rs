@@ -1,14 +1,15 @@
 use crate::address::EthereumAddress;
 use crate::format::EthereumFormat;
 use crate::public_key::EthereumPublicKey;
-use wagyu_model::{Address, AddressError, PrivateKey, PrivateKeyError, PublicKey};
+use wagyu_model::no_std::*;
+use wagyu_model::{crypto::constant_time_eq, Address, AddressError, PrivateKey, PrivateKeyError, PublicKey};
 
 use core::{fmt, fmt::Display, str::FromStr};
 use rand::Rng;
 use secp256k1;
 
 /// Represents an Ethereum private key
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Eq)]
 pub struct EthereumPrivateKey(secp256k1::SecretKey);
 
 impl PrivateKey for EthereumPrivateKey {
@@ -58,6 +59,13 @@ impl FromStr for EthereumPrivateKey {
     }
 }
 
+impl PartialEq for EthereumPrivateKey {
+    /// Returns `true` if the private keys are equal, comparing their secret keys in constant time.
+    fn eq(&self, other: &Self) -> bool {
+        constant_time_eq(&self.0.serialize(), &other.0.serialize())
+    }
+}
+
 impl Display for EthereumPrivateKey {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let mut private_key = [0u8; 32];
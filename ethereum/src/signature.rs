@@ -0,0 +1,341 @@
+use crate::private_key::EthereumPrivateKey;
+use crate::public_key::EthereumPublicKey;
+use wagyu_model::no_std::*;
+
+use core::{fmt, str::FromStr};
+use hmac::{Hmac, Mac};
+use secp256k1;
+use sha2::Sha256;
+use tiny_keccak::keccak256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+#[derive(Debug, Fail)]
+pub enum SignatureError {
+    #[fail(display = "{}: {}", _0, _1)]
+    Crate(&'static str, String),
+
+    #[fail(display = "invalid message hash length: {{ expected: 32, found: {:?} }}", _0)]
+    InvalidMessageHashLength(usize),
+
+    #[fail(display = "invalid signature length: {{ expected: 65, found: {:?} }}", _0)]
+    InvalidSignatureLength(usize),
+
+    #[fail(display = "invalid signature recovery id: {}", _0)]
+    InvalidRecoveryId(u8),
+}
+
+impl From<hex::FromHexError> for SignatureError {
+    fn from(error: hex::FromHexError) -> Self {
+        SignatureError::Crate("hex", format!("{:?}", error))
+    }
+}
+
+impl From<secp256k1::Error> for SignatureError {
+    fn from(error: secp256k1::Error) -> Self {
+        SignatureError::Crate("libsecp256k1", format!("{:?}", error))
+    }
+}
+
+/// Represents a recoverable Ethereum signature over a 32-byte message hash
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EthereumSignature {
+    /// The R field of the signature
+    pub r: [u8; 32],
+    /// The S field of the signature
+    pub s: [u8; 32],
+    /// The V field of the signature (27 or 28)
+    pub v: u8,
+}
+
+/// Represents the derivation of the deterministic nonce of a signature (RFC 6979, HMAC-SHA256),
+/// for auditing that the nonce is not biased. The nonce reveals the private key of the signature,
+/// and must never be shared.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SignatureNonce {
+    /// The intermediate values of `K` and `V` of the HMAC-DRBG, in order
+    pub steps: Vec<(String, [u8; 32])>,
+    /// The nonce `k`
+    pub k: [u8; 32],
+    /// The x-coordinate of `k * G` modulo the curve order, the R field of the signature
+    pub r: [u8; 32],
+}
+
+impl SignatureNonce {
+    /// Returns the derivation of the nonce of the given message hash and private key (RFC 6979 section 3.2).
+    pub fn derive(message_hash: &[u8], private_key: &EthereumPrivateKey) -> Result<Self, SignatureError> {
+        if message_hash.len() != 32 {
+            return Err(SignatureError::InvalidMessageHashLength(message_hash.len()));
+        }
+
+        // The message hash is reduced modulo the curve order (bits2octets)
+        let x = private_key.to_secp256k1_secret_key().serialize();
+        let h1 = secp256k1::Message::parse_slice(message_hash)?.serialize();
+
+        let hmac = |key: &[u8; 32], data: &[&[u8]]| -> Result<[u8; 32], SignatureError> {
+            let mut mac =
+                HmacSha256::new_varkey(key).map_err(|error| SignatureError::Crate("hmac", format!("{:?}", error)))?;
+            data.iter().for_each(|data| mac.input(data));
+            let mut output = [0u8; 32];
+            output.copy_from_slice(&mac.result().code());
+            Ok(output)
+        };
+
+        let mut steps = vec![];
+        let mut v = [0x01u8; 32];
+        let mut k = [0x00u8; 32];
+        k = hmac(&k, &[&v, &[0x00], &x, &h1])?;
+        steps.push(("K = HMAC_K(V || 0x00 || x || h1)".into(), k));
+        v = hmac(&k, &[&v])?;
+        steps.push(("V = HMAC_K(V)".into(), v));
+        k = hmac(&k, &[&v, &[0x01], &x, &h1])?;
+        steps.push(("K = HMAC_K(V || 0x01 || x || h1)".into(), k));
+        v = hmac(&k, &[&v])?;
+        steps.push(("V = HMAC_K(V)".into(), v));
+
+        // The candidate nonce is retried until it is within [1, n - 1]
+        loop {
+            v = hmac(&k, &[&v])?;
+            steps.push(("T = V = HMAC_K(V)".into(), v));
+
+            if let Ok(nonce) = secp256k1::SecretKey::parse(&v) {
+                let point = secp256k1::PublicKey::from_secret_key(&nonce).serialize_compressed();
+                let mut r = secp256k1::curve::Scalar::default();
+                let mut x_coordinate = [0u8; 32];
+                x_coordinate.copy_from_slice(&point[1..33]);
+                let _ = r.set_b32(&x_coordinate);
+
+                return Ok(Self {
+                    steps,
+                    k: v,
+                    r: r.b32(),
+                });
+            }
+
+            k = hmac(&k, &[&v, &[0x00]])?;
+            steps.push(("K = HMAC_K(V || 0x00)".into(), k));
+            v = hmac(&k, &[&v])?;
+            steps.push(("V = HMAC_K(V)".into(), v));
+        }
+    }
+}
+
+impl EthereumSignature {
+    /// Returns the signature of the given message hash using the given private key.
+    /// The nonce is derived deterministically (RFC 6979).
+    pub fn sign(message_hash: &[u8], private_key: &EthereumPrivateKey) -> Result<Self, SignatureError> {
+        if message_hash.len() != 32 {
+            return Err(SignatureError::InvalidMessageHashLength(message_hash.len()));
+        }
+
+        let (signature, recovery_id) = secp256k1::sign(
+            &secp256k1::Message::parse_slice(message_hash)?,
+            &private_key.to_secp256k1_secret_key(),
+        );
+        let signature = signature.serialize();
+
+        let mut r = [0u8; 32];
+        let mut s = [0u8; 32];
+        r.copy_from_slice(&signature[0..32]);
+        s.copy_from_slice(&signature[32..64]);
+
+        Ok(Self {
+            r,
+            s,
+            v: recovery_id.serialize() + 27,
+        })
+    }
+
+    /// Returns the public key of the signer given the signed message hash (ecrecover).
+    pub fn recover(&self, message_hash: &[u8]) -> Result<EthereumPublicKey, SignatureError> {
+        if message_hash.len() != 32 {
+            return Err(SignatureError::InvalidMessageHashLength(message_hash.len()));
+        }
+
+        let mut signature = [0u8; 64];
+        signature[0..32].copy_from_slice(&self.r);
+        signature[32..64].copy_from_slice(&self.s);
+
+        Ok(EthereumPublicKey::from_secp256k1_public_key(secp256k1::recover(
+            &secp256k1::Message::parse_slice(message_hash)?,
+            &secp256k1::Signature::parse(&signature),
+            &self.to_recovery_id()?,
+        )?))
+    }
+
+    /// Returns the hash of the given message with the EIP-191 personal message prefix,
+    /// `keccak256("\x19Ethereum Signed Message:\n" || len(message) || message)`, as used by `personal_sign`.
+    pub fn personal_message_hash(message: &[u8]) -> [u8; 32] {
+        let mut prefixed_message = format!("\x19Ethereum Signed Message:\n{}", message.len()).into_bytes();
+        prefixed_message.extend_from_slice(message);
+        keccak256(&prefixed_message)
+    }
+
+    /// Returns the signature given the 65-byte `r || s || v` encoding.
+    /// Accepts a `v` of 0, 1, 27, or 28.
+    pub fn from_bytes(signature: &[u8]) -> Result<Self, SignatureError> {
+        if signature.len() != 65 {
+            return Err(SignatureError::InvalidSignatureLength(signature.len()));
+        }
+
+        let v = match signature[64] {
+            0 | 1 => signature[64] + 27,
+            27 | 28 => signature[64],
+            v => return Err(SignatureError::InvalidRecoveryId(v)),
+        };
+
+        let mut r = [0u8; 32];
+        let mut s = [0u8; 32];
+        r.copy_from_slice(&signature[0..32]);
+        s.copy_from_slice(&signature[32..64]);
+
+        Ok(Self { r, s, v })
+    }
+
+    /// Returns the 65-byte `r || s || v` encoding of the signature.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut signature = Vec::with_capacity(65);
+        signature.extend_from_slice(&self.r);
+        signature.extend_from_slice(&self.s);
+        signature.push(self.v);
+        signature
+    }
+
+    /// Returns the secp256k1 recovery id of the signature.
+    pub fn to_recovery_id(&self) -> Result<secp256k1::RecoveryId, SignatureError> {
+        Ok(secp256k1::RecoveryId::parse(self.v - 27)?)
+    }
+}
+
+impl FromStr for EthereumSignature {
+    type Err = SignatureError;
+
+    fn from_str(signature: &str) -> Result<Self, Self::Err> {
+        let signature = match signature.starts_with("0x") {
+            true => &signature[2..],
+            false => signature,
+        };
+        Self::from_bytes(&hex::decode(signature)?)
+    }
+}
+
+impl fmt::Display for EthereumSignature {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "0x{}", hex::encode(self.to_bytes()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format::EthereumFormat;
+    use sha2::Digest;
+    use wagyu_model::PublicKey;
+
+    #[test]
+    fn sign() {
+        // https://github.com/ethereum/EIPs/blob/master/assets/eip-712/Example.js
+        let private_key = EthereumPrivateKey::from_str(&hex::encode(keccak256(b"cow"))).unwrap();
+        let message_hash = hex::decode("be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2").unwrap();
+
+        let signature = EthereumSignature::sign(&message_hash, &private_key).unwrap();
+        assert_eq!(28, signature.v);
+        assert_eq!(
+            "4355c47d63924e8a72e509b65029052eb6c299d53a04e167c5775fd466751c9d",
+            hex::encode(signature.r)
+        );
+        assert_eq!(
+            "07299936d304c153f6443dfa05f40ff007d72911b6f72307f996231605b91562",
+            hex::encode(signature.s)
+        );
+    }
+
+    #[test]
+    fn nonce() {
+        // https://bitcointalk.org/index.php?topic=285142.msg3299061#msg3299061
+        let private_key =
+            EthereumPrivateKey::from_str("0000000000000000000000000000000000000000000000000000000000000001").unwrap();
+        let message_hash = Sha256::digest(b"Satoshi Nakamoto");
+
+        let nonce = SignatureNonce::derive(&message_hash, &private_key).unwrap();
+        assert_eq!(
+            "8f8a276c19f4149656b280621e358cce24f5f52542772691ee69063b74f15d15",
+            hex::encode(nonce.k)
+        );
+        assert_eq!(5, nonce.steps.len());
+
+        let signature = EthereumSignature::sign(&message_hash, &private_key).unwrap();
+        assert_eq!(signature.r, nonce.r);
+        assert_eq!(
+            "934b1ea10a4b3c1757e2b0c017d0b6143ce3c9a7e6a4a49860d7a6ab210ee3d8",
+            hex::encode(nonce.r)
+        );
+    }
+
+    #[test]
+    fn nonce_matches_signature() {
+        let private_key = EthereumPrivateKey::from_str(&hex::encode(keccak256(b"cow"))).unwrap();
+        for message in &[&b"a"[..], b"b", b"c"] {
+            let message_hash = keccak256(message);
+            let nonce = SignatureNonce::derive(&message_hash, &private_key).unwrap();
+            assert_eq!(EthereumSignature::sign(&message_hash, &private_key).unwrap().r, nonce.r);
+        }
+    }
+
+    #[test]
+    fn recover() {
+        // https://web3js.readthedocs.io/en/v1.2.11/web3-eth-accounts.html#sign
+        let message_hash = EthereumSignature::personal_message_hash(b"Some data");
+        assert_eq!(
+            "1da44b586eb0729ff70a73c326926f6ed5a25f5b056e7f47fbc6e58d86871655",
+            hex::encode(message_hash)
+        );
+
+        let signature = EthereumSignature::from_str("0xb91467e570a6466aa9e9876cbcd013baba02900b8979d43fe208a4a4f339f5fd6007e74cd82e037b800186422fc2da167c747ef045e5d18a5f5d4300f8e1a0291c").unwrap();
+        let public_key = signature.recover(&message_hash).unwrap();
+        assert_eq!(
+            "0x2c7536E3605D9C16a7a3D7b1898e529396a65c23",
+            public_key.to_address(&EthereumFormat::Standard).unwrap().to_string()
+        );
+
+        let private_key =
+            EthereumPrivateKey::from_str("4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318").unwrap();
+        assert_eq!(signature, EthereumSignature::sign(&message_hash, &private_key).unwrap());
+        assert_eq!(EthereumPublicKey::from_private_key(&private_key), public_key);
+    }
+
+    #[test]
+    fn recover_mismatched_message() {
+        let private_key =
+            EthereumPrivateKey::from_str("4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318").unwrap();
+        let signature =
+            EthereumSignature::sign(&EthereumSignature::personal_message_hash(b"Some data"), &private_key).unwrap();
+
+        let public_key = signature
+            .recover(&EthereumSignature::personal_message_hash(b"Other data"))
+            .unwrap();
+        assert_ne!(EthereumPublicKey::from_private_key(&private_key), public_key);
+        assert!(signature.recover(&[0u8; 31]).is_err());
+    }
+
+    #[test]
+    fn from_str() {
+        let expected_signature = "0x4355c47d63924e8a72e509b65029052eb6c299d53a04e167c5775fd466751c9d07299936d304c153f6443dfa05f40ff007d72911b6f72307f996231605b915621c";
+        let signature = EthereumSignature::from_str(expected_signature).unwrap();
+        assert_eq!(28, signature.v);
+        assert_eq!(expected_signature, signature.to_string());
+
+        // A raw recovery id of 1 is normalized to 28
+        let signature = EthereumSignature::from_str(&format!("{}01", &expected_signature[2..130])).unwrap();
+        assert_eq!(28, signature.v);
+    }
+
+    #[test]
+    fn invalid_signature() {
+        let signature = "0x4355c47d63924e8a72e509b65029052eb6c299d53a04e167c5775fd466751c9d";
+        assert!(EthereumSignature::from_str(signature).is_err());
+
+        let signature = "0x4355c47d63924e8a72e509b65029052eb6c299d53a04e167c5775fd466751c9d07299936d304c153f6443dfa05f40ff007d72911b6f72307f996231605b91562ff";
+        assert!(EthereumSignature::from_str(signature).is_err());
+    }
+}
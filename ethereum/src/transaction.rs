@@ -4,6 +4,7 @@ use crate::format::EthereumFormat;
 use crate::network::EthereumNetwork;
 use crate::private_key::EthereumPrivateKey;
 use crate::public_key::EthereumPublicKey;
+use wagyu_model::no_std::*;
 use wagyu_model::{PrivateKey, PublicKey, Transaction, TransactionError, TransactionId};
 
 use core::{fmt, marker::PhantomData, str::FromStr};
@@ -0,0 +1,592 @@
+//!
+//! TypedData
+//!
+//! This module contains the representation of EIP-712 typed structured data and the
+//! functions for hashing and signing it (https://eips.ethereum.org/EIPS/eip-712).
+//!
+//! The signing hash is `keccak256("\x19\x01" || domainSeparator || hashStruct(message))`, where
+//! - `domainSeparator = hashStruct(eip712Domain)`, and
+//! - `hashStruct(s) = keccak256(typeHash || encodeData(s))`.
+//!
+
+use crate::private_key::EthereumPrivateKey;
+use crate::signature::{EthereumSignature, SignatureError};
+use wagyu_model::no_std::*;
+
+use core::str::FromStr;
+use ethereum_types::U256;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use tiny_keccak::keccak256;
+
+/// The name of the EIP-712 domain type
+pub const EIP712_DOMAIN: &str = "EIP712Domain";
+/// The name of the EIP-2612 permit type
+pub const PERMIT: &str = "Permit";
+
+#[derive(Debug, Fail)]
+pub enum TypedDataError {
+    #[fail(display = "{}: {}", _0, _1)]
+    Crate(&'static str, String),
+
+    #[fail(
+        display = "invalid array length for type {}: {{ expected: {:?}, found: {:?} }}",
+        _0, _1, _2
+    )]
+    InvalidArrayLength(String, usize, usize),
+
+    #[fail(display = "invalid value for type {}: {}", _0, _1)]
+    InvalidValue(String, String),
+
+    #[fail(display = "missing field {} in type {}", _0, _1)]
+    MissingField(String, String),
+
+    #[fail(display = "{}", _0)]
+    SignatureError(SignatureError),
+
+    #[fail(display = "unsupported type: {}", _0)]
+    UnsupportedType(String),
+}
+
+impl From<hex::FromHexError> for TypedDataError {
+    fn from(error: hex::FromHexError) -> Self {
+        TypedDataError::Crate("hex", format!("{:?}", error))
+    }
+}
+
+impl From<serde_json::error::Error> for TypedDataError {
+    fn from(error: serde_json::error::Error) -> Self {
+        TypedDataError::Crate("serde_json", format!("{:?}", error))
+    }
+}
+
+impl From<SignatureError> for TypedDataError {
+    fn from(error: SignatureError) -> Self {
+        TypedDataError::SignatureError(error)
+    }
+}
+
+/// Represents a member of an EIP-712 struct type
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TypedDataField {
+    /// The name of the member
+    pub name: String,
+    /// The type of the member
+    #[serde(rename = "type")]
+    pub field_type: String,
+}
+
+/// Represents EIP-712 typed structured data, as accepted by `eth_signTypedData_v4`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TypedData {
+    /// The struct type definitions, including `EIP712Domain`
+    pub types: BTreeMap<String, Vec<TypedDataField>>,
+    /// The type of the message
+    pub primary_type: String,
+    /// The domain separator values
+    pub domain: Value,
+    /// The message values
+    pub message: Value,
+}
+
+impl TypedData {
+    /// Returns the encoding of the given struct type, followed by its referenced struct types
+    /// in alphabetical order, e.g. `Mail(Person from,Person to,string contents)Person(string name,address wallet)`.
+    pub fn encode_type(&self, primary_type: &str) -> Result<String, TypedDataError> {
+        let mut dependencies = vec![];
+        self.find_dependencies(primary_type, &mut dependencies)?;
+        dependencies.retain(|dependency| dependency != primary_type);
+        dependencies.sort();
+        dependencies.insert(0, primary_type.to_string());
+
+        let mut encoding = String::new();
+        for dependency in dependencies {
+            let fields = self.fields(&dependency)?;
+            let members = fields
+                .iter()
+                .map(|field| format!("{} {}", field.field_type, field.name))
+                .collect::<Vec<String>>()
+                .join(",");
+            encoding.push_str(&format!("{}({})", dependency, members));
+        }
+        Ok(encoding)
+    }
+
+    /// Returns the type hash of the given struct type.
+    pub fn type_hash(&self, primary_type: &str) -> Result<[u8; 32], TypedDataError> {
+        Ok(keccak256(self.encode_type(primary_type)?.as_bytes()))
+    }
+
+    /// Returns the hash of the given struct value.
+    pub fn hash_struct(&self, primary_type: &str, data: &Value) -> Result<[u8; 32], TypedDataError> {
+        let mut encoding = self.type_hash(primary_type)?.to_vec();
+        for field in self.fields(primary_type)? {
+            let value = match data.get(&field.name) {
+                Some(value) => value,
+                None => return Err(TypedDataError::MissingField(field.name.clone(), primary_type.into())),
+            };
+            encoding.extend_from_slice(&self.encode_value(&field.field_type, value)?);
+        }
+        Ok(keccak256(&encoding))
+    }
+
+    /// Returns the domain separator.
+    pub fn domain_separator(&self) -> Result<[u8; 32], TypedDataError> {
+        self.hash_struct(EIP712_DOMAIN, &self.domain)
+    }
+
+    /// Returns the hash of the message.
+    pub fn message_hash(&self) -> Result<[u8; 32], TypedDataError> {
+        self.hash_struct(&self.primary_type, &self.message)
+    }
+
+    /// Returns the hash to be signed, `keccak256("\x19\x01" || domainSeparator || hashStruct(message))`.
+    /// If the primary type is `EIP712Domain`, the message hash is omitted.
+    pub fn to_signing_hash(&self) -> Result<[u8; 32], TypedDataError> {
+        let mut encoding = vec![0x19, 0x01];
+        encoding.extend_from_slice(&self.domain_separator()?);
+        if self.primary_type != EIP712_DOMAIN {
+            encoding.extend_from_slice(&self.message_hash()?);
+        }
+        Ok(keccak256(&encoding))
+    }
+
+    /// Returns the signature of the typed data given the private key of the signer.
+    pub fn sign(&self, private_key: &EthereumPrivateKey) -> Result<EthereumSignature, TypedDataError> {
+        Ok(EthereumSignature::sign(&self.to_signing_hash()?, private_key)?)
+    }
+
+    /// Returns the members of the given struct type.
+    fn fields(&self, struct_type: &str) -> Result<&Vec<TypedDataField>, TypedDataError> {
+        match self.types.get(struct_type) {
+            Some(fields) => Ok(fields),
+            None => Err(TypedDataError::UnsupportedType(struct_type.into())),
+        }
+    }
+
+    /// Collects the given struct type and every struct type it references.
+    fn find_dependencies(&self, struct_type: &str, dependencies: &mut Vec<String>) -> Result<(), TypedDataError> {
+        if dependencies.iter().any(|dependency| dependency == struct_type) {
+            return Ok(());
+        }
+        dependencies.push(struct_type.to_string());
+
+        for field in self.fields(struct_type)? {
+            let base_type = field.field_type.split('[').next().unwrap_or_default();
+            if self.types.contains_key(base_type) {
+                self.find_dependencies(base_type, dependencies)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the 32-byte encoding of the given value for the given type.
+    fn encode_value(&self, value_type: &str, value: &Value) -> Result<[u8; 32], TypedDataError> {
+        let invalid_value = || TypedDataError::InvalidValue(value_type.into(), value.to_string());
+
+        // Arrays are encoded as the hash of the concatenated encodings of their elements
+        if value_type.ends_with(']') {
+            let open = value_type.rfind('[').ok_or_else(invalid_value)?;
+            let (element_type, length) = (&value_type[..open], &value_type[open + 1..value_type.len() - 1]);
+            let elements = value.as_array().ok_or_else(invalid_value)?;
+
+            if !length.is_empty() {
+                let length = usize::from_str(length).map_err(|_| TypedDataError::UnsupportedType(value_type.into()))?;
+                if length != elements.len() {
+                    return Err(TypedDataError::InvalidArrayLength(
+                        value_type.into(),
+                        length,
+                        elements.len(),
+                    ));
+                }
+            }
+
+            let mut encoding = vec![];
+            for element in elements {
+                encoding.extend_from_slice(&self.encode_value(element_type, element)?);
+            }
+            return Ok(keccak256(&encoding));
+        }
+
+        // Structs are encoded as their struct hash
+        if self.types.contains_key(value_type) {
+            return self.hash_struct(value_type, value);
+        }
+
+        let mut encoding = [0u8; 32];
+        match value_type {
+            "address" => {
+                let address = decode_hex(value.as_str().ok_or_else(invalid_value)?)?;
+                if address.len() != 20 {
+                    return Err(invalid_value());
+                }
+                encoding[12..].copy_from_slice(&address);
+            }
+            "bool" => {
+                encoding[31] = match value {
+                    Value::Bool(boolean) => *boolean as u8,
+                    Value::String(boolean) if boolean == "true" => 1,
+                    Value::String(boolean) if boolean == "false" => 0,
+                    _ => return Err(invalid_value()),
+                };
+            }
+            "bytes" => encoding = keccak256(&decode_hex(value.as_str().ok_or_else(invalid_value)?)?),
+            "string" => encoding = keccak256(value.as_str().ok_or_else(invalid_value)?.as_bytes()),
+            _ if value_type.starts_with("bytes") => {
+                let size = usize::from_str(&value_type[5..])
+                    .map_err(|_| TypedDataError::UnsupportedType(value_type.into()))?;
+                let bytes = decode_hex(value.as_str().ok_or_else(invalid_value)?)?;
+                if size == 0 || size > 32 || bytes.len() != size {
+                    return Err(invalid_value());
+                }
+                encoding[..size].copy_from_slice(&bytes);
+            }
+            _ if value_type.starts_with("uint") || value_type.starts_with("int") => {
+                let signed = value_type.starts_with("int");
+                let bits = match &value_type[if signed { 3 } else { 4 }..] {
+                    "" => 256,
+                    bits => usize::from_str(bits).map_err(|_| TypedDataError::UnsupportedType(value_type.into()))?,
+                };
+                if bits == 0 || bits > 256 || bits % 8 != 0 {
+                    return Err(TypedDataError::UnsupportedType(value_type.into()));
+                }
+
+                // A signed integer of N bits is at least -2^(N-1) and less than 2^(N-1)
+                let (negative, magnitude) = decode_integer(value).ok_or_else(invalid_value)?;
+                let in_range = match (signed, negative) {
+                    (false, false) => magnitude.bits() <= bits,
+                    (false, true) => false,
+                    (true, false) => magnitude.bits() < bits,
+                    (true, true) => magnitude <= U256::one() << (bits - 1),
+                };
+                if !in_range {
+                    return Err(invalid_value());
+                }
+
+                // Signed integers are sign-extended to 256 bits in two's complement
+                let integer = match negative {
+                    true => (!magnitude).overflowing_add(U256::one()).0,
+                    false => magnitude,
+                };
+                integer.to_big_endian(&mut encoding);
+            }
+            _ => return Err(TypedDataError::UnsupportedType(value_type.into())),
+        };
+        Ok(encoding)
+    }
+}
+
+/// Represents an EIP-2612 permit, which approves a spender for the ERC-20 tokens of an owner
+/// with a signature instead of a transaction (https://eips.ethereum.org/EIPS/eip-2612)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Permit {
+    /// The name of the token, as in its EIP-712 domain
+    pub name: String,
+    /// The version of the token, as in its EIP-712 domain
+    pub version: String,
+    /// The chain id of the token
+    pub chain_id: u64,
+    /// The address of the token
+    pub verifying_contract: String,
+    /// The owner of the tokens, and signer of the permit
+    pub owner: String,
+    /// The spender approved by the permit
+    pub spender: String,
+    /// The amount of tokens approved, in the smallest unit of the token
+    pub value: String,
+    /// The current permit nonce of the owner, as returned by `nonces(owner)`
+    pub nonce: String,
+    /// The timestamp after which the permit expires
+    pub deadline: String,
+}
+
+impl Permit {
+    /// Returns the typed data of the permit,
+    /// `Permit(address owner,address spender,uint256 value,uint256 nonce,uint256 deadline)`.
+    pub fn to_typed_data(&self) -> TypedData {
+        let field = |name: &str, field_type: &str| TypedDataField {
+            name: name.into(),
+            field_type: field_type.into(),
+        };
+
+        let mut types = BTreeMap::new();
+        types.insert(
+            EIP712_DOMAIN.to_string(),
+            vec![
+                field("name", "string"),
+                field("version", "string"),
+                field("chainId", "uint256"),
+                field("verifyingContract", "address"),
+            ],
+        );
+        types.insert(
+            PERMIT.to_string(),
+            vec![
+                field("owner", "address"),
+                field("spender", "address"),
+                field("value", "uint256"),
+                field("nonce", "uint256"),
+                field("deadline", "uint256"),
+            ],
+        );
+
+        TypedData {
+            types,
+            primary_type: PERMIT.into(),
+            domain: json!({
+                "name": self.name,
+                "version": self.version,
+                "chainId": self.chain_id,
+                "verifyingContract": self.verifying_contract,
+            }),
+            message: json!({
+                "owner": self.owner,
+                "spender": self.spender,
+                "value": self.value,
+                "nonce": self.nonce,
+                "deadline": self.deadline,
+            }),
+        }
+    }
+}
+
+impl FromStr for TypedData {
+    type Err = TypedDataError;
+
+    fn from_str(typed_data: &str) -> Result<Self, Self::Err> {
+        Ok(serde_json::from_str(typed_data)?)
+    }
+}
+
+/// Returns the bytes of the given hex string, with or without a `0x` prefix.
+fn decode_hex(value: &str) -> Result<Vec<u8>, TypedDataError> {
+    match value.starts_with("0x") {
+        true => Ok(hex::decode(&value[2..])?),
+        false => Ok(hex::decode(value)?),
+    }
+}
+
+/// Returns the sign and magnitude of the given integer value.
+/// Accepts JSON numbers, decimal strings, and `0x`-prefixed hex strings.
+pub(crate) fn decode_integer(value: &Value) -> Option<(bool, U256)> {
+    match value {
+        Value::Number(number) => match (number.as_u64(), number.as_i64()) {
+            (Some(number), _) => Some((false, U256::from(number))),
+            (None, Some(number)) => Some((true, U256::from(number.unsigned_abs()))),
+            _ => None,
+        },
+        Value::String(number) => {
+            let (negative, number) = match number.starts_with('-') {
+                true => (true, &number[1..]),
+                false => (false, &number[..]),
+            };
+            let magnitude = match number.starts_with("0x") {
+                true => {
+                    let number = match number.len() % 2 {
+                        0 => hex::decode(&number[2..]).ok()?,
+                        _ => hex::decode(format!("0{}", &number[2..])).ok()?,
+                    };
+                    if number.len() > 32 {
+                        return None;
+                    }
+                    U256::from_big_endian(&number)
+                }
+                false => U256::from_dec_str(number).ok()?,
+            };
+            Some((negative && !magnitude.is_zero(), magnitude))
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // https://github.com/ethereum/EIPs/blob/master/assets/eip-712/Example.js
+    const MAIL: &str = r#"{
+        "types": {
+            "EIP712Domain": [
+                { "name": "name", "type": "string" },
+                { "name": "version", "type": "string" },
+                { "name": "chainId", "type": "uint256" },
+                { "name": "verifyingContract", "type": "address" }
+            ],
+            "Person": [
+                { "name": "name", "type": "string" },
+                { "name": "wallet", "type": "address" }
+            ],
+            "Mail": [
+                { "name": "from", "type": "Person" },
+                { "name": "to", "type": "Person" },
+                { "name": "contents", "type": "string" }
+            ]
+        },
+        "primaryType": "Mail",
+        "domain": {
+            "name": "Ether Mail",
+            "version": "1",
+            "chainId": 1,
+            "verifyingContract": "0xCcCCccccCCCCcCCCCCCcCcCccCcCCCcCcccccccC"
+        },
+        "message": {
+            "from": { "name": "Cow", "wallet": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826" },
+            "to": { "name": "Bob", "wallet": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB" },
+            "contents": "Hello, Bob!"
+        }
+    }"#;
+
+    #[test]
+    fn encode_type() {
+        let typed_data = TypedData::from_str(MAIL).unwrap();
+        assert_eq!(
+            "Mail(Person from,Person to,string contents)Person(string name,address wallet)",
+            typed_data.encode_type("Mail").unwrap()
+        );
+        assert_eq!(
+            "a0cedeb2dc280ba39b857546d74f5549c3a1d7bdc2dd96bf881f76108e23dac2",
+            hex::encode(typed_data.type_hash("Mail").unwrap())
+        );
+    }
+
+    #[test]
+    fn hash_struct() {
+        let typed_data = TypedData::from_str(MAIL).unwrap();
+        assert_eq!(
+            "f2cee375fa42b42143804025fc449deafd50cc031ca257e0b194a650a912090f",
+            hex::encode(typed_data.domain_separator().unwrap())
+        );
+        assert_eq!(
+            "c52c0ee5d84264471806290a3f2c4cecfc5490626bf912d01f240d7a274b371e",
+            hex::encode(typed_data.message_hash().unwrap())
+        );
+    }
+
+    #[test]
+    fn sign() {
+        let typed_data = TypedData::from_str(MAIL).unwrap();
+        assert_eq!(
+            "be609aee343fb3c4b28e1df9e632fca64fcfaede20f02e86244efddf30957bd2",
+            hex::encode(typed_data.to_signing_hash().unwrap())
+        );
+
+        let private_key = EthereumPrivateKey::from_str(&hex::encode(keccak256(b"cow"))).unwrap();
+        assert_eq!(
+            "0x4355c47d63924e8a72e509b65029052eb6c299d53a04e167c5775fd466751c9d07299936d304c153f6443dfa05f40ff007d72911b6f72307f996231605b915621c",
+            typed_data.sign(&private_key).unwrap().to_string()
+        );
+    }
+
+    #[test]
+    fn encode_integer() {
+        let typed_data = TypedData::from_str(MAIL).unwrap();
+        let encode = |value_type: &str, value: Value| typed_data.encode_value(value_type, &value);
+
+        assert_eq!([0xff; 32], encode("int8", Value::from(-1)).unwrap());
+        assert_eq!([0xff; 32], encode("int256", Value::from("-1")).unwrap());
+        assert_eq!(
+            encode("uint256", Value::from(255)).unwrap(),
+            encode("uint8", Value::from("0xff")).unwrap()
+        );
+
+        let mut min_int8 = [0xff; 32];
+        min_int8[31] = 0x80;
+        assert_eq!(min_int8, encode("int8", Value::from(-128)).unwrap());
+        assert_eq!(127, encode("int8", Value::from(127)).unwrap()[31]);
+        let mut min_int256 = [0u8; 32];
+        min_int256[0] = 0x80;
+        assert_eq!(
+            min_int256,
+            encode("int256", Value::from(format!("-0x80{}", "00".repeat(31)))).unwrap()
+        );
+
+        assert!(encode("uint8", Value::from(256)).is_err());
+        assert!(encode("uint256", Value::from(-1)).is_err());
+        assert!(encode("int8", Value::from(128)).is_err());
+        assert!(encode("int8", Value::from(-129)).is_err());
+        assert!(encode("int16", Value::from("0x8000")).is_err());
+        assert!(encode("int256", Value::from(format!("0x80{}", "00".repeat(31)))).is_err());
+        assert!(encode("uint7", Value::from(1)).is_err());
+    }
+
+    #[test]
+    fn permit() {
+        let permit = Permit {
+            name: "USD Coin".into(),
+            version: "2".into(),
+            chain_id: 1,
+            verifying_contract: "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48".into(),
+            owner: "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826".into(),
+            spender: "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB".into(),
+            value: "1000000".into(),
+            nonce: "0".into(),
+            deadline: "0xffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff".into(),
+        };
+        let typed_data = permit.to_typed_data();
+
+        // The PERMIT_TYPEHASH of EIP-2612
+        assert_eq!(
+            "6e71edae12b1b97f4d1f60370fef10105fa2faae0126114a169c64845d6126c9",
+            hex::encode(typed_data.type_hash(PERMIT).unwrap())
+        );
+
+        let expected_typed_data = TypedData::from_str(
+            r#"{
+            "types": {
+                "EIP712Domain": [
+                    { "name": "name", "type": "string" },
+                    { "name": "version", "type": "string" },
+                    { "name": "chainId", "type": "uint256" },
+                    { "name": "verifyingContract", "type": "address" }
+                ],
+                "Permit": [
+                    { "name": "owner", "type": "address" },
+                    { "name": "spender", "type": "address" },
+                    { "name": "value", "type": "uint256" },
+                    { "name": "nonce", "type": "uint256" },
+                    { "name": "deadline", "type": "uint256" }
+                ]
+            },
+            "primaryType": "Permit",
+            "domain": {
+                "name": "USD Coin",
+                "version": "2",
+                "chainId": 1,
+                "verifyingContract": "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48"
+            },
+            "message": {
+                "owner": "0xCD2a3d9F938E13CD947Ec05AbC7FE734Df8DD826",
+                "spender": "0xbBbBBBBbbBBBbbbBbbBbbbbBBbBbbbbBbBbbBBbB",
+                "value": 1000000,
+                "nonce": 0,
+                "deadline": "0xffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff"
+            }
+        }"#,
+        )
+        .unwrap();
+        assert_eq!(
+            expected_typed_data.to_signing_hash().unwrap(),
+            typed_data.to_signing_hash().unwrap()
+        );
+
+        let mut negative_value = permit.clone();
+        negative_value.value = "-1".into();
+        assert!(negative_value.to_typed_data().to_signing_hash().is_err());
+    }
+
+    #[test]
+    fn invalid_typed_data() {
+        let mut typed_data = TypedData::from_str(MAIL).unwrap();
+        typed_data.message["to"] = Value::from("Bob");
+        assert!(typed_data.message_hash().is_err());
+
+        let mut typed_data = TypedData::from_str(MAIL).unwrap();
+        typed_data.primary_type = "Letter".into();
+        assert!(typed_data.to_signing_hash().is_err());
+
+        let mut typed_data = TypedData::from_str(MAIL).unwrap();
+        typed_data.message.as_object_mut().unwrap().remove("contents");
+        assert!(typed_data.to_signing_hash().is_err());
+    }
+}
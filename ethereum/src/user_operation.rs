@@ -0,0 +1,312 @@
+//!
+//! UserOperation
+//!
+//! This module contains the representation of ERC-4337 account abstraction user operations and
+//! the functions for hashing and signing them (https://eips.ethereum.org/EIPS/eip-4337).
+//!
+//! The user operation hash is `keccak256(abi.encode(keccak256(pack(userOp)), entryPoint, chainId))`,
+//! where `pack` ABI-encodes the fields of the user operation with its dynamic fields hashed.
+//! Both the v0.6 user operation and the v0.7 packed user operation are supported.
+//!
+
+use crate::address::EthereumAddress;
+use crate::private_key::EthereumPrivateKey;
+use crate::signature::{EthereumSignature, SignatureError};
+use crate::typed_data::decode_integer;
+use wagyu_model::no_std::*;
+
+use core::str::FromStr;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use tiny_keccak::keccak256;
+
+#[derive(Debug, Fail)]
+pub enum UserOperationError {
+    #[fail(display = "{}: {}", _0, _1)]
+    Crate(&'static str, String),
+
+    #[fail(display = "invalid value for field {}: {}", _0, _1)]
+    InvalidValue(String, String),
+
+    #[fail(display = "missing field {} in user operation", _0)]
+    MissingField(String),
+
+    #[fail(display = "{}", _0)]
+    SignatureError(SignatureError),
+}
+
+impl From<serde_json::error::Error> for UserOperationError {
+    fn from(error: serde_json::error::Error) -> Self {
+        UserOperationError::Crate("serde_json", format!("{:?}", error))
+    }
+}
+
+impl From<SignatureError> for UserOperationError {
+    fn from(error: SignatureError) -> Self {
+        UserOperationError::SignatureError(error)
+    }
+}
+
+/// Represents an ERC-4337 user operation, as accepted by `eth_sendUserOperation`.
+/// A user operation with `accountGasLimits` and `gasFees` is a v0.7 packed user operation,
+/// otherwise it is a v0.6 user operation with separate gas fields.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UserOperation {
+    /// The smart account sending the user operation
+    pub sender: String,
+    /// The anti-replay nonce of the smart account
+    pub nonce: Value,
+    /// The factory address and calldata deploying the smart account, if it is not yet deployed
+    #[serde(default)]
+    pub init_code: String,
+    /// The calldata executed by the smart account
+    pub call_data: String,
+    /// The gas limit of the execution (v0.6)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub call_gas_limit: Option<Value>,
+    /// The gas limit of the verification (v0.6)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub verification_gas_limit: Option<Value>,
+    /// The verification and execution gas limits, packed as two 16-byte values (v0.7)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub account_gas_limits: Option<String>,
+    /// The gas paid to the bundler for the calldata and overhead of the user operation
+    pub pre_verification_gas: Value,
+    /// The maximum fee per gas (v0.6)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_fee_per_gas: Option<Value>,
+    /// The maximum priority fee per gas (v0.6)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub max_priority_fee_per_gas: Option<Value>,
+    /// The maximum priority fee and maximum fee per gas, packed as two 16-byte values (v0.7)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub gas_fees: Option<String>,
+    /// The paymaster address and data sponsoring the user operation, if any
+    #[serde(default)]
+    pub paymaster_and_data: String,
+}
+
+impl UserOperation {
+    /// Returns the ABI encoding of the user operation, with its dynamic fields hashed.
+    pub fn pack(&self) -> Result<Vec<u8>, UserOperationError> {
+        let mut encoding = vec![];
+        encoding.extend_from_slice(&encode_address("sender", &self.sender)?);
+        encoding.extend_from_slice(&encode_uint("nonce", &self.nonce)?);
+        encoding.extend_from_slice(&keccak256(&decode_hex("initCode", &self.init_code)?));
+        encoding.extend_from_slice(&keccak256(&decode_hex("callData", &self.call_data)?));
+
+        match (&self.account_gas_limits, &self.gas_fees) {
+            (Some(account_gas_limits), Some(gas_fees)) => {
+                encoding.extend_from_slice(&encode_bytes32("accountGasLimits", account_gas_limits)?);
+                encoding.extend_from_slice(&encode_uint("preVerificationGas", &self.pre_verification_gas)?);
+                encoding.extend_from_slice(&encode_bytes32("gasFees", gas_fees)?);
+            }
+            (Some(_), None) => return Err(UserOperationError::MissingField("gasFees".into())),
+            (None, Some(_)) => return Err(UserOperationError::MissingField("accountGasLimits".into())),
+            (None, None) => {
+                let fields = [
+                    ("callGasLimit", &self.call_gas_limit),
+                    ("verificationGasLimit", &self.verification_gas_limit),
+                    ("preVerificationGas", &Some(self.pre_verification_gas.clone())),
+                    ("maxFeePerGas", &self.max_fee_per_gas),
+                    ("maxPriorityFeePerGas", &self.max_priority_fee_per_gas),
+                ];
+                for (name, value) in fields.iter() {
+                    match value {
+                        Some(value) => encoding.extend_from_slice(&encode_uint(name, value)?),
+                        None => return Err(UserOperationError::MissingField(name.to_string())),
+                    }
+                }
+            }
+        };
+
+        encoding.extend_from_slice(&keccak256(&decode_hex("paymasterAndData", &self.paymaster_and_data)?));
+        Ok(encoding)
+    }
+
+    /// Returns the user operation hash for the given entry point and chain id,
+    /// `keccak256(abi.encode(keccak256(pack(userOp)), entryPoint, chainId))`.
+    pub fn to_hash(&self, entry_point: &EthereumAddress, chain_id: u64) -> Result<[u8; 32], UserOperationError> {
+        let mut encoding = keccak256(&self.pack()?).to_vec();
+        encoding.extend_from_slice(&encode_address("entryPoint", &entry_point.to_string())?);
+        encoding.extend_from_slice(&encode_uint("chainId", &Value::from(chain_id))?);
+        Ok(keccak256(&encoding))
+    }
+
+    /// Returns the signature of the user operation given the private key of the owner of the smart account.
+    /// The user operation hash is signed as an EIP-191 personal message, as verified by the reference `SimpleAccount`.
+    pub fn sign(
+        &self,
+        entry_point: &EthereumAddress,
+        chain_id: u64,
+        private_key: &EthereumPrivateKey,
+    ) -> Result<EthereumSignature, UserOperationError> {
+        let hash = self.to_hash(entry_point, chain_id)?;
+        Ok(EthereumSignature::sign(
+            &EthereumSignature::personal_message_hash(&hash),
+            private_key,
+        )?)
+    }
+}
+
+impl FromStr for UserOperation {
+    type Err = UserOperationError;
+
+    fn from_str(user_operation: &str) -> Result<Self, Self::Err> {
+        Ok(serde_json::from_str(user_operation)?)
+    }
+}
+
+/// Returns the bytes of the given hex string of the given field, with or without a `0x` prefix.
+fn decode_hex(field: &str, value: &str) -> Result<Vec<u8>, UserOperationError> {
+    let hex = match value.starts_with("0x") {
+        true => &value[2..],
+        false => value,
+    };
+    hex::decode(hex).map_err(|_| UserOperationError::InvalidValue(field.into(), value.into()))
+}
+
+/// Returns the 32-byte encoding of the given address of the given field.
+fn encode_address(field: &str, value: &str) -> Result<[u8; 32], UserOperationError> {
+    let address = decode_hex(field, value)?;
+    if address.len() != 20 {
+        return Err(UserOperationError::InvalidValue(field.into(), value.into()));
+    }
+
+    let mut encoding = [0u8; 32];
+    encoding[12..].copy_from_slice(&address);
+    Ok(encoding)
+}
+
+/// Returns the 32-byte encoding of the given `bytes32` value of the given field.
+fn encode_bytes32(field: &str, value: &str) -> Result<[u8; 32], UserOperationError> {
+    let bytes = decode_hex(field, value)?;
+    if bytes.len() != 32 {
+        return Err(UserOperationError::InvalidValue(field.into(), value.into()));
+    }
+
+    let mut encoding = [0u8; 32];
+    encoding.copy_from_slice(&bytes);
+    Ok(encoding)
+}
+
+/// Returns the 32-byte encoding of the given `uint256` value of the given field.
+fn encode_uint(field: &str, value: &Value) -> Result<[u8; 32], UserOperationError> {
+    let mut encoding = [0u8; 32];
+    match decode_integer(value) {
+        Some((false, integer)) => integer.to_big_endian(&mut encoding),
+        _ => return Err(UserOperationError::InvalidValue(field.into(), value.to_string())),
+    };
+    Ok(encoding)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::format::EthereumFormat;
+    use wagyu_model::{PrivateKey, PublicKey};
+
+    const ENTRY_POINT_V06: &str = "0x5FF137D4b0FDCD49DcA30c7CF57E578a026d2789";
+    const ENTRY_POINT_V07: &str = "0x0000000071727De22E5E9d8BAf0edAc6f37da032";
+    const PRIVATE_KEY: &str = "4646464646464646464646464646464646464646464646464646464646464646";
+
+    const USER_OPERATION_V06: &str = r#"{
+        "sender": "0x9406Cc6185a346906296840746125a0E44976454",
+        "nonce": "0x0",
+        "initCode": "0x",
+        "callData": "0xb61d27f6000000000000000000000000d8da6bf26964af9d7eed9e03e53415d37aa9604500000000000000000000000000000000000000000000000000038d7ea4c6800000000000000000000000000000000000000000000000000000000000000000600000000000000000000000000000000000000000000000000000000000000000",
+        "callGasLimit": "0x5208",
+        "verificationGasLimit": "0x186a0",
+        "preVerificationGas": 50000,
+        "maxFeePerGas": "0x77359400",
+        "maxPriorityFeePerGas": "0x3b9aca00",
+        "paymasterAndData": "0x",
+        "signature": "0x"
+    }"#;
+
+    const USER_OPERATION_V07: &str = r#"{
+        "sender": "0x9406Cc6185a346906296840746125a0E44976454",
+        "nonce": "1",
+        "initCode": "0x",
+        "callData": "0xb61d27f6000000000000000000000000d8da6bf26964af9d7eed9e03e53415d37aa9604500000000000000000000000000000000000000000000000000038d7ea4c6800000000000000000000000000000000000000000000000000000000000000000600000000000000000000000000000000000000000000000000000000000000000",
+        "accountGasLimits": "0x000000000000000000000000000186a000000000000000000000000000005208",
+        "preVerificationGas": "50000",
+        "gasFees": "0x0000000000000000000000003b9aca0000000000000000000000000077359400",
+        "paymasterAndData": "0x"
+    }"#;
+
+    fn test_to_hash(user_operation: &str, entry_point: &str, chain_id: u64, expected_hash: &str) {
+        let user_operation = UserOperation::from_str(user_operation).unwrap();
+        let entry_point = EthereumAddress::from_str(entry_point).unwrap();
+        let hash = user_operation.to_hash(&entry_point, chain_id).unwrap();
+        assert_eq!(expected_hash, hex::encode(hash));
+    }
+
+    #[test]
+    fn to_hash_v06() {
+        test_to_hash(
+            USER_OPERATION_V06,
+            ENTRY_POINT_V06,
+            1,
+            "209b09b5d2c7c1106c8a111b8d99e931666fbf2cf411b68f14485811f974fdf1",
+        );
+        test_to_hash(
+            USER_OPERATION_V06,
+            ENTRY_POINT_V06,
+            11155111,
+            "98b62d559485188f521e8730a338115bd1892dfc4703e59f726f071563f83211",
+        );
+    }
+
+    #[test]
+    fn to_hash_v07() {
+        test_to_hash(
+            USER_OPERATION_V07,
+            ENTRY_POINT_V07,
+            1,
+            "c6e1b840e6a6c4e9f23b82def3fa06b3b85d2c90856f2b3ca2eea4bc59999973",
+        );
+    }
+
+    #[test]
+    fn sign() {
+        let user_operation = UserOperation::from_str(USER_OPERATION_V06).unwrap();
+        let entry_point = EthereumAddress::from_str(ENTRY_POINT_V06).unwrap();
+        let private_key = EthereumPrivateKey::from_str(PRIVATE_KEY).unwrap();
+        let signature = user_operation.sign(&entry_point, 1, &private_key).unwrap();
+
+        let hash = user_operation.to_hash(&entry_point, 1).unwrap();
+        let public_key = signature
+            .recover(&EthereumSignature::personal_message_hash(&hash))
+            .unwrap();
+        assert_eq!(
+            private_key.to_address(&EthereumFormat::Standard).unwrap(),
+            public_key.to_address(&EthereumFormat::Standard).unwrap()
+        );
+    }
+
+    #[test]
+    fn invalid_user_operation() {
+        let entry_point = EthereumAddress::from_str(ENTRY_POINT_V06).unwrap();
+        let user_operation = UserOperation::from_str(USER_OPERATION_V06).unwrap();
+
+        let mut missing_gas = user_operation.clone();
+        missing_gas.call_gas_limit = None;
+        assert!(missing_gas.to_hash(&entry_point, 1).is_err());
+
+        let mut missing_gas_fees = user_operation.clone();
+        missing_gas_fees.account_gas_limits = Some(hex::encode([0u8; 32]));
+        assert!(missing_gas_fees.to_hash(&entry_point, 1).is_err());
+
+        let mut invalid_sender = user_operation.clone();
+        invalid_sender.sender = "0x9406Cc6185a346906296840746125a0E449764".into();
+        assert!(invalid_sender.to_hash(&entry_point, 1).is_err());
+
+        let mut negative_nonce = user_operation.clone();
+        negative_nonce.nonce = Value::from(-1);
+        assert!(negative_nonce.to_hash(&entry_point, 1).is_err());
+
+        assert!(UserOperation::from_str("{\"sender\": \"0x\"}").is_err());
+    }
+}
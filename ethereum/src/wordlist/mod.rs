@@ -1,3 +1,4 @@
+use wagyu_model::no_std::*;
 use wagyu_model::wordlist::{Wordlist, WordlistError};
 
 pub mod chinese_simplified;
@@ -29,6 +30,9 @@ pub trait EthereumWordlist: Wordlist {
     /// The wordlist in original form.
     const WORDLIST: &'static str;
 
+    /// The separator of the words of a phrase.
+    const SEPARATOR: &'static str = " ";
+
     /// Returns the word of a given index from the word list.
     fn get(index: usize) -> Result<String, WordlistError> {
         if index >= 2048 {
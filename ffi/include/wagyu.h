@@ -0,0 +1,98 @@
+/*
+ * Wagyu FFI
+ *
+ * A C ABI to generate wallets, derive HD children, and sign transactions with wagyu.
+ *
+ * Each function takes NUL-terminated UTF-8 strings and writes its result to `output`
+ * as a string allocated by the library, which the caller must release with `wagyu_string_free`.
+ * On success, a function returns WAGYU_OK and writes the result. On failure, it returns
+ * WAGYU_ERROR and writes the error message, or returns WAGYU_INVALID_ARGUMENT and writes nothing
+ * if a required pointer is null or a string is not valid UTF-8.
+ */
+
+#ifndef WAGYU_H
+#define WAGYU_H
+
+#include <stddef.h>
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+/* The status of a successful call */
+#define WAGYU_OK 0
+
+/* The status of a failed call, with the error message written to `output` */
+#define WAGYU_ERROR 1
+
+/* The status of a call with a null pointer or an invalid UTF-8 string */
+#define WAGYU_INVALID_ARGUMENT 2
+
+/*
+ * Writes a new English BIP39 mnemonic of the given word count to `output`.
+ * The mnemonic is generated from the `seed_length` bytes of entropy at `seed`,
+ * which must be 32 bytes, or from the operating system RNG if `seed` is NULL.
+ */
+int32_t wagyu_generate_mnemonic(const uint8_t *seed, size_t seed_length, uint8_t word_count, char **output);
+
+/*
+ * Writes the Bitcoin address of the given mnemonic and password (which may be NULL)
+ * to `output`, derived at the given path, in the given format ("legacy", "segwit", or "bech32")
+ * on the given network ("mainnet" or "testnet").
+ */
+int32_t wagyu_bitcoin_address(const char *mnemonic,
+                              const char *password,
+                              const char *path,
+                              const char *format,
+                              const char *network,
+                              char **output);
+
+/*
+ * Writes the Bitcoin private key (in WIF) of the given mnemonic and password (which may be NULL)
+ * to `output`, derived at the given path on the given network ("mainnet" or "testnet").
+ */
+int32_t wagyu_bitcoin_private_key(const char *mnemonic,
+                                  const char *password,
+                                  const char *path,
+                                  const char *network,
+                                  char **output);
+
+/*
+ * Writes the Bitcoin address of the given private key (in WIF) to `output`,
+ * in the given format ("legacy", "segwit", or "bech32") on the network of the private key.
+ */
+int32_t wagyu_bitcoin_private_key_address(const char *private_key, const char *format, char **output);
+
+/*
+ * Writes the Ethereum address of the given mnemonic and password (which may be NULL)
+ * to `output`, derived at the given path.
+ */
+int32_t wagyu_ethereum_address(const char *mnemonic, const char *password, const char *path, char **output);
+
+/*
+ * Writes the Ethereum private key (in hex) of the given mnemonic and password (which may be NULL)
+ * to `output`, derived at the given path.
+ */
+int32_t wagyu_ethereum_private_key(const char *mnemonic, const char *password, const char *path, char **output);
+
+/* Writes the Ethereum address of the given private key (in hex) to `output`. */
+int32_t wagyu_ethereum_private_key_address(const char *private_key, char **output);
+
+/*
+ * Writes the signed transaction hex of the given unsigned Ethereum transaction hex to `output`,
+ * signed with the given private key for the given network (e.g. "mainnet" or "ropsten").
+ */
+int32_t wagyu_ethereum_sign_transaction(const char *transaction_hex,
+                                        const char *private_key,
+                                        const char *network,
+                                        char **output);
+
+/* Zeroes and releases a string written by the library. A NULL pointer is ignored. */
+void wagyu_string_free(char *string);
+
+#ifdef __cplusplus
+}
+#endif
+
+#endif /* WAGYU_H */
@@ -0,0 +1,402 @@
+//! # Wagyu FFI
+//!
+//! A C ABI to generate wallets, derive HD children, and sign transactions with wagyu,
+//! so that non-Rust applications (such as mobile wallets and Python services) can link
+//! the same code as the command-line tool. The C declarations are in `include/wagyu.h`.
+//!
+//! Each function takes NUL-terminated UTF-8 strings and writes its result to `output`
+//! as a string allocated by this library, which the caller must release with `wagyu_string_free`.
+//! On success, a function returns `WAGYU_OK` and writes the result. On failure, it returns
+//! `WAGYU_ERROR` and writes the error message, or returns `WAGYU_INVALID_ARGUMENT` and writes nothing
+//! if a required pointer is null or a string is not valid UTF-8.
+#![warn(unused_extern_crates, dead_code)]
+
+use wagyu_core::{api, os_rng};
+
+use rand::RngCore;
+use std::{
+    ffi::{CStr, CString},
+    os::raw::c_char,
+    panic::{catch_unwind, UnwindSafe},
+    ptr, slice,
+};
+
+/// The status of a successful call
+pub const WAGYU_OK: i32 = 0;
+
+/// The status of a failed call, with the error message written to `output`
+pub const WAGYU_ERROR: i32 = 1;
+
+/// The status of a call with a null pointer or an invalid UTF-8 string
+pub const WAGYU_INVALID_ARGUMENT: i32 = 2;
+
+/// Writes a new English BIP39 mnemonic of the given word count to `output`.
+/// The mnemonic is generated from the `seed_length` bytes of entropy at `seed`,
+/// which must be 32 bytes, or from the operating system RNG if `seed` is null.
+///
+/// # Safety
+///
+/// `seed` must be null or point to `seed_length` readable bytes, and `output` must be a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn wagyu_generate_mnemonic(
+    seed: *const u8,
+    seed_length: usize,
+    word_count: u8,
+    output: *mut *mut c_char,
+) -> i32 {
+    let mut entropy = [0u8; 32];
+    if !seed.is_null() {
+        if seed_length != entropy.len() {
+            return write_output(output, Err("expected 32 bytes of entropy".into()));
+        }
+        entropy.copy_from_slice(slice::from_raw_parts(seed, seed_length));
+    }
+    call(output, move || {
+        if seed.is_null() {
+            os_rng()
+                .try_fill_bytes(&mut entropy)
+                .map_err(|error| error.to_string())?;
+        }
+        api::generate_mnemonic(&entropy, word_count)
+    })
+}
+
+/// Writes the Bitcoin address of the given mnemonic and password (which may be null)
+/// to `output`, derived at the given path, in the given format (`legacy`, `segwit`, or `bech32`)
+/// on the given network (`mainnet` or `testnet`).
+///
+/// # Safety
+///
+/// Each string must be null or NUL-terminated, and `output` must be a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn wagyu_bitcoin_address(
+    mnemonic: *const c_char,
+    password: *const c_char,
+    path: *const c_char,
+    format: *const c_char,
+    network: *const c_char,
+    output: *mut *mut c_char,
+) -> i32 {
+    let (mnemonic, path, format, network) = match (to_str(mnemonic), to_str(path), to_str(format), to_str(network)) {
+        (Some(mnemonic), Some(path), Some(format), Some(network)) => (mnemonic, path, format, network),
+        _ => return WAGYU_INVALID_ARGUMENT,
+    };
+    if !password.is_null() && to_str(password).is_none() {
+        return WAGYU_INVALID_ARGUMENT;
+    }
+    let password = to_str(password);
+    call(output, move || {
+        api::bitcoin_address(mnemonic, password, path, format, network)
+    })
+}
+
+/// Writes the Bitcoin private key (in WIF) of the given mnemonic and password (which may be null)
+/// to `output`, derived at the given path on the given network (`mainnet` or `testnet`).
+///
+/// # Safety
+///
+/// Each string must be null or NUL-terminated, and `output` must be a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn wagyu_bitcoin_private_key(
+    mnemonic: *const c_char,
+    password: *const c_char,
+    path: *const c_char,
+    network: *const c_char,
+    output: *mut *mut c_char,
+) -> i32 {
+    let (mnemonic, path, network) = match (to_str(mnemonic), to_str(path), to_str(network)) {
+        (Some(mnemonic), Some(path), Some(network)) => (mnemonic, path, network),
+        _ => return WAGYU_INVALID_ARGUMENT,
+    };
+    if !password.is_null() && to_str(password).is_none() {
+        return WAGYU_INVALID_ARGUMENT;
+    }
+    let password = to_str(password);
+    call(output, move || {
+        api::bitcoin_private_key(mnemonic, password, path, network)
+    })
+}
+
+/// Writes the Bitcoin address of the given private key (in WIF) to `output`,
+/// in the given format (`legacy`, `segwit`, or `bech32`) on the network of the private key.
+///
+/// # Safety
+///
+/// Each string must be NUL-terminated, and `output` must be a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn wagyu_bitcoin_private_key_address(
+    private_key: *const c_char,
+    format: *const c_char,
+    output: *mut *mut c_char,
+) -> i32 {
+    let (private_key, format) = match (to_str(private_key), to_str(format)) {
+        (Some(private_key), Some(format)) => (private_key, format),
+        _ => return WAGYU_INVALID_ARGUMENT,
+    };
+    call(output, move || api::bitcoin_private_key_address(private_key, format))
+}
+
+/// Writes the Ethereum address of the given mnemonic and password (which may be null)
+/// to `output`, derived at the given path.
+///
+/// # Safety
+///
+/// Each string must be null or NUL-terminated, and `output` must be a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn wagyu_ethereum_address(
+    mnemonic: *const c_char,
+    password: *const c_char,
+    path: *const c_char,
+    output: *mut *mut c_char,
+) -> i32 {
+    let (mnemonic, path) = match (to_str(mnemonic), to_str(path)) {
+        (Some(mnemonic), Some(path)) => (mnemonic, path),
+        _ => return WAGYU_INVALID_ARGUMENT,
+    };
+    if !password.is_null() && to_str(password).is_none() {
+        return WAGYU_INVALID_ARGUMENT;
+    }
+    let password = to_str(password);
+    call(output, move || api::ethereum_address(mnemonic, password, path))
+}
+
+/// Writes the Ethereum private key (in hex) of the given mnemonic and password (which may be null)
+/// to `output`, derived at the given path.
+///
+/// # Safety
+///
+/// Each string must be null or NUL-terminated, and `output` must be a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn wagyu_ethereum_private_key(
+    mnemonic: *const c_char,
+    password: *const c_char,
+    path: *const c_char,
+    output: *mut *mut c_char,
+) -> i32 {
+    let (mnemonic, path) = match (to_str(mnemonic), to_str(path)) {
+        (Some(mnemonic), Some(path)) => (mnemonic, path),
+        _ => return WAGYU_INVALID_ARGUMENT,
+    };
+    if !password.is_null() && to_str(password).is_none() {
+        return WAGYU_INVALID_ARGUMENT;
+    }
+    let password = to_str(password);
+    call(output, move || api::ethereum_private_key(mnemonic, password, path))
+}
+
+/// Writes the Ethereum address of the given private key (in hex) to `output`.
+///
+/// # Safety
+///
+/// `private_key` must be NUL-terminated, and `output` must be a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn wagyu_ethereum_private_key_address(
+    private_key: *const c_char,
+    output: *mut *mut c_char,
+) -> i32 {
+    let private_key = match to_str(private_key) {
+        Some(private_key) => private_key,
+        None => return WAGYU_INVALID_ARGUMENT,
+    };
+    call(output, move || api::ethereum_private_key_address(private_key))
+}
+
+/// Writes the signed transaction hex of the given unsigned Ethereum transaction hex to `output`,
+/// signed with the given private key for the given network (e.g. `mainnet` or `ropsten`).
+///
+/// # Safety
+///
+/// Each string must be NUL-terminated, and `output` must be a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn wagyu_ethereum_sign_transaction(
+    transaction_hex: *const c_char,
+    private_key: *const c_char,
+    network: *const c_char,
+    output: *mut *mut c_char,
+) -> i32 {
+    let (transaction_hex, private_key, network) = match (to_str(transaction_hex), to_str(private_key), to_str(network))
+    {
+        (Some(transaction_hex), Some(private_key), Some(network)) => (transaction_hex, private_key, network),
+        _ => return WAGYU_INVALID_ARGUMENT,
+    };
+    call(output, move || {
+        api::ethereum_sign_transaction(transaction_hex, private_key, network)
+    })
+}
+
+/// Zeroes and releases a string written by this library. A null pointer is ignored.
+///
+/// # Safety
+///
+/// `string` must be null or a string written by this library, and must not be used afterwards.
+#[no_mangle]
+pub unsafe extern "C" fn wagyu_string_free(string: *mut c_char) {
+    if string.is_null() {
+        return;
+    }
+    // Mnemonics and signed transactions are sensitive, so they are zeroed before release
+    let mut bytes = CString::from_raw(string).into_bytes();
+    for byte in bytes.iter_mut() {
+        ptr::write_volatile(byte, 0);
+    }
+}
+
+/// Returns the string at the given pointer, if it is not null and is valid UTF-8.
+unsafe fn to_str<'a>(string: *const c_char) -> Option<&'a str> {
+    match string.is_null() {
+        true => None,
+        false => CStr::from_ptr(string).to_str().ok(),
+    }
+}
+
+/// Runs the given function, writing its result to `output` and returning its status.
+/// A panic is returned as an error, as it must not unwind across the C ABI.
+unsafe fn call<F: FnOnce() -> Result<String, String> + UnwindSafe>(output: *mut *mut c_char, function: F) -> i32 {
+    let result = catch_unwind(function).unwrap_or_else(|_| Err("internal error".into()));
+    write_output(output, result)
+}
+
+/// Writes the given result to `output` and returns its status.
+unsafe fn write_output(output: *mut *mut c_char, result: Result<String, String>) -> i32 {
+    if output.is_null() {
+        return WAGYU_INVALID_ARGUMENT;
+    }
+    let (status, string) = match result {
+        Ok(string) => (WAGYU_OK, string),
+        Err(error) => (WAGYU_ERROR, error),
+    };
+    match CString::new(string) {
+        Ok(string) => {
+            *output = string.into_raw();
+            status
+        }
+        Err(_) => WAGYU_INVALID_ARGUMENT,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MNEMONIC: &str =
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    fn take(output: *mut c_char) -> String {
+        let string = unsafe { CStr::from_ptr(output) }.to_str().unwrap().to_string();
+        unsafe { wagyu_string_free(output) };
+        string
+    }
+
+    #[test]
+    fn generate_mnemonic() {
+        let seed = [1u8; 32];
+        let mut output = ptr::null_mut();
+
+        let status = unsafe { wagyu_generate_mnemonic(seed.as_ptr(), seed.len(), 12, &mut output) };
+        assert_eq!(WAGYU_OK, status);
+        assert_eq!(12, take(output).split(' ').count());
+
+        let status = unsafe { wagyu_generate_mnemonic(ptr::null(), 0, 24, &mut output) };
+        assert_eq!(WAGYU_OK, status);
+        assert_eq!(24, take(output).split(' ').count());
+
+        let status = unsafe { wagyu_generate_mnemonic(seed.as_ptr(), 16, 12, &mut output) };
+        assert_eq!(WAGYU_ERROR, status);
+        assert_eq!("expected 32 bytes of entropy", take(output));
+    }
+
+    #[test]
+    fn bitcoin_address() {
+        let mnemonic = CString::new(MNEMONIC).unwrap();
+        let path = CString::new("m/84'/0'/0'/0/0").unwrap();
+        let format = CString::new("bech32").unwrap();
+        let network = CString::new("mainnet").unwrap();
+        let mut output = ptr::null_mut();
+
+        let status = unsafe {
+            wagyu_bitcoin_address(
+                mnemonic.as_ptr(),
+                ptr::null(),
+                path.as_ptr(),
+                format.as_ptr(),
+                network.as_ptr(),
+                &mut output,
+            )
+        };
+        assert_eq!(WAGYU_OK, status);
+        assert_eq!("bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu", take(output));
+    }
+
+    #[test]
+    fn ethereum_address() {
+        let mnemonic = CString::new(MNEMONIC).unwrap();
+        let path = CString::new("m/44'/60'/0'/0/0").unwrap();
+        let mut output = ptr::null_mut();
+
+        let status = unsafe { wagyu_ethereum_address(mnemonic.as_ptr(), ptr::null(), path.as_ptr(), &mut output) };
+        assert_eq!(WAGYU_OK, status);
+        assert_eq!("0x9858EfFD232B4033E47d90003D41EC34EcaEda94", take(output));
+
+        let status = unsafe { wagyu_ethereum_address(ptr::null(), ptr::null(), path.as_ptr(), &mut output) };
+        assert_eq!(WAGYU_INVALID_ARGUMENT, status);
+    }
+
+    #[test]
+    fn private_keys() {
+        let mnemonic = CString::new(MNEMONIC).unwrap();
+        let path = CString::new("m/44'/60'/0'/0/0").unwrap();
+        let mut output = ptr::null_mut();
+
+        let status = unsafe { wagyu_ethereum_private_key(mnemonic.as_ptr(), ptr::null(), path.as_ptr(), &mut output) };
+        assert_eq!(WAGYU_OK, status);
+        let private_key = CString::new(take(output)).unwrap();
+
+        let status = unsafe { wagyu_ethereum_private_key_address(private_key.as_ptr(), &mut output) };
+        assert_eq!(WAGYU_OK, status);
+        assert_eq!("0x9858EfFD232B4033E47d90003D41EC34EcaEda94", take(output));
+
+        let path = CString::new("m/84'/0'/0'/0/0").unwrap();
+        let network = CString::new("mainnet").unwrap();
+        let status = unsafe {
+            wagyu_bitcoin_private_key(
+                mnemonic.as_ptr(),
+                ptr::null(),
+                path.as_ptr(),
+                network.as_ptr(),
+                &mut output,
+            )
+        };
+        assert_eq!(WAGYU_OK, status);
+        let private_key = CString::new(take(output)).unwrap();
+
+        let format = CString::new("bech32").unwrap();
+        let status = unsafe { wagyu_bitcoin_private_key_address(private_key.as_ptr(), format.as_ptr(), &mut output) };
+        assert_eq!(WAGYU_OK, status);
+        assert_eq!("bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu", take(output));
+    }
+
+    #[test]
+    fn ethereum_sign_transaction() {
+        let transaction = CString::new("0xdf800182520894e5c7ab7a563af4e3f12b78e3396ca1e4f2d0b58e0180018080").unwrap();
+        let private_key = CString::new("f89f23eaeac18252fedf81bb8318d3c111d48c19b0680dcf6e0a8d5136caf287").unwrap();
+        let network = CString::new("mainnet").unwrap();
+        let mut output = ptr::null_mut();
+
+        let status = unsafe {
+            wagyu_ethereum_sign_transaction(
+                transaction.as_ptr(),
+                private_key.as_ptr(),
+                network.as_ptr(),
+                &mut output,
+            )
+        };
+        assert_eq!(WAGYU_OK, status);
+        assert!(take(output).starts_with("0xf8"));
+
+        let status = unsafe {
+            wagyu_ethereum_sign_transaction(transaction.as_ptr(), network.as_ptr(), network.as_ptr(), &mut output)
+        };
+        assert_eq!(WAGYU_ERROR, status);
+        take(output);
+    }
+}
@@ -0,0 +1,248 @@
+//! # Wagyu Memory
+//!
+//! A secure allocator for wagyu, which zeroes each allocation when it is released and, after `lock_memory`,
+//! locks the pages of each allocation against swapping. The seeds, mnemonics, and private keys of each coin
+//! are held in the heap buffers (such as `String` and `Vec`) of the types of each coin, so the allocator
+//! protects the buffers of each of these types, without a dedicated secret type for each.
+//!
+//! This crate holds the unsafe code of the command-line tool, so that the tool itself forbids unsafe code.
+#![warn(unused_extern_crates, dead_code)]
+
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    ptr, slice,
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+};
+use zeroize::Zeroize;
+
+/// The page size of the process, or zero if the pages of allocations are not locked
+static PAGE_SIZE: AtomicUsize = AtomicUsize::new(0);
+
+/// The number of slots of the table of locked pages
+const LOCKED_PAGE_SLOTS: usize = 1 << 14;
+
+/// A direct-mapped table of recently locked pages, each slot holding the number of a page plus one (or zero),
+/// so that an allocation within pages already locked needs no further system call
+static LOCKED_PAGES: [AtomicUsize; LOCKED_PAGE_SLOTS] = [ZERO; LOCKED_PAGE_SLOTS];
+#[allow(clippy::declare_interior_mutable_const)]
+const ZERO: AtomicUsize = AtomicUsize::new(0);
+
+/// Whether the table of locked pages is used, which requires that the heap never returns its pages to the
+/// system, as a page that is unmapped and mapped again is no longer locked
+static TRACK_PAGES: AtomicBool = AtomicBool::new(false);
+
+/// An allocator of the system allocator, which zeroes each allocation when it is released
+/// and, after `lock_memory`, locks the pages of each allocation against swapping.
+///
+/// The default reallocation allocates, copies, and releases, so a moved allocation is zeroed as well.
+pub struct SecureAllocator;
+
+unsafe impl GlobalAlloc for SecureAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        lock(System.alloc(layout), layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        lock(System.alloc_zeroed(layout), layout)
+    }
+
+    unsafe fn dealloc(&self, pointer: *mut u8, layout: Layout) {
+        slice::from_raw_parts_mut(pointer, layout.size()).zeroize();
+        System.dealloc(pointer, layout)
+    }
+}
+
+/// Returns the given allocation after locking its pages, or null if its pages fail to be locked,
+/// so that a secret is never held in memory that may be swapped.
+///
+/// A page is shared by the allocations within it, so it is never unlocked, and stays locked for the
+/// later allocations of the heap. The number of locked pages is then bounded by the size of the heap.
+unsafe fn lock(pointer: *mut u8, layout: Layout) -> *mut u8 {
+    let page_size = PAGE_SIZE.load(Ordering::Relaxed);
+    if pointer.is_null() || page_size == 0 || layout.size() == 0 {
+        return pointer;
+    }
+    let start = pointer as usize & !(page_size - 1);
+    let end = pointer as usize + layout.size();
+    let track = TRACK_PAGES.load(Ordering::Relaxed);
+    if track && is_locked(start / page_size, (end - 1) / page_size) {
+        return pointer;
+    }
+    match lock_pages(start, end - start) {
+        true => {
+            if track {
+                set_locked(start / page_size, (end - 1) / page_size);
+            }
+            pointer
+        }
+        false => {
+            System.dealloc(pointer, layout);
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Returns `true` if each of the given pages, from first to last, is in the table of locked pages.
+fn is_locked(first: usize, last: usize) -> bool {
+    (first..=last).all(|page| LOCKED_PAGES[page % LOCKED_PAGE_SLOTS].load(Ordering::Relaxed) == page + 1)
+}
+
+/// Records each of the given pages, from first to last, in the table of locked pages.
+fn set_locked(first: usize, last: usize) {
+    for page in first..=last {
+        LOCKED_PAGES[page % LOCKED_PAGE_SLOTS].store(page + 1, Ordering::Relaxed);
+    }
+}
+
+#[cfg(target_os = "linux")]
+unsafe fn lock_pages(start: usize, length: usize) -> bool {
+    libc::mlock(start as *const libc::c_void, length) == 0
+}
+
+#[cfg(not(target_os = "linux"))]
+unsafe fn lock_pages(_start: usize, _length: usize) -> bool {
+    true
+}
+
+/// Disables core dumps of the process, and locks the pages of each later allocation of `SecureAllocator`
+/// against swapping. An allocation that fails to be locked, such as beyond the limit of locked memory
+/// of the process (see `ulimit -l`), fails and aborts the process, instead of being swapped.
+#[cfg(target_os = "linux")]
+pub fn lock_memory() -> std::io::Result<()> {
+    // A core file size limit of zero disables the core dumps written by the kernel, and a process that is not
+    // dumpable is neither dumped by a core dump handler nor attached to with ptrace by other processes.
+    let no_core_dumps = libc::rlimit {
+        rlim_cur: 0,
+        rlim_max: 0,
+    };
+    // Safety: setrlimit reads the given limit, which outlives the call
+    if unsafe { libc::setrlimit(libc::RLIMIT_CORE, &no_core_dumps) } != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+    // Safety: prctl with PR_SET_DUMPABLE reads no memory
+    if unsafe { libc::prctl(libc::PR_SET_DUMPABLE, 0) } != 0 {
+        return Err(std::io::Error::last_os_error());
+    }
+
+    // Pages already locked are only skipped if the heap keeps its pages mapped, with no allocation mapped
+    // apart from the heap and no free memory at its top returned to the system
+    TRACK_PAGES.store(keep_heap_mapped(), Ordering::Relaxed);
+
+    // Safety: sysconf reads no memory
+    match unsafe { libc::sysconf(libc::_SC_PAGESIZE) } {
+        page_size if page_size > 0 && (page_size as usize).is_power_of_two() => {
+            PAGE_SIZE.store(page_size as usize, Ordering::Relaxed);
+            Ok(())
+        }
+        _ => Err(std::io::Error::last_os_error()),
+    }
+}
+
+/// Returns `true` if the system allocator is set to keep the pages of its heap mapped.
+#[cfg(all(target_os = "linux", target_env = "gnu"))]
+fn keep_heap_mapped() -> bool {
+    // Safety: mallopt sets the parameters of the allocator, and reads no memory
+    unsafe { libc::mallopt(libc::M_MMAP_MAX, 0) == 1 && libc::mallopt(libc::M_TRIM_THRESHOLD, libc::c_int::MAX) == 1 }
+}
+
+/// Returns `false`, as the allocator of other C libraries may return the pages of its heap to the system.
+#[cfg(all(target_os = "linux", not(target_env = "gnu")))]
+fn keep_heap_mapped() -> bool {
+    false
+}
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+    use super::*;
+    use std::process::Command;
+
+    /// The environment variable set in the child process of `alloc_locks_pages`
+    const LOCK_MEMORY_CHILD: &str = "WAGYU_MEMORY_LOCK_CHILD";
+
+    /// The locked memory needed by `alloc_locks_pages`, for its allocation and the heap of the test harness
+    const LOCKED_MEMORY_NEEDED: u64 = 1024 * 1024;
+
+    /// Returns the size of the locked memory of the process, in kB.
+    fn locked_memory() -> usize {
+        let status = std::fs::read_to_string("/proc/self/status").unwrap();
+        let line = status.lines().find(|line| line.starts_with("VmLck:")).unwrap();
+        line.split_whitespace().nth(1).unwrap().parse().unwrap()
+    }
+
+    /// Locks the memory of the process, and checks that an allocation is locked. As `lock_memory` disables
+    /// core dumps for good and locks each later allocation, it runs in a child process of the test binary,
+    /// and is skipped if the limit of locked memory (see `ulimit -l`) is below `LOCKED_MEMORY_NEEDED`.
+    #[test]
+    fn alloc_locks_pages() {
+        if std::env::var_os(LOCK_MEMORY_CHILD).is_some() {
+            lock_memory().unwrap();
+            let layout = Layout::from_size_align(16 * 4096, 4096).unwrap();
+            let pointer = unsafe { SecureAllocator.alloc(layout) };
+            assert!(!pointer.is_null());
+            assert!(locked_memory() >= 16 * 4);
+            unsafe { SecureAllocator.dealloc(pointer, layout) };
+            return;
+        }
+
+        let mut limit = libc::rlimit {
+            rlim_cur: 0,
+            rlim_max: 0,
+        };
+        assert_eq!(0, unsafe { libc::getrlimit(libc::RLIMIT_MEMLOCK, &mut limit) });
+        if limit.rlim_cur < LOCKED_MEMORY_NEEDED {
+            eprintln!(
+                "skipped alloc_locks_pages: the limit of locked memory is below {} bytes",
+                LOCKED_MEMORY_NEEDED
+            );
+            return;
+        }
+
+        let status = Command::new(std::env::current_exe().unwrap())
+            .args(["tests::alloc_locks_pages", "--exact", "--test-threads=1"])
+            .env(LOCK_MEMORY_CHILD, "1")
+            .status()
+            .unwrap();
+        assert!(status.success());
+    }
+
+    #[test]
+    fn locked_pages() {
+        // The pages of the table are far above the pages of the heap of the test process
+        let first = usize::MAX / 2;
+        assert!(!is_locked(first, first + 2));
+        set_locked(first, first + 1);
+        assert!(is_locked(first, first + 1));
+        assert!(!is_locked(first, first + 2));
+
+        // A page of the same slot replaces the page of the slot
+        set_locked(first + LOCKED_PAGE_SLOTS, first + LOCKED_PAGE_SLOTS);
+        assert!(!is_locked(first, first));
+        assert!(is_locked(first + 1, first + 1));
+    }
+
+    #[test]
+    fn realloc_preserves_contents() {
+        let layout = Layout::from_size_align(32, 1).unwrap();
+        unsafe {
+            let pointer = SecureAllocator.alloc(layout);
+            assert!(!pointer.is_null());
+            slice::from_raw_parts_mut(pointer, 32).copy_from_slice(&[7u8; 32]);
+
+            let pointer = SecureAllocator.realloc(pointer, layout, 4096);
+            assert!(!pointer.is_null());
+            assert_eq!(&[7u8; 32], slice::from_raw_parts(pointer, 32));
+            SecureAllocator.dealloc(pointer, Layout::from_size_align(4096, 1).unwrap());
+        }
+    }
+
+    #[test]
+    fn alloc_zeroed() {
+        let layout = Layout::from_size_align(256, 8).unwrap();
+        unsafe {
+            let pointer = SecureAllocator.alloc_zeroed(layout);
+            assert!(!pointer.is_null());
+            assert!(slice::from_raw_parts(pointer, 256).iter().all(|byte| *byte == 0));
+            SecureAllocator.dealloc(pointer, layout);
+        }
+    }
+}
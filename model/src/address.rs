@@ -35,12 +35,27 @@ pub enum AddressError {
     #[fail(display = "invalid address: {}", _0)]
     InvalidAddress(String),
 
+    #[fail(
+        display = "invalid base58 block at characters {} to {} (exceeds its byte length)",
+        _0, _1
+    )]
+    InvalidBase58Block(usize, usize),
+
+    #[fail(display = "invalid base58 character: {:?} at position {}", _0, _1)]
+    InvalidBase58Character(char, usize),
+
     #[fail(display = "invalid byte length: {}", _0)]
     InvalidByteLength(usize),
 
     #[fail(display = "invalid character length: {}", _0)]
     InvalidCharacterLength(usize),
 
+    #[fail(
+        display = "invalid bech32 checksum: {} (probable mistyped character at position {:?})",
+        _0, _1
+    )]
+    InvalidBech32Checksum(String, Vec<usize>),
+
     #[fail(display = "invalid address checksum: {{ expected: {:?}, found: {:?} }}", _0, _1)]
     InvalidChecksum(String, String),
 
@@ -131,3 +146,9 @@ impl From<rand_core::Error> for AddressError {
         AddressError::Crate("rand", format!("{:?}", error))
     }
 }
+
+impl From<secp256k1::Error> for AddressError {
+    fn from(error: secp256k1::Error) -> Self {
+        AddressError::Crate("libsecp256k1", format!("{:?}", error))
+    }
+}
@@ -12,6 +12,16 @@ pub trait DerivationPath: Clone + Debug + Display + FromStr + Send + Sync + 'sta
 
     /// Returns a derivation path given the child index vector.
     fn from_vec(path: &Vec<ChildIndex>) -> Result<Self, DerivationPathError>;
+
+    /// Returns a child index vector given the derivation path,
+    /// or errors if the derivation path contains a hardened child index.
+    fn non_hardened_only(&self) -> Result<Vec<ChildIndex>, DerivationPathError> {
+        let path = self.to_vec()?;
+        match path.iter().any(ChildIndex::is_hardened) {
+            true => Err(DerivationPathError::HardenedChildFromPublicKey(self.to_string())),
+            false => Ok(path),
+        }
+    }
 }
 
 #[derive(Debug, Fail, PartialEq, Eq)]
@@ -25,6 +35,12 @@ pub enum DerivationPathError {
     #[fail(display = "expected BIP49 path")]
     ExpectedBIP49Path,
 
+    #[fail(display = "expected BIP84 path")]
+    ExpectedBIP84Path,
+
+    #[fail(display = "expected BIP86 path")]
+    ExpectedBIP86Path,
+
     #[fail(display = "expected valid Ethereum derivation path")]
     ExpectedValidEthereumDerivationPath,
 
@@ -37,14 +53,23 @@ pub enum DerivationPathError {
     #[fail(display = "expected normal path")]
     ExpectedNormalPath,
 
+    #[fail(display = "cannot derive hardened child from xpub at {}", _0)]
+    HardenedChildFromPublicKey(String),
+
     #[fail(display = "invalid child number: {}", _0)]
     InvalidChildNumber(u32),
 
     #[fail(display = "invalid child number format")]
     InvalidChildNumberFormat,
 
+    #[fail(display = "invalid child number range: {}", _0)]
+    InvalidChildNumberRange(String),
+
     #[fail(display = "invalid derivation path: {}", _0)]
     InvalidDerivationPath(String),
+
+    #[fail(display = "invalid derivation path template: {}", _0)]
+    InvalidPathTemplate(String),
 }
 
 /// Represents a child index for a derivation path
@@ -0,0 +1,242 @@
+use crate::derivation_path::{ChildIndex, DerivationPathError};
+use crate::no_std::*;
+use core::{fmt, str::FromStr};
+
+/// The placeholders of a derivation path template
+const PLACEHOLDERS: [&str; 2] = ["{account}", "{index}"];
+
+/// Represents an inclusive range of child numbers, such as `5` or `0-9`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ChildNumberRange {
+    start: u32,
+    end: u32,
+}
+
+impl ChildNumberRange {
+    /// Returns a range of child numbers from `start` to `end` (inclusive),
+    /// or errors if the range is empty or exceeds a normal child number.
+    pub fn new(start: u32, end: u32) -> Result<Self, DerivationPathError> {
+        ChildIndex::normal(start)?;
+        ChildIndex::normal(end)?;
+        match start <= end {
+            true => Ok(Self { start, end }),
+            false => Err(DerivationPathError::InvalidChildNumberRange(format!(
+                "{}-{}",
+                start, end
+            ))),
+        }
+    }
+
+    /// Returns the first child number of the range.
+    pub fn start(&self) -> u32 {
+        self.start
+    }
+
+    /// Returns the last child number of the range.
+    pub fn end(&self) -> u32 {
+        self.end
+    }
+}
+
+impl From<u32> for ChildNumberRange {
+    fn from(number: u32) -> Self {
+        Self {
+            start: number,
+            end: number,
+        }
+    }
+}
+
+impl FromStr for ChildNumberRange {
+    type Err = DerivationPathError;
+
+    fn from_str(range: &str) -> Result<Self, Self::Err> {
+        let parse = |number: &str| {
+            number
+                .trim()
+                .parse::<u32>()
+                .map_err(|_| DerivationPathError::InvalidChildNumberRange(range.into()))
+        };
+
+        match range.find('-') {
+            Some(separator) => Self::new(parse(&range[..separator])?, parse(&range[separator + 1..])?),
+            None => Self::new(parse(range)?, parse(range)?),
+        }
+    }
+}
+
+impl fmt::Display for ChildNumberRange {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.start == self.end {
+            true => write!(f, "{}", self.start),
+            false => write!(f, "{}-{}", self.start, self.end),
+        }
+    }
+}
+
+/// Represents a derivation path template, such as `m/44'/60'/{account}'/0/{index}`,
+/// where the `{account}` and `{index}` placeholders are expanded to child numbers.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DerivationPathTemplate {
+    template: String,
+}
+
+impl DerivationPathTemplate {
+    /// Returns `true` if the given derivation path contains a placeholder.
+    pub fn is_template(path: &str) -> bool {
+        path.contains('{')
+    }
+
+    /// Returns the derivation paths of the template for every combination of the given
+    /// account and index child numbers, ordered by account and then by index.
+    pub fn expand(&self, account: &ChildNumberRange, index: &ChildNumberRange) -> Vec<String> {
+        let accounts = match self.template.contains(PLACEHOLDERS[0]) {
+            true => (account.start..=account.end).collect(),
+            false => vec![account.start],
+        };
+        let indices = match self.template.contains(PLACEHOLDERS[1]) {
+            true => (index.start..=index.end).collect(),
+            false => vec![index.start],
+        };
+
+        let mut paths = Vec::with_capacity(accounts.len() * indices.len());
+        for account in accounts.iter() {
+            for index in indices.iter() {
+                paths.push(
+                    self.template
+                        .replace(PLACEHOLDERS[0], &account.to_string())
+                        .replace(PLACEHOLDERS[1], &index.to_string()),
+                );
+            }
+        }
+        paths
+    }
+}
+
+impl FromStr for DerivationPathTemplate {
+    type Err = DerivationPathError;
+
+    fn from_str(template: &str) -> Result<Self, Self::Err> {
+        let mut parts = template.split('/');
+        if parts.next() != Some("m") {
+            return Err(DerivationPathError::InvalidPathTemplate(template.into()));
+        }
+
+        // Each component must be a child index once its placeholder is substituted
+        for part in parts {
+            let part = PLACEHOLDERS.iter().fold(part.to_string(), |part, placeholder| {
+                match part == *placeholder || part == format!("{}'", placeholder) || part == format!("{}h", placeholder)
+                {
+                    true => part.replace(placeholder, "0"),
+                    false => part,
+                }
+            });
+            if ChildIndex::from_str(&part).is_err() {
+                return Err(DerivationPathError::InvalidPathTemplate(template.into()));
+            }
+        }
+
+        Ok(Self {
+            template: template.to_string(),
+        })
+    }
+}
+
+impl fmt::Display for DerivationPathTemplate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.template)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    mod child_number_range {
+        use super::*;
+
+        #[test]
+        fn from_str() {
+            assert_eq!(ChildNumberRange::from(5), ChildNumberRange::from_str("5").unwrap());
+            assert_eq!(
+                ChildNumberRange::new(0, 9).unwrap(),
+                ChildNumberRange::from_str("0-9").unwrap()
+            );
+        }
+
+        #[test]
+        fn to_string() {
+            assert_eq!("5", ChildNumberRange::from(5).to_string());
+            assert_eq!("0-9", ChildNumberRange::new(0, 9).unwrap().to_string());
+        }
+
+        #[test]
+        fn invalid() {
+            assert_eq!(
+                Err(DerivationPathError::InvalidChildNumberRange("9-0".into())),
+                ChildNumberRange::from_str("9-0")
+            );
+            assert_eq!(
+                Err(DerivationPathError::InvalidChildNumberRange("a".into())),
+                ChildNumberRange::from_str("a")
+            );
+            assert_eq!(
+                Err(DerivationPathError::InvalidChildNumber(2147483648)),
+                ChildNumberRange::from_str("0-2147483648")
+            );
+        }
+    }
+
+    mod derivation_path_template {
+        use super::*;
+
+        #[test]
+        fn expand() {
+            let template = DerivationPathTemplate::from_str("m/44'/60'/{account}'/0/{index}").unwrap();
+            assert_eq!(
+                vec!["m/44'/60'/0'/0/5"],
+                template.expand(&ChildNumberRange::from(0), &ChildNumberRange::from(5))
+            );
+            assert_eq!(
+                vec![
+                    "m/44'/60'/0'/0/0",
+                    "m/44'/60'/0'/0/1",
+                    "m/44'/60'/1'/0/0",
+                    "m/44'/60'/1'/0/1"
+                ],
+                template.expand(
+                    &ChildNumberRange::new(0, 1).unwrap(),
+                    &ChildNumberRange::new(0, 1).unwrap()
+                )
+            );
+        }
+
+        #[test]
+        fn expand_without_placeholder() {
+            let template = DerivationPathTemplate::from_str("m/44'/0'/0'/0/{index}").unwrap();
+            assert_eq!(
+                vec!["m/44'/0'/0'/0/3", "m/44'/0'/0'/0/4"],
+                template.expand(
+                    &ChildNumberRange::new(0, 9).unwrap(),
+                    &ChildNumberRange::new(3, 4).unwrap()
+                )
+            );
+        }
+
+        #[test]
+        fn is_template() {
+            assert!(DerivationPathTemplate::is_template("m/44'/60'/{account}'/0/0"));
+            assert!(!DerivationPathTemplate::is_template("m/44'/60'/0'/0/0"));
+        }
+
+        #[test]
+        fn invalid() {
+            for template in ["44'/{index}", "m/{chain}/0", "m/{index}x", "m/{index}/{account}''"].iter() {
+                assert_eq!(
+                    Err(DerivationPathError::InvalidPathTemplate(template.to_string())),
+                    DerivationPathTemplate::from_str(template)
+                );
+            }
+        }
+    }
+}
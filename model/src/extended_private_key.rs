@@ -13,6 +13,9 @@ use core::{
 };
 
 /// The interface for a generic extended private key.
+///
+/// The equality of extended private keys compares their private keys and chain codes in constant time,
+/// with `crypto::constant_time_eq`, so that a comparison does not reveal where two keys differ.
 pub trait ExtendedPrivateKey: Clone + Debug + Display + FromStr + Send + Sync + 'static + Eq + Sized {
     type Address: Address;
     type DerivationPath: DerivationPath;
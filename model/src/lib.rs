@@ -26,6 +26,9 @@ pub use self::amount::*;
 pub mod derivation_path;
 pub use self::derivation_path::*;
 
+pub mod derivation_path_template;
+pub use self::derivation_path_template::*;
+
 pub mod extended_private_key;
 pub use self::extended_private_key::*;
 
@@ -13,6 +13,9 @@ use core::{
 };
 use rand::Rng;
 
+/// The valid word counts of a mnemonic for extended keys
+pub const MNEMONIC_WORD_COUNTS: [usize; 5] = [12, 15, 18, 21, 24];
+
 /// The interface for a generic mnemonic.
 pub trait Mnemonic: Clone + Debug + Display + FromStr + Send + Sync + 'static + Eq + Sized {
     type Address: Address;
@@ -43,6 +46,15 @@ pub trait Mnemonic: Clone + Debug + Display + FromStr + Send + Sync + 'static +
 pub trait MnemonicCount: Mnemonic {
     /// Returns a new mnemonic given the word count.
     fn new_with_count<R: Rng>(rng: &mut R, word_count: u8) -> Result<Self, MnemonicError>;
+
+    /// Returns the entropy length in bytes of a mnemonic with the given word count,
+    /// where each word encodes 11 bits and every 32 bits of entropy carry 1 checksum bit.
+    fn entropy_length(word_count: usize) -> Result<usize, MnemonicError> {
+        match MNEMONIC_WORD_COUNTS.contains(&word_count) {
+            true => Ok(word_count * 4 / 3),
+            false => Err(MnemonicError::InvalidWordCount(word_count)),
+        }
+    }
 }
 
 /// The interface for a generic mnemonic for extended keys.
@@ -86,8 +98,11 @@ pub enum MnemonicError {
     #[fail(display = "Invalid word not found in monero: {}", _0)]
     InvalidWord(String),
 
-    #[fail(display = "Invalid mnemonic word count: {}", _0)]
-    InvalidWordCount(u8),
+    #[fail(
+        display = "Invalid mnemonic word count: {} [possible values: 12, 15, 18, 21, 24]",
+        _0
+    )]
+    InvalidWordCount(usize),
 
     #[fail(display = "Missing the last word (checksum)")]
     MissingChecksumWord,
@@ -10,6 +10,9 @@ use core::{
 use rand::Rng;
 
 /// The interface for a generic private key.
+///
+/// The equality of private keys compares their secret material in constant time, with
+/// `crypto::constant_time_eq`, so that a comparison does not reveal where two keys differ.
 pub trait PrivateKey: Clone + Debug + Display + FromStr + Send + Sync + 'static + Eq + Sized {
     type Address: Address;
     type Format: Format;
@@ -0,0 +1,81 @@
+//! # Batch hash
+//!
+//! The hashes of a batch of messages, such as the serialized public keys of a scan of the addresses
+//! of an extended public key, so that the address derivation of a scan hashes its public keys in one call.
+//! Each message is hashed with the `sha2` and `ripemd160` implementations of the rest of the crate.
+
+use crate::no_std::*;
+use ripemd160::Ripemd160;
+use sha2::{Digest, Sha256};
+
+/// Returns the SHA-256 hash of each of the given messages.
+pub fn sha256_batch<M: AsRef<[u8]>>(messages: &[M]) -> Vec<[u8; 32]> {
+    messages
+        .iter()
+        .map(|message| {
+            let mut digest = [0u8; 32];
+            digest.copy_from_slice(&Sha256::digest(message.as_ref()));
+            digest
+        })
+        .collect()
+}
+
+/// Returns the RIPEMD-160 hash of the SHA-256 hash of each of the given messages,
+/// with the SHA-256 hashes of the messages computed as a batch.
+pub fn hash160_batch<M: AsRef<[u8]>>(messages: &[M]) -> Vec<[u8; 20]> {
+    sha256_batch(messages)
+        .iter()
+        .map(|hash| {
+            let mut digest = [0u8; 20];
+            digest.copy_from_slice(&Ripemd160::digest(hash));
+            digest
+        })
+        .collect()
+}
+
+/// Returns the double SHA-256 checksum of each of the given messages.
+pub fn checksum_batch<M: AsRef<[u8]>>(messages: &[M]) -> Vec<[u8; 32]> {
+    sha256_batch(&sha256_batch(messages))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The lengths of the messages of the tests, of one, two, and three blocks,
+    /// including the lengths at which the padding spills into another block
+    const LENGTHS: [usize; 14] = [0, 1, 20, 33, 55, 56, 64, 65, 119, 135, 136, 137, 200, 300];
+
+    fn messages() -> Vec<Vec<u8>> {
+        LENGTHS
+            .iter()
+            .map(|length| (0..*length).map(|byte| (byte * 7 + length) as u8).collect())
+            .collect()
+    }
+
+    #[test]
+    fn sha256() {
+        let messages = messages();
+        let digests = sha256_batch(&messages);
+        assert_eq!(messages.len(), digests.len());
+        for (message, digest) in messages.iter().zip(digests.iter()) {
+            assert_eq!(Sha256::digest(message).as_slice(), digest);
+        }
+    }
+
+    #[test]
+    fn hash160() {
+        let messages = messages();
+        for (message, digest) in messages.iter().zip(hash160_batch(&messages).iter()) {
+            assert_eq!(crate::crypto::hash160(message), digest.to_vec());
+        }
+    }
+
+    #[test]
+    fn checksum() {
+        let messages = messages();
+        for (message, digest) in messages.iter().zip(checksum_batch(&messages).iter()) {
+            assert_eq!(crate::crypto::checksum(message), digest.to_vec());
+        }
+    }
+}
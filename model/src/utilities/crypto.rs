@@ -1,6 +1,7 @@
 use crate::no_std::*;
 use ripemd160::Ripemd160;
 use sha2::{Digest, Sha256};
+use subtle::ConstantTimeEq;
 
 pub fn checksum(data: &[u8]) -> Vec<u8> {
     Sha256::digest(&Sha256::digest(&data)).to_vec()
@@ -10,6 +11,13 @@ pub fn hash160(bytes: &[u8]) -> Vec<u8> {
     Ripemd160::digest(&Sha256::digest(&bytes)).to_vec()
 }
 
+/// Returns `true` if the given bytes are equal, in time independent of their contents, for comparing
+/// secret material such as private keys and MACs. Bytes of different lengths are unequal, in time
+/// dependent only on their lengths.
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.ct_eq(b).into()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -27,6 +35,17 @@ mod tests {
         assert_eq!(result, expected);
     }
 
+    #[test]
+    fn test_constant_time_eq() {
+        assert!(constant_time_eq(b"", b""));
+        assert!(constant_time_eq(&[7u8; 32], &[7u8; 32]));
+        assert!(!constant_time_eq(&[7u8; 32], &[7u8; 31]));
+
+        let mut different = [7u8; 32];
+        different[31] = 8;
+        assert!(!constant_time_eq(&[7u8; 32], &different));
+    }
+
     #[test]
     fn test_functionality_checksum() {
         let expected_bytes: [u8; 32] = [
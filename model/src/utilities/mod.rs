@@ -1,8 +1,12 @@
 use crate::no_std::*;
 
+pub mod batch_hash;
+
 #[cfg_attr(test, macro_use)]
 pub mod crypto;
 
+pub mod unicode;
+
 pub fn to_hex_string(bytes: &[u8]) -> String {
     bytes
         .iter()
@@ -0,0 +1,167 @@
+//! # Unicode
+//!
+//! The compatibility decomposition (NFKD) of a mnemonic phrase and its password, as specified by
+//! BIP-39 before they are seeded. The decomposition covers the scripts of the BIP-39 wordlists: the
+//! accented Latin letters of the French and Spanish wordlists, the voiced kana of the Japanese
+//! wordlist, and the Hangul syllables of the Korean wordlist, along with the ideographic space and
+//! the full-width forms of an input method.
+
+use crate::no_std::*;
+
+/// The ideographic space, which separates the words of a Japanese phrase
+pub const IDEOGRAPHIC_SPACE: char = '\u{3000}';
+
+/// The first Hangul syllable, and the number of vowel and trailing jamo of its syllables
+const HANGUL_BASE: u32 = 0xAC00;
+const HANGUL_VOWELS: u32 = 21;
+const HANGUL_TRAILS: u32 = 28;
+
+/// The decompositions of the accented Latin letters and the kana, sorted by character
+const DECOMPOSITIONS: [(char, char, char); 87] = [
+    ('à', 'a', '\u{0300}'),
+    ('á', 'a', '\u{0301}'),
+    ('â', 'a', '\u{0302}'),
+    ('ã', 'a', '\u{0303}'),
+    ('ä', 'a', '\u{0308}'),
+    ('å', 'a', '\u{030A}'),
+    ('ç', 'c', '\u{0327}'),
+    ('è', 'e', '\u{0300}'),
+    ('é', 'e', '\u{0301}'),
+    ('ê', 'e', '\u{0302}'),
+    ('ë', 'e', '\u{0308}'),
+    ('ì', 'i', '\u{0300}'),
+    ('í', 'i', '\u{0301}'),
+    ('î', 'i', '\u{0302}'),
+    ('ï', 'i', '\u{0308}'),
+    ('ñ', 'n', '\u{0303}'),
+    ('ò', 'o', '\u{0300}'),
+    ('ó', 'o', '\u{0301}'),
+    ('ô', 'o', '\u{0302}'),
+    ('õ', 'o', '\u{0303}'),
+    ('ö', 'o', '\u{0308}'),
+    ('ù', 'u', '\u{0300}'),
+    ('ú', 'u', '\u{0301}'),
+    ('û', 'u', '\u{0302}'),
+    ('ü', 'u', '\u{0308}'),
+    ('ý', 'y', '\u{0301}'),
+    ('ÿ', 'y', '\u{0308}'),
+    ('が', 'か', '\u{3099}'),
+    ('ぎ', 'き', '\u{3099}'),
+    ('ぐ', 'く', '\u{3099}'),
+    ('げ', 'け', '\u{3099}'),
+    ('ご', 'こ', '\u{3099}'),
+    ('ざ', 'さ', '\u{3099}'),
+    ('じ', 'し', '\u{3099}'),
+    ('ず', 'す', '\u{3099}'),
+    ('ぜ', 'せ', '\u{3099}'),
+    ('ぞ', 'そ', '\u{3099}'),
+    ('だ', 'た', '\u{3099}'),
+    ('ぢ', 'ち', '\u{3099}'),
+    ('づ', 'つ', '\u{3099}'),
+    ('で', 'て', '\u{3099}'),
+    ('ど', 'と', '\u{3099}'),
+    ('ば', 'は', '\u{3099}'),
+    ('ぱ', 'は', '\u{309A}'),
+    ('び', 'ひ', '\u{3099}'),
+    ('ぴ', 'ひ', '\u{309A}'),
+    ('ぶ', 'ふ', '\u{3099}'),
+    ('ぷ', 'ふ', '\u{309A}'),
+    ('べ', 'へ', '\u{3099}'),
+    ('ぺ', 'へ', '\u{309A}'),
+    ('ぼ', 'ほ', '\u{3099}'),
+    ('ぽ', 'ほ', '\u{309A}'),
+    ('ゔ', 'う', '\u{3099}'),
+    ('ゞ', 'ゝ', '\u{3099}'),
+    ('ゟ', 'よ', '\u{308A}'),
+    ('ガ', 'カ', '\u{3099}'),
+    ('ギ', 'キ', '\u{3099}'),
+    ('グ', 'ク', '\u{3099}'),
+    ('ゲ', 'ケ', '\u{3099}'),
+    ('ゴ', 'コ', '\u{3099}'),
+    ('ザ', 'サ', '\u{3099}'),
+    ('ジ', 'シ', '\u{3099}'),
+    ('ズ', 'ス', '\u{3099}'),
+    ('ゼ', 'セ', '\u{3099}'),
+    ('ゾ', 'ソ', '\u{3099}'),
+    ('ダ', 'タ', '\u{3099}'),
+    ('ヂ', 'チ', '\u{3099}'),
+    ('ヅ', 'ツ', '\u{3099}'),
+    ('デ', 'テ', '\u{3099}'),
+    ('ド', 'ト', '\u{3099}'),
+    ('バ', 'ハ', '\u{3099}'),
+    ('パ', 'ハ', '\u{309A}'),
+    ('ビ', 'ヒ', '\u{3099}'),
+    ('ピ', 'ヒ', '\u{309A}'),
+    ('ブ', 'フ', '\u{3099}'),
+    ('プ', 'フ', '\u{309A}'),
+    ('ベ', 'ヘ', '\u{3099}'),
+    ('ペ', 'ヘ', '\u{309A}'),
+    ('ボ', 'ホ', '\u{3099}'),
+    ('ポ', 'ホ', '\u{309A}'),
+    ('ヴ', 'ウ', '\u{3099}'),
+    ('ヷ', 'ワ', '\u{3099}'),
+    ('ヸ', 'ヰ', '\u{3099}'),
+    ('ヹ', 'ヱ', '\u{3099}'),
+    ('ヺ', 'ヲ', '\u{3099}'),
+    ('ヾ', 'ヽ', '\u{3099}'),
+    ('ヿ', 'コ', '\u{30C8}'),
+];
+
+/// Returns the compatibility decomposition (NFKD) of the given text.
+pub fn nfkd(text: &str) -> String {
+    let mut decomposed = String::with_capacity(text.len());
+    for character in text.chars() {
+        let code = character as u32;
+        match character {
+            IDEOGRAPHIC_SPACE => decomposed.push(' '),
+            // The full-width forms of the ASCII characters
+            '\u{FF01}'..='\u{FF5E}' => decomposed.push((code - 0xFEE0) as u8 as char),
+            // The Hangul syllables, of a leading, a vowel, and an optional trailing jamo
+            '\u{AC00}'..='\u{D7A3}' => {
+                let index = code - HANGUL_BASE;
+                let jamo = [
+                    0x1100 + index / (HANGUL_VOWELS * HANGUL_TRAILS),
+                    0x1161 + (index % (HANGUL_VOWELS * HANGUL_TRAILS)) / HANGUL_TRAILS,
+                    0x11A7 + index % HANGUL_TRAILS,
+                ];
+                jamo.iter()
+                    .filter(|jamo| **jamo != 0x11A7)
+                    .filter_map(|jamo| core::char::from_u32(*jamo))
+                    .for_each(|jamo| decomposed.push(jamo));
+            }
+            _ => match DECOMPOSITIONS.binary_search_by_key(&character, |(composed, _, _)| *composed) {
+                Ok(index) => {
+                    decomposed.push(DECOMPOSITIONS[index].1);
+                    decomposed.push(DECOMPOSITIONS[index].2);
+                }
+                Err(_) => decomposed.push(character),
+            },
+        }
+    }
+    decomposed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nfkd_japanese() {
+        assert_eq!("か\u{3099}っこう", nfkd("がっこう"));
+        assert_eq!("は\u{309A}ん は\u{3099}ん", nfkd("ぱん\u{3000}ばん"));
+        assert_eq!("ABC 123", nfkd("ＡＢＣ　１２３"));
+    }
+
+    #[test]
+    fn nfkd_korean() {
+        assert_eq!("\u{1100}\u{1161}\u{11A8}", nfkd("각"));
+        assert_eq!("\u{1100}\u{1161}", nfkd("가"));
+    }
+
+    #[test]
+    fn nfkd_latin() {
+        assert_eq!("abe\u{0301}ne", nfkd("abéne"));
+        assert_eq!("an\u{0303}o", nfkd("año"));
+        assert_eq!("abandon", nfkd("abandon"));
+    }
+}
@@ -2,13 +2,19 @@ use crate::format::MoneroFormat;
 use crate::network::MoneroNetwork;
 use crate::private_key::MoneroPrivateKey;
 use crate::public_key::MoneroPublicKey;
-use wagyu_model::no_std::{vec, String, ToString};
+use wagyu_model::no_std::{vec, String, ToString, Vec};
 use wagyu_model::{Address, AddressError, PrivateKey};
 
 use base58_monero as base58;
 use core::{convert::TryFrom, fmt, marker::PhantomData, str::FromStr};
 use tiny_keccak::keccak256;
 
+/// The alphabet of Monero base58
+const BASE58_ALPHABET: &[u8] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// The number of characters of a Monero base58 block of each number of bytes (0 to 8)
+const BASE58_BLOCK_SIZES: [usize; 9] = [0, 2, 3, 5, 6, 7, 9, 10, 11];
+
 /// Represents a Monero address
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct MoneroAddress<N: MoneroNetwork> {
@@ -115,6 +121,15 @@ impl<N: MoneroNetwork> MoneroAddress<N> {
         None
     }
 
+    /// Returns the standard address of a Monero integrated address, with the same public spend key
+    /// and public view key, or returns the address itself.
+    pub fn to_standard_address(&self) -> Result<Self, AddressError> {
+        match self.format()? {
+            MoneroFormat::Integrated(_) => Self::generate_address(&self.to_public_key()?, &MoneroFormat::Standard),
+            _ => Ok(self.clone()),
+        }
+    }
+
     /// Returns the format of the Monero address.
     pub fn format(&self) -> Result<MoneroFormat, AddressError> {
         MoneroFormat::from_address(&base58::decode(&self.address)?)
@@ -148,10 +163,7 @@ impl<N: MoneroNetwork> FromStr for MoneroAddress<N> {
     type Err = AddressError;
 
     fn from_str(address: &str) -> Result<Self, Self::Err> {
-        if address.len() != 95 && address.len() != 106 {
-            return Err(AddressError::InvalidCharacterLength(address.len()));
-        }
-        let bytes = base58::decode(address)?;
+        let bytes = decode_address(address)?;
 
         // Check that the network byte correspond with the correct network.
         let _ = N::from_address_prefix(bytes[0])?;
@@ -177,6 +189,43 @@ impl<N: MoneroNetwork> FromStr for MoneroAddress<N> {
     }
 }
 
+/// Returns the bytes of the given Monero address, decoded from Monero base58 in blocks of 11 characters
+/// (8 bytes) and a final block of 7 characters (5 bytes), with the position of the first invalid
+/// character or block of a malformed address.
+pub fn decode_address(address: &str) -> Result<Vec<u8>, AddressError> {
+    if let Some((position, character)) = address
+        .chars()
+        .enumerate()
+        .find(|(_, character)| !character.is_ascii() || !BASE58_ALPHABET.contains(&(*character as u8)))
+    {
+        return Err(AddressError::InvalidBase58Character(character, position));
+    }
+    if address.len() != 95 && address.len() != 106 {
+        return Err(AddressError::InvalidCharacterLength(address.len()));
+    }
+
+    let full_block = BASE58_BLOCK_SIZES[8];
+    let mut bytes = Vec::with_capacity(address.len() * 8 / full_block);
+    for (block, characters) in address.as_bytes().chunks(full_block).enumerate() {
+        let size = match BASE58_BLOCK_SIZES.iter().position(|size| *size == characters.len()) {
+            Some(size) => size,
+            None => return Err(AddressError::InvalidCharacterLength(address.len())),
+        };
+
+        // A block of 11 characters holds values up to 58^11, beyond the 8 bytes of the block
+        let value = characters.iter().fold(0u128, |value, character| {
+            let digit = BASE58_ALPHABET.iter().position(|c| c == character).unwrap_or_default();
+            value * 58 + digit as u128
+        });
+        if value >> (8 * size) != 0 {
+            let start = block * full_block;
+            return Err(AddressError::InvalidBase58Block(start, start + characters.len() - 1));
+        }
+        bytes.extend_from_slice(&value.to_be_bytes()[16 - size..]);
+    }
+    Ok(bytes)
+}
+
 impl<N: MoneroNetwork> fmt::Display for MoneroAddress<N> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "{}", self.address)
@@ -334,6 +383,17 @@ mod tests {
             });
         }
 
+        #[test]
+        fn to_standard_address() {
+            KEYPAIRS.iter().for_each(|(seed, _, address)| {
+                let private_key = MoneroPrivateKey::<N>::from_seed(seed, &MoneroFormat::Standard).unwrap();
+                let expected = MoneroAddress::<N>::from_private_key(&private_key, &MoneroFormat::Standard).unwrap();
+                let address = MoneroAddress::<N>::from_str(address).unwrap();
+                assert_eq!(expected, address.to_standard_address().unwrap());
+                assert_eq!(expected, expected.to_standard_address().unwrap());
+            });
+        }
+
         #[test]
         fn from_str() {
             KEYPAIRS.iter().for_each(|(_, _, address)| {
@@ -452,6 +512,30 @@ mod tests {
             assert!(address.is_err());
         }
 
+        #[test]
+        fn test_invalid_base58() {
+            let address =
+                "48XeJoEK8swMyYwNaLwYDfPTD9YkeyBQnLhspCWyipPShsJ8SGhCHEJdD6y93S31mmEJTmPjMteR4Hky1vDHV2xmDrctPv3";
+            assert_eq!(base58::decode(address).unwrap(), decode_address(address).unwrap());
+
+            let mistyped = address.replacen("XeJ", "XeO", 1);
+            match decode_address(&mistyped) {
+                Err(AddressError::InvalidBase58Character('O', 4)) => (),
+                error => panic!("unexpected result: {:?}", error),
+            };
+
+            let overflowed = format!("{}zzzzzzzzzzz{}", &address[..11], &address[22..]);
+            match decode_address(&overflowed) {
+                Err(AddressError::InvalidBase58Block(11, 21)) => (),
+                error => panic!("unexpected result: {:?}", error),
+            };
+
+            match decode_address(&address[..94]) {
+                Err(AddressError::InvalidCharacterLength(94)) => (),
+                error => panic!("unexpected result: {:?}", error),
+            };
+        }
+
         #[test]
         fn test_invalid_from_address() {
             let address = base58::decode(
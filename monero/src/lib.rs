@@ -16,6 +16,9 @@ pub use self::amount::*;
 pub mod mnemonic;
 pub use self::mnemonic::*;
 
+pub mod multisig;
+pub use self::multisig::*;
+
 pub mod format;
 pub use self::format::*;
 
@@ -25,9 +28,15 @@ pub use self::network::*;
 pub mod one_time_key;
 pub use self::one_time_key::*;
 
+pub mod payment_id;
+pub use self::payment_id::*;
+
 pub mod private_key;
 pub use self::private_key::*;
 
+pub mod proof;
+pub use self::proof::*;
+
 pub mod public_key;
 pub use self::public_key::*;
 
@@ -0,0 +1,640 @@
+#![allow(non_snake_case)]
+
+//!
+//! Multisig
+//!
+//! This module contains the key exchange of Monero multisig wallets, following the rounds of `prepare_multisig`,
+//! `make_multisig`, and `finalize_multisig` in the Monero wallet before v0.18 (which replaced the key exchange).
+//!
+//! Each participant blinds their private keys as `H_s(key || "Multisig")`:
+//! - In the first round, each participant shares a `MultisigV1` info, with their blinded private view key
+//!   and the public key `B = b * G` of their blinded private spend key `b`, both signed with `b`.
+//! - An N-of-N wallet is made from the infos of the other participants. Its private view key is the sum of
+//!   the blinded private view keys, and its public spend key is the sum of the public keys `B`.
+//! - An (N-1)-of-N wallet takes a second round, in which each participant derives a key `k = H_s(b * B')`
+//!   shared with each other participant, and shares a `MultisigxV1` info with the public keys `K = k * G`
+//!   of their shared keys, signed with `b`. Its public spend key is the sum of the distinct public keys `K`.
+//!
+//! Other thresholds take further rounds of key exchange, and are not supported.
+//!
+
+use crate::address::MoneroAddress;
+use crate::format::MoneroFormat;
+use crate::network::MoneroNetwork;
+use crate::private_key::MoneroPrivateKey;
+use crate::public_key::MoneroPublicKey;
+use wagyu_model::no_std::{format, vec, String, Vec};
+use wagyu_model::{AddressError, PublicKey, PublicKeyError};
+
+use base58_monero as base58;
+use core::{fmt, marker::PhantomData, str::FromStr};
+use curve25519_dalek::constants::ED25519_BASEPOINT_POINT;
+use curve25519_dalek::edwards::{CompressedEdwardsY, EdwardsPoint};
+use curve25519_dalek::scalar::Scalar;
+use rand::{CryptoRng, Rng};
+use tiny_keccak::keccak256;
+
+/// The salt of blinded multisig keys, `"Multisig"` padded with zeros to 32 bytes
+const MULTISIG_SALT: [u8; 32] = [
+    b'M', b'u', b'l', b't', b'i', b's', b'i', b'g', 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0,
+    0, 0,
+];
+
+/// The header of a first round multisig info
+const MULTISIG_INFO_HEADER: &str = "MultisigV1";
+
+/// The header of a second round multisig info
+const MULTISIG_EXTRA_INFO_HEADER: &str = "MultisigxV1";
+
+#[derive(Debug, Fail)]
+pub enum MultisigError {
+    #[fail(display = "{}", _0)]
+    AddressError(AddressError),
+
+    #[fail(display = "{}: {}", _0, _1)]
+    Crate(&'static str, String),
+
+    #[fail(display = "duplicate multisig signer {}", _0)]
+    DuplicateSigner(String),
+
+    #[fail(display = "could not generate Edwards point from slice {:?}", _0)]
+    EdwardsPointError([u8; 32]),
+
+    #[fail(display = "invalid multisig info: {}", _0)]
+    InvalidMultisigInfo(String),
+
+    #[fail(display = "invalid multisig info header: {}", _0)]
+    InvalidMultisigInfoHeader(String),
+
+    #[fail(
+        display = "mismatched number of multisig infos: {{ expected: {:?}, found: {:?} }}",
+        _0, _1
+    )]
+    MismatchedMultisigInfos(usize, usize),
+
+    #[fail(display = "{}", _0)]
+    PublicKeyError(PublicKeyError),
+
+    #[fail(display = "unsupported multisig threshold: {} of {} participants", _0, _1)]
+    UnsupportedThreshold(usize, usize),
+}
+
+impl From<AddressError> for MultisigError {
+    fn from(error: AddressError) -> Self {
+        MultisigError::AddressError(error)
+    }
+}
+
+impl From<PublicKeyError> for MultisigError {
+    fn from(error: PublicKeyError) -> Self {
+        MultisigError::PublicKeyError(error)
+    }
+}
+
+impl From<base58::base58::Error> for MultisigError {
+    fn from(error: base58::base58::Error) -> Self {
+        MultisigError::Crate("base58", format!("{:?}", error))
+    }
+}
+
+/// Represents the first round multisig info of a participant (`MultisigV1`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MoneroMultisigInfo {
+    /// The blinded private view key, shared by the participants
+    pub private_view_key: [u8; 32],
+    /// The public key `B` of the blinded private spend key, which identifies the participant
+    pub signer: [u8; 32],
+    /// The signature `(c, r)` of the signer
+    pub signature: [u8; 64],
+}
+
+impl MoneroMultisigInfo {
+    /// Returns the multisig info of the participant with the given private key.
+    pub fn new<N: MoneroNetwork, R: Rng + CryptoRng>(rng: &mut R, private_key: &MoneroPrivateKey<N>) -> Self {
+        let (b, B) = signer_keys(private_key);
+        let private_view_key = blind(&private_key.to_private_view_key()).to_bytes();
+        let signer = B.compress().to_bytes();
+        Self {
+            private_view_key,
+            signer,
+            signature: generate_signature(rng, &Self::message_hash(&private_view_key, &signer), &B, &b),
+        }
+    }
+
+    /// Returns `true` if the multisig info is signed by its signer.
+    pub fn verify(&self) -> bool {
+        check_signature(
+            &Self::message_hash(&self.private_view_key, &self.signer),
+            &self.signer,
+            &self.signature,
+        )
+    }
+
+    /// Returns the message hash `H(private_view_key || signer)` signed by the multisig info.
+    fn message_hash(private_view_key: &[u8; 32], signer: &[u8; 32]) -> [u8; 32] {
+        keccak256(&[&private_view_key[..], &signer[..]].concat())
+    }
+}
+
+impl FromStr for MoneroMultisigInfo {
+    type Err = MultisigError;
+
+    fn from_str(info: &str) -> Result<Self, Self::Err> {
+        let data = decode(info, MULTISIG_INFO_HEADER)?;
+        if data.len() != 128 {
+            return Err(MultisigError::InvalidMultisigInfo(format!(
+                "invalid multisig info length {}",
+                data.len()
+            )));
+        }
+
+        let mut private_view_key = [0u8; 32];
+        let mut signer = [0u8; 32];
+        let mut signature = [0u8; 64];
+        private_view_key.copy_from_slice(&data[0..32]);
+        signer.copy_from_slice(&data[32..64]);
+        signature.copy_from_slice(&data[64..128]);
+        Ok(Self {
+            private_view_key,
+            signer,
+            signature,
+        })
+    }
+}
+
+impl fmt::Display for MoneroMultisigInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let data = [&self.private_view_key[..], &self.signer, &self.signature].concat();
+        match base58::encode(&data) {
+            Ok(data) => write!(f, "{}{}", MULTISIG_INFO_HEADER, data),
+            Err(_) => Err(fmt::Error),
+        }
+    }
+}
+
+/// Represents the second round multisig info of a participant of an (N-1)-of-N wallet (`MultisigxV1`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MoneroMultisigExtraInfo {
+    /// The public key `B` of the blinded private spend key, which identifies the participant
+    pub signer: [u8; 32],
+    /// The public keys `K` of the keys shared with each other participant
+    pub public_keys: Vec<[u8; 32]>,
+    /// The signature `(c, r)` of the signer
+    pub signature: [u8; 64],
+}
+
+impl MoneroMultisigExtraInfo {
+    /// Returns the second round multisig info of the participant with the given private key,
+    /// from the first round multisig infos of the other participants.
+    pub fn new<N: MoneroNetwork, R: Rng + CryptoRng>(
+        rng: &mut R,
+        private_key: &MoneroPrivateKey<N>,
+        infos: &[MoneroMultisigInfo],
+    ) -> Result<Self, MultisigError> {
+        check_infos(private_key, infos)?;
+
+        let (b, B) = signer_keys(private_key);
+        let signer = B.compress().to_bytes();
+        let public_keys = shared_keys(&b, infos)?
+            .iter()
+            .map(|k| (k * ED25519_BASEPOINT_POINT).compress().to_bytes())
+            .collect::<Vec<[u8; 32]>>();
+        let signature = generate_signature(rng, &Self::message_hash(&signer, &public_keys), &B, &b);
+
+        Ok(Self {
+            signer,
+            public_keys,
+            signature,
+        })
+    }
+
+    /// Returns `true` if the multisig info is signed by its signer.
+    pub fn verify(&self) -> bool {
+        check_signature(
+            &Self::message_hash(&self.signer, &self.public_keys),
+            &self.signer,
+            &self.signature,
+        )
+    }
+
+    /// Returns the message hash `H(signer || public_keys)` signed by the multisig info.
+    fn message_hash(signer: &[u8; 32], public_keys: &[[u8; 32]]) -> [u8; 32] {
+        keccak256(&[&signer[..], &public_keys.concat()].concat())
+    }
+}
+
+impl FromStr for MoneroMultisigExtraInfo {
+    type Err = MultisigError;
+
+    fn from_str(info: &str) -> Result<Self, Self::Err> {
+        let data = decode(info, MULTISIG_EXTRA_INFO_HEADER)?;
+        if data.len() < 128 || data.len() % 32 != 0 {
+            return Err(MultisigError::InvalidMultisigInfo(format!(
+                "invalid multisig info length {}",
+                data.len()
+            )));
+        }
+
+        let mut signer = [0u8; 32];
+        let mut signature = [0u8; 64];
+        signer.copy_from_slice(&data[0..32]);
+        signature.copy_from_slice(&data[data.len() - 64..]);
+        let public_keys = data[32..data.len() - 64]
+            .chunks(32)
+            .map(|chunk| {
+                let mut public_key = [0u8; 32];
+                public_key.copy_from_slice(chunk);
+                public_key
+            })
+            .collect();
+        Ok(Self {
+            signer,
+            public_keys,
+            signature,
+        })
+    }
+}
+
+impl fmt::Display for MoneroMultisigExtraInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let data = [&self.signer[..], &self.public_keys.concat(), &self.signature].concat();
+        match base58::encode(&data) {
+            Ok(data) => write!(f, "{}{}", MULTISIG_EXTRA_INFO_HEADER, data),
+            Err(_) => Err(fmt::Error),
+        }
+    }
+}
+
+/// Represents the multisig wallet of a participant, once the key exchange is complete
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MoneroMultisigWallet<N: MoneroNetwork> {
+    /// The number of participants required to sign
+    pub threshold: usize,
+    /// The number of participants
+    pub participants: usize,
+    /// The private view key, shared by the participants
+    pub private_view_key: [u8; 32],
+    /// The public spend key, shared by the participants
+    pub public_spend_key: [u8; 32],
+    /// The private multisig keys of the participant
+    pub multisig_keys: Vec<[u8; 32]>,
+    _network: PhantomData<N>,
+}
+
+impl<N: MoneroNetwork> MoneroMultisigWallet<N> {
+    /// Returns the multisig wallet of the participant with the given private key, from the first round
+    /// multisig infos of the other participants, and for an (N-1)-of-N wallet, from their second round
+    /// multisig infos.
+    pub fn new(
+        private_key: &MoneroPrivateKey<N>,
+        threshold: usize,
+        infos: &[MoneroMultisigInfo],
+        extra_infos: &[MoneroMultisigExtraInfo],
+    ) -> Result<Self, MultisigError> {
+        check_infos(private_key, infos)?;
+        let participants = infos.len() + 1;
+        if threshold < 2 || (threshold != participants && threshold + 1 != participants) {
+            return Err(MultisigError::UnsupportedThreshold(threshold, participants));
+        }
+
+        let (b, B) = signer_keys(private_key);
+        let private_view_key = infos
+            .iter()
+            .fold(blind(&private_key.to_private_view_key()), |sum, info| {
+                sum + Scalar::from_bytes_mod_order(info.private_view_key)
+            });
+
+        let (multisig_keys, public_spend_key) = match threshold == participants {
+            // The public spend key is the sum of the signers
+            true => {
+                let mut public_spend_key = B;
+                for info in infos {
+                    public_spend_key += decompress(&info.signer)?;
+                }
+                (vec![b], public_spend_key)
+            }
+            // The public spend key is the sum of the distinct public keys of the shared keys
+            false => {
+                if extra_infos.len() != infos.len() {
+                    return Err(MultisigError::MismatchedMultisigInfos(infos.len(), extra_infos.len()));
+                }
+
+                let multisig_keys = shared_keys(&b, infos)?;
+                let mut public_keys = multisig_keys
+                    .iter()
+                    .map(|k| (k * ED25519_BASEPOINT_POINT).compress().to_bytes())
+                    .collect::<Vec<[u8; 32]>>();
+                for (info, public_key) in infos.iter().zip(public_keys.clone()) {
+                    let extra_info = match extra_infos.iter().find(|extra_info| extra_info.signer == info.signer) {
+                        Some(extra_info) => extra_info,
+                        None => {
+                            return Err(MultisigError::InvalidMultisigInfo(format!(
+                                "missing second round info of signer {}",
+                                hex::encode(info.signer)
+                            )))
+                        }
+                    };
+                    if !extra_info.verify() || extra_info.public_keys.len() != infos.len() {
+                        return Err(MultisigError::InvalidMultisigInfo(format!(
+                            "invalid second round info of signer {}",
+                            hex::encode(info.signer)
+                        )));
+                    }
+                    // The signer shares the key derived by this participant
+                    if !extra_info.public_keys.contains(&public_key) {
+                        return Err(MultisigError::InvalidMultisigInfo(format!(
+                            "mismatched shared key of signer {}",
+                            hex::encode(info.signer)
+                        )));
+                    }
+                    public_keys.extend(extra_info.public_keys.iter());
+                }
+                public_keys.sort_unstable();
+                public_keys.dedup();
+
+                let mut public_spend_key = EdwardsPoint::default();
+                for public_key in &public_keys {
+                    public_spend_key += decompress(public_key)?;
+                }
+                (multisig_keys, public_spend_key)
+            }
+        };
+
+        Ok(Self {
+            threshold,
+            participants,
+            private_view_key: private_view_key.to_bytes(),
+            public_spend_key: public_spend_key.compress().to_bytes(),
+            multisig_keys: multisig_keys.iter().map(Scalar::to_bytes).collect(),
+            _network: PhantomData,
+        })
+    }
+
+    /// Returns the private spend key of the participant, the sum of its private multisig keys.
+    pub fn to_private_spend_key(&self) -> [u8; 32] {
+        self.multisig_keys
+            .iter()
+            .fold(Scalar::zero(), |sum, key| sum + Scalar::from_bytes_mod_order(*key))
+            .to_bytes()
+    }
+
+    /// Returns the public view key of the multisig wallet.
+    pub fn to_public_view_key(&self) -> [u8; 32] {
+        (Scalar::from_bytes_mod_order(self.private_view_key) * ED25519_BASEPOINT_POINT)
+            .compress()
+            .to_bytes()
+    }
+
+    /// Returns the standard address of the multisig wallet.
+    pub fn to_address(&self) -> Result<MoneroAddress<N>, MultisigError> {
+        let public_key = MoneroPublicKey::<N>::from(
+            &hex::encode(self.public_spend_key),
+            &hex::encode(self.to_public_view_key()),
+            &MoneroFormat::Standard,
+        )?;
+        Ok(public_key.to_address(&MoneroFormat::Standard)?)
+    }
+}
+
+/// Returns the blinded key `H_s(key || "Multisig")`.
+fn blind(key: &[u8; 32]) -> Scalar {
+    Scalar::from_bytes_mod_order(keccak256(&[&key[..], &MULTISIG_SALT].concat()))
+}
+
+/// Returns the blinded private spend key `b` of the given private key, and its public key `B`.
+fn signer_keys<N: MoneroNetwork>(private_key: &MoneroPrivateKey<N>) -> (Scalar, EdwardsPoint) {
+    let b = blind(&private_key.to_private_spend_key());
+    (b, b * ED25519_BASEPOINT_POINT)
+}
+
+/// Returns the keys `k = H_s(b * B')` shared with the signers `B'` of the given multisig infos.
+fn shared_keys(b: &Scalar, infos: &[MoneroMultisigInfo]) -> Result<Vec<Scalar>, MultisigError> {
+    infos
+        .iter()
+        .map(|info| Ok(blind(&(b * decompress(&info.signer)?).compress().to_bytes())))
+        .collect()
+}
+
+/// Checks that the given multisig infos of the other participants are signed, and by distinct signers
+/// other than the participant with the given private key.
+fn check_infos<N: MoneroNetwork>(
+    private_key: &MoneroPrivateKey<N>,
+    infos: &[MoneroMultisigInfo],
+) -> Result<(), MultisigError> {
+    let mut signers = vec![signer_keys(private_key).1.compress().to_bytes()];
+    for info in infos {
+        if !info.verify() {
+            return Err(MultisigError::InvalidMultisigInfo(format!(
+                "invalid signature of signer {}",
+                hex::encode(info.signer)
+            )));
+        }
+        if signers.contains(&info.signer) {
+            return Err(MultisigError::DuplicateSigner(hex::encode(info.signer)));
+        }
+        signers.push(info.signer);
+    }
+    Ok(())
+}
+
+/// Returns the data of the given multisig info, after the given header.
+fn decode(info: &str, header: &str) -> Result<Vec<u8>, MultisigError> {
+    match info.starts_with(header) {
+        true => Ok(base58::decode(&info[header.len()..])?),
+        false => Err(MultisigError::InvalidMultisigInfoHeader(info.chars().take(11).collect())),
+    }
+}
+
+/// Returns the signature `(c, r)` of the given message hash, proving knowledge of `x` such that `P = x * G`.
+fn generate_signature<R: Rng + CryptoRng>(
+    rng: &mut R,
+    message_hash: &[u8; 32],
+    P: &EdwardsPoint,
+    x: &Scalar,
+) -> [u8; 64] {
+    let k = Scalar::random(rng);
+    let c = challenge(message_hash, &P.compress().to_bytes(), &(k * ED25519_BASEPOINT_POINT));
+    let r = k - c * x;
+
+    let mut signature = [0u8; 64];
+    signature[0..32].copy_from_slice(c.as_bytes());
+    signature[32..64].copy_from_slice(r.as_bytes());
+    signature
+}
+
+/// Returns `true` if the signature `(c, r)` of the given message hash is valid for the public key `P`.
+fn check_signature(message_hash: &[u8; 32], P: &[u8; 32], signature: &[u8; 64]) -> bool {
+    let (mut c, mut r) = ([0u8; 32], [0u8; 32]);
+    c.copy_from_slice(&signature[0..32]);
+    r.copy_from_slice(&signature[32..64]);
+
+    let (c, r, point) = match (
+        Scalar::from_canonical_bytes(c),
+        Scalar::from_canonical_bytes(r),
+        decompress(P),
+    ) {
+        (Some(c), Some(r), Ok(point)) => (c, r, point),
+        _ => return false,
+    };
+
+    challenge(message_hash, P, &(c * point + r * ED25519_BASEPOINT_POINT)) == c
+}
+
+/// Returns the challenge `H_s(message_hash || P || X)`.
+fn challenge(message_hash: &[u8; 32], P: &[u8; 32], X: &EdwardsPoint) -> Scalar {
+    Scalar::from_bytes_mod_order(keccak256(
+        &[&message_hash[..], &P[..], &X.compress().to_bytes()].concat(),
+    ))
+}
+
+/// Returns the Edwards point of the given compressed point.
+fn decompress(point: &[u8; 32]) -> Result<EdwardsPoint, MultisigError> {
+    match CompressedEdwardsY::from_slice(point).decompress() {
+        Some(point) => Ok(point),
+        None => Err(MultisigError::EdwardsPointError(*point)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Mainnet;
+    use rand::{rngs::StdRng, SeedableRng};
+    use wagyu_model::PrivateKey;
+
+    type N = Mainnet;
+
+    fn private_keys(rng: &mut StdRng, participants: usize) -> Vec<MoneroPrivateKey<N>> {
+        (0..participants).map(|_| MoneroPrivateKey::<N>::new(rng).unwrap()).collect()
+    }
+
+    /// Returns the first round infos of the participants other than the given participant.
+    fn others<T: Clone>(infos: &[T], participant: usize) -> Vec<T> {
+        infos
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| *index != participant)
+            .map(|(_, info)| info.clone())
+            .collect()
+    }
+
+    fn test_multisig(threshold: usize, participants: usize) {
+        let rng = &mut StdRng::from_seed([1u8; 32]);
+        let private_keys = private_keys(rng, participants);
+        let infos = private_keys
+            .iter()
+            .map(|private_key| MoneroMultisigInfo::new(rng, private_key))
+            .collect::<Vec<MoneroMultisigInfo>>();
+        let extra_infos = match threshold == participants {
+            true => vec![],
+            false => private_keys
+                .iter()
+                .enumerate()
+                .map(|(i, private_key)| MoneroMultisigExtraInfo::new(rng, private_key, &others(&infos, i)).unwrap())
+                .collect(),
+        };
+
+        let wallets = private_keys
+            .iter()
+            .enumerate()
+            .map(|(i, private_key)| {
+                let extra_infos = others(&extra_infos, i);
+                MoneroMultisigWallet::new(private_key, threshold, &others(&infos, i), &extra_infos).unwrap()
+            })
+            .collect::<Vec<MoneroMultisigWallet<N>>>();
+
+        // Every participant derives the same address and private view key
+        let address = wallets[0].to_address().unwrap();
+        for wallet in &wallets {
+            assert_eq!(address, wallet.to_address().unwrap());
+            assert_eq!(wallets[0].private_view_key, wallet.private_view_key);
+        }
+
+        // The distinct multisig keys of all participants sum to the private spend key of the address
+        let mut multisig_keys = wallets
+            .iter()
+            .flat_map(|wallet| wallet.multisig_keys.clone())
+            .collect::<Vec<[u8; 32]>>();
+        multisig_keys.sort_unstable();
+        multisig_keys.dedup();
+        let private_spend_key = multisig_keys
+            .iter()
+            .fold(Scalar::zero(), |sum, key| sum + Scalar::from_bytes_mod_order(*key));
+        assert_eq!(
+            wallets[0].public_spend_key,
+            (private_spend_key * ED25519_BASEPOINT_POINT).compress().to_bytes()
+        );
+    }
+
+    #[test]
+    fn two_of_two() {
+        test_multisig(2, 2);
+    }
+
+    #[test]
+    fn three_of_three() {
+        test_multisig(3, 3);
+    }
+
+    #[test]
+    fn two_of_three() {
+        test_multisig(2, 3);
+    }
+
+    #[test]
+    fn three_of_four() {
+        test_multisig(3, 4);
+    }
+
+    #[test]
+    fn to_string_from_str() {
+        let rng = &mut StdRng::from_seed([2u8; 32]);
+        let private_keys = private_keys(rng, 3);
+        let info = MoneroMultisigInfo::new(rng, &private_keys[0]);
+        let info_string = info.to_string();
+        assert!(info_string.starts_with("MultisigV1"));
+        assert_eq!(info, MoneroMultisigInfo::from_str(&info_string).unwrap());
+        assert!(MoneroMultisigInfo::from_str(&info_string).unwrap().verify());
+
+        let infos = vec![
+            MoneroMultisigInfo::new(rng, &private_keys[1]),
+            MoneroMultisigInfo::new(rng, &private_keys[2]),
+        ];
+        let extra_info = MoneroMultisigExtraInfo::new(rng, &private_keys[0], &infos).unwrap();
+        let extra_info_string = extra_info.to_string();
+        assert!(extra_info_string.starts_with("MultisigxV1"));
+        assert_eq!(extra_info, MoneroMultisigExtraInfo::from_str(&extra_info_string).unwrap());
+        assert!(MoneroMultisigExtraInfo::from_str(&extra_info_string).unwrap().verify());
+    }
+
+    #[test]
+    fn invalid_multisig() {
+        let rng = &mut StdRng::from_seed([3u8; 32]);
+        let private_keys = private_keys(rng, 4);
+        let infos = private_keys
+            .iter()
+            .map(|private_key| MoneroMultisigInfo::new(rng, private_key))
+            .collect::<Vec<MoneroMultisigInfo>>();
+
+        assert!(MoneroMultisigInfo::from_str("MultisigxV1").is_err());
+        assert!(MoneroMultisigInfo::from_str("MultisigV1abc").is_err());
+        assert!(MoneroMultisigExtraInfo::from_str("MultisigV1").is_err());
+
+        // Thresholds other than N-of-N and (N-1)-of-N are not supported
+        assert!(MoneroMultisigWallet::new(&private_keys[0], 2, &infos[1..], &[]).is_err());
+        assert!(MoneroMultisigWallet::new(&private_keys[0], 1, &infos[1..2], &[]).is_err());
+
+        // A participant does not sign its own info, and signers are distinct
+        assert!(MoneroMultisigWallet::new(&private_keys[0], 2, &infos[0..1], &[]).is_err());
+        assert!(MoneroMultisigWallet::new(&private_keys[0], 3, &[infos[1].clone(), infos[1].clone()], &[]).is_err());
+
+        // A tampered info fails its signature
+        let mut tampered = infos[1].clone();
+        tampered.private_view_key[0] ^= 1;
+        assert!(!tampered.verify());
+        assert!(MoneroMultisigWallet::new(&private_keys[0], 2, &[tampered], &[]).is_err());
+
+        // An (N-1)-of-N wallet requires the second round infos of all other participants
+        assert!(MoneroMultisigWallet::new(&private_keys[0], 3, &infos[1..], &[]).is_err());
+    }
+}
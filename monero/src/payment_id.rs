@@ -0,0 +1,154 @@
+//!
+//! Payment IDs
+//!
+//! This module contains the short payment IDs of integrated addresses, and their encryption
+//! in the extra field of a transaction. A short payment ID is encrypted with the first 8 bytes of
+//! `keccak256(8 * r * A || 0x8d)`, where `r` is the transaction private key and `A` is the public
+//! view key of the recipient. As `8 * r * A = 8 * a * R`, it is decrypted with the same operation
+//! by the recipient, using its private view key `a` and the transaction public key `R`.
+//!
+//! Long (32-byte) payment IDs were deprecated and are no longer relayed, so they are rejected.
+//!
+
+use wagyu_model::no_std::String;
+
+use core::{fmt, str::FromStr};
+use curve25519_dalek::{edwards::CompressedEdwardsY, scalar::Scalar};
+use tiny_keccak::keccak256;
+
+/// The domain separator of encrypted payment IDs
+const ENCRYPTED_PAYMENT_ID_TAIL: u8 = 0x8d;
+
+#[derive(Debug, Fail)]
+pub enum PaymentIdError {
+    #[fail(display = "could not generate Edwards point from slice {:?}", _0)]
+    EdwardsPointError([u8; 32]),
+
+    #[fail(display = "invalid payment id: {}", _0)]
+    InvalidPaymentId(String),
+
+    #[fail(display = "long payment ids are deprecated, use an 8-byte payment id: {}", _0)]
+    LongPaymentId(String),
+}
+
+/// Represents a Monero short payment ID
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MoneroPaymentId([u8; 8]);
+
+impl MoneroPaymentId {
+    /// Returns the payment ID of the given bytes.
+    pub fn new(payment_id: [u8; 8]) -> Self {
+        Self(payment_id)
+    }
+
+    /// Returns the bytes of the payment ID.
+    pub fn to_bytes(&self) -> [u8; 8] {
+        self.0
+    }
+
+    /// Returns the payment ID encrypted with the shared secret of the given public key and secret key,
+    /// which are the public view key of the recipient and the transaction private key of the sender.
+    pub fn encrypt(&self, public_key: &[u8; 32], secret_key: &[u8; 32]) -> Result<Self, PaymentIdError> {
+        let point = match CompressedEdwardsY::from_slice(public_key).decompress() {
+            Some(point) => point,
+            None => return Err(PaymentIdError::EdwardsPointError(*public_key)),
+        };
+        let derivation = (Scalar::from_bits(*secret_key) * point).mul_by_cofactor();
+
+        let mut data = derivation.compress().to_bytes().to_vec();
+        data.push(ENCRYPTED_PAYMENT_ID_TAIL);
+        let key = keccak256(&data);
+
+        let mut payment_id = self.0;
+        payment_id
+            .iter_mut()
+            .zip(key.iter())
+            .for_each(|(byte, key)| *byte ^= key);
+        Ok(Self(payment_id))
+    }
+
+    /// Returns the payment ID decrypted with the shared secret of the given public key and secret key,
+    /// which are the transaction public key and the private view key of the recipient.
+    pub fn decrypt(&self, public_key: &[u8; 32], secret_key: &[u8; 32]) -> Result<Self, PaymentIdError> {
+        self.encrypt(public_key, secret_key)
+    }
+}
+
+impl FromStr for MoneroPaymentId {
+    type Err = PaymentIdError;
+
+    fn from_str(payment_id: &str) -> Result<Self, Self::Err> {
+        let bytes = hex::decode(payment_id).map_err(|_| PaymentIdError::InvalidPaymentId(payment_id.into()))?;
+        match bytes.len() {
+            8 => {
+                let mut id = [0u8; 8];
+                id.copy_from_slice(&bytes);
+                Ok(Self(id))
+            }
+            32 => Err(PaymentIdError::LongPaymentId(payment_id.into())),
+            _ => Err(PaymentIdError::InvalidPaymentId(payment_id.into())),
+        }
+    }
+}
+
+impl fmt::Display for MoneroPaymentId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", hex::encode(self.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use curve25519_dalek::constants::ED25519_BASEPOINT_TABLE;
+
+    fn public_key(secret_key: &[u8; 32]) -> [u8; 32] {
+        (&Scalar::from_bits(*secret_key) * &ED25519_BASEPOINT_TABLE)
+            .compress()
+            .to_bytes()
+    }
+
+    fn test_encrypt_decrypt(payment_id: &str, private_view_key: &[u8; 32], transaction_key: &[u8; 32]) {
+        let payment_id = MoneroPaymentId::from_str(payment_id).unwrap();
+        let encrypted = payment_id
+            .encrypt(&public_key(private_view_key), transaction_key)
+            .unwrap();
+        assert_ne!(payment_id, encrypted);
+
+        let decrypted = encrypted
+            .decrypt(&public_key(transaction_key), private_view_key)
+            .unwrap();
+        assert_eq!(payment_id, decrypted);
+    }
+
+    #[test]
+    fn encrypt_decrypt() {
+        let mut private_view_key = [0u8; 32];
+        private_view_key
+            .copy_from_slice(&hex::decode("6ee02d3b8e1b30d1d2a7c8a8c9b7b4979bf1a1e3fb2d7ff8412f979c4c94e10c").unwrap());
+        let mut transaction_key = [0u8; 32];
+        transaction_key
+            .copy_from_slice(&hex::decode("a4e3ba1a4de2d0c8c0d0debf8c3fa0d9a0b0e4d5b103fb7ac8d0cfbcd1ed640b").unwrap());
+
+        test_encrypt_decrypt("0123456789abcdef", &private_view_key, &transaction_key);
+        test_encrypt_decrypt("0000000000000000", &private_view_key, &transaction_key);
+        test_encrypt_decrypt("ffffffffffffffff", &transaction_key, &private_view_key);
+    }
+
+    #[test]
+    fn to_string_from_str() {
+        let payment_id = MoneroPaymentId::from_str("0123456789abcdef").unwrap();
+        assert_eq!([0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd, 0xef], payment_id.to_bytes());
+        assert_eq!("0123456789abcdef", payment_id.to_string());
+    }
+
+    #[test]
+    fn invalid_payment_ids() {
+        assert!(MoneroPaymentId::from_str("0123456789abcd").is_err());
+        assert!(MoneroPaymentId::from_str("0123456789abcdeg").is_err());
+        match MoneroPaymentId::from_str(&"ab".repeat(32)) {
+            Err(PaymentIdError::LongPaymentId(_)) => {}
+            _ => panic!("expected a long payment id error"),
+        }
+    }
+}
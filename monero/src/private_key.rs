@@ -2,7 +2,9 @@ use crate::address::MoneroAddress;
 use crate::format::MoneroFormat;
 use crate::network::MoneroNetwork;
 use crate::public_key::MoneroPublicKey;
-use wagyu_model::{no_std::Vec, Address, AddressError, PrivateKey, PrivateKeyError, PublicKey};
+use wagyu_model::{
+    crypto::constant_time_eq, no_std::Vec, Address, AddressError, PrivateKey, PrivateKeyError, PublicKey,
+};
 
 use core::{fmt, fmt::Display, marker::PhantomData, str::FromStr};
 use curve25519_dalek::scalar::Scalar;
@@ -11,7 +13,7 @@ use rand::Rng;
 use tiny_keccak::keccak256;
 
 /// Represents a Monero private key
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Eq)]
 pub struct MoneroPrivateKey<N: MoneroNetwork> {
     /// The private spending key
     spend_key: [u8; 32],
@@ -130,6 +132,15 @@ impl<N: MoneroNetwork> FromStr for MoneroPrivateKey<N> {
     }
 }
 
+impl<N: MoneroNetwork> PartialEq for MoneroPrivateKey<N> {
+    /// Returns `true` if the private keys are equal, comparing their spend and view keys in constant time.
+    fn eq(&self, other: &Self) -> bool {
+        let keys_eq =
+            constant_time_eq(&self.spend_key, &other.spend_key) & constant_time_eq(&self.view_key, &other.view_key);
+        keys_eq && self.format == other.format
+    }
+}
+
 impl<N: MoneroNetwork> Display for MoneroPrivateKey<N> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         write!(f, "(")?;
@@ -336,6 +347,27 @@ mod tests {
                     test_to_str(private_spend_key, private_view_key, &private_key);
                 });
         }
+
+        #[test]
+        fn eq() {
+            KEYPAIRS.iter().zip(KEYPAIRS.iter().skip(1)).for_each(
+                |((seed, (private_spend_key, _), _, _), (other_seed, _, _, _))| {
+                    let private_key = MoneroPrivateKey::<N>::from_seed(seed, FORMAT).unwrap();
+                    assert_eq!(
+                        private_key,
+                        MoneroPrivateKey::<N>::from_private_spend_key(private_spend_key, FORMAT).unwrap()
+                    );
+                    assert_ne!(
+                        private_key,
+                        MoneroPrivateKey::<N>::from_seed(other_seed, FORMAT).unwrap()
+                    );
+                    assert_ne!(
+                        private_key,
+                        MoneroPrivateKey::<N>::from_seed(seed, &MoneroFormat::Subaddress(0, 1)).unwrap()
+                    );
+                },
+            );
+        }
     }
 
     mod integrated_mainnet {
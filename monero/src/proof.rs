@@ -0,0 +1,533 @@
+#![allow(non_snake_case)]
+
+//!
+//! Proof
+//!
+//! This module contains Monero transaction proofs (`OutProofV2` and `InProofV2`), as produced by
+//! `get_tx_proof` and checked by `check_tx_proof` in the Monero wallet.
+//!
+//! A transaction proof demonstrates that a transaction paid an address, without revealing any spend key:
+//! - An outbound proof is made by the sender with the transaction private key `r`,
+//!   proving knowledge of `r` such that `R = r * G` and `D = r * A`.
+//! - An inbound proof is made by the recipient with the private view key `a`,
+//!   proving knowledge of `a` such that `A = a * G` and `D = a * R`.
+//!
+//! Where `R` is a transaction public key, `A` is the public view key of the address, and `D` is the shared secret.
+//! For subaddresses, the public spend key of the subaddress is used as the base point in place of `G`.
+//!
+//! Spend proofs (`SpendProofV1`) and reserve proofs (`ReserveProofV2`) are out of scope, and are not planned.
+//! They sign with ring signatures over the ring members and key images of spent or unspent outputs, which
+//! require the blockchain state of a full wallet and a hash to point that this crate does not implement.
+//!
+
+use crate::address::MoneroAddress;
+use crate::format::MoneroFormat;
+use crate::network::MoneroNetwork;
+use wagyu_model::no_std::{format, vec, String, ToString, Vec};
+use wagyu_model::AddressError;
+
+use base58_monero as base58;
+use core::{fmt, str::FromStr};
+use curve25519_dalek::constants::ED25519_BASEPOINT_POINT;
+use curve25519_dalek::edwards::{CompressedEdwardsY, EdwardsPoint};
+use curve25519_dalek::scalar::Scalar;
+use rand::{CryptoRng, Rng};
+use tiny_keccak::keccak256;
+
+/// The domain separator of version 2 transaction proofs
+const HASH_KEY_TXPROOF_V2: &[u8] = b"TXPROOF_V2";
+
+#[derive(Debug, Fail)]
+pub enum ProofError {
+    #[fail(display = "{}", _0)]
+    AddressError(AddressError),
+
+    #[fail(display = "{}: {}", _0, _1)]
+    Crate(&'static str, String),
+
+    #[fail(display = "could not generate Edwards point from slice {:?}", _0)]
+    EdwardsPointError([u8; 32]),
+
+    #[fail(display = "invalid proof: {}", _0)]
+    InvalidProof(String),
+
+    #[fail(display = "invalid proof header: {}", _0)]
+    InvalidProofHeader(String),
+
+    #[fail(
+        display = "mismatched number of transaction public keys: {{ expected: {:?}, found: {:?} }}",
+        _0, _1
+    )]
+    MismatchedTransactionPublicKeys(usize, usize),
+}
+
+impl From<AddressError> for ProofError {
+    fn from(error: AddressError) -> Self {
+        ProofError::AddressError(error)
+    }
+}
+
+impl From<base58::base58::Error> for ProofError {
+    fn from(error: base58::base58::Error) -> Self {
+        ProofError::Crate("base58", format!("{:?}", error))
+    }
+}
+
+/// Represents the direction of a Monero transaction proof
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum MoneroProofType {
+    /// Proves a payment was received, using the private view key of the recipient
+    Inbound,
+    /// Proves a payment was sent, using the transaction private key of the sender
+    Outbound,
+}
+
+impl MoneroProofType {
+    /// Returns the header of the proof string
+    pub fn header(&self) -> &'static str {
+        match self {
+            MoneroProofType::Inbound => "InProofV2",
+            MoneroProofType::Outbound => "OutProofV2",
+        }
+    }
+}
+
+impl fmt::Display for MoneroProofType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MoneroProofType::Inbound => write!(f, "inbound"),
+            MoneroProofType::Outbound => write!(f, "outbound"),
+        }
+    }
+}
+
+/// Represents a Monero transaction proof, with a shared secret and signature per transaction public key
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MoneroTransactionProof {
+    /// The direction of the proof
+    pub proof_type: MoneroProofType,
+    /// The shared secrets `D`
+    pub shared_secrets: Vec<[u8; 32]>,
+    /// The signatures `(c, r)`
+    pub signatures: Vec<[u8; 64]>,
+}
+
+impl MoneroTransactionProof {
+    /// Returns an outbound proof that the transaction with the given id paid the given address,
+    /// using the transaction private key(s) of the sender.
+    pub fn outbound<N: MoneroNetwork, R: Rng + CryptoRng>(
+        rng: &mut R,
+        transaction_id: &[u8; 32],
+        address: &MoneroAddress<N>,
+        transaction_private_keys: &[[u8; 32]],
+        message: &[u8],
+    ) -> Result<Self, ProofError> {
+        let prefix_hash = Self::prefix_hash(transaction_id, message);
+        let (A, B) = Self::address_points(address)?;
+
+        let mut shared_secrets = vec![];
+        let mut signatures = vec![];
+        for transaction_private_key in transaction_private_keys {
+            let r = Scalar::from_bytes_mod_order(*transaction_private_key);
+            let R = r * B.unwrap_or(ED25519_BASEPOINT_POINT);
+            let D = r * A;
+
+            signatures.push(Self::generate(rng, &prefix_hash, &R, &A, B.as_ref(), &D, &r));
+            shared_secrets.push(D.compress().to_bytes());
+        }
+
+        Ok(Self {
+            proof_type: MoneroProofType::Outbound,
+            shared_secrets,
+            signatures,
+        })
+    }
+
+    /// Returns an inbound proof that the given address received the transaction with the given id,
+    /// using the private view key of the recipient and the transaction public key(s).
+    pub fn inbound<N: MoneroNetwork, R: Rng + CryptoRng>(
+        rng: &mut R,
+        transaction_id: &[u8; 32],
+        address: &MoneroAddress<N>,
+        private_view_key: &[u8; 32],
+        transaction_public_keys: &[[u8; 32]],
+        message: &[u8],
+    ) -> Result<Self, ProofError> {
+        let prefix_hash = Self::prefix_hash(transaction_id, message);
+        let (A, B) = Self::address_points(address)?;
+        let a = Scalar::from_bytes_mod_order(*private_view_key);
+
+        if a * B.unwrap_or(ED25519_BASEPOINT_POINT) != A {
+            return Err(ProofError::InvalidProof(
+                "private view key does not correspond to the address".into(),
+            ));
+        }
+
+        let mut shared_secrets = vec![];
+        let mut signatures = vec![];
+        for transaction_public_key in transaction_public_keys {
+            let R = decompress(transaction_public_key)?;
+            let D = a * R;
+
+            signatures.push(Self::generate(rng, &prefix_hash, &A, &R, B.as_ref(), &D, &a));
+            shared_secrets.push(D.compress().to_bytes());
+        }
+
+        Ok(Self {
+            proof_type: MoneroProofType::Inbound,
+            shared_secrets,
+            signatures,
+        })
+    }
+
+    /// Returns `true` if the proof is valid for the given transaction id, address,
+    /// transaction public key(s), and message.
+    pub fn verify<N: MoneroNetwork>(
+        &self,
+        transaction_id: &[u8; 32],
+        address: &MoneroAddress<N>,
+        transaction_public_keys: &[[u8; 32]],
+        message: &[u8],
+    ) -> Result<bool, ProofError> {
+        if transaction_public_keys.len() != self.signatures.len() {
+            return Err(ProofError::MismatchedTransactionPublicKeys(
+                self.signatures.len(),
+                transaction_public_keys.len(),
+            ));
+        }
+
+        let prefix_hash = Self::prefix_hash(transaction_id, message);
+        let (A, B) = Self::address_points(address)?;
+
+        for ((transaction_public_key, shared_secret), signature) in transaction_public_keys
+            .iter()
+            .zip(&self.shared_secrets)
+            .zip(&self.signatures)
+        {
+            let R = decompress(transaction_public_key)?;
+            let D = decompress(shared_secret)?;
+
+            let valid = match self.proof_type {
+                MoneroProofType::Outbound => Self::check(&prefix_hash, &R, &A, B.as_ref(), &D, signature),
+                MoneroProofType::Inbound => Self::check(&prefix_hash, &A, &R, B.as_ref(), &D, signature),
+            };
+            if !valid {
+                return Ok(false);
+            }
+        }
+
+        Ok(true)
+    }
+
+    /// Returns the message hash `H(transaction_id || message)` signed by the proof.
+    fn prefix_hash(transaction_id: &[u8; 32], message: &[u8]) -> [u8; 32] {
+        keccak256(&[&transaction_id[..], message].concat())
+    }
+
+    /// Returns the public view key point of the address, and the public spend key point for subaddresses.
+    fn address_points<N: MoneroNetwork>(
+        address: &MoneroAddress<N>,
+    ) -> Result<(EdwardsPoint, Option<EdwardsPoint>), ProofError> {
+        let public_key = address.to_public_key()?;
+        let (public_spend_key, public_view_key) =
+            match (public_key.to_public_spend_key(), public_key.to_public_view_key()) {
+                (Some(public_spend_key), Some(public_view_key)) => (public_spend_key, public_view_key),
+                _ => return Err(ProofError::InvalidProof("address is missing public keys".into())),
+            };
+
+        let B = match address.format()? {
+            MoneroFormat::Subaddress(_, _) => Some(decompress(&public_spend_key)?),
+            _ => None,
+        };
+
+        Ok((decompress(&public_view_key)?, B))
+    }
+
+    /// Returns the challenge `H_s(prefix_hash || D || X || Y || H(sep) || R || A || B)`.
+    fn challenge(
+        prefix_hash: &[u8; 32],
+        R: &EdwardsPoint,
+        A: &EdwardsPoint,
+        B: Option<&EdwardsPoint>,
+        D: &EdwardsPoint,
+        X: &EdwardsPoint,
+        Y: &EdwardsPoint,
+    ) -> Scalar {
+        let mut buffer = Vec::with_capacity(256);
+        buffer.extend_from_slice(prefix_hash);
+        buffer.extend_from_slice(&D.compress().to_bytes());
+        buffer.extend_from_slice(&X.compress().to_bytes());
+        buffer.extend_from_slice(&Y.compress().to_bytes());
+        buffer.extend_from_slice(&keccak256(HASH_KEY_TXPROOF_V2));
+        buffer.extend_from_slice(&R.compress().to_bytes());
+        buffer.extend_from_slice(&A.compress().to_bytes());
+        match B {
+            Some(B) => buffer.extend_from_slice(&B.compress().to_bytes()),
+            None => buffer.extend_from_slice(&[0u8; 32]),
+        };
+
+        Scalar::from_bytes_mod_order(keccak256(&buffer))
+    }
+
+    /// Returns a signature proving knowledge of `r` such that `R = r * B` (or `r * G`) and `D = r * A`.
+    fn generate<R: Rng + CryptoRng>(
+        rng: &mut R,
+        prefix_hash: &[u8; 32],
+        R: &EdwardsPoint,
+        A: &EdwardsPoint,
+        B: Option<&EdwardsPoint>,
+        D: &EdwardsPoint,
+        r: &Scalar,
+    ) -> [u8; 64] {
+        let k = Scalar::random(rng);
+        let X = k * B.unwrap_or(&ED25519_BASEPOINT_POINT);
+        let Y = k * A;
+
+        let c = Self::challenge(prefix_hash, R, A, B, D, &X, &Y);
+        let s = k - c * r;
+
+        let mut signature = [0u8; 64];
+        signature[0..32].copy_from_slice(c.as_bytes());
+        signature[32..64].copy_from_slice(s.as_bytes());
+        signature
+    }
+
+    /// Returns `true` if the signature proves knowledge of `r` such that `R = r * B` (or `r * G`) and `D = r * A`.
+    fn check(
+        prefix_hash: &[u8; 32],
+        R: &EdwardsPoint,
+        A: &EdwardsPoint,
+        B: Option<&EdwardsPoint>,
+        D: &EdwardsPoint,
+        signature: &[u8; 64],
+    ) -> bool {
+        let (mut c, mut s) = ([0u8; 32], [0u8; 32]);
+        c.copy_from_slice(&signature[0..32]);
+        s.copy_from_slice(&signature[32..64]);
+
+        let (c, s) = match (Scalar::from_canonical_bytes(c), Scalar::from_canonical_bytes(s)) {
+            (Some(c), Some(s)) => (c, s),
+            _ => return false,
+        };
+
+        // The shared secret must lie in the prime order subgroup
+        if !D.is_torsion_free() {
+            return false;
+        }
+
+        let X = c * R + s * B.unwrap_or(&ED25519_BASEPOINT_POINT);
+        let Y = c * D + s * A;
+
+        Self::challenge(prefix_hash, R, A, B, D, &X, &Y) == c
+    }
+}
+
+/// Returns the Edwards point of the given compressed point.
+fn decompress(point: &[u8; 32]) -> Result<EdwardsPoint, ProofError> {
+    match CompressedEdwardsY::from_slice(point).decompress() {
+        Some(point) => Ok(point),
+        None => Err(ProofError::EdwardsPointError(*point)),
+    }
+}
+
+impl FromStr for MoneroTransactionProof {
+    type Err = ProofError;
+
+    fn from_str(proof: &str) -> Result<Self, Self::Err> {
+        let (proof_type, data) = if proof.starts_with(MoneroProofType::Outbound.header()) {
+            (
+                MoneroProofType::Outbound,
+                &proof[MoneroProofType::Outbound.header().len()..],
+            )
+        } else if proof.starts_with(MoneroProofType::Inbound.header()) {
+            (
+                MoneroProofType::Inbound,
+                &proof[MoneroProofType::Inbound.header().len()..],
+            )
+        } else {
+            return Err(ProofError::InvalidProofHeader(proof.chars().take(10).collect()));
+        };
+
+        // Each entry is base58(D) || base58(c || r), which encode to 44 and 88 characters
+        if data.is_empty() || data.len() % 132 != 0 || !data.is_char_boundary(data.len()) {
+            return Err(ProofError::InvalidProof(format!("invalid proof length {}", data.len())));
+        }
+
+        let mut shared_secrets = vec![];
+        let mut signatures = vec![];
+        for index in (0..data.len()).step_by(132) {
+            let shared_secret = base58::decode(&data[index..index + 44])?;
+            let signature = base58::decode(&data[index + 44..index + 132])?;
+            if shared_secret.len() != 32 || signature.len() != 64 {
+                return Err(ProofError::InvalidProof("invalid proof encoding".into()));
+            }
+
+            let mut D = [0u8; 32];
+            let mut sig = [0u8; 64];
+            D.copy_from_slice(&shared_secret);
+            sig.copy_from_slice(&signature);
+            shared_secrets.push(D);
+            signatures.push(sig);
+        }
+
+        Ok(Self {
+            proof_type,
+            shared_secrets,
+            signatures,
+        })
+    }
+}
+
+impl fmt::Display for MoneroTransactionProof {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut proof = self.proof_type.header().to_string();
+        for (shared_secret, signature) in self.shared_secrets.iter().zip(&self.signatures) {
+            match (base58::encode(shared_secret), base58::encode(signature)) {
+                (Ok(shared_secret), Ok(signature)) => {
+                    proof.push_str(&shared_secret);
+                    proof.push_str(&signature);
+                }
+                _ => return Err(fmt::Error),
+            }
+        }
+        write!(f, "{}", proof)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::private_key::MoneroPrivateKey;
+    use crate::public_key::MoneroPublicKey;
+    use crate::Mainnet;
+    use rand::{rngs::StdRng, SeedableRng};
+    use wagyu_model::{PrivateKey, PublicKey};
+
+    type N = Mainnet;
+
+    const PRIVATE_SPEND_KEY: &str = "c595161ea20ccd8c692947c2d3ced471e9b13a18b150c881232794e8042bf107";
+    const TRANSACTION_ID: [u8; 32] = [7u8; 32];
+
+    fn private_key(format: &MoneroFormat) -> MoneroPrivateKey<N> {
+        MoneroPrivateKey::<N>::from_private_spend_key(PRIVATE_SPEND_KEY, format).unwrap()
+    }
+
+    fn transaction_key(rng: &mut StdRng, address: &MoneroAddress<N>) -> ([u8; 32], [u8; 32]) {
+        let r = Scalar::random(rng);
+        let (_, B) = MoneroTransactionProof::address_points(address).unwrap();
+        let R = r * B.unwrap_or(ED25519_BASEPOINT_POINT);
+        (r.to_bytes(), R.compress().to_bytes())
+    }
+
+    fn test_proofs(format: &MoneroFormat) {
+        let rng = &mut StdRng::from_seed([1u8; 32]);
+        let private_key = private_key(format);
+        let address = private_key.to_address(format).unwrap();
+        let (r, R) = transaction_key(rng, &address);
+
+        let outbound = MoneroTransactionProof::outbound(rng, &TRANSACTION_ID, &address, &[r], b"message").unwrap();
+        assert!(outbound.verify(&TRANSACTION_ID, &address, &[R], b"message").unwrap());
+        assert!(!outbound.verify(&TRANSACTION_ID, &address, &[R], b"other").unwrap());
+        assert!(!outbound.verify(&[8u8; 32], &address, &[R], b"message").unwrap());
+
+        // Subaddresses are proven with the private view key of the account
+        let private_view_key = private_key.to_private_view_key();
+        let inbound =
+            MoneroTransactionProof::inbound(rng, &TRANSACTION_ID, &address, &private_view_key, &[R], b"message")
+                .unwrap();
+        assert!(inbound.verify(&TRANSACTION_ID, &address, &[R], b"message").unwrap());
+        assert!(!inbound.verify(&TRANSACTION_ID, &address, &[R], b"other").unwrap());
+
+        // Both sides derive the same shared secret
+        assert_eq!(outbound.shared_secrets, inbound.shared_secrets);
+    }
+
+    #[test]
+    fn standard_proofs() {
+        test_proofs(&MoneroFormat::Standard);
+    }
+
+    #[test]
+    fn subaddress_proofs() {
+        test_proofs(&MoneroFormat::Subaddress(0, 1));
+    }
+
+    #[test]
+    fn to_string_from_str() {
+        let rng = &mut StdRng::from_seed([2u8; 32]);
+        let address = private_key(&MoneroFormat::Standard)
+            .to_address(&MoneroFormat::Standard)
+            .unwrap();
+        let (r, R) = transaction_key(rng, &address);
+
+        let proof = MoneroTransactionProof::outbound(rng, &TRANSACTION_ID, &address, &[r], b"").unwrap();
+        let proof_string = proof.to_string();
+        assert!(proof_string.starts_with("OutProofV2"));
+        assert_eq!(10 + 132, proof_string.len());
+
+        let decoded = MoneroTransactionProof::from_str(&proof_string).unwrap();
+        assert_eq!(proof, decoded);
+        assert!(decoded.verify(&TRANSACTION_ID, &address, &[R], b"").unwrap());
+    }
+
+    /// Pins the outbound proof of a fixed transaction key, address, and message, as a regression test of this
+    /// implementation. It was checked with a separate implementation of `check_tx_proof`, but was not produced by
+    /// `monero-wallet-cli`, so it is no evidence of compatibility with the Monero wallet.
+    #[test]
+    fn outbound_proof_regression() {
+        let rng = &mut StdRng::from_seed([4u8; 32]);
+        let address = private_key(&MoneroFormat::Standard)
+            .to_address(&MoneroFormat::Standard)
+            .unwrap();
+        let mut r = [0u8; 32];
+        r.copy_from_slice(&hex::decode("0f3fe9c20b24a11bf4d6d1acd335c6a80543f1f0380590d7323caf1390c78e0e").unwrap());
+        let R = (Scalar::from_bytes_mod_order(r) * ED25519_BASEPOINT_POINT)
+            .compress()
+            .to_bytes();
+
+        // The proof string is `OutProofV2` followed by base58(D) and base58(c || s)
+        let proof = MoneroTransactionProof::outbound(rng, &TRANSACTION_ID, &address, &[r], b"wagyu").unwrap();
+        assert_eq!(
+            "43tXwm6UNNvSyMdHU4Jfeg4GRgU7KEVAfHo3B5RrXYMjZMRaowr68y12HSo14wv2qcYqqpG1U5AHrJtBdFHKPDEA9UxK6Hy",
+            address.to_string()
+        );
+        assert_eq!(
+            "d7ad49fb72eac7f6f2a964763d2d003522f54c076f2d2203b214ef2bbdba3a1d",
+            hex::encode(R)
+        );
+        assert_eq!(
+            "OutProofV2KTUtKhbSfpdWyp3i8dPrhF2btkFW6japT2nAU1eqghDQNjuc9UteiNWeqc3V2bszQpYVp14PBMNuHhqUPvNMQnGLH2TcQ7heMYd9hGBdUx9f6SgvcZVmaPVFk2nCgsGMGVAR",
+            proof.to_string()
+        );
+
+        let decoded = MoneroTransactionProof::from_str(&proof.to_string()).unwrap();
+        assert!(decoded.verify(&TRANSACTION_ID, &address, &[R], b"wagyu").unwrap());
+        assert!(!decoded.verify(&TRANSACTION_ID, &address, &[R], b"wagyv").unwrap());
+    }
+
+    #[test]
+    fn invalid_proofs() {
+        assert!(MoneroTransactionProof::from_str("SpendProofV1").is_err());
+        assert!(MoneroTransactionProof::from_str("OutProofV2").is_err());
+        assert!(MoneroTransactionProof::from_str("InProofV2abc").is_err());
+
+        let rng = &mut StdRng::from_seed([3u8; 32]);
+        let address = private_key(&MoneroFormat::Standard)
+            .to_address(&MoneroFormat::Standard)
+            .unwrap();
+        let other_address = MoneroPublicKey::<N>::from_private_key(&MoneroPrivateKey::<N>::new(rng).unwrap())
+            .to_address(&MoneroFormat::Standard)
+            .unwrap();
+        let (r, R) = transaction_key(rng, &address);
+
+        let proof = MoneroTransactionProof::outbound(rng, &TRANSACTION_ID, &address, &[r], b"").unwrap();
+        assert!(!proof.verify(&TRANSACTION_ID, &other_address, &[R], b"").unwrap());
+        assert!(proof.verify(&TRANSACTION_ID, &address, &[R, R], b"").is_err());
+
+        let private_view_key = private_key(&MoneroFormat::Standard).to_private_view_key();
+        assert!(
+            MoneroTransactionProof::inbound(rng, &TRANSACTION_ID, &other_address, &private_view_key, &[R], b"")
+                .is_err()
+        );
+    }
+}
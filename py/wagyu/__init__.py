@@ -0,0 +1,189 @@
+"""Python bindings to wagyu.
+
+Exposes the wagyu model (mnemonics, extended private keys, private keys, and addresses)
+as Python classes, calling the C ABI of the `wagyu-ffi` library through `ctypes`.
+
+The library is loaded from the path in the `WAGYU_FFI_LIBRARY` environment variable, if set,
+or otherwise found by name on the system library path.
+
+    >>> from wagyu import Mnemonic
+    >>> mnemonic = Mnemonic("abandon abandon abandon abandon abandon abandon "
+    ...                     "abandon abandon abandon abandon abandon about")
+    >>> key = mnemonic.to_extended_private_key("ethereum").derive("m/44'/60'/0'/0/0")
+    >>> str(key.to_address())
+    '0x9858EfFD232B4033E47d90003D41EC34EcaEda94'
+"""
+
+import ctypes
+import ctypes.util
+import os
+
+__all__ = ["Address", "ExtendedPrivateKey", "Mnemonic", "PrivateKey", "WagyuError"]
+
+WAGYU_OK = 0
+WAGYU_ERROR = 1
+WAGYU_INVALID_ARGUMENT = 2
+
+# The environment variable to read the path of the wagyu-ffi library from
+WAGYU_FFI_LIBRARY = "WAGYU_FFI_LIBRARY"
+
+# The coins and their default address formats
+COINS = {"bitcoin": "bech32", "ethereum": None}
+
+
+class WagyuError(Exception):
+    """An error returned by wagyu."""
+
+
+def _load():
+    path = os.environ.get(WAGYU_FFI_LIBRARY) or ctypes.util.find_library("wagyu_ffi")
+    if path is None:
+        raise WagyuError("wagyu-ffi library not found, set %s to its path" % WAGYU_FFI_LIBRARY)
+    library = ctypes.CDLL(path)
+
+    output = ctypes.POINTER(ctypes.c_void_p)
+    signatures = {
+        "wagyu_generate_mnemonic": [ctypes.c_char_p, ctypes.c_size_t, ctypes.c_uint8, output],
+        "wagyu_bitcoin_address": [ctypes.c_char_p] * 5 + [output],
+        "wagyu_bitcoin_private_key": [ctypes.c_char_p] * 4 + [output],
+        "wagyu_bitcoin_private_key_address": [ctypes.c_char_p] * 2 + [output],
+        "wagyu_ethereum_address": [ctypes.c_char_p] * 3 + [output],
+        "wagyu_ethereum_private_key": [ctypes.c_char_p] * 3 + [output],
+        "wagyu_ethereum_private_key_address": [ctypes.c_char_p] + [output],
+        "wagyu_ethereum_sign_transaction": [ctypes.c_char_p] * 3 + [output],
+    }
+    for name, arguments in signatures.items():
+        function = getattr(library, name)
+        function.argtypes = arguments
+        function.restype = ctypes.c_int32
+    library.wagyu_string_free.argtypes = [ctypes.c_void_p]
+    library.wagyu_string_free.restype = None
+    return library
+
+
+_library = None
+
+
+def _call(name, *arguments):
+    """Calls the given wagyu-ffi function, returning its output or raising its error."""
+    global _library
+    if _library is None:
+        _library = _load()
+
+    encoded = [argument.encode("utf-8") if isinstance(argument, str) else argument for argument in arguments]
+    output = ctypes.c_void_p()
+    status = getattr(_library, name)(*encoded, ctypes.byref(output))
+    if status == WAGYU_INVALID_ARGUMENT:
+        raise WagyuError("invalid argument")
+
+    try:
+        result = ctypes.string_at(output.value).decode("utf-8")
+    finally:
+        _library.wagyu_string_free(output)
+    if status != WAGYU_OK:
+        raise WagyuError(result)
+    return result
+
+
+def _coin(coin):
+    if coin not in COINS:
+        raise WagyuError("unsupported coin: %s" % coin)
+    return coin
+
+
+class Mnemonic(object):
+    """An English BIP39 mnemonic."""
+
+    def __init__(self, phrase):
+        self.phrase = phrase
+
+    @classmethod
+    def generate(cls, word_count=12, seed=None):
+        """Returns a new mnemonic of the given word count, generated from the given
+        32 bytes of entropy, or from the operating system RNG if no seed is given."""
+        length = 0 if seed is None else len(seed)
+        return cls(_call("wagyu_generate_mnemonic", seed, length, word_count))
+
+    def to_extended_private_key(self, coin, password=None, network="mainnet"):
+        """Returns the master extended private key of the mnemonic and password for the given coin."""
+        return ExtendedPrivateKey(_coin(coin), self, password, network)
+
+    def __str__(self):
+        return self.phrase
+
+    def __repr__(self):
+        return "Mnemonic(<%d words>)" % len(self.phrase.split())
+
+
+class ExtendedPrivateKey(object):
+    """The master extended private key of a mnemonic, from which private keys are derived."""
+
+    def __init__(self, coin, mnemonic, password=None, network="mainnet"):
+        self.coin = _coin(coin)
+        self.mnemonic = mnemonic
+        self.password = password
+        self.network = network
+
+    def derive(self, path):
+        """Returns the private key derived at the given path."""
+        phrase = str(self.mnemonic)
+        if self.coin == "bitcoin":
+            key = _call("wagyu_bitcoin_private_key", phrase, self.password, path, self.network)
+        else:
+            key = _call("wagyu_ethereum_private_key", phrase, self.password, path)
+        return PrivateKey(self.coin, key, self.network)
+
+    def __repr__(self):
+        return "ExtendedPrivateKey(%s, %s)" % (self.coin, self.network)
+
+
+class PrivateKey(object):
+    """A private key, in WIF for Bitcoin or in hex for Ethereum."""
+
+    def __init__(self, coin, key, network="mainnet"):
+        self.coin = _coin(coin)
+        self.key = key
+        self.network = network
+
+    def to_address(self, format=None):
+        """Returns the address of the private key in the given format
+        (`legacy`, `segwit`, or `bech32` for Bitcoin)."""
+        if self.coin == "bitcoin":
+            address = _call("wagyu_bitcoin_private_key_address", self.key, format or COINS["bitcoin"])
+        else:
+            address = _call("wagyu_ethereum_private_key_address", self.key)
+        return Address(self.coin, address)
+
+    def sign_transaction(self, transaction_hex):
+        """Returns the signed transaction hex of the given unsigned Ethereum transaction hex."""
+        if self.coin != "ethereum":
+            raise WagyuError("transaction signing is only supported for ethereum")
+        return _call("wagyu_ethereum_sign_transaction", transaction_hex, self.key, self.network)
+
+    def __str__(self):
+        return self.key
+
+    def __repr__(self):
+        return "PrivateKey(%s, <redacted>)" % self.coin
+
+
+class Address(object):
+    """An address of a coin."""
+
+    def __init__(self, coin, address):
+        self.coin = _coin(coin)
+        self.address = address
+
+    def __eq__(self, other):
+        if isinstance(other, Address):
+            return (self.coin, self.address) == (other.coin, other.address)
+        return self.address == other
+
+    def __hash__(self):
+        return hash((self.coin, self.address))
+
+    def __str__(self):
+        return self.address
+
+    def __repr__(self):
+        return "Address(%s, %s)" % (self.coin, self.address)
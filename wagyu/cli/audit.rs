@@ -0,0 +1,111 @@
+//! # Audit
+//!
+//! An append-only audit log of the operations performed, written as JSON lines.
+//! Each record holds the time, the operation, and its public fields (such as paths,
+//! addresses, and transaction ids). The audit log never records secrets.
+//!
+//! With `--log-file`, the command-line tool records each command and the public fields
+//! of the wallets it produced to the process audit log, as evidence of key-ceremony steps.
+//!
+//! The records are written with `serde_json`, rather than as `tracing` events with the JSON
+//! formatter of `tracing-subscriber`, as neither is a dependency of wagyu. A record is one JSON
+//! object per line, so the log is read by the same tools as the JSON logs of a subscriber.
+
+use crate::cli::{CLIError, RedactionPolicy};
+
+use lazy_static::lazy_static;
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+use std::{
+    fs::{File, OpenOptions},
+    io::Write,
+    sync::{Mutex, RwLock},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use crate::model::no_std::{ToString, Vec};
+
+/// The wallet fields that are recorded to the audit log, none of which are secret
+pub const AUDIT_FIELDS: [&str; 6] = ["path", "address", "format", "network", "transaction_id", "valid"];
+
+lazy_static! {
+    /// The process audit log, if opened with `--log-file`
+    static ref LOG_FILE: RwLock<Option<AuditLog>> = RwLock::new(None);
+}
+
+/// Represents an append-only audit log
+#[derive(Debug)]
+pub struct AuditLog {
+    file: Mutex<File>,
+}
+
+impl AuditLog {
+    /// Returns the audit log appending to the file at the given path.
+    pub fn open(path: &str) -> Result<Self, CLIError> {
+        Ok(Self {
+            file: Mutex::new(OpenOptions::new().create(true).append(true).open(path)?),
+        })
+    }
+
+    /// Appends a record of the given operation and its public fields.
+    pub fn record(&self, operation: &str, fields: Value) -> Result<(), CLIError> {
+        let time = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+
+        let mut record = json!({ "time": time, "operation": operation });
+        if let (Value::Object(record), Value::Object(fields)) = (&mut record, fields) {
+            record.extend(fields);
+        }
+
+        let mut file = self
+            .file
+            .lock()
+            .map_err(|error| CLIError::Crate("audit", error.to_string()))?;
+        writeln!(file, "{}", serde_json::to_string(&record)?)?;
+        Ok(file.flush()?)
+    }
+
+    /// Opens the process audit log at the given path, to which `log_wallets` records.
+    pub fn set_log_file(path: &str) -> Result<(), CLIError> {
+        let audit_log = Self::open(path)?;
+        let mut log_file = LOG_FILE
+            .write()
+            .map_err(|error| CLIError::Crate("audit", error.to_string()))?;
+        *log_file = Some(audit_log);
+        Ok(())
+    }
+
+    /// Records the given operation and the public fields of the given wallets
+    /// to the process audit log, if it is open, with the fields of the redaction policy redacted.
+    pub fn log_wallets<T: Serialize>(operation: &str, wallets: &[T]) -> Result<(), CLIError> {
+        let log_file = LOG_FILE
+            .read()
+            .map_err(|error| CLIError::Crate("audit", error.to_string()))?;
+        let audit_log = match log_file.as_ref() {
+            Some(audit_log) => audit_log,
+            None => return Ok(()),
+        };
+
+        let policy = RedactionPolicy::current();
+        let mut records = Vec::with_capacity(wallets.len());
+        for wallet in wallets {
+            records.push(Self::public_fields(serde_json::to_value(policy.redact(wallet)?)?));
+        }
+        audit_log.record(operation, json!({ "wallets": records }))
+    }
+
+    /// Returns the audit fields of the given wallet, dropping every other field.
+    fn public_fields(wallet: Value) -> Value {
+        let mut fields = Map::new();
+        if let Value::Object(wallet) = wallet {
+            for (name, value) in wallet {
+                if AUDIT_FIELDS.contains(&name.as_str()) && !value.is_null() {
+                    fields.insert(name, value);
+                }
+            }
+        }
+        Value::Object(fields)
+    }
+}
@@ -0,0 +1,189 @@
+//! # Backend
+//!
+//! The registry of the coins of the command-line tool. Each coin registers itself as a `CoinBackend`,
+//! which builds its subcommand, runs it, and generates and verifies its wallets, so the dispatch of the
+//! tool and the commands over several coins (`generate`, `portfolio`, and `verify`) do not name the coins.
+//! The `info` subcommand of each coin prints its parameters, which the coin returns from its networks.
+//!
+//! A coin of another crate implements `CLI`, and is registered in `Registry::default` behind a
+//! feature that enables the crate, without changes to the dispatch of the tool.
+
+use crate::cli::bitcoin::BitcoinCLI;
+use crate::cli::ethereum::EthereumCLI;
+use crate::cli::monero::MoneroCLI;
+use crate::cli::nostr::NostrCLI;
+use crate::cli::zcash::ZcashCLI;
+use crate::cli::{
+    types::{NameType, ValueHint},
+    CLIError, Mismatch, CLI,
+};
+use crate::model::ChildIndex;
+
+use clap::{App, ArgMatches};
+use core::marker::PhantomData;
+use serde_json::Value;
+
+use crate::model::no_std::{ToString, Vec};
+
+/// The coin of a subcommand of the command-line tool
+pub trait CoinBackend {
+    /// Returns the name of the coin, which is the name of its subcommand.
+    fn name(&self) -> NameType;
+
+    /// Returns the subcommand of the coin, suggesting the given value hints for its options in shell completions.
+    fn subcommand<'b>(&self, hints: &'b [ValueHint]) -> App<'static, 'b>;
+
+    /// Parses the given arguments of the subcommand of the coin, and prints its output.
+    fn run(&self, arguments: &ArgMatches) -> Result<(), CLIError>;
+
+    /// Returns the given number of new wallets, derived from the given BIP-39 mnemonic and password
+    /// if a mnemonic is specified.
+    fn generate(&self, count: usize, mnemonic: Option<&str>, password: Option<&str>) -> Result<Vec<Value>, CLIError>;
+
+    /// Returns the values of the options of the coin to suggest in shell completions.
+    fn value_hints(&self) -> Result<Vec<ValueHint>, CLIError>;
+
+    /// Returns the mismatches between the stored fields of the given wallet of a prior output and
+    /// the fields re-derived from it, or `None` if the wallet is not a wallet of the coin.
+    fn verify(&self, wallet: &Value) -> Result<Option<Vec<Mismatch>>, CLIError>;
+}
+
+/// Returns the BIP-44 coin type of the given child index of a network, without the offset of a hardened index.
+pub fn to_coin_type(index: ChildIndex) -> u32 {
+    match index {
+        ChildIndex::Hardened(coin_type) | ChildIndex::Normal(coin_type) => coin_type,
+    }
+}
+
+/// Represents the backend of a coin implementing `CLI`
+pub struct Backend<C: CLI>(PhantomData<C>);
+
+impl<C: CLI> Backend<C> {
+    /// Returns the backend of the coin.
+    pub fn new() -> Self {
+        Self(PhantomData)
+    }
+}
+
+impl<C: CLI> Default for Backend<C> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<C: CLI> CoinBackend for Backend<C> {
+    fn name(&self) -> NameType {
+        C::NAME
+    }
+
+    #[cfg_attr(tarpaulin, skip)]
+    fn subcommand<'b>(&self, hints: &'b [ValueHint]) -> App<'static, 'b> {
+        C::new_with_hints(hints)
+    }
+
+    #[cfg_attr(tarpaulin, skip)]
+    fn run(&self, arguments: &ArgMatches) -> Result<(), CLIError> {
+        match arguments.subcommand() {
+            ("info", Some(_)) => {
+                println!("{}\n", serde_json::to_string_pretty(&C::info()?)?);
+                Ok(())
+            }
+            _ => C::print(C::parse(arguments)?),
+        }
+    }
+
+    fn generate(&self, count: usize, mnemonic: Option<&str>, password: Option<&str>) -> Result<Vec<Value>, CLIError> {
+        C::generate(count, mnemonic, password)
+    }
+
+    #[cfg_attr(tarpaulin, skip)]
+    fn value_hints(&self) -> Result<Vec<ValueHint>, CLIError> {
+        C::value_hints()
+    }
+
+    fn verify(&self, wallet: &Value) -> Result<Option<Vec<Mismatch>>, CLIError> {
+        C::verify(wallet)
+    }
+}
+
+/// Represents the registered coins, in the order of registration
+pub struct Registry {
+    backends: Vec<Box<dyn CoinBackend>>,
+}
+
+impl Registry {
+    /// Returns an empty registry.
+    pub fn new() -> Self {
+        Self { backends: Vec::new() }
+    }
+
+    /// Registers the given coin, which must not share its name with a registered coin.
+    pub fn register(&mut self, backend: Box<dyn CoinBackend>) -> Result<(), CLIError> {
+        match self
+            .backends
+            .iter()
+            .any(|registered| registered.name() == backend.name())
+        {
+            true => Err(CLIError::DuplicateCoin(backend.name().to_string())),
+            false => {
+                self.backends.push(backend);
+                Ok(())
+            }
+        }
+    }
+
+    /// Returns the registered coin of the given name.
+    pub fn find(&self, name: &str) -> Result<&dyn CoinBackend, CLIError> {
+        match self.backends.iter().find(|backend| backend.name() == name) {
+            Some(backend) => Ok(backend.as_ref()),
+            None => Err(CLIError::UnknownCoin(name.to_string(), self.names().join(", "))),
+        }
+    }
+
+    /// Returns the names of the registered coins.
+    pub fn names(&self) -> Vec<NameType> {
+        self.backends.iter().map(|backend| backend.name()).collect()
+    }
+
+    /// Returns the registered coins.
+    pub fn backends(&self) -> impl Iterator<Item = &dyn CoinBackend> {
+        self.backends.iter().map(|backend| backend.as_ref())
+    }
+
+    /// Returns the subcommands of the registered coins, suggesting the given value hints of each coin
+    /// (in the order of registration) in shell completions. A coin without value hints suggests none.
+    #[cfg_attr(tarpaulin, skip)]
+    pub fn subcommands<'b>(&self, hints: &'b [Vec<ValueHint>]) -> Vec<App<'static, 'b>> {
+        self.backends
+            .iter()
+            .enumerate()
+            .map(|(i, backend)| backend.subcommand(hints.get(i).map(Vec::as_slice).unwrap_or_default()))
+            .collect()
+    }
+
+    /// Returns the value hints of the registered coins, in the order of registration.
+    #[cfg_attr(tarpaulin, skip)]
+    pub fn value_hints(&self) -> Result<Vec<Vec<ValueHint>>, CLIError> {
+        self.backends.iter().map(|backend| backend.value_hints()).collect()
+    }
+}
+
+impl Default for Registry {
+    /// Returns the registry of the coins of wagyu.
+    fn default() -> Self {
+        let backends: Vec<Box<dyn CoinBackend>> = vec![
+            Box::new(Backend::<BitcoinCLI>::new()),
+            Box::new(Backend::<EthereumCLI>::new()),
+            Box::new(Backend::<MoneroCLI>::new()),
+            Box::new(Backend::<NostrCLI>::new()),
+            Box::new(Backend::<ZcashCLI>::new()),
+        ];
+        Self { backends }
+    }
+}
+
+impl core::fmt::Debug for Registry {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_struct("Registry").field("coins", &self.names()).finish()
+    }
+}
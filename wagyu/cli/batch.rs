@@ -0,0 +1,463 @@
+//! # Batch
+//!
+//! Imports wallets from a file of keys or addresses, with one entry per line or in a column of a CSV file,
+//! and prints the result of each entry as a line of NDJSON, continuing past the entries that fail to import.
+//!
+//! Signs the prepared transactions of a JSON file, each with the key derived at its own derivation path,
+//! and prints the signed transaction of each entry as a line of NDJSON, continuing past the entries that fail to sign.
+//!
+//! Derives the addresses of a range of indices of an extended public key as lines of NDJSON, such as the
+//! deposit addresses of an exchange, in chunks of a bounded number of addresses. A checkpoint file is written
+//! after each chunk, from whose next index an interrupted batch is resumed.
+
+use crate::cli::{localize, AuditLog, CLIError, MaskSecrets, ProgressBar};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::json;
+use std::{
+    fs::{self, File, OpenOptions},
+    io::{self, BufWriter, Write},
+    ops::Range,
+    panic,
+    path::Path,
+    thread,
+};
+
+use crate::model::no_std::{format, String, ToString, Vec};
+
+/// An entry of a batch file, with its line number (from 1)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BatchEntry {
+    pub line: usize,
+    pub value: String,
+}
+
+/// Returns the entries of the given batch file, skipping blank lines and `#` comments.
+/// If a column is specified, each line is read as CSV, and the column is either an index (from 1),
+/// or the name of a column in the header on the first line. A line without the column is an empty entry.
+pub fn read_batch_file(path: &str, column: Option<&str>) -> Result<Vec<BatchEntry>, CLIError> {
+    let contents = fs::read_to_string(path)?;
+    let mut lines = contents
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'));
+
+    let column = match column {
+        Some(column) => match column.parse::<usize>() {
+            Ok(index) if index > 0 => Some(index - 1),
+            Ok(_) => return Err(CLIError::MissingBatchColumn(column.into())),
+            Err(_) => {
+                let header = lines.next().map(|(_, line)| split_csv(line)).unwrap_or_default();
+                let index = header.iter().position(|name| name.eq_ignore_ascii_case(column));
+                Some(index.ok_or_else(|| CLIError::MissingBatchColumn(column.into()))?)
+            }
+        },
+        None => None,
+    };
+
+    Ok(lines
+        .map(|(line, value)| BatchEntry {
+            line,
+            value: match column {
+                Some(column) => split_csv(value).get(column).cloned().unwrap_or_default(),
+                None => value.into(),
+            },
+        })
+        .collect())
+}
+
+/// Returns the fields of the given CSV line (RFC 4180), without their surrounding whitespace.
+/// A quoted field may contain commas and escaped quotes (`""`), but not a line break.
+fn split_csv(line: &str) -> Vec<String> {
+    let mut fields = Vec::new();
+    let mut chars = line.chars().peekable();
+    loop {
+        let mut field = String::new();
+
+        // A quoted field starts with a quote, after any whitespace
+        while matches!(chars.peek(), Some(c) if c.is_whitespace()) {
+            chars.next();
+        }
+        if chars.peek() == Some(&'"') {
+            chars.next();
+            while let Some(c) = chars.next() {
+                match c {
+                    '"' if chars.peek() == Some(&'"') => {
+                        chars.next();
+                        field.push('"');
+                    }
+                    '"' => break,
+                    c => field.push(c),
+                }
+            }
+        }
+
+        // The rest of the field, up to the next comma, is unquoted
+        let mut separated = false;
+        for c in chars.by_ref() {
+            if c == ',' {
+                separated = true;
+                break;
+            }
+            field.push(c);
+        }
+        fields.push(field.trim().to_string());
+        if !separated {
+            return fields;
+        }
+    }
+}
+
+/// Imports each of the given entries with the given import function, printing a line of NDJSON
+/// with either the wallet (with its secrets masked if specified) or the error of each entry,
+/// and records the imported wallets to the audit log.
+pub fn import_batch<T: Clone + MaskSecrets + Serialize, F: Fn(&str) -> Result<T, CLIError>>(
+    operation: &str,
+    entries: &[BatchEntry],
+    mask_secrets: bool,
+    import: F,
+) -> Result<(), CLIError> {
+    let (results, wallets) = import_entries(entries, mask_secrets, import);
+    for result in &results {
+        println!("{}", serde_json::to_string(result)?);
+    }
+
+    eprintln!(
+        "{}",
+        localize(&format!("Imported {} of {} entries", wallets.len(), entries.len()))
+    );
+    AuditLog::log_wallets(operation, &wallets)
+}
+
+/// Imports each of the given entries with the given import function, and returns the result of each entry
+/// (its wallet, with its secrets masked if specified, or its error) and the imported wallets.
+fn import_entries<T: Clone + MaskSecrets + Serialize, F: Fn(&str) -> Result<T, CLIError>>(
+    entries: &[BatchEntry],
+    mask_secrets: bool,
+    import: F,
+) -> (Vec<serde_json::Value>, Vec<T>) {
+    let mut results = Vec::with_capacity(entries.len());
+    let mut wallets = Vec::with_capacity(entries.len());
+    for entry in entries {
+        results.push(match import(&entry.value) {
+            Ok(wallet) => {
+                let mut masked = wallet.clone();
+                if mask_secrets {
+                    masked.mask_secrets();
+                }
+                wallets.push(wallet);
+                json!({ "line": entry.line, "wallet": masked })
+            }
+            Err(error) => json!({ "line": entry.line, "error": error.to_string() }),
+        });
+    }
+    (results, wallets)
+}
+
+/// Returns the prepared transactions of the given JSON batch file, an array of entries.
+pub fn read_transaction_batch_file<E: DeserializeOwned>(path: &str) -> Result<Vec<E>, CLIError> {
+    Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+}
+
+/// Signs each of the given prepared transactions with the given signing function, printing a line of NDJSON
+/// with either the signed transaction or the error of each entry (from 1),
+/// and records the signed transactions to the audit log.
+pub fn sign_batch<E, T: Serialize, F: Fn(&E) -> Result<T, CLIError>>(
+    operation: &str,
+    entries: &[E],
+    sign: F,
+) -> Result<(), CLIError> {
+    let mut wallets = Vec::with_capacity(entries.len());
+    for (index, entry) in entries.iter().enumerate() {
+        let result = match sign(entry) {
+            Ok(wallet) => {
+                let result = json!({ "entry": index + 1, "wallet": wallet });
+                wallets.push(wallet);
+                result
+            }
+            Err(error) => json!({ "entry": index + 1, "error": error.to_string() }),
+        };
+        println!("{}", serde_json::to_string(&result)?);
+    }
+
+    eprintln!(
+        "{}",
+        localize(&format!("Signed {} of {} entries", wallets.len(), entries.len()))
+    );
+    AuditLog::log_wallets(operation, &wallets)
+}
+
+/// Represents a batch of addresses of an extended public key, and its checkpoint file
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AddressBatch {
+    /// The extended public key of the addresses
+    pub extended_public_key: String,
+    /// The path of the parent of the addresses, relative to the extended public key
+    pub path: String,
+    /// The format of the addresses
+    pub format: String,
+    /// The index after the last address of the batch
+    pub count: u32,
+    /// The index of the next address to derive
+    pub next_index: u32,
+    /// The length of the output file up to the next index, if the addresses are written to a file
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub output_length: Option<u64>,
+}
+
+/// Represents an address of a batch, printed as a line of NDJSON
+#[derive(Serialize, Clone, Debug)]
+struct BatchAddress<'a> {
+    index: u32,
+    path: String,
+    address: &'a str,
+}
+
+impl AddressBatch {
+    /// Returns `true` if the given batch derives the same addresses.
+    fn is_same_batch(&self, other: &Self) -> bool {
+        self.extended_public_key == other.extended_public_key && self.path == other.path && self.format == other.format
+    }
+
+    /// Writes the batch to the checkpoint file at the given path, replacing it only once it is written in full.
+    fn write_checkpoint(&self, path: &str) -> Result<(), CLIError> {
+        let temporary = format!("{}.tmp", path);
+        fs::write(&temporary, serde_json::to_string(self)?)?;
+        Ok(fs::rename(&temporary, path)?)
+    }
+}
+
+/// Derives the addresses of the given batch from its next index, with the given derivation function of the
+/// addresses of a range of indices, and prints each address as a line of NDJSON to the given output file or standard output.
+/// The addresses are derived in chunks of the given size, each derived on the given number of threads and
+/// written before the next is derived, and the checkpoint file, if specified, is written after each chunk.
+///
+/// A resumed batch is appended to its output file, which is first truncated to the length of its checkpoint,
+/// if the checkpoint is of the resumed index, to discard the addresses written after the checkpoint.
+pub fn derive_address_batch<F: Fn(Range<u32>) -> Result<Vec<String>, CLIError> + Sync>(
+    batch: &mut AddressBatch,
+    resume: bool,
+    checkpoint: Option<&str>,
+    output: Option<&str>,
+    chunk_size: usize,
+    threads: usize,
+    derive: F,
+) -> Result<(), CLIError> {
+    let start = batch.next_index;
+    let mut output_length = None;
+    if let Some(path) = checkpoint.filter(|path| Path::new(path).exists()) {
+        let saved: AddressBatch = serde_json::from_str(&fs::read_to_string(path)?)?;
+        if !saved.is_same_batch(batch) {
+            let reason = "of another extended public key, path, or format".to_string();
+            return Err(CLIError::InvalidCheckpointFile(path.into(), reason));
+        }
+        if resume && saved.next_index == start {
+            output_length = saved.output_length;
+        }
+    }
+
+    let mut file = match output {
+        Some(path) if resume => {
+            let file = OpenOptions::new().create(true).append(true).open(path)?;
+            if let Some(length) = output_length {
+                file.set_len(length)?;
+            }
+            Some(file)
+        }
+        Some(path) => Some(File::create(path)?),
+        None => None,
+    };
+
+    // The progress bar is only drawn with an output file, as it would be interleaved with standard output
+    let progress = output.map(|_| ProgressBar::new("Deriving", batch.count.saturating_sub(start) as u64));
+    let chunk_size = chunk_size.max(1) as u32;
+    let threads = threads.max(1) as u32;
+    while batch.next_index < batch.count {
+        let chunk_start = batch.next_index;
+        let chunk_end = chunk_start.saturating_add(chunk_size).min(batch.count);
+
+        // Each thread derives a contiguous part of the chunk, so the parts are written in order
+        let part_size = (chunk_end - chunk_start).div_ceil(threads);
+        let parent = batch.path.as_str();
+        let parts = thread::scope(|scope| {
+            let workers = (chunk_start..chunk_end)
+                .step_by(part_size as usize)
+                .map(|part_start| {
+                    let derive = &derive;
+                    let part_end = part_start.saturating_add(part_size).min(chunk_end);
+                    scope.spawn(move || {
+                        (part_start..part_end)
+                            .zip(derive(part_start..part_end)?)
+                            .map(|(index, address)| {
+                                let path = format!("{}/{}", parent, index);
+                                Ok(serde_json::to_string(&BatchAddress {
+                                    index,
+                                    path,
+                                    address: &address,
+                                })?)
+                            })
+                            .collect::<Result<Vec<String>, CLIError>>()
+                    })
+                })
+                .collect::<Vec<_>>();
+            workers
+                .into_iter()
+                .map(|worker| worker.join().unwrap_or_else(|panic| panic::resume_unwind(panic)))
+                .collect::<Result<Vec<Vec<String>>, CLIError>>()
+        })?;
+
+        // The chunk is written in full before the checkpoint, so a checkpoint never runs ahead of the output
+        match &mut file {
+            Some(file) => {
+                let mut writer = BufWriter::new(&*file);
+                for line in parts.iter().flatten() {
+                    writeln!(writer, "{}", line)?;
+                }
+                writer.flush()?;
+                drop(writer);
+
+                // An output device or pipe, such as /dev/null, is neither synced nor truncated on resumption
+                let metadata = file.metadata()?;
+                if metadata.is_file() {
+                    file.sync_data()?;
+                    batch.output_length = Some(metadata.len());
+                }
+            }
+            None => {
+                let stdout = io::stdout();
+                let mut writer = BufWriter::new(stdout.lock());
+                for line in parts.iter().flatten() {
+                    writeln!(writer, "{}", line)?;
+                }
+                writer.flush()?;
+            }
+        }
+
+        batch.next_index = chunk_end;
+        if let Some(path) = checkpoint {
+            batch.write_checkpoint(path)?;
+        }
+        if let Some(progress) = &progress {
+            progress.update((batch.next_index - start) as u64);
+        }
+    }
+    if let Some(progress) = &progress {
+        progress.finish((batch.next_index - start) as u64);
+    }
+
+    eprintln!(
+        "{}",
+        localize(&format!(
+            "Derived {} addresses (next index {})",
+            batch.next_index.saturating_sub(start),
+            batch.next_index
+        ))
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::{mask, SECRET_MASK};
+
+    #[derive(Clone, Serialize)]
+    struct TestWallet {
+        address: String,
+        private_key: Option<String>,
+    }
+
+    impl MaskSecrets for TestWallet {
+        fn mask_secrets(&mut self) {
+            mask(&mut self.private_key);
+        }
+    }
+
+    fn entry(line: usize, value: &str) -> BatchEntry {
+        BatchEntry {
+            line,
+            value: value.into(),
+        }
+    }
+
+    fn read(name: &str, contents: &str, column: Option<&str>) -> Result<Vec<BatchEntry>, CLIError> {
+        let path = std::env::temp_dir().join(format!("wagyu_batch_{}_{}.csv", name, std::process::id()));
+        fs::write(&path, contents).unwrap();
+        let entries = read_batch_file(path.to_str().unwrap(), column);
+        fs::remove_file(&path).unwrap();
+        entries
+    }
+
+    #[test]
+    fn split_quoted_fields() {
+        assert_eq!(vec!["a", "b", "c"], split_csv("a,b,c"));
+        assert_eq!(vec!["a", "b"], split_csv(" a , b "));
+        assert_eq!(vec!["Smith, John", "1A1zP1"], split_csv("\"Smith, John\",1A1zP1"));
+        assert_eq!(vec!["say \"hi\"", "x"], split_csv("\"say \"\"hi\"\"\", x"));
+        assert_eq!(vec!["", "a", ""], split_csv(",a,"));
+        assert_eq!(vec![""], split_csv(""));
+    }
+
+    #[test]
+    fn read_lines() {
+        let contents = "# keys\n\nkey1\n  key2  \n# end\n";
+        assert_eq!(
+            vec![entry(3, "key1"), entry(4, "key2")],
+            read("lines", contents, None).unwrap()
+        );
+    }
+
+    #[test]
+    fn read_columns() {
+        let contents = "name,address\n# comment\n\"Smith, John\",1A1zP1\n\nDoe,\"3J98t1\"\n";
+        let expected = vec![entry(3, "1A1zP1"), entry(5, "3J98t1")];
+        assert_eq!(expected, read("header", contents, Some("Address")).unwrap());
+        assert_eq!(expected, read("index", contents, Some("2")).unwrap()[1..]);
+
+        // A line without the column is an empty entry, which fails to import
+        assert_eq!(
+            vec![entry(1, "")],
+            read("out_of_range", "Doe,3J98t1\n", Some("3")).unwrap()
+        );
+
+        match read("missing", contents, Some("balance")) {
+            Err(CLIError::MissingBatchColumn(column)) => assert_eq!("balance", column),
+            result => panic!("unexpected result {:?}", result),
+        }
+        match read("zero", contents, Some("0")) {
+            Err(CLIError::MissingBatchColumn(column)) => assert_eq!("0", column),
+            result => panic!("unexpected result {:?}", result),
+        }
+    }
+
+    #[test]
+    fn import_past_failures() {
+        let entries = vec![entry(1, "a"), entry(2, ""), entry(4, "c")];
+        let import = |value: &str| match value {
+            "" => Err(CLIError::MissingBatchColumn("address".into())),
+            value => Ok(TestWallet {
+                address: value.into(),
+                private_key: Some(format!("key_{}", value)),
+            }),
+        };
+
+        let (results, wallets) = import_entries(&entries, true, import);
+        assert_eq!(3, results.len());
+        assert_eq!(2, wallets.len());
+        assert_eq!(
+            json!({ "address": "a", "private_key": SECRET_MASK }),
+            results[0]["wallet"]
+        );
+        assert_eq!(json!(2), results[1]["line"]);
+        assert_eq!(json!("missing batch column: address"), results[1]["error"]);
+        assert_eq!(
+            json!({ "address": "c", "private_key": SECRET_MASK }),
+            results[2]["wallet"]
+        );
+        assert_eq!(Some("key_c".to_string()), wallets[1].private_key);
+
+        let (results, _) = import_entries(&entries, false, import);
+        assert_eq!(json!("key_a"), results[0]["wallet"]["private_key"]);
+    }
+}
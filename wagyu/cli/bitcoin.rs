@@ -1,32 +1,90 @@
 use crate::bitcoin::{
-    format::BitcoinFormat, wordlist::*, BitcoinAddress, BitcoinAmount, BitcoinDerivationPath,
-    BitcoinExtendedPrivateKey, BitcoinExtendedPublicKey, BitcoinMnemonic, BitcoinNetwork, BitcoinPrivateKey,
-    BitcoinPublicKey, BitcoinTransaction, BitcoinTransactionInput, BitcoinTransactionOutput,
-    BitcoinTransactionParameters, BitcoinWordlist, Mainnet as BitcoinMainnet, Outpoint, SignatureHash,
-    Testnet as BitcoinTestnet,
+    create_script_pub_key, format::BitcoinFormat, key_sort, nonce_agg, nonce_gen, verify_schnorr, wordlist::*,
+    BitcoinAddress, BitcoinAmount, BitcoinDerivationPath, BitcoinExtendedPrivateKey, BitcoinExtendedPublicKey,
+    BitcoinHtlc, BitcoinMiniscript, BitcoinMnemonic, BitcoinNetwork, BitcoinPrivateKey, BitcoinPublicKey,
+    BitcoinSignedMessage, BitcoinTimelock, BitcoinTransaction, BitcoinTransactionInput, BitcoinTransactionOutput,
+    BitcoinTransactionParameters, BitcoinWordlist, FrostError, FrostKeyPackage, FrostPublicPackage, FrostSecretPackage,
+    FrostSession, FrostShare, Mainnet as BitcoinMainnet, MiniscriptError, MuSigError, MuSigKeyAggContext, MuSigSession,
+    Outpoint, SignatureHash, TaprootScriptTree, Testnet as BitcoinTestnet, MAX_OP_RETURN_SIZE,
+};
+use crate::cli::{
+    check_clipboard_field, check_dust, check_fee, confirm_backups, copy_wallets, derive_address_batch, duress_account,
+    encrypt_to, flag, import_batch, mask, object, option, print_wallets, property, read_batch_file,
+    read_transaction_batch_file, read_wallet_dat, read_wallet_dump, rederive, rpc_call, rpc_call_with_timeout,
+    sanitize_option, sign_batch, subcommand, to_coin_type, types::*, AddressBatch, AuditLog, CLIError, ColdcardExport,
+    CustomWordlist, ElectrumWallet, EntropyGrid, HDTree, HDTreeNode, InputKind, Manifest, MaskSecrets, Mismatch,
+    ProgressBar, Recipient, Schema, SecretNonceFile, UnsignedTransactionFile, WalletFile, WalletSummary,
+    WatchOnlyWallet, BITCOIN_RPC_URL, CLI, CLIPBOARD_CLEAR_AFTER, DUST_LIMIT, MAX_TREE_DEPTH, SUMMARY_COUNT,
 };
-use crate::cli::{flag, option, subcommand, types::*, CLIError, CLI};
 use crate::model::{
-    crypto::hash160, ExtendedPrivateKey, ExtendedPublicKey, Mnemonic, MnemonicCount, MnemonicExtended, PrivateKey,
-    PublicKey, Transaction,
+    crypto::hash160, ChildIndex, ChildNumberRange, DerivationPath, DerivationPathTemplate, ExtendedPrivateKey,
+    ExtendedPublicKey, Mnemonic, MnemonicCount, MnemonicExtended, Network, PrivateKey, PublicKey, Transaction,
+    TransactionError,
 };
 
 use clap::{ArgMatches, Values};
 use colored::*;
-use core::{fmt, fmt::Display, str::FromStr};
+use core::{convert::TryFrom, fmt, fmt::Display, str::FromStr};
 use rand::{rngs::StdRng, Rng};
 use rand_core::SeedableRng;
+use rustc_serialize::base64::{FromBase64, ToBase64, STANDARD};
 use serde::{Deserialize, Serialize};
-use serde_json::from_str;
+use serde_json::{from_str, json, Value};
+use std::{
+    ops::Range,
+    panic,
+    sync::atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+    thread,
+    time::Duration,
+};
 
 use crate::model::no_std::{format, vec, String, ToOwned, ToString, Vec};
 
+/// The BIP-44 purposes of the address formats, in the order of their BIPs
+const PURPOSE_FORMATS: [(u32, BitcoinFormat); 4] = [
+    (44, BitcoinFormat::P2PKH),
+    (49, BitcoinFormat::P2SH_P2WPKH),
+    (84, BitcoinFormat::Bech32),
+    (86, BitcoinFormat::P2TR),
+];
+
+/// The address formats of Bitcoin wallets
+const FORMATS: [BitcoinFormat; 5] = [
+    BitcoinFormat::P2PKH,
+    BitcoinFormat::P2SH_P2WPKH,
+    BitcoinFormat::Bech32,
+    BitcoinFormat::P2WSH,
+    BitcoinFormat::P2TR,
+];
+
+/// The incremental relay fee rate of a replacement transaction in sat/vB (BIP-125)
+const INCREMENTAL_RELAY_FEERATE: i64 = 1;
+/// The confirmation target of the fee rate estimated for a sweep, in blocks
+const SWEEP_CONFIRMATION_TARGET: u32 = 6;
+/// The timeout of a scan of the UTXO set, which may take minutes on mainnet
+const SCAN_TIMEOUT: Duration = Duration::from_secs(600);
+/// The number of accounts of each purpose searched by `discover-paths`, from account 0
+const DISCOVER_PATHS_ACCOUNTS: u32 = 5;
+/// The number of indices of each chain searched by `discover-paths`
+const DISCOVER_PATHS_DEPTH: u32 = 100;
+/// The number of addresses of each chunk of `derive-addresses`, derived and written before the next
+const DERIVE_ADDRESSES_CHUNK_SIZE: usize = 10000;
+/// The number of indices of a chain derived at once, whose addresses are hashed as a batch
+const ADDRESS_BATCH_SIZE: u32 = 64;
+/// The interval at which the progress bar of a search is redrawn
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Represents the master fingerprint, and the path and extended public key, of an account of an HD wallet
+pub(crate) type BitcoinAccount<N> = ([u8; 4], String, BitcoinExtendedPublicKey<N>);
+
 /// Represents a generic wallet to output
-#[derive(Serialize, Debug, Default)]
-struct BitcoinWallet {
+#[derive(Serialize, Clone, Debug, Default)]
+pub(crate) struct BitcoinWallet {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub path: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub duress: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub password: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub mnemonic: Option<String>,
@@ -49,6 +107,223 @@ struct BitcoinWallet {
     pub transaction_hex: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub transaction_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fee: Option<i64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vsize: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub valid: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub miniscript: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub witness_script: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub script_size: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub satisfaction_weight: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub requires_signature: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timelock: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub spending_template: Option<BitcoinSpendingTemplate>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hash: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub claim_template: Option<BitcoinSpendingTemplate>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refund_template: Option<BitcoinSpendingTemplate>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub merkle_root: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub script_leaves: Option<Vec<BitcoinScriptLeaf>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub threshold: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secret_package: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub public_package: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shares: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub key_package: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aggregate_public_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub public_nonce: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub aggregate_nonce: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub partial_signature: Option<String>,
+}
+
+impl MaskSecrets for BitcoinWallet {
+    fn mask_secrets(&mut self) {
+        mask(&mut self.extended_private_key);
+        mask(&mut self.mnemonic);
+        mask(&mut self.password);
+        mask(&mut self.private_key);
+        mask(&mut self.secret_package);
+        mask(&mut self.shares);
+        mask(&mut self.key_package);
+    }
+}
+
+impl Schema for BitcoinWallet {
+    fn schema() -> serde_json::Value {
+        let spending_template = object(
+            vec![
+                (
+                    "version",
+                    property("integer", "The version of the spending transaction"),
+                ),
+                (
+                    "lock_time",
+                    property("integer", "The lock time of the spending transaction"),
+                ),
+                ("sequence", property("integer", "The sequence of the spending input")),
+                ("witness", property("array", "The witness stack of the spending input")),
+            ],
+            &["version", "lock_time", "sequence", "witness"],
+        );
+        let mut script_leaves = property(
+            "array",
+            "The tapscript leaves of the taproot script tree of the address",
+        );
+        script_leaves["items"] = object(
+            vec![
+                ("script", property("string", "The script of the leaf")),
+                ("leaf_hash", property("string", "The leaf hash of the script")),
+                (
+                    "control_block",
+                    property("string", "The control block spending the leaf"),
+                ),
+            ],
+            &["script", "leaf_hash", "control_block"],
+        );
+
+        object(
+            vec![
+                ("path", property("string", "The derivation path of the wallet")),
+                (
+                    "duress",
+                    property("string", "The derivation of the decoy account of a duress PIN"),
+                ),
+                ("password", property("string", "The password of the mnemonic")),
+                ("mnemonic", property("string", "The mnemonic phrase of the wallet")),
+                (
+                    "extended_private_key",
+                    property("string", "The extended private key of the wallet"),
+                ),
+                (
+                    "extended_public_key",
+                    property("string", "The extended public key of the wallet"),
+                ),
+                ("private_key", property("string", "The private key of the wallet")),
+                ("public_key", property("string", "The public key of the wallet")),
+                ("address", property("string|null", "The address of the wallet")),
+                ("format", property("string", "The address format of the wallet")),
+                ("network", property("string", "The network of the wallet")),
+                (
+                    "compressed",
+                    property("boolean", "Whether the public key is compressed"),
+                ),
+                ("transaction_hex", property("string", "The transaction in hex")),
+                ("transaction_id", property("string", "The transaction id")),
+                ("fee", property("integer", "The fee of the transaction in satoshis")),
+                (
+                    "vsize",
+                    property("integer", "The virtual size of the transaction in vbytes"),
+                ),
+                ("message", property("string", "The signed or proven message")),
+                ("signature", property("string", "The signature in hex")),
+                ("valid", property("boolean", "Whether the signature or proof is valid")),
+                (
+                    "miniscript",
+                    property("string", "The miniscript of the spending policy"),
+                ),
+                (
+                    "witness_script",
+                    property("string", "The witness script of the address in hex"),
+                ),
+                (
+                    "script_size",
+                    property("integer", "The size of the witness script in bytes"),
+                ),
+                (
+                    "satisfaction_weight",
+                    property("integer", "The maximum weight of the witness spending the address"),
+                ),
+                (
+                    "requires_signature",
+                    property("boolean", "Whether every spending path requires a signature"),
+                ),
+                ("timelock", property("string", "The timelock of the witness script")),
+                ("spending_template", spending_template.clone()),
+                (
+                    "hash",
+                    property("string", "The SHA-256 hash of the preimage that claims the contract"),
+                ),
+                ("claim_template", spending_template.clone()),
+                ("refund_template", spending_template),
+                (
+                    "merkle_root",
+                    property("string", "The Merkle root of the taproot script tree of the address"),
+                ),
+                ("script_leaves", script_leaves),
+                (
+                    "threshold",
+                    property("string", "The threshold of a FROST key, of its participants"),
+                ),
+                (
+                    "secret_package",
+                    property(
+                        "string",
+                        "The FROST secret package of a participant of a key generation",
+                    ),
+                ),
+                (
+                    "public_package",
+                    property(
+                        "string",
+                        "The FROST public package of a participant of a key generation",
+                    ),
+                ),
+                (
+                    "shares",
+                    property(
+                        "string",
+                        "The FROST secret shares of the other participants (comma separated), each sent confidentially",
+                    ),
+                ),
+                (
+                    "key_package",
+                    property("string", "The FROST key package of a participant, with which it signs"),
+                ),
+                (
+                    "aggregate_public_key",
+                    property(
+                        "string",
+                        "The x-only aggregate public key of the signers, the internal key of the address",
+                    ),
+                ),
+                ("public_nonce", property("string", "The public nonce of the signer")),
+                (
+                    "aggregate_nonce",
+                    property("string", "The MuSig2 aggregate nonce of the signers"),
+                ),
+                (
+                    "partial_signature",
+                    property("string", "The partial signature of the signer"),
+                ),
+            ],
+            &[],
+        )
+    }
 }
 
 impl BitcoinWallet {
@@ -128,11 +403,141 @@ impl BitcoinWallet {
         })
     }
 
+    /// Returns the tree of the accounts of the given BIP-44 purpose, their chains, and their addresses,
+    /// of the given mnemonic, or of a new mnemonic if none is specified.
+    pub fn new_hd_tree<N: BitcoinNetwork, W: BitcoinWordlist, R: Rng>(
+        rng: &mut R,
+        mnemonic: Option<&str>,
+        word_count: u8,
+        password: Option<&str>,
+        purpose: u32,
+        depth: u32,
+        breadth: u32,
+    ) -> Result<HDTree, CLIError> {
+        let new_mnemonic = match mnemonic {
+            Some(_) => None,
+            None => Some(BitcoinMnemonic::<N, W>::new_with_count(rng, word_count)?),
+        };
+        let master_extended_private_key = match (mnemonic, &new_mnemonic) {
+            (Some(phrase), _) => BitcoinMnemonic::<N, W>::from_phrase(phrase)?.to_extended_private_key(password)?,
+            (None, Some(mnemonic)) => mnemonic.to_extended_private_key(password)?,
+            (None, None) => unreachable!(),
+        };
+
+        // The accounts are printed with the SLIP-132 version bytes of the format of the purpose
+        let (_, format) = PURPOSE_FORMATS.iter().find(|(p, _)| *p == purpose).unwrap();
+        let master_extended_private_key = master_extended_private_key.with_format(format);
+        let prefix = [ChildIndex::hardened(purpose)?, N::HD_COIN_TYPE];
+        Ok(HDTree {
+            mnemonic: new_mnemonic.map(|mnemonic| mnemonic.to_string()),
+            network: N::NAME.to_string(),
+            tree: HDTreeNode::new(
+                &master_extended_private_key,
+                &prefix,
+                &[(0, "receive"), (1, "change")],
+                format,
+                depth,
+                breadth,
+                BitcoinExtendedPublicKey::to_fingerprint,
+            )?,
+        })
+    }
+
+    /// Returns the summary of the wallet at the given path of the given mnemonic, with the first `count`
+    /// addresses of its chain, and its account extended public key in the SLIP-132 format of the path.
+    pub fn to_summary<N: BitcoinNetwork, W: BitcoinWordlist>(
+        mnemonic: &str,
+        password: Option<&str>,
+        path: &str,
+        count: u32,
+    ) -> Result<WalletSummary, CLIError> {
+        let mnemonic = BitcoinMnemonic::<N, W>::from_phrase(mnemonic)?;
+        let master_extended_private_key = mnemonic.to_extended_private_key(password)?;
+        let derivation_path = BitcoinDerivationPath::from_str(path)?;
+        let format = master_extended_private_key.derive(&derivation_path)?.format();
+        WalletSummary::new(
+            &master_extended_private_key.with_format(&format),
+            &derivation_path,
+            &format,
+            count,
+            N::NAME,
+            BitcoinExtendedPublicKey::to_fingerprint,
+        )
+    }
+
+    /// Returns the master fingerprint, and the path and extended public key of the account,
+    /// of the wallet at the given path of the given mnemonic.
+    pub fn to_account<N: BitcoinNetwork, W: BitcoinWordlist>(
+        mnemonic: &str,
+        password: Option<&str>,
+        path: &str,
+    ) -> Result<BitcoinAccount<N>, CLIError> {
+        Self::to_account_from_extended_private_key(
+            &Self::to_master_extended_private_key::<N, W>(mnemonic, password)?,
+            path,
+        )
+    }
+
+    /// Returns the master extended private key of the given mnemonic.
+    pub fn to_master_extended_private_key<N: BitcoinNetwork, W: BitcoinWordlist>(
+        mnemonic: &str,
+        password: Option<&str>,
+    ) -> Result<BitcoinExtendedPrivateKey<N>, CLIError> {
+        Ok(BitcoinMnemonic::<N, W>::from_phrase(mnemonic)?.to_extended_private_key(password)?)
+    }
+
+    /// Returns the master fingerprint, and the path and extended public key of the account,
+    /// of the wallet at the given path of the given master extended private key.
+    pub fn to_account_from_extended_private_key<N: BitcoinNetwork>(
+        master_extended_private_key: &BitcoinExtendedPrivateKey<N>,
+        path: &str,
+    ) -> Result<BitcoinAccount<N>, CLIError> {
+        let (account_path, format) = to_account_path::<N>(path)?;
+        let account_key = master_extended_private_key
+            .derive(&account_path)?
+            .to_extended_public_key();
+        let format = format.unwrap_or_else(|| account_key.format());
+        Ok((
+            master_extended_private_key.to_extended_public_key().to_fingerprint(),
+            account_path.to_string(),
+            account_key.with_format(&format),
+        ))
+    }
+
+    /// Returns the given master fingerprint in hex, and the path and extended public key of the account,
+    /// of the given account extended public key of the wallet at the given path.
+    pub fn to_account_from_extended_public_key<N: BitcoinNetwork>(
+        extended_public_key: &str,
+        format: &Option<BitcoinFormat>,
+        master_fingerprint: &str,
+        path: &str,
+    ) -> Result<BitcoinAccount<N>, CLIError> {
+        let mut fingerprint = [0u8; 4];
+        match hex::decode(master_fingerprint)? {
+            ref bytes if bytes.len() == 4 => fingerprint.copy_from_slice(bytes),
+            _ => return Err(CLIError::InvalidMasterFingerprint(master_fingerprint.into())),
+        };
+
+        // The format of a key with the standard version bytes is the format of the purpose of the path
+        let (account_path, purpose_format) = to_account_path::<N>(path)?;
+        let account_key = BitcoinExtendedPublicKey::<N>::from_str(extended_public_key)?;
+        let format = match (format, account_key.format(), purpose_format) {
+            (Some(format), _, _) => format.clone(),
+            (None, BitcoinFormat::P2PKH, Some(format)) => format,
+            (None, format, _) => format,
+        };
+        Ok((fingerprint, account_path.to_string(), account_key.with_format(&format)))
+    }
+
     pub fn from_extended_private_key<N: BitcoinNetwork>(
         extended_private_key: &str,
         path: &Option<String>,
+        format: &Option<BitcoinFormat>,
     ) -> Result<Self, CLIError> {
         let mut extended_private_key = BitcoinExtendedPrivateKey::<N>::from_str(extended_private_key)?;
+        if let Some(format) = format {
+            extended_private_key = extended_private_key.with_format(format);
+        }
         if let Some(derivation_path) = path {
             let derivation_path = BitcoinDerivationPath::from_str(&derivation_path)?;
             extended_private_key = extended_private_key.derive(&derivation_path)?;
@@ -159,8 +564,12 @@ impl BitcoinWallet {
     pub fn from_extended_public_key<N: BitcoinNetwork>(
         extended_public_key: &str,
         path: &Option<String>,
+        format: &Option<BitcoinFormat>,
     ) -> Result<Self, CLIError> {
         let mut extended_public_key = BitcoinExtendedPublicKey::<N>::from_str(extended_public_key)?;
+        if let Some(format) = format {
+            extended_public_key = extended_public_key.with_format(format);
+        }
         if let Some(derivation_path) = path {
             let derivation_path = BitcoinDerivationPath::from_str(&derivation_path)?;
             extended_public_key = extended_public_key.derive(&derivation_path)?;
@@ -180,114 +589,891 @@ impl BitcoinWallet {
         })
     }
 
-    pub fn from_private_key<N: BitcoinNetwork>(private_key: &str, format: &BitcoinFormat) -> Result<Self, CLIError> {
-        let private_key = BitcoinPrivateKey::<N>::from_str(private_key)?;
-        let public_key = private_key.to_public_key();
-        let address = public_key.to_address(format)?;
-        Ok(Self {
-            private_key: Some(private_key.to_string()),
+    /// Returns the wallet of the given address, searched for in the first `depth` indices of the children,
+    /// receive chain, and change chain of the given extended public key, with a path relative to the key.
+    pub fn find_in_extended_public_key<N: BitcoinNetwork>(
+        extended_public_key: &str,
+        address: &str,
+        depth: u32,
+    ) -> Result<Self, CLIError> {
+        let extended_public_key = BitcoinExtendedPublicKey::<N>::from_str(extended_public_key)?;
+        let address = BitcoinAddress::<N>::from_str(address)?;
+        match Self::search_chains(&extended_public_key, &[None, Some(0), Some(1)], &address, depth)? {
+            Some((path, public_key)) => Ok(Self::from_found_public_key(format!("m{}", path), &public_key, &address)),
+            None => Err(CLIError::AddressNotFound(address.to_string(), depth)),
+        }
+    }
+
+    /// Returns the wallet of the given address, searched for in the first `depth` indices of the
+    /// receive and change chains of the first account of each BIP-44 purpose of the given mnemonic,
+    /// starting with the purpose of the address format.
+    pub fn find_in_mnemonic<N: BitcoinNetwork, W: BitcoinWordlist>(
+        mnemonic: &str,
+        password: &Option<&str>,
+        address: &str,
+        depth: u32,
+    ) -> Result<Self, CLIError> {
+        let mnemonic = BitcoinMnemonic::<N, W>::from_phrase(mnemonic)?;
+        let master_extended_private_key = mnemonic.to_extended_private_key(*password)?;
+        let address = BitcoinAddress::<N>::from_str(address)?;
+
+        let mut purposes = PURPOSE_FORMATS.to_vec();
+        purposes.sort_by_key(|(_, format)| *format != address.format());
+        for (purpose, _) in purposes {
+            let account_path = format!("m/{}'/{}/0'", purpose, N::HD_COIN_TYPE);
+            let account = master_extended_private_key
+                .derive(&BitcoinDerivationPath::from_str(&account_path)?)?
+                .to_extended_public_key();
+            if let Some((path, public_key)) = Self::search_chains(&account, &[Some(0), Some(1)], &address, depth)? {
+                let path = format!("{}{}", account_path, path);
+                return Ok(Self::from_found_public_key(path, &public_key, &address));
+            }
+        }
+        Err(CLIError::AddressNotFound(address.to_string(), depth))
+    }
+
+    /// Returns the path of the given address in the first `depth` indices of the receive and change chains
+    /// of the given accounts of each BIP-44 purpose of the given mnemonic or master extended private key,
+    /// with the address in any script format, as a key may be used with the format of another purpose.
+    /// The chains are searched on the given number of threads, with a progress bar on standard error.
+    pub fn discover_paths<N: BitcoinNetwork, W: BitcoinWordlist>(
+        mnemonic: Option<&str>,
+        extended_private_key: Option<&str>,
+        password: Option<&str>,
+        address: &str,
+        accounts: &ChildNumberRange,
+        depth: u32,
+        threads: usize,
+    ) -> Result<BitcoinDiscoveredPath, CLIError> {
+        let master_extended_private_key = match (extended_private_key, mnemonic) {
+            (Some(extended_private_key), _) => BitcoinExtendedPrivateKey::<N>::from_str(extended_private_key)?,
+            (None, Some(mnemonic)) => {
+                BitcoinMnemonic::<N, W>::from_phrase(mnemonic)?.to_extended_private_key(password)?
+            }
+            (None, None) => return Err(CLIError::MissingMasterKey("a path discovery".into())),
+        };
+        let address = BitcoinAddress::<N>::from_str(address)?;
+        let format = address.format();
+
+        let mut chains = vec![];
+        for (purpose, _) in PURPOSE_FORMATS.iter() {
+            for account in accounts.start()..=accounts.end() {
+                chains.push((*purpose, account, 0));
+                chains.push((*purpose, account, 1));
+            }
+        }
+
+        // Each thread takes the next chain to search, until the address is found by any thread
+        let next_chain = AtomicUsize::new(0);
+        let searched = AtomicU64::new(0);
+        let found = AtomicBool::new(false);
+        let search = || -> Result<Option<BitcoinDiscoveredPath>, CLIError> {
+            while let Some((purpose, account, chain)) = chains.get(next_chain.fetch_add(1, Ordering::Relaxed)) {
+                let chain_path = format!("m/{}'/{}/{}'/{}", purpose, N::HD_COIN_TYPE, account, chain);
+                let chain_key = master_extended_private_key
+                    .derive(&BitcoinDerivationPath::from_str(&chain_path)?)?
+                    .to_extended_public_key();
+                for start in (0..depth).step_by(ADDRESS_BATCH_SIZE as usize) {
+                    if found.load(Ordering::Relaxed) {
+                        return Ok(None);
+                    }
+                    let indices = start..start.saturating_add(ADDRESS_BATCH_SIZE).min(depth);
+                    searched.fetch_add(indices.len() as u64, Ordering::Relaxed);
+                    let public_keys = Self::derive_children(&chain_key, indices)?;
+                    let addresses = BitcoinAddress::from_public_keys(&public_keys, &format)?;
+                    if let Some(position) = addresses.iter().position(|candidate| *candidate == address) {
+                        found.store(true, Ordering::Relaxed);
+                        return Ok(Some(BitcoinDiscoveredPath {
+                            path: format!("{}/{}", chain_path, start + position as u32),
+                            format: format.to_string(),
+                            standard: PURPOSE_FORMATS.contains(&(*purpose, format.clone())),
+                            address: address.to_string(),
+                            network: N::NAME.to_string(),
+                        }));
+                    }
+                }
+            }
+            Ok(None)
+        };
+
+        let progress = ProgressBar::new("Searching", chains.len() as u64 * depth as u64);
+        let results = thread::scope(|scope| {
+            let workers = (0..threads.max(1)).map(|_| scope.spawn(search)).collect::<Vec<_>>();
+            while !workers.iter().all(|worker| worker.is_finished()) {
+                progress.update(searched.load(Ordering::Relaxed));
+                thread::sleep(PROGRESS_INTERVAL);
+            }
+            progress.finish(searched.load(Ordering::Relaxed));
+            workers
+                .into_iter()
+                .map(|worker| worker.join().unwrap_or_else(|panic| panic::resume_unwind(panic)))
+                .collect::<Vec<_>>()
+        });
+
+        for result in results {
+            if let Some(discovered) = result? {
+                return Ok(discovered);
+            }
+        }
+        Err(CLIError::AddressNotFound(address.to_string(), depth))
+    }
+
+    /// Returns the relative path and public key of the given address in the first `depth` indices
+    /// of the given chains of the extended public key, where `None` is the key itself.
+    /// Each index is derived from its chain alone, as a chain is derived once for all of its indices.
+    fn search_chains<N: BitcoinNetwork>(
+        extended_public_key: &BitcoinExtendedPublicKey<N>,
+        chains: &[Option<u32>],
+        address: &BitcoinAddress<N>,
+        depth: u32,
+    ) -> Result<Option<(String, BitcoinPublicKey<N>)>, CLIError> {
+        let format = address.format();
+        for chain in chains {
+            let (chain_key, chain_path) = match chain {
+                Some(chain) => (
+                    extended_public_key.derive(&BitcoinDerivationPath::try_from(vec![ChildIndex::normal(*chain)?])?)?,
+                    format!("/{}", chain),
+                ),
+                None => (extended_public_key.clone(), String::new()),
+            };
+            for start in (0..depth).step_by(ADDRESS_BATCH_SIZE as usize) {
+                let indices = start..start.saturating_add(ADDRESS_BATCH_SIZE).min(depth);
+                let public_keys = Self::derive_children(&chain_key, indices)?;
+                let addresses = BitcoinAddress::from_public_keys(&public_keys, &format)?;
+                if let Some(position) = addresses.iter().position(|candidate| candidate == address) {
+                    let path = format!("{}/{}", chain_path, start + position as u32);
+                    return Ok(Some((path, public_keys[position].clone())));
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Returns the public keys of the given indices of the children of the extended public key,
+    /// whose addresses are then hashed as a batch.
+    pub(crate) fn derive_children<N: BitcoinNetwork>(
+        extended_public_key: &BitcoinExtendedPublicKey<N>,
+        indices: Range<u32>,
+    ) -> Result<Vec<BitcoinPublicKey<N>>, CLIError> {
+        indices
+            .map(|index| {
+                let path = BitcoinDerivationPath::try_from(vec![ChildIndex::normal(index)?])?;
+                Ok(extended_public_key.derive(&path)?.to_public_key())
+            })
+            .collect()
+    }
+
+    /// Returns the wallet of a public key found for the given address.
+    fn from_found_public_key<N: BitcoinNetwork>(
+        path: String,
+        public_key: &BitcoinPublicKey<N>,
+        address: &BitcoinAddress<N>,
+    ) -> Self {
+        Self {
+            path: Some(path),
             public_key: Some(public_key.to_string()),
             address: Some(address.to_string()),
-            network: Some(N::NAME.to_string()),
             format: Some(address.format().to_string()),
-            compressed: private_key.is_compressed().into(),
+            network: Some(N::NAME.to_string()),
+            compressed: Some(public_key.is_compressed()),
             ..Default::default()
-        })
+        }
     }
 
-    pub fn from_public_key<N: BitcoinNetwork>(public_key: &str, format: &BitcoinFormat) -> Result<Self, CLIError> {
-        let public_key = BitcoinPublicKey::<N>::from_str(public_key)?;
-        let address = public_key.to_address(format)?;
+    pub fn to_extended_public_key<N: BitcoinNetwork>(
+        extended_private_key: &str,
+        path: &Option<String>,
+    ) -> Result<Self, CLIError> {
+        let mut extended_private_key = BitcoinExtendedPrivateKey::<N>::from_str(extended_private_key)?;
+        if let Some(derivation_path) = path {
+            let derivation_path = BitcoinDerivationPath::from_str(derivation_path)?;
+            extended_private_key = extended_private_key.derive(&derivation_path)?;
+        }
+        let extended_public_key = extended_private_key.to_extended_public_key();
         Ok(Self {
-            public_key: Some(public_key.to_string()),
-            address: Some(address.to_string()),
+            path: path.clone(),
+            extended_public_key: Some(extended_public_key.to_string()),
             network: Some(N::NAME.to_string()),
-            format: Some(address.format().to_string()),
-            compressed: public_key.is_compressed().into(),
             ..Default::default()
         })
     }
 
-    pub fn from_address<N: BitcoinNetwork>(address: &str) -> Result<Self, CLIError> {
-        let address = BitcoinAddress::<N>::from_str(address)?;
+    /// Returns the miniscript of the given spending policy, with its public keys in hex or named in the given keys,
+    /// and the P2WSH address of its witness script.
+    pub fn to_miniscript<N: BitcoinNetwork>(policy: &str, keys: &[(String, Vec<u8>)]) -> Result<Self, CLIError> {
+        let miniscript = BitcoinMiniscript::compile(policy, keys)?;
+        let address = miniscript.to_address::<N>()?;
         Ok(Self {
             address: Some(address.to_string()),
-            network: Some(N::NAME.to_string()),
             format: Some(address.format().to_string()),
+            network: Some(N::NAME.to_string()),
+            miniscript: Some(miniscript.miniscript.clone()),
+            witness_script: Some(hex::encode(&miniscript.script)),
+            script_size: Some(miniscript.script.len()),
+            satisfaction_weight: Some(miniscript.max_satisfaction_weight()?),
+            requires_signature: Some(miniscript.requires_signature),
             ..Default::default()
         })
     }
 
-    pub fn to_raw_transaction<N: BitcoinNetwork>(
-        inputs: &Vec<BitcoinInput>,
-        outputs: &Vec<&str>,
-        version: u32,
-        lock_time: u32,
+    /// Returns the MuSig2 aggregate public key of the given public keys (sorted), and its P2TR address.
+    /// With a private key and a message, returns the public nonce of the signer and writes its secret nonce
+    /// to the given secret nonce file, and with the public nonces of the signers, returns the partial signature
+    /// of the signer with the secret nonce of the file (consuming it), or the Schnorr signature of the partial
+    /// signatures of the signers, valid for the output key of the address.
+    pub fn to_musig<N: BitcoinNetwork>(
+        public_keys: &str,
+        message: Option<&str>,
+        private_key: Option<&str>,
+        public_nonces: Option<&str>,
+        secret_nonce_file: Option<&str>,
+        partial_signatures: Option<&str>,
     ) -> Result<Self, CLIError> {
-        let mut transaction_inputs = vec![];
-        for input in inputs {
-            let transaction_input = BitcoinTransactionInput::<N>::new(
-                hex::decode(&input.txid)?,
-                input.vout,
-                None,
-                None,
-                None,
-                None,
-                None,
-                SignatureHash::SIGHASH_ALL,
-            )?;
-            transaction_inputs.push(transaction_input);
-        }
+        let public_keys = public_keys
+            .split(',')
+            .map(|public_key| {
+                let public_key = BitcoinPublicKey::<N>::from_str(public_key.trim())?;
+                Ok(public_key.to_secp256k1_public_key().serialize_compressed())
+            })
+            .collect::<Result<Vec<[u8; 33]>, CLIError>>()?;
+        let mut key_agg = MuSigKeyAggContext::new(&key_sort(&public_keys))?;
+        let address = key_agg.to_address::<N>()?;
+        let mut wallet = Self {
+            address: Some(address.to_string()),
+            format: Some(address.format().to_string()),
+            network: Some(N::NAME.to_string()),
+            aggregate_public_key: Some(hex::encode(key_agg.to_x_only_public_key())),
+            ..Default::default()
+        };
 
-        let mut transaction_outputs = vec![];
-        for output in outputs {
-            let values: Vec<&str> = output.split(":").collect();
-            let address = BitcoinAddress::<N>::from_str(values[0])?;
-            transaction_outputs.push(BitcoinTransactionOutput::new(
-                &address,
-                BitcoinAmount::from_satoshi(i64::from_str(values[1])?)?,
-            )?);
-        }
+        let message = match message {
+            Some(message) => hex::decode(message)?,
+            None => return Ok(wallet),
+        };
+        key_agg.apply_taproot_tweak()?;
+        wallet.message = Some(hex::encode(&message));
 
-        let transaction_parameters = BitcoinTransactionParameters::<N> {
-            version,
-            inputs: transaction_inputs,
-            outputs: transaction_outputs,
-            lock_time,
-            segwit_flag: false,
+        let private_key = match private_key {
+            Some(private_key) => Some(BitcoinPrivateKey::<N>::from_str(private_key)?),
+            None => None,
+        };
+        let public_nonces = match public_nonces {
+            Some(public_nonces) => to_hex_values::<[u8; 66], _>(public_nonces, MuSigError::InvalidPublicNonce)?,
+            None => match private_key {
+                // The first round generates the nonces of the signer
+                Some(private_key) => {
+                    let public_key = private_key
+                        .to_public_key()
+                        .to_secp256k1_public_key()
+                        .serialize_compressed();
+                    if !key_agg.public_keys.contains(&public_key) {
+                        return Err(MuSigError::UnknownSigner.into());
+                    }
+                    let (secret_nonce, public_nonce) = nonce_gen(
+                        &mut StdRng::from_entropy(),
+                        Some(&private_key.to_secp256k1_secret_key().serialize()),
+                        &public_key,
+                        Some(&key_agg.to_x_only_public_key()),
+                        Some(&message),
+                    )?;
+                    SecretNonceFile::new("musig2", &message, &public_nonce, &secret_nonce)
+                        .create(secret_nonce_file.ok_or(CLIError::MissingSecretNonceFile)?)?;
+                    wallet.public_key = Some(hex::encode(&public_key[..]));
+                    wallet.public_nonce = Some(hex::encode(&public_nonce[..]));
+                    return Ok(wallet);
+                }
+                None => return Ok(wallet),
+            },
         };
 
-        let transaction = BitcoinTransaction::<N>::new(&transaction_parameters)?;
-        let raw_transaction_hex = hex::encode(&transaction.to_transaction_bytes()?);
+        let aggregate_nonce = nonce_agg(&public_nonces)?;
+        let session = MuSigSession::new(&key_agg, &aggregate_nonce, &message)?;
+        wallet.aggregate_nonce = Some(hex::encode(&aggregate_nonce[..]));
+
+        // The second round signs with the secret nonce of the signer, or aggregates the partial signatures
+        match (partial_signatures, private_key) {
+            (Some(partial_signatures), _) => {
+                let partial_signatures =
+                    to_hex_values::<[u8; 32], _>(partial_signatures, MuSigError::InvalidPartialSignature)?;
+                let signature = session.aggregate(&partial_signatures)?;
+                wallet.signature = Some(hex::encode(&signature[..]));
+                wallet.valid = Some(verify_schnorr(&key_agg.to_x_only_public_key(), &message, &signature));
+            }
+            (None, Some(private_key)) => {
+                let path = secret_nonce_file.ok_or(CLIError::MissingSecretNonceFile)?;
+                let (public_nonce, secret_nonce) = SecretNonceFile::consume(path, "musig2", &message)?;
+                if !public_nonces.iter().any(|nonce| nonce[..] == public_nonce[..]) {
+                    let reason = "the public nonce is not of the public nonces of the signers".to_string();
+                    return Err(CLIError::InvalidSecretNonceFile(path.to_string(), reason));
+                }
+                let secret_nonce =
+                    <[u8; 97]>::try_from(&secret_nonce[..]).map_err(|_| MuSigError::InvalidSecretNonce)?;
+                let partial_signature =
+                    session.sign(&secret_nonce, &private_key.to_secp256k1_secret_key().serialize())?;
+                wallet.partial_signature = Some(hex::encode(partial_signature));
+            }
+            _ => {}
+        };
+        Ok(wallet)
+    }
 
+    /// Returns the secret package and public package of the given participant of an M-of-N FROST key generation,
+    /// of which the public package is broadcast to the other participants.
+    pub fn to_frost_packages(identifier: u8, threshold: u8, participants: u8) -> Result<Self, CLIError> {
+        let (secret_package, public_package) =
+            FrostSecretPackage::new(&mut StdRng::from_entropy(), identifier, threshold, participants)?;
         Ok(Self {
-            transaction_hex: Some(raw_transaction_hex),
+            threshold: Some(format!("{}/{}", threshold, participants)),
+            secret_package: Some(secret_package.to_string()),
+            public_package: Some(public_package.to_string()),
             ..Default::default()
         })
     }
 
-    pub fn to_signed_transaction<N: BitcoinNetwork>(
-        transaction_hex: &str,
-        inputs: &Vec<BitcoinInput>,
+    /// Returns the secret shares of the other participants of a FROST key generation, after verifying their
+    /// public packages, or with the secret shares sent to the participant, returns its key package.
+    pub fn to_frost_shares<N: BitcoinNetwork>(
+        secret_package: &str,
+        packages: &str,
+        shares: Option<&str>,
     ) -> Result<Self, CLIError> {
-        let mut transaction = BitcoinTransaction::<N>::from_transaction_bytes(&hex::decode(transaction_hex)?)?;
+        let secret_package = FrostSecretPackage::from_str(secret_package)?;
+        let packages = packages
+            .split(',')
+            .map(|package| FrostPublicPackage::from_str(package.trim()))
+            .collect::<Result<Vec<_>, _>>()?;
 
-        for input in inputs {
-            match (input.amount.clone(), input.address.clone(), input.private_key.clone()) {
-                (Some(amount), Some(address), Some(private_key)) => {
-                    let private_key = BitcoinPrivateKey::<N>::from_str(&private_key)?;
-                    let address = BitcoinAddress::<N>::from_str(&address)?;
+        match shares {
+            Some(shares) => {
+                let shares = shares
+                    .split(',')
+                    .map(|share| FrostShare::from_str(share.trim()))
+                    .collect::<Result<Vec<_>, _>>()?;
+                Self::from_frost_key_package::<N>(&secret_package.to_key_package(&packages, &shares)?)
+            }
+            None => {
+                let shares = secret_package.to_shares(&packages)?;
+                Ok(Self {
+                    threshold: Some(format!("{}/{}", secret_package.threshold, secret_package.participants)),
+                    shares: Some(
+                        shares
+                            .iter()
+                            .map(|share| share.to_string())
+                            .collect::<Vec<String>>()
+                            .join(","),
+                    ),
+                    ..Default::default()
+                })
+            }
+        }
+    }
 
-                    let redeem_script = match (input.redeem_script.clone(), address.format()) {
-                        (Some(script), _) => Some(hex::decode(script)?),
-                        (None, BitcoinFormat::P2SH_P2WPKH) => {
-                            let mut redeem_script = vec![0x00, 0x14];
-                            redeem_script.extend(&hash160(
-                                &private_key.to_public_key().to_secp256k1_public_key().serialize(),
-                            ));
-                            Some(redeem_script)
-                        }
-                        (None, _) => None,
+    /// Returns the P2TR address of the group public key of the given FROST key package. With a message, returns
+    /// the public nonce of the participant and writes its secret nonce to the given secret nonce file, and with
+    /// the public nonces of the signers, returns the partial signature of the participant with the secret nonce
+    /// of the file (consuming it), or the Schnorr signature of the partial signatures of the signers, valid for
+    /// the output key of the address.
+    pub fn to_frost_signature<N: BitcoinNetwork>(
+        key_package: &str,
+        message: Option<&str>,
+        public_nonces: Option<&str>,
+        secret_nonce_file: Option<&str>,
+        partial_signatures: Option<&str>,
+    ) -> Result<Self, CLIError> {
+        let key_package = FrostKeyPackage::from_str(key_package)?;
+        let mut wallet = Self::from_frost_key_package::<N>(&key_package)?;
+        wallet.key_package = None;
+
+        let message = match message {
+            Some(message) => hex::decode(message)?,
+            None => return Ok(wallet),
+        };
+        wallet.message = Some(hex::encode(&message));
+
+        // The first round generates the nonces of the participant
+        let public_nonces = match public_nonces {
+            Some(public_nonces) => {
+                to_hex_values::<[u8; 67], _>(public_nonces, |_| FrostError::InvalidEncoding("public nonce"))?
+            }
+            None => {
+                let path = secret_nonce_file.ok_or(CLIError::MissingSecretNonceFile)?;
+                let (secret_nonce, public_nonce) = key_package.nonce_gen(&mut StdRng::from_entropy())?;
+                SecretNonceFile::new("frost", &message, &public_nonce, &secret_nonce).create(path)?;
+                wallet.public_nonce = Some(hex::encode(&public_nonce[..]));
+                return Ok(wallet);
+            }
+        };
+        let session = FrostSession::new(&key_package, &public_nonces, &message)?;
+
+        // The second round signs with the secret nonce of the participant, or aggregates the partial signatures
+        match (partial_signatures, secret_nonce_file) {
+            (Some(partial_signatures), _) => {
+                let partial_signatures = to_hex_values::<[u8; 33], _>(partial_signatures, |_| {
+                    FrostError::InvalidEncoding("partial signature")
+                })?;
+                let signature = session.aggregate(&partial_signatures)?;
+                wallet.signature = Some(hex::encode(&signature[..]));
+                wallet.valid = Some(verify_schnorr(&session.to_x_only_public_key(), &message, &signature));
+            }
+            (None, Some(path)) => {
+                let (_, secret_nonce) = SecretNonceFile::consume(path, "frost", &message)?;
+                let secret_nonce =
+                    <[u8; 65]>::try_from(&secret_nonce[..]).map_err(|_| FrostError::InvalidEncoding("secret nonce"))?;
+                let partial_signature = session.sign(&key_package, &secret_nonce)?;
+                wallet.partial_signature = Some(hex::encode(&partial_signature[..]));
+            }
+            (None, None) => return Err(CLIError::MissingSecretNonceFile),
+        };
+        Ok(wallet)
+    }
+
+    /// Returns the key package of a participant of a FROST key, its verification share, and the P2TR address
+    /// of the group public key.
+    fn from_frost_key_package<N: BitcoinNetwork>(key_package: &FrostKeyPackage) -> Result<Self, CLIError> {
+        let address = key_package.to_address::<N>()?;
+        Ok(Self {
+            public_key: Some(hex::encode(
+                &key_package.to_verification_share().serialize_compressed()[..],
+            )),
+            address: Some(address.to_string()),
+            format: Some(address.format().to_string()),
+            network: Some(N::NAME.to_string()),
+            threshold: Some(format!("{}/{}", key_package.threshold, key_package.participants())),
+            key_package: Some(key_package.to_string()),
+            aggregate_public_key: Some(hex::encode(key_package.to_x_only_public_key())),
+            ..Default::default()
+        })
+    }
+
+    /// Returns the P2WSH address of the witness script of the given public key, locked until the given timelock,
+    /// and the template of a transaction spending it, with the signature of the public key in its witness.
+    pub fn to_timelock<N: BitcoinNetwork>(public_key: &str, timelock: &BitcoinTimelock) -> Result<Self, CLIError> {
+        let public_key = BitcoinPublicKey::<N>::from_str(public_key)?;
+        let witness_script = timelock.to_witness_script(&public_key);
+        let address = timelock.to_address(&public_key)?;
+        Ok(Self {
+            public_key: Some(public_key.to_string()),
+            address: Some(address.to_string()),
+            format: Some(address.format().to_string()),
+            network: Some(N::NAME.to_string()),
+            witness_script: Some(hex::encode(&witness_script)),
+            timelock: Some(timelock.to_string()),
+            spending_template: Some(BitcoinSpendingTemplate {
+                version: timelock.version(),
+                lock_time: timelock.lock_time(),
+                sequence: timelock.sequence(),
+                witness: vec!["<signature>".into(), hex::encode(&witness_script)],
+            }),
+            ..Default::default()
+        })
+    }
+
+    /// Returns the P2WSH address of the hash time-locked contract of the given recipient and refund public keys,
+    /// SHA-256 hash, and timelock, and the templates of the transactions claiming and refunding it.
+    pub fn to_htlc<N: BitcoinNetwork>(
+        recipient: &str,
+        refund: &str,
+        hash: &str,
+        timelock: &BitcoinTimelock,
+    ) -> Result<Self, CLIError> {
+        let recipient = BitcoinPublicKey::<N>::from_str(recipient)?;
+        let refund = BitcoinPublicKey::<N>::from_str(refund)?;
+        let htlc = BitcoinHtlc::new(recipient, refund, &hex::decode(hash)?, *timelock)?;
+        let witness_script = htlc.to_witness_script();
+        let address = htlc.to_address()?;
+        Ok(Self {
+            address: Some(address.to_string()),
+            format: Some(address.format().to_string()),
+            network: Some(N::NAME.to_string()),
+            witness_script: Some(hex::encode(&witness_script)),
+            timelock: Some(timelock.to_string()),
+            hash: Some(hex::encode(htlc.hash)),
+            // The claim path selects OP_IF with a true push, and has no timelock
+            claim_template: Some(BitcoinSpendingTemplate {
+                version: 1,
+                lock_time: 0,
+                sequence: 0xffffffff,
+                witness: vec![
+                    "<signature>".into(),
+                    "<preimage>".into(),
+                    "01".into(),
+                    hex::encode(&witness_script),
+                ],
+            }),
+            // The refund path selects OP_ELSE with an empty push
+            refund_template: Some(BitcoinSpendingTemplate {
+                version: timelock.version(),
+                lock_time: timelock.lock_time(),
+                sequence: timelock.sequence(),
+                witness: vec!["<signature>".into(), "".into(), hex::encode(&witness_script)],
+            }),
+            ..Default::default()
+        })
+    }
+
+    pub fn from_private_key<N: BitcoinNetwork>(private_key: &str, format: &BitcoinFormat) -> Result<Self, CLIError> {
+        let private_key = BitcoinPrivateKey::<N>::from_str(private_key)?;
+        let public_key = private_key.to_public_key();
+        let address = public_key.to_address(format)?;
+        Ok(Self {
+            private_key: Some(private_key.to_string()),
+            public_key: Some(public_key.to_string()),
+            address: Some(address.to_string()),
+            network: Some(N::NAME.to_string()),
+            format: Some(address.format().to_string()),
+            compressed: private_key.is_compressed().into(),
+            ..Default::default()
+        })
+    }
+
+    pub fn from_public_key<N: BitcoinNetwork>(public_key: &str, format: &BitcoinFormat) -> Result<Self, CLIError> {
+        let public_key = BitcoinPublicKey::<N>::from_str(public_key)?;
+        let address = public_key.to_address(format)?;
+        Ok(Self {
+            public_key: Some(public_key.to_string()),
+            address: Some(address.to_string()),
+            network: Some(N::NAME.to_string()),
+            format: Some(address.format().to_string()),
+            compressed: public_key.is_compressed().into(),
+            ..Default::default()
+        })
+    }
+
+    /// Returns the wallet with the P2TR address of its public key, as the internal key of a script tree
+    /// of the given tapscript leaves, and the control block spending each leaf.
+    /// If no leaves are specified, the wallet is returned unchanged.
+    pub fn with_script_tree<N: BitcoinNetwork>(mut self, script_leaves: &[String]) -> Result<Self, CLIError> {
+        let public_key = match (&self.public_key, script_leaves.is_empty()) {
+            (Some(public_key), false) => BitcoinPublicKey::<N>::from_str(public_key)?,
+            _ => return Ok(self),
+        };
+        let scripts = script_leaves
+            .iter()
+            .map(|script| hex::decode(script.trim()))
+            .collect::<Result<Vec<Vec<u8>>, _>>()?;
+        let script_tree = TaprootScriptTree::new(&scripts)?;
+        let control_blocks = script_tree.to_control_blocks(&public_key.to_secp256k1_public_key())?;
+
+        let address = BitcoinAddress::<N>::p2tr_script_tree(&public_key, &script_tree)?;
+        self.address = Some(address.to_string());
+        self.format = Some(address.format().to_string());
+        self.merkle_root = Some(hex::encode(script_tree.merkle_root));
+        self.script_leaves = Some(
+            scripts
+                .iter()
+                .zip(&script_tree.leaf_hashes)
+                .zip(&control_blocks)
+                .map(|((script, leaf_hash), control_block)| BitcoinScriptLeaf {
+                    script: hex::encode(script),
+                    leaf_hash: hex::encode(leaf_hash),
+                    control_block: hex::encode(control_block),
+                })
+                .collect(),
+        );
+        Ok(self)
+    }
+
+    pub fn from_address<N: BitcoinNetwork>(address: &str) -> Result<Self, CLIError> {
+        let address = BitcoinAddress::<N>::from_str(address)?;
+        Ok(Self {
+            address: Some(address.to_string()),
+            network: Some(N::NAME.to_string()),
+            format: Some(address.format().to_string()),
+            ..Default::default()
+        })
+    }
+
+    pub fn to_raw_transaction<N: BitcoinNetwork>(
+        inputs: &Vec<BitcoinInput>,
+        outputs: &Vec<&str>,
+        op_return: Option<&[u8]>,
+        version: u32,
+        lock_time: u32,
+    ) -> Result<Self, CLIError> {
+        let mut transaction_inputs = vec![];
+        for input in inputs {
+            let transaction_input = BitcoinTransactionInput::<N>::new(
+                hex::decode(&input.txid)?,
+                input.vout,
+                None,
+                None,
+                None,
+                None,
+                None,
+                SignatureHash::SIGHASH_ALL,
+            )?;
+            transaction_inputs.push(transaction_input);
+        }
+
+        let mut transaction_outputs = vec![];
+        for output in outputs {
+            let values: Vec<&str> = output.split(":").collect();
+            let address = BitcoinAddress::<N>::from_str(values[0])?;
+            transaction_outputs.push(BitcoinTransactionOutput::new(
+                &address,
+                BitcoinAmount::from_satoshi(i64::from_str(values[1])?)?,
+            )?);
+        }
+        if let Some(data) = op_return {
+            transaction_outputs.push(BitcoinTransactionOutput::op_return(data)?);
+        }
+
+        let transaction_parameters = BitcoinTransactionParameters::<N> {
+            version,
+            inputs: transaction_inputs,
+            outputs: transaction_outputs,
+            lock_time,
+            segwit_flag: false,
+        };
+
+        let transaction = BitcoinTransaction::<N>::new(&transaction_parameters)?;
+        let raw_transaction_hex = hex::encode(&transaction.to_transaction_bytes()?);
+
+        Ok(Self {
+            transaction_hex: Some(raw_transaction_hex),
+            ..Default::default()
+        })
+    }
+
+    pub fn to_unsigned_transaction_file<N: BitcoinNetwork>(
+        inputs: &Vec<BitcoinInput>,
+        outputs: &Vec<&str>,
+        op_return: Option<&[u8]>,
+        version: u32,
+        lock_time: u32,
+        path: &str,
+    ) -> Result<Self, CLIError> {
+        // The signing context of each input is recorded, while any private keys are left out
+        let mut context = vec![];
+        for input in inputs {
+            match (input.amount, &input.address) {
+                (Some(_), Some(address)) => BitcoinAddress::<N>::from_str(address)?,
+                _ => {
+                    return Err(CLIError::MissingSigningContext(format!(
+                        "{}:{}",
+                        input.txid, input.vout
+                    )))
+                }
+            };
+            context.push(BitcoinInput {
+                private_key: None,
+                ..input.clone()
+            });
+        }
+
+        let transaction_hex = Self::to_raw_transaction::<N>(inputs, outputs, op_return, version, lock_time)?
+            .transaction_hex
+            .unwrap_or_default();
+        UnsignedTransactionFile::new("bitcoin", N::NAME, &transaction_hex, context).write(path)?;
+
+        Ok(Self {
+            network: Some(N::NAME.to_string()),
+            transaction_hex: Some(transaction_hex),
+            ..Default::default()
+        })
+    }
+
+    pub fn from_unsigned_transaction_file<N: BitcoinNetwork>(
+        file: &UnsignedTransactionFile<Vec<BitcoinInput>>,
+        private_keys: &str,
+    ) -> Result<Self, CLIError> {
+        let inputs = to_signing_inputs::<N>(&file.context, &to_private_keys::<N>(private_keys)?)?;
+
+        Ok(Self {
+            network: Some(N::NAME.to_string()),
+            ..Self::to_signed_transaction::<N>(&file.transaction_hex, &inputs)?
+        })
+    }
+
+    /// Returns the given replaceable transaction (BIP-125) with its fee bumped to the given fee rate in sat/vB,
+    /// paid from its change output, and signed again with the given private keys.
+    pub fn to_bumped_transaction<N: BitcoinNetwork>(
+        transaction_hex: &str,
+        context: &[BitcoinInput],
+        private_keys: &str,
+        fee_rate: f64,
+        change_output: Option<usize>,
+    ) -> Result<Self, CLIError> {
+        let transaction = BitcoinTransaction::<N>::from_transaction_bytes(&hex::decode(transaction_hex)?)?;
+        if !transaction.signals_rbf() {
+            return Err(
+                TransactionError::Message("transaction does not signal replace-by-fee (BIP-125)".into()).into(),
+            );
+        }
+        if !fee_rate.is_finite() || fee_rate <= 0.0 {
+            return Err(TransactionError::Message(format!("invalid fee rate: {} sat/vB", fee_rate)).into());
+        }
+
+        let private_keys = to_private_keys::<N>(private_keys)?;
+        let inputs = to_signing_inputs::<N>(context, &private_keys)?;
+
+        // The fee of the transaction is the amount of its inputs, from their signing context, less its outputs
+        let mut fee = 0;
+        for input in &transaction.parameters().inputs {
+            let mut transaction_id = input.outpoint.reverse_transaction_id.clone();
+            transaction_id.reverse();
+            let transaction_id = hex::encode(&transaction_id);
+            let outpoint = format!("{}:{}", transaction_id, input.outpoint.index);
+            match inputs
+                .iter()
+                .find(|input_context| input_context.txid.to_lowercase() == transaction_id)
+                .filter(|input_context| input_context.vout == input.outpoint.index)
+                .and_then(|input_context| input_context.amount)
+            {
+                Some(amount) => fee += amount as i64,
+                None => return Err(CLIError::MissingSigningContext(outpoint)),
+            };
+        }
+        let outputs = &transaction.parameters().outputs;
+        fee -= outputs.iter().map(|output| output.amount.0).sum::<i64>();
+
+        // The change output is the specified output, or otherwise the last output to an address of the private keys
+        let change = match change_output {
+            Some(index) if index < outputs.len() => index,
+            Some(index) => return Err(TransactionError::Message(format!("invalid change output: {}", index)).into()),
+            None => {
+                let mut scripts = vec![];
+                for private_key in &private_keys {
+                    for (_, format) in PURPOSE_FORMATS.iter() {
+                        if let Ok(address) = private_key.to_address(format) {
+                            scripts.push(create_script_pub_key(&address)?);
+                        }
+                    }
+                }
+                outputs
+                    .iter()
+                    .rposition(|output| scripts.contains(&output.script_pub_key))
+                    .ok_or_else(|| TransactionError::Message("missing change output".into()))?
+            }
+        };
+
+        // The size of a signature may vary by a byte, so the fee is recomputed until the size does not grow
+        let mut parameters = transaction.to_unsigned().parameters().clone();
+        let mut vsize = transaction.vsize()?;
+        loop {
+            let bumped_fee = (fee_rate * vsize as f64).ceil() as i64;
+            let minimum_fee = fee + INCREMENTAL_RELAY_FEERATE * vsize as i64;
+            if bumped_fee < minimum_fee {
+                return Err(TransactionError::Message(format!(
+                    "insufficient fee: {} sat for {} vB, expected at least {} sat",
+                    bumped_fee, vsize, minimum_fee
+                ))
+                .into());
+            }
+
+            let change_amount = outputs[change].amount.0 + fee - bumped_fee;
+            if change_amount < DUST_LIMIT as i64 {
+                return Err(TransactionError::Message(format!(
+                    "insufficient change: {} sat, expected at least {} sat",
+                    change_amount, DUST_LIMIT
+                ))
+                .into());
+            }
+            parameters.outputs[change].amount = BitcoinAmount::from_satoshi(change_amount)?;
+
+            let unsigned_transaction_hex =
+                hex::encode(&BitcoinTransaction::<N>::new(&parameters)?.to_transaction_bytes()?);
+            let signed = Self::to_signed_transaction::<N>(&unsigned_transaction_hex, &inputs)?;
+            let signed_vsize = match &signed.transaction_hex {
+                Some(signed_hex) => {
+                    BitcoinTransaction::<N>::from_transaction_bytes(&hex::decode(signed_hex)?)?.vsize()?
+                }
+                None => vsize,
+            };
+
+            if signed_vsize <= vsize {
+                return Ok(Self {
+                    network: Some(N::NAME.to_string()),
+                    fee: Some(bumped_fee),
+                    vsize: Some(signed_vsize),
+                    ..signed
+                });
+            }
+            vsize = signed_vsize;
+        }
+    }
+
+    /// Returns a transaction sweeping the given unspent outputs of the given private key to the given address,
+    /// as a single output of their amount less the fee at the given fee rate.
+    pub fn to_swept_transaction<N: BitcoinNetwork>(
+        private_key: &str,
+        unspents: &[BitcoinInput],
+        to: &str,
+        fee_rate: f64,
+    ) -> Result<Self, CLIError> {
+        if unspents.is_empty() {
+            return Err(TransactionError::Message("no unspent outputs to sweep".into()).into());
+        }
+        if !fee_rate.is_finite() || fee_rate <= 0.0 {
+            return Err(TransactionError::Message(format!("invalid fee rate: {} sat/vB", fee_rate)).into());
+        }
+        BitcoinAddress::<N>::from_str(to)?;
+
+        let private_keys = to_private_keys::<N>(private_key)?;
+        let inputs = to_signing_inputs::<N>(unspents, &private_keys)?;
+        let amount = inputs
+            .iter()
+            .map(|input| input.amount.unwrap_or_default() as i64)
+            .sum::<i64>();
+
+        // The size of a signature may vary by a byte, so the fee is recomputed until the size does not grow
+        let mut vsize = 0;
+        loop {
+            let fee = (fee_rate * vsize as f64).ceil() as i64;
+            let sweep_amount = amount - fee;
+            if sweep_amount < DUST_LIMIT as i64 {
+                return Err(TransactionError::Message(format!(
+                    "insufficient funds: {} sat less a fee of {} sat, expected at least {} sat",
+                    amount, fee, DUST_LIMIT
+                ))
+                .into());
+            }
+
+            let output = format!("{}:{}", to, sweep_amount);
+            let unsigned = Self::to_raw_transaction::<N>(&inputs, &vec![output.as_str()], None, 1, 0)?;
+            let unsigned_transaction_hex = unsigned.transaction_hex.unwrap_or_default();
+            let signed = Self::to_signed_transaction::<N>(&unsigned_transaction_hex, &inputs)?;
+            let signed_vsize = match &signed.transaction_hex {
+                Some(signed_hex) => {
+                    BitcoinTransaction::<N>::from_transaction_bytes(&hex::decode(signed_hex)?)?.vsize()?
+                }
+                None => vsize,
+            };
+
+            if signed_vsize <= vsize {
+                return Ok(Self {
+                    network: Some(N::NAME.to_string()),
+                    fee: Some(fee),
+                    vsize: Some(signed_vsize),
+                    ..signed
+                });
+            }
+            vsize = signed_vsize;
+        }
+    }
+
+    pub fn to_signed_transaction<N: BitcoinNetwork>(
+        transaction_hex: &str,
+        inputs: &Vec<BitcoinInput>,
+    ) -> Result<Self, CLIError> {
+        let mut transaction = BitcoinTransaction::<N>::from_transaction_bytes(&hex::decode(transaction_hex)?)?;
+
+        for input in inputs {
+            match (input.amount.clone(), input.address.clone(), input.private_key.clone()) {
+                (Some(amount), Some(address), Some(private_key)) => {
+                    let private_key = BitcoinPrivateKey::<N>::from_str(&private_key)?;
+                    let address = BitcoinAddress::<N>::from_str(&address)?;
+
+                    let redeem_script = match (input.redeem_script.clone(), address.format()) {
+                        (Some(script), _) => Some(hex::decode(script)?),
+                        (None, BitcoinFormat::P2SH_P2WPKH) => {
+                            let mut redeem_script = vec![0x00, 0x14];
+                            redeem_script.extend(&hash160(
+                                &private_key.to_public_key().to_secp256k1_public_key().serialize(),
+                            ));
+                            Some(redeem_script)
+                        }
+                        (None, _) => None,
                     };
 
                     let script_pub_key = match &input.script_pub_key {
@@ -320,6 +1506,79 @@ impl BitcoinWallet {
             ..Default::default()
         })
     }
+
+    pub fn to_signed_batch_transaction<N: BitcoinNetwork>(
+        extended_private_key: &str,
+        entry: &BitcoinBatchTransaction,
+    ) -> Result<Self, CLIError> {
+        let derivation_path = BitcoinDerivationPath::from_str(&entry.path)?;
+        let private_key = BitcoinExtendedPrivateKey::<N>::from_str(extended_private_key)?
+            .derive(&derivation_path)?
+            .to_private_key();
+        let inputs = to_signing_inputs(&entry.inputs, &[private_key])?;
+
+        Ok(Self {
+            path: Some(entry.path.clone()),
+            network: Some(N::NAME.to_string()),
+            ..Self::to_signed_transaction::<N>(&entry.transaction_hex, &inputs)?
+        })
+    }
+
+    pub fn to_signed_message<N: BitcoinNetwork>(
+        private_key: &str,
+        format: &BitcoinFormat,
+        message: &str,
+        proof: Option<&str>,
+    ) -> Result<Self, CLIError> {
+        let private_key = BitcoinPrivateKey::<N>::from_str(private_key)?;
+        let address = private_key.to_address(format)?;
+        let signed_message = BitcoinSignedMessage::<N>::new(&address, message.as_bytes())?;
+
+        let signature = match (proof, address.format()) {
+            (Some("full"), _) => signed_message.sign_full(&private_key)?,
+            (None, BitcoinFormat::P2PKH) | (None, BitcoinFormat::P2SH_P2WPKH) => {
+                signed_message.sign_full(&private_key)?
+            }
+            _ => signed_message.sign_simple(&private_key)?,
+        };
+
+        Ok(Self {
+            address: Some(address.to_string()),
+            format: Some(address.format().to_string()),
+            network: Some(N::NAME.to_string()),
+            message: Some(message.to_string()),
+            signature: Some(signature.to_base64(STANDARD)),
+            ..Default::default()
+        })
+    }
+
+    pub fn from_signed_message<N: BitcoinNetwork>(
+        address: &str,
+        message: &str,
+        signature: &str,
+    ) -> Result<Self, CLIError> {
+        let address = BitcoinAddress::<N>::from_str(address)?;
+        let signed_message = BitcoinSignedMessage::<N>::new(&address, message.as_bytes())?;
+        let proof = signature.from_base64()?;
+
+        // A simple proof is a witness stack, while a full proof is a transaction starting with version 0
+        let valid = match address.format() {
+            BitcoinFormat::Bech32 | BitcoinFormat::P2TR if !proof.starts_with(&[0u8; 4]) => {
+                signed_message.verify_simple(&proof)
+            }
+            _ => signed_message.verify_full(&proof),
+        };
+
+        Ok(Self {
+            address: Some(address.to_string()),
+            format: Some(address.format().to_string()),
+            network: Some(N::NAME.to_string()),
+            message: Some(message.to_string()),
+            signature: Some(signature.to_string()),
+            valid: Some(valid.unwrap_or(false)),
+            ..Default::default()
+        })
+    }
 }
 
 #[cfg_attr(tarpaulin, skip)]
@@ -330,6 +1589,10 @@ impl Display for BitcoinWallet {
                 Some(path) => format!("      {}                 {}\n", "Path".cyan().bold(), path),
                 _ => "".to_owned(),
             },
+            match &self.duress {
+                Some(duress) => format!("      {}               {}\n", "Duress".cyan().bold(), duress),
+                _ => "".to_owned(),
+            },
             match &self.password {
                 Some(password) => format!("      {}             {}\n", "Password".cyan().bold(), password),
                 _ => "".to_owned(),
@@ -388,6 +1651,174 @@ impl Display for BitcoinWallet {
                 }
                 _ => "".to_owned(),
             },
+            match &self.fee {
+                Some(fee) => format!("      {}                  {} sat\n", "Fee".cyan().bold(), fee),
+                _ => "".to_owned(),
+            },
+            match &self.vsize {
+                Some(vsize) => format!("      {}         {} vB\n", "Virtual Size".cyan().bold(), vsize),
+                _ => "".to_owned(),
+            },
+            match &self.message {
+                Some(message) => format!("      {}              {}\n", "Message".cyan().bold(), message),
+                _ => "".to_owned(),
+            },
+            match &self.signature {
+                Some(signature) => format!("      {}            {}\n", "Signature".cyan().bold(), signature),
+                _ => "".to_owned(),
+            },
+            match &self.valid {
+                Some(valid) => format!("      {}                {}\n", "Valid".cyan().bold(), valid),
+                _ => "".to_owned(),
+            },
+            match &self.miniscript {
+                Some(miniscript) => format!("      {}           {}\n", "Miniscript".cyan().bold(), miniscript),
+                _ => "".to_owned(),
+            },
+            match &self.witness_script {
+                Some(witness_script) => format!("      {}       {}\n", "Witness Script".cyan().bold(), witness_script),
+                _ => "".to_owned(),
+            },
+            match &self.script_size {
+                Some(script_size) => format!("      {}          {}\n", "Script Size".cyan().bold(), script_size),
+                _ => "".to_owned(),
+            },
+            match &self.satisfaction_weight {
+                Some(satisfaction_weight) => format!(
+                    "      {}  {}\n",
+                    "Satisfaction Weight".cyan().bold(),
+                    satisfaction_weight
+                ),
+                _ => "".to_owned(),
+            },
+            match &self.requires_signature {
+                Some(requires_signature) => format!(
+                    "      {}   {}\n",
+                    "Requires Signature".cyan().bold(),
+                    requires_signature
+                ),
+                _ => "".to_owned(),
+            },
+            match &self.timelock {
+                Some(timelock) => format!("      {}             {}\n", "Timelock".cyan().bold(), timelock),
+                _ => "".to_owned(),
+            },
+            match &self.spending_template {
+                Some(template) => template.to_lines("Spend"),
+                _ => "".to_owned(),
+            },
+            match &self.hash {
+                Some(hash) => format!("      {}                 {}\n", "Hash".cyan().bold(), hash),
+                _ => "".to_owned(),
+            },
+            match &self.claim_template {
+                Some(template) => template.to_lines("Claim"),
+                _ => "".to_owned(),
+            },
+            match &self.refund_template {
+                Some(template) => template.to_lines("Refund"),
+                _ => "".to_owned(),
+            },
+            match &self.merkle_root {
+                Some(merkle_root) => format!("      {}          {}\n", "Merkle Root".cyan().bold(), merkle_root),
+                _ => "".to_owned(),
+            },
+            match &self.script_leaves {
+                Some(script_leaves) => script_leaves
+                    .iter()
+                    .map(|leaf| {
+                        [
+                            format!("      {}          {}\n", "Script Leaf".cyan().bold(), leaf.script),
+                            format!("      {}            {}\n", "Leaf Hash".cyan().bold(), leaf.leaf_hash),
+                            format!(
+                                "      {}        {}\n",
+                                "Control Block".cyan().bold(),
+                                leaf.control_block
+                            ),
+                        ]
+                        .concat()
+                    })
+                    .collect::<Vec<String>>()
+                    .concat(),
+                _ => "".to_owned(),
+            },
+            match &self.threshold {
+                Some(threshold) => format!("      {}            {}\n", "Threshold".cyan().bold(), threshold),
+                _ => "".to_owned(),
+            },
+            match &self.secret_package {
+                Some(secret_package) => format!("      {}       {}\n", "Secret Package".cyan().bold(), secret_package),
+                _ => "".to_owned(),
+            },
+            match &self.public_package {
+                Some(public_package) => format!("      {}       {}\n", "Public Package".cyan().bold(), public_package),
+                _ => "".to_owned(),
+            },
+            match &self.shares {
+                Some(shares) => format!("      {}               {}\n", "Shares".cyan().bold(), shares),
+                _ => "".to_owned(),
+            },
+            match &self.key_package {
+                Some(key_package) => format!("      {}          {}\n", "Key Package".cyan().bold(), key_package),
+                _ => "".to_owned(),
+            },
+            match &self.aggregate_public_key {
+                Some(aggregate_public_key) => format!(
+                    "      {} {}\n",
+                    "Aggregate Public Key".cyan().bold(),
+                    aggregate_public_key
+                ),
+                _ => "".to_owned(),
+            },
+            match &self.public_nonce {
+                Some(public_nonce) => format!("      {}         {}\n", "Public Nonce".cyan().bold(), public_nonce),
+                _ => "".to_owned(),
+            },
+            match &self.aggregate_nonce {
+                Some(aggregate_nonce) => {
+                    format!("      {}      {}\n", "Aggregate Nonce".cyan().bold(), aggregate_nonce)
+                }
+                _ => "".to_owned(),
+            },
+            match &self.partial_signature {
+                Some(partial_signature) => {
+                    format!("      {}    {}\n", "Partial Signature".cyan().bold(), partial_signature)
+                }
+                _ => "".to_owned(),
+            },
+        ]
+        .concat();
+
+        // Removes final new line character
+        let output = output[..output.len() - 1].to_owned();
+        write!(f, "\n{}", output)
+    }
+}
+
+/// Represents a derivation path of an HD wallet that derives a searched address
+#[derive(Serialize, Clone, Debug)]
+pub struct BitcoinDiscoveredPath {
+    pub path: String,
+    pub format: String,
+    /// Whether the format of the address is the format of the purpose of the path
+    pub standard: bool,
+    pub address: String,
+    pub network: String,
+}
+
+impl MaskSecrets for BitcoinDiscoveredPath {
+    fn mask_secrets(&mut self) {}
+}
+
+#[cfg_attr(tarpaulin, skip)]
+impl Display for BitcoinDiscoveredPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let output = [
+            format!("      {}                 {}\n", "Path".cyan().bold(), self.path),
+            format!("      {}               {}\n", "Format".cyan().bold(), self.format),
+            format!("      {}             {}\n", "Standard".cyan().bold(), self.standard),
+            format!("      {}              {}\n", "Address".cyan().bold(), self.address),
+            format!("      {}              {}\n", "Network".cyan().bold(), self.network),
         ]
         .concat();
 
@@ -397,6 +1828,47 @@ impl Display for BitcoinWallet {
     }
 }
 
+/// Represents a tapscript leaf of a taproot script tree, with the control block spending it
+#[derive(Serialize, Clone, Debug)]
+pub struct BitcoinScriptLeaf {
+    pub script: String,
+    pub leaf_hash: String,
+    pub control_block: String,
+}
+
+/// Represents the template of a transaction spending a time-locked output
+#[derive(Serialize, Clone, Debug)]
+pub struct BitcoinSpendingTemplate {
+    pub version: u32,
+    pub lock_time: u32,
+    pub sequence: u32,
+    pub witness: Vec<String>,
+}
+
+impl BitcoinSpendingTemplate {
+    /// Returns the lines of the template to display, with the given label.
+    fn to_lines(&self, label: &str) -> String {
+        [
+            ("Version", self.version.to_string()),
+            ("Lock Time", self.lock_time.to_string()),
+            ("Sequence", self.sequence.to_string()),
+            ("Witness", self.witness.join(" ")),
+        ]
+        .iter()
+        .map(|(name, value)| {
+            let name = format!("{} {}", label, name);
+            format!(
+                "      {}{} {}\n",
+                name.cyan().bold(),
+                " ".repeat(20 - name.len()),
+                value
+            )
+        })
+        .collect::<Vec<String>>()
+        .concat()
+    }
+}
+
 /// Represents parameters for a Bitcoin transaction input
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub struct BitcoinInput {
@@ -404,78 +1876,290 @@ pub struct BitcoinInput {
     pub vout: u32,
     pub amount: Option<u64>,
     pub address: Option<String>,
-    #[serde(rename(deserialize = "privatekey"))]
+    #[serde(alias = "privatekey", skip_serializing_if = "Option::is_none")]
     pub private_key: Option<String>,
-    #[serde(rename(deserialize = "scriptPubKey"))]
+    #[serde(alias = "scriptPubKey", skip_serializing_if = "Option::is_none")]
     pub script_pub_key: Option<String>,
-    #[serde(rename(deserialize = "redeemScript"))]
+    #[serde(alias = "redeemScript", skip_serializing_if = "Option::is_none")]
     pub redeem_script: Option<String>,
 }
 
+impl Schema for BitcoinInput {
+    fn schema() -> serde_json::Value {
+        object(
+            vec![
+                (
+                    "txid",
+                    property("string", "The id of the transaction of the spent output"),
+                ),
+                ("vout", property("integer", "The index of the spent output")),
+                (
+                    "amount",
+                    property("integer", "The amount of the spent output in satoshi"),
+                ),
+                ("address", property("string", "The address of the spent output")),
+                (
+                    "script_pub_key",
+                    property("string", "The script public key of the spent output"),
+                ),
+                (
+                    "redeem_script",
+                    property("string", "The redeem script of the spent output"),
+                ),
+            ],
+            &["txid", "vout", "amount", "address"],
+        )
+    }
+}
+
+/// Represents a prepared transaction of a batch file, signed with the key derived at its derivation path
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct BitcoinBatchTransaction {
+    pub path: String,
+    pub transaction_hex: String,
+    pub inputs: Vec<BitcoinInput>,
+}
+
 /// Represents options for a Bitcoin wallet
 #[derive(Clone, Debug, Serialize)]
 pub struct BitcoinOptions {
     // Standard command
+    clear_after: u64,
+    confirm_backup: bool,
     count: usize,
+    copy: Option<String>,
+    encrypt: bool,
+    encrypt_to: Option<String>,
     format: BitcoinFormat,
     json: bool,
+    manifest: Option<String>,
+    mask_secrets: bool,
     network: String,
+    reveal: bool,
+    save: Option<String>,
     subcommand: Option<String>,
     // HD and Import HD subcommands
     account: u32,
+    #[serde(skip)]
+    account_range: ChildNumberRange,
     chain: u32,
     derivation: String,
+    duress: Option<String>,
+    extended_format: Option<BitcoinFormat>,
     extended_private_key: Option<String>,
     extended_public_key: Option<String>,
     index: u32,
+    #[serde(skip)]
+    index_range: ChildNumberRange,
     language: String,
     mnemonic: Option<String>,
     password: Option<String>,
     path: Option<String>,
+    purpose: Option<u32>,
     word_count: u8,
+    wordlist_file: Option<String>,
+    // HD tree
+    breadth: u32,
+    depth: u32,
+    tree: bool,
+    // Import HD summary
+    summary: bool,
+    summary_count: u32,
+    // Import HD watch-only export
+    export_file: Option<String>,
+    export_format: Option<String>,
+    master_fingerprint: Option<String>,
+    // Derive addresses subcommand
+    checkpoint: Option<String>,
+    chunk_size: usize,
+    output: Option<String>,
+    resume_from: Option<u32>,
+    // Contains and discover paths subcommands
+    search_depth: u32,
+    threads: usize,
     // Import subcommand
     address: Option<String>,
+    column: Option<String>,
+    dumpfile: Option<String>,
+    electrum_wallet: Option<String>,
+    file: Option<String>,
     private: Option<String>,
     public: Option<String>,
+    script_leaves: Vec<String>,
     // Transaction subcommand
+    allow_dust: bool,
+    dust_limit: u64,
     transaction_inputs: Option<String>,
     transaction_hex: Option<String>,
     transaction_outputs: Option<String>,
     lock_time: Option<u32>,
+    op_return: Option<String>,
     version: Option<u32>,
+    // Create transaction and sign transaction subcommands
+    unsigned_transaction: Option<String>,
+    // Miniscript subcommand
+    keys: Option<String>,
+    policy: Option<String>,
+    // Bump fee subcommand
+    change_output: Option<usize>,
+    new_feerate: Option<f64>,
+    // Sweep subcommand
+    broadcast: bool,
+    fee_rate: Option<f64>,
+    rpc_url: Option<String>,
+    to: Option<String>,
+    // FROST subcommand
+    identifier: Option<u8>,
+    key_package: Option<String>,
+    packages: Option<String>,
+    participants: Option<u8>,
+    secret_package: Option<String>,
+    shares: Option<String>,
+    threshold: Option<u8>,
+    // MuSig subcommand
+    public_keys: Option<String>,
+    // FROST and MuSig subcommands
+    nonces: Option<String>,
+    partial_signatures: Option<String>,
+    secret_nonce_file: Option<String>,
+    // HTLC subcommand
+    hash: Option<String>,
+    recipient_pubkey: Option<String>,
+    refund_pubkey: Option<String>,
+    // Timelock and HTLC subcommands
+    locktime: Option<String>,
+    relative: bool,
+    // Sign message and verify message subcommands
+    message: Option<String>,
+    proof: Option<String>,
+    signature: Option<String>,
+    // Entropy grid subcommand
+    entropy: Option<String>,
+    pattern: Option<String>,
+    pdf: Option<String>,
+    // Recover subcommand
+    passphrase: Option<String>,
+    wallet_dat: Option<String>,
 }
 
 impl Default for BitcoinOptions {
     fn default() -> Self {
         Self {
             // Standard command
+            clear_after: CLIPBOARD_CLEAR_AFTER,
+            confirm_backup: false,
             count: 1,
+            copy: None,
+            encrypt: false,
+            encrypt_to: None,
             format: BitcoinFormat::P2PKH,
             json: false,
+            manifest: None,
+            mask_secrets: false,
             network: "mainnet".into(),
+            reveal: false,
+            save: None,
             subcommand: None,
             // HD and Import HD subcommands
             account: 0,
+            account_range: ChildNumberRange::from(0),
             chain: 0,
             derivation: "bip32".into(),
+            duress: None,
+            extended_format: None,
             extended_private_key: None,
             extended_public_key: None,
             index: 0,
+            index_range: ChildNumberRange::from(0),
             language: "english".into(),
             mnemonic: None,
             password: None,
             path: None,
+            purpose: None,
             word_count: 12,
+            wordlist_file: None,
+            // HD tree
+            breadth: 5,
+            depth: MAX_TREE_DEPTH,
+            tree: false,
+            // Import HD summary
+            summary: false,
+            summary_count: SUMMARY_COUNT,
+            // Import HD watch-only export
+            export_file: None,
+            export_format: None,
+            master_fingerprint: None,
+            // Derive addresses subcommand
+            checkpoint: None,
+            chunk_size: DERIVE_ADDRESSES_CHUNK_SIZE,
+            output: None,
+            resume_from: None,
+            // Contains and discover paths subcommands
+            search_depth: 1000,
+            threads: thread::available_parallelism().map_or(1, |threads| threads.get()),
             // Import subcommand
             address: None,
+            column: None,
+            dumpfile: None,
+            electrum_wallet: None,
+            file: None,
             private: None,
             public: None,
+            script_leaves: vec![],
             // Transaction subcommand
+            allow_dust: false,
+            dust_limit: DUST_LIMIT,
             transaction_inputs: None,
             transaction_hex: None,
             transaction_outputs: None,
             lock_time: None,
+            op_return: None,
             version: None,
+            // Create transaction and sign transaction subcommands
+            unsigned_transaction: None,
+            // Miniscript subcommand
+            keys: None,
+            policy: None,
+            // Bump fee subcommand
+            change_output: None,
+            new_feerate: None,
+            // Sweep subcommand
+            broadcast: false,
+            fee_rate: None,
+            rpc_url: None,
+            to: None,
+            // FROST subcommand
+            identifier: None,
+            key_package: None,
+            packages: None,
+            participants: None,
+            secret_package: None,
+            shares: None,
+            threshold: None,
+            // MuSig subcommand
+            public_keys: None,
+            // FROST and MuSig subcommands
+            nonces: None,
+            partial_signatures: None,
+            secret_nonce_file: None,
+            // HTLC subcommand
+            hash: None,
+            recipient_pubkey: None,
+            refund_pubkey: None,
+            // Timelock and HTLC subcommands
+            locktime: None,
+            relative: false,
+            // Sign message and verify message subcommands
+            message: None,
+            proof: None,
+            signature: None,
+            // Entropy grid subcommand
+            entropy: None,
+            pattern: None,
+            pdf: None,
+            // Recover subcommand
+            passphrase: None,
+            wallet_dat: None,
         }
     }
 }
@@ -483,36 +2167,129 @@ impl Default for BitcoinOptions {
 impl BitcoinOptions {
     fn parse(&mut self, arguments: &ArgMatches, options: &[&str]) {
         options.iter().for_each(|option| match *option {
-            "account" => self.account(clap::value_t!(arguments.value_of(*option), u32).ok()),
+            "account" => self.account(arguments.value_of(option)),
             "address" => self.address(arguments.value_of(option)),
+            "allow dust" => self.allow_dust(arguments.is_present(option)),
+            "breadth" => self.breadth(clap::value_t!(arguments.value_of(*option), u32).ok()),
+            "broadcast" => self.broadcast(arguments.is_present(option)),
             "chain" => self.chain(clap::value_t!(arguments.value_of(*option), u32).ok()),
+            "change" => self.chain(clap::value_t!(arguments.value_of(*option), u32).ok()),
+            "change output" => self.change_output(clap::value_t!(arguments.value_of(*option), usize).ok()),
+            "checkpoint" => self.checkpoint(arguments.value_of(option)),
+            "chunk size" => self.chunk_size(clap::value_t!(arguments.value_of(*option), usize).ok()),
+            "clear after" => self.clear_after(clap::value_t!(arguments.value_of(*option), u64).ok()),
+            "column" => self.column(arguments.value_of(option)),
+            "confirm backup" => self.confirm_backup(arguments.is_present(option)),
+            "copy" => self.copy(arguments.value_of(option)),
             "count" => self.count(clap::value_t!(arguments.value_of(*option), usize).ok()),
             "createrawtransaction" => self.create_raw_transaction(arguments.values_of(option)),
+            "depth" => self.depth(clap::value_t!(arguments.value_of(*option), u32).ok()),
             "derivation" => self.derivation(arguments.value_of(option)),
+            "dumpfile" => self.dumpfile(arguments.value_of(option)),
+            "duress" => self.duress(arguments.value_of(option)),
+            "dust limit" => self.dust_limit(clap::value_t!(arguments.value_of(*option), u64).ok()),
+            "electrum wallet" => self.electrum_wallet(arguments.value_of(option)),
+            "encrypt" => self.encrypt(arguments.is_present(option)),
+            "encrypt to" => self.encrypt_to(arguments.value_of(option)),
+            "entropy" => self.entropy(arguments.value_of(option)),
+            "export file" => self.export_file(arguments.value_of(option)),
+            "export format" => self.export_format(arguments.value_of(option)),
             "extended private" => self.extended_private(arguments.value_of(option)),
+            "extended private key" => self.extended_private(arguments.value_of(option)),
             "extended public" => self.extended_public(arguments.value_of(option)),
+            "fee rate" => self.fee_rate(clap::value_t!(arguments.value_of(*option), f64).ok()),
+            "file" => self.file(arguments.value_of(option)),
             "format" => self.format(arguments.value_of(option)),
             "json" => self.json(arguments.is_present(option)),
-            "index" => self.index(clap::value_t!(arguments.value_of(*option), u32).ok()),
+            "hash" => self.hash(arguments.value_of(option)),
+            "identifier" => self.identifier(clap::value_t!(arguments.value_of(*option), u8).ok()),
+            "inputs" => self.inputs(arguments.value_of(option)),
+            "index" => self.index(arguments.value_of(option)),
+            "key package" => self.key_package(arguments.value_of(option)),
+            "keys" => self.keys(arguments.value_of(option)),
             "language" => self.language(arguments.value_of(option)),
             "lock time" => self.lock_time(clap::value_t!(arguments.value_of(*option), u32).ok()),
+            "locktime" => self.locktime(arguments.value_of(option)),
+            "manifest" => self.manifest(arguments.value_of(option)),
+            "mask secrets" => self.mask_secrets(arguments.is_present(option)),
+            "master fingerprint" => self.master_fingerprint(arguments.value_of(option)),
+            "message" => self.message(arguments.value_of(option)),
             "mnemonic" => self.mnemonic(arguments.value_of(option)),
             "network" => self.network(arguments.value_of(option)),
+            "new feerate" => self.new_feerate(clap::value_t!(arguments.value_of(*option), f64).ok()),
+            "nonces" => self.nonces(arguments.value_of(option)),
+            "op return" => self.op_return(arguments.value_of(option)),
+            "output" => self.output(arguments.value_of(option)),
+            "outputs" => self.outputs(arguments.value_of(option)),
+            "packages" => self.packages(arguments.value_of(option)),
+            "partial signatures" => self.partial_signatures(arguments.value_of(option)),
+            "participants" => self.participants(clap::value_t!(arguments.value_of(*option), u8).ok()),
+            "passphrase" => self.passphrase(arguments.value_of(option)),
             "password" => self.password(arguments.value_of(option)),
+            "path" => self.path(arguments.value_of(option)),
+            "pattern" => self.pattern(arguments.value_of(option)),
+            "pdf" => self.pdf(arguments.value_of(option)),
+            "policy" => self.policy(arguments.value_of(option)),
             "private" => self.private(arguments.value_of(option)),
+            "private key" => self.private(arguments.value_of(option)),
+            "proof" => self.proof(arguments.value_of(option)),
             "public" => self.public(arguments.value_of(option)),
+            "pubkey" => self.public(arguments.value_of(option)),
+            "public keys" => self.public_keys(arguments.value_of(option)),
+            "purpose" => self.purpose(clap::value_t!(arguments.value_of(*option), u32).ok()),
+            "raw tx" => self.raw_transaction(arguments.value_of(option)),
+            "recipient pubkey" => self.recipient_pubkey(arguments.value_of(option)),
+            "refund pubkey" => self.refund_pubkey(arguments.value_of(option)),
+            "relative" => self.relative(arguments.is_present(option)),
+            "resume from" => self.resume_from(clap::value_t!(arguments.value_of(*option), u32).ok()),
+            "reveal" => self.reveal(arguments.is_present(option)),
+            "rpc url" => self.rpc_url(arguments.value_of(option)),
+            "save" => self.save(arguments.value_of(option)),
+            "script leaf" => self.script_leaves(arguments.values_of(option)),
+            "search depth" => self.search_depth(clap::value_t!(arguments.value_of(*option), u32).ok()),
+            "secret nonce file" => self.secret_nonce_file(arguments.value_of(option)),
+            "secret package" => self.secret_package(arguments.value_of(option)),
+            "shares" => self.shares(arguments.value_of(option)),
+            "signature" => self.signature(arguments.value_of(option)),
             "signrawtransaction" => self.sign_raw_transaction(arguments.values_of(option)),
+            "summary" => self.summary(arguments.is_present(option)),
+            "summary count" => self.summary_count(clap::value_t!(arguments.value_of(*option), u32).ok()),
+            "threads" => self.threads(clap::value_t!(arguments.value_of(*option), usize).ok()),
+            "threshold" => self.threshold(clap::value_t!(arguments.value_of(*option), u8).ok()),
+            "to" => self.to(arguments.value_of(option)),
+            "tree" => self.tree(arguments.is_present(option)),
+            "unsigned tx" => self.unsigned_transaction(arguments.value_of(option)),
             "word count" => self.word_count(clap::value_t!(arguments.value_of(*option), u8).ok()),
+            "wordlist file" => self.wordlist_file(arguments.value_of(option)),
             "version" => self.version(clap::value_t!(arguments.value_of(*option), u32).ok()),
+            "wallet dat" => self.wallet_dat(arguments.value_of(option)),
+            "wif" => self.private(arguments.value_of(option)),
+            "xpub" => self.extended_public(arguments.value_of(option)),
             _ => (),
         });
     }
 
-    /// Sets `account` to the specified account index, overriding its previous state.
+    /// Sanitizes the key material of the options, with `--strict` rejecting key material in a non-canonical form.
+    fn sanitize_inputs(&mut self) -> Result<(), CLIError> {
+        sanitize_option("address", &mut self.address, InputKind::Text)?;
+        sanitize_option("entropy", &mut self.entropy, InputKind::Hex)?;
+        sanitize_option("extended private key", &mut self.extended_private_key, InputKind::Text)?;
+        sanitize_option("extended public key", &mut self.extended_public_key, InputKind::Text)?;
+        sanitize_option("key package", &mut self.key_package, InputKind::Hex)?;
+        sanitize_option("master fingerprint", &mut self.master_fingerprint, InputKind::Hex)?;
+        sanitize_option("mnemonic", &mut self.mnemonic, InputKind::Mnemonic)?;
+        sanitize_option("private key", &mut self.private, InputKind::Text)?;
+        sanitize_option("public key", &mut self.public, InputKind::Hex)?;
+        sanitize_option("secret package", &mut self.secret_package, InputKind::Hex)?;
+        sanitize_option("to address", &mut self.to, InputKind::Text)
+    }
+
+    /// Sets `account` and `account_range` to the specified account index or range, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
-    fn account(&mut self, argument: Option<u32>) {
-        if let Some(account) = argument {
-            self.account = account;
+    fn account(&mut self, argument: Option<&str>) {
+        if let Some(Ok(account)) = argument.map(ChildNumberRange::from_str) {
+            self.account = account.start();
+            self.account_range = account;
         }
     }
 
@@ -524,6 +2301,28 @@ impl BitcoinOptions {
         }
     }
 
+    /// Sets `allow_dust` to the specified boolean value, overriding its previous state.
+    fn allow_dust(&mut self, argument: bool) {
+        if argument {
+            self.allow_dust = true;
+        }
+    }
+
+    /// Sets `breadth` to the specified number of accounts and addresses of a tree, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn breadth(&mut self, argument: Option<u32>) {
+        if let Some(breadth) = argument {
+            self.breadth = breadth;
+        }
+    }
+
+    /// Sets `broadcast` to the specified boolean value, overriding its previous state.
+    fn broadcast(&mut self, argument: bool) {
+        if argument {
+            self.broadcast = true;
+        }
+    }
+
     /// Sets `chain` to the specified chain index, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
     fn chain(&mut self, argument: Option<u32>) {
@@ -532,6 +2331,59 @@ impl BitcoinOptions {
         }
     }
 
+    /// Sets `change_output` to the specified output index, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn change_output(&mut self, argument: Option<usize>) {
+        if let Some(change_output) = argument {
+            self.change_output = Some(change_output);
+        }
+    }
+
+    /// Sets `checkpoint` to the specified checkpoint file path of a batch, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn checkpoint(&mut self, argument: Option<&str>) {
+        if let Some(path) = argument {
+            self.checkpoint = Some(path.to_string());
+        }
+    }
+
+    /// Sets `chunk_size` to the specified number of addresses of a chunk, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn chunk_size(&mut self, argument: Option<usize>) {
+        if let Some(chunk_size) = argument {
+            self.chunk_size = chunk_size;
+        }
+    }
+
+    /// Sets `clear_after` to the specified number of seconds, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn clear_after(&mut self, argument: Option<u64>) {
+        if let Some(seconds) = argument {
+            self.clear_after = seconds;
+        }
+    }
+
+    /// Sets `column` to the specified CSV column of a batch file, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn column(&mut self, argument: Option<&str>) {
+        if let Some(column) = argument {
+            self.column = Some(column.to_string());
+        }
+    }
+
+    /// Sets `confirm_backup` to the specified boolean value, overriding its previous state.
+    fn confirm_backup(&mut self, argument: bool) {
+        self.confirm_backup = argument;
+    }
+
+    /// Sets `copy` to the specified wallet field, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn copy(&mut self, argument: Option<&str>) {
+        if let Some(field) = argument {
+            self.copy = Some(field.to_string());
+        }
+    }
+
     /// Sets `count` to the specified count, overriding its previous state.
     fn count(&mut self, argument: Option<usize>) {
         if let Some(count) = argument {
@@ -549,6 +2401,14 @@ impl BitcoinOptions {
         }
     }
 
+    /// Sets `depth` to the specified number of levels of a tree below its accounts, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn depth(&mut self, argument: Option<u32>) {
+        if let Some(depth) = argument {
+            self.depth = depth;
+        }
+    }
+
     /// Sets `derivation` to the specified derivation, overriding its previous state.
     /// If `derivation` is `\"custom\"`, then `path` is set to the specified path.
     /// If the specified argument is `None`, then no change occurs.
@@ -557,6 +2417,8 @@ impl BitcoinOptions {
             Some("bip32") => self.derivation = "bip32".into(),
             Some("bip44") => self.derivation = "bip44".into(),
             Some("bip49") => self.derivation = "bip49".into(),
+            Some("bip84") => self.derivation = "bip84".into(),
+            Some("bip86") => self.derivation = "bip86".into(),
             Some(custom) => {
                 self.derivation = "custom".into();
                 self.path = Some(custom.to_string());
@@ -565,6 +2427,86 @@ impl BitcoinOptions {
         };
     }
 
+    /// Sets `dumpfile` to the specified Bitcoin Core wallet dump, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn dumpfile(&mut self, argument: Option<&str>) {
+        if let Some(path) = argument {
+            self.dumpfile = Some(path.to_string());
+        }
+    }
+
+    /// Sets `duress` to the specified duress PIN, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn duress(&mut self, argument: Option<&str>) {
+        if let Some(pin) = argument {
+            self.duress = Some(pin.to_string());
+        }
+    }
+
+    /// Returns an error if a duress PIN is specified with a custom derivation path, which has no account
+    /// for the decoy account of the PIN.
+    fn duress_derivation(&self) -> Result<(), CLIError> {
+        match (&self.duress, self.derivation.as_str()) {
+            (Some(_), "custom") => Err(CLIError::UnsupportedDuressDerivation),
+            _ => Ok(()),
+        }
+    }
+
+    /// Sets `dust_limit` to the specified amount in satoshis, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn dust_limit(&mut self, argument: Option<u64>) {
+        if let Some(dust_limit) = argument {
+            self.dust_limit = dust_limit;
+        }
+    }
+
+    /// Sets `electrum_wallet` to the specified Electrum wallet file, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn electrum_wallet(&mut self, argument: Option<&str>) {
+        if let Some(path) = argument {
+            self.electrum_wallet = Some(path.to_string());
+        }
+    }
+
+    /// Sets `encrypt` to the specified boolean value, overriding its previous state.
+    fn encrypt(&mut self, argument: bool) {
+        if argument {
+            self.encrypt = true;
+        }
+    }
+
+    /// Sets `encrypt_to` to the specified recipients, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn encrypt_to(&mut self, argument: Option<&str>) {
+        if let Some(recipients) = argument {
+            self.encrypt_to = Some(recipients.to_string());
+        }
+    }
+
+    /// Sets `entropy` to the specified entropy of a grid, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn entropy(&mut self, argument: Option<&str>) {
+        if let Some(entropy) = argument {
+            self.entropy = Some(entropy.to_string());
+        }
+    }
+
+    /// Sets `export_file` to the specified file path of a watch-only wallet, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn export_file(&mut self, argument: Option<&str>) {
+        if let Some(path) = argument {
+            self.export_file = Some(path.to_string());
+        }
+    }
+
+    /// Sets `export_format` to the specified wallet of a watch-only wallet file, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn export_format(&mut self, argument: Option<&str>) {
+        if let Some(wallet) = argument {
+            self.export_format = Some(wallet.to_string());
+        }
+    }
+
     /// Sets `extended_private_key` to the specified extended private key, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
     fn extended_private(&mut self, argument: Option<&str>) {
@@ -581,6 +2523,22 @@ impl BitcoinOptions {
         }
     }
 
+    /// Sets `fee_rate` to the specified fee rate in sat/vB, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn fee_rate(&mut self, argument: Option<f64>) {
+        if let Some(fee_rate) = argument {
+            self.fee_rate = Some(fee_rate);
+        }
+    }
+
+    /// Sets `file` to the specified batch file of keys or addresses, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn file(&mut self, argument: Option<&str>) {
+        if let Some(path) = argument {
+            self.file = Some(path.to_string());
+        }
+    }
+
     /// Sets `format` to the specified format, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
     fn format(&mut self, argument: Option<&str>) {
@@ -588,15 +2546,41 @@ impl BitcoinOptions {
             Some("legacy") => self.format = BitcoinFormat::P2PKH,
             Some("segwit") => self.format = BitcoinFormat::P2SH_P2WPKH,
             Some("bech32") => self.format = BitcoinFormat::Bech32,
+            Some("taproot") => self.format = BitcoinFormat::P2TR,
             _ => (),
         };
     }
 
-    /// Sets `index` to the specified index, overriding its previous state.
+    /// Sets `hash` to the specified hash of a preimage, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
-    fn index(&mut self, argument: Option<u32>) {
-        if let Some(index) = argument {
-            self.index = index;
+    fn hash(&mut self, argument: Option<&str>) {
+        if let Some(hash) = argument {
+            self.hash = Some(hash.to_string());
+        }
+    }
+
+    /// Sets `identifier` to the specified identifier of a participant, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn identifier(&mut self, argument: Option<u8>) {
+        if let Some(identifier) = argument {
+            self.identifier = Some(identifier);
+        }
+    }
+
+    /// Sets `index` and `index_range` to the specified index or range, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn index(&mut self, argument: Option<&str>) {
+        if let Some(Ok(index)) = argument.map(ChildNumberRange::from_str) {
+            self.index = index.start();
+            self.index_range = index;
+        }
+    }
+
+    /// Sets `transaction_inputs` to the specified transaction inputs, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn inputs(&mut self, argument: Option<&str>) {
+        if let Some(inputs) = argument {
+            self.transaction_inputs = Some(inputs.to_string());
         }
     }
 
@@ -605,6 +2589,22 @@ impl BitcoinOptions {
         self.json = argument;
     }
 
+    /// Sets `key_package` to the specified key package of a participant, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn key_package(&mut self, argument: Option<&str>) {
+        if let Some(key_package) = argument {
+            self.key_package = Some(key_package.to_string());
+        }
+    }
+
+    /// Sets `keys` to the specified named public keys, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn keys(&mut self, argument: Option<&str>) {
+        if let Some(keys) = argument {
+            self.keys = Some(keys.to_string());
+        }
+    }
+
     /// Sets `language` to the specified language, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
     fn language(&mut self, argument: Option<&str>) {
@@ -629,6 +2629,43 @@ impl BitcoinOptions {
         }
     }
 
+    /// Sets `locktime` to the specified timelock, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn locktime(&mut self, argument: Option<&str>) {
+        if let Some(locktime) = argument {
+            self.locktime = Some(locktime.to_string());
+        }
+    }
+
+    /// Sets `manifest` to the specified manifest file, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn manifest(&mut self, argument: Option<&str>) {
+        if let Some(manifest) = argument {
+            self.manifest = Some(manifest.to_string());
+        }
+    }
+
+    /// Sets `mask_secrets` to the specified boolean value, overriding its previous state.
+    fn mask_secrets(&mut self, argument: bool) {
+        self.mask_secrets = argument;
+    }
+
+    /// Sets `master_fingerprint` to the specified master fingerprint, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn master_fingerprint(&mut self, argument: Option<&str>) {
+        if let Some(fingerprint) = argument {
+            self.master_fingerprint = Some(fingerprint.to_string());
+        }
+    }
+
+    /// Sets `message` to the specified message, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn message(&mut self, argument: Option<&str>) {
+        if let Some(message) = argument {
+            self.message = Some(message.to_string());
+        }
+    }
+
     /// Sets `mnemonic` to the specified mnemonic, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
     fn mnemonic(&mut self, argument: Option<&str>) {
@@ -647,70 +2684,713 @@ impl BitcoinOptions {
         };
     }
 
-    /// Sets `password` to the specified password, overriding its previous state.
+    /// Sets `new_feerate` to the specified fee rate in sat/vB, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
-    fn password(&mut self, argument: Option<&str>) {
-        if let Some(password) = argument {
-            self.password = Some(password.to_string());
+    fn new_feerate(&mut self, argument: Option<f64>) {
+        if let Some(new_feerate) = argument {
+            self.new_feerate = Some(new_feerate);
         }
     }
 
-    /// Imports a wallet for the specified private key, overriding its previous state.
+    /// Sets `nonces` to the specified public nonces of the signers, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
-    fn private(&mut self, argument: Option<&str>) {
-        if let Some(private_key) = argument {
-            self.private = Some(private_key.to_string());
+    fn nonces(&mut self, argument: Option<&str>) {
+        if let Some(nonces) = argument {
+            self.nonces = Some(nonces.to_string());
         }
     }
 
-    /// Imports a wallet for the specified public key, overriding its previous state.
+    /// Sets `op_return` to the specified OP_RETURN data, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
-    fn public(&mut self, argument: Option<&str>) {
-        if let Some(public_key) = argument {
-            self.public = Some(public_key.to_string())
+    fn op_return(&mut self, argument: Option<&str>) {
+        if let Some(op_return) = argument {
+            self.op_return = Some(op_return.to_string());
         }
     }
 
-    /// Sets `transaction_hex` and `transaction_inputs` to the specified transaction values, overriding its previous state.
+    /// Sets `output` to the specified output file path of a batch, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
-    fn sign_raw_transaction(&mut self, argument: Option<Values>) {
-        if let Some(transaction_parameters) = argument {
-            let params: Vec<&str> = transaction_parameters.collect();
-            self.transaction_hex = Some(params[0].to_string());
-            self.transaction_inputs = Some(params[1].to_string());
+    fn output(&mut self, argument: Option<&str>) {
+        if let Some(path) = argument {
+            self.output = Some(path.to_string());
         }
     }
 
-    /// Sets `word_count` to the specified word count, overriding its previous state.
+    /// Sets `transaction_outputs` to the specified transaction outputs, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
-    fn word_count(&mut self, argument: Option<u8>) {
-        if let Some(word_count) = argument {
-            self.word_count = word_count;
+    fn outputs(&mut self, argument: Option<&str>) {
+        if let Some(outputs) = argument {
+            self.transaction_outputs = Some(outputs.to_string());
         }
     }
 
-    /// Returns the derivation path with the specified account, chain, derivation, index, and path.
-    /// If `default` is enabled, then return the default path if no derivation was provided.
-    fn to_derivation_path(&self, default: bool) -> Option<String> {
-        match self.derivation.as_str() {
-            "bip32" => Some(format!("m/0'/0'/{}'", self.index)),
-            "bip44" => Some(format!("m/44'/0'/{}'/{}/{}", self.account, self.chain, self.index)),
-            "bip49" => Some(format!("m/49'/0'/{}'/{}/{}", self.account, self.chain, self.index)),
-            "custom" => self.path.clone(),
-            _ => match default {
-                true => Some(format!("m/0'/0'/{}'", self.index)),
-                false => None,
-            },
+    /// Sets `packages` to the specified public packages of the other participants, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn packages(&mut self, argument: Option<&str>) {
+        if let Some(packages) = argument {
+            self.packages = Some(packages.to_string());
         }
     }
 
-    /// Sets `version` to the specified transaction version, overriding its previous state.
+    /// Sets `partial_signatures` to the specified partial signatures of the signers, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
-    fn version(&mut self, argument: Option<u32>) {
-        if let Some(version) = argument {
-            self.version = Some(version);
+    fn partial_signatures(&mut self, argument: Option<&str>) {
+        if let Some(partial_signatures) = argument {
+            self.partial_signatures = Some(partial_signatures.to_string());
         }
     }
+
+    /// Sets `participants` to the specified number of participants, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn participants(&mut self, argument: Option<u8>) {
+        if let Some(participants) = argument {
+            self.participants = Some(participants);
+        }
+    }
+
+    /// Sets `passphrase` to the specified wallet passphrase, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn passphrase(&mut self, argument: Option<&str>) {
+        if let Some(passphrase) = argument {
+            self.passphrase = Some(passphrase.to_string());
+        }
+    }
+
+    /// Sets `password` to the specified password, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn password(&mut self, argument: Option<&str>) {
+        if let Some(password) = argument {
+            self.password = Some(password.to_string());
+        }
+    }
+
+    /// Sets `path` to the specified derivation path, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn path(&mut self, argument: Option<&str>) {
+        if let Some(path) = argument {
+            self.path = Some(path.to_string());
+        }
+    }
+
+    /// Sets `pattern` to the specified cells of a grid, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn pattern(&mut self, argument: Option<&str>) {
+        if let Some(pattern) = argument {
+            self.pattern = Some(pattern.to_string());
+        }
+    }
+
+    /// Sets `pdf` to the specified file path of a grid PDF, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn pdf(&mut self, argument: Option<&str>) {
+        if let Some(pdf) = argument {
+            self.pdf = Some(pdf.to_string());
+        }
+    }
+
+    /// Sets `policy` to the specified spending policy, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn policy(&mut self, argument: Option<&str>) {
+        if let Some(policy) = argument {
+            self.policy = Some(policy.to_string());
+        }
+    }
+
+    /// Imports a wallet for the specified private key, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn private(&mut self, argument: Option<&str>) {
+        if let Some(private_key) = argument {
+            self.private = Some(private_key.to_string());
+        }
+    }
+
+    /// Sets `proof` to the specified signed message proof type, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn proof(&mut self, argument: Option<&str>) {
+        match argument {
+            Some("full") => self.proof = Some("full".into()),
+            Some("simple") => self.proof = Some("simple".into()),
+            _ => (),
+        };
+    }
+
+    /// Imports a wallet for the specified public key, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn public(&mut self, argument: Option<&str>) {
+        if let Some(public_key) = argument {
+            self.public = Some(public_key.to_string())
+        }
+    }
+
+    /// Sets `public_keys` to the specified public keys of the signers, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn public_keys(&mut self, argument: Option<&str>) {
+        if let Some(public_keys) = argument {
+            self.public_keys = Some(public_keys.to_string());
+        }
+    }
+
+    /// Sets `purpose` and `derivation` to the specified BIP-43 purpose, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn purpose(&mut self, argument: Option<u32>) {
+        if let Some(purpose) = argument {
+            self.derivation = "purpose".into();
+            self.purpose = Some(purpose);
+        }
+    }
+
+    /// Sets `format` to the address format of the specified purpose, or `purpose` to the purpose
+    /// of the specified format if no purpose was specified. Returns an error if the purpose
+    /// and the specified format disagree.
+    fn purpose_format(&mut self, format: Option<&str>) -> Result<(), CLIError> {
+        match (self.purpose, format) {
+            (Some(purpose), format) => {
+                let (_, purpose_format) = PURPOSE_FORMATS.iter().find(|(p, _)| *p == purpose).unwrap();
+                if format.is_some() && self.format != *purpose_format {
+                    return Err(CLIError::MismatchedPurposeFormat(purpose, self.format.to_string()));
+                }
+                self.format = purpose_format.clone();
+            }
+            (None, Some(_)) => {
+                let (purpose, _) = PURPOSE_FORMATS.iter().find(|(_, f)| *f == self.format).unwrap();
+                self.purpose(Some(*purpose));
+            }
+            (None, None) => (),
+        };
+        Ok(())
+    }
+
+    /// Sets `transaction_hex` to the specified raw transaction, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn raw_transaction(&mut self, argument: Option<&str>) {
+        if let Some(transaction_hex) = argument {
+            self.transaction_hex = Some(transaction_hex.to_string());
+        }
+    }
+
+    /// Sets `recipient_pubkey` to the specified public key of the recipient, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn recipient_pubkey(&mut self, argument: Option<&str>) {
+        if let Some(recipient_pubkey) = argument {
+            self.recipient_pubkey = Some(recipient_pubkey.to_string());
+        }
+    }
+
+    /// Sets `refund_pubkey` to the specified refund public key, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn refund_pubkey(&mut self, argument: Option<&str>) {
+        if let Some(refund_pubkey) = argument {
+            self.refund_pubkey = Some(refund_pubkey.to_string());
+        }
+    }
+
+    /// Sets `relative` to the specified boolean value, overriding its previous state.
+    fn relative(&mut self, argument: bool) {
+        if argument {
+            self.relative = true;
+        }
+    }
+
+    /// Sets `resume_from` to the specified index of a batch, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn resume_from(&mut self, argument: Option<u32>) {
+        if let Some(index) = argument {
+            self.resume_from = Some(index);
+        }
+    }
+
+    /// Sets `reveal` to the specified boolean value, overriding its previous state.
+    fn reveal(&mut self, argument: bool) {
+        self.reveal = argument;
+    }
+
+    /// Sets `rpc_url` to the specified URL of a Bitcoin node, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn rpc_url(&mut self, argument: Option<&str>) {
+        if let Some(rpc_url) = argument {
+            self.rpc_url = Some(rpc_url.to_string());
+        }
+    }
+
+    /// Sets `save` to the specified wallet file, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn save(&mut self, argument: Option<&str>) {
+        if let Some(save) = argument {
+            self.save = Some(save.to_string());
+        }
+    }
+
+    /// Sets `script_leaves` to the specified tapscript leaves, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn script_leaves(&mut self, argument: Option<Values>) {
+        if let Some(script_leaves) = argument {
+            self.script_leaves = script_leaves.map(|script| script.to_string()).collect();
+        }
+    }
+
+    /// Sets `search_depth` to the specified number of indices, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn search_depth(&mut self, argument: Option<u32>) {
+        if let Some(depth) = argument {
+            self.search_depth = depth;
+        }
+    }
+
+    /// Sets `secret_nonce_file` to the specified secret nonce file, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn secret_nonce_file(&mut self, argument: Option<&str>) {
+        if let Some(secret_nonce_file) = argument {
+            self.secret_nonce_file = Some(secret_nonce_file.to_string());
+        }
+    }
+
+    /// Sets `secret_package` to the specified secret package of a participant, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn secret_package(&mut self, argument: Option<&str>) {
+        if let Some(secret_package) = argument {
+            self.secret_package = Some(secret_package.to_string());
+        }
+    }
+
+    /// Sets `shares` to the specified secret shares sent to a participant, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn shares(&mut self, argument: Option<&str>) {
+        if let Some(shares) = argument {
+            self.shares = Some(shares.to_string());
+        }
+    }
+
+    /// Sets `signature` to the specified signature, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn signature(&mut self, argument: Option<&str>) {
+        if let Some(signature) = argument {
+            self.signature = Some(signature.to_string());
+        }
+    }
+
+    /// Sets `transaction_hex` and `transaction_inputs` to the specified transaction values, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn sign_raw_transaction(&mut self, argument: Option<Values>) {
+        if let Some(transaction_parameters) = argument {
+            let params: Vec<&str> = transaction_parameters.collect();
+            self.transaction_hex = Some(params[0].to_string());
+            self.transaction_inputs = Some(params[1].to_string());
+        }
+    }
+
+    /// Sets `summary` to the specified boolean value, overriding its previous state.
+    fn summary(&mut self, argument: bool) {
+        if argument {
+            self.summary = true;
+        }
+    }
+
+    /// Sets `summary_count` to the specified number of addresses of a summary, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn summary_count(&mut self, argument: Option<u32>) {
+        if let Some(count) = argument {
+            self.summary_count = count;
+        }
+    }
+
+    /// Sets `threads` to the specified number of threads of a search, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn threads(&mut self, argument: Option<usize>) {
+        if let Some(threads) = argument {
+            self.threads = threads;
+        }
+    }
+
+    /// Sets `threshold` to the specified threshold of a FROST key, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn threshold(&mut self, argument: Option<u8>) {
+        if let Some(threshold) = argument {
+            self.threshold = Some(threshold);
+        }
+    }
+
+    /// Sets `to` to the specified address, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn to(&mut self, argument: Option<&str>) {
+        if let Some(to) = argument {
+            self.to = Some(to.to_string());
+        }
+    }
+
+    /// Sets `tree` to the specified boolean value, overriding its previous state.
+    fn tree(&mut self, argument: bool) {
+        if argument {
+            self.tree = true;
+        }
+    }
+
+    /// Sets `unsigned_transaction` to the specified unsigned transaction file, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn unsigned_transaction(&mut self, argument: Option<&str>) {
+        if let Some(unsigned_transaction) = argument {
+            self.unsigned_transaction = Some(unsigned_transaction.to_string());
+        }
+    }
+
+    /// Sets `wallet_dat` to the specified wallet.dat, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn wallet_dat(&mut self, argument: Option<&str>) {
+        if let Some(path) = argument {
+            self.wallet_dat = Some(path.to_string());
+        }
+    }
+
+    /// Sets `word_count` to the specified word count, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn word_count(&mut self, argument: Option<u8>) {
+        if let Some(word_count) = argument {
+            self.word_count = word_count;
+        }
+    }
+
+    /// Sets `wordlist_file` to the specified wordlist file, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn wordlist_file(&mut self, argument: Option<&str>) {
+        if let Some(path) = argument {
+            self.wordlist_file = Some(path.to_string());
+        }
+    }
+
+    /// Returns the derivation path with the specified account, chain, derivation, index, and path.
+    /// If `default` is enabled, then return the default path if no derivation was provided.
+    /// If a duress PIN is specified, then the account is the decoy account of the PIN.
+    fn to_derivation_path(&self, default: bool) -> Option<String> {
+        let decoy_account = self.duress.as_deref().map(duress_account);
+        let account = decoy_account.unwrap_or(self.account);
+        match self.derivation.as_str() {
+            "bip32" => Some(format!("m/{}'/0'/{}'", decoy_account.unwrap_or(0), self.index)),
+            "bip44" => Some(format!("m/44'/0'/{}'/{}/{}", account, self.chain, self.index)),
+            "bip49" => Some(format!("m/49'/0'/{}'/{}/{}", account, self.chain, self.index)),
+            "bip84" => Some(format!("m/84'/0'/{}'/{}/{}", account, self.chain, self.index)),
+            "bip86" => Some(format!("m/86'/0'/{}'/{}/{}", account, self.chain, self.index)),
+            "purpose" => {
+                let coin_type = match self.network.as_str() {
+                    "testnet" => BitcoinTestnet::HD_COIN_TYPE,
+                    _ => BitcoinMainnet::HD_COIN_TYPE,
+                };
+                Some(format!(
+                    "m/{}'/{}/{}'/{}/{}",
+                    self.purpose.unwrap_or(44),
+                    coin_type,
+                    account,
+                    self.chain,
+                    self.index
+                ))
+            }
+            "custom" => self.path.clone(),
+            _ => match default {
+                true => Some(format!("m/{}'/0'/{}'", decoy_account.unwrap_or(0), self.index)),
+                false => None,
+            },
+        }
+    }
+
+    /// Returns the derivation paths with the specified account and index ranges, if the path is a template.
+    /// Otherwise, returns the derivation path with the specified account, chain, derivation, index, and path.
+    fn to_derivation_paths(&self, default: bool) -> Result<Vec<Option<String>>, CLIError> {
+        match &self.path {
+            Some(path) if self.derivation == "custom" && DerivationPathTemplate::is_template(path) => {
+                let template = DerivationPathTemplate::from_str(path)?;
+                Ok(template
+                    .expand(&self.account_range, &self.index_range)
+                    .into_iter()
+                    .map(Some)
+                    .collect())
+            }
+            _ => Ok(vec![self.to_derivation_path(default)]),
+        }
+    }
+
+    /// Returns the specified OP_RETURN data, which is hex with a `0x` prefix, and otherwise UTF-8 text.
+    fn to_op_return(&self) -> Result<Option<Vec<u8>>, CLIError> {
+        let data = match &self.op_return {
+            Some(data) => match data.strip_prefix("0x") {
+                Some(hex) => hex::decode(hex)?,
+                None => data.as_bytes().to_vec(),
+            },
+            None => return Ok(None),
+        };
+        // The size is checked before the networks are tried, so that its error is not masked
+        match data.len() {
+            0..=MAX_OP_RETURN_SIZE => Ok(Some(data)),
+            size => Err(TransactionError::Message(format!(
+                "invalid OP_RETURN data: {} bytes exceeds {} bytes",
+                size, MAX_OP_RETURN_SIZE
+            ))
+            .into()),
+        }
+    }
+
+    /// Returns the specified named public keys of a policy, as `name=public key` pairs in hex (comma separated).
+    fn to_keys(&self) -> Result<Vec<(String, Vec<u8>)>, CLIError> {
+        let keys = match &self.keys {
+            Some(keys) => keys,
+            None => return Ok(vec![]),
+        };
+        keys.split(',')
+            .map(
+                |key| match key.trim().splitn(2, '=').collect::<Vec<&str>>().as_slice() {
+                    [name, public_key] => match hex::decode(public_key.trim()) {
+                        Ok(public_key) => Ok((name.trim().to_string(), public_key)),
+                        Err(_) => Err(MiniscriptError::InvalidKey(key.to_string()).into()),
+                    },
+                    _ => Err(MiniscriptError::InvalidKey(key.to_string()).into()),
+                },
+            )
+            .collect()
+    }
+
+    /// Returns the specified RPC URL, or the RPC URL of the `WAGYU_BITCOIN_RPC` environment variable if none is specified.
+    fn to_rpc_url(&self) -> Option<String> {
+        self.rpc_url.clone().or_else(|| std::env::var(BITCOIN_RPC_URL).ok())
+    }
+
+    /// Returns the specified timelock, which is a relative number of blocks, or of seconds with an `s` suffix,
+    /// if `relative` is set, and otherwise an absolute block height or Unix timestamp.
+    fn to_timelock(&self) -> Result<Option<BitcoinTimelock>, CLIError> {
+        let locktime = match &self.locktime {
+            Some(locktime) => locktime.trim(),
+            None => return Ok(None),
+        };
+        let timelock = match (self.relative, locktime.strip_suffix('s')) {
+            (true, Some(seconds)) => BitcoinTimelock::relative_seconds(u32::from_str(seconds)?)?,
+            (true, None) => BitcoinTimelock::relative_blocks(u32::from_str(locktime)?)?,
+            (false, _) => BitcoinTimelock::absolute(u32::from_str(locktime)?)?,
+        };
+        Ok(Some(timelock))
+    }
+
+    /// Sets `version` to the specified transaction version, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn version(&mut self, argument: Option<u32>) {
+        if let Some(version) = argument {
+            self.version = Some(version);
+        }
+    }
+}
+
+/// Returns the account of the given path, its deepest hardened key (or the master key, if it has none),
+/// with the format of the purpose of the path, if it is the purpose of a format.
+fn to_account_path<N: BitcoinNetwork>(
+    path: &str,
+) -> Result<(BitcoinDerivationPath<N>, Option<BitcoinFormat>), CLIError> {
+    let indices = BitcoinDerivationPath::<N>::from_str(path)?.to_vec()?;
+    let account_depth = indices.iter().rposition(ChildIndex::is_hardened).map_or(0, |i| i + 1);
+    let format = match indices.first() {
+        Some(ChildIndex::Hardened(purpose)) => PURPOSE_FORMATS
+            .iter()
+            .find(|(p, _)| p == purpose)
+            .map(|(_, format)| format.clone()),
+        _ => None,
+    };
+    Ok((
+        BitcoinDerivationPath::<N>::from_vec(&indices[..account_depth].to_vec())?,
+        format,
+    ))
+}
+
+/// Returns the given values in hex (comma separated), or the given error of the index of an invalid value.
+fn to_hex_values<T: for<'a> TryFrom<&'a [u8]>, E: Into<CLIError>>(
+    values: &str,
+    error: fn(usize) -> E,
+) -> Result<Vec<T>, CLIError> {
+    values
+        .split(',')
+        .enumerate()
+        .map(|(i, value)| {
+            let bytes = hex::decode(value.trim()).map_err(|_| error(i).into())?;
+            T::try_from(&bytes).map_err(|_| error(i).into())
+        })
+        .collect()
+}
+
+/// Returns the given private keys (comma separated).
+fn to_private_keys<N: BitcoinNetwork>(private_keys: &str) -> Result<Vec<BitcoinPrivateKey<N>>, CLIError> {
+    Ok(private_keys
+        .split(',')
+        .map(|private_key| BitcoinPrivateKey::<N>::from_str(private_key.trim()))
+        .collect::<Result<Vec<_>, _>>()?)
+}
+
+/// Returns the given signing context of inputs, each with the private key that corresponds to its address.
+fn to_signing_inputs<N: BitcoinNetwork>(
+    context: &[BitcoinInput],
+    private_keys: &[BitcoinPrivateKey<N>],
+) -> Result<Vec<BitcoinInput>, CLIError> {
+    let mut inputs = vec![];
+    for input in context {
+        let outpoint = format!("{}:{}", input.txid, input.vout);
+        let address = match &input.address {
+            Some(address) => BitcoinAddress::<N>::from_str(address)?,
+            None => return Err(CLIError::MissingSigningContext(outpoint)),
+        };
+        let private_key = private_keys
+            .iter()
+            .find(|private_key| private_key.to_address(&address.format()).ok().as_ref() == Some(&address))
+            .ok_or(CLIError::MissingSigningKey(outpoint))?;
+
+        inputs.push(BitcoinInput {
+            private_key: Some(private_key.to_string()),
+            ..input.clone()
+        });
+    }
+    Ok(inputs)
+}
+
+/// Returns the unspent outputs of the addresses of the given private key, from a scan of the UTXO set
+/// of the node at the given RPC URL. Only the formats that can be signed for are scanned.
+fn to_unspents<N: BitcoinNetwork>(rpc_url: &str, private_key: &str) -> Result<Vec<BitcoinInput>, CLIError> {
+    let private_key = BitcoinPrivateKey::<N>::from_str(private_key)?;
+    let formats: &[BitcoinFormat] = match private_key.is_compressed() {
+        true => &[BitcoinFormat::P2PKH, BitcoinFormat::P2SH_P2WPKH, BitcoinFormat::Bech32],
+        false => &[BitcoinFormat::P2PKH],
+    };
+
+    let mut addresses = vec![];
+    for format in formats {
+        let address = private_key.to_address(format)?;
+        addresses.push((hex::encode(create_script_pub_key(&address)?), address.to_string()));
+    }
+    let descriptors = addresses
+        .iter()
+        .map(|(_, address)| format!("addr({})", address))
+        .collect::<Vec<_>>();
+
+    let result = rpc_call_with_timeout(rpc_url, "scantxoutset", json!(["start", descriptors]), SCAN_TIMEOUT)?;
+    let mut unspents = vec![];
+    for unspent in result["unspents"].as_array().cloned().unwrap_or_default() {
+        let (txid, vout, script_pub_key, amount) = match (
+            unspent["txid"].as_str(),
+            unspent["vout"].as_u64(),
+            unspent["scriptPubKey"].as_str(),
+            unspent["amount"].as_f64(),
+        ) {
+            (Some(txid), Some(vout), Some(script_pub_key), Some(amount)) => (txid, vout, script_pub_key, amount),
+            _ => return Err(CLIError::RpcError(format!("malformed unspent output: {}", unspent))),
+        };
+        let address = addresses
+            .iter()
+            .find(|(script, _)| script == script_pub_key)
+            .map(|(_, address)| address.clone());
+
+        unspents.push(BitcoinInput {
+            txid: txid.to_string(),
+            vout: vout as u32,
+            amount: Some((amount * 100_000_000.0).round() as u64),
+            address,
+            private_key: None,
+            script_pub_key: None,
+            redeem_script: None,
+        });
+    }
+    Ok(unspents)
+}
+
+/// Returns the fee rate in sat/vB estimated by the node at the given RPC URL, for confirmation within the sweep target.
+fn to_estimated_fee_rate(rpc_url: &str) -> Result<f64, CLIError> {
+    let result = rpc_call(rpc_url, "estimatesmartfee", json!([SWEEP_CONFIRMATION_TARGET]))?;
+    match result.get("feerate").and_then(Value::as_f64) {
+        // The fee rate is estimated in BTC/kvB
+        Some(fee_rate) => Ok(fee_rate * 100_000_000.0 / 1000.0),
+        None => Err(CLIError::RpcError(format!(
+            "unable to estimate a fee rate, specify --fee-rate instead ({})",
+            result.get("errors").map(Value::to_string).unwrap_or_default()
+        ))),
+    }
+}
+
+/// Prints a warning if the format inferred from the SLIP-132 version bytes of an extended key
+/// conflicts with the specified format. The BIP-32 version bytes (xprv and xpub) are shared
+/// by BIP-44 and BIP-86 keys, so they do not conflict with a taproot format.
+fn warn_format_conflict(inferred_format: Option<BitcoinFormat>, format: &Option<BitcoinFormat>) {
+    match (inferred_format, format) {
+        (Some(BitcoinFormat::P2PKH), Some(BitcoinFormat::P2TR)) => (),
+        (Some(inferred_format), Some(format)) if inferred_format != *format => eprintln!(
+            "Warning: the extended key version bytes are for {} addresses, but {} addresses were specified",
+            inferred_format, format
+        ),
+        _ => (),
+    }
+}
+
+/// Returns the parameters of the given network, with the address prefix of each format (the version byte of a
+/// base58 address, or the human-readable part of a Bech32 address), its BIP-44 purpose, and its extended key
+/// versions (SLIP-132).
+fn to_network_info<N: BitcoinNetwork>() -> Value {
+    let options = BitcoinOptions {
+        network: N::NAME.into(),
+        ..Default::default()
+    };
+    let formats = FORMATS
+        .iter()
+        .map(|format| {
+            let address_prefix = match format {
+                BitcoinFormat::P2PKH | BitcoinFormat::P2SH_P2WPKH => hex::encode(N::to_address_prefix(format)),
+                _ => String::from_utf8_lossy(&N::to_address_prefix(&BitcoinFormat::Bech32)).to_string(),
+            };
+            let purpose = PURPOSE_FORMATS
+                .iter()
+                .find(|(_, purpose_format)| purpose_format == format)
+                .map(|(purpose, _)| purpose);
+            let info = json!({
+                "address_prefix": address_prefix,
+                "purpose": purpose,
+                "extended_private_key_version": N::to_extended_private_key_version_bytes(format).ok().map(hex::encode),
+                "extended_public_key_version": N::to_extended_public_key_version_bytes(format).ok().map(hex::encode),
+            });
+            (format.to_string(), info)
+        })
+        .collect::<serde_json::Map<String, Value>>();
+
+    json!({
+        "network": N::NAME,
+        "coin_type": to_coin_type(N::HD_COIN_TYPE),
+        "default_derivation_path": options.to_derivation_path(true),
+        "private_key_prefix": hex::encode([N::to_private_key_prefix()]),
+        "formats": formats,
+    })
+}
+
+/// Returns the mismatches between the stored fields of the given wallet and the fields re-derived from it,
+/// with its addresses re-derived in the given format.
+fn to_mismatches<N: BitcoinNetwork>(wallet: &Value, format: &BitcoinFormat) -> Vec<Mismatch> {
+    let mut mismatches = Vec::new();
+    if let Some(path) = wallet["path"].as_str() {
+        let password = wallet["password"].as_str();
+        mismatches.extend(rederive(
+            wallet,
+            "mnemonic",
+            &["private_key", "public_key"],
+            |mnemonic| BitcoinWallet::from_mnemonic::<N, English>(mnemonic, &password, path),
+        ));
+    }
+    mismatches.extend(rederive(
+        wallet,
+        "extended_private_key",
+        &["extended_public_key", "private_key", "public_key"],
+        |extended_private_key| BitcoinWallet::from_extended_private_key::<N>(extended_private_key, &None, &None),
+    ));
+    mismatches.extend(rederive(
+        wallet,
+        "extended_public_key",
+        &["public_key"],
+        |extended_public_key| BitcoinWallet::from_extended_public_key::<N>(extended_public_key, &None, &None),
+    ));
+    mismatches.extend(rederive(
+        wallet,
+        "private_key",
+        &["public_key", "address"],
+        |private_key| BitcoinWallet::from_private_key::<N>(private_key, format),
+    ));
+    mismatches.extend(rederive(wallet, "public_key", &["address"], |public_key| {
+        BitcoinWallet::from_public_key::<N>(public_key, format)
+    }));
+    mismatches
 }
 
 pub struct BitcoinCLI;
@@ -720,15 +3400,98 @@ impl CLI for BitcoinCLI {
 
     const NAME: NameType = "bitcoin";
     const ABOUT: AboutType = "Generates a Bitcoin wallet (include -h for more options)";
-    const FLAGS: &'static [FlagType] = &[flag::JSON];
+    const FLAGS: &'static [FlagType] = &[
+        flag::CLEAR_AFTER,
+        flag::COPY,
+        flag::ENCRYPT,
+        flag::ENCRYPT_TO,
+        flag::JSON,
+        flag::MANIFEST,
+        flag::MASK_SECRETS,
+        flag::REVEAL,
+        flag::SAVE,
+    ];
     const OPTIONS: &'static [OptionType] = &[option::COUNT, option::FORMAT_BITCOIN, option::NETWORK_BITCOIN];
     const SUBCOMMANDS: &'static [SubCommandType] = &[
+        subcommand::BUMP_FEE_BITCOIN,
+        subcommand::CONTAINS,
+        subcommand::CREATE_TX_BITCOIN,
+        subcommand::DERIVE_ADDRESSES_BITCOIN,
+        subcommand::DISCOVER_PATHS_BITCOIN,
+        subcommand::ENTROPY_GRID_BITCOIN,
+        subcommand::FROST_BITCOIN,
         subcommand::HD_BITCOIN,
+        subcommand::HTLC_BITCOIN,
         subcommand::IMPORT_BITCOIN,
         subcommand::IMPORT_HD_BITCOIN,
+        subcommand::MINISCRIPT_BITCOIN,
+        subcommand::MUSIG_BITCOIN,
+        subcommand::RECOVER_BITCOIN,
+        subcommand::SIGN_BATCH_BITCOIN,
+        subcommand::SIGN_MESSAGE_BITCOIN,
+        subcommand::SIGN_TX_BITCOIN,
+        subcommand::SWEEP_BITCOIN,
+        subcommand::TIMELOCK_BITCOIN,
         subcommand::TRANSACTION_BITCOIN,
+        subcommand::VERIFY_MESSAGE_BITCOIN,
+        subcommand::XPUB,
     ];
 
+    /// Generate Bitcoin wallets, derived from the mnemonic at `m/44'/0'/0'/0/i` if one is specified
+    #[cfg_attr(tarpaulin, skip)]
+    fn generate(
+        count: usize,
+        mnemonic: Option<&str>,
+        password: Option<&str>,
+    ) -> Result<Vec<serde_json::Value>, CLIError> {
+        (0..count)
+            .map(|index| {
+                let wallet = match mnemonic {
+                    Some(mnemonic) => BitcoinWallet::from_mnemonic::<BitcoinMainnet, English>(
+                        mnemonic,
+                        &password,
+                        &format!("m/44'/0'/0'/0/{}", index),
+                    )?,
+                    None => {
+                        BitcoinWallet::new::<BitcoinMainnet, _>(&mut StdRng::from_entropy(), &BitcoinFormat::P2PKH)?
+                    }
+                };
+                Ok(serde_json::to_value(wallet)?)
+            })
+            .collect()
+    }
+
+    /// Describe the parameters of the Bitcoin mainnet and testnet
+    #[cfg_attr(tarpaulin, skip)]
+    fn info() -> Result<Value, CLIError> {
+        Ok(json!({
+            "coin": Self::NAME,
+            "networks": [to_network_info::<BitcoinMainnet>(), to_network_info::<BitcoinTestnet>()],
+        }))
+    }
+
+    /// Suggest the derivations of Bitcoin HD wallets for `--derivation`
+    #[cfg_attr(tarpaulin, skip)]
+    fn value_hints() -> Result<Vec<ValueHint>, CLIError> {
+        let derivations = ["bip32", "bip44", "bip49", "bip84", "bip86"];
+        Ok(vec![(
+            "derivation",
+            derivations.iter().map(|d| d.to_string()).collect(),
+        )])
+    }
+
+    /// Verify a Bitcoin wallet, recognized by its mainnet or testnet address
+    fn verify(wallet: &Value) -> Result<Option<Vec<Mismatch>>, CLIError> {
+        let address = wallet["address"].as_str().unwrap_or_default();
+        if let Ok(address) = BitcoinAddress::<BitcoinMainnet>::from_str(address) {
+            return Ok(Some(to_mismatches::<BitcoinMainnet>(wallet, &address.format())));
+        }
+        if let Ok(address) = BitcoinAddress::<BitcoinTestnet>::from_str(address) {
+            return Ok(Some(to_mismatches::<BitcoinTestnet>(wallet, &address.format())));
+        }
+        Ok(None)
+    }
+
     /// Handle all CLI arguments and flags for Bitcoin
     #[cfg_attr(tarpaulin, skip)]
     fn parse(arguments: &ArgMatches) -> Result<Self::Options, CLIError> {
@@ -736,43 +3499,285 @@ impl CLI for BitcoinCLI {
         options.parse(arguments, &["count", "format", "json", "network"]);
 
         match arguments.subcommand() {
+            ("bump-fee", Some(arguments)) => {
+                options.subcommand = Some("bump-fee".into());
+                options.parse(arguments, &["json"]);
+                options.parse(
+                    arguments,
+                    &["change output", "inputs", "new feerate", "private key", "raw tx"],
+                );
+            }
+            ("contains", Some(arguments)) => {
+                options.subcommand = Some("contains".into());
+                options.parse(arguments, &["json"]);
+                options.parse(
+                    arguments,
+                    &["address", "language", "mnemonic", "password", "search depth", "xpub"],
+                );
+            }
+            ("create-tx", Some(arguments)) => {
+                options.subcommand = Some("create-tx".into());
+                options.parse(arguments, &["json"]);
+                options.parse(
+                    arguments,
+                    &[
+                        "allow dust",
+                        "dust limit",
+                        "inputs",
+                        "lock time",
+                        "op return",
+                        "outputs",
+                        "unsigned tx",
+                        "version",
+                    ],
+                );
+            }
             ("hd", Some(arguments)) => {
                 options.subcommand = Some("hd".into());
-                options.parse(arguments, &["count", "json", "network"]);
-                options.parse(arguments, &["derivation", "language", "password", "word count"]);
+                options.parse(arguments, &["count", "format", "json", "network"]);
+                options.parse(
+                    arguments,
+                    &[
+                        "account",
+                        "breadth",
+                        "change",
+                        "confirm backup",
+                        "depth",
+                        "derivation",
+                        "duress",
+                        "language",
+                        "mnemonic",
+                        "password",
+                        "purpose",
+                        "tree",
+                        "word count",
+                        "wordlist file",
+                    ],
+                );
+                options.purpose_format(arguments.value_of("format"))?;
+                options.duress_derivation()?;
+            }
+            ("derive-addresses", Some(arguments)) => {
+                options.subcommand = Some("derive-addresses".into());
+                options.parse(arguments, &["format"]);
+                options.parse(
+                    arguments,
+                    &[
+                        "checkpoint",
+                        "chunk size",
+                        "count",
+                        "output",
+                        "path",
+                        "resume from",
+                        "threads",
+                        "xpub",
+                    ],
+                );
+                // The format of the extended public key is inferred from its version bytes, unless a format is specified
+                if arguments.is_present("format") {
+                    options.extended_format = Some(options.format.clone());
+                }
+            }
+            ("discover-paths", Some(arguments)) => {
+                options.subcommand = Some("discover-paths".into());
+                options.account_range = ChildNumberRange::new(0, DISCOVER_PATHS_ACCOUNTS - 1)?;
+                options.search_depth = DISCOVER_PATHS_DEPTH;
+                options.parse(arguments, &["json"]);
+                options.parse(
+                    arguments,
+                    &[
+                        "account",
+                        "address",
+                        "extended private key",
+                        "language",
+                        "mnemonic",
+                        "password",
+                        "search depth",
+                        "threads",
+                    ],
+                );
+            }
+            ("entropy-grid", Some(arguments)) => {
+                options.subcommand = Some("entropy-grid".into());
+                options.parse(arguments, &["json"]);
+                options.parse(arguments, &["entropy", "mnemonic", "pattern", "pdf"]);
+            }
+            ("frost", Some(arguments)) => {
+                options.subcommand = Some("frost".into());
+                options.parse(arguments, &["json", "network"]);
+                options.parse(
+                    arguments,
+                    &[
+                        "identifier",
+                        "key package",
+                        "message",
+                        "nonces",
+                        "packages",
+                        "partial signatures",
+                        "participants",
+                        "secret nonce file",
+                        "secret package",
+                        "shares",
+                        "threshold",
+                    ],
+                );
+            }
+            ("htlc", Some(arguments)) => {
+                options.subcommand = Some("htlc".into());
+                options.parse(arguments, &["json", "network"]);
+                options.parse(
+                    arguments,
+                    &["hash", "locktime", "recipient pubkey", "refund pubkey", "relative"],
+                );
             }
             ("import", Some(arguments)) => {
                 options.subcommand = Some("import".into());
                 options.parse(arguments, &["format", "json", "network"]);
-                options.parse(arguments, &["address", "private", "public"]);
+                options.parse(
+                    arguments,
+                    &[
+                        "address",
+                        "column",
+                        "dumpfile",
+                        "electrum wallet",
+                        "file",
+                        "private",
+                        "public",
+                        "script leaf",
+                    ],
+                );
             }
             ("import-hd", Some(arguments)) => {
                 options.subcommand = Some("import-hd".into());
-                options.parse(arguments, &["json", "network"]);
+                options.parse(arguments, &["format", "json", "network"]);
                 options.parse(
                     arguments,
                     &[
                         "account",
                         "chain",
                         "derivation",
+                        "duress",
+                        "export file",
+                        "export format",
                         "extended private",
                         "extended public",
                         "index",
+                        "master fingerprint",
                         "mnemonic",
                         "password",
+                        "summary",
+                        "summary count",
+                        "wordlist file",
                     ],
                 );
+                // The format of an extended key is inferred from its version bytes, unless a format is specified
+                if arguments.is_present("format") {
+                    options.extended_format = Some(options.format.clone());
+                }
+                options.duress_derivation()?;
+            }
+            ("miniscript", Some(arguments)) => {
+                options.subcommand = Some("miniscript".into());
+                options.parse(arguments, &["json", "network"]);
+                options.parse(arguments, &["keys", "policy"]);
+            }
+            ("musig", Some(arguments)) => {
+                options.subcommand = Some("musig".into());
+                options.parse(arguments, &["json", "network"]);
+                options.parse(
+                    arguments,
+                    &[
+                        "message",
+                        "nonces",
+                        "partial signatures",
+                        "private key",
+                        "public keys",
+                        "secret nonce file",
+                    ],
+                );
+            }
+            ("recover", Some(arguments)) => {
+                options.subcommand = Some("recover".into());
+                options.parse(arguments, &["json", "network"]);
+                options.parse(arguments, &["passphrase", "wallet dat"]);
+            }
+            ("sign-message", Some(arguments)) => {
+                options.subcommand = Some("sign-message".into());
+                options.parse(arguments, &["format", "json"]);
+                options.parse(arguments, &["message", "private key", "proof"]);
+            }
+            ("sign-batch", Some(arguments)) => {
+                options.subcommand = Some("sign-batch".into());
+                options.parse(arguments, &["extended private key", "file"]);
+            }
+            ("sign-tx", Some(arguments)) => {
+                options.subcommand = Some("sign-tx".into());
+                options.parse(arguments, &["json"]);
+                options.parse(arguments, &["private key", "unsigned tx"]);
+            }
+            ("sweep", Some(arguments)) => {
+                options.subcommand = Some("sweep".into());
+                options.parse(arguments, &["json"]);
+                options.parse(arguments, &["broadcast", "fee rate", "rpc url", "to", "wif"]);
+            }
+            ("timelock", Some(arguments)) => {
+                options.subcommand = Some("timelock".into());
+                options.parse(arguments, &["json", "network"]);
+                options.parse(arguments, &["locktime", "pubkey", "relative"]);
             }
             ("transaction", Some(arguments)) => {
                 options.subcommand = Some("transaction".into());
                 options.parse(
                     arguments,
-                    &["createrawtransaction", "lock time", "signrawtransaction", "version"],
+                    &[
+                        "allow dust",
+                        "createrawtransaction",
+                        "dust limit",
+                        "lock time",
+                        "op return",
+                        "signrawtransaction",
+                        "version",
+                    ],
                 );
             }
+            ("verify-message", Some(arguments)) => {
+                options.subcommand = Some("verify-message".into());
+                options.parse(arguments, &["json"]);
+                options.parse(arguments, &["address", "message", "signature"]);
+            }
+            ("xpub", Some(arguments)) => {
+                options.subcommand = Some("xpub".into());
+                options.parse(arguments, &["json"]);
+                options.parse(arguments, &["extended private key", "path"]);
+            }
             _ => {}
         };
 
+        // The save flags are global, and thus parsed from the innermost subcommand arguments
+        let arguments = arguments.subcommand().1.unwrap_or(arguments);
+        options.parse(
+            arguments,
+            &[
+                "clear after",
+                "copy",
+                "encrypt",
+                "encrypt to",
+                "manifest",
+                "mask secrets",
+                "reveal",
+                "save",
+            ],
+        );
+        check_clipboard_field(&options.copy)?;
+        if options.encrypt && options.save.is_none() {
+            return Err(CLIError::MissingSaveFile);
+        }
+        if options.encrypt && options.encrypt_to.is_some() {
+            return Err(CLIError::ConflictingEncryption);
+        }
+
+        options.sanitize_inputs()?;
+
         Ok(options)
     }
 
@@ -780,123 +3785,742 @@ impl CLI for BitcoinCLI {
     #[cfg_attr(tarpaulin, skip)]
     fn print(options: Self::Options) -> Result<(), CLIError> {
         fn output<N: BitcoinNetwork, W: BitcoinWordlist>(options: BitcoinOptions) -> Result<(), CLIError> {
-            let wallets =
-                match options.subcommand.as_ref().map(String::as_str) {
-                    Some("hd") => match options.to_derivation_path(true) {
-                        Some(path) => (0..options.count)
-                            .flat_map(|_| {
-                                match BitcoinWallet::new_hd::<N, W, _>(
-                                    &mut StdRng::from_entropy(),
-                                    options.word_count,
-                                    options.password.as_ref().map(String::as_str),
-                                    &path,
-                                ) {
-                                    Ok(wallet) => vec![wallet],
-                                    _ => vec![],
-                                }
-                            })
-                            .collect(),
-                        None => vec![],
+            // Import each key of a Bitcoin Core wallet dump, verified against its addresses and path
+            if let (Some("import"), Some(path)) = (options.subcommand.as_deref(), &options.dumpfile) {
+                let keys = read_wallet_dump(path)?;
+                print_wallets(&keys, options.json, options.mask_secrets && !options.reveal)?;
+                return AuditLog::log_wallets("bitcoin import dumpfile", &keys);
+            }
+
+            // Recover the private keys of a legacy wallet.dat, decrypted with the wallet passphrase
+            if let (Some("recover"), Some(path)) = (options.subcommand.as_deref(), &options.wallet_dat) {
+                let keys = read_wallet_dat::<N>(path, options.passphrase.as_deref())?;
+                print_wallets(&keys, options.json, options.mask_secrets && !options.reveal)?;
+                return AuditLog::log_wallets("bitcoin recover", &keys);
+            }
+
+            // Import the seed, extended keys, and addresses of an Electrum wallet file
+            if let (Some("import"), Some(path)) = (options.subcommand.as_deref(), &options.electrum_wallet) {
+                let wallet = ElectrumWallet::read(path)?;
+                print_wallets(
+                    std::slice::from_ref(&wallet),
+                    options.json,
+                    options.mask_secrets && !options.reveal,
+                )?;
+                return AuditLog::log_wallets("bitcoin import electrum", std::slice::from_ref(&wallet));
+            }
+
+            // Derive the addresses of an extended public key as a batch, resumable from its checkpoint file
+            if let (Some("derive-addresses"), Some(extended_public_key)) =
+                (options.subcommand.as_deref(), &options.extended_public_key)
+            {
+                fn derive<SN: BitcoinNetwork>(
+                    options: &BitcoinOptions,
+                    extended_public_key: &str,
+                ) -> Result<AddressBatch, CLIError> {
+                    let mut parent_key = BitcoinExtendedPublicKey::<SN>::from_str(extended_public_key)?;
+                    warn_format_conflict(Some(parent_key.format()), &options.extended_format);
+                    if let Some(format) = &options.extended_format {
+                        parent_key = parent_key.with_format(format);
+                    }
+                    let path = options.path.clone().unwrap_or_else(|| "m".into());
+                    let derivation_path = BitcoinDerivationPath::<SN>::from_str(&path)?;
+                    derivation_path.non_hardened_only()?;
+                    parent_key = parent_key.derive(&derivation_path)?;
+                    let format = parent_key.format();
+
+                    // The last index is checked before the batch, instead of failing after its earlier chunks
+                    let count = u32::try_from(options.count).unwrap_or(u32::MAX);
+                    if count > 0 {
+                        ChildIndex::normal(count - 1)?;
+                    }
+
+                    let mut batch = AddressBatch {
+                        extended_public_key: extended_public_key.to_string(),
+                        path: path.trim_end_matches('/').to_string(),
+                        format: format.to_string(),
+                        count,
+                        next_index: options.resume_from.unwrap_or_default(),
+                        output_length: None,
+                    };
+                    derive_address_batch(
+                        &mut batch,
+                        options.resume_from.is_some(),
+                        options.checkpoint.as_deref(),
+                        options.output.as_deref(),
+                        options.chunk_size,
+                        options.threads,
+                        |indices| {
+                            let public_keys = BitcoinWallet::derive_children(&parent_key, indices)?;
+                            let addresses = BitcoinAddress::from_public_keys(&public_keys, &format)?;
+                            Ok(addresses.iter().map(|address| address.to_string()).collect())
+                        },
+                    )?;
+                    Ok(batch)
+                }
+
+                let batch = match BitcoinExtendedPublicKey::<BitcoinMainnet>::from_str(extended_public_key).is_ok() {
+                    true => derive::<BitcoinMainnet>(&options, extended_public_key)?,
+                    false => derive::<BitcoinTestnet>(&options, extended_public_key)?,
+                };
+                return AuditLog::log_wallets("bitcoin derive-addresses", std::slice::from_ref(&batch));
+            }
+
+            // Print the path of an address of a mnemonic or master extended private key, searched on its own network
+            if let (Some("discover-paths"), Some(address)) = (options.subcommand.as_deref(), &options.address) {
+                fn discover<SN: BitcoinNetwork, W: BitcoinWordlist>(
+                    options: &BitcoinOptions,
+                    address: &str,
+                ) -> Result<BitcoinDiscoveredPath, CLIError> {
+                    BitcoinWallet::discover_paths::<SN, W>(
+                        options.mnemonic.as_deref(),
+                        options.extended_private_key.as_deref(),
+                        options.password.as_deref(),
+                        address,
+                        &options.account_range,
+                        options.search_depth,
+                        options.threads,
+                    )
+                }
+
+                let discovered = match BitcoinAddress::<BitcoinMainnet>::from_str(address).is_ok() {
+                    true => discover::<BitcoinMainnet, W>(&options, address)?,
+                    false => discover::<BitcoinTestnet, W>(&options, address)?,
+                };
+                print_wallets(std::slice::from_ref(&discovered), options.json, false)?;
+                return AuditLog::log_wallets("bitcoin discover-paths", std::slice::from_ref(&discovered));
+            }
+
+            // Import each entry of a batch file as a private key, public key, or address
+            if let (Some("import"), Some(path)) = (options.subcommand.as_deref(), &options.file) {
+                let entries = read_batch_file(path, options.column.as_deref())?;
+                return import_batch(
+                    "bitcoin import",
+                    &entries,
+                    options.mask_secrets && !options.reveal,
+                    |entry| {
+                        BitcoinWallet::from_private_key::<BitcoinMainnet>(entry, &options.format)
+                            .or_else(|_| BitcoinWallet::from_private_key::<BitcoinTestnet>(entry, &options.format))
+                            .or_else(|_| BitcoinWallet::from_public_key::<N>(entry, &options.format))
+                            .or_else(|_| BitcoinWallet::from_address::<BitcoinMainnet>(entry))
+                            .or_else(|_| BitcoinWallet::from_address::<BitcoinTestnet>(entry))
                     },
-                    Some("import") => {
-                        if let Some(private_key) = options.private {
-                            vec![
-                                BitcoinWallet::from_private_key::<BitcoinMainnet>(&private_key, &options.format).or(
-                                    BitcoinWallet::from_private_key::<BitcoinTestnet>(&private_key, &options.format),
-                                )?,
-                            ]
-                        } else if let Some(public_key) = options.public {
-                            vec![BitcoinWallet::from_public_key::<N>(&public_key, &options.format)?]
-                        } else if let Some(address) = options.address {
-                            vec![BitcoinWallet::from_address::<BitcoinMainnet>(&address)
-                                .or(BitcoinWallet::from_address::<BitcoinTestnet>(&address))?]
-                        } else {
-                            vec![]
+                );
+            }
+
+            // Sign each prepared transaction of a batch file with the key derived at its derivation path
+            if let (Some("sign-batch"), Some(path), Some(key)) = (
+                options.subcommand.as_deref(),
+                &options.file,
+                &options.extended_private_key,
+            ) {
+                let entries = read_transaction_batch_file::<BitcoinBatchTransaction>(path)?;
+                return match BitcoinExtendedPrivateKey::<BitcoinMainnet>::from_str(key) {
+                    Ok(_) => sign_batch("bitcoin sign-batch", &entries, |entry| {
+                        BitcoinWallet::to_signed_batch_transaction::<BitcoinMainnet>(key, entry)
+                    }),
+                    Err(_) => sign_batch("bitcoin sign-batch", &entries, |entry| {
+                        BitcoinWallet::to_signed_batch_transaction::<BitcoinTestnet>(key, entry)
+                    }),
+                };
+            }
+
+            // Print the accounts, chains, and addresses of an HD wallet as a tree
+            if let (Some("hd"), true) = (options.subcommand.as_deref(), options.tree) {
+                let tree = BitcoinWallet::new_hd_tree::<N, W, _>(
+                    &mut StdRng::from_entropy(),
+                    options.mnemonic.as_deref(),
+                    options.word_count,
+                    options.password.as_deref(),
+                    options.purpose.unwrap_or(44),
+                    options.depth,
+                    options.breadth,
+                )?;
+                print_wallets(
+                    std::slice::from_ref(&tree),
+                    options.json,
+                    options.mask_secrets && !options.reveal,
+                )?;
+                return AuditLog::log_wallets("bitcoin hd tree", &tree.tree.to_addresses());
+            }
+
+            // Print the entropy grid of a Border Wallet, with the mnemonic of a pattern of its cells
+            if let Some("entropy-grid") = options.subcommand.as_deref() {
+                let grid = match (&options.entropy, &options.mnemonic) {
+                    (Some(entropy), _) => EntropyGrid::from_entropy(&hex::decode(entropy)?)?,
+                    (None, Some(mnemonic)) => EntropyGrid::from_mnemonic(mnemonic)?,
+                    (None, None) => EntropyGrid::new(&mut StdRng::from_entropy())?,
+                };
+                let grid = match &options.pattern {
+                    Some(pattern) => grid.with_pattern(pattern)?,
+                    None => grid,
+                };
+                if let Some(path) = &options.pdf {
+                    std::fs::write(path, grid.to_pdf())?;
+                }
+                print_wallets(
+                    std::slice::from_ref(&grid),
+                    options.json,
+                    options.mask_secrets && !options.reveal,
+                )?;
+                return AuditLog::log_wallets("bitcoin entropy-grid", &[&grid]);
+            }
+
+            // Write the Coldcard export file of an account of an HD wallet, from its master key
+            if let (Some("import-hd"), Some(format)) = (options.subcommand.as_deref(), &options.export_format) {
+                if format.starts_with("coldcard") {
+                    let master_extended_private_key = match (&options.mnemonic, &options.extended_private_key) {
+                        (Some(mnemonic), _) => {
+                            let password = options.password.as_deref();
+                            BitcoinWallet::to_master_extended_private_key::<N, CustomWordlist>(mnemonic, password)
+                                .or(BitcoinWallet::to_master_extended_private_key::<N, ChineseSimplified>(
+                                    mnemonic, password,
+                                ))
+                                .or(BitcoinWallet::to_master_extended_private_key::<N, ChineseTraditional>(
+                                    mnemonic, password,
+                                ))
+                                .or(BitcoinWallet::to_master_extended_private_key::<N, English>(
+                                    mnemonic, password,
+                                ))
+                                .or(BitcoinWallet::to_master_extended_private_key::<N, French>(
+                                    mnemonic, password,
+                                ))
+                                .or(BitcoinWallet::to_master_extended_private_key::<N, Italian>(
+                                    mnemonic, password,
+                                ))
+                                .or(BitcoinWallet::to_master_extended_private_key::<N, Japanese>(
+                                    mnemonic, password,
+                                ))
+                                .or(BitcoinWallet::to_master_extended_private_key::<N, Korean>(
+                                    mnemonic, password,
+                                ))
+                                .or(BitcoinWallet::to_master_extended_private_key::<N, Spanish>(
+                                    mnemonic, password,
+                                ))?
+                        }
+                        (None, Some(extended_private_key)) => {
+                            BitcoinExtendedPrivateKey::<N>::from_str(extended_private_key)?
                         }
+                        (None, None) => return Err(CLIError::MissingMasterKey("a Coldcard export".into())),
+                    };
+                    let export = ColdcardExport::new(format, &master_extended_private_key, options.account)?;
+                    std::fs::write(options.export_file.as_deref().unwrap_or_default(), &export.contents)?;
+                    print_wallets(std::slice::from_ref(&export), options.json, false)?;
+                    return AuditLog::log_wallets("bitcoin import-hd coldcard", &[&export]);
+                }
+            }
+
+            // Write the watch-only wallet file of the account of an HD wallet, for the first derivation path
+            if let (Some("import-hd"), Some(wallet)) = (options.subcommand.as_deref(), &options.export_format) {
+                let path = options.to_derivation_paths(true)?.remove(0).unwrap_or_default();
+                let (master_fingerprint, account_path, account_key) = match (
+                    &options.mnemonic,
+                    &options.extended_private_key,
+                    &options.extended_public_key,
+                ) {
+                    (Some(mnemonic), _, _) => {
+                        let password = options.password.as_deref();
+                        BitcoinWallet::to_account::<N, CustomWordlist>(mnemonic, password, &path)
+                            .or(BitcoinWallet::to_account::<N, ChineseSimplified>(
+                                mnemonic, password, &path,
+                            ))
+                            .or(BitcoinWallet::to_account::<N, ChineseTraditional>(
+                                mnemonic, password, &path,
+                            ))
+                            .or(BitcoinWallet::to_account::<N, English>(mnemonic, password, &path))
+                            .or(BitcoinWallet::to_account::<N, French>(mnemonic, password, &path))
+                            .or(BitcoinWallet::to_account::<N, Italian>(mnemonic, password, &path))
+                            .or(BitcoinWallet::to_account::<N, Japanese>(mnemonic, password, &path))
+                            .or(BitcoinWallet::to_account::<N, Korean>(mnemonic, password, &path))
+                            .or(BitcoinWallet::to_account::<N, Spanish>(mnemonic, password, &path))?
                     }
-                    Some("import-hd") => {
-                        if let Some(mnemonic) = options.mnemonic.clone() {
-                            let password = &options.password.as_ref().map(String::as_str);
-
-                            match options.to_derivation_path(true) {
-                                Some(path) => vec![BitcoinWallet::from_mnemonic::<N, ChineseSimplified>(
-                                    &mnemonic, password, &path,
-                                )
-                                .or(BitcoinWallet::from_mnemonic::<N, ChineseTraditional>(
-                                    &mnemonic, password, &path,
-                                ))
-                                .or(BitcoinWallet::from_mnemonic::<N, English>(&mnemonic, password, &path))
-                                .or(BitcoinWallet::from_mnemonic::<N, French>(&mnemonic, password, &path))
-                                .or(BitcoinWallet::from_mnemonic::<N, Italian>(&mnemonic, password, &path))
-                                .or(BitcoinWallet::from_mnemonic::<N, Japanese>(&mnemonic, password, &path))
-                                .or(BitcoinWallet::from_mnemonic::<N, Korean>(&mnemonic, password, &path))
-                                .or(BitcoinWallet::from_mnemonic::<N, Spanish>(&mnemonic, password, &path))?],
-                                None => vec![],
+                    (None, Some(extended_private_key), _) => BitcoinWallet::to_account_from_extended_private_key(
+                        &BitcoinExtendedPrivateKey::<N>::from_str(extended_private_key)?,
+                        &path,
+                    )?,
+                    (None, None, Some(extended_public_key)) => BitcoinWallet::to_account_from_extended_public_key::<N>(
+                        extended_public_key,
+                        &options.extended_format,
+                        options
+                            .master_fingerprint
+                            .as_deref()
+                            .ok_or(CLIError::MissingMasterFingerprint)?,
+                        &path,
+                    )?,
+                    (None, None, None) => return Ok(()),
+                };
+                let watch_only = WatchOnlyWallet::new(wallet, &master_fingerprint, &account_path, &account_key)?;
+                std::fs::write(options.export_file.as_deref().unwrap_or_default(), &watch_only.contents)?;
+                print_wallets(std::slice::from_ref(&watch_only), options.json, false)?;
+                return AuditLog::log_wallets("bitcoin import-hd watch-only", &[&watch_only]);
+            }
+
+            // Print only the identifying data of an HD wallet, for the first derivation path
+            if let (Some("import-hd"), true) = (options.subcommand.as_deref(), options.summary) {
+                let mnemonic = options.mnemonic.as_deref().unwrap_or_default();
+                let password = options.password.as_deref();
+                let path = options.to_derivation_paths(true)?.remove(0).unwrap_or_default();
+                let count = options.summary_count;
+                let summary = BitcoinWallet::to_summary::<N, CustomWordlist>(mnemonic, password, &path, count)
+                    .or(BitcoinWallet::to_summary::<N, ChineseSimplified>(
+                        mnemonic, password, &path, count,
+                    ))
+                    .or(BitcoinWallet::to_summary::<N, ChineseTraditional>(
+                        mnemonic, password, &path, count,
+                    ))
+                    .or(BitcoinWallet::to_summary::<N, English>(
+                        mnemonic, password, &path, count,
+                    ))
+                    .or(BitcoinWallet::to_summary::<N, French>(mnemonic, password, &path, count))
+                    .or(BitcoinWallet::to_summary::<N, Italian>(
+                        mnemonic, password, &path, count,
+                    ))
+                    .or(BitcoinWallet::to_summary::<N, Japanese>(
+                        mnemonic, password, &path, count,
+                    ))
+                    .or(BitcoinWallet::to_summary::<N, Korean>(mnemonic, password, &path, count))
+                    .or(BitcoinWallet::to_summary::<N, Spanish>(
+                        mnemonic, password, &path, count,
+                    ))?;
+                print_wallets(std::slice::from_ref(&summary), options.json, false)?;
+                return AuditLog::log_wallets("bitcoin import-hd summary", &summary.addresses);
+            }
+
+            let wallets = match options.subcommand.as_ref().map(String::as_str) {
+                Some("bump-fee") => match (
+                    &options.transaction_hex,
+                    &options.transaction_inputs,
+                    &options.private,
+                    options.new_feerate,
+                ) {
+                    (Some(transaction_hex), Some(transaction_inputs), Some(private_keys), Some(fee_rate)) => {
+                        let inputs: &Vec<BitcoinInput> = &from_str(transaction_inputs)?;
+                        let change_output = options.change_output;
+
+                        // The transaction is bumped on the network of its private keys
+                        let private_key = private_keys.split(',').next().unwrap_or_default().trim();
+                        match BitcoinPrivateKey::<BitcoinMainnet>::from_str(private_key).is_ok() {
+                            true => vec![BitcoinWallet::to_bumped_transaction::<BitcoinMainnet>(
+                                transaction_hex,
+                                inputs,
+                                private_keys,
+                                fee_rate,
+                                change_output,
+                            )?],
+                            false => vec![BitcoinWallet::to_bumped_transaction::<BitcoinTestnet>(
+                                transaction_hex,
+                                inputs,
+                                private_keys,
+                                fee_rate,
+                                change_output,
+                            )?],
+                        }
+                    }
+                    _ => vec![],
+                },
+                Some("contains") => {
+                    // The address is searched for on its own network
+                    fn search<SN: BitcoinNetwork, W: BitcoinWordlist>(
+                        options: &BitcoinOptions,
+                        address: &str,
+                    ) -> Result<BitcoinWallet, CLIError> {
+                        let depth = options.search_depth;
+                        match (&options.extended_public_key, &options.mnemonic) {
+                            (Some(xpub), _) => BitcoinWallet::find_in_extended_public_key::<SN>(xpub, address, depth),
+                            (None, Some(mnemonic)) => {
+                                let password = &options.password.as_deref();
+                                BitcoinWallet::find_in_mnemonic::<SN, W>(mnemonic, password, address, depth)
                             }
-                        } else if let Some(extended_private_key) = options.extended_private_key.clone() {
-                            let key = &extended_private_key;
-                            let path = &options.to_derivation_path(false);
-
-                            vec![BitcoinWallet::from_extended_private_key::<BitcoinMainnet>(key, path)
-                                .or(BitcoinWallet::from_extended_private_key::<BitcoinTestnet>(key, path))?]
-                        } else if let Some(extended_public_key) = options.extended_public_key.clone() {
-                            let key = &extended_public_key;
-                            let path = &options.to_derivation_path(false);
-
-                            vec![BitcoinWallet::from_extended_public_key::<BitcoinMainnet>(key, path)
-                                .or(BitcoinWallet::from_extended_public_key::<BitcoinTestnet>(key, path))?]
-                        } else {
-                            vec![]
+                            (None, None) => Err(CLIError::MissingSearchKey),
                         }
                     }
-                    Some("transaction") => {
-                        if let (Some(transaction_inputs), Some(transaction_outputs)) =
-                            (options.transaction_inputs.clone(), options.transaction_outputs.clone())
-                        {
-                            let inputs: &Vec<BitcoinInput> = &from_str(&transaction_inputs)?;
-                            let outputs = transaction_outputs.replace(&['{', '}', '"', ' '][..], "");
-                            let outputs: &Vec<&str> = &outputs.split(",").collect();
-                            let version = options.version.unwrap_or(1);
-                            let lock_time = options.lock_time.unwrap_or(0);
-
-                            vec![BitcoinWallet::to_raw_transaction::<BitcoinMainnet>(
-                                inputs, outputs, version, lock_time,
+
+                    let address = options.address.clone().unwrap_or_default();
+                    match BitcoinAddress::<BitcoinMainnet>::from_str(&address).is_ok() {
+                        true => vec![search::<BitcoinMainnet, W>(&options, &address)?],
+                        false => vec![search::<BitcoinTestnet, W>(&options, &address)?],
+                    }
+                }
+                Some("create-tx") => match (
+                    options.transaction_inputs.clone(),
+                    options.transaction_outputs.clone(),
+                    options.unsigned_transaction.clone(),
+                ) {
+                    (Some(transaction_inputs), Some(transaction_outputs), Some(path)) => {
+                        let inputs: &Vec<BitcoinInput> = &from_str(&transaction_inputs)?;
+                        let outputs = transaction_outputs.replace(&['{', '}', '"', ' '][..], "");
+                        let outputs: &Vec<&str> = &outputs.split(",").collect();
+                        let version = options.version.unwrap_or(1);
+                        let lock_time = options.lock_time.unwrap_or(0);
+                        let op_return = options.to_op_return()?;
+                        let op_return = op_return.as_deref();
+
+                        check_dust(outputs, options.dust_limit, options.allow_dust)?;
+                        check_fee(&inputs.iter().map(|input| input.amount).collect::<Vec<_>>(), outputs);
+
+                        vec![BitcoinWallet::to_unsigned_transaction_file::<BitcoinMainnet>(
+                            inputs, outputs, op_return, version, lock_time, &path,
+                        )
+                        .or(BitcoinWallet::to_unsigned_transaction_file::<BitcoinTestnet>(
+                            inputs, outputs, op_return, version, lock_time, &path,
+                        ))?]
+                    }
+                    _ => vec![],
+                },
+                Some("hd") => match options.to_derivation_paths(true)?.remove(0) {
+                    Some(path) => (0..options.count)
+                        .map(|_| {
+                            BitcoinWallet::new_hd::<N, W, _>(
+                                &mut StdRng::from_entropy(),
+                                options.word_count,
+                                options.password.as_ref().map(String::as_str),
+                                &path,
                             )
-                            .or(BitcoinWallet::to_raw_transaction::<BitcoinTestnet>(
-                                inputs, outputs, version, lock_time,
-                            ))?]
-                        } else if let (Some(transaction_hex), Some(transaction_inputs)) =
-                            (options.transaction_hex.clone(), options.transaction_inputs.clone())
-                        {
-                            let inputs: &Vec<BitcoinInput> = &from_str(&transaction_inputs)?;
-
-                            vec![
-                                BitcoinWallet::to_signed_transaction::<BitcoinMainnet>(&transaction_hex, inputs).or(
-                                    BitcoinWallet::to_signed_transaction::<BitcoinTestnet>(&transaction_hex, inputs),
+                        })
+                        .collect::<Result<Vec<_>, CLIError>>()?,
+                    None => vec![],
+                },
+                Some("htlc") => match (
+                    &options.recipient_pubkey,
+                    &options.refund_pubkey,
+                    &options.hash,
+                    options.to_timelock()?,
+                ) {
+                    (Some(recipient), Some(refund), Some(hash), Some(timelock)) => {
+                        vec![BitcoinWallet::to_htlc::<N>(recipient, refund, hash, &timelock)?]
+                    }
+                    _ => vec![],
+                },
+                Some("import") => {
+                    let leaves = &options.script_leaves;
+                    if let Some(private_key) = options.private {
+                        let wallet =
+                            BitcoinWallet::from_private_key::<BitcoinMainnet>(&private_key, &options.format).or(
+                                BitcoinWallet::from_private_key::<BitcoinTestnet>(&private_key, &options.format),
+                            )?;
+                        // The script tree is built for the network of the private key
+                        match wallet.network.as_deref() == Some(BitcoinTestnet::NAME) {
+                            true => vec![wallet.with_script_tree::<BitcoinTestnet>(leaves)?],
+                            false => vec![wallet.with_script_tree::<BitcoinMainnet>(leaves)?],
+                        }
+                    } else if let Some(public_key) = options.public {
+                        vec![BitcoinWallet::from_public_key::<N>(&public_key, &options.format)?
+                            .with_script_tree::<N>(leaves)?]
+                    } else if let Some(address) = options.address {
+                        vec![BitcoinWallet::from_address::<BitcoinMainnet>(&address)
+                            .or(BitcoinWallet::from_address::<BitcoinTestnet>(&address))?]
+                    } else {
+                        vec![]
+                    }
+                }
+                Some("import-hd") => {
+                    // Generate a wallet for each derivation path, from the expanded path template if specified
+                    if let Some(mnemonic) = options.mnemonic.clone() {
+                        let password = &options.password.as_ref().map(String::as_str);
+
+                        let mut wallets = vec![];
+                        for path in options.to_derivation_paths(true)?.iter().flatten() {
+                            wallets.push(
+                                BitcoinWallet::from_mnemonic::<N, CustomWordlist>(&mnemonic, password, path)
+                                    .or(BitcoinWallet::from_mnemonic::<N, ChineseSimplified>(
+                                        &mnemonic, password, path,
+                                    ))
+                                    .or(BitcoinWallet::from_mnemonic::<N, ChineseTraditional>(
+                                        &mnemonic, password, path,
+                                    ))
+                                    .or(BitcoinWallet::from_mnemonic::<N, English>(&mnemonic, password, path))
+                                    .or(BitcoinWallet::from_mnemonic::<N, French>(&mnemonic, password, path))
+                                    .or(BitcoinWallet::from_mnemonic::<N, Italian>(&mnemonic, password, path))
+                                    .or(BitcoinWallet::from_mnemonic::<N, Japanese>(&mnemonic, password, path))
+                                    .or(BitcoinWallet::from_mnemonic::<N, Korean>(&mnemonic, password, path))
+                                    .or(BitcoinWallet::from_mnemonic::<N, Spanish>(&mnemonic, password, path))?,
+                            );
+                        }
+                        wallets
+                    } else if let Some(extended_private_key) = options.extended_private_key.clone() {
+                        let key = &extended_private_key;
+                        let format = &options.extended_format;
+                        let inferred_format = BitcoinExtendedPrivateKey::<BitcoinMainnet>::from_str(key)
+                            .map(|key| key.format())
+                            .or(BitcoinExtendedPrivateKey::<BitcoinTestnet>::from_str(key).map(|key| key.format()));
+                        warn_format_conflict(inferred_format.ok(), format);
+
+                        let mut wallets = vec![];
+                        for path in options.to_derivation_paths(false)?.iter() {
+                            wallets.push(
+                                BitcoinWallet::from_extended_private_key::<BitcoinMainnet>(key, path, format).or(
+                                    BitcoinWallet::from_extended_private_key::<BitcoinTestnet>(key, path, format),
                                 )?,
-                            ]
-                        } else {
-                            vec![]
+                            );
                         }
+                        wallets
+                    } else if let Some(extended_public_key) = options.extended_public_key.clone() {
+                        let key = &extended_public_key;
+                        let format = &options.extended_format;
+                        let inferred_format = BitcoinExtendedPublicKey::<BitcoinMainnet>::from_str(key)
+                            .map(|key| key.format())
+                            .or(BitcoinExtendedPublicKey::<BitcoinTestnet>::from_str(key).map(|key| key.format()));
+                        warn_format_conflict(inferred_format.ok(), format);
+
+                        let mut wallets = vec![];
+                        for path in options.to_derivation_paths(false)?.iter() {
+                            // Hardened paths are rejected before the network fallback can mask the error
+                            if let Some(path) = path {
+                                BitcoinDerivationPath::<BitcoinMainnet>::from_str(path)?.non_hardened_only()?;
+                            }
+
+                            wallets.push(
+                                BitcoinWallet::from_extended_public_key::<BitcoinMainnet>(key, path, format).or(
+                                    BitcoinWallet::from_extended_public_key::<BitcoinTestnet>(key, path, format),
+                                )?,
+                            );
+                        }
+                        wallets
+                    } else {
+                        vec![]
                     }
-                    _ => (0..options.count)
-                        .flat_map(
-                            |_| match BitcoinWallet::new::<N, _>(&mut StdRng::from_entropy(), &options.format) {
-                                Ok(wallet) => vec![wallet],
-                                _ => vec![],
-                            },
+                }
+                Some("frost") => match (&options.key_package, &options.secret_package, &options.packages) {
+                    (Some(key_package), _, _) => vec![BitcoinWallet::to_frost_signature::<N>(
+                        key_package,
+                        options.message.as_deref(),
+                        options.nonces.as_deref(),
+                        options.secret_nonce_file.as_deref(),
+                        options.partial_signatures.as_deref(),
+                    )?],
+                    (None, Some(secret_package), Some(packages)) => vec![BitcoinWallet::to_frost_shares::<N>(
+                        secret_package,
+                        packages,
+                        options.shares.as_deref(),
+                    )?],
+                    _ => match (options.identifier, options.threshold, options.participants) {
+                        (Some(identifier), Some(threshold), Some(participants)) => {
+                            vec![BitcoinWallet::to_frost_packages(identifier, threshold, participants)?]
+                        }
+                        _ => vec![],
+                    },
+                },
+                Some("musig") => match &options.public_keys {
+                    Some(public_keys) => vec![BitcoinWallet::to_musig::<N>(
+                        public_keys,
+                        options.message.as_deref(),
+                        options.private.as_deref(),
+                        options.nonces.as_deref(),
+                        options.secret_nonce_file.as_deref(),
+                        options.partial_signatures.as_deref(),
+                    )?],
+                    None => vec![],
+                },
+                Some("sign-message") => match (options.private.clone(), options.message.clone()) {
+                    (Some(private_key), Some(message)) => {
+                        let proof = options.proof.as_deref();
+                        vec![BitcoinWallet::to_signed_message::<BitcoinMainnet>(
+                            &private_key,
+                            &options.format,
+                            &message,
+                            proof,
                         )
-                        .collect(),
-                };
+                        .or(BitcoinWallet::to_signed_message::<BitcoinTestnet>(
+                            &private_key,
+                            &options.format,
+                            &message,
+                            proof,
+                        ))?]
+                    }
+                    _ => vec![],
+                },
+                Some("sign-tx") => match (options.unsigned_transaction.clone(), options.private.clone()) {
+                    (Some(path), Some(private_keys)) => {
+                        let file = UnsignedTransactionFile::<Vec<BitcoinInput>>::read(&path, "bitcoin")?;
+
+                        match file.network.as_str() {
+                            BitcoinMainnet::NAME => vec![BitcoinWallet::from_unsigned_transaction_file::<
+                                BitcoinMainnet,
+                            >(&file, &private_keys)?],
+                            BitcoinTestnet::NAME => vec![BitcoinWallet::from_unsigned_transaction_file::<
+                                BitcoinTestnet,
+                            >(&file, &private_keys)?],
+                            network => return Err(CLIError::UnsupportedNetwork(network.to_string())),
+                        }
+                    }
+                    _ => vec![],
+                },
+                Some("miniscript") => match &options.policy {
+                    Some(policy) => vec![BitcoinWallet::to_miniscript::<N>(policy, &options.to_keys()?)?],
+                    _ => vec![],
+                },
+                Some("sweep") => match (&options.private, &options.to) {
+                    (Some(private_key), Some(to)) => {
+                        let rpc_url = options.to_rpc_url().ok_or(CLIError::MissingRpcUrl)?;
+
+                        // The funds are swept on the network of the private key
+                        fn sweep<SN: BitcoinNetwork>(
+                            options: &BitcoinOptions,
+                            rpc_url: &str,
+                            private_key: &str,
+                            to: &str,
+                        ) -> Result<BitcoinWallet, CLIError> {
+                            let unspents = to_unspents::<SN>(rpc_url, private_key)?;
+                            let fee_rate = match options.fee_rate {
+                                Some(fee_rate) => fee_rate,
+                                None => to_estimated_fee_rate(rpc_url)?,
+                            };
+                            let wallet =
+                                BitcoinWallet::to_swept_transaction::<SN>(private_key, &unspents, to, fee_rate)?;
+
+                            if options.broadcast {
+                                let transaction_hex = wallet.transaction_hex.clone().unwrap_or_default();
+                                let transaction_id = rpc_call(rpc_url, "sendrawtransaction", json!([transaction_hex]))?;
+                                eprintln!("Broadcast transaction {}", transaction_id.as_str().unwrap_or_default());
+                            }
+                            Ok(wallet)
+                        }
 
-            match options.json {
-                true => println!("{}\n", serde_json::to_string_pretty(&wallets)?),
-                false => wallets.iter().for_each(|wallet| println!("{}\n", wallet)),
+                        match BitcoinPrivateKey::<BitcoinMainnet>::from_str(private_key).is_ok() {
+                            true => vec![sweep::<BitcoinMainnet>(&options, &rpc_url, private_key, to)?],
+                            false => vec![sweep::<BitcoinTestnet>(&options, &rpc_url, private_key, to)?],
+                        }
+                    }
+                    _ => vec![],
+                },
+                Some("timelock") => match (&options.public, options.to_timelock()?) {
+                    (Some(public_key), Some(timelock)) => vec![BitcoinWallet::to_timelock::<N>(public_key, &timelock)?],
+                    _ => vec![],
+                },
+                Some("transaction") => {
+                    if let (Some(transaction_inputs), Some(transaction_outputs)) =
+                        (options.transaction_inputs.clone(), options.transaction_outputs.clone())
+                    {
+                        let inputs: &Vec<BitcoinInput> = &from_str(&transaction_inputs)?;
+                        let outputs = transaction_outputs.replace(&['{', '}', '"', ' '][..], "");
+                        let outputs: &Vec<&str> = &outputs.split(",").collect();
+                        let version = options.version.unwrap_or(1);
+                        let lock_time = options.lock_time.unwrap_or(0);
+                        let op_return = options.to_op_return()?;
+                        let op_return = op_return.as_deref();
+
+                        check_dust(outputs, options.dust_limit, options.allow_dust)?;
+                        check_fee(&inputs.iter().map(|input| input.amount).collect::<Vec<_>>(), outputs);
+
+                        vec![BitcoinWallet::to_raw_transaction::<BitcoinMainnet>(
+                            inputs, outputs, op_return, version, lock_time,
+                        )
+                        .or(BitcoinWallet::to_raw_transaction::<BitcoinTestnet>(
+                            inputs, outputs, op_return, version, lock_time,
+                        ))?]
+                    } else if let (Some(transaction_hex), Some(transaction_inputs)) =
+                        (options.transaction_hex.clone(), options.transaction_inputs.clone())
+                    {
+                        let inputs: &Vec<BitcoinInput> = &from_str(&transaction_inputs)?;
+
+                        vec![
+                            BitcoinWallet::to_signed_transaction::<BitcoinMainnet>(&transaction_hex, inputs).or(
+                                BitcoinWallet::to_signed_transaction::<BitcoinTestnet>(&transaction_hex, inputs),
+                            )?,
+                        ]
+                    } else {
+                        vec![]
+                    }
+                }
+                Some("verify-message") => match (
+                    options.address.clone(),
+                    options.message.clone(),
+                    options.signature.clone(),
+                ) {
+                    (Some(address), Some(message), Some(signature)) => {
+                        vec![
+                            BitcoinWallet::from_signed_message::<BitcoinMainnet>(&address, &message, &signature).or(
+                                BitcoinWallet::from_signed_message::<BitcoinTestnet>(&address, &message, &signature),
+                            )?,
+                        ]
+                    }
+                    _ => vec![],
+                },
+                Some("xpub") => match options.extended_private_key.clone() {
+                    Some(extended_private_key) => {
+                        let key = &extended_private_key;
+                        let path = &options.path;
+
+                        vec![BitcoinWallet::to_extended_public_key::<BitcoinMainnet>(key, path)
+                            .or(BitcoinWallet::to_extended_public_key::<BitcoinTestnet>(key, path))?]
+                    }
+                    None => vec![],
+                },
+                _ => (0..options.count)
+                    .flat_map(
+                        |_| match BitcoinWallet::new::<N, _>(&mut StdRng::from_entropy(), &options.format) {
+                            Ok(wallet) => vec![wallet],
+                            _ => vec![],
+                        },
+                    )
+                    .collect(),
             };
 
+            // The wallets of a duress PIN document the derivation of their decoy account
+            let wallets = match options.duress.as_deref().map(duress_account) {
+                Some(account) => wallets
+                    .into_iter()
+                    .map(|wallet| BitcoinWallet {
+                        duress: Some(format!(
+                            "decoy account {}' of the duress PIN (the first SLIP-13 index of duress://<PIN>)",
+                            account
+                        )),
+                        ..wallet
+                    })
+                    .collect(),
+                None => wallets,
+            };
+
+            if options.confirm_backup {
+                confirm_backups(&wallets)?;
+            }
+
+            match &options.encrypt_to {
+                Some(recipients) => {
+                    let recipients = Recipient::from_list(recipients)?;
+                    print!(
+                        "{}",
+                        encrypt_to(serde_json::to_string_pretty(&wallets)?.as_bytes(), &recipients)?
+                    );
+                    if let Some(path) = &options.save {
+                        let file = WalletFile::new(&wallets, "bitcoin")?;
+                        std::fs::write(
+                            path,
+                            encrypt_to(serde_json::to_string_pretty(&file)?.as_bytes(), &recipients)?,
+                        )?;
+                    }
+                }
+                None => {
+                    print_wallets(&wallets, options.json, options.mask_secrets && !options.reveal)?;
+                    if let Some(path) = &options.save {
+                        WalletFile::save(&wallets, "bitcoin", path, options.encrypt)?;
+                    }
+                }
+            };
+
+            if let Some(path) = &options.manifest {
+                Manifest::new(&wallets, "bitcoin")?.write(path)?;
+            }
+
+            if let Some(field) = &options.copy {
+                copy_wallets(&wallets, field, options.clear_after)?;
+            }
+
+            let operation = format!("bitcoin {}", options.subcommand.as_deref().unwrap_or("generate"));
+            AuditLog::log_wallets(&operation, &wallets)?;
+
             Ok(())
         }
 
+        // A custom wordlist is loaded from its file, and used in place of the compiled-in languages
+        if let Some(path) = &options.wordlist_file {
+            CustomWordlist::load(path)?;
+            return match options.network.as_str() {
+                "testnet" => output::<BitcoinTestnet, CustomWordlist>(options),
+                _ => output::<BitcoinMainnet, CustomWordlist>(options),
+            };
+        }
+
         match options.language.as_str() {
             "chinese_simplified" => match options.network.as_str() {
                 "testnet" => output::<BitcoinTestnet, ChineseSimplified>(options),
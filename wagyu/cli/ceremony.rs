@@ -0,0 +1,224 @@
+//! # Ceremony
+//!
+//! A key ceremony to generate a mnemonic under dual control. Each operator contributes entropy,
+//! which is mixed with the entropy of the operating system, so that no single operator (nor the machine)
+//! determines the mnemonic. The mnemonic is then verified by reading back random words of the backup,
+//! and each operator confirms the ceremony.
+//!
+//! The ceremony produces a summary of its operators, signed with the key of the mnemonic at `m/44'/0'/0'/0/0`
+//! as a Bitcoin signed message, which proves that it was produced by the holder of the mnemonic. The summary
+//! holds no secrets, nor digests of the entropy contributions, as a short contribution could be recovered
+//! from its digest.
+
+use crate::bitcoin::{wordlist::English, BitcoinFormat, BitcoinMnemonic, Mainnet};
+use crate::cli::bitcoin::BitcoinWallet;
+use crate::cli::{confirm, object, prompt, property, verify_backup, CLIError, Schema};
+use crate::model::{Mnemonic, MnemonicCount};
+
+use crypto::{digest::Digest, sha2::Sha256};
+use rand::{rngs::StdRng, Rng};
+use rand_core::SeedableRng;
+use serde::{Deserialize, Serialize};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::model::no_std::{format, String, ToString, Vec};
+
+/// The current version of the ceremony summary schema
+pub const CEREMONY_VERSION: u32 = 1;
+
+/// The derivation path of the key that signs the ceremony summary
+pub const CEREMONY_SIGNING_PATH: &str = "m/44'/0'/0'/0/0";
+
+/// The minimum number of characters of each entropy contribution
+pub const MIN_CONTRIBUTION_LENGTH: usize = 20;
+
+/// Represents a key ceremony
+#[derive(Debug, Clone)]
+pub struct Ceremony {
+    /// The number of operators contributing entropy
+    pub operators: usize,
+    /// The number of words of the mnemonic
+    pub word_count: u8,
+    /// The number of words to read back from the mnemonic backup
+    pub checks: usize,
+}
+
+/// Represents the signed summary of a key ceremony
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CeremonySummary {
+    /// The version of the ceremony summary schema
+    pub version: u32,
+    /// The time of the ceremony, in seconds since the Unix epoch
+    pub time: u64,
+    /// The number of words of the mnemonic
+    pub word_count: u8,
+    /// The number of words read back from the mnemonic backup
+    pub checks: usize,
+    /// The operators of the ceremony
+    pub operators: Vec<CeremonyOperator>,
+    /// The derivation path of the signing key
+    pub path: String,
+    /// The address of the signing key
+    pub address: String,
+    /// The signature of the summary, as a Bitcoin signed message
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+}
+
+/// Represents an operator of a key ceremony
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CeremonyOperator {
+    /// The name of the operator
+    pub name: String,
+    /// The number of characters of the entropy contribution of the operator
+    pub contribution_length: usize,
+}
+
+impl Schema for CeremonySummary {
+    fn schema() -> serde_json::Value {
+        let operator = object(
+            vec![
+                ("name", property("string", "The name of the operator")),
+                (
+                    "contribution_length",
+                    property("integer", "The number of characters of the entropy contribution"),
+                ),
+            ],
+            &["name", "contribution_length"],
+        );
+        let mut operators = property("array", "The operators of the ceremony");
+        operators["items"] = operator;
+
+        object(
+            vec![
+                (
+                    "version",
+                    property("integer", "The version of the ceremony summary schema"),
+                ),
+                (
+                    "time",
+                    property("integer", "The time of the ceremony, in seconds since the Unix epoch"),
+                ),
+                ("word_count", property("integer", "The number of words of the mnemonic")),
+                (
+                    "checks",
+                    property("integer", "The number of words read back from the backup"),
+                ),
+                ("operators", operators),
+                ("path", property("string", "The derivation path of the signing key")),
+                ("address", property("string", "The address of the signing key")),
+                ("signature", property("string", "The signature of the summary")),
+            ],
+            &[
+                "version",
+                "time",
+                "word_count",
+                "checks",
+                "operators",
+                "path",
+                "address",
+            ],
+        )
+    }
+}
+
+impl Ceremony {
+    /// Runs the ceremony on the terminal, returning the mnemonic and its signed summary.
+    pub fn run(&self) -> Result<(String, CeremonySummary), CLIError> {
+        if self.operators == 0 {
+            return Err(CLIError::CeremonyAborted("no operators".into()));
+        }
+
+        // The entropy of the operating system is mixed in first, under a domain separator
+        let mut entropy = Sha256::new();
+        entropy.input(b"wagyu ceremony v1");
+        entropy.input(&StdRng::from_entropy().gen::<[u8; 32]>());
+
+        let mut operators = Vec::with_capacity(self.operators);
+        for index in 1..=self.operators {
+            let name = prompt(&format!("Operator {}, enter your name: ", index))?
+                .trim()
+                .to_string();
+            let contribution = prompt(&format!(
+                "{}, enter at least {} characters of entropy (e.g. dice rolls or random keystrokes): ",
+                name, MIN_CONTRIBUTION_LENGTH
+            ))?;
+            let contribution_length = contribution.chars().count();
+            if contribution_length < MIN_CONTRIBUTION_LENGTH {
+                return Err(CLIError::CeremonyAborted(format!(
+                    "short entropy contribution from {}",
+                    name
+                )));
+            }
+
+            // Each contribution is length-prefixed, so that contributions cannot be shifted between operators
+            entropy.input(&(contribution.len() as u64).to_be_bytes());
+            entropy.input(contribution.as_bytes());
+            operators.push(CeremonyOperator {
+                name,
+                contribution_length,
+            });
+        }
+
+        let mut seed = [0u8; 32];
+        entropy.result(&mut seed);
+        let mnemonic =
+            BitcoinMnemonic::<Mainnet, English>::new_with_count(&mut StdRng::from_seed(seed), self.word_count)?;
+        let phrase = mnemonic.to_phrase()?;
+
+        println!("\n      {}\n", phrase);
+        verify_backup(&mut StdRng::from_entropy(), &phrase, self.checks)?;
+
+        for operator in &operators {
+            if !confirm(&format!("{}, confirm that the backup was verified", operator.name))? {
+                return Err(CLIError::CeremonyAborted(format!("unconfirmed by {}", operator.name)));
+            }
+        }
+
+        let wallet = BitcoinWallet::from_mnemonic::<Mainnet, English>(&phrase, &None, CEREMONY_SIGNING_PATH)?;
+        let mut summary = CeremonySummary {
+            version: CEREMONY_VERSION,
+            time: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_secs())
+                .unwrap_or_default(),
+            word_count: self.word_count,
+            checks: self.checks,
+            operators,
+            path: CEREMONY_SIGNING_PATH.to_string(),
+            address: wallet.address.clone().unwrap_or_default(),
+            signature: None,
+        };
+
+        let private_key = wallet.private_key.unwrap_or_default();
+        let signed = BitcoinWallet::to_signed_message::<Mainnet>(
+            &private_key,
+            &BitcoinFormat::P2PKH,
+            &summary.message()?,
+            None,
+        )?;
+        summary.signature = signed.signature;
+        Ok((phrase, summary))
+    }
+}
+
+impl CeremonySummary {
+    /// Returns the signed message of the summary, which is its JSON without the signature.
+    pub fn message(&self) -> Result<String, CLIError> {
+        let unsigned = Self {
+            signature: None,
+            ..self.clone()
+        };
+        Ok(serde_json::to_string(&unsigned)?)
+    }
+
+    /// Returns whether the signature of the summary is valid for its address.
+    pub fn verify(&self) -> Result<bool, CLIError> {
+        let signature = match &self.signature {
+            Some(signature) => signature,
+            None => return Ok(false),
+        };
+        let verified = BitcoinWallet::from_signed_message::<Mainnet>(&self.address, &self.message()?, signature)?;
+        Ok(verified.valid.unwrap_or(false))
+    }
+}
@@ -0,0 +1,132 @@
+//! # Clipboard
+//!
+//! Copies a field of the generated wallets to the system clipboard, and clears it after a delay,
+//! so that the field can be pasted without printing it to the terminal scrollback.
+//!
+//! The clipboard is accessed through the clipboard utility of the platform: `pbcopy` on macOS,
+//! `clip` on Windows, and `wl-copy`, `xclip`, or `xsel` on Linux and BSD.
+
+use crate::cli::{localize, CLIError};
+
+use serde::Serialize;
+use serde_json::Value;
+use std::{
+    io::Write,
+    process::{Command, Stdio},
+    thread,
+    time::Duration,
+};
+
+use crate::model::no_std::{String, ToString, Vec};
+
+/// The fields of a wallet that can be copied to the clipboard
+pub const CLIPBOARD_FIELDS: [&str; 2] = ["address", "private_key"];
+
+/// The default number of seconds before the clipboard is cleared
+pub const CLIPBOARD_CLEAR_AFTER: u64 = 30;
+
+/// The clipboard utilities of each platform, as (copy command, paste command)
+#[cfg(target_os = "macos")]
+const CLIPBOARD_COMMANDS: &[(&[&str], &[&str])] = &[(&["pbcopy"], &["pbpaste"])];
+#[cfg(target_os = "windows")]
+const CLIPBOARD_COMMANDS: &[(&[&str], &[&str])] = &[(&["clip"], &["powershell", "-command", "Get-Clipboard"])];
+#[cfg(not(any(target_os = "macos", target_os = "windows")))]
+const CLIPBOARD_COMMANDS: &[(&[&str], &[&str])] = &[
+    (&["wl-copy"], &["wl-paste", "--no-newline"]),
+    (
+        &["xclip", "-selection", "clipboard"],
+        &["xclip", "-selection", "clipboard", "-o"],
+    ),
+    (
+        &["xsel", "--clipboard", "--input"],
+        &["xsel", "--clipboard", "--output"],
+    ),
+];
+
+/// Returns an error if the given field cannot be copied to the clipboard.
+pub fn check_clipboard_field(field: &Option<String>) -> Result<(), CLIError> {
+    match field {
+        Some(field) if !CLIPBOARD_FIELDS.contains(&field.as_str()) => {
+            Err(CLIError::InvalidClipboardField(field.clone()))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Copies the given field of the given wallets (one per line) to the clipboard,
+/// then waits the given number of seconds and clears the clipboard, unless it was since overwritten.
+pub fn copy_wallets<T: Serialize>(wallets: &[T], field: &str, clear_after: u64) -> Result<(), CLIError> {
+    let values = wallets
+        .iter()
+        .enumerate()
+        .map(|(index, wallet)| {
+            let wallet = serde_json::to_value(wallet)?;
+            // Monero wallets have no single private key, so their private spend key is copied instead,
+            // and Nostr keys have no address, so their npub is copied instead
+            let value = match field {
+                "private_key" => wallet.get("private_key").or_else(|| wallet.get("private_spend_key")),
+                "address" => wallet.get("address").or_else(|| wallet.get("npub")),
+                _ => wallet.get(field),
+            };
+            value
+                .and_then(Value::as_str)
+                .map(str::to_string)
+                .ok_or_else(|| CLIError::MissingClipboardField(field.to_string(), index + 1))
+        })
+        .collect::<Result<Vec<String>, CLIError>>()?;
+    let text = values.join("\n");
+
+    let (copy, paste) = copy_to_clipboard(&text)?;
+    eprintln!(
+        "{}",
+        localize(&format!(
+            "Copied the {} of {} wallet(s) to the clipboard, clearing it in {} seconds",
+            field.replace('_', " "),
+            values.len(),
+            clear_after
+        ))
+    );
+    thread::sleep(Duration::from_secs(clear_after));
+
+    let unchanged = run(paste, None).map(|pasted| pasted.trim_end() == text).unwrap_or(true);
+    if unchanged {
+        run(copy, Some(""))?;
+        eprintln!("{}", localize("Cleared the clipboard"));
+    }
+    Ok(())
+}
+
+/// Copies the given text with the first available clipboard utility, returning its copy and paste commands.
+fn copy_to_clipboard(text: &str) -> Result<(&'static [&'static str], &'static [&'static str]), CLIError> {
+    for (copy, paste) in CLIPBOARD_COMMANDS {
+        if run(copy, Some(text)).is_ok() {
+            return Ok((copy, paste));
+        }
+    }
+    Err(CLIError::MissingClipboard)
+}
+
+/// Runs the given command, writing the given standard input to a copy utility,
+/// or otherwise returning the standard output of a paste utility.
+fn run(command: &[&'static str], input: Option<&str>) -> Result<String, CLIError> {
+    // The standard output of a copy utility is discarded, as X11 utilities stay running to serve the selection
+    let stdout = match input {
+        Some(_) => Stdio::null(),
+        None => Stdio::piped(),
+    };
+    let mut child = Command::new(command[0])
+        .args(&command[1..])
+        .stdin(Stdio::piped())
+        .stdout(stdout)
+        .stderr(Stdio::null())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(input.unwrap_or_default().as_bytes())?;
+    }
+
+    let output = child.wait_with_output()?;
+    match output.status.success() {
+        true => Ok(String::from_utf8_lossy(&output.stdout).to_string()),
+        false => Err(CLIError::Crate(command[0], "clipboard utility failed".into())),
+    }
+}
@@ -0,0 +1,331 @@
+//! # Coldcard
+//!
+//! Exports the extended public keys of an HD wallet in the export files of Coldcard, so wallets and
+//! multisig coordinators that import a Coldcard can add the wallet as a signer:
+//!
+//! - `coldcard`, the generic JSON export (`coldcard-export.json`), with the single-signature accounts
+//!   (BIP-44, BIP-49, BIP-84, and BIP-86) and multisig accounts (BIP-48 and BIP-45) of the wallet
+//! - `coldcard-multisig`, the multisig xpub export (`ccxp-<fingerprint>.json`), with the multisig
+//!   accounts of the wallet, as read by multisig coordinators to build a quorum
+//!
+//! The multisig keys are exported with the SLIP-132 version bytes of their script, as Coldcard does
+//! (`Ypub` and `Zpub`, or `Upub` and `Vpub` on testnet).
+
+use crate::bitcoin::{BitcoinDerivationPath, BitcoinExtendedPrivateKey, BitcoinFormat, BitcoinNetwork};
+use crate::cli::{to_descriptor, CLIError, MaskSecrets};
+use crate::model::{crypto::checksum, ExtendedPrivateKey, ExtendedPublicKey};
+
+use base58::{FromBase58, ToBase58};
+use colored::*;
+use core::{fmt, fmt::Display, str::FromStr};
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+
+use crate::model::no_std::{format, String, ToString, Vec};
+
+/// The accounts of a Coldcard export, of their name, path template, script, and address format
+const COLDCARD_ACCOUNTS: [(&str, &str, &str, Option<BitcoinFormat>); 7] = [
+    ("bip44", "m/44'/{}'/{}'", "p2pkh", Some(BitcoinFormat::P2PKH)),
+    (
+        "bip49",
+        "m/49'/{}'/{}'",
+        "p2sh-p2wpkh",
+        Some(BitcoinFormat::P2SH_P2WPKH),
+    ),
+    ("bip84", "m/84'/{}'/{}'", "p2wpkh", Some(BitcoinFormat::Bech32)),
+    ("bip86", "m/86'/{}'/{}'", "p2tr", Some(BitcoinFormat::P2TR)),
+    ("bip48_1", "m/48'/{}'/{}'/1'", "p2sh-p2wsh", None),
+    ("bip48_2", "m/48'/{}'/{}'/2'", "p2wsh", None),
+    ("bip45", "m/45'", "p2sh", None),
+];
+
+/// The SLIP-132 version bytes of the multisig scripts on mainnet and testnet (Ypub and Upub, Zpub and Vpub)
+const MULTISIG_VERSIONS: [(&str, [u8; 4], [u8; 4]); 2] = [
+    ("p2sh-p2wsh", [0x02, 0x95, 0xb4, 0x3f], [0x02, 0x42, 0x89, 0xef]),
+    ("p2wsh", [0x02, 0xaa, 0x7e, 0xd3], [0x02, 0x57, 0x54, 0x83]),
+];
+
+/// Represents an extended public key of a Coldcard export
+#[derive(Serialize, Clone, Debug)]
+pub struct ColdcardKey {
+    /// The name of the account, as `bip84` or `bip48_2`
+    pub name: String,
+    pub path: String,
+    /// The extended public key, with the SLIP-132 version bytes of its script
+    pub extended_public_key: String,
+}
+
+/// Represents the Coldcard export file of an HD wallet
+#[derive(Serialize, Clone, Debug)]
+pub struct ColdcardExport {
+    /// The export file, `coldcard` or `coldcard-multisig`
+    pub format: String,
+    pub master_fingerprint: String,
+    pub account: u32,
+    pub keys: Vec<ColdcardKey>,
+    /// The contents of the file
+    #[serde(skip)]
+    pub contents: String,
+}
+
+impl ColdcardExport {
+    /// Returns the Coldcard export file of the given format, of the given account of the given master extended private key.
+    pub fn new<N: BitcoinNetwork>(
+        format: &str,
+        master_extended_private_key: &BitcoinExtendedPrivateKey<N>,
+        account: u32,
+    ) -> Result<Self, CLIError> {
+        let multisig = match format {
+            "coldcard" => false,
+            "coldcard-multisig" => true,
+            _ => return Err(CLIError::UnknownWatchOnlyWallet(format.into())),
+        };
+        let master_key = master_extended_private_key
+            .to_extended_public_key()
+            .with_format(&BitcoinFormat::P2PKH);
+        let master_fingerprint = hex::encode(master_key.to_fingerprint()).to_uppercase();
+        let coin_type = N::HD_COIN_TYPE.to_index() - (1 << 31);
+
+        let mut keys = Vec::new();
+        let mut file = Map::new();
+        for (name, template, script, address_format) in COLDCARD_ACCOUNTS.iter() {
+            // The BIP-45 account has no account number, and is only exported for the first account
+            if (*name == "bip45" && account != 0) || (multisig && address_format.is_some()) {
+                continue;
+            }
+            let path = template
+                .replacen("{}", &coin_type.to_string(), 1)
+                .replacen("{}", &account.to_string(), 1);
+            let account_key = master_extended_private_key
+                .derive(&BitcoinDerivationPath::<N>::from_str(&path)?)?
+                .to_extended_public_key();
+            let xpub = account_key.with_format(&BitcoinFormat::P2PKH).to_string();
+            let slip132_key = match (address_format, MULTISIG_VERSIONS.iter().find(|(s, _, _)| s == script)) {
+                (Some(address_format), _) => account_key.with_format(address_format).to_string(),
+                (None, Some((_, _, testnet_version))) if N::NAME == "testnet" => {
+                    to_slip132_key(&xpub, testnet_version)?
+                }
+                (None, Some((_, mainnet_version, _))) => to_slip132_key(&xpub, mainnet_version)?,
+                (None, None) => xpub.clone(),
+            };
+
+            match multisig {
+                true => {
+                    let name = &script.replace('-', "_");
+                    file.insert(format!("{}_deriv", name), json!(path));
+                    file.insert(name.to_string(), json!(slip132_key));
+                }
+                false => {
+                    let mut section = Map::new();
+                    section.insert("name".into(), json!(script));
+                    section.insert(
+                        "xfp".into(),
+                        json!(hex::encode(account_key.to_fingerprint()).to_uppercase()),
+                    );
+                    section.insert("deriv".into(), json!(path));
+                    section.insert("xpub".into(), json!(xpub));
+                    if let Some(address_format) = address_format {
+                        section.insert(
+                            "desc".into(),
+                            json!(to_descriptor(&to_script(
+                                address_format,
+                                &format!(
+                                    "[{}{}]{}/<0;1>/*",
+                                    master_fingerprint.to_lowercase(),
+                                    path.trim_start_matches('m').replace('\'', "h"),
+                                    xpub
+                                )
+                            ))),
+                        );
+                        section.insert(
+                            "first".into(),
+                            json!(account_key
+                                .derive(&BitcoinDerivationPath::<N>::from_str("m/0/0")?)?
+                                .to_address(address_format)?
+                                .to_string()),
+                        );
+                    }
+                    if slip132_key != xpub {
+                        section.insert("_pub".into(), json!(slip132_key));
+                    }
+                    file.insert(name.to_string(), Value::Object(section));
+                }
+            };
+            keys.push(ColdcardKey {
+                name: name.to_string(),
+                path,
+                extended_public_key: slip132_key,
+            });
+        }
+
+        match multisig {
+            true => file.insert("account".into(), json!(account.to_string())),
+            false => {
+                file.insert("chain".into(), json!(if coin_type == 1 { "XTN" } else { "BTC" }));
+                file.insert("xpub".into(), json!(master_key.to_string()));
+                file.insert("account".into(), json!(account))
+            }
+        };
+        file.insert("xfp".into(), json!(master_fingerprint));
+
+        Ok(Self {
+            format: format.into(),
+            master_fingerprint,
+            account,
+            keys,
+            contents: format!("{}\n", serde_json::to_string_pretty(&Value::Object(file))?),
+        })
+    }
+}
+
+/// Returns the output script of the given address format of the given key.
+fn to_script(format: &BitcoinFormat, key: &str) -> String {
+    match format {
+        BitcoinFormat::P2SH_P2WPKH => format!("sh(wpkh({}))", key),
+        BitcoinFormat::Bech32 => format!("wpkh({})", key),
+        BitcoinFormat::P2TR => format!("tr({})", key),
+        _ => format!("pkh({})", key),
+    }
+}
+
+/// Returns the given extended public key with the given SLIP-132 version bytes.
+fn to_slip132_key(extended_public_key: &str, version: &[u8; 4]) -> Result<String, CLIError> {
+    let mut payload = extended_public_key
+        .from_base58()
+        .map_err(|_| CLIError::InvalidEncoding(format!("invalid base58 string {}", extended_public_key)))?;
+    payload.truncate(payload.len() - 4);
+    payload[..4].copy_from_slice(version);
+    let checksum = checksum(&payload);
+    payload.extend_from_slice(&checksum[..4]);
+    Ok(payload.to_base58())
+}
+
+impl MaskSecrets for ColdcardExport {
+    fn mask_secrets(&mut self) {}
+}
+
+#[cfg_attr(tarpaulin, skip)]
+impl Display for ColdcardExport {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut output = [
+            format!("      {}               {}\n", "Format".cyan().bold(), self.format),
+            format!(
+                "      {}   {}\n",
+                "Master Fingerprint".cyan().bold(),
+                self.master_fingerprint
+            ),
+            format!("      {}              {}\n", "Account".cyan().bold(), self.account),
+        ]
+        .concat();
+        for key in &self.keys {
+            output += &format!(
+                "      {} {} {}\n",
+                format!("{:<20}", key.name).cyan().bold(),
+                key.path,
+                key.extended_public_key
+            );
+        }
+
+        // Removes final new line character
+        let output = output[..output.len() - 1].to_string();
+        write!(f, "\n{}", output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bitcoin::{wordlist::English, BitcoinMnemonic, Mainnet, Testnet};
+    use crate::model::{Mnemonic, MnemonicExtended};
+
+    const MNEMONIC: &str =
+        "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+    fn master_key<N: BitcoinNetwork>() -> BitcoinExtendedPrivateKey<N> {
+        BitcoinMnemonic::<N, English>::from_phrase(MNEMONIC)
+            .unwrap()
+            .to_extended_private_key(None)
+            .unwrap()
+    }
+
+    #[test]
+    fn generic_export() {
+        let export = ColdcardExport::new("coldcard", &master_key::<Mainnet>(), 0).unwrap();
+        let contents: Value = serde_json::from_str(&export.contents).unwrap();
+        assert_eq!("73C5DA0A", contents["xfp"]);
+        assert_eq!("BTC", contents["chain"]);
+        assert_eq!(
+            "xpub661MyMwAqRbcFkPHucMnrGNzDwb6teAX1RbKQmqtEF8kK3Z7LZ59qafCjB9eCRLiTVG3uxBxgKvRgbubRhqSKXnGGb1aoaqLrpMBDrVxga8",
+            contents["xpub"]
+        );
+
+        // The first addresses are of the test vectors of BIP-44, BIP-84, and BIP-86
+        let bip44 = &contents["bip44"];
+        assert_eq!("m/44'/0'/0'", bip44["deriv"]);
+        assert_eq!(
+            "xpub6BosfCnifzxcFwrSzQiqu2DBVTshkCXacvNsWGYJVVhhawA7d4R5WSWGFNbi8Aw6ZRc1brxMyWMzG3DSSSSoekkudhUd9yLb6qx39T9nMdj",
+            bip44["xpub"]
+        );
+        assert_eq!("1LqBGSKuX5yYUonjxT5qGfpUsXKYYWeabA", bip44["first"]);
+        let bip84 = &contents["bip84"];
+        assert_eq!("FD13AAC9", bip84["xfp"]);
+        assert_eq!(
+            "zpub6rFR7y4Q2AijBEqTUquhVz398htDFrtymD9xYYfG1m4wAcvPhXNfE3EfH1r1ADqtfSdVCToUG868RvUUkgDKf31mGDtKsAYz2oz2AGutZYs",
+            bip84["_pub"]
+        );
+        assert_eq!("bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu", bip84["first"]);
+        assert_eq!(
+            "bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr",
+            contents["bip86"]["first"]
+        );
+
+        assert_eq!(
+            "xpub6DkFAXWQ2dHxq2vatrt9qyA3bXYU4ToWQwCHbf5XB2mSTexcHZCeKS1VZYcPoBd5X8yVcbXFHJR9R8UCVpt82VX1VhR28mCyxUFL4r6KFrf",
+            contents["bip48_2"]["xpub"]
+        );
+        assert_eq!(
+            "xpub68jrRzQopSUSiczuqjRwvVn3CFtSEZY6a3jbT66LM3tvt1rXtYT7Udi8dt3m1qj3q8pKZjt7tqrSt7bRN4LD2vSVq1167PSA5AyM31FUHwU",
+            contents["bip45"]["xpub"]
+        );
+        assert_eq!(7, export.keys.len());
+    }
+
+    #[test]
+    fn multisig_export() {
+        let export = ColdcardExport::new("coldcard-multisig", &master_key::<Mainnet>(), 0).unwrap();
+        let contents: Value = serde_json::from_str(&export.contents).unwrap();
+        assert_eq!("73C5DA0A", contents["xfp"]);
+        assert_eq!("0", contents["account"]);
+        assert_eq!("m/48'/0'/0'/2'", contents["p2wsh_deriv"]);
+        assert_eq!(
+            "Zpub74Jru6aftwwHxCUCWEvP6DgrfFsdA4U6ZRtQ5i8qJpMcC39yZGv3egBhQfV3MS9pZtH5z8iV5qWkJsK6ESs6mSzt4qvGhzJxPeeVS2e1zUG",
+            contents["p2wsh"]
+        );
+        assert_eq!(
+            "Ypub6jUbbRukkGPp4DgJDD4HL2NKkSZ1UPk111mg59XtJRQZHvJ6XqvJzrntik9U4jCFQkgrBqevdKLPMdYZXU9KAGhKpMhW5XujwqiQ7Csmm4Z",
+            contents["p2sh_p2wsh"]
+        );
+        assert_eq!("m/45'", contents["p2sh_deriv"]);
+        assert_eq!(3, export.keys.len());
+
+        // The BIP-45 account is only exported for the first account, and testnet keys are Vpub
+        let export = ColdcardExport::new("coldcard-multisig", &master_key::<Testnet>(), 1).unwrap();
+        let contents: Value = serde_json::from_str(&export.contents).unwrap();
+        assert!(contents.get("p2sh").is_none());
+        assert_eq!(2, export.keys.len());
+        let export = ColdcardExport::new("coldcard-multisig", &master_key::<Testnet>(), 0).unwrap();
+        let contents: Value = serde_json::from_str(&export.contents).unwrap();
+        assert_eq!(
+            "Vpub5n95dMZrDHj6SeBgJ1oz4Fae2N2eJNuWK3VTKDb2dzGpMFLUHLmtyDfen7AaQxwQ5mZnMyXdVrkEaoMLVTH8FmVBRVWPGFYWhmtDUGehGmq",
+            contents["p2wsh"]
+        );
+    }
+
+    #[test]
+    fn unknown_format() {
+        match ColdcardExport::new("trezor", &master_key::<Mainnet>(), 0) {
+            Err(CLIError::UnknownWatchOnlyWallet(_)) => {}
+            result => panic!("unexpected result {:?}", result),
+        }
+    }
+}
@@ -0,0 +1,139 @@
+//! # Convert
+//!
+//! Converts a key between its representations: entropy and mnemonics, seeds and extended private keys,
+//! WIF and hex private keys, compressed and uncompressed public keys, and the SLIP-132 versions of
+//! extended keys. Each conversion is built on the Bitcoin model types, following the conversion matrix.
+
+use crate::bitcoin::{
+    format::BitcoinFormat, wordlist::English, BitcoinExtendedPrivateKey, BitcoinExtendedPublicKey, BitcoinMnemonic,
+    BitcoinNetwork, BitcoinPrivateKey, BitcoinPublicKey,
+};
+use crate::cli::{sanitize, CLIError, InputKind};
+use crate::model::{crypto::checksum, ExtendedPrivateKey, ExtendedPublicKey, Mnemonic, PrivateKey};
+
+use base58::ToBase58;
+use core::str::FromStr;
+
+use crate::model::no_std::{String, ToString, Vec};
+
+/// The conversion matrix, from each representation to the representations it converts to
+pub const CONVERSIONS: [(&str, &[&str]); 8] = [
+    ("entropy", &["mnemonic"]),
+    ("hex", &["wif", "wif-uncompressed", "compressed", "uncompressed"]),
+    ("mnemonic", &["entropy", "seed", "xprv", "yprv", "zprv"]),
+    ("public", &["compressed", "uncompressed"]),
+    ("seed", &["xprv", "yprv", "zprv"]),
+    ("wif", &["hex", "wif", "wif-uncompressed", "compressed", "uncompressed"]),
+    ("xprv", &["xprv", "yprv", "zprv", "xpub", "ypub", "zpub", "wif", "hex"]),
+    ("xpub", &["xpub", "ypub", "zpub", "compressed", "uncompressed"]),
+];
+
+/// Returns the given input converted from one representation to another, where mnemonics are English,
+/// and the seed of a mnemonic is derived with the given password. Extended keys are converted to the
+/// SLIP-132 version of the network, so `xprv`, `yprv`, and `zprv` are `tprv`, `uprv`, and `vprv` on testnet.
+pub fn convert_key<N: BitcoinNetwork>(
+    from: &str,
+    to: &str,
+    input: &str,
+    password: Option<&str>,
+) -> Result<String, CLIError> {
+    let targets = CONVERSIONS
+        .iter()
+        .find(|(source, _)| *source == from)
+        .map(|(_, targets)| *targets)
+        .unwrap_or_default();
+    if !targets.contains(&to) {
+        return Err(CLIError::UnsupportedConversion(
+            from.into(),
+            to.into(),
+            targets.join(", "),
+        ));
+    }
+
+    let kind = match from {
+        "entropy" => InputKind::Entropy,
+        "hex" | "public" | "seed" => InputKind::Hex,
+        "mnemonic" => InputKind::Mnemonic,
+        _ => InputKind::Text,
+    };
+    let input = &sanitize(from, input, kind)?;
+    match from {
+        "entropy" => Ok(BitcoinMnemonic::<N, English>::from_entropy(&decode_hex(input)?)?.to_string()),
+        "hex" => private_key_to(&private_key_from_hex::<N>(&decode_hex(input)?)?, to),
+        "mnemonic" => {
+            let mnemonic = BitcoinMnemonic::<N, English>::from_phrase(input)?;
+            match to {
+                "entropy" => Ok(hex::encode(mnemonic.to_entropy())),
+                "seed" => Ok(hex::encode(mnemonic.to_seed(password)?)),
+                _ => seed_to::<N>(&mnemonic.to_seed(password)?, to),
+            }
+        }
+        "public" => Ok(public_key_to(&BitcoinPublicKey::<N>::from_str(input)?, to)),
+        "seed" => seed_to::<N>(&decode_hex(input)?, to),
+        "wif" => private_key_to(&BitcoinPrivateKey::<N>::from_str(input)?, to),
+        "xprv" => {
+            let extended_private_key = BitcoinExtendedPrivateKey::<N>::from_str(input)?;
+            match to {
+                "hex" | "wif" => private_key_to(&extended_private_key.to_private_key(), to),
+                "xprv" | "yprv" | "zprv" => Ok(extended_private_key.with_format(&slip132_format(to)).to_string()),
+                _ => Ok(extended_private_key
+                    .to_extended_public_key()
+                    .with_format(&slip132_format(to))
+                    .to_string()),
+            }
+        }
+        _ => {
+            let extended_public_key = BitcoinExtendedPublicKey::<N>::from_str(input)?;
+            match to {
+                "xpub" | "ypub" | "zpub" => Ok(extended_public_key.with_format(&slip132_format(to)).to_string()),
+                _ => Ok(public_key_to(&extended_public_key.to_public_key(), to)),
+            }
+        }
+    }
+}
+
+/// Returns the bytes of the given hex string, with an optional `0x` prefix.
+fn decode_hex(input: &str) -> Result<Vec<u8>, CLIError> {
+    Ok(hex::decode(input.strip_prefix("0x").unwrap_or(input))?)
+}
+
+/// Returns the address format of the given SLIP-132 version, by the first letter of its prefix.
+fn slip132_format(version: &str) -> BitcoinFormat {
+    match &version[..1] {
+        "y" => BitcoinFormat::P2SH_P2WPKH,
+        "z" => BitcoinFormat::Bech32,
+        _ => BitcoinFormat::P2PKH,
+    }
+}
+
+/// Returns the compressed private key of the given hex secret key, through its WIF encoding.
+fn private_key_from_hex<N: BitcoinNetwork>(secret_key: &[u8]) -> Result<BitcoinPrivateKey<N>, CLIError> {
+    let mut wif = vec![N::to_private_key_prefix()];
+    wif.extend_from_slice(secret_key);
+    wif.push(0x01);
+    let checksum = checksum(&wif);
+    wif.extend_from_slice(&checksum[..4]);
+    Ok(BitcoinPrivateKey::<N>::from_str(&wif.to_base58())?)
+}
+
+/// Returns the master extended private key of the given seed, in the given SLIP-132 version.
+fn seed_to<N: BitcoinNetwork>(seed: &[u8], version: &str) -> Result<String, CLIError> {
+    Ok(BitcoinExtendedPrivateKey::<N>::new_master(seed, &slip132_format(version))?.to_string())
+}
+
+/// Returns the given private key in the given representation.
+fn private_key_to<N: BitcoinNetwork>(private_key: &BitcoinPrivateKey<N>, to: &str) -> Result<String, CLIError> {
+    let secret_key = private_key.to_secp256k1_secret_key();
+    match to {
+        "hex" => Ok(hex::encode(secret_key.serialize())),
+        "wif" => Ok(BitcoinPrivateKey::<N>::from_secp256k1_secret_key(&secret_key, true).to_string()),
+        "wif-uncompressed" => Ok(BitcoinPrivateKey::<N>::from_secp256k1_secret_key(&secret_key, false).to_string()),
+        _ => Ok(public_key_to(&private_key.to_public_key(), to)),
+    }
+}
+
+/// Returns the given public key in its compressed or uncompressed encoding.
+fn public_key_to<N: BitcoinNetwork>(public_key: &BitcoinPublicKey<N>, to: &str) -> String {
+    BitcoinPublicKey::<N>::from_secp256k1_public_key(public_key.to_secp256k1_public_key(), to == "compressed")
+        .to_string()
+}
@@ -0,0 +1,164 @@
+//! # Custom wordlist
+//!
+//! A non-standard wordlist of 2048 unique words, loaded at runtime with `--wordlist-file`, with which
+//! BIP-39 mnemonics are generated and imported alongside the compiled-in languages. A mnemonic of a
+//! custom wordlist is only recoverable with the same wordlist file, so each use of one is warned about.
+
+use crate::bitcoin::BitcoinWordlist;
+use crate::cli::{localize, CLIError};
+use crate::ethereum::EthereumWordlist;
+use crate::model::{utilities::unicode::nfkd, wordlist::Wordlist};
+
+use lazy_static::lazy_static;
+use std::{collections::HashSet, sync::RwLock};
+
+use crate::model::no_std::{format, String, ToString, Vec};
+
+/// The number of words of a BIP-39 wordlist
+pub const WORDLIST_LENGTH: usize = 2048;
+
+lazy_static! {
+    /// The words of the custom wordlist of the process, one per line, if loaded with `--wordlist-file`
+    static ref WORDS: RwLock<&'static str> = RwLock::new("");
+}
+
+/// Represents the custom wordlist loaded from a wordlist file
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CustomWordlist;
+
+impl CustomWordlist {
+    /// Loads the wordlist of the file at the given path, of 2048 unique words, one per line,
+    /// and warns that it is non-standard.
+    pub fn load(path: &str) -> Result<(), CLIError> {
+        let invalid = |reason: String| CLIError::InvalidWordlistFile(path.into(), reason);
+
+        // The words are decomposed as those of the compiled-in wordlists and the imported phrases
+        let contents = std::fs::read_to_string(path)?;
+        let words = contents
+            .lines()
+            .map(|word| nfkd(word.trim()))
+            .filter(|word| !word.is_empty())
+            .collect::<Vec<String>>();
+        if words.len() != WORDLIST_LENGTH {
+            return Err(invalid(format!("{} words (expected {})", words.len(), WORDLIST_LENGTH)));
+        }
+        if let Some(word) = words.iter().find(|word| word.contains(char::is_whitespace)) {
+            return Err(invalid(format!("the word {:?} contains whitespace", word)));
+        }
+        let mut unique = HashSet::with_capacity(WORDLIST_LENGTH);
+        if let Some(word) = words.iter().find(|word| !unique.insert(word.as_str())) {
+            return Err(invalid(format!("the word {} is repeated", word)));
+        }
+
+        // The words are kept for the lifetime of the process, as the words of a wordlist are static
+        let words: &'static str = Box::leak(words.join("\n").into_boxed_str());
+        *WORDS
+            .write()
+            .map_err(|error| CLIError::Crate("wordlist", error.to_string()))? = words;
+
+        eprintln!(
+            "{}",
+            localize(&format!(
+                "Warning: non-standard wordlist {}, whose mnemonics are only recoverable with the same wordlist file",
+                path
+            ))
+        );
+        Ok(())
+    }
+
+    /// Returns the words of the loaded wordlist, or no words if none is loaded.
+    fn words() -> Vec<&'static str> {
+        WORDS.read().map(|words| words.lines().collect()).unwrap_or_default()
+    }
+}
+
+impl Wordlist for CustomWordlist {}
+
+impl BitcoinWordlist for CustomWordlist {
+    /// The wordlist is loaded at runtime, instead of compiled in.
+    const WORDLIST: &'static str = "";
+
+    /// Returns the words of the loaded wordlist.
+    fn get_all() -> Vec<&'static str> {
+        Self::words()
+    }
+}
+
+impl EthereumWordlist for CustomWordlist {
+    /// The wordlist is loaded at runtime, instead of compiled in.
+    const WORDLIST: &'static str = "";
+
+    /// Returns the words of the loaded wordlist.
+    fn get_all() -> Vec<&'static str> {
+        Self::words()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bitcoin::{wordlist::English, BitcoinMnemonic, Mainnet};
+    use crate::model::Mnemonic;
+
+    fn write(name: &str, words: &[&str]) -> String {
+        let path = std::env::temp_dir()
+            .join(format!("wagyu_wordlist_{}_{}.txt", name, std::process::id()))
+            .to_string_lossy()
+            .to_string();
+        std::fs::write(&path, words.join("\n")).unwrap();
+        path
+    }
+
+    #[test]
+    fn load() {
+        // The English wordlist in reverse order, so that each word is of the index 2047 minus its English index
+        let mut words = <English as BitcoinWordlist>::get_all();
+        words.reverse();
+        let path = write("load", &words);
+        CustomWordlist::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        let vectors = [
+            (
+                "00000000000000000000000000000000",
+                "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zebra",
+                "e77e542aac44d3f5eb0f5f8a43fea01ada98fdc4e0e61ac5092c02664a71da5705a42bf21370ea0b46e2be91d176276a09bd662299828651bc8317eb6a544a23",
+            ),
+            (
+                "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+                "letter advice cage absurd amount doctor acoustic avoid letter advice cage abstract",
+                "74a1be381efe74b9fe0530503ad0cf48529e10584c233dfb6e741235826a1c6c6172e1ac9140f36b84b80a51b5cc2b6a0bbfe837cd133ea6d2f92566676d8f85",
+            ),
+        ];
+        for (entropy, phrase, seed) in vectors.iter() {
+            let mnemonic =
+                BitcoinMnemonic::<Mainnet, CustomWordlist>::from_entropy(&hex::decode(entropy).unwrap()).unwrap();
+            assert_eq!(*phrase, mnemonic.to_phrase().unwrap());
+            let mnemonic = BitcoinMnemonic::<Mainnet, CustomWordlist>::from_phrase(phrase).unwrap();
+            assert_eq!(*seed, hex::encode(mnemonic.to_seed(Some("TREZOR")).unwrap()));
+        }
+    }
+
+    #[test]
+    fn invalid() {
+        let words = <English as BitcoinWordlist>::get_all();
+        let mut repeated = words.clone();
+        repeated[1] = "abandon";
+        let mut spaced = words.clone();
+        spaced[1] = "ability able";
+        let files = [
+            ("short", &words[..2047]),
+            ("repeated", &repeated[..]),
+            ("spaced", &spaced[..]),
+        ];
+        for (name, words) in files.iter() {
+            let path = write(name, words);
+            let result = CustomWordlist::load(&path);
+            std::fs::remove_file(&path).unwrap();
+            match result {
+                Err(CLIError::InvalidWordlistFile(..)) => {}
+                result => panic!("unexpected result {:?}", result),
+            }
+        }
+    }
+}
@@ -0,0 +1,225 @@
+//! # Dice
+//!
+//! Verifies the mnemonic of a dice-roll worksheet, by recomputing it from the rolls of a six-sided die,
+//! to catch the mistakes of a manual conversion in a cold storage ceremony. The entropy of the rolls is
+//! computed with one of two methods:
+//!
+//! - `raw`, the SHA-256 hash of the rolls as a string of digits from 1 to 6, truncated to the length
+//!   of the entropy of the mnemonic (the method of Coldcard)
+//! - `base6`, the rolls as the digits of a number in base 6 (with a 6 as a 0), of which the entropy
+//!   of the mnemonic is the last 128 to 256 bits
+//!
+//! The number of rolls must carry at least as much entropy as the mnemonic, at log2(6) bits per roll.
+
+use crate::bitcoin::{wordlist::English, BitcoinMnemonic, Mainnet};
+use crate::cli::{CLIError, MaskSecrets, SECRET_MASK};
+use crate::model::Mnemonic;
+
+use colored::*;
+use core::{fmt, fmt::Display};
+use crypto::{digest::Digest, sha2::Sha256};
+use serde::Serialize;
+
+use crate::model::no_std::{format, vec, String, ToString, Vec};
+
+/// Represents the verification of a mnemonic against the dice rolls of its worksheet
+#[derive(Serialize, Clone, Debug)]
+pub struct DiceVerification {
+    /// The method of computing the entropy, `raw` or `base6`
+    pub method: String,
+    /// The number of dice rolls
+    pub rolls: usize,
+    /// The entropy of the rolls in hex
+    pub entropy: String,
+    /// The mnemonic of the worksheet
+    pub mnemonic: String,
+    /// The mnemonic recomputed from the rolls
+    pub derived_mnemonic: String,
+    /// The positions (from 1) of the words of the worksheet that differ from the recomputed mnemonic
+    pub mismatched_words: Vec<usize>,
+    pub valid: bool,
+}
+
+impl DiceVerification {
+    /// Returns the verification of the given mnemonic against the given dice rolls, with the given method.
+    /// The rolls may be separated by spaces or commas.
+    pub fn new(rolls: &str, mnemonic: &str, method: &str) -> Result<Self, CLIError> {
+        let digits = rolls
+            .chars()
+            .filter(|c| !c.is_whitespace() && *c != ',')
+            .map(|c| match (c.to_digit(10), method) {
+                (Some(digit @ 1..=6), _) | (Some(digit @ 0), "base6") => Ok(digit as u8),
+                _ => Err(CLIError::InvalidDiceRoll(c.to_string())),
+            })
+            .collect::<Result<Vec<u8>, CLIError>>()?;
+
+        let words = mnemonic
+            .split_whitespace()
+            .map(str::to_lowercase)
+            .collect::<Vec<String>>();
+        let bits = match words.len() {
+            12 | 15 | 18 | 21 | 24 => words.len() * 32 / 3,
+            count => return Err(CLIError::InvalidDiceWordCount(count)),
+        };
+        let minimum = (bits as f64 / 6f64.log2()).ceil() as usize;
+        if digits.len() < minimum {
+            return Err(CLIError::InsufficientDiceRolls(words.len(), digits.len(), minimum));
+        }
+
+        let entropy = match method {
+            "base6" => to_base6_entropy(&digits, bits / 8),
+            "raw" => {
+                let rolls = digits.iter().map(|digit| digit.to_string()).collect::<String>();
+                let mut sha256 = Sha256::new();
+                sha256.input_str(&rolls);
+                let mut hash = [0u8; 32];
+                sha256.result(&mut hash);
+                hash[..bits / 8].to_vec()
+            }
+            _ => return Err(CLIError::UnsupportedDiceMethod(method.into())),
+        };
+
+        let derived_mnemonic = BitcoinMnemonic::<Mainnet, English>::from_entropy(&entropy)?.to_phrase()?;
+        let mismatched_words = derived_mnemonic
+            .split(' ')
+            .zip(&words)
+            .enumerate()
+            .filter(|(_, (derived, word))| derived != word)
+            .map(|(i, _)| i + 1)
+            .collect::<Vec<usize>>();
+
+        Ok(Self {
+            method: method.into(),
+            rolls: digits.len(),
+            entropy: hex::encode(entropy),
+            mnemonic: words.join(" "),
+            derived_mnemonic,
+            valid: mismatched_words.is_empty(),
+            mismatched_words,
+        })
+    }
+}
+
+/// Returns the given number of last bytes of the number of the given digits in base 6, with a 6 as a 0.
+fn to_base6_entropy(digits: &[u8], length: usize) -> Vec<u8> {
+    // The number is accumulated in big-endian bytes, multiplied by 6 and added to for each digit
+    let mut number = vec![0u8; length];
+    for digit in digits {
+        let mut carry = (digit % 6) as u32;
+        for byte in number.iter_mut().rev() {
+            let value = *byte as u32 * 6 + carry;
+            *byte = value as u8;
+            carry = value >> 8;
+        }
+    }
+    number
+}
+
+impl MaskSecrets for DiceVerification {
+    fn mask_secrets(&mut self) {
+        self.entropy = SECRET_MASK.into();
+        self.mnemonic = SECRET_MASK.into();
+        self.derived_mnemonic = SECRET_MASK.into();
+    }
+}
+
+#[cfg_attr(tarpaulin, skip)]
+impl Display for DiceVerification {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let status = match self.valid {
+            true => "verified".green().bold(),
+            false => format!(
+                "MISMATCH (words {})",
+                self.mismatched_words
+                    .iter()
+                    .map(|i| i.to_string())
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            )
+            .red()
+            .bold(),
+        };
+        let output = [
+            format!("      {}               {}\n", "Method".cyan().bold(), self.method),
+            format!("      {}                {}\n", "Rolls".cyan().bold(), self.rolls),
+            format!("      {}              {}\n", "Entropy".cyan().bold(), self.entropy),
+            format!("      {}             {}\n", "Mnemonic".cyan().bold(), self.mnemonic),
+            format!(
+                "      {}     {}\n",
+                "Derived Mnemonic".cyan().bold(),
+                self.derived_mnemonic
+            ),
+            format!("      {}                {}\n", "Valid".cyan().bold(), status),
+        ]
+        .concat();
+
+        // Removes final new line character
+        let output = output[..output.len() - 1].to_string();
+        write!(f, "\n{}", output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn verify_raw() {
+        let rolls = "1234561234 5612345612 3456123456 1234561234 5612345612";
+        let mnemonic = "unveil nice picture region tragic fault cream strike tourist control recipe tourist";
+        let verification = DiceVerification::new(rolls, mnemonic, "raw").unwrap();
+        assert_eq!(50, verification.rolls);
+        assert_eq!("ee72ae915a4e6ea7ccbeb8e5e5eecef2", verification.entropy);
+        assert_eq!(mnemonic, verification.derived_mnemonic);
+        assert!(verification.valid);
+    }
+
+    #[test]
+    fn verify_base6() {
+        let rolls = "6543216".repeat(15)[..100].to_string();
+        let mnemonic = "train stumble struggle episode trial wasp heart width sister other inch scorpion boy bird \
+                        capable guide tail fame boat price apology vibrant kidney repair";
+        let verification = DiceVerification::new(&rolls, mnemonic, "base6").unwrap();
+        assert_eq!(
+            "e71af75d25fe83ef1a97d6c993a1c96091aa2d087b3cdd4a4c635530a7e6de9d",
+            verification.entropy
+        );
+        assert!(verification.valid);
+
+        // A 6 is a 0, so rolls of only 6 are the zero entropy
+        let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        let verification = DiceVerification::new(&"6".repeat(50), mnemonic, "base6").unwrap();
+        assert_eq!("00".repeat(16), verification.entropy);
+        assert!(verification.valid);
+    }
+
+    #[test]
+    fn mismatched_words() {
+        let rolls = "123456".repeat(9)[..50].to_string();
+        let mnemonic = "unveil nice picture region tragic fault cream strike tourist control zoo tourist";
+        let verification = DiceVerification::new(&rolls, mnemonic, "raw").unwrap();
+        assert_eq!(vec![11], verification.mismatched_words);
+        assert!(!verification.valid);
+    }
+
+    #[test]
+    fn invalid() {
+        let mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+        match DiceVerification::new(&"1".repeat(49), mnemonic, "raw") {
+            Err(CLIError::InsufficientDiceRolls(12, 49, 50)) => {}
+            result => panic!("unexpected result {:?}", result),
+        }
+        match DiceVerification::new(&"0".repeat(50), mnemonic, "raw") {
+            Err(CLIError::InvalidDiceRoll(_)) => {}
+            result => panic!("unexpected result {:?}", result),
+        }
+        match DiceVerification::new(&"1".repeat(50), "abandon about", "raw") {
+            Err(CLIError::InvalidDiceWordCount(2)) => {}
+            result => panic!("unexpected result {:?}", result),
+        }
+        match DiceVerification::new(&"1".repeat(50), mnemonic, "base10") {
+            Err(CLIError::UnsupportedDiceMethod(_)) => {}
+            result => panic!("unexpected result {:?}", result),
+        }
+    }
+}
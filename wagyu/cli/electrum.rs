@@ -0,0 +1,401 @@
+//! # Electrum
+//!
+//! Reads the keys of an Electrum wallet file, of a standard wallet with a BIP-32 keystore, to audit
+//! or migrate them: its seed, extended keys, and receiving and change addresses. Electrum seeds are
+//! not BIP-39 mnemonics, and their version, the hash of the seed, is their seed type:
+//!
+//! - `standard`, with legacy (P2PKH) addresses at `m/{chain}/{index}` of the master key
+//! - `segwit`, with bech32 (P2WPKH) addresses at `m/0'/{chain}/{index}` of the master key
+//!
+//! The keystore of a wallet with a password is encrypted with AES-256-CBC under the double SHA-256
+//! hash of the password, which is read as for encrypted wallet files. The seed is rederived into the
+//! extended keys of the keystore, and each address from the extended public key, so a wallet that
+//! does not match its keys is reported as invalid. Wallet files with file encryption are not supported.
+
+use crate::bitcoin::{
+    BitcoinDerivationPath, BitcoinExtendedPrivateKey, BitcoinExtendedPublicKey, BitcoinFormat, BitcoinNetwork,
+    Mainnet as BitcoinMainnet, Testnet as BitcoinTestnet,
+};
+use crate::cli::{mask, read_passphrase, CLIError, MaskSecrets};
+use crate::model::{crypto::checksum, ExtendedPrivateKey, ExtendedPublicKey};
+
+use colored::*;
+use core::{fmt, fmt::Display, str::FromStr};
+use crypto::{
+    aes::{cbc_decryptor, KeySize},
+    blockmodes::PkcsPadding,
+    buffer::{ReadBuffer, RefReadBuffer, RefWriteBuffer, WriteBuffer},
+    hmac::Hmac,
+    mac::Mac,
+    pbkdf2::pbkdf2,
+    sha2::Sha512,
+};
+use rustc_serialize::base64::FromBase64;
+use serde::Serialize;
+use serde_json::Value;
+use std::fs;
+
+use crate::model::no_std::{format, vec, String, ToString, Vec};
+
+/// The seed types of Electrum seeds, of the prefix of their version and their account path
+const ELECTRUM_SEED_TYPES: [(&str, &str, &str, BitcoinFormat); 2] = [
+    ("standard", "01", "m", BitcoinFormat::P2PKH),
+    ("segwit", "100", "m/0'", BitcoinFormat::Bech32),
+];
+
+/// The number of PBKDF2 rounds of the stretching of an Electrum seed
+const ELECTRUM_PBKDF2_ROUNDS: u32 = 2048;
+
+/// Represents the keys and addresses of an Electrum wallet file
+#[derive(Serialize, Clone, Debug)]
+pub struct ElectrumWallet {
+    /// The seed type of the seed, as `standard` or `segwit`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seed_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub seed: Option<String>,
+    /// The seed extension of the seed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub passphrase: Option<String>,
+    /// The path of the account of the extended keys
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub derivation: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub root_fingerprint: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extended_private_key: Option<String>,
+    pub extended_public_key: String,
+    pub receiving: Vec<String>,
+    pub change: Vec<String>,
+    pub network: String,
+    /// Whether the seed, extended keys, and addresses of the wallet match
+    pub valid: bool,
+}
+
+impl ElectrumWallet {
+    /// Returns the keys and addresses of the Electrum wallet file at the given path.
+    pub fn read(path: &str) -> Result<Self, CLIError> {
+        let contents = fs::read_to_string(path)?;
+        let wallet: Value = match serde_json::from_str(&contents) {
+            Ok(wallet) => wallet,
+            // A wallet file with file encryption is an ECIES message in base64, of the magic bytes `BIE1`
+            Err(_) => match contents.trim().from_base64() {
+                Ok(encrypted) if encrypted.starts_with(b"BIE1") => {
+                    return Err(CLIError::UnsupportedElectrumWallet("file encryption".into()))
+                }
+                _ => return Err(CLIError::InvalidElectrumWallet("expected a wallet file".into())),
+            },
+        };
+
+        match wallet["wallet_type"].as_str() {
+            Some("standard") => {}
+            Some(wallet_type) => return Err(CLIError::UnsupportedElectrumWallet(wallet_type.into())),
+            None => return Err(CLIError::InvalidElectrumWallet("missing wallet_type".into())),
+        };
+        let keystore = &wallet["keystore"];
+        match keystore["type"].as_str() {
+            Some("bip32") => {}
+            Some(keystore_type) => {
+                return Err(CLIError::UnsupportedElectrumWallet(format!(
+                    "{} keystore",
+                    keystore_type
+                )))
+            }
+            None => return Err(CLIError::InvalidElectrumWallet("missing keystore".into())),
+        };
+
+        // The secrets of the keystore of a wallet with a password are encrypted
+        let password = match wallet["use_encryption"].as_bool().unwrap_or(false) {
+            true => Some(read_passphrase(false)?),
+            false => None,
+        };
+        let secret = |field: &str| -> Result<Option<String>, CLIError> {
+            match (keystore[field].as_str(), &password) {
+                (Some(value), Some(password)) => Ok(Some(decrypt(value, password)?)),
+                (Some(value), None) => Ok(Some(value.to_string())),
+                (None, _) => Ok(None),
+            }
+        };
+        let addresses = |chain: &str| -> Vec<String> {
+            wallet["addresses"][chain]
+                .as_array()
+                .map(|addresses| {
+                    addresses
+                        .iter()
+                        .filter_map(|address| address.as_str().map(String::from))
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+
+        let extended_public_key = keystore["xpub"]
+            .as_str()
+            .ok_or_else(|| CLIError::InvalidElectrumWallet("missing xpub".into()))?;
+        let mut electrum_wallet = Self {
+            seed_type: keystore["seed_type"].as_str().map(String::from),
+            seed: secret("seed")?,
+            passphrase: secret("passphrase")?.filter(|passphrase| !passphrase.is_empty()),
+            derivation: keystore["derivation"].as_str().map(String::from),
+            root_fingerprint: keystore["root_fingerprint"].as_str().map(String::from),
+            extended_private_key: secret("xprv")?,
+            extended_public_key: extended_public_key.into(),
+            receiving: addresses("receiving"),
+            change: addresses("change"),
+            network: "".into(),
+            valid: false,
+        };
+
+        match BitcoinExtendedPublicKey::<BitcoinMainnet>::from_str(extended_public_key) {
+            Ok(_) => electrum_wallet.verify::<BitcoinMainnet>()?,
+            Err(_) => electrum_wallet.verify::<BitcoinTestnet>()?,
+        };
+        Ok(electrum_wallet)
+    }
+
+    /// Verifies the seed, extended keys, and addresses of the wallet on the given network.
+    fn verify<N: BitcoinNetwork>(&mut self) -> Result<(), CLIError> {
+        self.network = N::NAME.into();
+        let extended_public_key = BitcoinExtendedPublicKey::<N>::from_str(&self.extended_public_key)
+            .map_err(|_| CLIError::InvalidElectrumWallet(format!("invalid xpub {}", self.extended_public_key)))?;
+        let mut valid = true;
+
+        if let Some(extended_private_key) = &self.extended_private_key {
+            let extended_private_key = BitcoinExtendedPrivateKey::<N>::from_str(extended_private_key)
+                .map_err(|_| CLIError::InvalidPassphrase)?;
+            valid &= extended_private_key.to_extended_public_key().to_string() == self.extended_public_key;
+        }
+
+        // The seed type is the prefix of the version of the seed, and the seed is rederived into the account key
+        if let Some(seed) = &self.seed {
+            let seed = seed.to_lowercase().split_whitespace().collect::<Vec<&str>>().join(" ");
+            let mut hmac = Hmac::new(Sha512::new(), b"Seed version");
+            hmac.input(seed.as_bytes());
+            let version = hex::encode(hmac.result().code());
+            let (seed_type, derivation, format) = match ELECTRUM_SEED_TYPES
+                .iter()
+                .find(|(_, prefix, _, _)| version.starts_with(prefix))
+            {
+                Some((seed_type, _, derivation, format)) => (*seed_type, *derivation, format),
+                None => {
+                    return Err(CLIError::UnsupportedElectrumWallet(format!(
+                        "seed version {}",
+                        &version[..3]
+                    )))
+                }
+            };
+            valid &= self.seed_type.as_deref().unwrap_or(seed_type) == seed_type;
+            self.seed_type = Some(seed_type.into());
+
+            let mut stretched_seed = [0u8; 64];
+            let salt = format!("electrum{}", self.passphrase.as_deref().unwrap_or_default());
+            pbkdf2(
+                &mut Hmac::new(Sha512::new(), seed.as_bytes()),
+                salt.as_bytes(),
+                ELECTRUM_PBKDF2_ROUNDS,
+                &mut stretched_seed,
+            );
+            let master_key = BitcoinExtendedPrivateKey::<N>::new_master(&stretched_seed, format)?;
+            let account_key = master_key.derive(&BitcoinDerivationPath::<N>::from_str(derivation)?)?;
+            let root_fingerprint = hex::encode(master_key.to_extended_public_key().to_fingerprint());
+
+            valid &= account_key.to_extended_public_key().to_string() == self.extended_public_key;
+            valid &= self.root_fingerprint.as_deref().unwrap_or(&root_fingerprint) == root_fingerprint;
+            valid &= self.derivation.as_deref().unwrap_or(derivation) == derivation;
+            self.root_fingerprint = Some(root_fingerprint);
+            self.derivation = Some(derivation.into());
+        }
+
+        // The addresses are of the format of the extended public key, at `{chain}/{index}` of the account key
+        let format = extended_public_key.format();
+        for (chain, addresses) in [(0, &self.receiving), (1, &self.change)].iter() {
+            for (index, address) in addresses.iter().enumerate() {
+                let path = BitcoinDerivationPath::<N>::from_str(&format!("m/{}/{}", chain, index))?;
+                valid &= extended_public_key.derive(&path)?.to_address(&format)?.to_string() == *address;
+            }
+        }
+
+        self.valid = valid;
+        Ok(())
+    }
+}
+
+/// Returns the given field of an Electrum keystore, decrypted with the given password.
+fn decrypt(field: &str, password: &str) -> Result<String, CLIError> {
+    let encrypted = field.from_base64().map_err(|_| CLIError::InvalidPassphrase)?;
+    if encrypted.len() < 32 || encrypted.len() % 16 != 0 {
+        return Err(CLIError::InvalidPassphrase);
+    }
+
+    // The field is the IV and ciphertext, with the double SHA-256 hash of the password as the key
+    let (iv, ciphertext) = encrypted.split_at(16);
+    let plaintext = decrypt_cbc(&checksum(password.as_bytes()), iv, ciphertext)?;
+    String::from_utf8(plaintext).map_err(|_| CLIError::InvalidPassphrase)
+}
+
+/// Returns the given ciphertext decrypted with AES-256-CBC under the given key and IV, without its PKCS#7 padding.
+pub(crate) fn decrypt_cbc(key: &[u8], iv: &[u8], ciphertext: &[u8]) -> Result<Vec<u8>, CLIError> {
+    let mut decryptor = cbc_decryptor(KeySize::KeySize256, key, iv, PkcsPadding);
+    let mut plaintext = vec![0u8; ciphertext.len()];
+    let mut buffer = RefWriteBuffer::new(&mut plaintext);
+    decryptor
+        .decrypt(&mut RefReadBuffer::new(ciphertext), &mut buffer, true)
+        .map_err(|_| CLIError::InvalidPassphrase)?;
+    Ok(buffer.take_read_buffer().take_remaining().to_vec())
+}
+
+impl MaskSecrets for ElectrumWallet {
+    fn mask_secrets(&mut self) {
+        mask(&mut self.seed);
+        mask(&mut self.passphrase);
+        mask(&mut self.extended_private_key);
+    }
+}
+
+#[cfg_attr(tarpaulin, skip)]
+impl Display for ElectrumWallet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut output = [
+            match &self.seed_type {
+                Some(seed_type) => format!("      {}            {}\n", "Seed Type".cyan().bold(), seed_type),
+                _ => "".to_string(),
+            },
+            match &self.seed {
+                Some(seed) => format!("      {}                 {}\n", "Seed".cyan().bold(), seed),
+                _ => "".to_string(),
+            },
+            match &self.passphrase {
+                Some(passphrase) => format!("      {}           {}\n", "Passphrase".cyan().bold(), passphrase),
+                _ => "".to_string(),
+            },
+            match &self.derivation {
+                Some(derivation) => format!("      {}           {}\n", "Derivation".cyan().bold(), derivation),
+                _ => "".to_string(),
+            },
+            match &self.root_fingerprint {
+                Some(root_fingerprint) => {
+                    format!("      {}     {}\n", "Root Fingerprint".cyan().bold(), root_fingerprint)
+                }
+                _ => "".to_string(),
+            },
+            match &self.extended_private_key {
+                Some(extended_private_key) => format!(
+                    "      {} {}\n",
+                    "Extended Private Key".cyan().bold(),
+                    extended_private_key
+                ),
+                _ => "".to_string(),
+            },
+            format!(
+                "      {}  {}\n",
+                "Extended Public Key".cyan().bold(),
+                self.extended_public_key
+            ),
+            format!("      {}              {}\n", "Network".cyan().bold(), self.network),
+            format!("      {}                {}\n", "Valid".cyan().bold(), self.valid),
+        ]
+        .concat();
+        let derivation = self.derivation.as_deref().unwrap_or("m");
+        for (chain, addresses) in [(0, &self.receiving), (1, &self.change)].iter() {
+            for (index, address) in addresses.iter().enumerate() {
+                output += &format!(
+                    "      {} {}\n",
+                    format!("{:<20}", format!("{}/{}/{}", derivation, chain, index))
+                        .cyan()
+                        .bold(),
+                    address
+                );
+            }
+        }
+
+        // Removes final new line character
+        let output = output[..output.len() - 1].to_string();
+        write!(f, "\n{}", output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A standard wallet of the Electrum seed of a `standard` seed type, of legacy addresses
+    const ELECTRUM_STANDARD: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/wagyu/cli/fixtures/electrum_standard.json");
+
+    /// A standard wallet of the Electrum seed of a `segwit` seed type, of bech32 addresses
+    const ELECTRUM_SEGWIT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/wagyu/cli/fixtures/electrum_segwit.json");
+
+    /// A watching-only wallet of an old (Electrum 1.x) seed, of its master public key
+    const ELECTRUM_OLD: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/wagyu/cli/fixtures/electrum_old.json");
+
+    /// Returns the wallet of the given fixture with the given replacement, read from a temporary file.
+    fn read_modified_wallet(fixture: &str, from: &str, to: &str) -> Result<ElectrumWallet, CLIError> {
+        let contents = fs::read_to_string(fixture).unwrap();
+        assert!(contents.contains(from));
+        let path = std::env::temp_dir().join(format!("wagyu_electrum_{}.json", std::process::id()));
+        fs::write(&path, contents.replacen(from, to, 1)).unwrap();
+        let wallet = ElectrumWallet::read(path.to_str().unwrap());
+        fs::remove_file(&path).unwrap();
+        wallet
+    }
+
+    #[test]
+    fn read_standard() {
+        let wallet = ElectrumWallet::read(ELECTRUM_STANDARD).unwrap();
+        assert!(wallet.valid);
+        assert_eq!(Some("standard"), wallet.seed_type.as_deref());
+        assert_eq!(Some("m"), wallet.derivation.as_deref());
+        assert_eq!(Some("48adc7a0"), wallet.root_fingerprint.as_deref());
+        assert_eq!(
+            "xpub661MyMwAqRbcFWohJWt7PHsFEJfZAvw9ZxwQoDa4SoMgsDDM1T7WK3u9E4edkC4ugRnZ8E4xDZRpk8Rnts3Nbt97dPwT52CwBdDWroaZf8U",
+            wallet.extended_public_key
+        );
+        assert_eq!("1NNkttn1YvVGdqBW4PR6zvc3Zx3H5owKRf", wallet.receiving[0]);
+        assert_eq!("1KSezYMhAJMWqFbVFB2JshYg69UpmEXR4D", wallet.change[0]);
+        assert_eq!("mainnet", wallet.network);
+    }
+
+    #[test]
+    fn read_segwit() {
+        let wallet = ElectrumWallet::read(ELECTRUM_SEGWIT).unwrap();
+        assert!(wallet.valid);
+        assert_eq!(Some("segwit"), wallet.seed_type.as_deref());
+        assert_eq!(Some("m/0'"), wallet.derivation.as_deref());
+        assert_eq!(Some("b2e35a7d"), wallet.root_fingerprint.as_deref());
+        assert_eq!("bc1q3g5tmkmlvxryhh843v4dz026avatc0zzr6h3af", wallet.receiving[0]);
+        assert_eq!("bc1qdy94n2q5qcp0kg7v9yzwe6wvfkhnvyzje7nx2p", wallet.change[0]);
+    }
+
+    #[test]
+    fn read_old() {
+        match ElectrumWallet::read(ELECTRUM_OLD) {
+            Err(CLIError::UnsupportedElectrumWallet(keystore)) => assert_eq!("old keystore", keystore),
+            _ => panic!("expected an unsupported keystore"),
+        };
+    }
+
+    #[test]
+    fn read_modified() {
+        // An address of another index, a seed of another seed type, and a seed with a seed extension
+        let wallet = read_modified_wallet(
+            ELECTRUM_STANDARD,
+            "18KCZB4y7SKPmTGN2rDQuUsNxyVBX7CAbG",
+            "15NuNdCwazxBd3y2MZin9qevuviA3pJe65",
+        )
+        .unwrap();
+        assert!(!wallet.valid);
+        let wallet = read_modified_wallet(
+            ELECTRUM_SEGWIT,
+            "\"seed_type\": \"segwit\"",
+            "\"seed_type\": \"standard\"",
+        )
+        .unwrap();
+        assert!(!wallet.valid);
+        assert_eq!(Some("segwit"), wallet.seed_type.as_deref());
+        let wallet = read_modified_wallet(
+            ELECTRUM_STANDARD,
+            "\"passphrase\": \"\"",
+            "\"passphrase\": \"extension\"",
+        )
+        .unwrap();
+        assert!(!wallet.valid);
+    }
+}
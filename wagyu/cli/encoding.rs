@@ -0,0 +1,580 @@
+//! # Encoding
+//!
+//! Encodes and decodes the Base32, Base58, Base58Check, Bech32, Bech32m, and CashAddr encodings of
+//! addresses and keys. When a checksum is invalid, the position of the mistyped character that breaks
+//! it is reported in the error: with the BCH code of the checksum for Bech32, Bech32m, and CashAddr,
+//! and by substituting each character in turn for Base58Check, which has no error-locating code.
+
+use crate::bitcoin::bech32m;
+use crate::cli::CLIError;
+use crate::model::crypto::checksum;
+
+use base58::{FromBase58, ToBase58};
+use bech32::{u5, Bech32};
+use core::str::FromStr;
+use serde::Serialize;
+
+use crate::model::no_std::{format, vec, String, ToString, Vec};
+
+/// The Base32 character set (RFC 4648)
+const BASE32_CHARSET: &[u8; 32] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+
+/// The Base58 character set
+const BASE58_CHARSET: &[u8; 58] = b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// The Bech32, Bech32m, and CashAddr character set
+const BECH32_CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/// Represents the decoded payload of an encoding
+#[derive(Debug, Clone, Serialize)]
+pub struct Decoded {
+    /// The encoding of the payload
+    pub encoding: String,
+    /// The human-readable part of a Bech32 or Bech32m payload, or the prefix of a CashAddr payload
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prefix: Option<String>,
+    /// The payload in hex, unless it is a witness version and program
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub data: Option<String>,
+    /// The witness version of a segwit address
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub witness_version: Option<u8>,
+    /// The witness program of a segwit address in hex
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub witness_program: Option<String>,
+}
+
+/// Returns the given hex payload in the given encoding. Bech32, Bech32m, and CashAddr
+/// payloads are encoded with the given prefix (e.g. `bc`, or `bitcoincash`).
+pub fn encode(encoding: &str, data: &[u8], prefix: Option<&str>) -> Result<String, CLIError> {
+    let prefix = || prefix.ok_or_else(|| CLIError::MissingEncodingPrefix(encoding.into()));
+    match encoding {
+        // Base32 is unpadded, as in the secrets of TOTP URIs
+        "base32" => Ok(convert_bits(data, 8, 5, true)
+            .unwrap_or_default()
+            .iter()
+            .map(|value| BASE32_CHARSET[*value as usize] as char)
+            .collect()),
+        "base58" => Ok(data.to_base58()),
+        "base58check" => {
+            let mut payload = data.to_vec();
+            payload.extend_from_slice(&checksum(data)[0..4]);
+            Ok(payload.to_base58())
+        }
+        "bech32" => {
+            let values = to_u5(&convert_bits(data, 8, 5, true).unwrap_or_default());
+            Ok(Bech32::new(prefix()?.to_lowercase(), values)?.to_string())
+        }
+        "bech32m" => Ok(bech32m::encode(
+            &prefix()?.to_lowercase(),
+            &convert_bits(data, 8, 5, true).unwrap_or_default(),
+        )),
+        "cashaddr" => Ok(cashaddr_encode(
+            &prefix()?.to_lowercase(),
+            &convert_bits(data, 8, 5, true).unwrap_or_default(),
+        )),
+        _ => Err(CLIError::UnsupportedEncoding(encoding.into())),
+    }
+}
+
+/// Returns the decoded payload of the given string in the given encoding. If the checksum is
+/// invalid, returns an error with the position of the probable mistyped character.
+pub fn decode(encoding: &str, encoded: &str) -> Result<Decoded, CLIError> {
+    let encoded = encoded.trim();
+    let (prefix, values) = match encoding {
+        "base32" => (None, base32_decode(encoded)?),
+        "base58" => (None, base58_decode(encoded)?),
+        "base58check" => {
+            let payload = base58_decode(encoded)?;
+            if !base58check_verify(&payload) {
+                return Err(checksum_error(encoding, &base58check_locate_errors(encoded)));
+            }
+            (None, payload[..payload.len() - 4].to_vec())
+        }
+        "bech32" | "bech32m" | "cashaddr" => {
+            // The case of a string is that of its first letter, so the first letter of the other case is mistyped
+            let lowercase = encoded.to_lowercase();
+            if lowercase != encoded && encoded.to_uppercase() != encoded {
+                let uppercase =
+                    matches!(encoded.chars().find(char::is_ascii_alphabetic), Some(c) if c.is_ascii_uppercase());
+                let position = encoded
+                    .chars()
+                    .position(|c| c.is_ascii_alphabetic() && c.is_ascii_uppercase() != uppercase);
+                return Err(CLIError::InvalidEncoding(format!(
+                    "mixed case {} string (probable mistyped character at position {})",
+                    encoding,
+                    position.unwrap_or_default() + 1
+                )));
+            }
+
+            let separator = match encoding {
+                "cashaddr" => ':',
+                _ => '1',
+            };
+            let split = lowercase
+                .rfind(separator)
+                .ok_or_else(|| CLIError::InvalidEncoding(format!("missing {} separator '{}'", encoding, separator)))?;
+            let prefix = &lowercase[..split];
+            let values = bech32_values(encoding, &lowercase, split + 1)?;
+
+            let valid = match encoding {
+                "bech32" => Bech32::from_str(&lowercase).is_ok(),
+                "bech32m" => bech32m::decode(&lowercase).is_ok(),
+                _ => cashaddr_verify(prefix, &values),
+            };
+            if !valid {
+                let positions = match encoding {
+                    "cashaddr" => cashaddr_locate_errors(prefix, &values, split),
+                    _ => bech32m::locate_errors(&lowercase),
+                };
+                return Err(checksum_error(encoding, &positions));
+            }
+
+            let checksum_length = match encoding {
+                "cashaddr" => 8,
+                _ => 6,
+            };
+            (
+                Some(prefix.to_string()),
+                values[..values.len() - checksum_length].to_vec(),
+            )
+        }
+        _ => return Err(CLIError::UnsupportedEncoding(encoding.into())),
+    };
+
+    let (data, witness_version, witness_program) = match &prefix {
+        Some(prefix) if encoding != "cashaddr" => {
+            // The first value of a segwit address is its witness version, followed by its witness program
+            let witness = match ["bc", "tb", "bcrt"].contains(&prefix.as_str()) && !values.is_empty() {
+                true => convert_bits(&values[1..], 5, 8, false).map(|program| (values[0], program)),
+                false => None,
+            };
+            match witness {
+                Some((version, program)) => (None, Some(version), Some(hex::encode(program))),
+                None => (convert_bits(&values, 5, 8, false).map(hex::encode), None, None),
+            }
+        }
+        Some(_) => (convert_bits(&values, 5, 8, false).map(hex::encode), None, None),
+        None => (Some(hex::encode(values)), None, None),
+    };
+
+    Ok(Decoded {
+        encoding: encoding.into(),
+        prefix,
+        data,
+        witness_version,
+        witness_program,
+    })
+}
+
+/// Returns the bytes of the given Base32 string, with or without padding,
+/// or the position of its first invalid character.
+fn base32_decode(encoded: &str) -> Result<Vec<u8>, CLIError> {
+    let values = encoded
+        .trim_end_matches('=')
+        .bytes()
+        .enumerate()
+        .map(|(index, byte)| {
+            BASE32_CHARSET
+                .iter()
+                .position(|character| *character == byte.to_ascii_uppercase())
+                .map(|value| value as u8)
+                .ok_or_else(|| invalid_character("base32", encoded, index))
+        })
+        .collect::<Result<Vec<u8>, CLIError>>()?;
+    convert_bits(&values, 5, 8, false)
+        .ok_or_else(|| CLIError::InvalidEncoding(format!("invalid base32 padding of {}", encoded)))
+}
+
+/// Returns the bytes of the given Base58 string, or the position of its first invalid character.
+fn base58_decode(encoded: &str) -> Result<Vec<u8>, CLIError> {
+    if let Some(position) = encoded.bytes().position(|byte| !BASE58_CHARSET.contains(&byte)) {
+        return Err(invalid_character("base58", encoded, position));
+    }
+    encoded
+        .from_base58()
+        .map_err(|_| CLIError::InvalidEncoding(format!("invalid base58 string {}", encoded)))
+}
+
+/// Returns whether the last four bytes of the given payload are its double SHA-256 checksum.
+fn base58check_verify(payload: &[u8]) -> bool {
+    payload.len() >= 4 && checksum(&payload[..payload.len() - 4])[0..4] == payload[payload.len() - 4..]
+}
+
+/// Returns the 5-bit values of the given lowercase string after the given start,
+/// or the position of its first invalid character.
+fn bech32_values(encoding: &str, encoded: &str, start: usize) -> Result<Vec<u8>, CLIError> {
+    encoded[start..]
+        .bytes()
+        .enumerate()
+        .map(|(index, byte)| {
+            BECH32_CHARSET
+                .iter()
+                .position(|character| *character == byte)
+                .map(|value| value as u8)
+                .ok_or_else(|| invalid_character(encoding, encoded, start + index))
+        })
+        .collect()
+}
+
+/// Returns the error of an invalid checksum, with the given positions (from 1) of the probable mistyped character.
+fn checksum_error(encoding: &str, positions: &[usize]) -> CLIError {
+    match positions.is_empty() {
+        true => CLIError::InvalidEncoding(format!(
+            "invalid {} checksum (no single mistyped character makes it valid)",
+            encoding
+        )),
+        false => CLIError::InvalidEncoding(format!(
+            "invalid {} checksum (probable mistyped character at position {})",
+            encoding,
+            positions
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<String>>()
+                .join(", or ")
+        )),
+    }
+}
+
+/// Returns the positions (from 1) of the characters of the given Base58Check string that make its
+/// checksum valid when substituted by another character of the Base58 character set.
+fn base58check_locate_errors(encoded: &str) -> Vec<usize> {
+    let mut positions = Vec::new();
+    let mut candidate = encoded.as_bytes().to_vec();
+    for position in 0..candidate.len() {
+        let original = candidate[position];
+        let valid = BASE58_CHARSET.iter().any(|character| {
+            candidate[position] = *character;
+            *character != original
+                && String::from_utf8_lossy(&candidate)
+                    .from_base58()
+                    .map(|payload| base58check_verify(&payload))
+                    .unwrap_or(false)
+        });
+        if valid {
+            positions.push(position + 1);
+        }
+        candidate[position] = original;
+    }
+    positions
+}
+
+/// Returns the error of an invalid character at the given position of the given string.
+fn invalid_character(encoding: &str, encoded: &str, position: usize) -> CLIError {
+    CLIError::InvalidEncoding(format!(
+        "invalid {} character '{}' at position {}",
+        encoding,
+        encoded[position..].chars().next().unwrap_or_default(),
+        position + 1
+    ))
+}
+
+/// Returns the values of the given bit width regrouped into the given bit width,
+/// or `None` if the padding is invalid.
+fn convert_bits(data: &[u8], from: u32, to: u32, pad: bool) -> Option<Vec<u8>> {
+    let (mut accumulator, mut bits) = (0u32, 0u32);
+    let mut values = Vec::new();
+    for value in data {
+        accumulator = (accumulator << from) | u32::from(*value);
+        bits += from;
+        while bits >= to {
+            bits -= to;
+            values.push(((accumulator >> bits) & ((1 << to) - 1)) as u8);
+        }
+    }
+    match (pad, bits) {
+        (true, 0) => (),
+        (true, _) => values.push(((accumulator << (to - bits)) & ((1 << to) - 1)) as u8),
+        (false, _) if bits >= from || (accumulator << (to - bits)) & ((1 << to) - 1) != 0 => return None,
+        (false, _) => (),
+    };
+    Some(values)
+}
+
+/// Returns the given 5-bit values as `u5` values of the `bech32` crate.
+fn to_u5(values: &[u8]) -> Vec<u5> {
+    values.iter().filter_map(|value| u5::try_from_u8(*value).ok()).collect()
+}
+
+/// Returns the BCH checksum of the given CashAddr values.
+/// https://github.com/bitcoincashorg/bitcoincash.org/blob/master/spec/cashaddr.md
+fn cashaddr_polymod(values: &[u8]) -> u64 {
+    const GENERATOR: [u64; 5] = [
+        0x98_f2bc_8e61,
+        0x79_b76d_99e2,
+        0xf3_3e5f_b3c4,
+        0xae_2eab_e2a8,
+        0x1e_4f43_e470,
+    ];
+    let mut checksum = 1u64;
+    for value in values {
+        let top = checksum >> 35;
+        checksum = ((checksum & 0x07_ffff_ffff) << 5) ^ u64::from(*value);
+        for (index, generator) in GENERATOR.iter().enumerate() {
+            if (top >> index) & 1 == 1 {
+                checksum ^= generator;
+            }
+        }
+    }
+    checksum ^ 1
+}
+
+/// Returns the CashAddr values of the given prefix, followed by the given values.
+fn cashaddr_values(prefix: &str, values: &[u8]) -> Vec<u8> {
+    let mut expanded: Vec<u8> = prefix.bytes().map(|byte| byte & 0x1f).collect();
+    expanded.push(0);
+    expanded.extend_from_slice(values);
+    expanded
+}
+
+/// Returns the CashAddr encoding of the given prefix and 5-bit values.
+fn cashaddr_encode(prefix: &str, values: &[u8]) -> String {
+    let mut expanded = cashaddr_values(prefix, values);
+    expanded.extend_from_slice(&[0u8; 8]);
+    let checksum = cashaddr_polymod(&expanded);
+
+    let mut encoded = format!("{}:", prefix);
+    for value in values {
+        encoded.push(BECH32_CHARSET[*value as usize] as char);
+    }
+    for index in 0..8 {
+        encoded.push(BECH32_CHARSET[((checksum >> (5 * (7 - index))) & 0x1f) as usize] as char);
+    }
+    encoded
+}
+
+/// Returns whether the checksum of the given CashAddr prefix and 5-bit values is valid.
+fn cashaddr_verify(prefix: &str, values: &[u8]) -> bool {
+    values.len() >= 8 && cashaddr_polymod(&cashaddr_values(prefix, values)) == 0
+}
+
+/// Returns the positions (from 1) of the probably mistyped characters of the given CashAddr prefix and
+/// 5-bit values, whose separator is at the given position of the string.
+///
+/// As for Bech32 (see `bech32m::locate_errors`), the checksum is linear, so the residue of the values is
+/// the residue of the error alone, which matches the residue of one error value at one position.
+fn cashaddr_locate_errors(prefix: &str, values: &[u8], separator: usize) -> Vec<usize> {
+    let residue = cashaddr_polymod(&cashaddr_values(prefix, values));
+    let mut zeros = vec![0u8; prefix.len() + 1 + values.len()];
+    let zero = cashaddr_polymod(&zeros);
+
+    let mut positions = Vec::new();
+    for index in 0..values.len() {
+        for error in 1..32u8 {
+            zeros[prefix.len() + 1 + index] = error;
+            if cashaddr_polymod(&zeros) ^ zero == residue {
+                positions.push(separator + index + 2);
+            }
+        }
+        zeros[prefix.len() + 1 + index] = 0;
+    }
+    positions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn error(encoding: &str, encoded: &str) -> String {
+        match decode(encoding, encoded) {
+            Err(CLIError::InvalidEncoding(message)) => message,
+            result => panic!("decoded {}: {:?}", encoded, result),
+        }
+    }
+
+    #[test]
+    fn known_answers() {
+        // Base58Check (Bitcoin wiki), Bech32 (BIP-173), Bech32m (BIP-350), and CashAddr (spec) test vectors
+        let vectors = [
+            (
+                "base58check",
+                None,
+                "0062e907b15cbf27d5425399ebf6f0fb50ebb88f18",
+                "1A1zP1eP5QGefi2DMPTfTL5SLmv7DivfNa",
+            ),
+            (
+                "base58check",
+                None,
+                "0077bff20c60e522dfaa3350c39b030a5d004e839a",
+                "1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2",
+            ),
+            (
+                "base58check",
+                None,
+                "800c28fca386c7a227600b2fe50b7cae11ec86d3bf1fbe471be89827e19d72aa1d",
+                "5HueCGU8rMjxEXxiPuD5BDku4MkFqeZyd4dZ1jvhTVqvbTLvyTJ",
+            ),
+            ("bech32", Some("a"), "", "a12uel5l"),
+            (
+                "bech32",
+                Some("abcdef"),
+                "00443214c74254b635cf84653a56d7c675be77df",
+                "abcdef1qpzry9x8gf2tvdw0s3jn54khce6mua7lmqqqxw",
+            ),
+            (
+                "bech32",
+                Some("split"),
+                "c5f38b70305f519bf66d85fb6cf03058f3dde463ecd7918f2dc743918f2d",
+                "split1checkupstagehandshakeupstreamerranterredcaperred2y9e3w",
+            ),
+            ("bech32m", Some("a"), "", "a1lqfn3a"),
+            (
+                "bech32m",
+                Some("abcdef"),
+                "ffbbcdeb38bdab49ca307b9ac5a928398a418820",
+                "abcdef1l7aum6echk45nj3s0wdvt2fg8x9yrzpqzd3ryx",
+            ),
+            (
+                "bech32m",
+                Some("split"),
+                "c5f38b70305f519bf66d85fb6cf03058f3dde463ecd7918f2dc743918f2d",
+                "split1checkupstagehandshakeupstreamerranterredcaperredlc445v",
+            ),
+            (
+                "cashaddr",
+                Some("bitcoincash"),
+                "0076a04053bda0a88bda5177b86a15c3b29f559873",
+                "bitcoincash:qpm2qsznhks23z7629mms6s4cwef74vcwvy22gdx6a",
+            ),
+            (
+                "cashaddr",
+                Some("bitcoincash"),
+                "00f5bf48b397dae70be82b3cca4793f8eb2b6cdac9",
+                "bitcoincash:qr6m7j9njldwwzlg9v7v53unlr4jkmx6eylep8ekg2",
+            ),
+            (
+                "cashaddr",
+                Some("bchtest"),
+                "08f5bf48b397dae70be82b3cca4793f8eb2b6cdac9",
+                "bchtest:pr6m7j9njldwwzlg9v7v53unlr4jkmx6eyvwc0uz5t",
+            ),
+            ("cashaddr", Some("prefix"), "", "prefix:x64nx6hz"),
+        ];
+        for (encoding, prefix, data, encoded) in vectors.iter() {
+            assert_eq!(
+                *encoded,
+                encode(encoding, &hex::decode(data).unwrap(), *prefix).unwrap()
+            );
+            let decoded = decode(encoding, encoded).unwrap();
+            assert_eq!(prefix.map(String::from), decoded.prefix);
+            assert_eq!(Some(data.to_string()), decoded.data);
+        }
+
+        // Segwit addresses decode to their witness version and program
+        let addresses = [
+            (
+                "bech32",
+                "BC1QW508D6QEJXTDG4Y5R3ZARVARY0C5XW7KV8F3T4",
+                0,
+                "751e76e8199196d454941c45d1b3a323f1433bd6",
+            ),
+            (
+                "bech32",
+                "tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3q0sl5k7",
+                0,
+                "1863143c14c5166804bd19203356da136c985678cd4d27a1b8c6329604903262",
+            ),
+            (
+                "bech32m",
+                "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0",
+                1,
+                "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+            ),
+        ];
+        for (encoding, encoded, version, program) in addresses.iter() {
+            let decoded = decode(encoding, encoded).unwrap();
+            assert_eq!(Some(*version), decoded.witness_version);
+            assert_eq!(Some(program.to_string()), decoded.witness_program);
+        }
+    }
+
+    #[test]
+    fn invalid_strings() {
+        // Invalid checksums of BIP-173 (with a Bech32m checksum, or of an uppercase prefix) and BIP-350
+        // (with a Bech32 checksum, or of an uppercase prefix)
+        let checksums = [
+            ("bech32", "A1G7SGD8"),
+            ("bech32", "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kemeawh"),
+            ("bech32m", "M1VUXWEZ"),
+            (
+                "bech32m",
+                "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqh2y7hd",
+            ),
+        ];
+        for (encoding, encoded) in checksums.iter() {
+            assert!(error(encoding, encoded).starts_with(&format!("invalid {} checksum", encoding)));
+        }
+        assert!(decode("bech32m", "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kemeawh").is_ok());
+        assert!(decode(
+            "bech32",
+            "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqh2y7hd"
+        )
+        .is_ok());
+
+        // Invalid strings of BIP-173 and BIP-350: no separator, an empty prefix, an invalid character,
+        // or a checksum that is too short
+        let strings = [
+            ("bech32", "pzry9x0s0muk"),
+            ("bech32", "1pzry9x0s0muk"),
+            ("bech32", "x1b4n0q5v"),
+            ("bech32", "li1dgmt3"),
+            ("bech32", "10a06t8"),
+            ("bech32", "1qzzfhee"),
+            ("bech32m", "qyrz8wqd2c9m"),
+            ("bech32m", "1qyrz8wqd2c9m"),
+            ("bech32m", "y1b0jsk6g"),
+            ("bech32m", "lt1igcx5c0"),
+            ("bech32m", "in1muywd"),
+            ("bech32m", "mm1crxm3i"),
+            ("bech32m", "au1s5cgom"),
+            ("bech32m", "16plkw9"),
+            ("bech32m", "1p2gdwpf"),
+        ];
+        for (encoding, encoded) in strings.iter() {
+            assert!(decode(encoding, encoded).is_err(), "decoded {}", encoded);
+        }
+    }
+
+    #[test]
+    fn locate_errors() {
+        let errors = [
+            (
+                "bech32",
+                "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t5",
+                "invalid bech32 checksum (probable mistyped character at position 42)",
+            ),
+            (
+                "bech32m",
+                "bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcq",
+                "invalid bech32m checksum (probable mistyped character at position 62)",
+            ),
+            (
+                "cashaddr",
+                "bitcoincash:qpm2qsznhks23z7629mms6s4cwef74vcwvy22gdx6q",
+                "invalid cashaddr checksum (probable mistyped character at position 54)",
+            ),
+            (
+                "base58check",
+                "1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN3",
+                "invalid base58check checksum (probable mistyped character at position 34)",
+            ),
+            (
+                "bech32",
+                "bc1qw508d6qejxtdg4y5r3zarvory0c5xw7kv8f3t4",
+                "invalid bech32 character 'o' at position 27",
+            ),
+            (
+                "bech32",
+                "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3T4",
+                "mixed case bech32 string (probable mistyped character at position 41)",
+            ),
+        ];
+        for (encoding, encoded, message) in errors.iter() {
+            assert_eq!(*message, error(encoding, encoded));
+        }
+
+        // Two mistyped characters of a Bech32 checksum are not located
+        let message = error("bech32", "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3a5");
+        assert!(message.starts_with("invalid bech32 checksum"));
+    }
+}
@@ -0,0 +1,270 @@
+//! # Entropy
+//!
+//! A heuristic analysis of user-supplied entropy and mnemonics, which warns about inputs that are
+//! unlikely to have been randomly generated (such as repeating or sequential bytes, printable text,
+//! and repeated or dictionary-sequential words). Such inputs are rejected unless forced with `--force`.
+//!
+//! The analysis only detects patterned inputs, and passing it is no evidence of a random input.
+
+use crate::cli::{localize, CLIError};
+use crate::model::wordlist::{bip39, monero};
+
+use core::sync::atomic::{AtomicBool, Ordering};
+
+use crate::model::no_std::{format, vec, Vec};
+
+/// The BIP-39 and Monero wordlists, in which the words of a mnemonic are looked up
+const WORDLISTS: [&str; 21] = [
+    bip39::CHINESE_SIMPLIFIED,
+    bip39::CHINESE_TRADITIONAL,
+    bip39::ENGLISH,
+    bip39::FRENCH,
+    bip39::ITALIAN,
+    bip39::JAPANESE,
+    bip39::KOREAN,
+    bip39::SPANISH,
+    monero::CHINESE_SIMPLIFIED,
+    monero::DUTCH,
+    monero::ENGLISH,
+    monero::ENGLISH_OLD,
+    monero::ESPERANTO,
+    monero::FRENCH,
+    monero::GERMAN,
+    monero::ITALIAN,
+    monero::JAPANESE,
+    monero::LOJBAN,
+    monero::PORTUGUESE,
+    monero::RUSSIAN,
+    monero::SPANISH,
+];
+
+/// The number of BIP-39 wordlists at the start of `WORDLISTS`, before the Monero wordlists
+const BIP39_WORDLISTS: usize = 8;
+
+/// The number of words of a BIP-39 wordlist, assumed for the words of a mnemonic of no known wordlist
+const BIP39_WORDS: usize = 2048;
+
+/// The number of consecutive words of a mnemonic, adjacent in its wordlist, that are warned about
+const SEQUENTIAL_WORDS: usize = 3;
+
+/// The probability of the repeated words of a random mnemonic below which its words are warned about
+const REPEATED_WORDS_PROBABILITY: f64 = 1e-6;
+
+/// Whether low-entropy inputs are accepted with a warning, set with `--force`
+static FORCE: AtomicBool = AtomicBool::new(false);
+
+/// Sets whether low-entropy inputs are accepted with a warning, instead of rejected.
+pub fn set_force(force: bool) {
+    FORCE.store(force, Ordering::Relaxed);
+}
+
+/// Returns the patterns of the given entropy that suggest it was not randomly generated.
+pub fn analyze_entropy(entropy: &[u8]) -> Vec<&'static str> {
+    let mut issues = Vec::new();
+    if entropy.len() < 2 {
+        return issues;
+    }
+
+    let differences = entropy
+        .windows(2)
+        .map(|pair| pair[1].wrapping_sub(pair[0]))
+        .collect::<Vec<u8>>();
+    if (1..=entropy.len() / 2).any(|period| (period..entropy.len()).all(|i| entropy[i] == entropy[i - period])) {
+        issues.push("repeating bytes");
+    } else if differences.iter().all(|difference| *difference == differences[0]) {
+        issues.push("sequential bytes");
+    }
+
+    let mut distinct = entropy.to_vec();
+    distinct.sort_unstable();
+    distinct.dedup();
+    if distinct.len() * 2 < entropy.len() {
+        issues.push("few distinct bytes");
+    }
+
+    if entropy.iter().all(|byte| byte.is_ascii_graphic() || *byte == b' ') {
+        issues.push("printable text");
+    }
+    issues
+}
+
+/// Returns the patterns of the given mnemonic that suggest it was not randomly generated.
+/// The words are looked up in the first BIP-39 or Monero wordlist with all of its words, and the
+/// checksum word of a Monero mnemonic, a repeat of one of its other words, is not analyzed.
+///
+/// Repeated words are warned about if a random mnemonic of as many words of the wordlist is less likely
+/// than `REPEATED_WORDS_PROBABILITY` to have as few distinct words, or to repeat a word as many times.
+pub fn analyze_mnemonic(mnemonic: &str) -> Vec<&'static str> {
+    let mut issues = Vec::new();
+    let mut words = mnemonic.split_whitespace().collect::<Vec<&str>>();
+    if words.is_empty() {
+        return issues;
+    }
+
+    let lookup = WORDLISTS.iter().enumerate().find_map(|(i, wordlist)| {
+        let wordlist = wordlist.lines().collect::<Vec<&str>>();
+        words
+            .iter()
+            .map(|word| wordlist.iter().position(|element| element == word))
+            .collect::<Option<Vec<usize>>>()
+            .map(|indices| (i >= BIP39_WORDLISTS, wordlist.len(), indices))
+    });
+    let (wordlist_size, indices) = match lookup {
+        Some((true, size, mut indices)) if words.len() % 12 == 1 => {
+            words.pop();
+            indices.pop();
+            (size, Some(indices))
+        }
+        Some((_, size, indices)) => (size, Some(indices)),
+        None => (BIP39_WORDS, None),
+    };
+
+    let mut distinct = words.clone();
+    distinct.sort_unstable();
+    distinct.dedup();
+    let repeats = distinct
+        .iter()
+        .map(|word| words.iter().filter(|other| *other == word).count())
+        .max()
+        .unwrap_or_default();
+    if fewest_distinct_probability(words.len(), distinct.len(), wordlist_size) < REPEATED_WORDS_PROBABILITY
+        || repeat_probability(words.len(), repeats, wordlist_size) < REPEATED_WORDS_PROBABILITY
+    {
+        issues.push("repeated words");
+    }
+
+    if let Some(indices) = indices {
+        let sequential = indices.windows(SEQUENTIAL_WORDS).any(|run| {
+            run.windows(2).all(|pair| pair[1] == pair[0] + 1) || run.windows(2).all(|pair| pair[0] == pair[1] + 1)
+        });
+        if sequential {
+            issues.push("dictionary-sequential words");
+        }
+    }
+    issues
+}
+
+/// Returns the probability that a given number of words, drawn uniformly at random from a wordlist
+/// of a given size, have at most a given number of distinct words.
+fn fewest_distinct_probability(words: usize, distinct: usize, wordlist_size: usize) -> f64 {
+    let size = wordlist_size as f64;
+    // The probabilities of each number of distinct words among the words drawn so far
+    let mut probabilities = vec![0.0; words + 1];
+    probabilities[0] = 1.0;
+    for drawn in 1..=words {
+        for count in (1..=drawn).rev() {
+            probabilities[count] = probabilities[count] * count as f64 / size
+                + probabilities[count - 1] * (size - (count - 1) as f64) / size;
+        }
+        probabilities[0] = 0.0;
+    }
+    probabilities.iter().take(distinct + 1).sum()
+}
+
+/// Returns an upper bound of the probability that some word is repeated a given number of times among
+/// a given number of words, drawn uniformly at random from a wordlist of a given size.
+fn repeat_probability(words: usize, repeats: usize, wordlist_size: usize) -> f64 {
+    if repeats < 2 {
+        return 1.0;
+    }
+    // The expected number of sets of the given number of words, of which all words are the same
+    let sets = (0..repeats).fold(1.0, |sets, i| sets * (words - i) as f64 / (i + 1) as f64);
+    (sets * (wordlist_size as f64).powi(1 - repeats as i32)).min(1.0)
+}
+
+/// Checks the given patterns of a specified user-supplied input, returning an error for a low-entropy
+/// input, or printing a warning if it is forced.
+pub fn check_entropy(field: &str, issues: &[&str]) -> Result<(), CLIError> {
+    match (issues.is_empty(), FORCE.load(Ordering::Relaxed)) {
+        (true, _) => Ok(()),
+        (false, false) => Err(CLIError::LowEntropyInput(field.into(), issues.join(", "))),
+        (false, true) => {
+            eprintln!(
+                "{}",
+                localize(&format!("Warning: low-entropy {} ({})", field, issues.join(", ")))
+            );
+            Ok(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bitcoin::{wordlist::English as BitcoinEnglish, BitcoinMnemonic, Mainnet as BitcoinMainnet};
+    use crate::model::Mnemonic;
+    use crate::monero::{wordlist::English as MoneroEnglish, Mainnet as MoneroMainnet, MoneroMnemonic};
+
+    use rand::{rngs::StdRng, Rng, SeedableRng};
+
+    #[test]
+    fn random_mnemonics() {
+        let mut rng = StdRng::seed_from_u64(641);
+        for _ in 0..3000 {
+            let entropy: [u8; 32] = rng.gen();
+            let mnemonic = BitcoinMnemonic::<BitcoinMainnet, BitcoinEnglish>::from_entropy(&entropy).unwrap();
+            let phrase = mnemonic.to_phrase().unwrap();
+            assert!(analyze_mnemonic(&phrase).is_empty(), "{}", phrase);
+
+            let mnemonic = MoneroMnemonic::<MoneroMainnet, MoneroEnglish>::from_private_spend_key(&rng.gen());
+            let phrase = mnemonic.to_phrase().unwrap();
+            assert!(analyze_mnemonic(&phrase).is_empty(), "{}", phrase);
+        }
+    }
+
+    #[test]
+    fn repeated_words() {
+        let mnemonics = [
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+            "legal winner legal winner legal winner legal winner legal winner legal winner",
+            "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo vote",
+        ];
+        for mnemonic in mnemonics.iter() {
+            assert_eq!(vec!["repeated words"], analyze_mnemonic(mnemonic));
+        }
+
+        // A word of a random 12-word mnemonic is repeated 4 times with a probability below 10^-7
+        let mnemonic = "legal winner thank legal winner legal winner thank legal year wave sausage";
+        assert!(analyze_mnemonic(mnemonic).contains(&"repeated words"));
+    }
+
+    #[test]
+    fn repeated_words_probability() {
+        // Two of 12 words of 2048 are the same with a probability of about 66/2048
+        assert!((fewest_distinct_probability(12, 11, 2048) - 0.0317).abs() < 0.001);
+        assert_eq!(1.0, fewest_distinct_probability(12, 12, 2048));
+        assert!(fewest_distinct_probability(24, 6, 2048) < REPEATED_WORDS_PROBABILITY);
+        assert!(repeat_probability(24, 3, 1626) > REPEATED_WORDS_PROBABILITY);
+        assert!(repeat_probability(24, 5, 1626) < REPEATED_WORDS_PROBABILITY);
+    }
+
+    #[test]
+    fn monero_checksum_word() {
+        // The checksum word of a Monero mnemonic repeats one of its words
+        let mnemonic = MoneroMnemonic::<MoneroMainnet, MoneroEnglish>::from_private_spend_key(&[7u8; 32]);
+        let phrase = mnemonic.to_phrase().unwrap();
+        let words = phrase.split_whitespace().collect::<Vec<&str>>();
+        assert_eq!(25, words.len());
+        assert!(words[..24].contains(&words[24]));
+    }
+
+    #[test]
+    fn sequential_words() {
+        let mnemonic = "abandon ability able about above absent absorb abstract absurd abuse access accident";
+        assert!(analyze_mnemonic(mnemonic).contains(&"dictionary-sequential words"));
+    }
+
+    #[test]
+    fn patterned_entropy() {
+        assert_eq!(
+            vec!["repeating bytes", "few distinct bytes"],
+            analyze_entropy(&[0u8; 16])
+        );
+        assert_eq!(
+            vec!["sequential bytes"],
+            analyze_entropy(&(0u8..16).collect::<Vec<u8>>())
+        );
+        assert_eq!(vec!["printable text"], analyze_entropy(b"correct horse ba"));
+        assert!(analyze_entropy(&hex::decode("9e885d952ad362caeb4efe34a8e91bd2").unwrap()).is_empty());
+    }
+}
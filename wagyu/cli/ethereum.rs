@@ -1,28 +1,49 @@
-use crate::cli::{flag, option, subcommand, types::*, CLIError, CLI};
+use crate::cli::{
+    check_clipboard_field, confirm_backups, copy_wallets, encrypt_to, flag, import_batch, mask, object, option,
+    print_wallets, property, read_batch_file, read_transaction_batch_file, rederive, rpc_call, sanitize_option,
+    sign_batch, subcommand, to_coin_type, types::*, AuditLog, CLIError, CustomWordlist, DerivationPresets, HDTree,
+    HDTreeNode, InputKind, Keystore, Manifest, MaskSecrets, Mismatch, NonceFile, Recipient, Schema,
+    UnsignedTransactionFile, WalletFile, WalletSummary, CLI, CLIPBOARD_CLEAR_AFTER, ETHEREUM_RPC_URL, MAX_TREE_DEPTH,
+    SUMMARY_COUNT,
+};
 use crate::ethereum::{
     wordlist::*, EthereumAddress, EthereumAmount, EthereumDerivationPath, EthereumExtendedPrivateKey,
-    EthereumExtendedPublicKey, EthereumFormat, EthereumMnemonic, EthereumNetwork, EthereumPrivateKey,
-    EthereumPublicKey, EthereumTransaction, EthereumTransactionParameters, Goerli, Kovan, Mainnet as EthereumMainnet,
-    Rinkeby, Ropsten,
+    EthereumExtendedPublicKey, EthereumFormat, EthereumMnemonic, EthereumNetwork, EthereumNodeRecord,
+    EthereumPrivateKey, EthereumPublicKey, EthereumSignature, EthereumTransaction, EthereumTransactionParameters,
+    Goerli, Kovan, Mainnet as EthereumMainnet, Permit, Rinkeby, Ropsten, SignatureNonce, TypedData, UserOperation,
 };
 use crate::model::{
-    ExtendedPrivateKey, ExtendedPublicKey, Mnemonic, MnemonicCount, MnemonicExtended, Network, PrivateKey, PublicKey,
-    Transaction,
+    ChildIndex, ChildNumberRange, DerivationPathTemplate, ExtendedPrivateKey, ExtendedPublicKey, Mnemonic,
+    MnemonicCount, MnemonicExtended, Network, PrivateKey, PublicKey, Transaction,
 };
 
 use clap::{ArgMatches, Values};
 use colored::*;
-use core::{fmt, fmt::Display, str::FromStr};
+use core::{convert::TryFrom, fmt, fmt::Display, str::FromStr};
 use rand::{rngs::StdRng, Rng};
 use rand_core::SeedableRng;
+use rustc_serialize::base64::{CharacterSet, Config, Newline, ToBase64};
 use serde::{Deserialize, Serialize};
 use serde_json::from_str;
+use std::{collections::HashMap, net::Ipv4Addr};
+
+use crate::model::no_std::{format, vec, BTreeMap, String, ToOwned, ToString, Vec};
 
-use crate::model::no_std::{format, vec, String, ToOwned, ToString, Vec};
+/// The default gas limit of an Ether transfer
+pub const TRANSFER_GAS: u64 = 21_000;
+/// The default gas limit of an ERC-20 transfer, approval, or transfer from
+pub const ERC20_GAS: u64 = 65_000;
+/// The default gas limit of any other contract call
+pub const CONTRACT_CALL_GAS: u64 = 200_000;
+/// The function selectors of ERC-20 `transfer`, `approve`, and `transferFrom`
+const ERC20_SELECTORS: [&str; 3] = ["a9059cbb", "095ea7b3", "23b872dd"];
+
+/// The default number of indices of each derivation path searched by `discover-paths`
+const DISCOVER_PATHS_DEPTH: u32 = 20;
 
 /// Represents a generic wallet to output
-#[derive(Serialize, Debug, Default)]
-struct EthereumWallet {
+#[derive(Serialize, Clone, Debug, Default)]
+pub(crate) struct EthereumWallet {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub path: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -40,11 +61,107 @@ struct EthereumWallet {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub address: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub init_code_hash: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub node_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enode: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub enr: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub peer_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub transaction_id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub network: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub transaction_hex: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas_estimation: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message_hash: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub typed_data_hash: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub user_op_hash: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub v: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub r: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub s: Option<String>,
+}
+
+impl MaskSecrets for EthereumWallet {
+    fn mask_secrets(&mut self) {
+        mask(&mut self.extended_private_key);
+        mask(&mut self.mnemonic);
+        mask(&mut self.password);
+        mask(&mut self.private_key);
+    }
+}
+
+impl Schema for EthereumWallet {
+    fn schema() -> serde_json::Value {
+        object(
+            vec![
+                ("path", property("string", "The derivation path of the wallet")),
+                ("password", property("string", "The password of the mnemonic")),
+                ("mnemonic", property("string", "The mnemonic phrase of the wallet")),
+                (
+                    "extended_private_key",
+                    property("string", "The extended private key of the wallet"),
+                ),
+                (
+                    "extended_public_key",
+                    property("string", "The extended public key of the wallet"),
+                ),
+                ("private_key", property("string", "The private key of the wallet")),
+                ("public_key", property("string", "The public key of the wallet")),
+                ("address", property("string", "The address of the wallet")),
+                (
+                    "init_code_hash",
+                    property("string", "The keccak256 hash of the init code of the contract"),
+                ),
+                (
+                    "node_id",
+                    property("string", "The node ID of the node (keccak256 of its public key)"),
+                ),
+                ("enode", property("string", "The enode URL of the node")),
+                (
+                    "enr",
+                    property("string", "The Ethereum Node Record of the node (EIP-778)"),
+                ),
+                ("peer_id", property("string", "The libp2p peer ID of the node")),
+                ("transaction_id", property("string", "The transaction id")),
+                ("network", property("string", "The network of the wallet")),
+                ("transaction_hex", property("string", "The transaction in hex")),
+                ("gas", property("string", "The gas limit of the transaction")),
+                (
+                    "gas_estimation",
+                    property("string", "The method of the gas estimation (rpc or default)"),
+                ),
+                ("message_hash", property("string", "The hash of the signed message")),
+                (
+                    "typed_data_hash",
+                    property("string", "The EIP-712 hash of the signed typed data"),
+                ),
+                (
+                    "user_op_hash",
+                    property("string", "The ERC-4337 hash of the signed user operation"),
+                ),
+                ("signature", property("string", "The signature in hex")),
+                ("v", property("string", "The V field of the signature (27 or 28)")),
+                ("r", property("string", "The R field of the signature in hex")),
+                ("s", property("string", "The S field of the signature in hex")),
+            ],
+            &[],
+        )
+    }
 }
 
 impl EthereumWallet {
@@ -87,6 +204,42 @@ impl EthereumWallet {
         })
     }
 
+    /// Returns the tree of the BIP-44 accounts, their receive chains, and their addresses,
+    /// of the given mnemonic, or of a new mnemonic if none is specified.
+    pub fn new_hd_tree<N: EthereumNetwork, W: EthereumWordlist, R: Rng>(
+        rng: &mut R,
+        mnemonic: Option<&str>,
+        word_count: u8,
+        password: Option<&str>,
+        depth: u32,
+        breadth: u32,
+    ) -> Result<HDTree, CLIError> {
+        let new_mnemonic = match mnemonic {
+            Some(_) => None,
+            None => Some(EthereumMnemonic::<N, W>::new_with_count(rng, word_count)?),
+        };
+        let master_extended_private_key = match (mnemonic, &new_mnemonic) {
+            (Some(phrase), _) => EthereumMnemonic::<N, W>::from_phrase(phrase)?.to_extended_private_key(password)?,
+            (None, Some(mnemonic)) => mnemonic.to_extended_private_key(password)?,
+            (None, None) => unreachable!(),
+        };
+
+        // Ethereum wallets derive their addresses from the receive chain alone
+        Ok(HDTree {
+            mnemonic: new_mnemonic.map(|mnemonic| mnemonic.to_string()),
+            network: N::NAME.to_string(),
+            tree: HDTreeNode::new(
+                &master_extended_private_key,
+                &[ChildIndex::hardened(44)?, ChildIndex::hardened(60)?],
+                &[(0, "receive")],
+                &EthereumFormat::Standard,
+                depth,
+                breadth,
+                EthereumExtendedPublicKey::to_fingerprint,
+            )?,
+        })
+    }
+
     pub fn from_mnemonic<N: EthereumNetwork, W: EthereumWordlist>(
         mnemonic: &str,
         password: Option<&str>,
@@ -113,6 +266,25 @@ impl EthereumWallet {
         })
     }
 
+    /// Returns the summary of the wallet at the given path of the given mnemonic,
+    /// with the first `count` addresses of its chain.
+    pub fn to_summary<N: EthereumNetwork, W: EthereumWordlist>(
+        mnemonic: &str,
+        password: Option<&str>,
+        path: &str,
+        count: u32,
+    ) -> Result<WalletSummary, CLIError> {
+        let mnemonic = EthereumMnemonic::<N, W>::from_phrase(mnemonic)?;
+        WalletSummary::new(
+            &mnemonic.to_extended_private_key(password)?,
+            &EthereumDerivationPath::from_str(path)?,
+            &EthereumFormat::Standard,
+            count,
+            N::NAME,
+            EthereumExtendedPublicKey::to_fingerprint,
+        )
+    }
+
     pub fn from_extended_private_key<N: EthereumNetwork>(
         extended_private_key: &str,
         path: &Option<String>,
@@ -157,6 +329,184 @@ impl EthereumWallet {
         })
     }
 
+    /// Returns the wallet of the given address, searched for in the first `depth` indices of the children,
+    /// receive chain, and change chain of the given extended public key, with a path relative to the key.
+    pub fn find_in_extended_public_key<N: EthereumNetwork>(
+        extended_public_key: &str,
+        address: &str,
+        depth: u32,
+    ) -> Result<Self, CLIError> {
+        let extended_public_key = EthereumExtendedPublicKey::<N>::from_str(extended_public_key)?;
+        let address = EthereumAddress::from_str(address)?;
+        match Self::search_chains(&extended_public_key, &[None, Some(0), Some(1)], &address, depth)? {
+            Some((path, public_key)) => Ok(Self::from_found_public_key(format!("m{}", path), &public_key, &address)),
+            None => Err(CLIError::AddressNotFound(address.to_string(), depth)),
+        }
+    }
+
+    /// Returns the wallet of the given address, searched for in the first `depth` indices of the
+    /// common derivation paths of the given mnemonic: m/44'/60'/0'/0/{index} (Ethereum, MetaMask, Trezor),
+    /// m/44'/60'/0'/{index} (Electrum, Ledger Legacy), and m/44'/60'/{index}'/0/0 (Ledger Live).
+    pub fn find_in_mnemonic<N: EthereumNetwork, W: EthereumWordlist>(
+        mnemonic: &str,
+        password: Option<&str>,
+        address: &str,
+        depth: u32,
+    ) -> Result<Self, CLIError> {
+        let mnemonic = EthereumMnemonic::<N, W>::from_phrase(mnemonic)?;
+        let master_extended_private_key = mnemonic.to_extended_private_key(password)?;
+        let address = EthereumAddress::from_str(address)?;
+
+        let account_path = "m/44'/60'/0'";
+        let account = master_extended_private_key
+            .derive(&EthereumDerivationPath::from_str(account_path)?)?
+            .to_extended_public_key();
+        if let Some((path, public_key)) = Self::search_chains(&account, &[Some(0), None], &address, depth)? {
+            let path = format!("{}{}", account_path, path);
+            return Ok(Self::from_found_public_key(path, &public_key, &address));
+        }
+
+        // The accounts of Ledger Live are hardened, and thus derived from the private key
+        let coin = master_extended_private_key.derive(&EthereumDerivationPath::from_str("m/44'/60'")?)?;
+        for index in 0..depth {
+            let path = vec![
+                ChildIndex::hardened(index)?,
+                ChildIndex::Normal(0),
+                ChildIndex::Normal(0),
+            ];
+            let public_key = coin
+                .derive(&EthereumDerivationPath::try_from(path)?)?
+                .to_extended_public_key()
+                .to_public_key();
+            if public_key.to_address(&EthereumFormat::Standard)? == address {
+                let path = format!("m/44'/60'/{}'/0/0", index);
+                return Ok(Self::from_found_public_key(path, &public_key, &address));
+            }
+        }
+        Err(CLIError::AddressNotFound(address.to_string(), depth))
+    }
+
+    /// Returns the derivation paths of the given address in the first `depth` indices of the derivation
+    /// presets of the given mnemonic, with the wallets (presets) of each path.
+    pub fn discover_paths<N: EthereumNetwork, W: EthereumWordlist>(
+        mnemonic: &str,
+        password: Option<&str>,
+        address: &str,
+        depth: u32,
+    ) -> Result<Vec<EthereumDiscoveredPath>, CLIError> {
+        let mnemonic = EthereumMnemonic::<N, W>::from_phrase(mnemonic)?;
+        let master_extended_private_key = mnemonic.to_extended_private_key(password)?;
+        let address = EthereumAddress::from_str(address)?;
+
+        // The wallets of a template are searched together, with the first account of the template
+        let mut templates: BTreeMap<&String, Vec<String>> = BTreeMap::new();
+        let presets = DerivationPresets::load("ethereum")?;
+        for (name, template) in presets.presets() {
+            templates.entry(template).or_default().push(name.clone());
+        }
+
+        // Each key is derived from its parent, as the indices of a template share their parents
+        let mut keys = HashMap::new();
+        keys.insert("m".to_string(), master_extended_private_key);
+        let mut discovered: Vec<EthereumDiscoveredPath> = vec![];
+        for (template, wallets) in templates {
+            let indices = ChildNumberRange::new(0, depth.saturating_sub(1))?;
+            for path in DerivationPathTemplate::from_str(template)?.expand(&ChildNumberRange::from(0), &indices) {
+                let key = Self::derive_cached(&mut keys, &path)?;
+                if key.to_address(&EthereumFormat::Standard)? != address {
+                    continue;
+                }
+                match discovered.iter_mut().find(|discovered| discovered.path == path) {
+                    Some(discovered) => discovered.wallets.extend(wallets.iter().cloned()),
+                    None => discovered.push(EthereumDiscoveredPath {
+                        wallets: wallets.clone(),
+                        template: template.clone(),
+                        path,
+                        address: address.to_string(),
+                    }),
+                };
+            }
+        }
+
+        match discovered.is_empty() {
+            true => Err(CLIError::AddressNotFound(address.to_string(), depth)),
+            false => Ok(discovered),
+        }
+    }
+
+    /// Returns the extended private key of the given path, derived from the cached key of its parent.
+    fn derive_cached<N: EthereumNetwork>(
+        keys: &mut HashMap<String, EthereumExtendedPrivateKey<N>>,
+        path: &str,
+    ) -> Result<EthereumExtendedPrivateKey<N>, CLIError> {
+        if let Some(key) = keys.get(path) {
+            return Ok(key.clone());
+        }
+        let (parent, child) = path
+            .rsplit_once('/')
+            .ok_or_else(|| CLIError::InvalidEncoding(format!("invalid derivation path {}", path)))?;
+        let key =
+            Self::derive_cached(keys, parent)?.derive(&EthereumDerivationPath::from_str(&format!("m/{}", child))?)?;
+        keys.insert(path.to_string(), key.clone());
+        Ok(key)
+    }
+
+    /// Returns the relative path and public key of the given address in the first `depth` indices
+    /// of the given chains of the extended public key, where `None` is the key itself.
+    /// Each index is derived from its chain alone, as a chain is derived once for all of its indices.
+    fn search_chains<N: EthereumNetwork>(
+        extended_public_key: &EthereumExtendedPublicKey<N>,
+        chains: &[Option<u32>],
+        address: &EthereumAddress,
+        depth: u32,
+    ) -> Result<Option<(String, EthereumPublicKey)>, CLIError> {
+        for chain in chains {
+            let (chain_key, chain_path) = match chain {
+                Some(chain) => (
+                    extended_public_key
+                        .derive(&EthereumDerivationPath::try_from(vec![ChildIndex::normal(*chain)?])?)?,
+                    format!("/{}", chain),
+                ),
+                None => (extended_public_key.clone(), String::new()),
+            };
+            for index in 0..depth {
+                let path = EthereumDerivationPath::try_from(vec![ChildIndex::normal(index)?])?;
+                let public_key = chain_key.derive(&path)?.to_public_key();
+                if public_key.to_address(&EthereumFormat::Standard)? == *address {
+                    return Ok(Some((format!("{}/{}", chain_path, index), public_key)));
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Returns the wallet of a public key found for the given address.
+    fn from_found_public_key(path: String, public_key: &EthereumPublicKey, address: &EthereumAddress) -> Self {
+        Self {
+            path: Some(path),
+            public_key: Some(public_key.to_string()),
+            address: Some(address.to_string()),
+            ..Default::default()
+        }
+    }
+
+    pub fn to_extended_public_key<N: EthereumNetwork>(
+        extended_private_key: &str,
+        path: &Option<String>,
+    ) -> Result<Self, CLIError> {
+        let mut extended_private_key = EthereumExtendedPrivateKey::<N>::from_str(extended_private_key)?;
+        if let Some(derivation_path) = path {
+            let derivation_path = EthereumDerivationPath::from_str(derivation_path)?;
+            extended_private_key = extended_private_key.derive(&derivation_path)?;
+        }
+        let extended_public_key = extended_private_key.to_extended_public_key();
+        Ok(Self {
+            path: path.clone(),
+            extended_public_key: Some(extended_public_key.to_string()),
+            ..Default::default()
+        })
+    }
+
     pub fn from_private_key(private_key: &str) -> Result<Self, CLIError> {
         let private_key = EthereumPrivateKey::from_str(private_key)?;
         let public_key = private_key.to_public_key();
@@ -191,9 +541,9 @@ impl EthereumWallet {
         let transaction_parameters = EthereumTransactionParameters {
             receiver: EthereumAddress::from_str(&parameters.to)?,
             amount: EthereumAmount::from_wei(&parameters.value)?,
-            gas: EthereumAmount::u256_from_str(&parameters.gas)?,
+            gas: EthereumAmount::u256_from_str(parameters.gas.as_ref().ok_or(CLIError::MissingGas)?)?,
             gas_price: EthereumAmount::from_wei(&parameters.gas_price)?,
-            nonce: EthereumAmount::u256_from_str(&parameters.nonce.to_string())?,
+            nonce: EthereumAmount::u256_from_str(&parameters.nonce.ok_or(CLIError::MissingNonce)?.to_string())?,
             data: parameters.data.unwrap_or("".to_string()).as_bytes().to_vec(),
         };
 
@@ -206,9 +556,45 @@ impl EthereumWallet {
         })
     }
 
+    pub fn to_unsigned_transaction_file<N: EthereumNetwork>(
+        parameters: EthereumInput,
+        path: &str,
+    ) -> Result<Self, CLIError> {
+        let transaction_hex = Self::to_raw_transaction::<N>(parameters.clone())?
+            .transaction_hex
+            .unwrap_or_default();
+        UnsignedTransactionFile::new("ethereum", N::NAME, &transaction_hex, parameters).write(path)?;
+
+        Ok(Self {
+            network: Some(N::NAME.to_string()),
+            transaction_hex: Some(transaction_hex),
+            ..Default::default()
+        })
+    }
+
+    pub fn from_unsigned_transaction_file<N: EthereumNetwork>(
+        file: &UnsignedTransactionFile<EthereumInput>,
+        private_key: &str,
+        debug_signature: bool,
+    ) -> Result<Self, CLIError> {
+        // The transaction is rebuilt from its signing context to ensure the file was not altered
+        let transaction_hex = Self::to_raw_transaction::<N>(file.context.clone())?
+            .transaction_hex
+            .unwrap_or_default();
+        if transaction_hex != file.transaction_hex {
+            return Err(CLIError::MismatchedTransaction(file.transaction_hex.clone()));
+        }
+
+        Ok(Self {
+            network: Some(N::NAME.to_string()),
+            ..Self::to_signed_transaction::<N>(transaction_hex, private_key.to_string(), debug_signature)?
+        })
+    }
+
     pub fn to_signed_transaction<N: EthereumNetwork>(
         transaction_hex: String,
         private_key: String,
+        debug_signature: bool,
     ) -> Result<Self, CLIError> {
         let transaction_bytes = match &transaction_hex[0..2] {
             "0x" => hex::decode(&transaction_hex[2..])?,
@@ -218,6 +604,9 @@ impl EthereumWallet {
         let private_key = EthereumPrivateKey::from_str(&private_key)?;
 
         let mut transaction = EthereumTransaction::<N>::from_transaction_bytes(&transaction_bytes)?;
+        if debug_signature {
+            print_signature_nonce(&transaction.to_transaction_id()?.txid, &private_key)?;
+        }
         transaction = transaction.sign(&private_key)?;
 
         Ok(Self {
@@ -226,6 +615,273 @@ impl EthereumWallet {
             ..Default::default()
         })
     }
+
+    pub fn from_signature(
+        message: Option<&str>,
+        message_hash: Option<&str>,
+        signature: &str,
+    ) -> Result<Self, CLIError> {
+        let message_hash = match (message, message_hash) {
+            (Some(message), _) => EthereumSignature::personal_message_hash(message.as_bytes()).to_vec(),
+            (None, Some(message_hash)) => match message_hash.starts_with("0x") {
+                true => hex::decode(&message_hash[2..])?,
+                false => hex::decode(message_hash)?,
+            },
+            (None, None) => return Err(CLIError::MissingMessage),
+        };
+
+        let signature = EthereumSignature::from_str(signature)?;
+        let public_key = signature.recover(&message_hash)?;
+        let address = public_key.to_address(&EthereumFormat::Standard)?;
+
+        Ok(Self {
+            public_key: Some(public_key.to_string()),
+            address: Some(address.to_string()),
+            message_hash: Some(format!("0x{}", hex::encode(message_hash))),
+            signature: Some(signature.to_string()),
+            ..Default::default()
+        })
+    }
+
+    pub fn to_signed_batch_transaction<N: EthereumNetwork>(
+        extended_private_key: &str,
+        entry: &EthereumBatchTransaction,
+    ) -> Result<Self, CLIError> {
+        let derivation_path = EthereumDerivationPath::from_str(&entry.path)?;
+        let private_key = EthereumExtendedPrivateKey::<N>::from_str(extended_private_key)?
+            .derive(&derivation_path)?
+            .to_private_key();
+
+        Ok(Self {
+            path: Some(entry.path.clone()),
+            address: Some(private_key.to_address(&EthereumFormat::Standard)?.to_string()),
+            network: Some(N::NAME.to_string()),
+            ..Self::to_signed_transaction::<N>(entry.transaction_hex.clone(), private_key.to_string(), false)?
+        })
+    }
+
+    pub fn to_signed_typed_data(typed_data: &str, private_key: &str, debug_signature: bool) -> Result<Self, CLIError> {
+        let typed_data = TypedData::from_str(typed_data)?;
+        let private_key = EthereumPrivateKey::from_str(private_key)?;
+        let address = private_key.to_address(&EthereumFormat::Standard)?;
+        let signature = typed_data.sign(&private_key)?;
+        if debug_signature {
+            print_signature_nonce(&typed_data.to_signing_hash()?, &private_key)?;
+        }
+
+        Ok(Self {
+            address: Some(address.to_string()),
+            typed_data_hash: Some(format!("0x{}", hex::encode(typed_data.to_signing_hash()?))),
+            signature: Some(signature.to_string()),
+            ..Default::default()
+        })
+    }
+
+    pub fn to_contract_address(
+        deployer: &str,
+        nonce: Option<u64>,
+        salt: Option<&str>,
+        init_code: Option<&str>,
+        init_code_hash: Option<&str>,
+        implementation: Option<&str>,
+        create3: bool,
+    ) -> Result<Self, CLIError> {
+        let deployer = EthereumAddress::from_str(deployer)?;
+        let salt = match (nonce, salt) {
+            (Some(nonce), _) => {
+                return Ok(Self {
+                    address: Some(EthereumAddress::create(&deployer, nonce)?.to_string()),
+                    ..Default::default()
+                })
+            }
+            (None, Some(salt)) => to_bytes32("salt", salt)?,
+            (None, None) => return Ok(Self::default()),
+        };
+
+        if create3 {
+            return Ok(Self {
+                address: Some(EthereumAddress::create3(&deployer, &salt)?.to_string()),
+                ..Default::default()
+            });
+        }
+
+        let init_code_hash = match (implementation, init_code, init_code_hash) {
+            (Some(implementation), _, _) => tiny_keccak::keccak256(&EthereumAddress::minimal_proxy_init_code(
+                &EthereumAddress::from_str(implementation)?,
+            )?),
+            (None, Some(init_code), _) => tiny_keccak::keccak256(&hex::decode(init_code.trim_start_matches("0x"))?),
+            (None, None, Some(init_code_hash)) => to_bytes32("init code hash", init_code_hash)?,
+            (None, None, None) => return Ok(Self::default()),
+        };
+
+        Ok(Self {
+            address: Some(EthereumAddress::create2(&deployer, &salt, &init_code_hash)?.to_string()),
+            init_code_hash: Some(format!("0x{}", hex::encode(init_code_hash))),
+            ..Default::default()
+        })
+    }
+
+    pub fn to_node_identities(
+        private_key: &str,
+        seq: u64,
+        ip: Option<Ipv4Addr>,
+        tcp: Option<u16>,
+        udp: Option<u16>,
+    ) -> Result<Self, CLIError> {
+        let private_key = EthereumPrivateKey::from_str(private_key)?;
+
+        // The ports of a node with an IP address default to 30303, with discovery on the TCP port
+        let tcp = tcp.or_else(|| ip.map(|_| 30303));
+        let udp = udp.or(tcp);
+        let record = EthereumNodeRecord::new(&private_key, seq, ip.map(|ip| ip.octets()), tcp, udp)?;
+
+        // The devp2p node ID is the uncompressed public key of the node
+        let enode_id = hex::encode(&record.to_enode_id()[..]);
+        let enode = match (ip, tcp, udp) {
+            (Some(ip), Some(tcp), Some(udp)) if tcp != udp => {
+                Some(format!("enode://{}@{}:{}?discport={}", enode_id, ip, tcp, udp))
+            }
+            (Some(ip), Some(tcp), _) => Some(format!("enode://{}@{}:{}", enode_id, ip, tcp)),
+            _ => None,
+        };
+        let enr = record.to_rlp().to_base64(Config {
+            char_set: CharacterSet::UrlSafe,
+            newline: Newline::LF,
+            pad: false,
+            line_length: None,
+        });
+
+        Ok(Self {
+            public_key: Some(record.public_key.to_string()),
+            node_id: Some(hex::encode(record.to_node_id())),
+            enode,
+            enr: Some(format!("enr:{}", enr)),
+            peer_id: Some(record.to_peer_id()),
+            ..Default::default()
+        })
+    }
+
+    pub fn to_signed_permit(permit: Permit, private_key: &str, debug_signature: bool) -> Result<Self, CLIError> {
+        let private_key = EthereumPrivateKey::from_str(private_key)?;
+        let owner = private_key.to_address(&EthereumFormat::Standard)?;
+        let typed_data = Permit {
+            owner: owner.to_string(),
+            ..permit
+        }
+        .to_typed_data();
+        let signature = typed_data.sign(&private_key)?;
+        if debug_signature {
+            print_signature_nonce(&typed_data.to_signing_hash()?, &private_key)?;
+        }
+
+        Ok(Self {
+            address: Some(owner.to_string()),
+            typed_data_hash: Some(format!("0x{}", hex::encode(typed_data.to_signing_hash()?))),
+            signature: Some(signature.to_string()),
+            v: Some(signature.v.to_string()),
+            r: Some(format!("0x{}", hex::encode(signature.r))),
+            s: Some(format!("0x{}", hex::encode(signature.s))),
+            ..Default::default()
+        })
+    }
+
+    pub fn to_signed_user_operation(
+        user_operation: &str,
+        entry_point: &str,
+        chain_id: u64,
+        private_key: &str,
+        debug_signature: bool,
+    ) -> Result<Self, CLIError> {
+        let user_operation = UserOperation::from_str(user_operation)?;
+        let entry_point = EthereumAddress::from_str(entry_point)?;
+        let private_key = EthereumPrivateKey::from_str(private_key)?;
+        let address = private_key.to_address(&EthereumFormat::Standard)?;
+        let user_op_hash = user_operation.to_hash(&entry_point, chain_id)?;
+        let signature = user_operation.sign(&entry_point, chain_id, &private_key)?;
+        if debug_signature {
+            print_signature_nonce(&EthereumSignature::personal_message_hash(&user_op_hash), &private_key)?;
+        }
+
+        Ok(Self {
+            address: Some(address.to_string()),
+            user_op_hash: Some(format!("0x{}", hex::encode(user_op_hash))),
+            signature: Some(signature.to_string()),
+            ..Default::default()
+        })
+    }
+}
+
+/// Returns the 32 bytes of the given hex value of the given option, with or without a `0x` prefix.
+fn to_bytes32(option: &str, value: &str) -> Result<[u8; 32], CLIError> {
+    match hex::decode(value.trim_start_matches("0x")) {
+        Ok(bytes) if bytes.len() == 32 => {
+            let mut bytes32 = [0u8; 32];
+            bytes32.copy_from_slice(&bytes);
+            Ok(bytes32)
+        }
+        _ => Err(CLIError::InvalidEncoding(format!(
+            "invalid {}: {} (expected 32 bytes in hex)",
+            option, value
+        ))),
+    }
+}
+
+/// Prints the derivation of the RFC 6979 nonce of the signature of the given message hash
+/// with the given private key to stderr, for auditing that the nonce is not biased.
+/// The nonce is derived independently of the signer, and checked against the R field of its signature.
+fn print_signature_nonce(message_hash: &[u8], private_key: &EthereumPrivateKey) -> Result<(), CLIError> {
+    let nonce = SignatureNonce::derive(message_hash, private_key)?;
+    let signature = EthereumSignature::sign(message_hash, private_key)?;
+
+    eprintln!(
+        "\n      {}",
+        "UNSAFE DIAGNOSTIC: the nonce reveals the private key, never share this output"
+            .red()
+            .bold()
+    );
+    eprintln!(
+        "      {:<34} {}",
+        "h1 = message hash".cyan().bold(),
+        hex::encode(message_hash)
+    );
+    for (step, value) in &nonce.steps {
+        eprintln!("      {:<34} {}", step.cyan().bold(), hex::encode(value));
+    }
+    eprintln!("      {:<34} {}", "k".cyan().bold(), hex::encode(nonce.k));
+    eprintln!(
+        "      {:<34} {}",
+        "r = x(k * G) mod n".cyan().bold(),
+        hex::encode(nonce.r)
+    );
+    match nonce.r == signature.r {
+        true => eprintln!("      {:<34} {}", "Signature R".cyan().bold(), "matches r".green()),
+        false => eprintln!(
+            "      {:<34} {}",
+            "Signature R".cyan().bold(),
+            "DOES NOT match r".red().bold()
+        ),
+    };
+    Ok(())
+}
+
+/// Returns the parameters of the given network, with the default derivation path of its wallets,
+/// and the extended key versions of its HD wallets.
+fn to_network_info<N: EthereumNetwork>() -> Result<serde_json::Value, CLIError> {
+    let path = EthereumOptions::default()
+        .to_derivation_paths()?
+        .into_iter()
+        .next()
+        .flatten();
+    Ok(serde_json::json!({
+        "network": N::NAME,
+        "chain_id": N::CHAIN_ID,
+        "network_id": N::NETWORK_ID,
+        "coin_type": to_coin_type(N::HD_COIN_TYPE),
+        "default_derivation_path": path,
+        "formats": ["standard"],
+        "extended_private_key_version": hex::encode(N::EXTENDED_PRIVATE_KEY_VERSION_BYTES),
+        "extended_public_key_version": hex::encode(N::EXTENDED_PUBLIC_KEY_VERSION_BYTES),
+    }))
 }
 
 #[cfg_attr(tarpaulin, skip)]
@@ -272,6 +928,26 @@ impl Display for EthereumWallet {
                 Some(address) => format!("      {}              {}\n", "Address".cyan().bold(), address),
                 _ => "".to_owned(),
             },
+            match &self.init_code_hash {
+                Some(init_code_hash) => format!("      {}       {}\n", "Init Code Hash".cyan().bold(), init_code_hash),
+                _ => "".to_owned(),
+            },
+            match &self.node_id {
+                Some(node_id) => format!("      {}              {}\n", "Node Id".cyan().bold(), node_id),
+                _ => "".to_owned(),
+            },
+            match &self.enode {
+                Some(enode) => format!("      {}                {}\n", "Enode".cyan().bold(), enode),
+                _ => "".to_owned(),
+            },
+            match &self.enr {
+                Some(enr) => format!("      {}                  {}\n", "ENR".cyan().bold(), enr),
+                _ => "".to_owned(),
+            },
+            match &self.peer_id {
+                Some(peer_id) => format!("      {}              {}\n", "Peer Id".cyan().bold(), peer_id),
+                _ => "".to_owned(),
+            },
             match &self.transaction_id {
                 Some(transaction_id) => format!("      {}       {}\n", "Transaction Id".cyan().bold(), transaction_id),
                 _ => "".to_owned(),
@@ -286,6 +962,81 @@ impl Display for EthereumWallet {
                 }
                 _ => "".to_owned(),
             },
+            match (&self.gas, &self.gas_estimation) {
+                (Some(gas), Some(gas_estimation)) => format!(
+                    "      {}                  {} ({})\n",
+                    "Gas".cyan().bold(),
+                    gas,
+                    gas_estimation
+                ),
+                (Some(gas), None) => format!("      {}                  {}\n", "Gas".cyan().bold(), gas),
+                _ => "".to_owned(),
+            },
+            match &self.message_hash {
+                Some(message_hash) => format!("      {}         {}\n", "Message Hash".cyan().bold(), message_hash),
+                _ => "".to_owned(),
+            },
+            match &self.typed_data_hash {
+                Some(typed_data_hash) => {
+                    format!("      {}      {}\n", "Typed Data Hash".cyan().bold(), typed_data_hash)
+                }
+                _ => "".to_owned(),
+            },
+            match &self.user_op_hash {
+                Some(user_op_hash) => format!("      {}          {}\n", "UserOp Hash".cyan().bold(), user_op_hash),
+                _ => "".to_owned(),
+            },
+            match &self.signature {
+                Some(signature) => format!("      {}            {}\n", "Signature".cyan().bold(), signature),
+                _ => "".to_owned(),
+            },
+            match &self.v {
+                Some(v) => format!("      {}                    {}\n", "V".cyan().bold(), v),
+                _ => "".to_owned(),
+            },
+            match &self.r {
+                Some(r) => format!("      {}                    {}\n", "R".cyan().bold(), r),
+                _ => "".to_owned(),
+            },
+            match &self.s {
+                Some(s) => format!("      {}                    {}\n", "S".cyan().bold(), s),
+                _ => "".to_owned(),
+            },
+        ]
+        .concat();
+
+        // Removes final new line character
+        let output = output[..output.len() - 1].to_owned();
+        write!(f, "\n{}", output)
+    }
+}
+
+/// Represents a derivation path of a mnemonic that derives a searched address
+#[derive(Serialize, Clone, Debug)]
+pub struct EthereumDiscoveredPath {
+    /// The wallets (derivation presets) that derive the address
+    pub wallets: Vec<String>,
+    pub template: String,
+    pub path: String,
+    pub address: String,
+}
+
+impl MaskSecrets for EthereumDiscoveredPath {
+    fn mask_secrets(&mut self) {}
+}
+
+#[cfg_attr(tarpaulin, skip)]
+impl Display for EthereumDiscoveredPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let output = [
+            format!(
+                "      {}              {}\n",
+                "Wallets".cyan().bold(),
+                self.wallets.join(", ")
+            ),
+            format!("      {}             {}\n", "Template".cyan().bold(), self.template),
+            format!("      {}                 {}\n", "Path".cyan().bold(), self.path),
+            format!("      {}              {}\n", "Address".cyan().bold(), self.address),
         ]
         .concat();
 
@@ -300,33 +1051,131 @@ impl Display for EthereumWallet {
 pub struct EthereumInput {
     pub to: String,
     pub value: String,
-    pub gas: String,
-    #[serde(rename(deserialize = "gasPrice"))]
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub gas: Option<String>,
+    #[serde(alias = "gasPrice")]
     pub gas_price: String,
-    pub nonce: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nonce: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub data: Option<String>,
 }
 
+impl Schema for EthereumInput {
+    fn schema() -> serde_json::Value {
+        object(
+            vec![
+                ("to", property("string", "The address of the receiver")),
+                ("value", property("string", "The amount in wei")),
+                ("gas", property("string", "The gas limit")),
+                ("gas_price", property("string", "The gas price in wei")),
+                ("nonce", property("integer", "The nonce of the sender")),
+                ("from", property("string", "The address of the sender")),
+                ("data", property("string", "The data of the transaction")),
+            ],
+            &["to", "value", "gas_price"],
+        )
+    }
+}
+
+/// Represents a prepared transaction of a batch file, signed with the key derived at its derivation path
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct EthereumBatchTransaction {
+    pub path: String,
+    pub transaction_hex: String,
+}
+
+impl EthereumInput {
+    /// Sets `gas` to the gas limit estimated by the node at the given RPC URL with `eth_estimateGas`,
+    /// or to the default gas limit of the operation if no RPC URL is specified.
+    /// Returns the method of the estimation, `rpc` or `default`.
+    pub fn estimate_gas(&mut self, rpc_url: Option<&str>) -> Result<String, CLIError> {
+        let data = self.data.clone().unwrap_or_default();
+
+        let (gas, method) = match rpc_url {
+            Some(rpc_url) => {
+                let mut call = serde_json::json!({
+                    "to": EthereumAddress::from_str(&self.to)?.to_string(),
+                    "value": format!("{:#x}", EthereumAmount::from_wei(&self.value)?.0),
+                    "data": format!("0x{}", hex::encode(data.as_bytes())),
+                });
+                if let Some(from) = &self.from {
+                    call["from"] = EthereumAddress::from_str(from)?.to_string().into();
+                }
+
+                let gas = match rpc_call(rpc_url, "eth_estimateGas", serde_json::json!([call]))? {
+                    serde_json::Value::String(gas) => u64::from_str_radix(gas.trim_start_matches("0x"), 16)
+                        .map_err(|_| CLIError::RpcError(format!("invalid gas estimate: {}", gas)))?,
+                    gas => return Err(CLIError::RpcError(format!("invalid gas estimate: {}", gas))),
+                };
+                (gas, "rpc")
+            }
+            None => {
+                // The operation is identified by the function selector of the call data, if any
+                let selector = data.trim_start_matches("0x").to_lowercase();
+                let gas = match selector.get(..8) {
+                    None if data.is_empty() => TRANSFER_GAS,
+                    Some(selector) if ERC20_SELECTORS.contains(&selector) => ERC20_GAS,
+                    _ => CONTRACT_CALL_GAS,
+                };
+                (gas, "default")
+            }
+        };
+
+        self.gas = Some(gas.to_string());
+        Ok(method.to_string())
+    }
+}
+
 /// Represents options for an Ethereum wallet
 #[derive(Clone, Debug, Serialize)]
 pub struct EthereumOptions {
     // Standard command
+    clear_after: u64,
+    confirm_backup: bool,
     count: usize,
+    copy: Option<String>,
+    encrypt: bool,
+    encrypt_to: Option<String>,
     json: bool,
+    manifest: Option<String>,
+    mask_secrets: bool,
+    reveal: bool,
+    save: Option<String>,
     subcommand: Option<String>,
     // HD and Import HD subcommands
+    #[serde(skip)]
+    account_range: ChildNumberRange,
     derivation: String,
     extended_private_key: Option<String>,
     extended_public_key: Option<String>,
-    index: u32,
+    #[serde(skip)]
+    index_range: ChildNumberRange,
     indices: u32,
     language: String,
+    list_presets: bool,
     mnemonic: Option<String>,
     password: Option<String>,
     path: Option<String>,
     word_count: u8,
+    wordlist_file: Option<String>,
+    // HD tree
+    breadth: u32,
+    depth: u32,
+    tree: bool,
+    // Import HD summary
+    summary: bool,
+    summary_count: u32,
+    // Import keystore and import HD keystore export
+    keystore: Option<String>,
+    // Contains subcommand
+    search_depth: u32,
     // Import subcommand
     address: Option<String>,
+    column: Option<String>,
+    file: Option<String>,
     private: Option<String>,
     public: Option<String>,
     // Transaction subcommand
@@ -334,28 +1183,88 @@ pub struct EthereumOptions {
     transaction_parameters: Option<String>,
     transaction_private_key: Option<String>,
     network: Option<String>,
+    // Create transaction and sign transaction subcommands
+    nonce_file: Option<String>,
+    unsigned_transaction: Option<String>,
+    // Create transaction and transaction subcommands
+    estimate_gas: bool,
+    rpc_url: Option<String>,
+    // Sign subcommands
+    debug_signature: bool,
+    message: Option<String>,
+    message_hash: Option<String>,
+    signature: Option<String>,
+    typed_data: Option<String>,
+    // Contract address subcommand
+    create3: bool,
+    deployer: Option<String>,
+    implementation: Option<String>,
+    init_code: Option<String>,
+    init_code_hash: Option<String>,
+    salt: Option<String>,
+    // Node ID subcommand
+    ip: Option<String>,
+    seq: Option<String>,
+    tcp_port: Option<String>,
+    udp_port: Option<String>,
+    // Permit and sign userop subcommands
+    chain_id: Option<u64>,
+    deadline: Option<String>,
+    entry_point: Option<String>,
+    nonce: Option<String>,
+    spender: Option<String>,
+    token: Option<String>,
+    token_name: Option<String>,
+    token_version: String,
+    user_operation: Option<String>,
+    value: Option<String>,
 }
 
 impl Default for EthereumOptions {
     fn default() -> Self {
         Self {
             // Standard command
+            clear_after: CLIPBOARD_CLEAR_AFTER,
+            confirm_backup: false,
             count: 1,
+            copy: None,
+            encrypt: false,
+            encrypt_to: None,
             json: false,
+            manifest: None,
+            mask_secrets: false,
+            reveal: false,
+            save: None,
             subcommand: None,
             // HD and Import HD subcommands
+            account_range: ChildNumberRange::from(0),
             derivation: "ethereum".into(),
             extended_private_key: None,
             extended_public_key: None,
-            index: 0,
+            index_range: ChildNumberRange::from(0),
             indices: 1,
             language: "english".into(),
+            list_presets: false,
             mnemonic: None,
             password: None,
             path: None,
             word_count: 12,
+            wordlist_file: None,
+            // HD tree
+            breadth: 5,
+            depth: MAX_TREE_DEPTH,
+            tree: false,
+            // Import HD summary
+            summary: false,
+            summary_count: SUMMARY_COUNT,
+            // Import keystore and import HD keystore export
+            keystore: None,
+            // Contains subcommand
+            search_depth: 1000,
             // Import subcommand
             address: None,
+            column: None,
+            file: None,
             private: None,
             public: None,
             // Transaction subcommand
@@ -363,6 +1272,41 @@ impl Default for EthereumOptions {
             transaction_parameters: None,
             transaction_private_key: None,
             network: None,
+            // Create transaction and sign transaction subcommands
+            nonce_file: None,
+            unsigned_transaction: None,
+            // Create transaction and transaction subcommands
+            estimate_gas: false,
+            rpc_url: None,
+            // Sign subcommands
+            debug_signature: false,
+            message: None,
+            message_hash: None,
+            signature: None,
+            typed_data: None,
+            // Contract address subcommand
+            create3: false,
+            deployer: None,
+            implementation: None,
+            init_code: None,
+            init_code_hash: None,
+            salt: None,
+            // Node ID subcommand
+            ip: None,
+            seq: None,
+            tcp_port: None,
+            udp_port: None,
+            // Permit and sign userop subcommands
+            chain_id: None,
+            deadline: None,
+            entry_point: None,
+            nonce: None,
+            spender: None,
+            token: None,
+            token_name: None,
+            token_version: "1".into(),
+            user_operation: None,
+            value: None,
         }
     }
 }
@@ -370,27 +1314,100 @@ impl Default for EthereumOptions {
 impl EthereumOptions {
     fn parse(&mut self, arguments: &ArgMatches, options: &[&str]) {
         options.iter().for_each(|option| match *option {
+            "account" => self.account(arguments.value_of(option)),
             "address" => self.address(arguments.value_of(option)),
+            "breadth" => self.breadth(clap::value_t!(arguments.value_of(*option), u32).ok()),
+            "chain id" => self.chain_id(clap::value_t!(arguments.value_of(*option), u64).ok()),
+            "clear after" => self.clear_after(clap::value_t!(arguments.value_of(*option), u64).ok()),
+            "column" => self.column(arguments.value_of(option)),
+            "confirm backup" => self.confirm_backup(arguments.is_present(option)),
+            "copy" => self.copy(arguments.value_of(option)),
             "count" => self.count(clap::value_t!(arguments.value_of(*option), usize).ok()),
+            "create3" => self.create3(arguments.is_present(option)),
             "createrawtransaction" => self.create_raw_transaction(arguments.value_of(option)),
+            "deadline" => self.deadline(arguments.value_of(option)),
+            "debug signature" => self.debug_signature(arguments.is_present(option)),
+            "deployer" => self.deployer(arguments.value_of(option)),
+            "depth" => self.depth(clap::value_t!(arguments.value_of(*option), u32).ok()),
             "derivation" => self.derivation(arguments.value_of(option)),
+            "encrypt" => self.encrypt(arguments.is_present(option)),
+            "encrypt to" => self.encrypt_to(arguments.value_of(option)),
+            "entry point" => self.entry_point(arguments.value_of(option)),
+            "estimate gas" => self.estimate_gas(arguments.is_present(option)),
             "extended private" => self.extended_private(arguments.value_of(option)),
+            "extended private key" => self.extended_private(arguments.value_of(option)),
             "extended public" => self.extended_public(arguments.value_of(option)),
+            "file" => self.file(arguments.value_of(option)),
+            "ip" => self.ip(arguments.value_of(option)),
             "json" => self.json(arguments.is_present(option)),
-            "index" => self.index(clap::value_t!(arguments.value_of(*option), u32).ok()),
+            "implementation" => self.implementation(arguments.value_of(option)),
+            "index" => self.index(arguments.value_of(option)),
             "indices" => self.indices(clap::value_t!(arguments.value_of(*option), u32).ok()),
+            "init code" => self.init_code(arguments.value_of(option)),
+            "init code hash" => self.init_code_hash(arguments.value_of(option)),
+            "keystore" => self.keystore(arguments.value_of(option)),
             "language" => self.language(arguments.value_of(option)),
+            "list presets" => self.list_presets(arguments.is_present(option)),
+            "manifest" => self.manifest(arguments.value_of(option)),
+            "mask secrets" => self.mask_secrets(arguments.is_present(option)),
+            "message" => self.message(arguments.value_of(option)),
+            "message hash" => self.message_hash(arguments.value_of(option)),
             "mnemonic" => self.mnemonic(arguments.value_of(option)),
             "network" => self.network(arguments.value_of(option)),
+            "nonce" => self.nonce(arguments.value_of(option)),
+            "nonce file" => self.nonce_file(arguments.value_of(option)),
+            "parameters" => self.create_raw_transaction(arguments.value_of(option)),
             "password" => self.password(arguments.value_of(option)),
+            "path" => self.path(arguments.value_of(option)),
             "private" => self.private(arguments.value_of(option)),
+            "private key" => self.private(arguments.value_of(option)),
             "public" => self.public(arguments.value_of(option)),
+            "reveal" => self.reveal(arguments.is_present(option)),
+            "rpc url" => self.rpc_url(arguments.value_of(option)),
+            "salt" => self.salt(arguments.value_of(option)),
+            "save" => self.save(arguments.value_of(option)),
+            "search depth" => self.search_depth(clap::value_t!(arguments.value_of(*option), u32).ok()),
+            "seq" => self.seq(arguments.value_of(option)),
+            "signature" => self.signature(arguments.value_of(option)),
             "signrawtransaction" => self.sign_raw_transaction(arguments.values_of(option)),
+            "spender" => self.spender(arguments.value_of(option)),
+            "summary" => self.summary(arguments.is_present(option)),
+            "summary count" => self.summary_count(clap::value_t!(arguments.value_of(*option), u32).ok()),
+            "tcp port" => self.tcp_port(arguments.value_of(option)),
+            "token" => self.token(arguments.value_of(option)),
+            "token name" => self.token_name(arguments.value_of(option)),
+            "token version" => self.token_version(arguments.value_of(option)),
+            "tree" => self.tree(arguments.is_present(option)),
+            "typed data" => self.typed_data(arguments.value_of(option)),
+            "udp port" => self.udp_port(arguments.value_of(option)),
+            "unsigned tx" => self.unsigned_transaction(arguments.value_of(option)),
+            "userop" => self.user_operation(arguments.value_of(option)),
+            "value" => self.value(arguments.value_of(option)),
             "word count" => self.word_count(clap::value_t!(arguments.value_of(*option), u8).ok()),
+            "wordlist file" => self.wordlist_file(arguments.value_of(option)),
+            "xpub" => self.extended_public(arguments.value_of(option)),
             _ => (),
         });
     }
 
+    /// Sanitizes the key material of the options, with `--strict` rejecting key material in a non-canonical form.
+    fn sanitize_inputs(&mut self) -> Result<(), CLIError> {
+        sanitize_option("address", &mut self.address, InputKind::Text)?;
+        sanitize_option("extended private key", &mut self.extended_private_key, InputKind::Text)?;
+        sanitize_option("extended public key", &mut self.extended_public_key, InputKind::Text)?;
+        sanitize_option("mnemonic", &mut self.mnemonic, InputKind::Mnemonic)?;
+        sanitize_option("private key", &mut self.private, InputKind::Hex)?;
+        sanitize_option("public key", &mut self.public, InputKind::Hex)
+    }
+
+    /// Sets `account_range` to the specified account index or range, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn account(&mut self, argument: Option<&str>) {
+        if let Some(Ok(account)) = argument.map(ChildNumberRange::from_str) {
+            self.account_range = account;
+        }
+    }
+
     /// Imports a wallet for the specified address, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
     fn address(&mut self, argument: Option<&str>) {
@@ -399,6 +1416,51 @@ impl EthereumOptions {
         }
     }
 
+    /// Sets `breadth` to the specified number of accounts and addresses of a tree, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn breadth(&mut self, argument: Option<u32>) {
+        if let Some(breadth) = argument {
+            self.breadth = breadth;
+        }
+    }
+
+    /// Sets `chain_id` to the specified chain id, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn chain_id(&mut self, argument: Option<u64>) {
+        if let Some(chain_id) = argument {
+            self.chain_id = Some(chain_id);
+        }
+    }
+
+    /// Sets `clear_after` to the specified number of seconds, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn clear_after(&mut self, argument: Option<u64>) {
+        if let Some(seconds) = argument {
+            self.clear_after = seconds;
+        }
+    }
+
+    /// Sets `column` to the specified CSV column of a batch file, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn column(&mut self, argument: Option<&str>) {
+        if let Some(column) = argument {
+            self.column = Some(column.to_string());
+        }
+    }
+
+    /// Sets `confirm_backup` to the specified boolean value, overriding its previous state.
+    fn confirm_backup(&mut self, argument: bool) {
+        self.confirm_backup = argument;
+    }
+
+    /// Sets `copy` to the specified wallet field, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn copy(&mut self, argument: Option<&str>) {
+        if let Some(field) = argument {
+            self.copy = Some(field.to_string());
+        }
+    }
+
     /// Sets `count` to the specified count, overriding its previous state.
     fn count(&mut self, argument: Option<usize>) {
         if let Some(count) = argument {
@@ -414,24 +1476,88 @@ impl EthereumOptions {
         }
     }
 
-    /// Sets `derivation` to the specified derivation, overriding its previous state.
-    /// If `derivation` is `\"custom\"`, then `path` is set to the specified path.
+    /// Sets `create3` to the specified boolean value, overriding its previous state.
+    fn create3(&mut self, argument: bool) {
+        if argument {
+            self.create3 = true;
+        }
+    }
+
+    /// Sets `deadline` to the specified deadline, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn deadline(&mut self, argument: Option<&str>) {
+        if let Some(deadline) = argument {
+            self.deadline = Some(deadline.to_string());
+        }
+    }
+
+    /// Sets `debug_signature` to the specified boolean value, overriding its previous state.
+    fn debug_signature(&mut self, argument: bool) {
+        if argument {
+            self.debug_signature = true;
+        }
+    }
+
+    /// Sets `deployer` to the specified deployer address, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn deployer(&mut self, argument: Option<&str>) {
+        if let Some(deployer) = argument {
+            self.deployer = Some(deployer.to_string());
+        }
+    }
+
+    /// Sets `depth` to the specified number of levels of a tree below its accounts, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn depth(&mut self, argument: Option<u32>) {
+        if let Some(depth) = argument {
+            self.depth = depth;
+        }
+    }
+
+    /// Sets `derivation` to the specified derivation preset, overriding its previous state.
+    /// If `derivation` is a path, then `derivation` is set to `\"custom\"` and `path` is set to the specified path.
     /// If the specified argument is `None`, then no change occurs.
     fn derivation(&mut self, argument: Option<&str>) {
         match argument {
-            Some("ethereum") => self.derivation = "ethereum".into(),
-            Some("keepkey") => self.derivation = "keepkey".into(),
-            Some("ledger-legacy") => self.derivation = "ledger-legacy".into(),
-            Some("ledger-live") => self.derivation = "ledger-legacy".into(),
-            Some("trezor") => self.derivation = "trezor".into(),
-            Some(custom) => {
+            Some(custom) if custom == "m" || custom.starts_with("m/") => {
                 self.derivation = "custom".into();
                 self.path = Some(custom.to_string());
             }
+            Some(preset) => self.derivation = preset.to_string(),
             _ => (),
         };
     }
 
+    /// Sets `encrypt` to the specified boolean value, overriding its previous state.
+    fn encrypt(&mut self, argument: bool) {
+        if argument {
+            self.encrypt = true;
+        }
+    }
+
+    /// Sets `encrypt_to` to the specified recipients, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn encrypt_to(&mut self, argument: Option<&str>) {
+        if let Some(recipients) = argument {
+            self.encrypt_to = Some(recipients.to_string());
+        }
+    }
+
+    /// Sets `entry_point` to the specified entry point address, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn entry_point(&mut self, argument: Option<&str>) {
+        if let Some(entry_point) = argument {
+            self.entry_point = Some(entry_point.to_string());
+        }
+    }
+
+    /// Sets `estimate_gas` to the specified boolean value, overriding its previous state.
+    fn estimate_gas(&mut self, argument: bool) {
+        if argument {
+            self.estimate_gas = true;
+        }
+    }
+
     /// Sets `extended_private_key` to the specified extended private key, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
     fn extended_private(&mut self, argument: Option<&str>) {
@@ -448,11 +1574,27 @@ impl EthereumOptions {
         }
     }
 
-    /// Sets `index` to the specified index, overriding its previous state.
+    /// Sets `file` to the specified batch file of keys or addresses, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn file(&mut self, argument: Option<&str>) {
+        if let Some(path) = argument {
+            self.file = Some(path.to_string());
+        }
+    }
+
+    /// Sets `implementation` to the specified implementation address, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
-    fn index(&mut self, argument: Option<u32>) {
-        if let Some(index) = argument {
-            self.index = index;
+    fn implementation(&mut self, argument: Option<&str>) {
+        if let Some(implementation) = argument {
+            self.implementation = Some(implementation.to_string());
+        }
+    }
+
+    /// Sets `index_range` to the specified index or range, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn index(&mut self, argument: Option<&str>) {
+        if let Some(Ok(index)) = argument.map(ChildNumberRange::from_str) {
+            self.index_range = index;
         }
     }
 
@@ -464,11 +1606,43 @@ impl EthereumOptions {
         }
     }
 
+    /// Sets `init_code` to the specified init code, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn init_code(&mut self, argument: Option<&str>) {
+        if let Some(init_code) = argument {
+            self.init_code = Some(init_code.to_string());
+        }
+    }
+
+    /// Sets `init_code_hash` to the specified init code hash, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn init_code_hash(&mut self, argument: Option<&str>) {
+        if let Some(init_code_hash) = argument {
+            self.init_code_hash = Some(init_code_hash.to_string());
+        }
+    }
+
+    /// Sets `ip` to the specified IP address, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn ip(&mut self, argument: Option<&str>) {
+        if let Some(ip) = argument {
+            self.ip = Some(ip.to_string());
+        }
+    }
+
     /// Sets `json` to the specified boolean value, overriding its previous state.
     fn json(&mut self, argument: bool) {
         self.json = argument;
     }
 
+    /// Sets `keystore` to the specified keystore file or directory, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn keystore(&mut self, argument: Option<&str>) {
+        if let Some(directory) = argument {
+            self.keystore = Some(directory.to_string());
+        }
+    }
+
     /// Sets `language` to the specified language, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
     fn language(&mut self, argument: Option<&str>) {
@@ -485,6 +1659,40 @@ impl EthereumOptions {
         };
     }
 
+    /// Sets `list_presets` to the specified boolean value, overriding its previous state.
+    fn list_presets(&mut self, argument: bool) {
+        self.list_presets = argument;
+    }
+
+    /// Sets `manifest` to the specified manifest file, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn manifest(&mut self, argument: Option<&str>) {
+        if let Some(manifest) = argument {
+            self.manifest = Some(manifest.to_string());
+        }
+    }
+
+    /// Sets `mask_secrets` to the specified boolean value, overriding its previous state.
+    fn mask_secrets(&mut self, argument: bool) {
+        self.mask_secrets = argument;
+    }
+
+    /// Sets `message` to the specified message, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn message(&mut self, argument: Option<&str>) {
+        if let Some(message) = argument {
+            self.message = Some(message.to_string());
+        }
+    }
+
+    /// Sets `message_hash` to the specified message hash, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn message_hash(&mut self, argument: Option<&str>) {
+        if let Some(message_hash) = argument {
+            self.message_hash = Some(message_hash.to_string());
+        }
+    }
+
     /// Sets `mnemonic` to the specified mnemonic, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
     fn mnemonic(&mut self, argument: Option<&str>) {
@@ -501,6 +1709,22 @@ impl EthereumOptions {
         }
     }
 
+    /// Sets `nonce` to the specified permit nonce, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn nonce(&mut self, argument: Option<&str>) {
+        if let Some(nonce) = argument {
+            self.nonce = Some(nonce.to_string());
+        }
+    }
+
+    /// Sets `nonce_file` to the specified nonce file, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn nonce_file(&mut self, argument: Option<&str>) {
+        if let Some(nonce_file) = argument {
+            self.nonce_file = Some(nonce_file.to_string());
+        }
+    }
+
     /// Sets `password` to the specified password, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
     fn password(&mut self, argument: Option<&str>) {
@@ -509,6 +1733,14 @@ impl EthereumOptions {
         }
     }
 
+    /// Sets `path` to the specified derivation path, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn path(&mut self, argument: Option<&str>) {
+        if let Some(path) = argument {
+            self.path = Some(path.to_string());
+        }
+    }
+
     /// Imports a wallet for the specified private key, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
     fn private(&mut self, argument: Option<&str>) {
@@ -525,6 +1757,59 @@ impl EthereumOptions {
         }
     }
 
+    /// Sets `reveal` to the specified boolean value, overriding its previous state.
+    fn reveal(&mut self, argument: bool) {
+        self.reveal = argument;
+    }
+
+    /// Sets `rpc_url` to the specified RPC URL, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn rpc_url(&mut self, argument: Option<&str>) {
+        if let Some(rpc_url) = argument {
+            self.rpc_url = Some(rpc_url.to_string());
+        }
+    }
+
+    /// Sets `salt` to the specified salt, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn salt(&mut self, argument: Option<&str>) {
+        if let Some(salt) = argument {
+            self.salt = Some(salt.to_string());
+        }
+    }
+
+    /// Sets `save` to the specified wallet file, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn save(&mut self, argument: Option<&str>) {
+        if let Some(save) = argument {
+            self.save = Some(save.to_string());
+        }
+    }
+
+    /// Sets `search_depth` to the specified number of indices, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn search_depth(&mut self, argument: Option<u32>) {
+        if let Some(depth) = argument {
+            self.search_depth = depth;
+        }
+    }
+
+    /// Sets `seq` to the specified sequence number, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn seq(&mut self, argument: Option<&str>) {
+        if let Some(seq) = argument {
+            self.seq = Some(seq.to_string());
+        }
+    }
+
+    /// Sets `signature` to the specified signature, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn signature(&mut self, argument: Option<&str>) {
+        if let Some(signature) = argument {
+            self.signature = Some(signature.to_string());
+        }
+    }
+
     /// Sets `transaction_hex` and `transaction_private_key` to the specified transaction values, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
     fn sign_raw_transaction(&mut self, argument: Option<Values>) {
@@ -535,6 +1820,116 @@ impl EthereumOptions {
         }
     }
 
+    /// Sets `spender` to the specified spender address, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn spender(&mut self, argument: Option<&str>) {
+        if let Some(spender) = argument {
+            self.spender = Some(spender.to_string());
+        }
+    }
+
+    /// Sets `summary` to the specified boolean value, overriding its previous state.
+    fn summary(&mut self, argument: bool) {
+        if argument {
+            self.summary = true;
+        }
+    }
+
+    /// Sets `summary_count` to the specified number of addresses of a summary, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn summary_count(&mut self, argument: Option<u32>) {
+        if let Some(count) = argument {
+            self.summary_count = count;
+        }
+    }
+
+    /// Sets `tcp_port` to the specified TCP port, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn tcp_port(&mut self, argument: Option<&str>) {
+        if let Some(tcp_port) = argument {
+            self.tcp_port = Some(tcp_port.to_string());
+        }
+    }
+
+    /// Sets `token` to the specified token address, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn token(&mut self, argument: Option<&str>) {
+        if let Some(token) = argument {
+            self.token = Some(token.to_string());
+        }
+    }
+
+    /// Sets `token_name` to the specified token name, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn token_name(&mut self, argument: Option<&str>) {
+        if let Some(token_name) = argument {
+            self.token_name = Some(token_name.to_string());
+        }
+    }
+
+    /// Sets `token_version` to the specified token version, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn token_version(&mut self, argument: Option<&str>) {
+        if let Some(token_version) = argument {
+            self.token_version = token_version.to_string();
+        }
+    }
+
+    /// Sets `tree` to the specified boolean value, overriding its previous state.
+    fn tree(&mut self, argument: bool) {
+        if argument {
+            self.tree = true;
+        }
+    }
+
+    /// Sets `typed_data` to the specified typed data, overriding its previous state.
+    /// If the specified argument is the path of a readable file, its contents are used instead.
+    /// If the specified argument is `None`, then no change occurs.
+    fn typed_data(&mut self, argument: Option<&str>) {
+        if let Some(typed_data) = argument {
+            self.typed_data = match std::fs::read_to_string(typed_data) {
+                Ok(contents) => Some(contents),
+                Err(_) => Some(typed_data.to_string()),
+            };
+        }
+    }
+
+    /// Sets `udp_port` to the specified UDP port, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn udp_port(&mut self, argument: Option<&str>) {
+        if let Some(udp_port) = argument {
+            self.udp_port = Some(udp_port.to_string());
+        }
+    }
+
+    /// Sets `unsigned_transaction` to the specified unsigned transaction file, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn unsigned_transaction(&mut self, argument: Option<&str>) {
+        if let Some(unsigned_transaction) = argument {
+            self.unsigned_transaction = Some(unsigned_transaction.to_string());
+        }
+    }
+
+    /// Sets `user_operation` to the specified user operation, overriding its previous state.
+    /// If the specified argument is the path of a readable file, its contents are used instead.
+    /// If the specified argument is `None`, then no change occurs.
+    fn user_operation(&mut self, argument: Option<&str>) {
+        if let Some(user_operation) = argument {
+            self.user_operation = match std::fs::read_to_string(user_operation) {
+                Ok(contents) => Some(contents),
+                Err(_) => Some(user_operation.to_string()),
+            };
+        }
+    }
+
+    /// Sets `value` to the specified amount of tokens, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn value(&mut self, argument: Option<&str>) {
+        if let Some(value) = argument {
+            self.value = Some(value.to_string());
+        }
+    }
+
     /// Sets `word_count` to the specified word count, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
     fn word_count(&mut self, argument: Option<u8>) {
@@ -543,37 +1938,39 @@ impl EthereumOptions {
         }
     }
 
-    /// Returns the derivation path with the specified account, chain, derivation, index, and path.
-    /// If `default` is enabled, then return the default path if no derivation was provided.
-    fn to_derivation_path(&self, default: bool) -> Option<String> {
-        match self.derivation.as_str() {
-            "ethereum" => Some(format!("m/44'/60'/0'/{}", self.index)),
-            "keepkey" => Some(format!("m/44'/60'/{}'/0", self.index)),
-            "ledger-legacy" => Some(format!("m/44'/60'/0'/{}", self.index)),
-            "ledger-live" => Some(format!("m/44'/60'/{}'/0/0", self.index)),
-            "trezor" => Some(format!("m/44'/60'/0'/{}", self.index)),
-            "custom" => self.path.clone(),
-            _ => match default {
-                true => Some(format!("m/44'/60'/0'/0/{}", self.index)),
-                false => None,
-            },
+    /// Sets `wordlist_file` to the specified wordlist file, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn wordlist_file(&mut self, argument: Option<&str>) {
+        if let Some(path) = argument {
+            self.wordlist_file = Some(path.to_string());
         }
     }
 
-    /// Returns the derivation paths with the specified account, chain, derivation, indices, and path.
-    /// If `default` is enabled, then return the default path if no derivation was provided.
-    fn to_derivation_paths(&self, default: bool) -> Vec<Option<String>> {
-        let start = self.index;
-        let end = start + self.indices;
-        let mut options = self.clone();
-        (start..end)
-            .map(|index| {
-                // Sets the index to the specified index
-                options.index(Some(index));
-                // Generates the derivation path for the specified information
-                options.to_derivation_path(default)
-            })
-            .collect()
+    /// Returns the specified RPC URL, or the RPC URL of the `WAGYU_ETHEREUM_RPC` environment variable if none is specified.
+    fn to_rpc_url(&self) -> Option<String> {
+        self.rpc_url.clone().or_else(|| std::env::var(ETHEREUM_RPC_URL).ok())
+    }
+
+    /// Returns the derivation paths with the specified account, derivation, index, indices, and path.
+    /// The derivation preset or custom path is expanded from `index` to a number of specified `indices`,
+    /// if it is a derivation path template.
+    fn to_derivation_paths(&self) -> Result<Vec<Option<String>>, CLIError> {
+        let template = match (self.derivation.as_str(), &self.path) {
+            ("custom", Some(path)) if !DerivationPathTemplate::is_template(path) => {
+                return Ok(vec![Some(path.clone())])
+            }
+            ("custom", Some(path)) => DerivationPathTemplate::from_str(path)?,
+            ("custom", None) => return Ok(vec![None]),
+            (preset, _) => DerivationPresets::load("ethereum")?.get(preset)?,
+        };
+
+        let indices = self.indices.saturating_sub(1);
+        let index = ChildNumberRange::new(self.index_range.start(), self.index_range.end().saturating_add(indices))?;
+        Ok(template
+            .expand(&self.account_range, &index)
+            .into_iter()
+            .map(Some)
+            .collect())
     }
 }
 
@@ -583,16 +1980,126 @@ impl CLI for EthereumCLI {
     type Options = EthereumOptions;
 
     const ABOUT: AboutType = "Generates a Ethereum wallet (include -h for more options)";
-    const FLAGS: &'static [FlagType] = &[flag::JSON];
+    const FLAGS: &'static [FlagType] = &[
+        flag::CLEAR_AFTER,
+        flag::COPY,
+        flag::ENCRYPT,
+        flag::ENCRYPT_TO,
+        flag::JSON,
+        flag::MANIFEST,
+        flag::MASK_SECRETS,
+        flag::REVEAL,
+        flag::SAVE,
+    ];
     const NAME: NameType = "ethereum";
     const OPTIONS: &'static [OptionType] = &[option::COUNT];
     const SUBCOMMANDS: &'static [SubCommandType] = &[
+        subcommand::CONTAINS,
+        subcommand::CONTRACT_ADDRESS_ETHEREUM,
+        subcommand::CREATE_TX_ETHEREUM,
+        subcommand::DISCOVER_PATHS_ETHEREUM,
         subcommand::HD_ETHEREUM,
         subcommand::IMPORT_ETHEREUM,
         subcommand::IMPORT_HD_ETHEREUM,
+        subcommand::NODE_ID_ETHEREUM,
+        subcommand::PERMIT_ETHEREUM,
+        subcommand::RECOVER_ETHEREUM,
+        subcommand::SIGN_BATCH_ETHEREUM,
+        subcommand::SIGN_TX_ETHEREUM,
+        subcommand::SIGN_TYPED_DATA_ETHEREUM,
+        subcommand::SIGN_USEROP_ETHEREUM,
         subcommand::TRANSACTION_ETHEREUM,
+        subcommand::XPUB,
     ];
 
+    /// Generate Ethereum wallets, derived from the mnemonic at `m/44'/60'/0'/0/i` if one is specified
+    #[cfg_attr(tarpaulin, skip)]
+    fn generate(
+        count: usize,
+        mnemonic: Option<&str>,
+        password: Option<&str>,
+    ) -> Result<Vec<serde_json::Value>, CLIError> {
+        (0..count)
+            .map(|index| {
+                let wallet = match mnemonic {
+                    Some(mnemonic) => EthereumWallet::from_mnemonic::<EthereumMainnet, English>(
+                        mnemonic,
+                        password,
+                        &format!("m/44'/60'/0'/0/{}", index),
+                    )?,
+                    None => EthereumWallet::new(&mut StdRng::from_entropy())?,
+                };
+                Ok(serde_json::to_value(wallet)?)
+            })
+            .collect()
+    }
+
+    /// Describe the parameters of the Ethereum mainnet and testnets
+    #[cfg_attr(tarpaulin, skip)]
+    fn info() -> Result<serde_json::Value, CLIError> {
+        Ok(serde_json::json!({
+            "coin": Self::NAME,
+            "networks": [
+                to_network_info::<EthereumMainnet>()?,
+                to_network_info::<Goerli>()?,
+                to_network_info::<Kovan>()?,
+                to_network_info::<Rinkeby>()?,
+                to_network_info::<Ropsten>()?,
+            ],
+        }))
+    }
+
+    /// Suggest the built-in and user-defined derivation presets for `--derivation`
+    #[cfg_attr(tarpaulin, skip)]
+    fn value_hints() -> Result<Vec<ValueHint>, CLIError> {
+        let presets = DerivationPresets::load(Self::NAME)?;
+        Ok(vec![("derivation", presets.presets().keys().cloned().collect())])
+    }
+
+    /// Verify an Ethereum wallet, recognized by its address
+    fn verify(wallet: &serde_json::Value) -> Result<Option<Vec<Mismatch>>, CLIError> {
+        if EthereumAddress::from_str(wallet["address"].as_str().unwrap_or_default()).is_err() {
+            return Ok(None);
+        }
+
+        let mut mismatches = Vec::new();
+        if let Some(path) = wallet["path"].as_str() {
+            let password = wallet["password"].as_str();
+            mismatches.extend(rederive(
+                wallet,
+                "mnemonic",
+                &["private_key", "public_key", "address"],
+                |mnemonic| EthereumWallet::from_mnemonic::<EthereumMainnet, English>(mnemonic, password, path),
+            ));
+        }
+        mismatches.extend(rederive(
+            wallet,
+            "extended_private_key",
+            &["extended_public_key", "private_key", "public_key", "address"],
+            |extended_private_key| {
+                EthereumWallet::from_extended_private_key::<EthereumMainnet>(extended_private_key, &None)
+            },
+        ));
+        mismatches.extend(rederive(
+            wallet,
+            "extended_public_key",
+            &["public_key", "address"],
+            |extended_public_key| {
+                EthereumWallet::from_extended_public_key::<EthereumMainnet>(extended_public_key, &None)
+            },
+        ));
+        mismatches.extend(rederive(
+            wallet,
+            "private_key",
+            &["public_key", "address"],
+            EthereumWallet::from_private_key,
+        ));
+        mismatches.extend(rederive(wallet, "public_key", &["address"], |public_key| {
+            EthereumWallet::from_public_key(public_key)
+        }));
+        Ok(Some(mismatches))
+    }
+
     /// Handle all CLI arguments and flags for Ethereum
     #[cfg_attr(tarpaulin, skip)]
     fn parse(arguments: &ArgMatches) -> Result<Self::Options, CLIError> {
@@ -600,18 +2107,84 @@ impl CLI for EthereumCLI {
         options.parse(arguments, &["count", "json"]);
 
         match arguments.subcommand() {
+            ("contains", Some(arguments)) => {
+                options.subcommand = Some("contains".into());
+                options.parse(arguments, &["json"]);
+                options.parse(
+                    arguments,
+                    &["address", "language", "mnemonic", "password", "search depth", "xpub"],
+                );
+            }
+            ("contract-address", Some(arguments)) => {
+                options.subcommand = Some("contract-address".into());
+                options.parse(arguments, &["json"]);
+                options.parse(
+                    arguments,
+                    &[
+                        "create3",
+                        "deployer",
+                        "implementation",
+                        "init code",
+                        "init code hash",
+                        "nonce",
+                        "salt",
+                    ],
+                );
+            }
+            ("create-tx", Some(arguments)) => {
+                options.subcommand = Some("create-tx".into());
+                options.parse(arguments, &["json"]);
+                options.parse(
+                    arguments,
+                    &[
+                        "estimate gas",
+                        "network",
+                        "nonce file",
+                        "parameters",
+                        "rpc url",
+                        "unsigned tx",
+                    ],
+                );
+            }
+            ("discover-paths", Some(arguments)) => {
+                options.subcommand = Some("discover-paths".into());
+                options.search_depth = DISCOVER_PATHS_DEPTH;
+                options.parse(arguments, &["json"]);
+                options.parse(
+                    arguments,
+                    &["address", "language", "mnemonic", "password", "search depth"],
+                );
+            }
             ("hd", Some(arguments)) => {
                 options.subcommand = Some("hd".into());
                 options.parse(arguments, &["count", "json"]);
                 options.parse(
                     arguments,
-                    &["derivation", "index", "indices", "language", "password", "word count"],
+                    &[
+                        "account",
+                        "breadth",
+                        "confirm backup",
+                        "depth",
+                        "derivation",
+                        "index",
+                        "indices",
+                        "language",
+                        "list presets",
+                        "mnemonic",
+                        "password",
+                        "tree",
+                        "word count",
+                        "wordlist file",
+                    ],
                 );
             }
             ("import", Some(arguments)) => {
                 options.subcommand = Some("import".into());
                 options.parse(arguments, &["json"]);
-                options.parse(arguments, &["address", "private", "public"]);
+                options.parse(
+                    arguments,
+                    &["address", "column", "file", "keystore", "private", "public"],
+                );
             }
             ("import-hd", Some(arguments)) => {
                 options.subcommand = Some("import-hd".into());
@@ -626,18 +2199,116 @@ impl CLI for EthereumCLI {
                         "extended public",
                         "index",
                         "indices",
+                        "keystore",
                         "mnemonic",
                         "password",
+                        "summary",
+                        "summary count",
+                        "wordlist file",
+                    ],
+                );
+            }
+            ("node-id", Some(arguments)) => {
+                options.subcommand = Some("node-id".into());
+                options.parse(arguments, &["json"]);
+                options.parse(arguments, &["ip", "private key", "seq", "tcp port", "udp port"]);
+            }
+            ("permit", Some(arguments)) => {
+                options.subcommand = Some("permit".into());
+                options.parse(arguments, &["json"]);
+                options.parse(
+                    arguments,
+                    &[
+                        "chain id",
+                        "deadline",
+                        "debug signature",
+                        "nonce",
+                        "private key",
+                        "spender",
+                        "token",
+                        "token name",
+                        "token version",
+                        "value",
                     ],
                 );
             }
+            ("recover", Some(arguments)) => {
+                options.subcommand = Some("recover".into());
+                options.parse(arguments, &["json"]);
+                options.parse(arguments, &["message", "message hash", "signature"]);
+            }
+            ("sign-batch", Some(arguments)) => {
+                options.subcommand = Some("sign-batch".into());
+                options.parse(arguments, &["extended private key", "file", "network"]);
+            }
+            ("sign-tx", Some(arguments)) => {
+                options.subcommand = Some("sign-tx".into());
+                options.parse(arguments, &["json"]);
+                options.parse(
+                    arguments,
+                    &["debug signature", "nonce file", "private key", "unsigned tx"],
+                );
+            }
+            ("sign-typed-data", Some(arguments)) => {
+                options.subcommand = Some("sign-typed-data".into());
+                options.parse(arguments, &["json"]);
+                options.parse(arguments, &["debug signature", "private key", "typed data"]);
+            }
+            ("sign-userop", Some(arguments)) => {
+                options.subcommand = Some("sign-userop".into());
+                options.parse(arguments, &["json"]);
+                options.parse(
+                    arguments,
+                    &["chain id", "debug signature", "entry point", "private key", "userop"],
+                );
+            }
             ("transaction", Some(arguments)) => {
                 options.subcommand = Some("transaction".into());
-                options.parse(arguments, &["createrawtransaction", "network", "signrawtransaction"]);
+                options.parse(
+                    arguments,
+                    &[
+                        "createrawtransaction",
+                        "debug signature",
+                        "estimate gas",
+                        "network",
+                        "rpc url",
+                        "signrawtransaction",
+                    ],
+                );
+            }
+            ("xpub", Some(arguments)) => {
+                options.subcommand = Some("xpub".into());
+                options.parse(arguments, &["json"]);
+                options.parse(arguments, &["extended private key", "path"]);
             }
             _ => {}
         };
 
+        // The save flags are global, and thus parsed from the innermost subcommand arguments
+        let arguments = arguments.subcommand().1.unwrap_or(arguments);
+        options.parse(
+            arguments,
+            &[
+                "clear after",
+                "copy",
+                "encrypt",
+                "encrypt to",
+                "manifest",
+                "mask secrets",
+                "reveal",
+                "save",
+            ],
+        );
+        check_clipboard_field(&options.copy)?;
+        if options.encrypt && options.save.is_none() {
+            return Err(CLIError::MissingSaveFile);
+        }
+        if options.encrypt && options.encrypt_to.is_some() {
+            return Err(CLIError::ConflictingEncryption);
+        }
+
+        options.sanitize_inputs()?;
+
         Ok(options)
     }
 
@@ -645,38 +2316,227 @@ impl CLI for EthereumCLI {
     #[cfg_attr(tarpaulin, skip)]
     fn print(options: Self::Options) -> Result<(), CLIError> {
         fn output<N: EthereumNetwork, W: EthereumWordlist>(options: EthereumOptions) -> Result<(), CLIError> {
+            // The derivation presets are listed in place of generating a wallet
+            if options.list_presets {
+                let presets = DerivationPresets::load("ethereum")?;
+                match options.json {
+                    true => println!("{}\n", serde_json::to_string_pretty(presets.presets())?),
+                    false => {
+                        println!();
+                        for (name, template) in presets.presets() {
+                            println!("      {} {}", format!("{:<20}", name).cyan().bold(), template);
+                        }
+                        println!();
+                    }
+                };
+                return Ok(());
+            }
+
+            // Print the wallets and derivation paths of a mnemonic that derive an address
+            if let (Some("discover-paths"), Some(mnemonic), Some(address)) =
+                (options.subcommand.as_deref(), &options.mnemonic, &options.address)
+            {
+                let discovered = EthereumWallet::discover_paths::<N, W>(
+                    mnemonic,
+                    options.password.as_deref(),
+                    address,
+                    options.search_depth,
+                )?;
+                print_wallets(&discovered, options.json, false)?;
+                return AuditLog::log_wallets("ethereum discover-paths", &discovered);
+            }
+
+            // Import each entry of a batch file as a private key, public key, or address
+            if let (Some("import"), Some(path)) = (options.subcommand.as_deref(), &options.file) {
+                let entries = read_batch_file(path, options.column.as_deref())?;
+                return import_batch(
+                    "ethereum import",
+                    &entries,
+                    options.mask_secrets && !options.reveal,
+                    |entry| {
+                        EthereumWallet::from_private_key(entry)
+                            .or_else(|_| EthereumWallet::from_public_key(entry))
+                            .or_else(|_| EthereumWallet::from_address(entry))
+                    },
+                );
+            }
+
+            // Sign each prepared transaction of a batch file with the key derived at its derivation path
+            if let (Some("sign-batch"), Some(path), Some(key)) = (
+                options.subcommand.as_deref(),
+                &options.file,
+                &options.extended_private_key,
+            ) {
+                let entries = read_transaction_batch_file::<EthereumBatchTransaction>(path)?;
+                return sign_batch("ethereum sign-batch", &entries, |entry| {
+                    match options.network.as_deref() {
+                        Some(Goerli::NAME) => EthereumWallet::to_signed_batch_transaction::<Goerli>(key, entry),
+                        Some(Kovan::NAME) => EthereumWallet::to_signed_batch_transaction::<Kovan>(key, entry),
+                        Some(Rinkeby::NAME) => EthereumWallet::to_signed_batch_transaction::<Rinkeby>(key, entry),
+                        Some(Ropsten::NAME) => EthereumWallet::to_signed_batch_transaction::<Ropsten>(key, entry),
+                        _ => EthereumWallet::to_signed_batch_transaction::<EthereumMainnet>(key, entry),
+                    }
+                });
+            }
+
+            // Print the accounts, chains, and addresses of an HD wallet as a tree
+            if let (Some("hd"), true) = (options.subcommand.as_deref(), options.tree) {
+                let tree = EthereumWallet::new_hd_tree::<N, W, _>(
+                    &mut StdRng::from_entropy(),
+                    options.mnemonic.as_deref(),
+                    options.word_count,
+                    options.password.as_deref(),
+                    options.depth,
+                    options.breadth,
+                )?;
+                print_wallets(
+                    std::slice::from_ref(&tree),
+                    options.json,
+                    options.mask_secrets && !options.reveal,
+                )?;
+                return AuditLog::log_wallets("ethereum hd tree", &tree.tree.to_addresses());
+            }
+
+            // Print only the identifying data of an HD wallet, for the first derivation path
+            if let (Some("import-hd"), true) = (options.subcommand.as_deref(), options.summary) {
+                let mnemonic = options.mnemonic.as_deref().unwrap_or_default();
+                let password = options.password.as_deref();
+                let path = options.to_derivation_paths()?.remove(0).unwrap_or_default();
+                let count = options.summary_count;
+                let summary = EthereumWallet::to_summary::<N, CustomWordlist>(mnemonic, password, &path, count)
+                    .or(EthereumWallet::to_summary::<N, ChineseSimplified>(
+                        mnemonic, password, &path, count,
+                    ))
+                    .or(EthereumWallet::to_summary::<N, ChineseTraditional>(
+                        mnemonic, password, &path, count,
+                    ))
+                    .or(EthereumWallet::to_summary::<N, English>(
+                        mnemonic, password, &path, count,
+                    ))
+                    .or(EthereumWallet::to_summary::<N, French>(
+                        mnemonic, password, &path, count,
+                    ))
+                    .or(EthereumWallet::to_summary::<N, Italian>(
+                        mnemonic, password, &path, count,
+                    ))
+                    .or(EthereumWallet::to_summary::<N, Japanese>(
+                        mnemonic, password, &path, count,
+                    ))
+                    .or(EthereumWallet::to_summary::<N, Korean>(
+                        mnemonic, password, &path, count,
+                    ))
+                    .or(EthereumWallet::to_summary::<N, Spanish>(
+                        mnemonic, password, &path, count,
+                    ))?;
+                print_wallets(std::slice::from_ref(&summary), options.json, false)?;
+                return AuditLog::log_wallets("ethereum import-hd summary", &summary.addresses);
+            }
+
             let wallets = match options.subcommand.as_ref().map(String::as_str) {
+                Some("contains") => {
+                    let address = options.address.clone().unwrap_or_default();
+                    let depth = options.search_depth;
+                    match (&options.extended_public_key, &options.mnemonic) {
+                        (Some(xpub), _) => {
+                            vec![EthereumWallet::find_in_extended_public_key::<N>(xpub, &address, depth)?]
+                        }
+                        (None, Some(mnemonic)) => {
+                            let password = options.password.as_deref();
+                            vec![EthereumWallet::find_in_mnemonic::<N, W>(
+                                mnemonic, password, &address, depth,
+                            )?]
+                        }
+                        (None, None) => return Err(CLIError::MissingSearchKey),
+                    }
+                }
+                Some("contract-address") => match options.deployer.as_ref() {
+                    Some(deployer) => vec![EthereumWallet::to_contract_address(
+                        deployer,
+                        options.nonce.as_deref().map(u64::from_str).transpose()?,
+                        options.salt.as_deref(),
+                        options.init_code.as_deref(),
+                        options.init_code_hash.as_deref(),
+                        options.implementation.as_deref(),
+                        options.create3,
+                    )?],
+                    _ => vec![],
+                },
+                Some("create-tx") => match (
+                    options.transaction_parameters.clone(),
+                    options.unsigned_transaction.clone(),
+                ) {
+                    (Some(transaction_parameters), Some(path)) => {
+                        let mut parameters: EthereumInput = from_str(&transaction_parameters)?;
+                        let network = options.network.as_deref().unwrap_or(EthereumMainnet::NAME);
+
+                        // The nonce defaults to the next unused nonce of the sender in the nonce file
+                        let nonce_file = match options.nonce_file.as_ref() {
+                            Some(nonce_path) => {
+                                let sender = match parameters.from.as_ref() {
+                                    Some(from) => EthereumAddress::from_str(from)?.to_string(),
+                                    None => return Err(CLIError::MissingSender),
+                                };
+                                let mut nonce_file = NonceFile::read(nonce_path)?;
+                                parameters.nonce = parameters.nonce.or_else(|| nonce_file.next(network, &sender));
+                                nonce_file.record(network, &sender, parameters.nonce.ok_or(CLIError::MissingNonce)?)?;
+                                Some((nonce_file, nonce_path))
+                            }
+                            None => None,
+                        };
+
+                        let gas_estimation = match options.estimate_gas {
+                            true => Some(parameters.estimate_gas(options.to_rpc_url().as_deref())?),
+                            false => None,
+                        };
+                        let gas = gas_estimation.as_ref().and(parameters.gas.clone());
+
+                        let wallet = match network {
+                            Goerli::NAME => EthereumWallet::to_unsigned_transaction_file::<Goerli>(parameters, &path)?,
+                            Kovan::NAME => EthereumWallet::to_unsigned_transaction_file::<Kovan>(parameters, &path)?,
+                            Rinkeby::NAME => {
+                                EthereumWallet::to_unsigned_transaction_file::<Rinkeby>(parameters, &path)?
+                            }
+                            Ropsten::NAME => {
+                                EthereumWallet::to_unsigned_transaction_file::<Ropsten>(parameters, &path)?
+                            }
+                            _ => EthereumWallet::to_unsigned_transaction_file::<EthereumMainnet>(parameters, &path)?,
+                        };
+
+                        if let Some((nonce_file, nonce_path)) = nonce_file {
+                            nonce_file.write(nonce_path)?;
+                        }
+                        vec![EthereumWallet {
+                            gas,
+                            gas_estimation,
+                            ..wallet
+                        }]
+                    }
+                    _ => vec![],
+                },
                 Some("hd") => {
                     let password = options.password.as_ref().map(String::as_str);
-                    (0..options.count)
-                        .flat_map(|_| {
-                            // Sample a new HD wallet
-                            let wallet = EthereumWallet::new_hd::<N, W, _>(
-                                &mut StdRng::from_entropy(),
-                                options.word_count,
+                    let paths = options.to_derivation_paths()?;
+                    let mut wallets = vec![];
+                    for _ in 0..options.count {
+                        // Sample a new HD wallet
+                        let wallet = EthereumWallet::new_hd::<N, W, _>(
+                            &mut StdRng::from_entropy(),
+                            options.word_count,
+                            password,
+                            paths[0].as_ref().unwrap(),
+                        )?;
+                        let mnemonic = &wallet.mnemonic.unwrap();
+
+                        // Generate the HD wallet, from `index` to a number of specified `indices`
+                        for path in paths.iter() {
+                            wallets.push(EthereumWallet::from_mnemonic::<N, W>(
+                                mnemonic,
                                 password,
-                                &options.to_derivation_path(true).unwrap(),
-                            )
-                            .unwrap();
-                            let mnemonic = &wallet.mnemonic.unwrap();
-
-                            // Generate the HD wallet, from `index` to a number of specified `indices`
-                            options
-                                .to_derivation_paths(true)
-                                .iter()
-                                .flat_map(|path| {
-                                    match EthereumWallet::from_mnemonic::<N, W>(
-                                        mnemonic,
-                                        password,
-                                        path.as_ref().unwrap(),
-                                    ) {
-                                        Ok(wallet) => vec![wallet],
-                                        _ => vec![],
-                                    }
-                                })
-                                .collect::<Vec<EthereumWallet>>()
-                        })
-                        .collect()
+                                path.as_ref().unwrap(),
+                            )?);
+                        }
+                    }
+                    wallets
                 }
                 Some("import") => {
                     if let Some(private_key) = options.private {
@@ -685,6 +2545,8 @@ impl CLI for EthereumCLI {
                         vec![EthereumWallet::from_public_key(&public_key)?]
                     } else if let Some(address) = options.address {
                         vec![EthereumWallet::from_address(&address)?]
+                    } else if let Some(path) = &options.keystore {
+                        vec![EthereumWallet::from_private_key(&Keystore::read(path)?)?]
                     } else {
                         vec![]
                     }
@@ -698,7 +2560,7 @@ impl CLI for EthereumCLI {
                             // Generate the mnemonic wallets, from `index` to a number of specified `indices`
                             let mut wallets = vec![];
                             let password = options.password.as_ref().map(String::as_str);
-                            for path in options.to_derivation_paths(true) {
+                            for path in options.to_derivation_paths()? {
                                 wallets.push(EthereumWallet::from_mnemonic::<EN, EW>(
                                     mnemonic,
                                     password,
@@ -708,7 +2570,8 @@ impl CLI for EthereumCLI {
                             Ok(wallets)
                         }
 
-                        process_mnemonic::<N, ChineseSimplified>(&mnemonic, &options)
+                        process_mnemonic::<N, CustomWordlist>(&mnemonic, &options)
+                            .or(process_mnemonic::<N, ChineseSimplified>(&mnemonic, &options))
                             .or(process_mnemonic::<N, ChineseTraditional>(&mnemonic, &options))
                             .or(process_mnemonic::<N, English>(&mnemonic, &options))
                             .or(process_mnemonic::<N, French>(&mnemonic, &options))
@@ -719,7 +2582,7 @@ impl CLI for EthereumCLI {
                     } else if let Some(extended_private_key) = options.extended_private_key.clone() {
                         // Generate the extended private keys, from `index` to a number of specified `indices`
                         options
-                            .to_derivation_paths(true)
+                            .to_derivation_paths()?
                             .iter()
                             .flat_map(|path| {
                                 match EthereumWallet::from_extended_private_key::<N>(&extended_private_key, path) {
@@ -731,59 +2594,219 @@ impl CLI for EthereumCLI {
                     } else if let Some(extended_public_key) = options.extended_public_key.clone() {
                         // Generate the extended public keys, from `index` to a number of specified `indices`
                         options
-                            .to_derivation_paths(true)
+                            .to_derivation_paths()?
                             .iter()
-                            .flat_map(|path| {
-                                match EthereumWallet::from_extended_public_key::<N>(&extended_public_key, path) {
-                                    Ok(wallet) => vec![wallet],
-                                    _ => vec![],
-                                }
-                            })
-                            .collect::<Vec<EthereumWallet>>()
+                            .map(|path| EthereumWallet::from_extended_public_key::<N>(&extended_public_key, path))
+                            .collect::<Result<Vec<EthereumWallet>, CLIError>>()?
                     } else {
                         vec![]
                     }
                 }
+                Some("node-id") => match options.private.as_ref() {
+                    Some(private_key) => {
+                        let seq = u64::from_str(options.seq.as_deref().unwrap_or("1"))?;
+                        let ip = options.ip.as_deref().map(Ipv4Addr::from_str).transpose()?;
+                        let tcp = options.tcp_port.as_deref().map(u16::from_str).transpose()?;
+                        let udp = options.udp_port.as_deref().map(u16::from_str).transpose()?;
+                        vec![EthereumWallet::to_node_identities(private_key, seq, ip, tcp, udp)?]
+                    }
+                    _ => vec![],
+                },
+                Some("permit") => match (
+                    options.token.clone(),
+                    options.token_name.clone(),
+                    options.chain_id,
+                    options.spender.clone(),
+                    options.value.clone(),
+                    options.nonce.clone(),
+                    options.deadline.clone(),
+                    options.private.as_ref(),
+                ) {
+                    (
+                        Some(token),
+                        Some(name),
+                        Some(chain_id),
+                        Some(spender),
+                        Some(value),
+                        Some(nonce),
+                        Some(deadline),
+                        Some(private_key),
+                    ) => {
+                        let permit = Permit {
+                            name,
+                            version: options.token_version.clone(),
+                            chain_id,
+                            verifying_contract: token,
+                            owner: String::new(),
+                            spender,
+                            value,
+                            nonce,
+                            deadline,
+                        };
+                        vec![EthereumWallet::to_signed_permit(
+                            permit,
+                            private_key,
+                            options.debug_signature,
+                        )?]
+                    }
+                    _ => vec![],
+                },
+                Some("recover") => match options.signature.as_ref() {
+                    Some(signature) => vec![EthereumWallet::from_signature(
+                        options.message.as_deref(),
+                        options.message_hash.as_deref(),
+                        signature,
+                    )?],
+                    None => vec![],
+                },
+                Some("sign-tx") => match (options.unsigned_transaction.clone(), options.private.clone()) {
+                    (Some(path), Some(private_key)) => {
+                        let file = UnsignedTransactionFile::<EthereumInput>::read(&path, "ethereum")?;
+
+                        // The nonce of the signer is checked against the nonce file before signing
+                        let nonce_file = match options.nonce_file.as_ref() {
+                            Some(nonce_path) => {
+                                let signer = EthereumPrivateKey::from_str(&private_key)?
+                                    .to_address(&EthereumFormat::Standard)?
+                                    .to_string();
+                                let mut nonce_file = NonceFile::read(nonce_path)?;
+                                nonce_file.record(
+                                    &file.network,
+                                    &signer,
+                                    file.context.nonce.ok_or(CLIError::MissingNonce)?,
+                                )?;
+                                Some((nonce_file, nonce_path))
+                            }
+                            None => None,
+                        };
+
+                        let wallet = match file.network.as_str() {
+                            EthereumMainnet::NAME => EthereumWallet::from_unsigned_transaction_file::<EthereumMainnet>(
+                                &file,
+                                &private_key,
+                                options.debug_signature,
+                            )?,
+                            Goerli::NAME => EthereumWallet::from_unsigned_transaction_file::<Goerli>(
+                                &file,
+                                &private_key,
+                                options.debug_signature,
+                            )?,
+                            Kovan::NAME => EthereumWallet::from_unsigned_transaction_file::<Kovan>(
+                                &file,
+                                &private_key,
+                                options.debug_signature,
+                            )?,
+                            Rinkeby::NAME => EthereumWallet::from_unsigned_transaction_file::<Rinkeby>(
+                                &file,
+                                &private_key,
+                                options.debug_signature,
+                            )?,
+                            Ropsten::NAME => EthereumWallet::from_unsigned_transaction_file::<Ropsten>(
+                                &file,
+                                &private_key,
+                                options.debug_signature,
+                            )?,
+                            network => return Err(CLIError::UnsupportedNetwork(network.to_string())),
+                        };
+
+                        if let Some((nonce_file, nonce_path)) = nonce_file {
+                            nonce_file.write(nonce_path)?;
+                        }
+                        vec![wallet]
+                    }
+                    _ => vec![],
+                },
+                Some("sign-typed-data") => match (options.typed_data.as_ref(), options.private.as_ref()) {
+                    (Some(typed_data), Some(private_key)) => {
+                        vec![EthereumWallet::to_signed_typed_data(
+                            typed_data,
+                            private_key,
+                            options.debug_signature,
+                        )?]
+                    }
+                    _ => vec![],
+                },
+                Some("sign-userop") => match (
+                    options.user_operation.as_ref(),
+                    options.entry_point.as_ref(),
+                    options.chain_id,
+                    options.private.as_ref(),
+                ) {
+                    (Some(user_operation), Some(entry_point), Some(chain_id), Some(private_key)) => {
+                        vec![EthereumWallet::to_signed_user_operation(
+                            user_operation,
+                            entry_point,
+                            chain_id,
+                            private_key,
+                            options.debug_signature,
+                        )?]
+                    }
+                    _ => vec![],
+                },
                 Some("transaction") => {
                     if let Some(transaction_parameters) = options.transaction_parameters.clone() {
-                        let parameters: EthereumInput = from_str(&transaction_parameters)?;
+                        let mut parameters: EthereumInput = from_str(&transaction_parameters)?;
+                        let gas_estimation = match options.estimate_gas {
+                            true => Some(parameters.estimate_gas(options.to_rpc_url().as_deref())?),
+                            false => None,
+                        };
 
                         // Note: Raw Ethereum transactions are network agnostic
-                        vec![EthereumWallet::to_raw_transaction::<EthereumMainnet>(parameters)?]
+                        vec![EthereumWallet {
+                            gas: gas_estimation.as_ref().and(parameters.gas.clone()),
+                            gas_estimation,
+                            ..EthereumWallet::to_raw_transaction::<EthereumMainnet>(parameters)?
+                        }]
                     } else if let (Some(transaction_hex), Some(transaction_private_key)) =
                         (options.transaction_hex.clone(), options.transaction_private_key.clone())
                     {
                         match options.network.as_ref().map(String::as_str) {
-                            Some(EthereumMainnet::NAME) => vec![EthereumWallet::to_signed_transaction::<
-                                EthereumMainnet,
-                            >(
-                                transaction_hex, transaction_private_key
-                            )?],
+                            Some(EthereumMainnet::NAME) => {
+                                vec![EthereumWallet::to_signed_transaction::<EthereumMainnet>(
+                                    transaction_hex,
+                                    transaction_private_key,
+                                    options.debug_signature,
+                                )?]
+                            }
                             Some(Goerli::NAME) => vec![EthereumWallet::to_signed_transaction::<Goerli>(
                                 transaction_hex,
                                 transaction_private_key,
+                                options.debug_signature,
                             )?],
                             Some(Kovan::NAME) => vec![EthereumWallet::to_signed_transaction::<Kovan>(
                                 transaction_hex,
                                 transaction_private_key,
+                                options.debug_signature,
                             )?],
                             Some(Rinkeby::NAME) => vec![EthereumWallet::to_signed_transaction::<Rinkeby>(
                                 transaction_hex,
                                 transaction_private_key,
+                                options.debug_signature,
                             )?],
                             Some(Ropsten::NAME) => vec![EthereumWallet::to_signed_transaction::<Ropsten>(
                                 transaction_hex,
                                 transaction_private_key,
+                                options.debug_signature,
                             )?],
                             _ => vec![EthereumWallet::to_signed_transaction::<EthereumMainnet>(
                                 transaction_hex,
                                 transaction_private_key,
+                                options.debug_signature,
                             )?],
                         }
                     } else {
                         vec![]
                     }
                 }
+                Some("xpub") => match options.extended_private_key.clone() {
+                    Some(extended_private_key) => {
+                        vec![EthereumWallet::to_extended_public_key::<N>(
+                            &extended_private_key,
+                            &options.path,
+                        )?]
+                    }
+                    None => vec![],
+                },
                 _ => (0..options.count)
                     .flat_map(|_| match EthereumWallet::new::<_>(&mut StdRng::from_entropy()) {
                         Ok(wallet) => vec![wallet],
@@ -792,14 +2815,69 @@ impl CLI for EthereumCLI {
                     .collect(),
             };
 
-            match options.json {
-                true => println!("{}\n", serde_json::to_string_pretty(&wallets)?),
-                false => wallets.iter().for_each(|wallet| println!("{}\n", wallet)),
+            if options.confirm_backup {
+                confirm_backups(&wallets)?;
+            }
+
+            // Export the accounts as encrypted keystore files, in place of printing their secrets
+            if let (Some("import-hd"), Some(directory)) = (options.subcommand.as_deref(), &options.keystore) {
+                let accounts = wallets
+                    .iter()
+                    .map(|wallet| match (&wallet.private_key, &wallet.address) {
+                        (Some(private_key), Some(address)) => {
+                            Ok((wallet.path.clone(), private_key.clone(), address.clone()))
+                        }
+                        _ => Err(CLIError::MissingKeystoreKey(wallet.address.clone().unwrap_or_default())),
+                    })
+                    .collect::<Result<Vec<_>, CLIError>>()?;
+                let accounts = Keystore::export(&accounts, directory)?;
+                print_wallets(&accounts, options.json, false)?;
+                return AuditLog::log_wallets("ethereum import-hd keystore", &accounts);
+            }
+
+            match &options.encrypt_to {
+                Some(recipients) => {
+                    let recipients = Recipient::from_list(recipients)?;
+                    print!(
+                        "{}",
+                        encrypt_to(serde_json::to_string_pretty(&wallets)?.as_bytes(), &recipients)?
+                    );
+                    if let Some(path) = &options.save {
+                        let file = WalletFile::new(&wallets, "ethereum")?;
+                        std::fs::write(
+                            path,
+                            encrypt_to(serde_json::to_string_pretty(&file)?.as_bytes(), &recipients)?,
+                        )?;
+                    }
+                }
+                None => {
+                    print_wallets(&wallets, options.json, options.mask_secrets && !options.reveal)?;
+                    if let Some(path) = &options.save {
+                        WalletFile::save(&wallets, "ethereum", path, options.encrypt)?;
+                    }
+                }
             };
 
+            if let Some(path) = &options.manifest {
+                Manifest::new(&wallets, "ethereum")?.write(path)?;
+            }
+
+            if let Some(field) = &options.copy {
+                copy_wallets(&wallets, field, options.clear_after)?;
+            }
+
+            let operation = format!("ethereum {}", options.subcommand.as_deref().unwrap_or("generate"));
+            AuditLog::log_wallets(&operation, &wallets)?;
+
             Ok(())
         }
 
+        // A custom wordlist is loaded from its file, and used in place of the compiled-in languages
+        if let Some(path) = &options.wordlist_file {
+            CustomWordlist::load(path)?;
+            return output::<EthereumMainnet, CustomWordlist>(options);
+        }
+
         match options.language.as_str() {
             "chinese_simplified" => output::<EthereumMainnet, ChineseSimplified>(options),
             "chinese_traditional" => output::<EthereumMainnet, ChineseTraditional>(options),
@@ -0,0 +1,74 @@
+//! # Generate
+//!
+//! Generates the wallets of several coins in one document, from the registry of the coins.
+//! The wallets of each coin are new, or derived from one BIP-39 mnemonic at the first indices
+//! of the BIP-44 path of the coin. A mnemonic is only shared by the coins that derive wallets
+//! from BIP-39 mnemonics (Bitcoin and Ethereum).
+
+use crate::bitcoin::{wordlist::English, BitcoinMnemonic, Mainnet};
+use crate::cli::{AuditLog, CLIError, Registry};
+use crate::model::MnemonicCount;
+
+use rand::rngs::StdRng;
+use rand_core::SeedableRng;
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::model::no_std::{format, String, ToString, Vec};
+
+/// The number of words of a new shared mnemonic
+pub const GENERATE_WORD_COUNT: u8 = 12;
+
+/// Represents the generated wallets of several coins
+#[derive(Debug, Clone, Serialize)]
+pub struct GeneratedWallets {
+    /// The new mnemonic shared by the wallets, if one was generated
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mnemonic: Option<String>,
+    /// The wallets of each coin, in the specified order
+    pub coins: Vec<CoinWallets>,
+}
+
+/// Represents the generated wallets of a coin
+#[derive(Debug, Clone, Serialize)]
+pub struct CoinWallets {
+    /// The name of the coin
+    pub coin: String,
+    /// The wallets of the coin
+    pub wallets: Vec<Value>,
+}
+
+/// Returns the given number of wallets of each of the given coins (comma separated) of the registry. The wallets
+/// are derived from the given mnemonic, or from a new shared mnemonic if `same_mnemonic` is set, and are new otherwise.
+pub fn generate(
+    registry: &Registry,
+    coins: &str,
+    count: usize,
+    mnemonic: Option<&str>,
+    same_mnemonic: bool,
+) -> Result<GeneratedWallets, CLIError> {
+    let new_mnemonic = match (mnemonic, same_mnemonic) {
+        (None, true) => {
+            let mnemonic =
+                BitcoinMnemonic::<Mainnet, English>::new_with_count(&mut StdRng::from_entropy(), GENERATE_WORD_COUNT)?;
+            Some(mnemonic.to_string())
+        }
+        _ => None,
+    };
+    let mnemonic = mnemonic.or(new_mnemonic.as_deref());
+
+    let mut generated = Vec::new();
+    for coin in coins.split(',').map(str::trim) {
+        let wallets = registry.find(coin)?.generate(count, mnemonic, None)?;
+        AuditLog::log_wallets(&format!("generate {}", coin), &wallets)?;
+        generated.push(CoinWallets {
+            coin: coin.to_string(),
+            wallets,
+        });
+    }
+
+    Ok(GeneratedWallets {
+        mnemonic: new_mnemonic,
+        coins: generated,
+    })
+}
@@ -0,0 +1,373 @@
+//! # Grid
+//!
+//! Generates the entropy grid of a Border Wallet: the 2048 words of the English BIP-39 wordlist,
+//! shuffled by the entropy of the grid, in 128 rows (1 to 128) and 16 columns (A to P). A mnemonic is
+//! chosen as a memorized pattern of 11 or 23 cells of the grid, completed with a final word that makes
+//! its checksum valid, so that the mnemonic is recovered from the grid and the pattern alone. The grid
+//! is restored from the mnemonic of its entropy, and is exported as a PDF to be printed.
+//!
+//! The words are shuffled with a Fisher-Yates shuffle, of indices drawn from the stream of blocks
+//! `SHA-256("wagyu entropy grid" || entropy || counter)`, so the grids of wagyu are not those of
+//! the Border Wallets web generator.
+
+use crate::bitcoin::{wordlist::English, BitcoinMnemonic, BitcoinWordlist, Mainnet};
+use crate::cli::{mask, to_text_pdf, CLIError, MaskSecrets, SECRET_MASK};
+use crate::model::{Mnemonic, MnemonicError};
+
+use colored::*;
+use core::{fmt, fmt::Display};
+use crypto::{digest::Digest, sha2::Sha256};
+use rand::Rng;
+use serde::Serialize;
+
+use crate::model::no_std::{format, vec, String, ToString, Vec};
+
+/// The number of rows and columns of an entropy grid
+pub const GRID_ROWS: usize = 128;
+pub const GRID_COLUMNS: usize = 16;
+
+/// The domain of the stream of the shuffle of an entropy grid
+const GRID_DOMAIN: &[u8] = b"wagyu entropy grid";
+
+/// The number of rows of an entropy grid on each page of its PDF
+const PDF_ROWS_PER_PAGE: usize = 64;
+
+/// Represents the entropy grid of a Border Wallet, with the mnemonic of a pattern of its cells
+#[derive(Serialize, Clone, Debug)]
+pub struct EntropyGrid {
+    /// The entropy of the grid in hex
+    pub entropy: String,
+    /// The mnemonic of the entropy of the grid, to restore the grid
+    pub grid_mnemonic: String,
+    /// The fingerprint of the grid, the first four bytes of the SHA-256 hash of its entropy in hex
+    pub fingerprint: String,
+    /// The words of the grid, by row
+    pub rows: Vec<Vec<String>>,
+    /// The cells of the pattern, such as A1 or P128
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub pattern: Option<Vec<String>>,
+    /// The final words that complete the words of the pattern as a mnemonic with a valid checksum
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub final_words: Option<Vec<String>>,
+    /// The mnemonic of the pattern, completed with its first final word
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mnemonic: Option<String>,
+}
+
+impl EntropyGrid {
+    /// Returns the grid of 128 bits of random entropy.
+    pub fn new<R: Rng>(rng: &mut R) -> Result<Self, CLIError> {
+        Self::from_entropy(&rng.gen::<[u8; 16]>())
+    }
+
+    /// Returns the grid of the entropy of the given mnemonic.
+    pub fn from_mnemonic(mnemonic: &str) -> Result<Self, CLIError> {
+        Self::from_entropy(&BitcoinMnemonic::<Mainnet, English>::from_phrase(mnemonic)?.to_entropy())
+    }
+
+    /// Returns the grid of the given entropy, of the length of the entropy of a mnemonic (16 to 32 bytes).
+    pub fn from_entropy(entropy: &[u8]) -> Result<Self, CLIError> {
+        let grid_mnemonic = BitcoinMnemonic::<Mainnet, English>::from_entropy(entropy)?.to_phrase()?;
+
+        let mut sha256 = Sha256::new();
+        sha256.input(entropy);
+        let mut fingerprint = [0u8; 32];
+        sha256.result(&mut fingerprint);
+
+        // The stream of random 32-bit integers of the shuffle
+        let mut counter = 0u32;
+        let mut block = Vec::new();
+        let mut next = || {
+            if block.is_empty() {
+                let mut sha256 = Sha256::new();
+                sha256.input(GRID_DOMAIN);
+                sha256.input(entropy);
+                sha256.input(&counter.to_be_bytes());
+                block = vec![0u8; 32];
+                sha256.result(&mut block);
+                counter += 1;
+            }
+            let bytes = block.drain(..4).collect::<Vec<u8>>();
+            u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]])
+        };
+
+        // Each index is drawn uniformly, by rejecting the integers above the last multiple of its range
+        let mut words = English::get_all();
+        for i in (1..words.len()).rev() {
+            let range = i as u32 + 1;
+            let limit = u32::MAX - (u32::MAX % range + 1) % range;
+            let j = loop {
+                match next() {
+                    value if value <= limit => break value % range,
+                    _ => continue,
+                }
+            };
+            words.swap(i, j as usize);
+        }
+
+        Ok(Self {
+            entropy: hex::encode(entropy),
+            grid_mnemonic,
+            fingerprint: hex::encode(&fingerprint[..4]),
+            rows: words
+                .chunks(GRID_COLUMNS)
+                .map(|row| row.iter().map(|word| word.to_string()).collect())
+                .collect(),
+            pattern: None,
+            final_words: None,
+            mnemonic: None,
+        })
+    }
+
+    /// Returns the grid with the mnemonic of the given pattern of 11 or 23 cells (such as `A1 B2 C3 ...`,
+    /// separated by spaces or commas), completed with the final words that make its checksum valid.
+    pub fn with_pattern(mut self, pattern: &str) -> Result<Self, CLIError> {
+        let cells = pattern
+            .split(|c: char| c == ',' || c.is_whitespace())
+            .filter(|cell| !cell.is_empty())
+            .map(|cell| cell.to_uppercase())
+            .collect::<Vec<String>>();
+        if cells.len() != 11 && cells.len() != 23 {
+            return Err(CLIError::InvalidGridPattern(cells.len()));
+        }
+
+        let words = cells
+            .iter()
+            .map(|cell| {
+                let (row, column) = to_cell(cell).ok_or_else(|| CLIError::InvalidGridCell(cell.clone()))?;
+                Ok(self.rows[row][column].clone())
+            })
+            .collect::<Result<Vec<String>, CLIError>>()?;
+
+        // The final word has the free bits of the entropy, followed by the bits of the checksum
+        let mut bits = Vec::new();
+        for word in &words {
+            let index = English::get_index(word).map_err(MnemonicError::from)?;
+            bits.extend((0..11).rev().map(|i| (index >> i) & 1 == 1));
+        }
+        let checksum_bits = (words.len() + 1) / 3;
+        let free_bits = 11 - checksum_bits;
+        let final_words = (0..1usize << free_bits)
+            .map(|value| {
+                let mut entropy_bits = bits.clone();
+                entropy_bits.extend((0..free_bits).rev().map(|i| (value >> i) & 1 == 1));
+                let entropy = entropy_bits
+                    .chunks(8)
+                    .map(|byte| byte.iter().fold(0u8, |value, bit| (value << 1) | *bit as u8))
+                    .collect::<Vec<u8>>();
+                let phrase = BitcoinMnemonic::<Mainnet, English>::from_entropy(&entropy)?.to_phrase()?;
+                Ok(phrase.rsplit(' ').next().unwrap_or_default().to_string())
+            })
+            .collect::<Result<Vec<String>, CLIError>>()?;
+
+        self.mnemonic = Some(format!("{} {}", words.join(" "), final_words[0]));
+        self.pattern = Some(cells);
+        self.final_words = Some(final_words);
+        Ok(self)
+    }
+
+    /// Returns the PDF of the grid, of the first four letters of its words (which identify them),
+    /// on pages of 64 rows in Courier.
+    pub fn to_pdf(&self) -> Vec<u8> {
+        let pages = self.rows.len() / PDF_ROWS_PER_PAGE;
+        let pages = (0..pages)
+            .map(|page| {
+                let mut lines = vec![
+                    format!("Entropy Grid {}    Page {} of {}", self.fingerprint, page + 1, pages),
+                    "".to_string(),
+                    format!(
+                        "     {}",
+                        (0..GRID_COLUMNS)
+                            .map(|column| format!("{:<5}", (b'A' + column as u8) as char))
+                            .collect::<String>()
+                    ),
+                ];
+                for (i, row) in self
+                    .rows
+                    .iter()
+                    .enumerate()
+                    .skip(page * PDF_ROWS_PER_PAGE)
+                    .take(PDF_ROWS_PER_PAGE)
+                {
+                    lines.push(format!("{:03}  {}", i + 1, to_abbreviations(row)));
+                }
+                lines
+            })
+            .collect::<Vec<_>>();
+        to_text_pdf(&pages)
+    }
+}
+
+/// Returns the row and column of the given cell, such as A1 or P128.
+fn to_cell(cell: &str) -> Option<(usize, usize)> {
+    let column = cell.chars().next()?;
+    let row = cell[column.len_utf8()..].parse::<usize>().ok()?;
+    match (column, row) {
+        ('A'..='P', 1..=GRID_ROWS) => Some((row - 1, column as usize - 'A' as usize)),
+        _ => None,
+    }
+}
+
+/// Returns the given words of a row as their first four letters, in columns of five characters.
+fn to_abbreviations(row: &[String]) -> String {
+    row.iter()
+        .map(|word| format!("{:<5}", word.chars().take(4).collect::<String>()))
+        .collect::<String>()
+        .trim_end()
+        .to_string()
+}
+
+impl MaskSecrets for EntropyGrid {
+    fn mask_secrets(&mut self) {
+        self.entropy = SECRET_MASK.into();
+        self.grid_mnemonic = SECRET_MASK.into();
+        mask(&mut self.mnemonic);
+    }
+}
+
+#[cfg_attr(tarpaulin, skip)]
+impl Display for EntropyGrid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let header = (0..GRID_COLUMNS)
+            .map(|column| format!("{:<5}", (b'A' + column as u8) as char))
+            .collect::<String>();
+        let output = [
+            format!("      {}              {}\n", "Entropy".cyan().bold(), self.entropy),
+            format!(
+                "      {}        {}\n",
+                "Grid Mnemonic".cyan().bold(),
+                self.grid_mnemonic
+            ),
+            format!("      {}          {}\n", "Fingerprint".cyan().bold(), self.fingerprint),
+            // The grid is printed unindented, with its rows and columns labeled
+            format!("\n     {}\n", header.trim_end().cyan().bold()),
+            self.rows
+                .iter()
+                .enumerate()
+                .map(|(i, row)| format!("{} {}\n", format!("{:03} ", i + 1).cyan().bold(), to_abbreviations(row)))
+                .collect::<String>(),
+            match &self.pattern {
+                Some(pattern) => format!(
+                    "\n      {}              {}\n",
+                    "Pattern".cyan().bold(),
+                    pattern.join(" ")
+                ),
+                _ => "".to_owned(),
+            },
+            match &self.final_words {
+                Some(final_words) => format!(
+                    "      {}          {}\n",
+                    "Final Words".cyan().bold(),
+                    final_words.join(" ")
+                ),
+                _ => "".to_owned(),
+            },
+            match &self.mnemonic {
+                Some(mnemonic) => format!("      {}             {}\n", "Mnemonic".cyan().bold(), mnemonic),
+                _ => "".to_owned(),
+            },
+        ]
+        .concat();
+
+        // Removes final new line character
+        let output = output[..output.len() - 1].to_owned();
+        write!(f, "\n{}", output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The entropy 000102...0f, whose grid is computed independently of this module
+    const ENTROPY: &str = "000102030405060708090a0b0c0d0e0f";
+
+    #[test]
+    fn from_entropy() {
+        let grid = EntropyGrid::from_entropy(&hex::decode(ENTROPY).unwrap()).unwrap();
+        assert_eq!(ENTROPY, grid.entropy);
+        assert_eq!("be45cb26", grid.fingerprint);
+        assert_eq!(
+            "abandon amount liar amount expire adjust cage candy arch gather drum buyer",
+            grid.grid_mnemonic
+        );
+        assert_eq!(GRID_ROWS, grid.rows.len());
+        assert_eq!(
+            "outside life stove walk empty much champion trophy mimic ten welcome behind pelican beach pair humble",
+            grid.rows[0].join(" ")
+        );
+        assert_eq!(
+            "desk stairs cargo subject ancient glad valve canoe chase arrange bitter clog quantum pumpkin feed lizard",
+            grid.rows[GRID_ROWS - 1].join(" ")
+        );
+
+        // Each word of the wordlist is in the grid once
+        let mut words = grid.rows.concat();
+        words.sort();
+        assert_eq!(English::get_all(), words);
+
+        // The grid is restored from its mnemonic
+        let restored = EntropyGrid::from_mnemonic(&grid.grid_mnemonic).unwrap();
+        assert_eq!(grid.rows, restored.rows);
+    }
+
+    #[test]
+    fn from_seed() {
+        use rand::{rngs::StdRng, SeedableRng};
+
+        // A grid of a seeded random number generator is the grid of its first 16 bytes
+        let grid = EntropyGrid::new(&mut StdRng::seed_from_u64(1)).unwrap();
+        let entropy = StdRng::seed_from_u64(1).gen::<[u8; 16]>();
+        assert_eq!(hex::encode(entropy), grid.entropy);
+        assert_eq!(EntropyGrid::from_entropy(&entropy).unwrap().rows, grid.rows);
+        assert_eq!(grid.rows, EntropyGrid::new(&mut StdRng::seed_from_u64(1)).unwrap().rows);
+    }
+
+    #[test]
+    fn with_pattern() {
+        let grid = EntropyGrid::from_entropy(&hex::decode(ENTROPY).unwrap()).unwrap();
+
+        // The first 11 cells of the first row, with 128 final words of 7 free bits
+        let pattern = grid.clone().with_pattern("A1 B1 C1 D1 E1 F1 G1 H1 I1 J1 K1").unwrap();
+        let final_words = pattern.final_words.unwrap();
+        assert_eq!(128, final_words.len());
+        assert_eq!(["absent", "act", "airport"], final_words[..3]);
+        let mnemonic = pattern.mnemonic.unwrap();
+        assert_eq!(
+            "outside life stove walk empty much champion trophy mimic ten welcome absent",
+            mnemonic
+        );
+        assert!(BitcoinMnemonic::<Mainnet, English>::from_phrase(&mnemonic).is_ok());
+
+        // 23 cells (in lowercase, separated by commas), with 8 final words of 3 free bits
+        let cells = core::iter::once("p128".to_string())
+            .chain((1..=22).map(|row| format!("a{}", row)))
+            .collect::<Vec<String>>()
+            .join(",");
+        let pattern = grid.clone().with_pattern(&cells).unwrap();
+        assert_eq!(
+            ["best", "capital", "elder", "humor", "miss", "remain", "street", "verify"],
+            pattern.final_words.unwrap()[..]
+        );
+        let mnemonic = pattern.mnemonic.unwrap();
+        assert!(mnemonic.starts_with("lizard outside bounce option sight affair"));
+        assert!(mnemonic.ends_with("business wrestle excite best"));
+        assert!(BitcoinMnemonic::<Mainnet, English>::from_phrase(&mnemonic).is_ok());
+    }
+
+    #[test]
+    fn invalid_pattern() {
+        let grid = EntropyGrid::from_entropy(&hex::decode(ENTROPY).unwrap()).unwrap();
+        match grid.clone().with_pattern("A1 B1 C1") {
+            Err(CLIError::InvalidGridPattern(3)) => {}
+            result => panic!("unexpected result {:?}", result),
+        }
+        match grid.with_pattern("A1 B1 C1 D1 E1 F1 G1 H1 I1 J1 Q1") {
+            Err(CLIError::InvalidGridCell(cell)) => assert_eq!("Q1", cell),
+            result => panic!("unexpected result {:?}", result),
+        }
+        assert!(to_cell("A0").is_none());
+        assert!(to_cell("A129").is_none());
+        assert_eq!(Some((127, 15)), to_cell("P128"));
+    }
+}
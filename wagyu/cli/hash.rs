@@ -0,0 +1,47 @@
+//! # Hash
+//!
+//! Hashes hex or file input with the hash functions used in address derivations,
+//! so that derivations can be verified and test vectors built without external tools.
+
+use crate::cli::CLIError;
+use crate::model::crypto::checksum;
+
+use crypto::{blake2b::Blake2b, digest::Digest, ripemd160::Ripemd160};
+use std::{fs, path::Path};
+
+use crate::model::no_std::{String, Vec};
+
+/// Returns the bytes of the given input, read from a file if the input is the path of a file,
+/// or otherwise decoded from hex (with an optional `0x` prefix).
+pub fn read_hash_input(input: &str) -> Result<Vec<u8>, CLIError> {
+    if Path::new(input).is_file() {
+        return Ok(fs::read(input)?);
+    }
+    let hex = input.trim();
+    hex::decode(hex.strip_prefix("0x").unwrap_or(hex)).map_err(|_| CLIError::InvalidHashInput(input.into()))
+}
+
+/// Returns the hex encoded digest of the given data with the given hash algorithm,
+/// where `blake2b` is BLAKE2b-512, and `sha256d` is the double SHA-256 of Bitcoin checksums.
+pub fn hash(algorithm: &str, data: &[u8]) -> Result<String, CLIError> {
+    let digest = match algorithm {
+        "blake2b" => {
+            let mut digest = [0u8; 64];
+            let mut hasher = Blake2b::new(digest.len());
+            hasher.input(data);
+            hasher.result(&mut digest);
+            digest.to_vec()
+        }
+        "keccak256" => tiny_keccak::keccak256(data).to_vec(),
+        "ripemd160" => {
+            let mut digest = [0u8; 20];
+            let mut hasher = Ripemd160::new();
+            hasher.input(data);
+            hasher.result(&mut digest);
+            digest.to_vec()
+        }
+        "sha256d" => checksum(data),
+        _ => return Err(CLIError::UnsupportedHashAlgorithm(algorithm.into())),
+    };
+    Ok(hex::encode(digest))
+}
@@ -0,0 +1,586 @@
+//! # Inheritance
+//!
+//! Generates the inheritance package of a Bitcoin HD wallet, a directory that separates what the heirs
+//! of the wallet need to find and watch its funds from the secret that spends them:
+//!
+//! - `inheritance.json` and `inheritance.pdf`, the master fingerprint, the extended public key, descriptor,
+//!   and first address of the first account of each standard purpose (BIP-44, 49, 84, and 86), and the
+//!   instructions to recover the wallet, in plaintext
+//! - `secrets.json`, the mnemonic and BIP-39 password in a wallet file encrypted with a passphrase,
+//!   or `secrets.asc`, the wallet file encrypted to age or GPG recipients, or
+//! - `share-<index>.txt`, the Shamir shares of the entropy of the mnemonic, of which a threshold recovers it
+//!
+//! The shares are split byte by byte over GF(256), and each share is the English BIP-39 mnemonic of its
+//! bytes, written as `<index>:<threshold>:<mnemonic>`. A share is not a SLIP-39 share, and is only
+//! combined by `wagyu inheritance --combine`.
+
+use crate::bitcoin::{wordlist::English, BitcoinMnemonic, Mainnet};
+use crate::cli::{
+    bitcoin::BitcoinWallet, encrypt_to, to_text_pdf, CLIError, MaskSecrets, Recipient, WalletFile, WatchOnlyWallet,
+    PDF_LINE_WIDTH, SECRET_MASK,
+};
+use crate::model::{ExtendedPrivateKey, Mnemonic};
+
+use colored::*;
+use core::{fmt, fmt::Display, str::FromStr};
+use rand::{rngs::StdRng, Rng};
+use rand_core::SeedableRng;
+use serde::Serialize;
+use serde_json::json;
+use std::path::Path;
+
+use crate::model::no_std::{format, vec, String, ToString, Vec};
+
+/// The current version of the inheritance package schema
+pub const INHERITANCE_PACKAGE_VERSION: u32 = 1;
+
+/// The standard purposes of the accounts of an inheritance package, and their names
+const INHERITANCE_PURPOSES: [(u32, &str); 4] = [(44, "legacy"), (49, "segwit"), (84, "bech32"), (86, "taproot")];
+
+/// The number of lines of an inheritance package on each page of its PDF
+const PDF_LINES_PER_PAGE: usize = 64;
+
+/// Represents the first account of a standard purpose of an inheritance package
+#[derive(Serialize, Clone, Debug)]
+pub struct InheritanceAccount {
+    pub format: String,
+    pub account_path: String,
+    pub extended_public_key: String,
+    /// The descriptor of the receive chain of the account
+    pub descriptor: String,
+    /// The address at index 0 of the receive chain of the account
+    pub first_address: String,
+}
+
+/// Represents the plaintext contents of an inheritance package, without its secrets
+#[derive(Serialize, Clone, Debug)]
+pub struct InheritancePackage {
+    /// The version of the package schema
+    pub version: u32,
+    pub network: String,
+    pub master_fingerprint: String,
+    pub accounts: Vec<InheritanceAccount>,
+    /// The file of the encrypted secrets, or the shares of the mnemonic
+    pub secrets: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub shares: Option<u8>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub threshold: Option<u8>,
+    pub instructions: Vec<String>,
+    /// The files written to the directory of the package
+    #[serde(skip)]
+    pub files: Vec<String>,
+}
+
+/// Represents a share of the entropy of a mnemonic, of which `threshold` shares recover it
+#[derive(Serialize, Clone, Debug)]
+pub struct InheritanceShare {
+    pub index: u8,
+    pub threshold: u8,
+    /// The English BIP-39 mnemonic of the bytes of the share
+    pub mnemonic: String,
+}
+
+/// Represents the mnemonic recovered from the shares of an inheritance package
+#[derive(Serialize, Clone, Debug)]
+pub struct InheritanceRecovery {
+    pub mnemonic: String,
+    pub master_fingerprint: String,
+    pub shares: Vec<u8>,
+}
+
+impl InheritancePackage {
+    /// Writes the inheritance package of the given English mnemonic and BIP-39 password to the given directory.
+    /// The secrets are encrypted to the given recipients, or with a passphrase, unless the mnemonic is split
+    /// into the given number of shares and threshold.
+    pub fn write(
+        directory: &str,
+        mnemonic: &str,
+        password: Option<&str>,
+        shares: Option<(u8, u8)>,
+        recipients: Option<&str>,
+        note: Option<&str>,
+    ) -> Result<Self, CLIError> {
+        let phrase = BitcoinMnemonic::<Mainnet, English>::from_phrase(mnemonic)?;
+        let master_fingerprint = hex::encode(to_master_fingerprint(mnemonic, password)?);
+
+        let mut accounts = vec![];
+        for (purpose, _) in INHERITANCE_PURPOSES.iter() {
+            let path = format!("m/{}'/0'/0'/0/0", purpose);
+            let (fingerprint, account_path, account_key) =
+                BitcoinWallet::to_account::<Mainnet, English>(mnemonic, password, &path)?;
+            let watch_only = WatchOnlyWallet::new("sparrow", &fingerprint, &account_path, &account_key)?;
+            let wallet = BitcoinWallet::from_mnemonic::<Mainnet, English>(mnemonic, &password, &path)?;
+            accounts.push(InheritanceAccount {
+                format: wallet.format.unwrap_or_default(),
+                account_path,
+                extended_public_key: watch_only.extended_public_key,
+                descriptor: watch_only.descriptor,
+                first_address: wallet.address.unwrap_or_default(),
+            });
+        }
+
+        std::fs::create_dir_all(directory)?;
+        let file = |name: &str| Path::new(directory).join(name).to_string_lossy().to_string();
+        let secrets = json!({ "mnemonic": mnemonic, "password": password });
+        let mut files = vec![];
+        let (secrets, recovery) = match (shares, recipients) {
+            (Some((count, threshold)), _) => {
+                for share in split_shares(&phrase.to_entropy(), count, threshold)? {
+                    let path = file(&format!("share-{}.txt", share.index));
+                    std::fs::write(&path, share.to_text(&master_fingerprint, count))?;
+                    files.push(path);
+                }
+                // The BIP-39 password is not split, and is left to the heirs apart from the shares
+                let password = match password {
+                    Some(_) => " The wallet also has a BIP-39 password, which is not in the shares, and is \
+                                given with `--password \"<password>\"` when combining them."
+                        .to_string(),
+                    None => "".to_string(),
+                };
+                (
+                    format!("{} shares of the mnemonic, of which {} recover it", count, threshold),
+                    format!(
+                        "The mnemonic is split into {} shares, each held by a different person, of which any {} \
+                         recover it with `wagyu inheritance --combine \"<share>\" --combine \"<share>\"`.{}",
+                        count, threshold, password
+                    ),
+                )
+            }
+            (None, Some(recipients)) => {
+                let path = file("secrets.asc");
+                let wallet_file = WalletFile::new(&[secrets], "inheritance")?;
+                std::fs::write(
+                    &path,
+                    encrypt_to(
+                        serde_json::to_string_pretty(&wallet_file)?.as_bytes(),
+                        &Recipient::from_list(recipients)?,
+                    )?,
+                )?;
+                files.push(path);
+                (
+                    "secrets.asc".to_string(),
+                    format!(
+                        "The mnemonic is encrypted in secrets.asc to {}. Decrypt it with age or GPG \
+                         into a wallet file, and open the wallet file with `wagyu open <file>`.",
+                        recipients
+                    ),
+                )
+            }
+            (None, None) => {
+                let path = file("secrets.json");
+                WalletFile::save(&[secrets], "inheritance", &path, true)?;
+                files.push(path);
+                (
+                    "secrets.json".to_string(),
+                    "The mnemonic is encrypted in secrets.json. Open it with `wagyu open secrets.json`, \
+                     and the passphrase the owner of the wallet left for you."
+                        .to_string(),
+                )
+            }
+        };
+
+        let mut instructions = vec![
+            format!(
+                "This package holds the recovery information of a Bitcoin wallet of master fingerprint {}.",
+                master_fingerprint
+            ),
+            "The extended public keys and descriptors show the balance and history of each account without \
+             any secret, when imported as a watch-only wallet (such as into Sparrow or Bitcoin Core)."
+                .to_string(),
+            recovery,
+            format!(
+                "Restore the mnemonic in a wallet that supports BIP-39 and the account paths of this package, \
+                 and check that its master fingerprint is {} before moving any funds.",
+                master_fingerprint
+            ),
+            "Never enter the mnemonic into a website, or share it with anyone who contacts you about the wallet."
+                .to_string(),
+        ];
+        if let Some(note) = note {
+            instructions.push(format!("Note of the owner: {}", note));
+        }
+
+        let mut package = Self {
+            version: INHERITANCE_PACKAGE_VERSION,
+            network: "mainnet".into(),
+            master_fingerprint,
+            accounts,
+            secrets,
+            shares: shares.map(|(count, _)| count),
+            threshold: shares.map(|(_, threshold)| threshold),
+            instructions,
+            files: vec![],
+        };
+
+        let (json, pdf) = (file("inheritance.json"), file("inheritance.pdf"));
+        std::fs::write(&json, serde_json::to_string_pretty(&package)?)?;
+        std::fs::write(&pdf, package.to_pdf())?;
+        package.files = [vec![json, pdf], files].concat();
+        Ok(package)
+    }
+
+    /// Returns the PDF of the package, of its instructions and accounts, on pages of 64 lines in Courier.
+    pub fn to_pdf(&self) -> Vec<u8> {
+        let mut lines = vec![
+            format!("Inheritance Package {}", self.master_fingerprint),
+            "".to_string(),
+            "Instructions".to_string(),
+        ];
+        for (i, instruction) in self.instructions.iter().enumerate() {
+            lines.push("".to_string());
+            lines.extend(to_wrapped_lines(&format!("{}. {}", i + 1, instruction), "   "));
+        }
+        for (account, (_, name)) in self.accounts.iter().zip(INHERITANCE_PURPOSES.iter()) {
+            lines.extend(vec![
+                "".to_string(),
+                format!("Account {} ({})", account.account_path, name),
+            ]);
+            lines.extend(to_wrapped_lines(
+                &format!("Extended public key {}", account.extended_public_key),
+                "  ",
+            ));
+            lines.extend(to_wrapped_lines(&format!("Descriptor {}", account.descriptor), "  "));
+            lines.push(format!("First address {}", account.first_address));
+        }
+        lines.extend(vec!["".to_string(), format!("Secrets {}", self.secrets)]);
+
+        let pages = lines
+            .chunks(PDF_LINES_PER_PAGE)
+            .map(|page| page.to_vec())
+            .collect::<Vec<_>>();
+        to_text_pdf(&pages)
+    }
+}
+
+impl FromStr for InheritanceShare {
+    type Err = CLIError;
+
+    /// Returns the share of the given `<index>:<threshold>:<mnemonic>`.
+    fn from_str(share: &str) -> Result<Self, CLIError> {
+        let invalid = || CLIError::InvalidShare;
+        let mut parts = share.trim().splitn(3, ':');
+        let index = parts
+            .next()
+            .and_then(|index| index.trim().parse().ok())
+            .ok_or_else(invalid)?;
+        let threshold = parts
+            .next()
+            .and_then(|threshold| threshold.trim().parse().ok())
+            .ok_or_else(invalid)?;
+        let mnemonic = parts.next().map(str::trim).ok_or_else(invalid)?;
+        BitcoinMnemonic::<Mainnet, English>::from_phrase(mnemonic).map_err(|_| invalid())?;
+        match (index, threshold) {
+            (0, _) | (_, 0) => Err(invalid()),
+            _ => Ok(Self {
+                index,
+                threshold,
+                mnemonic: mnemonic.into(),
+            }),
+        }
+    }
+}
+
+impl InheritanceShare {
+    /// Returns the text of the file of the share, of the given number of shares of the wallet of the given fingerprint.
+    fn to_text(&self, master_fingerprint: &str, count: u8) -> String {
+        format!(
+            "Share {} of {} of the inheritance package of the Bitcoin wallet of master fingerprint {}.\n\
+             Any {} of the shares recover its mnemonic with wagyu inheritance --combine.\n\n{}:{}:{}\n",
+            self.index, count, master_fingerprint, self.threshold, self.index, self.threshold, self.mnemonic
+        )
+    }
+}
+
+impl InheritanceRecovery {
+    /// Returns the mnemonic recovered from the given shares, with its master fingerprint of the given BIP-39 password.
+    pub fn combine(shares: &[InheritanceShare], password: Option<&str>) -> Result<Self, CLIError> {
+        let threshold = shares.first().map(|share| share.threshold).unwrap_or_default();
+        if shares.iter().any(|share| share.threshold != threshold) {
+            return Err(CLIError::MismatchedShares);
+        }
+        if shares.len() < threshold as usize {
+            return Err(CLIError::MissingShares(threshold, shares.len()));
+        }
+
+        let points = shares
+            .iter()
+            .take(threshold as usize)
+            .map(|share| {
+                let mnemonic = BitcoinMnemonic::<Mainnet, English>::from_phrase(&share.mnemonic)?;
+                Ok((share.index, mnemonic.to_entropy()))
+            })
+            .collect::<Result<Vec<_>, CLIError>>()?;
+        let entropy = combine_shares(&points)?;
+        let mnemonic = BitcoinMnemonic::<Mainnet, English>::from_entropy(&entropy)?.to_phrase()?;
+        Ok(Self {
+            master_fingerprint: hex::encode(to_master_fingerprint(&mnemonic, password)?),
+            mnemonic,
+            shares: points.iter().map(|(index, _)| *index).collect(),
+        })
+    }
+}
+
+/// Returns the master fingerprint of the given English mnemonic and BIP-39 password.
+fn to_master_fingerprint(mnemonic: &str, password: Option<&str>) -> Result<[u8; 4], CLIError> {
+    Ok(
+        BitcoinWallet::to_master_extended_private_key::<Mainnet, English>(mnemonic, password)?
+            .to_extended_public_key()
+            .to_fingerprint(),
+    )
+}
+
+/// Returns the given text in lines that fit the width of a PDF page, with each line after the first indented.
+fn to_wrapped_lines(text: &str, indent: &str) -> Vec<String> {
+    // A word longer than a line, such as an extended public key or a descriptor, is split across lines
+    let words = text
+        .split(' ')
+        .filter(|word| !word.is_empty())
+        .flat_map(|word| {
+            word.chars()
+                .collect::<Vec<_>>()
+                .chunks(PDF_LINE_WIDTH - indent.len())
+                .map(|chunk| chunk.iter().collect::<String>())
+                .collect::<Vec<_>>()
+        })
+        .collect::<Vec<_>>();
+
+    let mut lines: Vec<String> = vec![];
+    for word in words {
+        match lines.last_mut() {
+            Some(line) if line.chars().count() + 1 + word.chars().count() <= PDF_LINE_WIDTH => {
+                line.push(' ');
+                line.push_str(&word);
+            }
+            Some(_) => lines.push(format!("{}{}", indent, word)),
+            None => lines.push(word),
+        }
+    }
+    lines
+}
+
+/// Returns the given number of shares of the given secret, of which the given threshold recovers it,
+/// with each byte of the secret the constant term of a random polynomial over GF(256) of degree `threshold - 1`.
+fn split_shares(secret: &[u8], count: u8, threshold: u8) -> Result<Vec<InheritanceShare>, CLIError> {
+    if threshold < 2 || threshold > count {
+        return Err(CLIError::InvalidShareThreshold(count, threshold));
+    }
+
+    let rng = &mut StdRng::from_entropy();
+    let polynomials = secret
+        .iter()
+        .map(|byte| {
+            let mut coefficients = vec![*byte];
+            coefficients.extend((1..threshold).map(|_| rng.gen::<u8>()));
+            coefficients
+        })
+        .collect::<Vec<_>>();
+
+    (1..=count)
+        .map(|index| {
+            // The polynomial is evaluated at the index of the share with Horner's method
+            let bytes = polynomials
+                .iter()
+                .map(|coefficients| {
+                    coefficients
+                        .iter()
+                        .rev()
+                        .fold(0u8, |value, coefficient| gf256_mul(value, index) ^ coefficient)
+                })
+                .collect::<Vec<_>>();
+            Ok(InheritanceShare {
+                index,
+                threshold,
+                mnemonic: BitcoinMnemonic::<Mainnet, English>::from_entropy(&bytes)?.to_phrase()?,
+            })
+        })
+        .collect()
+}
+
+/// Returns the secret of the given indices and bytes of shares, interpolated at zero with Lagrange polynomials.
+fn combine_shares(shares: &[(u8, Vec<u8>)]) -> Result<Vec<u8>, CLIError> {
+    let length = shares.first().map(|(_, bytes)| bytes.len()).unwrap_or_default();
+    for (i, (index, bytes)) in shares.iter().enumerate() {
+        if bytes.len() != length || shares[..i].iter().any(|(other, _)| other == index) {
+            return Err(CLIError::MismatchedShares);
+        }
+    }
+
+    let mut secret = vec![0u8; length];
+    for (i, (index, bytes)) in shares.iter().enumerate() {
+        // The basis polynomial of the share at zero is the product of x_j / (x_j - x_i) of the other shares
+        let basis = shares
+            .iter()
+            .enumerate()
+            .filter(|(j, _)| *j != i)
+            .fold(1u8, |basis, (_, (other, _))| {
+                gf256_mul(basis, gf256_mul(*other, gf256_inverse(other ^ index)))
+            });
+        for (byte, share) in secret.iter_mut().zip(bytes.iter()) {
+            *byte ^= gf256_mul(basis, *share);
+        }
+    }
+    Ok(secret)
+}
+
+/// Returns the product of the given elements of GF(256), of the polynomial x^8 + x^4 + x^3 + x + 1,
+/// without branches on the elements.
+fn gf256_mul(mut a: u8, mut b: u8) -> u8 {
+    let mut product = 0u8;
+    for _ in 0..8 {
+        product ^= a & 0u8.wrapping_sub(b & 1);
+        a = (a << 1) ^ (0x1b & 0u8.wrapping_sub(a >> 7));
+        b >>= 1;
+    }
+    product
+}
+
+/// Returns the multiplicative inverse of the given nonzero element of GF(256), its 254th power.
+fn gf256_inverse(a: u8) -> u8 {
+    let mut inverse = 1u8;
+    let mut power = a;
+    for bit in 0..8 {
+        if (254 >> bit) & 1 == 1 {
+            inverse = gf256_mul(inverse, power);
+        }
+        power = gf256_mul(power, power);
+    }
+    inverse
+}
+
+impl MaskSecrets for InheritancePackage {
+    fn mask_secrets(&mut self) {}
+}
+
+impl MaskSecrets for InheritanceRecovery {
+    fn mask_secrets(&mut self) {
+        self.mnemonic = SECRET_MASK.into();
+    }
+}
+
+#[cfg_attr(tarpaulin, skip)]
+impl Display for InheritancePackage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f)?;
+        writeln!(
+            f,
+            "      {}   {}",
+            "Master Fingerprint".cyan().bold(),
+            self.master_fingerprint
+        )?;
+        for account in &self.accounts {
+            writeln!(
+                f,
+                "      {}{}",
+                format!("{:<21}", account.account_path).cyan().bold(),
+                account.extended_public_key
+            )?;
+        }
+        writeln!(f, "      {}              {}", "Secrets".cyan().bold(), self.secrets)?;
+        write!(
+            f,
+            "      {}                {}",
+            "Files".cyan().bold(),
+            self.files.join(", ")
+        )
+    }
+}
+
+#[cfg_attr(tarpaulin, skip)]
+impl Display for InheritanceRecovery {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let shares = self.shares.iter().map(u8::to_string).collect::<Vec<_>>();
+        let output = [
+            format!("      {}             {}\n", "Mnemonic".cyan().bold(), self.mnemonic),
+            format!(
+                "      {}   {}\n",
+                "Master Fingerprint".cyan().bold(),
+                self.master_fingerprint
+            ),
+            format!("      {}               {}\n", "Shares".cyan().bold(), shares.join(", ")),
+        ]
+        .concat();
+
+        // Removes final new line character
+        let output = output[..output.len() - 1].to_string();
+        write!(f, "\n{}", output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The English BIP-39 mnemonic of the entropy 7f7f...7f of the BIP-39 test vectors
+    const MNEMONIC: &str = "legal winner thank year wave sausage worth useful legal winner thank yellow";
+
+    /// The shares 1 to 3 of the entropy of the mnemonic, of a threshold of 2, with the coefficient of
+    /// degree 1 of the polynomial of each byte its index + 1
+    const SHARES: [&str; 3] = [
+        "1:2:lazy typical burst visual nurse desert supply purse inflict near foster taste",
+        "2:2:latin swift fruit turtle damage response remember honey guess climb success salute",
+        "3:2:ladder slim orphan thought first crisp muscle clump hidden drop fortune palace",
+    ];
+
+    #[test]
+    fn gf256() {
+        // The products and inverse of FIPS-197 (AES)
+        assert_eq!(0xc1, gf256_mul(0x57, 0x83));
+        assert_eq!(0xfe, gf256_mul(0x57, 0x13));
+        assert_eq!(0xca, gf256_inverse(0x53));
+        assert!((1..=255u8).all(|a| gf256_mul(a, gf256_inverse(a)) == 1));
+    }
+
+    #[test]
+    fn combine() {
+        let shares = SHARES
+            .iter()
+            .map(|share| InheritanceShare::from_str(share).unwrap())
+            .collect::<Vec<_>>();
+        for pair in [[0, 1], [0, 2], [2, 1]].iter() {
+            let recovery =
+                InheritanceRecovery::combine(&[shares[pair[0]].clone(), shares[pair[1]].clone()], None).unwrap();
+            assert_eq!(MNEMONIC, recovery.mnemonic);
+            assert_eq!("b8688df1", recovery.master_fingerprint);
+        }
+
+        // The master fingerprint is of the BIP-39 password of the wallet
+        let recovery = InheritanceRecovery::combine(&shares, Some("TREZOR")).unwrap();
+        assert_eq!(MNEMONIC, recovery.mnemonic);
+        assert_eq!("1ddb040f", recovery.master_fingerprint);
+        assert_eq!(vec![1, 2], recovery.shares);
+
+        match InheritanceRecovery::combine(&shares[..1], None) {
+            Err(CLIError::MissingShares(2, 1)) => {}
+            _ => panic!("expected missing shares"),
+        };
+        match InheritanceRecovery::combine(&[shares[0].clone(), shares[0].clone()], None) {
+            Err(CLIError::MismatchedShares) => {}
+            _ => panic!("expected mismatched shares"),
+        };
+    }
+
+    #[test]
+    fn split() {
+        let entropy = BitcoinMnemonic::<Mainnet, English>::from_phrase(MNEMONIC)
+            .unwrap()
+            .to_entropy();
+        let shares = split_shares(&entropy, 5, 3).unwrap();
+        assert_eq!(
+            vec![1, 2, 3, 4, 5],
+            shares.iter().map(|share| share.index).collect::<Vec<_>>()
+        );
+
+        let recovery =
+            InheritanceRecovery::combine(&[shares[4].clone(), shares[0].clone(), shares[2].clone()], None).unwrap();
+        assert_eq!(MNEMONIC, recovery.mnemonic);
+        match InheritanceRecovery::combine(&shares[..2], None) {
+            Err(CLIError::MissingShares(3, 2)) => {}
+            _ => panic!("expected missing shares"),
+        };
+        match split_shares(&entropy, 2, 3) {
+            Err(CLIError::InvalidShareThreshold(2, 3)) => {}
+            _ => panic!("expected an invalid threshold"),
+        };
+    }
+}
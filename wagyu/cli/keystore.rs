@@ -0,0 +1,511 @@
+//! # Keystore
+//!
+//! Exports the accounts of an HD wallet as encrypted keystore files (Web3 Secret Storage, version 3),
+//! as imported account by account by MetaMask, MyEtherWallet, and geth, without exposing the mnemonic.
+//! Each private key is encrypted with AES-128-CTR under a key derived from a passphrase with scrypt,
+//! and authenticated with the keccak256 hash of the second half of the derived key and the ciphertext.
+//!
+//! The keystore files are written to a directory with an `index.json` of the derivation path,
+//! address, and file of each account. The passphrase is read as for encrypted wallet files.
+//!
+//! A keystore file is imported by its private key, decrypted with a key derived with scrypt or PBKDF2.
+//! A presale wallet of the Ethereum crowdsale (2014) is imported likewise, of its seed encrypted with
+//! AES-128-CBC under a key derived from the password with PBKDF2, as the keccak256 hash of the seed.
+
+use crate::cli::{read_passphrase, to_scrypt_params, CLIError, MaskSecrets};
+use crate::ethereum::{EthereumFormat, EthereumPrivateKey};
+use crate::model::{crypto::constant_time_eq, PrivateKey, PublicKey};
+
+use colored::*;
+use core::{fmt, fmt::Display, ops::RangeInclusive, str::FromStr};
+use crypto::{
+    aes::{cbc_decryptor, ctr, KeySize},
+    blockmodes::PkcsPadding,
+    buffer::{ReadBuffer, RefReadBuffer, RefWriteBuffer, WriteBuffer},
+    hmac::Hmac,
+    pbkdf2::pbkdf2,
+    scrypt::{scrypt, ScryptParams},
+    sha2::Sha256,
+};
+use rand::{rngs::StdRng, Rng};
+use rand_core::SeedableRng;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::Path;
+
+use crate::model::no_std::{format, vec, String, ToString, Vec};
+
+/// The current version of the keystore index schema
+pub const KEYSTORE_INDEX_VERSION: u32 = 1;
+
+/// The scrypt cost parameters of keystore files, the standard parameters of geth
+const SCRYPT_LOG_N: u8 = 18;
+const SCRYPT_R: u32 = 8;
+const SCRYPT_P: u32 = 1;
+
+/// The ranges of the derived key length and the PBKDF2 iterations of a keystore file
+const DKLEN_RANGE: RangeInclusive<usize> = 32..=64;
+const PBKDF2_ROUNDS_RANGE: RangeInclusive<u32> = 1..=10_000_000;
+
+/// The PBKDF2 iterations of the key of a presale wallet
+const PRESALE_PBKDF2_ROUNDS: u32 = 2000;
+
+/// Represents an encrypted keystore file of a private key (Web3 Secret Storage)
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Keystore {
+    /// The address of the private key, in lowercase hex without a prefix
+    #[serde(default)]
+    pub address: String,
+    #[serde(alias = "Crypto")]
+    pub crypto: KeystoreCrypto,
+    pub id: String,
+    pub version: u32,
+}
+
+/// Represents the encrypted private key of a keystore file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeystoreCrypto {
+    pub cipher: String,
+    pub cipherparams: KeystoreCipherParams,
+    pub ciphertext: String,
+    pub kdf: String,
+    pub kdfparams: KeystoreKdfParams,
+    pub mac: String,
+}
+
+/// Represents the cipher parameters of a keystore file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeystoreCipherParams {
+    pub iv: String,
+}
+
+/// Represents the scrypt or PBKDF2 parameters of a keystore file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeystoreKdfParams {
+    pub dklen: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub n: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub p: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub r: Option<u32>,
+    /// The PBKDF2 iterations and pseudorandom function
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub c: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub prf: Option<String>,
+    pub salt: String,
+}
+
+/// Represents an exported account of an HD wallet
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeystoreAccount {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    pub address: String,
+    /// The name of the keystore file of the account, in the keystore directory
+    pub file: String,
+}
+
+/// Represents the index of the keystore files of a directory
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct KeystoreIndex {
+    /// The version of the index schema
+    pub version: u32,
+    pub accounts: Vec<KeystoreAccount>,
+}
+
+impl Keystore {
+    /// Returns the keystore file of the given private key of the given address, encrypted with the given passphrase.
+    pub fn new<R: Rng>(rng: &mut R, private_key: &[u8], address: &str, passphrase: &str) -> Self {
+        let salt: [u8; 32] = rng.gen();
+        let iv: [u8; 16] = rng.gen();
+
+        let mut key = vec![0u8; 32];
+        scrypt(
+            passphrase.as_bytes(),
+            &salt,
+            &ScryptParams::new(SCRYPT_LOG_N, SCRYPT_R, SCRYPT_P),
+            &mut key,
+        );
+        let mut ciphertext = vec![0u8; private_key.len()];
+        ctr(KeySize::KeySize128, &key[..16], &iv).process(private_key, &mut ciphertext);
+        let mac = tiny_keccak::keccak256(&[&key[16..], &ciphertext[..]].concat());
+
+        // The ID is a random (version 4) UUID
+        let mut id: [u8; 16] = rng.gen();
+        id[6] = (id[6] & 0x0f) | 0x40;
+        id[8] = (id[8] & 0x3f) | 0x80;
+        let id = hex::encode(id);
+
+        Self {
+            address: address.trim_start_matches("0x").to_lowercase(),
+            crypto: KeystoreCrypto {
+                cipher: "aes-128-ctr".into(),
+                cipherparams: KeystoreCipherParams { iv: hex::encode(iv) },
+                ciphertext: hex::encode(ciphertext),
+                kdf: "scrypt".into(),
+                kdfparams: KeystoreKdfParams {
+                    dklen: key.len(),
+                    n: Some(1 << SCRYPT_LOG_N),
+                    p: Some(SCRYPT_P),
+                    r: Some(SCRYPT_R),
+                    c: None,
+                    prf: None,
+                    salt: hex::encode(salt),
+                },
+                mac: hex::encode(mac),
+            },
+            id: format!(
+                "{}-{}-{}-{}-{}",
+                &id[..8],
+                &id[8..12],
+                &id[12..16],
+                &id[16..20],
+                &id[20..]
+            ),
+            version: 3,
+        }
+    }
+
+    /// Returns the private key in hex of the keystore file or presale wallet at the given path,
+    /// decrypted with a passphrase.
+    pub fn read(path: &str) -> Result<String, CLIError> {
+        let file: Value = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+        let passphrase = read_passphrase(false)?;
+        let (private_key, address) = match file["encseed"].as_str() {
+            Some(encrypted_seed) => (
+                Self::decrypt_presale(encrypted_seed, &passphrase)?,
+                file["ethaddr"].as_str().unwrap_or_default().to_string(),
+            ),
+            None => {
+                let keystore: Self = serde_json::from_value(file)?;
+                (keystore.decrypt(&passphrase)?, keystore.address)
+            }
+        };
+
+        // The private key of a presale wallet is verified by its address, as its seed is not authenticated
+        let private_key = EthereumPrivateKey::from_str(&hex::encode(private_key))?;
+        let decrypted_address = private_key.to_public_key().to_address(&EthereumFormat::Standard)?;
+        match address.is_empty()
+            || decrypted_address
+                .to_string()
+                .to_lowercase()
+                .ends_with(&address.to_lowercase())
+        {
+            true => Ok(private_key.to_string()),
+            false => Err(CLIError::InvalidPassphrase),
+        }
+    }
+
+    /// Returns the private key of the keystore file, decrypted with the given passphrase.
+    /// The parameters of the file are checked before any key is derived.
+    fn decrypt(&self, passphrase: &str) -> Result<Vec<u8>, CLIError> {
+        let crypto = &self.crypto;
+        if crypto.cipher != "aes-128-ctr" {
+            return Err(CLIError::UnsupportedEncryption(format!(
+                "keystore cipher {}",
+                crypto.cipher
+            )));
+        }
+        let iv = hex::decode(&crypto.cipherparams.iv)?;
+        if iv.len() != 16 {
+            return Err(CLIError::InvalidKeystore(format!(
+                "iv of {} bytes (expected 16)",
+                iv.len()
+            )));
+        }
+
+        let params = &crypto.kdfparams;
+        if !DKLEN_RANGE.contains(&params.dklen) {
+            return Err(CLIError::InvalidKdfParameters(format!(
+                "dklen = {} (expected {} to {})",
+                params.dklen,
+                DKLEN_RANGE.start(),
+                DKLEN_RANGE.end()
+            )));
+        }
+        let salt = hex::decode(&params.salt)?;
+        let mut key = vec![0u8; params.dklen];
+        match (crypto.kdf.as_str(), params.n, params.r, params.p, params.c) {
+            ("scrypt", Some(n), Some(r), Some(p), _) => {
+                // The cost parameter is a power of two, of which the exponent is bounded as of a wallet file
+                if !n.is_power_of_two() {
+                    return Err(CLIError::InvalidKdfParameters(format!(
+                        "scrypt n = {} (expected a power of two)",
+                        n
+                    )));
+                }
+                scrypt(
+                    passphrase.as_bytes(),
+                    &salt,
+                    &to_scrypt_params(n.trailing_zeros() as u8, r, p)?,
+                    &mut key,
+                )
+            }
+            ("pbkdf2", _, _, _, Some(c)) => {
+                if params.prf.as_deref().unwrap_or("hmac-sha256") != "hmac-sha256" {
+                    return Err(CLIError::UnsupportedEncryption(format!(
+                        "keystore prf {}",
+                        params.prf.as_deref().unwrap_or_default()
+                    )));
+                }
+                if !PBKDF2_ROUNDS_RANGE.contains(&c) {
+                    return Err(CLIError::InvalidKdfParameters(format!(
+                        "pbkdf2 c = {} (expected {} to {})",
+                        c,
+                        PBKDF2_ROUNDS_RANGE.start(),
+                        PBKDF2_ROUNDS_RANGE.end()
+                    )));
+                }
+                pbkdf2(&mut Hmac::new(Sha256::new(), passphrase.as_bytes()), &salt, c, &mut key)
+            }
+            (kdf @ "scrypt", _, _, _, _) | (kdf @ "pbkdf2", _, _, _, _) => {
+                return Err(CLIError::InvalidKdfParameters(format!("missing {} parameters", kdf)))
+            }
+            (kdf, _, _, _, _) => return Err(CLIError::UnsupportedEncryption(format!("keystore kdf {}", kdf))),
+        };
+
+        let ciphertext = hex::decode(&crypto.ciphertext)?;
+        let mac = tiny_keccak::keccak256(&[&key[16..32], &ciphertext[..]].concat());
+        if !constant_time_eq(&mac, &hex::decode(&crypto.mac)?) {
+            return Err(CLIError::InvalidPassphrase);
+        }
+        let mut private_key = vec![0u8; ciphertext.len()];
+        ctr(KeySize::KeySize128, &key[..16], &iv).process(&ciphertext, &mut private_key);
+        Ok(private_key)
+    }
+
+    /// Returns the private key of the given encrypted seed of a presale wallet, decrypted with the given password.
+    fn decrypt_presale(encrypted_seed: &str, password: &str) -> Result<Vec<u8>, CLIError> {
+        let encrypted_seed = hex::decode(encrypted_seed)?;
+        if encrypted_seed.len() < 32 || encrypted_seed.len() % 16 != 0 {
+            return Err(CLIError::InvalidPassphrase);
+        }
+
+        // The key is derived from the password with itself as the salt, and the IV precedes the ciphertext
+        let mut key = [0u8; 16];
+        pbkdf2(
+            &mut Hmac::new(Sha256::new(), password.as_bytes()),
+            password.as_bytes(),
+            PRESALE_PBKDF2_ROUNDS,
+            &mut key,
+        );
+        let (iv, ciphertext) = encrypted_seed.split_at(16);
+        let mut decryptor = cbc_decryptor(KeySize::KeySize128, &key, iv, PkcsPadding);
+        let mut seed = vec![0u8; ciphertext.len()];
+        let mut buffer = RefWriteBuffer::new(&mut seed);
+        decryptor
+            .decrypt(&mut RefReadBuffer::new(ciphertext), &mut buffer, true)
+            .map_err(|_| CLIError::InvalidPassphrase)?;
+        Ok(tiny_keccak::keccak256(buffer.take_read_buffer().take_remaining()).to_vec())
+    }
+
+    /// Writes a keystore file of each of the given accounts, of their path, private key in hex, and address,
+    /// and their index, to the given directory, encrypted with a passphrase. Returns the exported accounts.
+    pub fn export(
+        accounts: &[(Option<String>, String, String)],
+        directory: &str,
+    ) -> Result<Vec<KeystoreAccount>, CLIError> {
+        let passphrase = read_passphrase(true)?;
+        let rng = &mut StdRng::from_entropy();
+        std::fs::create_dir_all(directory)?;
+
+        let mut exported = vec![];
+        for (path, private_key, address) in accounts {
+            let private_key = hex::decode(private_key.trim_start_matches("0x"))?;
+            let keystore = Self::new(rng, &private_key, address, &passphrase);
+            let file = format!("{}.json", address);
+            std::fs::write(
+                Path::new(directory).join(&file),
+                serde_json::to_string_pretty(&keystore)?,
+            )?;
+            exported.push(KeystoreAccount {
+                path: path.clone(),
+                address: address.clone(),
+                file,
+            });
+        }
+
+        let index = KeystoreIndex {
+            version: KEYSTORE_INDEX_VERSION,
+            accounts: exported.clone(),
+        };
+        std::fs::write(
+            Path::new(directory).join("index.json"),
+            serde_json::to_string_pretty(&index)?,
+        )?;
+        Ok(exported)
+    }
+}
+
+impl MaskSecrets for KeystoreAccount {
+    fn mask_secrets(&mut self) {}
+}
+
+#[cfg_attr(tarpaulin, skip)]
+impl Display for KeystoreAccount {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let output = [
+            match &self.path {
+                Some(path) => format!("      {}                 {}\n", "Path".cyan().bold(), path),
+                _ => "".to_string(),
+            },
+            format!("      {}              {}\n", "Address".cyan().bold(), self.address),
+            format!("      {}        {}\n", "Keystore File".cyan().bold(), self.file),
+        ]
+        .concat();
+
+        // Removes final new line character
+        let output = output[..output.len() - 1].to_string();
+        write!(f, "\n{}", output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // https://github.com/ethereum/wiki/wiki/Web3-Secret-Storage-Definition#test-vectors
+    const PASSWORD: &str = "testpassword";
+    const PRIVATE_KEY: &str = "7a28b5ba57c53603b0b07b56bba752f7784bf506fa95edc395f5cf6c7514fe9d";
+
+    const PBKDF2_KEYSTORE: &str = r#"{
+        "crypto": {
+            "cipher": "aes-128-ctr",
+            "cipherparams": { "iv": "6087dab2f9fdbbfaddc31a909735c1e6" },
+            "ciphertext": "5318b4d5bcd28de64ee5559e671353e16f075ecae9f99c7a79a38af5f869aa46",
+            "kdf": "pbkdf2",
+            "kdfparams": {
+                "c": 262144,
+                "dklen": 32,
+                "prf": "hmac-sha256",
+                "salt": "ae3cd4e7013836a3df6bd7241b12db061dbe2c6785853cce422d148a624ce0bd"
+            },
+            "mac": "517ead924a9d0dc3124507e3393d175ce3ff7c1e96529c6c555ce9e51205e9b2"
+        },
+        "id": "3198bc9c-6672-5ab3-d995-4942343ae5b6",
+        "version": 3
+    }"#;
+
+    const SCRYPT_KEYSTORE: &str = r#"{
+        "crypto": {
+            "cipher": "aes-128-ctr",
+            "cipherparams": { "iv": "83dbcc02d8ccb40e466191a123791e0e" },
+            "ciphertext": "d172bf743a674da9cdad04534d56926ef8358534d458fffccd4e6ad2fbde479c",
+            "kdf": "scrypt",
+            "kdfparams": {
+                "dklen": 32,
+                "n": 262144,
+                "p": 8,
+                "r": 1,
+                "salt": "ab0c7876052600dd703518d6fc3fe8984592145b591fc8fb5c6d43190334ba19"
+            },
+            "mac": "2103ac29920d71da29f15d75b4a16dbe95cfd7ff8faea1056c33131d846e3097"
+        },
+        "id": "3198bc9c-6672-5ab3-d995-4942343ae5b6",
+        "version": 3
+    }"#;
+
+    // https://github.com/ethereum/go-ethereum/blob/master/accounts/keystore/testdata/v3_test_vector.json
+    const LIGHT_SCRYPT_KEYSTORE: &str = r#"{
+        "crypto": {
+            "cipher": "aes-128-ctr",
+            "cipherparams": { "iv": "e0c41130a323adc1446fc82f724bca2f" },
+            "ciphertext": "9517cd5bdbe69076f9bf5057248c6c050141e970efa36ce53692d5d59a3984",
+            "kdf": "scrypt",
+            "kdfparams": {
+                "dklen": 32,
+                "n": 2,
+                "r": 8,
+                "p": 1,
+                "salt": "711f816911c92d649fb4c84b047915679933555030b3552c1212609b38208c63"
+            },
+            "mac": "d5e116151c6aa71470e67a7d42c9620c75c4d23229847dcc127794f0732b0db5"
+        },
+        "id": "fecfc4ce-e956-48fd-953b-30f8b52ed66c",
+        "version": 3
+    }"#;
+
+    // https://github.com/ethereum/go-ethereum/blob/master/accounts/keystore/presale.go (TestImportPreSaleKey)
+    const PRESALE_ENCRYPTED_SEED: &str = "26d87f5f2bf9835f9a47eefae571bc09f9107bb13d54ff12a4ec095d01f83897494cf34f7bed2ed34126ecba9db7b62de56c9d7cd136520a0427bfb11b8954ba7ac39b90d4650d3448e31185affcd74226a68f1e94b1108e6e0a4a91cdd83eba";
+    const PRESALE_PASSWORD: &str = "foo";
+    const PRESALE_ADDRESS: &str = "0xd4584b5f6229b7be90727b0fc8c6b91bb427821f";
+
+    fn test_decrypt(keystore: &str) {
+        let keystore: Keystore = serde_json::from_str(keystore).unwrap();
+        assert_eq!(PRIVATE_KEY, hex::encode(keystore.decrypt(PASSWORD).unwrap()));
+        match keystore.decrypt("wrongpassword") {
+            Err(CLIError::InvalidPassphrase) => {}
+            result => panic!("expected an invalid passphrase, found {:?}", result),
+        }
+    }
+
+    fn test_invalid_params(keystore: &str, field: &str, value: Value) {
+        let mut keystore: Value = serde_json::from_str(keystore).unwrap();
+        match field {
+            "iv" => keystore["crypto"]["cipherparams"]["iv"] = value,
+            field => keystore["crypto"]["kdfparams"][field] = value,
+        };
+        let keystore: Keystore = serde_json::from_value(keystore).unwrap();
+        match keystore.decrypt(PASSWORD) {
+            Err(CLIError::InvalidKdfParameters(_)) | Err(CLIError::InvalidKeystore(_)) => {}
+            result => panic!("expected invalid parameters of {}, found {:?}", field, result),
+        }
+    }
+
+    #[test]
+    fn decrypt_pbkdf2() {
+        test_decrypt(PBKDF2_KEYSTORE);
+    }
+
+    #[test]
+    fn decrypt_scrypt() {
+        // The scrypt test vector has N = 2^18 with r = 1, beyond the bound of N below 2^(16 * r) of RFC 7914
+        // of the scrypt implementation, so it is rejected before any key is derived
+        let keystore: Keystore = serde_json::from_str(SCRYPT_KEYSTORE).unwrap();
+        match keystore.decrypt(PASSWORD) {
+            Err(CLIError::InvalidKdfParameters(_)) => {}
+            result => panic!("expected invalid scrypt parameters, found {:?}", result),
+        }
+
+        // The scrypt test vector of geth, with r = 8
+        let keystore: Keystore = serde_json::from_str(LIGHT_SCRYPT_KEYSTORE).unwrap();
+        assert_eq!(
+            "fa7b3db73dc7dfdf8c5fbdb796d741e4488628c41fc4febd9160a866ba0f35",
+            hex::encode(keystore.decrypt("foo").unwrap())
+        );
+        match keystore.decrypt(PASSWORD) {
+            Err(CLIError::InvalidPassphrase) => {}
+            result => panic!("expected an invalid passphrase, found {:?}", result),
+        }
+    }
+
+    #[test]
+    fn decrypt_presale() {
+        let private_key = Keystore::decrypt_presale(PRESALE_ENCRYPTED_SEED, PRESALE_PASSWORD).unwrap();
+        let private_key = EthereumPrivateKey::from_str(&hex::encode(private_key)).unwrap();
+        let address = private_key
+            .to_public_key()
+            .to_address(&EthereumFormat::Standard)
+            .unwrap();
+        assert_eq!(PRESALE_ADDRESS, address.to_string().to_lowercase());
+    }
+
+    #[test]
+    fn invalid_scrypt_params() {
+        test_invalid_params(LIGHT_SCRYPT_KEYSTORE, "n", serde_json::json!(1));
+        test_invalid_params(LIGHT_SCRYPT_KEYSTORE, "n", serde_json::json!(0));
+        test_invalid_params(LIGHT_SCRYPT_KEYSTORE, "n", serde_json::json!(262143));
+        test_invalid_params(LIGHT_SCRYPT_KEYSTORE, "r", serde_json::json!(0));
+        test_invalid_params(LIGHT_SCRYPT_KEYSTORE, "p", serde_json::json!(0));
+        test_invalid_params(LIGHT_SCRYPT_KEYSTORE, "dklen", serde_json::json!(16));
+        test_invalid_params(LIGHT_SCRYPT_KEYSTORE, "iv", serde_json::json!("83dbcc02d8ccb40e"));
+    }
+
+    #[test]
+    fn invalid_pbkdf2_params() {
+        test_invalid_params(PBKDF2_KEYSTORE, "c", serde_json::json!(0));
+        test_invalid_params(PBKDF2_KEYSTORE, "iv", serde_json::json!(""));
+    }
+}
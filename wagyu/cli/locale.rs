@@ -0,0 +1,705 @@
+//! # Locale
+//!
+//! Localizes the prompts, diagnostics, and error messages of the command-line tool, in the languages of
+//! the supported wordlists. A message is looked up in the message catalog by its English template, with
+//! its arguments (such as a word count) carried over to the translated template. An argument of a term
+//! (`{term}`, such as the name of an input or an error) is translated from the catalog of terms or messages,
+//! so a message without a translation, or with a term without a translation, is printed in English as a whole.
+//!
+//! The locale is set with `--locale`, or read from the `LC_ALL`, `LC_MESSAGES`, or `LANG` environment
+//! variable of the system locale.
+
+use core::sync::atomic::{AtomicUsize, Ordering};
+use std::env;
+
+use crate::model::no_std::{String, ToString, Vec};
+
+/// The environment variables of the system locale, in order of precedence
+const LOCALE_VARIABLES: [&str; 3] = ["LC_ALL", "LC_MESSAGES", "LANG"];
+
+/// The locale of the process, as the index of its language in the message catalog
+static LOCALE: AtomicUsize = AtomicUsize::new(Locale::English as usize);
+
+/// Represents a language of the message catalog, in the order of its translations
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Locale {
+    English,
+    ChineseSimplified,
+    ChineseTraditional,
+    French,
+    Italian,
+    Japanese,
+    Korean,
+    Spanish,
+}
+
+impl Locale {
+    /// The locales of the message catalog, in the order of its translations
+    const LOCALES: [Locale; 8] = [
+        Locale::English,
+        Locale::ChineseSimplified,
+        Locale::ChineseTraditional,
+        Locale::French,
+        Locale::Italian,
+        Locale::Japanese,
+        Locale::Korean,
+        Locale::Spanish,
+    ];
+
+    /// Returns the locale of the given language, as a wordlist language (`french`) or as a
+    /// POSIX locale (`fr_FR.UTF-8`), or `None` if it has no translations.
+    pub fn from_name(name: &str) -> Option<Self> {
+        let name = name.split(['.', '@']).next().unwrap_or_default();
+        match name {
+            "chinese_simplified" => return Some(Locale::ChineseSimplified),
+            "chinese_traditional" => return Some(Locale::ChineseTraditional),
+            _ => {}
+        };
+        // Traditional Chinese is the script of Taiwan, Hong Kong, and Macau
+        let (language, region) = name.split_once(['_', '-']).unwrap_or((name, ""));
+        let traditional = region
+            .split(['_', '-'])
+            .any(|part| ["HANT", "HK", "MO", "TW"].contains(&part.to_uppercase().as_str()));
+        match language.to_lowercase().as_str() {
+            "zh" if traditional => Some(Locale::ChineseTraditional),
+            "zh" => Some(Locale::ChineseSimplified),
+            "english" | "en" | "c" | "posix" => Some(Locale::English),
+            "french" | "fr" => Some(Locale::French),
+            "italian" | "it" => Some(Locale::Italian),
+            "japanese" | "ja" => Some(Locale::Japanese),
+            "korean" | "ko" => Some(Locale::Korean),
+            "spanish" | "es" => Some(Locale::Spanish),
+            _ => None,
+        }
+    }
+
+    /// Returns the locale of the process.
+    pub fn current() -> Self {
+        Self::LOCALES[LOCALE.load(Ordering::Relaxed)]
+    }
+}
+
+/// Sets the locale of the process to the given language, or to the system locale if none is specified.
+/// A language without translations is printed in English.
+pub fn set_locale(locale: Option<&str>) {
+    let locale = match locale {
+        Some(locale) => Locale::from_name(locale),
+        None => LOCALE_VARIABLES
+            .iter()
+            .filter_map(|variable| env::var(variable).ok())
+            .find(|value| !value.is_empty())
+            .and_then(|value| Locale::from_name(&value)),
+    };
+    LOCALE.store(locale.unwrap_or(Locale::English) as usize, Ordering::Relaxed);
+}
+
+/// Returns the given message in the language of the locale of the process, or unchanged
+/// if its template or any of its terms has no translation.
+pub fn localize(message: &str) -> String {
+    match Locale::current() {
+        Locale::English => message.into(),
+        locale => localize_message(message, locale).unwrap_or_else(|| message.to_string()),
+    }
+}
+
+/// Returns the given message in the given language, or `None` if its template or any of its terms
+/// has no translation.
+fn localize_message(message: &str, locale: Locale) -> Option<String> {
+    let (translations, arguments) = MESSAGES.iter().find_map(|translations| {
+        let arguments = match_template(&translations[0].replace("{term}", "{}"), message)?;
+        Some((translations, arguments))
+    })?;
+
+    // The placeholders of the template are `{term}` for a term, or `{}` for a value such as a count
+    let terms = translations[0].split('{').skip(1).filter_map(|part| match part {
+        _ if part.starts_with('}') => Some(false),
+        _ if part.starts_with("term}") => Some(true),
+        _ => None,
+    });
+    let arguments = arguments
+        .iter()
+        .zip(terms)
+        .map(|(argument, term)| match term {
+            true => localize_term(argument, locale),
+            false => Some(argument.to_string()),
+        })
+        .collect::<Option<Vec<String>>>()?;
+
+    let mut localized = String::new();
+    for (index, part) in translations[locale as usize].split("{}").enumerate() {
+        if index > 0 {
+            localized.push_str(arguments.get(index - 1).map(String::as_str).unwrap_or_default());
+        }
+        localized.push_str(part);
+    }
+    Some(localized)
+}
+
+/// Returns the given term, or list of terms separated by commas, in the given language,
+/// or `None` if it has no translation.
+fn localize_term(term: &str, locale: Locale) -> Option<String> {
+    if let Some(message) = localize_message(term, locale) {
+        return Some(message);
+    }
+    let separator = match locale {
+        Locale::ChineseSimplified | Locale::ChineseTraditional | Locale::Japanese => "、",
+        _ => ", ",
+    };
+    term.split(", ")
+        .map(|term| {
+            TERMS
+                .iter()
+                .find(|translations| translations[0] == term)
+                .map(|translations| translations[locale as usize])
+        })
+        .collect::<Option<Vec<&str>>>()
+        .map(|terms| terms.join(separator))
+}
+
+/// Returns the arguments of the given message, if it is formatted from the given template.
+fn match_template<'a>(template: &str, message: &'a str) -> Option<Vec<&'a str>> {
+    let mut parts = template.split("{}");
+    let mut rest = message.strip_prefix(parts.next().unwrap_or_default())?;
+    let parts = parts.collect::<Vec<&str>>();
+
+    let mut arguments = Vec::with_capacity(parts.len());
+    for (index, part) in parts.iter().enumerate() {
+        // Each argument ends at the next part of the template, and the last argument at the end of the message
+        let end = match index == parts.len() - 1 {
+            true => rest.strip_suffix(part)?.len(),
+            false => rest.find(part)?,
+        };
+        arguments.push(&rest[..end]);
+        rest = &rest[end + part.len()..];
+    }
+    match parts.is_empty() && !rest.is_empty() {
+        true => None,
+        false => Some(arguments),
+    }
+}
+
+/// The message catalog, of the English template of each message and its translations in Simplified Chinese,
+/// Traditional Chinese, French, Italian, Japanese, Korean, and Spanish
+const MESSAGES: [[&str; 8]; 29] = [
+    // Prompts
+    [
+        "Enter passphrase: ",
+        "输入密码短语：",
+        "輸入密碼短語：",
+        "Entrez la phrase secrète : ",
+        "Inserisci la passphrase: ",
+        "パスフレーズを入力してください: ",
+        "암호문을 입력하세요: ",
+        "Introduzca la frase de contraseña: ",
+    ],
+    [
+        "Confirm passphrase: ",
+        "确认密码短语：",
+        "確認密碼短語：",
+        "Confirmez la phrase secrète : ",
+        "Conferma la passphrase: ",
+        "パスフレーズを確認してください: ",
+        "암호문을 확인하세요: ",
+        "Confirme la frase de contraseña: ",
+    ],
+    [
+        "Write down the mnemonic, then press Enter to hide it and verify the backup: ",
+        "请抄写助记词，然后按回车键隐藏它并验证备份：",
+        "請抄寫助記詞，然後按 Enter 鍵隱藏它並驗證備份：",
+        "Notez la phrase mnémonique, puis appuyez sur Entrée pour la masquer et vérifier la sauvegarde : ",
+        "Scrivi la frase mnemonica, poi premi Invio per nasconderla e verificare il backup: ",
+        "ニーモニックを書き留めてから、Enter キーを押して非表示にし、バックアップを確認してください: ",
+        "니모닉을 적어 둔 후 Enter 키를 눌러 숨기고 백업을 확인하세요: ",
+        "Anote la frase mnemotécnica y pulse Intro para ocultarla y verificar la copia de seguridad: ",
+    ],
+    [
+        "Enter word #{} of the mnemonic: ",
+        "输入助记词的第 {} 个单词：",
+        "輸入助記詞的第 {} 個單詞：",
+        "Entrez le mot nº {} de la phrase mnémonique : ",
+        "Inserisci la parola n. {} della frase mnemonica: ",
+        "ニーモニックの {} 番目の単語を入力してください: ",
+        "니모닉의 {}번째 단어를 입력하세요: ",
+        "Introduzca la palabra n.º {} de la frase mnemotécnica: ",
+    ],
+    [
+        "Incorrect word, try again",
+        "单词不正确，请重试",
+        "單詞不正確，請重試",
+        "Mot incorrect, réessayez",
+        "Parola errata, riprova",
+        "単語が正しくありません。もう一度入力してください",
+        "단어가 올바르지 않습니다. 다시 시도하세요",
+        "Palabra incorrecta, inténtelo de nuevo",
+    ],
+    [
+        "Press Enter to reveal the secrets, or Ctrl-C to keep them hidden: ",
+        "按回车键显示机密信息，或按 Ctrl-C 保持隐藏：",
+        "按 Enter 鍵顯示機密資訊，或按 Ctrl-C 保持隱藏：",
+        "Appuyez sur Entrée pour révéler les secrets, ou sur Ctrl-C pour les garder masqués : ",
+        "Premi Invio per mostrare i segreti, o Ctrl-C per tenerli nascosti: ",
+        "Enter キーで秘密情報を表示、Ctrl-C で非表示のままにします: ",
+        "Enter 키를 눌러 비밀 정보를 표시하거나 Ctrl-C를 눌러 숨긴 상태로 유지하세요: ",
+        "Pulse Intro para mostrar los secretos, o Ctrl-C para mantenerlos ocultos: ",
+    ],
+    [
+        "Operator {}, enter your name: ",
+        "操作员 {}，请输入您的姓名：",
+        "操作員 {}，請輸入您的姓名：",
+        "Opérateur {}, entrez votre nom : ",
+        "Operatore {}, inserisci il tuo nome: ",
+        "オペレーター {}、名前を入力してください: ",
+        "운영자 {}, 이름을 입력하세요: ",
+        "Operador {}, introduzca su nombre: ",
+    ],
+    [
+        "{}, enter at least {} characters of entropy (e.g. dice rolls or random keystrokes): ",
+        "{}，请输入至少 {} 个字符的熵（例如掷骰结果或随机按键）：",
+        "{}，請輸入至少 {} 個字元的熵（例如擲骰結果或隨機按鍵）：",
+        "{}, entrez au moins {} caractères d'entropie (par exemple des lancers de dés ou des frappes aléatoires) : ",
+        "{}, inserisci almeno {} caratteri di entropia (ad esempio lanci di dadi o tasti casuali): ",
+        "{} さん、{} 文字以上のエントロピーを入力してください (サイコロの出目やランダムなキー入力など): ",
+        "{}님, {}자 이상의 엔트로피를 입력하세요 (예: 주사위 결과 또는 무작위 키 입력): ",
+        "{}, introduzca al menos {} caracteres de entropía (p. ej., tiradas de dados o pulsaciones aleatorias): ",
+    ],
+    // Diagnostics
+    [
+        "Warning: low-entropy {term} ({term})",
+        "警告：低熵的 {}（{}）",
+        "警告：低熵的 {}（{}）",
+        "Avertissement : {} à faible entropie ({})",
+        "Attenzione: {} a bassa entropia ({})",
+        "警告: 低エントロピーの {} ({})",
+        "경고: 낮은 엔트로피의 {} ({})",
+        "Advertencia: {} de baja entropía ({})",
+    ],
+    [
+        "Normalized {term} ({term})",
+        "已规范化 {}（{}）",
+        "已正規化 {}（{}）",
+        "Normalisation de {} ({})",
+        "Normalizzazione di {} ({})",
+        "{} を正規化しました ({})",
+        "{}을(를) 정규화했습니다 ({})",
+        "Normalización de {} ({})",
+    ],
+    [
+        "Imported {} of {} entries",
+        "已导入 {} / {} 个条目",
+        "已匯入 {} / {} 個條目",
+        "{} entrées importées sur {}",
+        "Importate {} voci su {}",
+        "{} / {} 件のエントリをインポートしました",
+        "{}/{}개 항목을 가져왔습니다",
+        "Importadas {} de {} entradas",
+    ],
+    [
+        "Signed {} of {} entries",
+        "已签名 {} / {} 个条目",
+        "已簽署 {} / {} 個條目",
+        "{} entrées signées sur {}",
+        "Firmate {} voci su {}",
+        "{} / {} 件のエントリに署名しました",
+        "{}/{}개 항목에 서명했습니다",
+        "Firmadas {} de {} entradas",
+    ],
+    [
+        "Derived {} addresses (next index {})",
+        "已派生 {} 个地址（下一个索引 {}）",
+        "已衍生 {} 個地址（下一個索引 {}）",
+        "{} adresses dérivées (index suivant {})",
+        "Derivati {} indirizzi (indice successivo {})",
+        "{} 個のアドレスを導出しました (次のインデックス {})",
+        "{}개 주소를 파생했습니다 (다음 인덱스 {})",
+        "Derivadas {} direcciones (siguiente índice {})",
+    ],
+    [
+        "Copied the {term} of {} wallet(s) to the clipboard, clearing it in {} seconds",
+        "已将 {}（{} 个钱包）复制到剪贴板，{} 秒后清空",
+        "已將 {}（{} 個錢包）複製到剪貼簿，{} 秒後清除",
+        "Copie de {} de {} portefeuille(s) dans le presse-papiers, effacé dans {} secondes",
+        "Copiato {} di {} portafoglio/i negli appunti, verranno cancellati tra {} secondi",
+        "{} ({} 個のウォレット) をクリップボードにコピーしました。{} 秒後に消去します",
+        "{}({}개 지갑)을(를) 클립보드에 복사했습니다. {}초 후에 지웁니다",
+        "Copiado {} de {} monedero(s) al portapapeles, se borrará en {} segundos",
+    ],
+    [
+        "Cleared the clipboard",
+        "已清空剪贴板",
+        "已清除剪貼簿",
+        "Presse-papiers effacé",
+        "Appunti cancellati",
+        "クリップボードを消去しました",
+        "클립보드를 지웠습니다",
+        "Portapapeles borrado",
+    ],
+    [
+        "Warning: non-standard wordlist {}, whose mnemonics are only recoverable with the same wordlist file",
+        "警告：非标准词表 {}，其助记词只能使用同一词表文件恢复",
+        "警告：非標準詞表 {}，其助記詞只能使用同一詞表檔案還原",
+        "Avertissement : liste de mots non standard {}, dont les phrases mnémoniques ne sont récupérables qu'avec le même fichier de liste de mots",
+        "Attenzione: elenco di parole non standard {}, le cui frasi mnemoniche sono recuperabili solo con lo stesso file di elenco",
+        "警告: 非標準の単語リスト {} です。そのニーモニックは同じ単語リスト ファイルでのみ復元できます",
+        "경고: 비표준 단어 목록 {}입니다. 해당 니모닉은 같은 단어 목록 파일로만 복구할 수 있습니다",
+        "Advertencia: lista de palabras no estándar {}, cuyas frases mnemotécnicas solo se pueden recuperar con el mismo archivo de lista",
+    ],
+    // Errors
+    [
+        "Error: {term}",
+        "错误：{}",
+        "錯誤：{}",
+        "Erreur : {}",
+        "Errore: {}",
+        "エラー: {}",
+        "오류: {}",
+        "Error: {}",
+    ],
+    [
+        "Invalid mnemonic word count: {} [possible values: 12, 15, 18, 21, 24]",
+        "助记词单词数无效：{} [可能的值：12、15、18、21、24]",
+        "助記詞單詞數無效：{} [可能的值：12、15、18、21、24]",
+        "Nombre de mots de la phrase mnémonique invalide : {} [valeurs possibles : 12, 15, 18, 21, 24]",
+        "Numero di parole della frase mnemonica non valido: {} [valori possibili: 12, 15, 18, 21, 24]",
+        "ニーモニックの単語数が無効です: {} [使用可能な値: 12, 15, 18, 21, 24]",
+        "니모닉 단어 수가 올바르지 않습니다: {} [가능한 값: 12, 15, 18, 21, 24]",
+        "Número de palabras de la frase mnemotécnica no válido: {} [valores posibles: 12, 15, 18, 21, 24]",
+    ],
+    [
+        "invalid dice mnemonic of {} words (expected 12, 15, 18, 21, or 24)",
+        "骰子助记词的单词数 {} 无效（应为 12、15、18、21 或 24）",
+        "骰子助記詞的單詞數 {} 無效（應為 12、15、18、21 或 24）",
+        "phrase mnémonique de dés invalide de {} mots (attendu : 12, 15, 18, 21 ou 24)",
+        "frase mnemonica di dadi non valida di {} parole (previste 12, 15, 18, 21 o 24)",
+        "{} 単語のダイス ニーモニックは無効です (12、15、18、21、24 のいずれか)",
+        "{}단어 주사위 니모닉은 올바르지 않습니다 (12, 15, 18, 21 또는 24 필요)",
+        "frase mnemotécnica de dados de {} palabras no válida (se esperaban 12, 15, 18, 21 o 24)",
+    ],
+    [
+        "Invalid checksum word: { expected: {}, found: {} }",
+        "校验和单词无效：{ 预期：{}，实际：{} }",
+        "校驗和單詞無效：{ 預期：{}，實際：{} }",
+        "Mot de somme de contrôle invalide : { attendu : {}, trouvé : {} }",
+        "Parola di checksum non valida: { attesa: {}, trovata: {} }",
+        "チェックサムの単語が無効です: { 期待値: {}, 実際: {} }",
+        "체크섬 단어가 올바르지 않습니다: { 예상: {}, 실제: {} }",
+        "Palabra de suma de comprobación no válida: { esperada: {}, encontrada: {} }",
+    ],
+    [
+        "Invalid phrase: {}",
+        "助记词无效：{}",
+        "助記詞無效：{}",
+        "Phrase invalide : {}",
+        "Frase non valida: {}",
+        "無効なフレーズ: {}",
+        "올바르지 않은 구문: {}",
+        "Frase no válida: {}",
+    ],
+    [
+        "Missing the last word (checksum)",
+        "缺少最后一个单词（校验和）",
+        "缺少最後一個單詞（校驗和）",
+        "Le dernier mot (somme de contrôle) est manquant",
+        "Manca l'ultima parola (checksum)",
+        "最後の単語 (チェックサム) がありません",
+        "마지막 단어(체크섬)가 없습니다",
+        "Falta la última palabra (suma de comprobación)",
+    ],
+    [
+        "Missing word(s) in mnemonic",
+        "助记词缺少单词",
+        "助記詞缺少單詞",
+        "Mot(s) manquant(s) dans la phrase mnémonique",
+        "Parola/e mancante/i nella frase mnemonica",
+        "ニーモニックに単語が不足しています",
+        "니모닉에 단어가 없습니다",
+        "Faltan palabras en la frase mnemotécnica",
+    ],
+    [
+        "failed mnemonic backup verification",
+        "助记词备份验证失败",
+        "助記詞備份驗證失敗",
+        "échec de la vérification de la sauvegarde de la phrase mnémonique",
+        "verifica del backup della frase mnemonica non riuscita",
+        "ニーモニックのバックアップの確認に失敗しました",
+        "니모닉 백업 확인에 실패했습니다",
+        "error en la verificación de la copia de seguridad de la frase mnemotécnica",
+    ],
+    [
+        "low-entropy {term}: {term} (include --force to proceed)",
+        "低熵的 {}：{}（使用 --force 继续）",
+        "低熵的 {}：{}（使用 --force 繼續）",
+        "{} à faible entropie : {} (ajoutez --force pour continuer)",
+        "{} a bassa entropia: {} (aggiungi --force per procedere)",
+        "低エントロピーの {}: {} (続行するには --force を指定してください)",
+        "낮은 엔트로피의 {}: {} (계속하려면 --force를 추가하세요)",
+        "{} de baja entropía: {} (incluya --force para continuar)",
+    ],
+    [
+        "non-canonical {term}: {term} (rejected by --strict)",
+        "非规范的 {}：{}（被 --strict 拒绝）",
+        "非規範的 {}：{}（被 --strict 拒絕）",
+        "{} non canonique : {} (rejeté par --strict)",
+        "{} non canonico: {} (rifiutato da --strict)",
+        "正規形でない {}: {} (--strict により拒否されました)",
+        "정규 형식이 아닌 {}: {} (--strict로 거부됨)",
+        "{} no canónico: {} (rechazado por --strict)",
+    ],
+    [
+        "invalid passphrase or corrupted wallet file",
+        "密码短语无效或钱包文件已损坏",
+        "密碼短語無效或錢包檔案已損毀",
+        "phrase secrète invalide ou fichier de portefeuille corrompu",
+        "passphrase non valida o file del portafoglio danneggiato",
+        "パスフレーズが無効か、ウォレットファイルが破損しています",
+        "암호문이 올바르지 않거나 지갑 파일이 손상되었습니다",
+        "frase de contraseña no válida o archivo de monedero dañado",
+    ],
+    [
+        "missing passphrase",
+        "缺少密码短语",
+        "缺少密碼短語",
+        "phrase secrète manquante",
+        "passphrase mancante",
+        "パスフレーズがありません",
+        "암호문이 없습니다",
+        "falta la frase de contraseña",
+    ],
+    [
+        "mismatched passphrase confirmation",
+        "密码短语不匹配",
+        "密碼短語不相符",
+        "les phrases secrètes ne correspondent pas",
+        "le passphrase non corrispondono",
+        "パスフレーズが一致しません",
+        "암호문이 일치하지 않습니다",
+        "las frases de contraseña no coinciden",
+    ],
+];
+
+/// The catalog of terms, of the inputs and the patterns of the diagnostics of messages, in the languages
+/// of the message catalog
+const TERMS: [[&str; 8]; 17] = [
+    // Inputs
+    [
+        "mnemonic",
+        "助记词",
+        "助記詞",
+        "phrase mnémonique",
+        "frase mnemonica",
+        "ニーモニック",
+        "니모닉",
+        "frase mnemotécnica",
+    ],
+    [
+        "entropy",
+        "熵",
+        "熵",
+        "entropie",
+        "entropia",
+        "エントロピー",
+        "엔트로피",
+        "entropía",
+    ],
+    [
+        "address",
+        "地址",
+        "地址",
+        "adresse",
+        "indirizzo",
+        "アドレス",
+        "주소",
+        "dirección",
+    ],
+    [
+        "private key",
+        "私钥",
+        "私鑰",
+        "clé privée",
+        "chiave privata",
+        "秘密鍵",
+        "개인 키",
+        "clave privada",
+    ],
+    [
+        "public key",
+        "公钥",
+        "公鑰",
+        "clé publique",
+        "chiave pubblica",
+        "公開鍵",
+        "공개 키",
+        "clave pública",
+    ],
+    [
+        "extended private key",
+        "扩展私钥",
+        "擴充私鑰",
+        "clé privée étendue",
+        "chiave privata estesa",
+        "拡張秘密鍵",
+        "확장 개인 키",
+        "clave privada extendida",
+    ],
+    [
+        "extended public key",
+        "扩展公钥",
+        "擴充公鑰",
+        "clé publique étendue",
+        "chiave pubblica estesa",
+        "拡張公開鍵",
+        "확장 공개 키",
+        "clave pública extendida",
+    ],
+    // Patterns of low entropy
+    [
+        "repeating bytes",
+        "重复字节",
+        "重複位元組",
+        "octets répétés",
+        "byte ripetuti",
+        "繰り返しのバイト",
+        "반복되는 바이트",
+        "bytes repetidos",
+    ],
+    [
+        "sequential bytes",
+        "连续字节",
+        "連續位元組",
+        "octets séquentiels",
+        "byte sequenziali",
+        "連続したバイト",
+        "연속된 바이트",
+        "bytes secuenciales",
+    ],
+    [
+        "few distinct bytes",
+        "不同字节过少",
+        "不同位元組過少",
+        "peu d'octets distincts",
+        "pochi byte distinti",
+        "異なるバイトが少ない",
+        "서로 다른 바이트가 적음",
+        "pocos bytes distintos",
+    ],
+    [
+        "printable text",
+        "可打印文本",
+        "可列印文字",
+        "texte imprimable",
+        "testo stampabile",
+        "印字可能なテキスト",
+        "인쇄 가능한 텍스트",
+        "texto imprimible",
+    ],
+    [
+        "repeated words",
+        "重复单词",
+        "重複單詞",
+        "mots répétés",
+        "parole ripetute",
+        "繰り返しの単語",
+        "반복되는 단어",
+        "palabras repetidas",
+    ],
+    [
+        "dictionary-sequential words",
+        "词表顺序单词",
+        "詞表順序單詞",
+        "mots consécutifs du dictionnaire",
+        "parole consecutive del dizionario",
+        "単語リスト順の単語",
+        "단어 목록 순서의 단어",
+        "palabras consecutivas del diccionario",
+    ],
+    // Normalizations
+    [
+        "surrounding whitespace",
+        "首尾空白",
+        "首尾空白",
+        "espaces en début et en fin",
+        "spazi iniziali e finali",
+        "前後の空白",
+        "앞뒤 공백",
+        "espacios al principio y al final",
+    ],
+    [
+        "0x prefix",
+        "0x 前缀",
+        "0x 前綴",
+        "préfixe 0x",
+        "prefisso 0x",
+        "0x プレフィックス",
+        "0x 접두사",
+        "prefijo 0x",
+    ],
+    [
+        "uppercase hex",
+        "大写十六进制",
+        "大寫十六進位",
+        "hexadécimal en majuscules",
+        "esadecimale maiuscolo",
+        "大文字の 16 進数",
+        "대문자 16진수",
+        "hexadecimal en mayúsculas",
+    ],
+    [
+        "irregular spacing",
+        "不规则空格",
+        "不規則空格",
+        "espacement irrégulier",
+        "spaziatura irregolare",
+        "不規則な空白",
+        "불규칙한 공백",
+        "espaciado irregular",
+    ],
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn localize_terms() {
+        assert_eq!(
+            Some("警告: 低エントロピーの ニーモニック (繰り返しの単語、単語リスト順の単語)".to_string()),
+            localize_message(
+                "Warning: low-entropy mnemonic (repeated words, dictionary-sequential words)",
+                Locale::Japanese
+            )
+        );
+        assert_eq!(
+            Some("エラー: パスフレーズがありません".to_string()),
+            localize_message("Error: missing passphrase", Locale::Japanese)
+        );
+        assert_eq!(
+            Some("Erreur : entropie à faible entropie : octets répétés (ajoutez --force pour continuer)".to_string()),
+            localize_message(
+                "Error: low-entropy entropy: repeating bytes (include --force to proceed)",
+                Locale::French
+            )
+        );
+    }
+
+    #[test]
+    fn fall_back_to_english() {
+        // A message of a term without a translation, or of an error without a translation, is untranslated
+        assert_eq!(
+            None,
+            localize_message("Normalized key package (surrounding whitespace)", Locale::Japanese)
+        );
+        assert_eq!(
+            None,
+            localize_message("Error: invalid derivation path", Locale::Japanese)
+        );
+        assert_eq!(
+            None,
+            localize_message("Normalized mnemonic (trailing dots)", Locale::Japanese)
+        );
+
+        // A value is carried over as is
+        assert_eq!(
+            Some("ニーモニックの 3 番目の単語を入力してください: ".to_string()),
+            localize_message("Enter word #3 of the mnemonic: ", Locale::Japanese)
+        );
+    }
+}
@@ -0,0 +1,110 @@
+//! # Manifest
+//!
+//! A manifest of a generated batch of wallets, mapping each index to its address,
+//! and committing to the batch with a SHA-256 checksum. The manifest holds no private keys.
+//!
+//! The checksum is the SHA-256 of the addresses in order, each followed by a newline,
+//! so that it can be recomputed independently (e.g. `printf '%s\n' $ADDRESSES | sha256sum`).
+
+use crate::cli::{object, property, CLIError, Schema};
+
+use crypto::{digest::Digest, sha2::Sha256};
+use serde::{Deserialize, Serialize};
+
+use crate::model::no_std::{String, ToString, Vec};
+
+/// The current version of the manifest schema
+pub const MANIFEST_VERSION: u32 = 1;
+
+/// Represents a manifest of a batch of generated wallets
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Manifest {
+    /// The version of the manifest schema
+    pub version: u32,
+    /// The coin of the batch
+    pub coin: String,
+    /// The number of addresses in the batch
+    pub count: usize,
+    /// The SHA-256 checksum of the batch addresses
+    pub sha256: String,
+    /// The addresses of the batch
+    pub addresses: Vec<ManifestEntry>,
+}
+
+/// Represents an address of a manifest
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ManifestEntry {
+    pub index: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    pub address: String,
+}
+
+impl Schema for Manifest {
+    fn schema() -> serde_json::Value {
+        let entry = object(
+            vec![
+                ("index", property("integer", "The index of the address in the batch")),
+                ("path", property("string", "The derivation path of the address")),
+                ("address", property("string", "The address")),
+            ],
+            &["index", "address"],
+        );
+        let mut addresses = property("array", "The addresses of the batch");
+        addresses["items"] = entry;
+
+        object(
+            vec![
+                ("version", property("integer", "The version of the manifest schema")),
+                ("coin", property("string", "The coin of the batch")),
+                ("count", property("integer", "The number of addresses in the batch")),
+                (
+                    "sha256",
+                    property("string", "The SHA-256 checksum of the batch addresses"),
+                ),
+                ("addresses", addresses),
+            ],
+            &["version", "coin", "count", "sha256", "addresses"],
+        )
+    }
+}
+
+impl Manifest {
+    /// Returns the manifest of the given wallets of the given coin.
+    pub fn new<T: Serialize>(wallets: &[T], coin: &str) -> Result<Self, CLIError> {
+        let mut addresses = Vec::with_capacity(wallets.len());
+        for (index, wallet) in wallets.iter().enumerate() {
+            let wallet = serde_json::to_value(wallet)?;
+            let address = match wallet.get("address").and_then(|address| address.as_str()) {
+                Some(address) => address.to_string(),
+                None => return Err(CLIError::MissingAddress(index)),
+            };
+            let path = wallet.get("path").and_then(|path| path.as_str()).map(String::from);
+
+            addresses.push(ManifestEntry { index, path, address });
+        }
+
+        Ok(Self {
+            version: MANIFEST_VERSION,
+            coin: coin.to_string(),
+            count: addresses.len(),
+            sha256: Self::checksum(&addresses),
+            addresses,
+        })
+    }
+
+    /// Returns the SHA-256 checksum of the given manifest addresses.
+    pub fn checksum(addresses: &[ManifestEntry]) -> String {
+        let mut sha256 = Sha256::new();
+        for entry in addresses {
+            sha256.input_str(&entry.address);
+            sha256.input(b"\n");
+        }
+        sha256.result_str()
+    }
+
+    /// Writes the manifest to the given path.
+    pub fn write(&self, path: &str) -> Result<(), CLIError> {
+        Ok(std::fs::write(path, serde_json::to_string_pretty(self)?)?)
+    }
+}
@@ -0,0 +1,69 @@
+//! # Mask
+//!
+//! Masks the secrets of the printed wallets (such as mnemonics and private keys), so that they are
+//! not leaked by screenshots or screen shares. The secrets are revealed with the `--reveal` flag,
+//! or on an interactive terminal by pressing Enter once the masked wallets are printed.
+
+use crate::cli::{prompt, CLIError, RedactionPolicy};
+
+use serde::Serialize;
+use std::fmt::Display;
+
+use crate::model::no_std::Vec;
+
+/// The mask printed in place of a secret
+pub const SECRET_MASK: &str = "********";
+
+/// The trait for a wallet with secrets that can be masked
+pub trait MaskSecrets: Clone {
+    /// Replaces each secret of the wallet with `SECRET_MASK`.
+    fn mask_secrets(&mut self);
+}
+
+/// Replaces the given secret with `SECRET_MASK`, if it is present.
+pub fn mask(secret: &mut Option<String>) {
+    if secret.is_some() {
+        *secret = Some(SECRET_MASK.into());
+    }
+}
+
+/// Prints the given wallets, in JSON format or not, with the fields of the redaction policy redacted.
+/// If the secrets are masked, the wallets are printed with their secrets masked, and then printed
+/// again with their secrets on an Enter keypress of an interactive terminal.
+pub fn print_wallets<T: Display + MaskSecrets + Serialize>(
+    wallets: &[T],
+    json: bool,
+    mask_secrets: bool,
+) -> Result<(), CLIError> {
+    fn print<T: Display + Serialize>(wallets: &[T], json: bool) -> Result<(), CLIError> {
+        let policy = RedactionPolicy::current();
+        match (json, policy.is_empty()) {
+            (true, true) => println!("{}\n", serde_json::to_string_pretty(wallets)?),
+            (true, false) => println!("{}\n", serde_json::to_string_pretty(&policy.redact(&wallets)?)?),
+            (false, _) => wallets
+                .iter()
+                .for_each(|wallet| println!("{}\n", policy.redact_text(&wallet.to_string()))),
+        };
+        Ok(())
+    }
+
+    if !mask_secrets {
+        return print(wallets, json);
+    }
+
+    let masked = wallets
+        .iter()
+        .cloned()
+        .map(|mut wallet| {
+            wallet.mask_secrets();
+            wallet
+        })
+        .collect::<Vec<T>>();
+    print(&masked, json)?;
+
+    if atty::is(atty::Stream::Stdin) && atty::is(atty::Stream::Stdout) {
+        prompt("Press Enter to reveal the secrets, or Ctrl-C to keep them hidden: ")?;
+        print(wallets, json)?;
+    }
+    Ok(())
+}
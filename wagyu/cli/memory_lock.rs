@@ -0,0 +1,23 @@
+//! # Memory lock
+//!
+//! Locks the memory of the secrets of the process with `--lock-memory`, so that the seeds and private keys
+//! in its buffers are never written to swap or to a core dump during a long batch run. The buffers are
+//! allocated by the `SecureAllocator` of `wagyu-memory`, which zeroes each buffer when it is released and,
+//! once locked, locks the pages of each buffer, and core dumps are disabled.
+//!
+//! The locked memory of a process is limited by `RLIMIT_MEMLOCK` (see `ulimit -l`). An allocation beyond the
+//! limit fails and aborts the process, instead of being swapped, so a large batch may need a higher limit.
+
+use crate::cli::CLIError;
+
+/// Disables core dumps of the process, and locks the pages of each later allocation against swapping.
+#[cfg(target_os = "linux")]
+pub fn lock_memory() -> Result<(), CLIError> {
+    wagyu_memory::lock_memory().map_err(|error| CLIError::FailedMemoryLock(error.to_string()))
+}
+
+/// Returns an error, as the memory of a process is only locked on Linux.
+#[cfg(not(target_os = "linux"))]
+pub fn lock_memory() -> Result<(), CLIError> {
+    Err(CLIError::UnsupportedMemoryLock)
+}
@@ -0,0 +1,186 @@
+//! # Mnemonic entropy
+//!
+//! Converts between a BIP-39 mnemonic and its entropy, with the checksum bits that complete the final
+//! word of the mnemonic. The language of a mnemonic is detected from its words, and the mnemonic of
+//! an entropy is returned in the specified language.
+
+use crate::bitcoin::{wordlist::*, BitcoinMnemonic, BitcoinWordlist, Mainnet};
+use crate::cli::{CLIError, MaskSecrets, SECRET_MASK};
+use crate::model::Mnemonic;
+
+use colored::*;
+use core::{fmt, fmt::Display};
+use serde::Serialize;
+
+use crate::model::no_std::{format, String, ToString};
+
+/// Represents a mnemonic and its entropy
+#[derive(Serialize, Clone, Debug)]
+pub struct MnemonicEntropy {
+    /// The language of the mnemonic
+    pub language: String,
+    /// The mnemonic
+    pub mnemonic: String,
+    /// The entropy of the mnemonic in hex
+    pub entropy: String,
+    /// The checksum bits of the mnemonic, the first bits of the SHA256 hash of its entropy
+    pub checksum: String,
+}
+
+impl MnemonicEntropy {
+    /// Returns the entropy of the given mnemonic, in the first language with all of its words.
+    pub fn from_mnemonic(phrase: &str) -> Result<Self, CLIError> {
+        Self::from_phrase::<ChineseSimplified>("chinese_simplified", phrase)
+            .or(Self::from_phrase::<ChineseTraditional>("chinese_traditional", phrase))
+            .or(Self::from_phrase::<English>("english", phrase))
+            .or(Self::from_phrase::<French>("french", phrase))
+            .or(Self::from_phrase::<Italian>("italian", phrase))
+            .or(Self::from_phrase::<Japanese>("japanese", phrase))
+            .or(Self::from_phrase::<Korean>("korean", phrase))
+            .or(Self::from_phrase::<Spanish>("spanish", phrase))
+    }
+
+    /// Returns the mnemonic of the given hex entropy, in the given language.
+    pub fn from_entropy(entropy: &str, language: &str) -> Result<Self, CLIError> {
+        let entropy = hex::decode(entropy.trim_start_matches("0x"))?;
+        match language {
+            "chinese_simplified" => Self::new(
+                language,
+                BitcoinMnemonic::<Mainnet, ChineseSimplified>::from_entropy(&entropy)?,
+            ),
+            "chinese_traditional" => Self::new(
+                language,
+                BitcoinMnemonic::<Mainnet, ChineseTraditional>::from_entropy(&entropy)?,
+            ),
+            "english" => Self::new(language, BitcoinMnemonic::<Mainnet, English>::from_entropy(&entropy)?),
+            "french" => Self::new(language, BitcoinMnemonic::<Mainnet, French>::from_entropy(&entropy)?),
+            "italian" => Self::new(language, BitcoinMnemonic::<Mainnet, Italian>::from_entropy(&entropy)?),
+            "japanese" => Self::new(language, BitcoinMnemonic::<Mainnet, Japanese>::from_entropy(&entropy)?),
+            "korean" => Self::new(language, BitcoinMnemonic::<Mainnet, Korean>::from_entropy(&entropy)?),
+            "spanish" => Self::new(language, BitcoinMnemonic::<Mainnet, Spanish>::from_entropy(&entropy)?),
+            _ => Err(CLIError::UnsupportedLanguage),
+        }
+    }
+
+    /// Returns the entropy of the given mnemonic of the given wordlist.
+    fn from_phrase<W: BitcoinWordlist>(language: &str, phrase: &str) -> Result<Self, CLIError> {
+        Self::new(language, BitcoinMnemonic::<Mainnet, W>::from_phrase(phrase)?)
+    }
+
+    /// Returns the given mnemonic of the given language, with its entropy and checksum.
+    fn new<W: BitcoinWordlist>(language: &str, mnemonic: BitcoinMnemonic<Mainnet, W>) -> Result<Self, CLIError> {
+        Ok(Self {
+            language: language.into(),
+            mnemonic: mnemonic.to_phrase()?,
+            entropy: hex::encode(mnemonic.to_entropy()),
+            checksum: mnemonic.to_checksum()?,
+        })
+    }
+}
+
+impl MaskSecrets for MnemonicEntropy {
+    fn mask_secrets(&mut self) {
+        self.mnemonic = SECRET_MASK.into();
+        self.entropy = SECRET_MASK.into();
+    }
+}
+
+#[cfg_attr(tarpaulin, skip)]
+impl Display for MnemonicEntropy {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let output = [
+            format!("      {}             {}\n", "Language".cyan().bold(), self.language),
+            format!("      {}             {}\n", "Mnemonic".cyan().bold(), self.mnemonic),
+            format!("      {}              {}\n", "Entropy".cyan().bold(), self.entropy),
+            format!("      {}             {}\n", "Checksum".cyan().bold(), self.checksum),
+        ]
+        .concat();
+
+        // Removes final new line character
+        let output = output[..output.len() - 1].to_string();
+        write!(f, "\n{}", output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The English test vectors of BIP-39 (entropy, mnemonic, and checksum bits)
+    const VECTORS: [(&str, &str, &str); 7] = [
+        (
+            "00000000000000000000000000000000",
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+            "0011",
+        ),
+        (
+            "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+            "legal winner thank year wave sausage worth useful legal winner thank yellow",
+            "1000",
+        ),
+        (
+            "80808080808080808080808080808080",
+            "letter advice cage absurd amount doctor acoustic avoid letter advice cage above",
+            "0100",
+        ),
+        (
+            "9e885d952ad362caeb4efe34a8e91bd2",
+            "ozone drill grab fiber curtain grace pudding thank cruise elder eight picnic",
+            "0001",
+        ),
+        (
+            "0000000000000000000000000000000000000000000000000000000000000000",
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon \
+             abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon art",
+            "01100110",
+        ),
+        (
+            "7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f7f",
+            "legal winner thank year wave sausage worth useful legal winner thank year wave sausage worth useful \
+             legal winner thank year wave sausage worth title",
+            "00010111",
+        ),
+        (
+            "ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff",
+            "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo vote",
+            "10101111",
+        ),
+    ];
+
+    #[test]
+    fn to_entropy() {
+        for (entropy, mnemonic, checksum) in VECTORS.iter() {
+            let result = MnemonicEntropy::from_mnemonic(mnemonic).unwrap();
+            assert_eq!("english", result.language);
+            assert_eq!(*entropy, result.entropy);
+            assert_eq!(*checksum, result.checksum);
+        }
+    }
+
+    #[test]
+    fn from_entropy() {
+        for (entropy, mnemonic, checksum) in VECTORS.iter() {
+            let result = MnemonicEntropy::from_entropy(entropy, "english").unwrap();
+            assert_eq!(*mnemonic, result.mnemonic);
+            assert_eq!(*checksum, result.checksum);
+        }
+        let result = MnemonicEntropy::from_entropy(&format!("0x{}", VECTORS[0].0), "english").unwrap();
+        assert_eq!(VECTORS[0].1, result.mnemonic);
+    }
+
+    #[test]
+    fn invalid() {
+        // The final word of a valid mnemonic is replaced, breaking its checksum
+        let mnemonic =
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon";
+        assert!(MnemonicEntropy::from_mnemonic(mnemonic).is_err());
+        let mnemonic = VECTORS[1].1.replace("yellow", "year");
+        assert!(MnemonicEntropy::from_mnemonic(&mnemonic).is_err());
+
+        assert!(MnemonicEntropy::from_entropy("00", "english").is_err());
+        match MnemonicEntropy::from_entropy(VECTORS[0].0, "klingon") {
+            Err(CLIError::UnsupportedLanguage) => {}
+            result => panic!("unexpected result {:?}", result),
+        }
+    }
+}
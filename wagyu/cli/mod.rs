@@ -1,13 +1,172 @@
+use crate::bitcoin::{FrostError, MiniscriptError, MuSigError, SignedMessageError, TaprootError};
+use crate::ethereum::{SignatureError, TypedDataError, UserOperationError};
 use crate::model::{
     AddressError, AmountError, DerivationPathError, ExtendedPrivateKeyError, ExtendedPublicKeyError, MnemonicError,
     PrivateKeyError, PublicKeyError, TransactionError,
 };
+use crate::monero::{MultisigError, PaymentIdError, ProofError};
 
 pub mod bitcoin;
 pub mod ethereum;
 pub mod monero;
+pub mod nostr;
 pub mod zcash;
 
+pub mod audit;
+pub use self::audit::*;
+
+pub mod backend;
+pub use self::backend::*;
+
+pub mod batch;
+pub use self::batch::*;
+
+pub mod ceremony;
+pub use self::ceremony::*;
+
+pub mod clipboard;
+pub use self::clipboard::*;
+
+pub mod coldcard;
+pub use self::coldcard::*;
+
+pub mod convert;
+pub use self::convert::*;
+
+pub mod custom_wordlist;
+pub use self::custom_wordlist::*;
+
+pub mod derive;
+pub use self::derive::*;
+
+pub mod dice;
+pub use self::dice::*;
+
+pub mod electrum;
+pub use self::electrum::*;
+
+pub mod encoding;
+pub use self::encoding::*;
+
+pub mod entropy;
+pub use self::entropy::*;
+
+pub mod generate;
+pub use self::generate::*;
+
+pub mod grid;
+pub use self::grid::*;
+
+pub mod hash;
+pub use self::hash::*;
+
+pub mod inheritance;
+pub use self::inheritance::*;
+
+pub mod keystore;
+pub use self::keystore::*;
+
+pub mod locale;
+pub use self::locale::*;
+
+pub mod manifest;
+pub use self::manifest::*;
+
+pub mod mask;
+pub use self::mask::*;
+
+pub mod memory_lock;
+pub use self::memory_lock::*;
+
+pub mod mnemonic_entropy;
+pub use self::mnemonic_entropy::*;
+
+pub mod nonce;
+pub use self::nonce::*;
+
+pub mod pdf;
+pub use self::pdf::*;
+
+pub mod pgp;
+pub use self::pgp::*;
+
+pub mod preset;
+pub use self::preset::*;
+
+pub mod portfolio;
+pub use self::portfolio::*;
+
+pub mod progress;
+pub use self::progress::*;
+
+pub mod prompt;
+pub use self::prompt::*;
+
+pub mod qr;
+pub use self::qr::*;
+
+pub mod recipient;
+pub use self::recipient::*;
+
+pub mod redact;
+pub use self::redact::*;
+
+pub mod rpc;
+pub use self::rpc::*;
+
+pub mod sanitize;
+pub use self::sanitize::*;
+
+pub mod schema;
+pub use self::schema::*;
+
+pub mod secret_nonce;
+pub use self::secret_nonce::*;
+
+pub mod seedqr;
+pub use self::seedqr::*;
+
+pub mod selftest;
+pub use self::selftest::*;
+
+#[cfg(feature = "serve")]
+pub mod server;
+#[cfg(feature = "serve")]
+pub use self::server::*;
+
+pub mod sprout;
+pub use self::sprout::*;
+
+pub mod summary;
+pub use self::summary::*;
+
+pub mod tree;
+pub use self::tree::*;
+
+pub mod unsigned_transaction;
+pub use self::unsigned_transaction::*;
+
+pub mod validation;
+pub use self::validation::*;
+
+pub mod verify;
+pub use self::verify::*;
+
+pub mod wallet_dat;
+pub use self::wallet_dat::*;
+
+pub mod wallet_dump;
+pub use self::wallet_dump::*;
+
+pub mod wallet_file;
+pub use self::wallet_file::*;
+
+pub mod watch_only;
+pub use self::watch_only::*;
+
+pub mod wordlist;
+pub use self::wordlist::*;
+
 pub mod parameters;
 pub use self::parameters::*;
 
@@ -27,40 +186,34 @@ pub trait CLI {
     const SUBCOMMANDS: &'static [SubCommandType];
 
     #[cfg_attr(tarpaulin, skip)]
-    fn new<'a, 'b>() -> App<'a, 'b> {
+    fn new() -> App<'static, 'static> {
+        Self::new_with_hints(&[])
+    }
+
+    /// Returns the subcommand of the coin, suggesting the given value hints for its options in shell completions.
+    #[cfg_attr(tarpaulin, skip)]
+    fn new_with_hints<'b>(hints: &'b [ValueHint]) -> App<'static, 'b> {
         let flags = &Self::FLAGS
             .iter()
             .map(|a| Arg::from_usage(a).global(true))
-            .collect::<Vec<Arg<'static, 'static>>>();
+            .chain(option::COIN.iter().map(|a| to_arg(a, &[], &[]).global(true)))
+            .collect::<Vec<Arg<'static, 'b>>>();
+        let names = Self::OPTIONS
+            .iter()
+            .map(|a| a.0)
+            .chain(Self::FLAGS.iter().copied())
+            .map(to_name)
+            .collect::<Vec<&'static str>>();
         let options = &Self::OPTIONS
             .iter()
-            .map(|a| match a.2.len() > 0 {
-                true => Arg::from_usage(a.0)
-                    .conflicts_with_all(a.1)
-                    .possible_values(a.2)
-                    .requires_all(a.3),
-                false => Arg::from_usage(a.0).conflicts_with_all(a.1).requires_all(a.3),
-            })
-            .collect::<Vec<Arg<'static, 'static>>>();
+            .map(|a| to_arg(a, &names, hints))
+            .collect::<Vec<Arg<'static, 'b>>>();
+        // Each coin describes its parameters with the info subcommand
         let subcommands = Self::SUBCOMMANDS
             .iter()
-            .map(|s| {
-                SubCommand::with_name(s.0)
-                    .about(s.1)
-                    .args(
-                        &s.2.iter()
-                            .map(|a| match a.2.len() > 0 {
-                                true => Arg::from_usage(a.0)
-                                    .conflicts_with_all(a.1)
-                                    .possible_values(a.2)
-                                    .requires_all(a.3),
-                                false => Arg::from_usage(a.0).conflicts_with_all(a.1).requires_all(a.3),
-                            })
-                            .collect::<Vec<Arg<'static, 'static>>>(),
-                    )
-                    .settings(s.3)
-            })
-            .collect::<Vec<App<'static, 'static>>>();
+            .chain(core::iter::once(&subcommand::INFO))
+            .map(|s| to_subcommand(s, Self::FLAGS, hints))
+            .collect::<Vec<App<'static, 'b>>>();
 
         SubCommand::with_name(Self::NAME)
             .about(Self::ABOUT)
@@ -79,6 +232,31 @@ pub trait CLI {
 
     #[cfg_attr(tarpaulin, skip)]
     fn print(options: Self::Options) -> Result<(), CLIError>;
+
+    /// Returns the given number of new wallets, derived from the given BIP-39 mnemonic and password
+    /// if a mnemonic is specified.
+    #[cfg_attr(tarpaulin, skip)]
+    fn generate(
+        count: usize,
+        mnemonic: Option<&str>,
+        password: Option<&str>,
+    ) -> Result<Vec<serde_json::Value>, CLIError>;
+
+    /// Returns the parameters of the coin, from the data of its networks, with the coin type, address formats,
+    /// default derivation path, prefixes, and extended key versions of each network.
+    #[cfg_attr(tarpaulin, skip)]
+    fn info() -> Result<serde_json::Value, CLIError>;
+
+    /// Returns the values of the options of the coin to suggest in shell completions, for options
+    /// that accept other values than their suggestions (such as derivation presets).
+    #[cfg_attr(tarpaulin, skip)]
+    fn value_hints() -> Result<Vec<ValueHint>, CLIError> {
+        Ok(Vec::new())
+    }
+
+    /// Returns the mismatches between the stored fields of the given wallet of a prior output and
+    /// the fields re-derived from it, or `None` if the wallet is not a wallet of the coin.
+    fn verify(wallet: &serde_json::Value) -> Result<Option<Vec<Mismatch>>, CLIError>;
 }
 
 #[derive(Debug, Fail)]
@@ -86,38 +264,338 @@ pub enum CLIError {
     #[fail(display = "{}", _0)]
     AddressError(AddressError),
 
+    #[fail(display = "address not found: {} (searched {} indices of each chain)", _0, _1)]
+    AddressNotFound(String, u32),
+
     #[fail(display = "{}", _0)]
     AmountError(AmountError),
 
+    #[fail(display = "ceremony aborted: {}", _0)]
+    CeremonyAborted(String),
+
+    #[fail(display = "conflicting encryption: --encrypt and --encrypt-to cannot be used together")]
+    ConflictingEncryption,
+
     #[fail(display = "{}: {}", _0, _1)]
     Crate(&'static str, String),
 
     #[fail(display = "{}", _0)]
     DerivationPathError(DerivationPathError),
 
+    #[fail(display = "duplicate coin: {}", _0)]
+    DuplicateCoin(String),
+
+    #[fail(display = "dust output to {}: {{ amount: {}, dust limit: {} }}", _0, _1, _2)]
+    DustOutput(String, u64, u64),
+
+    #[fail(display = "existing secret nonce file: {} (expected a new file for each signing session)", _0)]
+    ExistingSecretNonceFile(String),
+
     #[fail(display = "{}", _0)]
     ExtendedPrivateKeyError(ExtendedPrivateKeyError),
 
     #[fail(display = "{}", _0)]
     ExtendedPublicKeyError(ExtendedPublicKeyError),
 
+    #[fail(display = "failed mnemonic backup verification")]
+    FailedBackupVerification,
+
+    #[fail(display = "failed dice verification: {} mismatched words", _0)]
+    FailedDiceVerification(usize),
+
+    #[fail(display = "failed to lock memory: {}", _0)]
+    FailedMemoryLock(String),
+
+    #[fail(display = "failed self-test: {} of {} test vectors", _0, _1)]
+    FailedSelfTest(usize, usize),
+
+    #[fail(display = "failed verification: {} of {} wallets", _0, _1)]
+    FailedVerification(usize, usize),
+
+    #[fail(display = "insufficient dice rolls for {} words: {} (expected at least {})", _0, _1, _2)]
+    InsufficientDiceRolls(usize, usize, usize),
+
+    #[fail(display = "invalid checkpoint file {}: {}", _0, _1)]
+    InvalidCheckpointFile(String, String),
+
+    #[fail(display = "invalid clipboard field: {} (expected address or private_key)", _0)]
+    InvalidClipboardField(String),
+
+    #[fail(display = "invalid dice roll: {} (expected 1 to 6)", _0)]
+    InvalidDiceRoll(String),
+
+    #[fail(display = "invalid dice mnemonic of {} words (expected 12, 15, 18, 21, or 24)", _0)]
+    InvalidDiceWordCount(usize),
+
+    #[fail(display = "invalid Electrum wallet: {}", _0)]
+    InvalidElectrumWallet(String),
+
+    #[fail(display = "{}", _0)]
+    InvalidEncoding(String),
+
+    #[fail(display = "invalid grid cell: {} (expected A1 to P128)", _0)]
+    InvalidGridCell(String),
+
+    #[fail(display = "invalid grid pattern of {} cells (expected 11 or 23)", _0)]
+    InvalidGridPattern(usize),
+
+    #[fail(display = "invalid hash input: {} (expected hex or a file)", _0)]
+    InvalidHashInput(String),
+
+    #[fail(display = "invalid key derivation parameters: {}", _0)]
+    InvalidKdfParameters(String),
+
+    #[fail(display = "invalid key length: {{ expected: 32, found: {:?} }}", _0)]
+    InvalidKeyLength(usize),
+
+    #[fail(display = "invalid keystore file: {}", _0)]
+    InvalidKeystore(String),
+
+    #[fail(display = "invalid master fingerprint: {} (expected 4 bytes in hex)", _0)]
+    InvalidMasterFingerprint(String),
+
+    #[fail(display = "invalid migration destination: {} address (expected a Sapling address)", _0)]
+    InvalidMigrationDestination(String),
+
     #[fail(display = "invalid derived mnemonic for a given private spend key")]
     InvalidMnemonicForPrivateSpendKey,
 
+    #[fail(display = "invalid passphrase or corrupted wallet file")]
+    InvalidPassphrase,
+
+    #[fail(display = "invalid {} password length: {} (expected {} to {})", _0, _1, _2, _3)]
+    InvalidPasswordLength(String, usize, usize, usize),
+
+    #[fail(display = "invalid recipient: {}", _0)]
+    InvalidRecipient(String),
+
+    #[fail(display = "invalid redact mode: {} (expected mask or omit)", _0)]
+    InvalidRedactMode(String),
+
+    #[fail(display = "invalid secret nonce file {}: {}", _0, _1)]
+    InvalidSecretNonceFile(String, String),
+
+    #[fail(display = "invalid SeedQR payload: {} (expected 48 or 96 digits, or 32 or 64 hex characters)", _0)]
+    InvalidSeedQr(String),
+
+    #[fail(display = "invalid share (expected <index>:<threshold>:<mnemonic> of an English BIP-39 mnemonic)")]
+    InvalidShare,
+
+    #[fail(display = "invalid threshold of {} shares: {} (expected 2 to the number of shares)", _0, _1)]
+    InvalidShareThreshold(u8, u8),
+
+    #[fail(display = "invalid Sprout spending key (expected SK or ST base58, or a 252-bit key in hex)")]
+    InvalidSproutSpendingKey,
+
+    #[fail(display = "invalid restore file of {} wallets (expected 1)", _0)]
+    InvalidRestoreFileCount(usize),
+
+    #[fail(display = "invalid request: {}", _0)]
+    InvalidRequest(String),
+
+    #[fail(display = "invalid verification input (expected a wallet or an array of wallets)")]
+    InvalidVerificationInput,
+
+    #[fail(display = "invalid wallet.dat: {}", _0)]
+    InvalidWalletDat(String),
+
+    #[fail(display = "invalid wallet dump line {}", _0)]
+    InvalidWalletDumpLine(usize),
+
+    #[fail(display = "invalid wallet file")]
+    InvalidWalletFile,
+
+    #[fail(display = "invalid word prefix: no {} words start with {}", _0, _1)]
+    InvalidWordPrefix(String, String),
+
+    #[fail(display = "invalid wordlist file {}: {}", _0, _1)]
+    InvalidWordlistFile(String, String),
+
+    #[fail(display = "low-entropy {}: {} (include --force to proceed)", _0, _1)]
+    LowEntropyInput(String, String),
+
+    #[fail(display = "mismatched coin: {{ expected: {:?}, found: {:?} }}", _0, _1)]
+    MismatchedCoin(String, String),
+
+    #[fail(display = "mismatched passphrase confirmation")]
+    MismatchedPassphrase,
+
+    #[fail(display = "mismatched purpose and format: {{ purpose: {}, format: {} }}", _0, _1)]
+    MismatchedPurposeFormat(u32, String),
+
+    #[fail(display = "mismatched shares: the shares are not of the same mnemonic")]
+    MismatchedShares,
+
+    #[fail(display = "mismatched transaction and signing context: {}", _0)]
+    MismatchedTransaction(String),
+
+    #[fail(display = "missing address for wallet {}", _0)]
+    MissingAddress(usize),
+
+    #[fail(display = "missing batch column: {}", _0)]
+    MissingBatchColumn(String),
+
+    #[fail(display = "missing clipboard utility (pbcopy, clip, wl-copy, xclip, or xsel)")]
+    MissingClipboard,
+
+    #[fail(display = "missing {} for wallet {}", _0, _1)]
+    MissingClipboardField(String, usize),
+
+    #[fail(display = "missing --prefix for the {} encoding", _0)]
+    MissingEncodingPrefix(String),
+
+    #[fail(display = "missing gas, or --estimate-gas")]
+    MissingGas,
+
+    #[fail(display = "missing mnemonic of the inheritance package, or shares to combine")]
+    MissingInheritanceMnemonic,
+
+    #[fail(display = "missing private key of account {} for its keystore file", _0)]
+    MissingKeystoreKey(String),
+
+    #[fail(display = "missing master fingerprint of an extended public key (specify --master-fingerprint)")]
+    MissingMasterFingerprint,
+
+    #[fail(display = "missing mnemonic or master extended private key of {}", _0)]
+    MissingMasterKey(String),
+
+    #[fail(display = "missing message or message hash")]
+    MissingMessage,
+
+    #[fail(display = "missing nonce, or from address with a nonce in the nonce file")]
+    MissingNonce,
+
+    #[fail(display = "missing passphrase")]
+    MissingPassphrase,
+
+    #[fail(display = "missing payment id or integrated address")]
+    MissingPaymentId,
+
+    #[fail(display = "missing RPC URL of a node (specify --rpc-url or $WAGYU_BITCOIN_RPC)")]
+    MissingRpcUrl,
+
+    #[fail(display = "missing private keys of the restore file (import a mnemonic or private spend key)")]
+    MissingRestoreKeys,
+
+    #[fail(display = "missing save file for encryption")]
+    MissingSaveFile,
+
+    #[fail(display = "missing extended public key or mnemonic to search")]
+    MissingSearchKey,
+
+    #[fail(display = "missing secret nonce file: a signer requires --secret-nonce-file")]
+    MissingSecretNonceFile,
+
+    #[fail(display = "missing token: a server {} requires --token or ${}", _0, _1)]
+    MissingServerToken(String, String),
+
+    #[fail(display = "missing shares: {} shares recover the mnemonic (found {})", _0, _1)]
+    MissingShares(u8, usize),
+
+    #[fail(display = "missing Sprout spending keys in the wallet dump")]
+    MissingSproutKeys,
+
+    #[fail(display = "missing from address to track in the nonce file")]
+    MissingSender,
+
+    #[fail(display = "missing signing context for input {}", _0)]
+    MissingSigningContext(String),
+
+    #[fail(display = "missing private key for input {}", _0)]
+    MissingSigningKey(String),
+
+    #[fail(display = "missing transaction private key, or private view key and transaction public key")]
+    MissingTransactionProofKey,
+
+    #[fail(display = "non-canonical {}: {} (rejected by --strict)", _0, _1)]
+    NonCanonicalInput(String, String),
+
     #[fail(display = "{}", _0)]
     PrivateKeyError(PrivateKeyError),
 
     #[fail(display = "{}", _0)]
     PublicKeyError(PublicKeyError),
 
+    #[fail(display = "data of {} bytes is too long for a QR code", _0)]
+    QrCodeTooLong(usize),
+
     #[fail(display = "{}", _0)]
     MnemonicError(MnemonicError),
 
+    #[fail(display = "reused nonce for {}: {{ nonce: {}, next: {} }}", _0, _1, _2)]
+    ReusedNonce(String, u64, u64),
+
+    #[fail(display = "reused secret nonce of {}: a secret nonce must never sign twice", _0)]
+    ReusedSecretNonce(String),
+
+    #[fail(display = "rpc error: {}", _0)]
+    RpcError(String),
+
     #[fail(display = "{}", _0)]
     TransactionError(TransactionError),
 
+    #[fail(display = "unknown coin: {} (expected one of: {})", _0, _1)]
+    UnknownCoin(String, String),
+
+    #[fail(display = "unknown derivation preset: {}", _0)]
+    UnknownDerivationPreset(String),
+
+    #[fail(display = "unknown key: {} (expected age, password, pgp, ssh, or totp)", _0)]
+    UnknownKey(String),
+
+    #[fail(display = "unknown schema output: {}", _0)]
+    UnknownSchema(String),
+
+    #[fail(
+        display = "unknown watch-only wallet: {} (expected bluewallet, electrum, sparrow, or specter)",
+        _0
+    )]
+    UnknownWatchOnlyWallet(String),
+
+    #[fail(display = "unsupported wallet file encryption: {}", _0)]
+    UnsupportedEncryption(String),
+
+    #[fail(display = "unsupported file version: {}", _0)]
+    UnsupportedFileVersion(u32),
+
+    #[fail(display = "unsupported conversion from {} to {} (expected one of: {})", _0, _1, _2)]
+    UnsupportedConversion(String, String, String),
+
+    #[fail(display = "unsupported dice method: {} (expected base6 or raw)", _0)]
+    UnsupportedDiceMethod(String),
+
+    #[fail(display = "unsupported Electrum wallet: {} (expected a standard wallet)", _0)]
+    UnsupportedElectrumWallet(String),
+
+    #[fail(display = "unsupported encoding: {}", _0)]
+    UnsupportedEncoding(String),
+
+    #[fail(display = "unsupported hash algorithm: {}", _0)]
+    UnsupportedHashAlgorithm(String),
+
+    #[fail(display = "unsupported --duress with a custom derivation path (expected bip32, bip44, bip49, bip84, or bip86)")]
+    UnsupportedDuressDerivation,
+
+    #[fail(display = "unsupported --lock-memory on this platform (expected Linux)")]
+    UnsupportedMemoryLock,
+
+    #[fail(
+        display = "unsupported mnemonic for {} wallets (expected a coin with BIP-39 mnemonics)",
+        _0
+    )]
+    UnsupportedMnemonic(String),
+
     #[fail(display = "unsupported mnemonic language")]
     UnsupportedLanguage,
+
+    #[fail(display = "unsupported network: {}", _0)]
+    UnsupportedNetwork(String),
+
+    #[fail(display = "unsupported SeedQR mnemonic of {} words (expected 12 or 24)", _0)]
+    UnsupportedSeedQr(usize),
+
+    #[fail(display = "unsupported {} watch-only wallet of format {}", _0, _1)]
+    UnsupportedWatchOnlyFormat(String, String),
 }
 
 impl From<AddressError> for CLIError {
@@ -132,6 +610,30 @@ impl From<AmountError> for CLIError {
     }
 }
 
+impl From<rustc_serialize::base64::FromBase64Error> for CLIError {
+    fn from(error: rustc_serialize::base64::FromBase64Error) -> Self {
+        CLIError::Crate("base64", format!("{:?}", error))
+    }
+}
+
+impl From<bech32::Error> for CLIError {
+    fn from(error: bech32::Error) -> Self {
+        CLIError::Crate("bech32", format!("{:?}", error))
+    }
+}
+
+impl From<std::io::Error> for CLIError {
+    fn from(error: std::io::Error) -> Self {
+        CLIError::Crate("std::io", format!("{:?}", error))
+    }
+}
+
+impl From<std::net::AddrParseError> for CLIError {
+    fn from(error: std::net::AddrParseError) -> Self {
+        CLIError::Crate("std::net", format!("{:?}", error))
+    }
+}
+
 impl From<core::num::ParseIntError> for CLIError {
     fn from(error: core::num::ParseIntError) -> Self {
         CLIError::Crate("parse_int", format!("{:?}", error))
@@ -168,26 +670,92 @@ impl From<MnemonicError> for CLIError {
     }
 }
 
+impl From<MultisigError> for CLIError {
+    fn from(error: MultisigError) -> Self {
+        CLIError::Crate("multisig", format!("{}", error))
+    }
+}
+
 impl From<PrivateKeyError> for CLIError {
     fn from(error: PrivateKeyError) -> Self {
         CLIError::PrivateKeyError(error)
     }
 }
 
+impl From<PaymentIdError> for CLIError {
+    fn from(error: PaymentIdError) -> Self {
+        CLIError::Crate("payment id", format!("{}", error))
+    }
+}
+
+impl From<ProofError> for CLIError {
+    fn from(error: ProofError) -> Self {
+        CLIError::Crate("proof", format!("{}", error))
+    }
+}
+
 impl From<PublicKeyError> for CLIError {
     fn from(error: PublicKeyError) -> Self {
         CLIError::PublicKeyError(error)
     }
 }
 
+impl From<FrostError> for CLIError {
+    fn from(error: FrostError) -> Self {
+        CLIError::Crate("frost", format!("{}", error))
+    }
+}
+
+impl From<MiniscriptError> for CLIError {
+    fn from(error: MiniscriptError) -> Self {
+        CLIError::Crate("miniscript", format!("{}", error))
+    }
+}
+
+impl From<MuSigError> for CLIError {
+    fn from(error: MuSigError) -> Self {
+        CLIError::Crate("musig", format!("{}", error))
+    }
+}
+
+impl From<SignatureError> for CLIError {
+    fn from(error: SignatureError) -> Self {
+        CLIError::Crate("signature", format!("{}", error))
+    }
+}
+
+impl From<SignedMessageError> for CLIError {
+    fn from(error: SignedMessageError) -> Self {
+        CLIError::Crate("signed_message", format!("{}", error))
+    }
+}
+
 impl From<serde_json::error::Error> for CLIError {
     fn from(error: serde_json::error::Error) -> Self {
         CLIError::Crate("serde_json", format!("{:?}", error))
     }
 }
 
+impl From<TaprootError> for CLIError {
+    fn from(error: TaprootError) -> Self {
+        CLIError::Crate("taproot", format!("{}", error))
+    }
+}
+
 impl From<TransactionError> for CLIError {
     fn from(error: TransactionError) -> Self {
         CLIError::TransactionError(error)
     }
 }
+
+impl From<TypedDataError> for CLIError {
+    fn from(error: TypedDataError) -> Self {
+        CLIError::Crate("typed_data", format!("{}", error))
+    }
+}
+
+impl From<UserOperationError> for CLIError {
+    fn from(error: UserOperationError) -> Self {
+        CLIError::Crate("user_operation", format!("{}", error))
+    }
+}
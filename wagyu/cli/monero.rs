@@ -1,8 +1,14 @@
-use crate::cli::{flag, option, subcommand, types::*, CLIError, CLI};
-use crate::model::{Mnemonic, PrivateKey, PublicKey};
+use crate::cli::{
+    check_clipboard_field, confirm_backups, copy_wallets, encrypt_to, flag, import_batch, mask, object, option,
+    print_wallets, property, read_batch_file, rederive, sanitize_option, subcommand, types::*, AuditLog, CLIError,
+    InputKind, Manifest, MaskSecrets, Mismatch, Recipient, Schema, WalletFile, CLI, CLIPBOARD_CLEAR_AFTER,
+};
+use crate::model::{AddressError, Mnemonic, PrivateKey, PublicKey};
 use crate::monero::{
-    format::MoneroFormat, wordlist::*, Mainnet as MoneroMainnet, MoneroAddress, MoneroMnemonic, MoneroNetwork,
-    MoneroPublicKey, MoneroWordlist, Stagenet as MoneroStagenet, Testnet as MoneroTestnet,
+    format::MoneroFormat, wordlist::*, Mainnet as MoneroMainnet, MoneroAddress, MoneroMnemonic,
+    MoneroMultisigExtraInfo, MoneroMultisigInfo, MoneroMultisigWallet, MoneroNetwork, MoneroPaymentId,
+    MoneroPrivateKey, MoneroPublicKey, MoneroTransactionProof, MoneroWordlist, Stagenet as MoneroStagenet,
+    Testnet as MoneroTestnet,
 };
 
 use clap::{ArgMatches, Values};
@@ -11,12 +17,14 @@ use core::{fmt, fmt::Display, str::FromStr};
 use rand::{rngs::StdRng, Rng};
 use rand_core::SeedableRng;
 use serde::Serialize;
+use serde_json::json;
+use std::path::Path;
 
 use crate::model::no_std::{format, vec, String, ToOwned, ToString, Vec};
 
 /// Represents a generic wallet to output
-#[derive(Serialize, Debug, Default)]
-struct MoneroWallet {
+#[derive(Serialize, Clone, Debug, Default)]
+pub(crate) struct MoneroWallet {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub mnemonic: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -34,7 +42,99 @@ struct MoneroWallet {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub payment_id: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub encrypted_payment_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub standard_address: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub network: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transaction_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub proof_type: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub valid: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub multisig_info: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub multisig_keys: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub threshold: Option<String>,
+}
+
+impl MaskSecrets for MoneroWallet {
+    fn mask_secrets(&mut self) {
+        mask(&mut self.mnemonic);
+        mask(&mut self.private_spend_key);
+        mask(&mut self.private_view_key);
+        mask(&mut self.multisig_info);
+        mask(&mut self.multisig_keys);
+    }
+}
+
+impl Schema for MoneroWallet {
+    fn schema() -> serde_json::Value {
+        object(
+            vec![
+                ("mnemonic", property("string", "The mnemonic phrase of the wallet")),
+                (
+                    "private_spend_key",
+                    property("string", "The private spend key of the wallet"),
+                ),
+                (
+                    "private_view_key",
+                    property("string", "The private view key of the wallet"),
+                ),
+                (
+                    "public_spend_key",
+                    property("string", "The public spend key of the wallet"),
+                ),
+                (
+                    "public_view_key",
+                    property("string", "The public view key of the wallet"),
+                ),
+                ("address", property("string", "The address of the wallet")),
+                ("format", property("string", "The address format of the wallet")),
+                (
+                    "payment_id",
+                    property("string", "The payment id of an integrated address"),
+                ),
+                (
+                    "encrypted_payment_id",
+                    property("string", "The payment id encrypted in a transaction"),
+                ),
+                (
+                    "standard_address",
+                    property("string", "The standard address of an integrated address"),
+                ),
+                ("network", property("string", "The network of the wallet")),
+                ("transaction_id", property("string", "The transaction id")),
+                ("message", property("string", "The signed or proven message")),
+                ("proof_type", property("string", "The type of the transaction proof")),
+                ("signature", property("string", "The signature in hex")),
+                ("valid", property("boolean", "Whether the signature or proof is valid")),
+                (
+                    "multisig_info",
+                    property("string", "The multisig info to share with the other participants"),
+                ),
+                (
+                    "multisig_keys",
+                    property(
+                        "string",
+                        "The private multisig keys of the participant (comma separated)",
+                    ),
+                ),
+                (
+                    "threshold",
+                    property("string", "The threshold of a multisig wallet, of its participants"),
+                ),
+            ],
+            &[],
+        )
+    }
 }
 
 impl MoneroWallet {
@@ -157,14 +257,283 @@ impl MoneroWallet {
 
     pub fn from_address<N: MoneroNetwork>(address: &str) -> Result<Self, CLIError> {
         let address = MoneroAddress::<N>::from_str(address)?;
+        let standard_address = address.to_standard_address()?;
         Ok(Self {
             address: Some(address.to_string()),
             network: Some(N::NAME.to_string()),
             format: Some(address.format()?.to_string()),
             payment_id: address.to_payment_id(),
+            standard_address: Some(standard_address.to_string()).filter(|_| standard_address != address),
             ..Default::default()
         })
     }
+
+    /// Returns the network, type, and public keys of the given address of any network, or the error of
+    /// the network of its prefix, such as an invalid character or checksum, if the address is malformed.
+    pub fn validate(address: &str) -> Result<Self, CLIError> {
+        let mut errors = Vec::with_capacity(3);
+        for result in [
+            Self::from_valid_address::<MoneroMainnet>(address),
+            Self::from_valid_address::<MoneroStagenet>(address),
+            Self::from_valid_address::<MoneroTestnet>(address),
+        ] {
+            match result {
+                Ok(wallet) => return Ok(wallet),
+                Err(error) => errors.push(error),
+            }
+        }
+
+        // An address of a known prefix fails as an invalid prefix on the other networks
+        let diagnosis = errors
+            .iter()
+            .position(|error| !matches!(error, CLIError::AddressError(AddressError::InvalidPrefix(_))))
+            .unwrap_or_default();
+        Err(errors.swap_remove(diagnosis))
+    }
+
+    fn from_valid_address<N: MoneroNetwork>(address: &str) -> Result<Self, CLIError> {
+        let valid_address = MoneroAddress::<N>::from_str(address)?;
+        let public_key = valid_address.to_public_key()?;
+        // The major and minor index of a subaddress are not recoverable from the address
+        let format = match valid_address.format()? {
+            MoneroFormat::Subaddress(_, _) => "subaddress".to_string(),
+            format => format.to_string(),
+        };
+        Ok(Self {
+            public_spend_key: public_key.to_public_spend_key().map(hex::encode),
+            public_view_key: public_key.to_public_view_key().map(hex::encode),
+            format: Some(format),
+            valid: Some(true),
+            ..Self::from_address::<N>(address)?
+        })
+    }
+
+    /// Returns the payment ID of a transaction to the given address encrypted with the transaction
+    /// private key, where the payment ID defaults to the payment ID of an integrated address.
+    pub fn to_encrypted_payment_id<N: MoneroNetwork>(
+        address: &str,
+        payment_id: Option<&str>,
+        transaction_key: &str,
+    ) -> Result<Self, CLIError> {
+        let address = MoneroAddress::<N>::from_str(address)?;
+        let payment_id = match payment_id.map(String::from).or_else(|| address.to_payment_id()) {
+            Some(payment_id) => MoneroPaymentId::from_str(&payment_id)?,
+            None => return Err(CLIError::MissingPaymentId),
+        };
+        let public_view_key = address.to_public_key()?.to_public_view_key().unwrap();
+        let encrypted_payment_id = payment_id.encrypt(&public_view_key, &to_key(transaction_key)?)?;
+
+        Ok(Self {
+            address: Some(address.to_string()),
+            network: Some(N::NAME.to_string()),
+            payment_id: Some(payment_id.to_string()),
+            encrypted_payment_id: Some(encrypted_payment_id.to_string()),
+            ..Default::default()
+        })
+    }
+
+    /// Returns the payment ID of a transaction decrypted with the private view key of the recipient.
+    pub fn from_encrypted_payment_id(
+        encrypted_payment_id: &str,
+        private_view_key: &str,
+        transaction_public_key: &str,
+    ) -> Result<Self, CLIError> {
+        let encrypted_payment_id = MoneroPaymentId::from_str(encrypted_payment_id)?;
+        let payment_id = encrypted_payment_id.decrypt(&to_key(transaction_public_key)?, &to_key(private_view_key)?)?;
+
+        Ok(Self {
+            payment_id: Some(payment_id.to_string()),
+            encrypted_payment_id: Some(encrypted_payment_id.to_string()),
+            ..Default::default()
+        })
+    }
+
+    pub fn to_transaction_proof<N: MoneroNetwork>(
+        transaction_id: &str,
+        address: &str,
+        transaction_keys: Option<&str>,
+        private_view_key: Option<&str>,
+        transaction_public_keys: Option<&str>,
+        message: &str,
+    ) -> Result<Self, CLIError> {
+        let address = MoneroAddress::<N>::from_str(address)?;
+        let transaction_id = to_key(transaction_id)?;
+        let rng = &mut StdRng::from_entropy();
+
+        let proof = match (transaction_keys, private_view_key, transaction_public_keys) {
+            (Some(transaction_keys), _, _) => MoneroTransactionProof::outbound(
+                rng,
+                &transaction_id,
+                &address,
+                &to_keys(transaction_keys)?,
+                message.as_bytes(),
+            )?,
+            (None, Some(private_view_key), Some(transaction_public_keys)) => MoneroTransactionProof::inbound(
+                rng,
+                &transaction_id,
+                &address,
+                &to_key(private_view_key)?,
+                &to_keys(transaction_public_keys)?,
+                message.as_bytes(),
+            )?,
+            _ => return Err(CLIError::MissingTransactionProofKey),
+        };
+
+        Ok(Self {
+            address: Some(address.to_string()),
+            network: Some(N::NAME.to_string()),
+            transaction_id: Some(hex::encode(transaction_id)),
+            message: Some(message.to_string()),
+            proof_type: Some(proof.proof_type.to_string()),
+            signature: Some(proof.to_string()),
+            ..Default::default()
+        })
+    }
+
+    /// Returns the multisig info of the participant with the given private spend key to share with the other
+    /// participants, or the multisig wallet of the participant once the key exchange is complete. The first
+    /// round info is returned without the infos of the other participants, and the second round info of an
+    /// (N-1)-of-N wallet is returned without their second round infos.
+    pub fn to_multisig<N: MoneroNetwork>(
+        private_spend_key: &str,
+        threshold: Option<usize>,
+        infos: Option<&str>,
+        extra_infos: Option<&str>,
+    ) -> Result<Self, CLIError> {
+        let private_key = MoneroPrivateKey::<N>::from_private_spend_key(private_spend_key, &MoneroFormat::Standard)?;
+        let rng = &mut StdRng::from_entropy();
+
+        let infos = match infos {
+            Some(infos) => infos
+                .split(',')
+                .map(|info| MoneroMultisigInfo::from_str(info.trim()))
+                .collect::<Result<Vec<MoneroMultisigInfo>, _>>()?,
+            None => {
+                return Ok(Self {
+                    multisig_info: Some(MoneroMultisigInfo::new(rng, &private_key).to_string()),
+                    network: Some(N::NAME.to_string()),
+                    ..Default::default()
+                })
+            }
+        };
+        let participants = infos.len() + 1;
+        let threshold = threshold.unwrap_or(participants);
+
+        let extra_infos = match extra_infos {
+            Some(extra_infos) => extra_infos
+                .split(',')
+                .map(|info| MoneroMultisigExtraInfo::from_str(info.trim()))
+                .collect::<Result<Vec<MoneroMultisigExtraInfo>, _>>()?,
+            None if threshold + 1 == participants => {
+                return Ok(Self {
+                    multisig_info: Some(MoneroMultisigExtraInfo::new(rng, &private_key, &infos)?.to_string()),
+                    threshold: Some(format!("{}/{}", threshold, participants)),
+                    network: Some(N::NAME.to_string()),
+                    ..Default::default()
+                })
+            }
+            None => vec![],
+        };
+
+        let wallet = MoneroMultisigWallet::<N>::new(&private_key, threshold, &infos, &extra_infos)?;
+        let address = wallet.to_address()?;
+        Ok(Self {
+            private_spend_key: Some(hex::encode(wallet.to_private_spend_key())),
+            private_view_key: Some(hex::encode(wallet.private_view_key)),
+            public_spend_key: Some(hex::encode(wallet.public_spend_key)),
+            public_view_key: Some(hex::encode(wallet.to_public_view_key())),
+            address: Some(address.to_string()),
+            format: Some(MoneroFormat::Standard.to_string()),
+            network: Some(N::NAME.to_string()),
+            multisig_keys: Some(
+                wallet
+                    .multisig_keys
+                    .iter()
+                    .map(hex::encode)
+                    .collect::<Vec<String>>()
+                    .join(","),
+            ),
+            threshold: Some(format!("{}/{}", wallet.threshold, wallet.participants)),
+            ..Default::default()
+        })
+    }
+
+    pub fn from_transaction_proof<N: MoneroNetwork>(
+        transaction_id: &str,
+        address: &str,
+        transaction_public_keys: &str,
+        message: &str,
+        signature: &str,
+    ) -> Result<Self, CLIError> {
+        let address = MoneroAddress::<N>::from_str(address)?;
+        let transaction_id = to_key(transaction_id)?;
+        let proof = MoneroTransactionProof::from_str(signature)?;
+        let valid = proof.verify(
+            &transaction_id,
+            &address,
+            &to_keys(transaction_public_keys)?,
+            message.as_bytes(),
+        )?;
+
+        Ok(Self {
+            address: Some(address.to_string()),
+            network: Some(N::NAME.to_string()),
+            transaction_id: Some(hex::encode(transaction_id)),
+            message: Some(message.to_string()),
+            proof_type: Some(proof.proof_type.to_string()),
+            signature: Some(signature.to_string()),
+            valid: Some(valid),
+            ..Default::default()
+        })
+    }
+
+    /// Returns the restore parameters of the wallet for `monero-wallet-cli --generate-from-json`, of a wallet file
+    /// named after the given restore file, scanned from the given block height. The wallet is restored from its
+    /// private keys, with the standard address of its public keys (rather than a subaddress or an integrated
+    /// address) as a check. The wallet password is omitted, so that it is not written to the file in plaintext.
+    pub fn to_restore_parameters<N: MoneroNetwork>(
+        &self,
+        path: &str,
+        scan_from_height: u64,
+    ) -> Result<serde_json::Value, CLIError> {
+        match (
+            &self.private_spend_key,
+            &self.private_view_key,
+            &self.public_spend_key,
+            &self.public_view_key,
+        ) {
+            (Some(private_spend_key), Some(private_view_key), Some(public_spend_key), Some(public_view_key)) => {
+                let standard = MoneroFormat::Standard;
+                let public_key = MoneroPublicKey::<N>::from(public_spend_key, public_view_key, &standard)?;
+                Ok(json!({
+                    "version": 1,
+                    "filename": Path::new(path).with_extension("").to_string_lossy(),
+                    "scan_from_height": scan_from_height,
+                    "spendkey": private_spend_key,
+                    "viewkey": private_view_key,
+                    "address": public_key.to_address(&standard)?.to_string(),
+                }))
+            }
+            _ => Err(CLIError::MissingRestoreKeys),
+        }
+    }
+}
+
+/// Returns the 32-byte key of the given hex string.
+fn to_key(key: &str) -> Result<[u8; 32], CLIError> {
+    let bytes = hex::decode(key.trim())?;
+    if bytes.len() != 32 {
+        return Err(CLIError::InvalidKeyLength(bytes.len()));
+    }
+
+    let mut key = [0u8; 32];
+    key.copy_from_slice(&bytes);
+    Ok(key)
+}
+
+/// Returns the 32-byte keys of the given comma separated hex strings.
+fn to_keys(keys: &str) -> Result<Vec<[u8; 32]>, CLIError> {
+    keys.split(',').map(to_key).collect()
 }
 
 #[cfg_attr(tarpaulin, skip)]
@@ -211,10 +580,56 @@ impl Display for MoneroWallet {
                 Some(payment_id) => format!("      {}           {}\n", "Payment ID".cyan().bold(), payment_id),
                 _ => "".to_owned(),
             },
+            match &self.encrypted_payment_id {
+                Some(encrypted_payment_id) => format!(
+                    "      {} {}\n",
+                    "Encrypted Payment ID".cyan().bold(),
+                    encrypted_payment_id
+                ),
+                _ => "".to_owned(),
+            },
+            match &self.standard_address {
+                Some(standard_address) => {
+                    format!("      {}     {}\n", "Standard Address".cyan().bold(), standard_address)
+                }
+                _ => "".to_owned(),
+            },
             match &self.network {
                 Some(network) => format!("      {}              {}\n", "Network".cyan().bold(), network),
                 _ => "".to_owned(),
             },
+            match &self.transaction_id {
+                Some(transaction_id) => format!("      {}       {}\n", "Transaction Id".cyan().bold(), transaction_id),
+                _ => "".to_owned(),
+            },
+            match &self.message {
+                Some(message) => format!("      {}              {}\n", "Message".cyan().bold(), message),
+                _ => "".to_owned(),
+            },
+            match &self.proof_type {
+                Some(proof_type) => format!("      {}           {}\n", "Proof Type".cyan().bold(), proof_type),
+                _ => "".to_owned(),
+            },
+            match &self.signature {
+                Some(signature) => format!("      {}            {}\n", "Signature".cyan().bold(), signature),
+                _ => "".to_owned(),
+            },
+            match &self.valid {
+                Some(valid) => format!("      {}                {}\n", "Valid".cyan().bold(), valid),
+                _ => "".to_owned(),
+            },
+            match &self.threshold {
+                Some(threshold) => format!("      {}            {}\n", "Threshold".cyan().bold(), threshold),
+                _ => "".to_owned(),
+            },
+            match &self.multisig_keys {
+                Some(multisig_keys) => format!("      {}        {}\n", "Multisig Keys".cyan().bold(), multisig_keys),
+                _ => "".to_owned(),
+            },
+            match &self.multisig_info {
+                Some(multisig_info) => format!("      {}        {}\n", "Multisig Info".cyan().bold(), multisig_info),
+                _ => "".to_owned(),
+            },
         ]
         .concat();
 
@@ -228,38 +643,88 @@ impl Display for MoneroWallet {
 #[derive(Serialize, Clone, Debug)]
 pub struct MoneroOptions {
     // Standard command
+    clear_after: u64,
+    confirm_backup: bool,
     count: usize,
+    copy: Option<String>,
+    encrypt: bool,
+    encrypt_to: Option<String>,
     format: MoneroFormat,
     json: bool,
+    manifest: Option<String>,
+    mask_secrets: bool,
     language: String,
     network: String,
+    restore_file: Option<String>,
+    restore_height: u64,
+    reveal: bool,
+    save: Option<String>,
     subcommand: Option<String>,
     // Import subcommand
     address: Option<String>,
+    column: Option<String>,
+    file: Option<String>,
     mnemonic: Option<String>,
+    payment_id: Option<String>,
     private_spend_key: Option<String>,
     private_view_key: Option<String>,
     public_spend_key: Option<String>,
     public_view_key: Option<String>,
+    // Multisig subcommand
+    extra_infos: Option<String>,
+    infos: Option<String>,
+    threshold: Option<usize>,
+    // Payment ID, prove, and check proof subcommands
+    encrypted_payment_id: Option<String>,
+    message: Option<String>,
+    signature: Option<String>,
+    transaction_id: Option<String>,
+    transaction_keys: Option<String>,
+    transaction_public_keys: Option<String>,
 }
 
 impl Default for MoneroOptions {
     fn default() -> Self {
         Self {
             // Standard command
+            clear_after: CLIPBOARD_CLEAR_AFTER,
+            confirm_backup: false,
             count: 1,
+            copy: None,
+            encrypt: false,
+            encrypt_to: None,
             format: MoneroFormat::Standard,
             json: false,
+            manifest: None,
+            mask_secrets: false,
             language: "english".into(),
             network: "mainnet".into(),
+            restore_file: None,
+            restore_height: 0,
+            reveal: false,
+            save: None,
             subcommand: None,
             // Import subcommand
             address: None,
+            column: None,
+            file: None,
             mnemonic: None,
+            payment_id: None,
             private_spend_key: None,
             private_view_key: None,
             public_spend_key: None,
             public_view_key: None,
+            // Multisig subcommand
+            extra_infos: None,
+            infos: None,
+            threshold: None,
+            // Payment ID, prove, and check proof subcommands
+            encrypted_payment_id: None,
+            message: None,
+            signature: None,
+            transaction_id: None,
+            transaction_keys: None,
+            transaction_public_keys: None,
         }
     }
 }
@@ -268,21 +733,54 @@ impl MoneroOptions {
     fn parse(&mut self, arguments: &ArgMatches, options: &[&str]) {
         options.iter().for_each(|option| match *option {
             "address" => self.address(arguments.value_of(option)),
+            "clear after" => self.clear_after(clap::value_t!(arguments.value_of(*option), u64).ok()),
+            "column" => self.column(arguments.value_of(option)),
+            "confirm backup" => self.confirm_backup(arguments.is_present(option)),
+            "copy" => self.copy(arguments.value_of(option)),
             "count" => self.count(clap::value_t!(arguments.value_of(*option), usize).ok()),
-            "integrated" => self.integrated(arguments.value_of(option)),
+            "encrypt" => self.encrypt(arguments.is_present(option)),
+            "encrypt to" => self.encrypt_to(arguments.value_of(option)),
+            "encrypted" => self.encrypted_payment_id(arguments.value_of(option)),
+            "extra info" => self.extra_infos(arguments.value_of(option)),
+            "file" => self.file(arguments.value_of(option)),
+            "info" => self.infos(arguments.value_of(option)),
+            "integrated" => self.payment_id(arguments.value_of(option)),
             "json" => self.json(arguments.is_present(option)),
             "language" => self.language(arguments.value_of(option)),
+            "manifest" => self.manifest(arguments.value_of(option)),
+            "mask secrets" => self.mask_secrets(arguments.is_present(option)),
+            "message" => self.message(arguments.value_of(option)),
             "mnemonic" => self.mnemonic(arguments.value_of(option)),
             "network" => self.network(arguments.value_of(option)),
+            "payment id" => self.payment_id(arguments.value_of(option)),
             "private spend" => self.private_spend(arguments.value_of(option)),
             "private view" => self.private_view(arguments.value_of(option)),
             "public spend" => self.public_spend(arguments.value_of(option)),
             "public view" => self.public_view(arguments.value_of(option)),
+            "restore file" => self.restore_file(arguments.value_of(option)),
+            "restore height" => self.restore_height(clap::value_t!(arguments.value_of(*option), u64).ok()),
+            "reveal" => self.reveal(arguments.is_present(option)),
+            "save" => self.save(arguments.value_of(option)),
+            "signature" => self.signature(arguments.value_of(option)),
             "subaddress" => self.subaddress(arguments.values_of(option)),
+            "threshold" => self.threshold(clap::value_t!(arguments.value_of(*option), usize).ok()),
+            "tx id" => self.transaction_id(arguments.value_of(option)),
+            "tx key" => self.transaction_keys(arguments.value_of(option)),
+            "tx public key" => self.transaction_public_keys(arguments.value_of(option)),
             _ => (),
         });
     }
 
+    /// Sanitizes the key material of the options, with `--strict` rejecting key material in a non-canonical form.
+    fn sanitize_inputs(&mut self) -> Result<(), CLIError> {
+        sanitize_option("address", &mut self.address, InputKind::Text)?;
+        sanitize_option("mnemonic", &mut self.mnemonic, InputKind::Mnemonic)?;
+        sanitize_option("private spend key", &mut self.private_spend_key, InputKind::Hex)?;
+        sanitize_option("private view key", &mut self.private_view_key, InputKind::Hex)?;
+        sanitize_option("public spend key", &mut self.public_spend_key, InputKind::Hex)?;
+        sanitize_option("public view key", &mut self.public_view_key, InputKind::Hex)
+    }
+
     /// Sets `address` to the specified address, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
     fn address(&mut self, argument: Option<&str>) {
@@ -291,6 +789,35 @@ impl MoneroOptions {
         }
     }
 
+    /// Sets `clear_after` to the specified number of seconds, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn clear_after(&mut self, argument: Option<u64>) {
+        if let Some(seconds) = argument {
+            self.clear_after = seconds;
+        }
+    }
+
+    /// Sets `column` to the specified CSV column of a batch file, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn column(&mut self, argument: Option<&str>) {
+        if let Some(column) = argument {
+            self.column = Some(column.to_string());
+        }
+    }
+
+    /// Sets `confirm_backup` to the specified boolean value, overriding its previous state.
+    fn confirm_backup(&mut self, argument: bool) {
+        self.confirm_backup = argument;
+    }
+
+    /// Sets `copy` to the specified wallet field, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn copy(&mut self, argument: Option<&str>) {
+        if let Some(field) = argument {
+            self.copy = Some(field.to_string());
+        }
+    }
+
     /// Sets `count` to the specified count, overriding its previous state.
     fn count(&mut self, argument: Option<usize>) {
         if let Some(count) = argument {
@@ -298,13 +825,50 @@ impl MoneroOptions {
         }
     }
 
-    /// Sets `format` to an integrated address with the specified payment ID, overriding its previous state.
+    /// Sets `encrypt` to the specified boolean value, overriding its previous state.
+    fn encrypt(&mut self, argument: bool) {
+        if argument {
+            self.encrypt = true;
+        }
+    }
+
+    /// Sets `encrypt_to` to the specified recipients, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn encrypt_to(&mut self, argument: Option<&str>) {
+        if let Some(recipients) = argument {
+            self.encrypt_to = Some(recipients.to_string());
+        }
+    }
+
+    /// Sets `encrypted_payment_id` to the specified encrypted payment ID, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn encrypted_payment_id(&mut self, argument: Option<&str>) {
+        if let Some(encrypted_payment_id) = argument {
+            self.encrypted_payment_id = Some(encrypted_payment_id.to_string());
+        }
+    }
+
+    /// Sets `extra_infos` to the specified second round multisig infos, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn extra_infos(&mut self, argument: Option<&str>) {
+        if let Some(extra_infos) = argument {
+            self.extra_infos = Some(extra_infos.to_string());
+        }
+    }
+
+    /// Sets `file` to the specified batch file of keys or addresses, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn file(&mut self, argument: Option<&str>) {
+        if let Some(path) = argument {
+            self.file = Some(path.to_string());
+        }
+    }
+
+    /// Sets `infos` to the specified first round multisig infos, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
-    fn integrated(&mut self, argument: Option<&str>) {
-        if let Some(id) = argument {
-            let mut payment_id = [0u8; 8];
-            payment_id.copy_from_slice(&hex::decode(id).unwrap());
-            self.format = MoneroFormat::Integrated(payment_id);
+    fn infos(&mut self, argument: Option<&str>) {
+        if let Some(infos) = argument {
+            self.infos = Some(infos.to_string());
         }
     }
 
@@ -334,6 +898,27 @@ impl MoneroOptions {
         };
     }
 
+    /// Sets `manifest` to the specified manifest file, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn manifest(&mut self, argument: Option<&str>) {
+        if let Some(manifest) = argument {
+            self.manifest = Some(manifest.to_string());
+        }
+    }
+
+    /// Sets `mask_secrets` to the specified boolean value, overriding its previous state.
+    fn mask_secrets(&mut self, argument: bool) {
+        self.mask_secrets = argument;
+    }
+
+    /// Sets `message` to the specified message, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn message(&mut self, argument: Option<&str>) {
+        if let Some(message) = argument {
+            self.message = Some(message.to_string());
+        }
+    }
+
     /// Sets `mnemonic` to the specified mnemonic, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
     fn mnemonic(&mut self, argument: Option<&str>) {
@@ -353,6 +938,14 @@ impl MoneroOptions {
         };
     }
 
+    /// Sets `payment_id` to the specified payment ID, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn payment_id(&mut self, argument: Option<&str>) {
+        if let Some(payment_id) = argument {
+            self.payment_id = Some(payment_id.to_string());
+        }
+    }
+
     /// Sets `private_spend_key` to the specified private spend key, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
     fn private_spend(&mut self, argument: Option<&str>) {
@@ -385,6 +978,43 @@ impl MoneroOptions {
         }
     }
 
+    /// Sets `restore_file` to the specified restore file, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn restore_file(&mut self, argument: Option<&str>) {
+        if let Some(path) = argument {
+            self.restore_file = Some(path.to_string());
+        }
+    }
+
+    /// Sets `restore_height` to the specified block height, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn restore_height(&mut self, argument: Option<u64>) {
+        if let Some(height) = argument {
+            self.restore_height = height;
+        }
+    }
+
+    /// Sets `reveal` to the specified boolean value, overriding its previous state.
+    fn reveal(&mut self, argument: bool) {
+        self.reveal = argument;
+    }
+
+    /// Sets `save` to the specified wallet file, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn save(&mut self, argument: Option<&str>) {
+        if let Some(save) = argument {
+            self.save = Some(save.to_string());
+        }
+    }
+
+    /// Sets `signature` to the specified transaction proof, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn signature(&mut self, argument: Option<&str>) {
+        if let Some(signature) = argument {
+            self.signature = Some(signature.to_string());
+        }
+    }
+
     /// Sets `subaddress` to the specified subaddress indices, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
     fn subaddress(&mut self, arguments: Option<Values>) {
@@ -396,6 +1026,112 @@ impl MoneroOptions {
             self.format = MoneroFormat::Subaddress(index[0], index[1]);
         }
     }
+
+    /// Sets `threshold` to the specified threshold of a multisig wallet, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn threshold(&mut self, argument: Option<usize>) {
+        if let Some(threshold) = argument {
+            self.threshold = Some(threshold);
+        }
+    }
+
+    /// Sets `transaction_id` to the specified transaction id, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn transaction_id(&mut self, argument: Option<&str>) {
+        if let Some(transaction_id) = argument {
+            self.transaction_id = Some(transaction_id.to_string());
+        }
+    }
+
+    /// Sets `transaction_keys` to the specified transaction private keys, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn transaction_keys(&mut self, argument: Option<&str>) {
+        if let Some(transaction_keys) = argument {
+            self.transaction_keys = Some(transaction_keys.to_string());
+        }
+    }
+
+    /// Sets `transaction_public_keys` to the specified transaction public keys, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn transaction_public_keys(&mut self, argument: Option<&str>) {
+        if let Some(transaction_public_keys) = argument {
+            self.transaction_public_keys = Some(transaction_public_keys.to_string());
+        }
+    }
+}
+
+/// Returns the parameters of the given network, with the address prefix of each format. Monero wallets are derived
+/// from their seed instead of a BIP-44 path, so the network has no coin type or default derivation path.
+fn to_network_info<N: MoneroNetwork>() -> serde_json::Value {
+    let formats = [
+        MoneroFormat::Standard,
+        MoneroFormat::Integrated([0u8; 8]),
+        MoneroFormat::Subaddress(0, 1),
+    ]
+    .iter()
+    .map(|format| {
+        let name = match format {
+            MoneroFormat::Subaddress(_, _) => "subaddress".to_string(),
+            _ => format.to_string(),
+        };
+        (name, json!({ "address_prefix": N::to_address_prefix(format) }))
+    })
+    .collect::<serde_json::Map<String, serde_json::Value>>();
+
+    json!({
+        "network": N::NAME,
+        "coin_type": None::<u32>,
+        "default_derivation_path": None::<String>,
+        "formats": formats,
+    })
+}
+
+/// Returns the mismatches between the stored fields of the given wallet and the fields re-derived from it.
+/// The address is re-derived only if it is in the given standard format, as integrated addresses and
+/// subaddresses are not derived from the keys alone.
+fn to_mismatches<N: MoneroNetwork>(wallet: &serde_json::Value, format: &MoneroFormat) -> Vec<Mismatch> {
+    let with_address = |fields: &[&'static str]| {
+        let address = Some("address").filter(|_| *format == MoneroFormat::Standard);
+        fields.iter().copied().chain(address).collect::<Vec<&str>>()
+    };
+
+    let mut mismatches = Vec::new();
+    mismatches.extend(rederive(
+        wallet,
+        "mnemonic",
+        &with_address(&[
+            "private_spend_key",
+            "private_view_key",
+            "public_spend_key",
+            "public_view_key",
+        ]),
+        |mnemonic| MoneroWallet::from_mnemonic::<N, English>(mnemonic, &MoneroFormat::Standard),
+    ));
+    mismatches.extend(rederive(
+        wallet,
+        "private_spend_key",
+        &with_address(&["private_view_key", "public_spend_key", "public_view_key"]),
+        |private_spend_key| {
+            MoneroWallet::from_private_spend_key::<N, English>(private_spend_key, &MoneroFormat::Standard)
+        },
+    ));
+    mismatches.extend(rederive(
+        wallet,
+        "private_view_key",
+        &["public_view_key"],
+        |private_view_key| MoneroWallet::from_private_view_key::<N>(private_view_key, &MoneroFormat::Standard),
+    ));
+    if let Some(public_view_key) = wallet["public_view_key"].as_str() {
+        mismatches.extend(rederive(
+            wallet,
+            "public_spend_key",
+            &with_address(&[]),
+            |public_spend_key| {
+                MoneroWallet::from_public_key::<N>(public_spend_key, public_view_key, &MoneroFormat::Standard)
+            },
+        ));
+    }
+    mismatches
 }
 
 pub struct MoneroCLI;
@@ -405,15 +1141,85 @@ impl CLI for MoneroCLI {
 
     const NAME: NameType = "monero";
     const ABOUT: AboutType = "Generates a Monero wallet (include -h for more options)";
-    const FLAGS: &'static [FlagType] = &[flag::JSON];
+    const FLAGS: &'static [FlagType] = &[
+        flag::CLEAR_AFTER,
+        flag::COPY,
+        flag::ENCRYPT,
+        flag::ENCRYPT_TO,
+        flag::JSON,
+        flag::MANIFEST,
+        flag::MASK_SECRETS,
+        flag::REVEAL,
+        flag::SAVE,
+    ];
     const OPTIONS: &'static [OptionType] = &[
+        option::CONFIRM_BACKUP,
         option::COUNT,
         option::INTEGRATED_MONERO,
         option::LANGUAGE_MONERO,
         option::NETWORK_MONERO,
+        option::PAYMENT_ID_MONERO,
+        option::RESTORE_FILE_MONERO,
+        option::RESTORE_HEIGHT_MONERO,
         option::SUBADDRESS_MONERO,
     ];
-    const SUBCOMMANDS: &'static [SubCommandType] = &[subcommand::IMPORT_MONERO];
+    const SUBCOMMANDS: &'static [SubCommandType] = &[
+        subcommand::CHECK_PROOF_MONERO,
+        subcommand::IMPORT_MONERO,
+        subcommand::MULTISIG_MONERO,
+        subcommand::PAYMENT_ID_MONERO,
+        subcommand::PROVE_MONERO,
+        subcommand::VALIDATE_MONERO,
+    ];
+
+    /// Generate Monero wallets, which are not derived from a BIP-39 mnemonic
+    #[cfg_attr(tarpaulin, skip)]
+    fn generate(
+        count: usize,
+        mnemonic: Option<&str>,
+        password: Option<&str>,
+    ) -> Result<Vec<serde_json::Value>, CLIError> {
+        if mnemonic.is_some() || password.is_some() {
+            return Err(CLIError::UnsupportedMnemonic(Self::NAME.to_string()));
+        }
+        (0..count)
+            .map(|_| {
+                let wallet = MoneroWallet::new::<MoneroMainnet, English, _>(
+                    &mut StdRng::from_entropy(),
+                    &MoneroFormat::Standard,
+                )?;
+                Ok(serde_json::to_value(wallet)?)
+            })
+            .collect()
+    }
+
+    /// Describe the parameters of the Monero mainnet, stagenet, and testnet
+    #[cfg_attr(tarpaulin, skip)]
+    fn info() -> Result<serde_json::Value, CLIError> {
+        Ok(json!({
+            "coin": Self::NAME,
+            "networks": [
+                to_network_info::<MoneroMainnet>(),
+                to_network_info::<MoneroStagenet>(),
+                to_network_info::<MoneroTestnet>(),
+            ],
+        }))
+    }
+
+    /// Verify a Monero wallet, recognized by its mainnet, testnet, or stagenet address
+    fn verify(wallet: &serde_json::Value) -> Result<Option<Vec<Mismatch>>, CLIError> {
+        let address = wallet["address"].as_str().unwrap_or_default();
+        if let Ok(address) = MoneroAddress::<MoneroMainnet>::from_str(address) {
+            return Ok(Some(to_mismatches::<MoneroMainnet>(wallet, &address.format()?)));
+        }
+        if let Ok(address) = MoneroAddress::<MoneroTestnet>::from_str(address) {
+            return Ok(Some(to_mismatches::<MoneroTestnet>(wallet, &address.format()?)));
+        }
+        if let Ok(address) = MoneroAddress::<MoneroStagenet>::from_str(address) {
+            return Ok(Some(to_mismatches::<MoneroStagenet>(wallet, &address.format()?)));
+        }
+        Ok(None)
+    }
 
     /// Handle all CLI arguments and flags for Monero
     #[cfg_attr(tarpaulin, skip)]
@@ -422,38 +1228,124 @@ impl CLI for MoneroCLI {
         options.parse(
             arguments,
             &[
+                "confirm backup",
                 "count",
                 "format",
                 "integrated",
                 "json",
                 "language",
                 "network",
+                "payment id",
+                "restore file",
+                "restore height",
                 "subaddress",
             ],
         );
 
         match arguments.subcommand() {
+            ("check-proof", Some(arguments)) => {
+                options.subcommand = Some("check-proof".into());
+                options.parse(arguments, &["json"]);
+                options.parse(
+                    arguments,
+                    &["address", "message", "signature", "tx id", "tx public key"],
+                );
+            }
             ("import", Some(arguments)) => {
                 options.subcommand = Some("import".into());
                 options.parse(
                     arguments,
-                    &["format", "integrated", "json", "language", "network", "subaddress"],
+                    &[
+                        "format",
+                        "integrated",
+                        "json",
+                        "language",
+                        "network",
+                        "payment id",
+                        "subaddress",
+                    ],
                 );
                 options.parse(
                     arguments,
                     &[
                         "address",
+                        "column",
+                        "file",
                         "mnemonic",
                         "private spend",
                         "private view",
                         "public spend",
                         "public view",
+                        "restore file",
+                        "restore height",
                     ],
                 );
             }
+            ("multisig", Some(arguments)) => {
+                options.subcommand = Some("multisig".into());
+                options.parse(arguments, &["json", "network"]);
+                options.parse(arguments, &["extra info", "info", "private spend", "threshold"]);
+            }
+            ("payment-id", Some(arguments)) => {
+                options.subcommand = Some("payment-id".into());
+                options.parse(arguments, &["json"]);
+                options.parse(
+                    arguments,
+                    &[
+                        "address",
+                        "encrypted",
+                        "payment id",
+                        "private view",
+                        "tx key",
+                        "tx public key",
+                    ],
+                );
+            }
+            ("prove", Some(arguments)) => {
+                options.subcommand = Some("prove".into());
+                options.parse(arguments, &["json"]);
+                options.parse(
+                    arguments,
+                    &["address", "message", "private view", "tx id", "tx key", "tx public key"],
+                );
+            }
+            ("validate", Some(arguments)) => {
+                options.subcommand = Some("validate".into());
+                options.parse(arguments, &["json"]);
+                options.parse(arguments, &["address"]);
+            }
             _ => {}
         };
 
+        // The save flags are global, and thus parsed from the innermost subcommand arguments
+        let arguments = arguments.subcommand().1.unwrap_or(arguments);
+        options.parse(
+            arguments,
+            &[
+                "clear after",
+                "copy",
+                "encrypt",
+                "encrypt to",
+                "manifest",
+                "mask secrets",
+                "reveal",
+                "save",
+            ],
+        );
+        check_clipboard_field(&options.copy)?;
+        // The payment ID of the payment ID subcommand is encrypted, and otherwise is of an integrated address
+        if let (Some(payment_id), false) = (&options.payment_id, options.subcommand.as_deref() == Some("payment-id")) {
+            options.format = MoneroFormat::Integrated(MoneroPaymentId::from_str(payment_id)?.to_bytes());
+        }
+        if options.encrypt && options.save.is_none() {
+            return Err(CLIError::MissingSaveFile);
+        }
+        if options.encrypt && options.encrypt_to.is_some() {
+            return Err(CLIError::ConflictingEncryption);
+        }
+
+        options.sanitize_inputs()?;
+
         Ok(options)
     }
 
@@ -461,67 +1353,239 @@ impl CLI for MoneroCLI {
     #[cfg_attr(tarpaulin, skip)]
     fn print(options: Self::Options) -> Result<(), CLIError> {
         fn output<N: MoneroNetwork, W: MoneroWordlist>(options: MoneroOptions) -> Result<(), CLIError> {
-            let wallets =
-                match options.subcommand.as_ref().map(String::as_str) {
-                    Some("import") => {
-                        if let Some(mnemonic) = options.mnemonic {
-                            vec![
-                                MoneroWallet::from_mnemonic::<N, ChineseSimplified>(&mnemonic, &options.format)
-                                    .or(MoneroWallet::from_mnemonic::<N, Dutch>(&mnemonic, &options.format))
-                                    .or(MoneroWallet::from_mnemonic::<N, English>(&mnemonic, &options.format))
-                                    .or(MoneroWallet::from_mnemonic::<N, EnglishOld>(&mnemonic, &options.format))
-                                    .or(MoneroWallet::from_mnemonic::<N, Esperanto>(&mnemonic, &options.format))
-                                    .or(MoneroWallet::from_mnemonic::<N, French>(&mnemonic, &options.format))
-                                    .or(MoneroWallet::from_mnemonic::<N, German>(&mnemonic, &options.format))
-                                    .or(MoneroWallet::from_mnemonic::<N, Italian>(&mnemonic, &options.format))
-                                    .or(MoneroWallet::from_mnemonic::<N, Japanese>(&mnemonic, &options.format))
-                                    .or(MoneroWallet::from_mnemonic::<N, Lojban>(&mnemonic, &options.format))
-                                    .or(MoneroWallet::from_mnemonic::<N, Portuguese>(&mnemonic, &options.format))
-                                    .or(MoneroWallet::from_mnemonic::<N, Russian>(&mnemonic, &options.format))
-                                    .or(MoneroWallet::from_mnemonic::<N, Spanish>(&mnemonic, &options.format))?,
-                            ]
-                        } else if let Some(private_spend_key) = options.private_spend_key {
-                            vec![MoneroWallet::from_private_spend_key::<N, W>(
-                                &private_spend_key,
-                                &options.format,
-                            )?]
-                        } else if let Some(private_view_key) = options.private_view_key {
-                            vec![MoneroWallet::from_private_view_key::<N>(
-                                &private_view_key,
+            // Import each entry of a batch file as a mnemonic (in the specified language),
+            // private spend key, or address
+            if let (Some("import"), Some(path)) = (options.subcommand.as_deref(), &options.file) {
+                let entries = read_batch_file(path, options.column.as_deref())?;
+                return import_batch(
+                    "monero import",
+                    &entries,
+                    options.mask_secrets && !options.reveal,
+                    |entry| match entry.contains(' ') {
+                        true => MoneroWallet::from_mnemonic::<N, W>(entry, &options.format),
+                        false => MoneroWallet::from_private_spend_key::<N, W>(entry, &options.format)
+                            .or_else(|_| MoneroWallet::from_address::<MoneroMainnet>(entry))
+                            .or_else(|_| MoneroWallet::from_address::<MoneroTestnet>(entry))
+                            .or_else(|_| MoneroWallet::from_address::<MoneroStagenet>(entry)),
+                    },
+                );
+            }
+
+            let wallets = match options.subcommand.as_ref().map(String::as_str) {
+                Some("check-proof") => match (
+                    options.transaction_id.clone(),
+                    options.address.clone(),
+                    options.transaction_public_keys.clone(),
+                    options.signature.clone(),
+                ) {
+                    (Some(transaction_id), Some(address), Some(transaction_public_keys), Some(signature)) => {
+                        let message = options.message.clone().unwrap_or_default();
+                        let (id, keys, signature) = (&transaction_id, &transaction_public_keys, &signature);
+
+                        vec![MoneroWallet::from_transaction_proof::<MoneroMainnet>(
+                            id, &address, keys, &message, signature,
+                        )
+                        .or(MoneroWallet::from_transaction_proof::<MoneroTestnet>(
+                            id, &address, keys, &message, signature,
+                        ))
+                        .or(MoneroWallet::from_transaction_proof::<MoneroStagenet>(
+                            id, &address, keys, &message, signature,
+                        ))?]
+                    }
+                    _ => vec![],
+                },
+                Some("import") => {
+                    if let Some(mnemonic) = options.mnemonic {
+                        vec![
+                            MoneroWallet::from_mnemonic::<N, ChineseSimplified>(&mnemonic, &options.format)
+                                .or(MoneroWallet::from_mnemonic::<N, Dutch>(&mnemonic, &options.format))
+                                .or(MoneroWallet::from_mnemonic::<N, English>(&mnemonic, &options.format))
+                                .or(MoneroWallet::from_mnemonic::<N, EnglishOld>(&mnemonic, &options.format))
+                                .or(MoneroWallet::from_mnemonic::<N, Esperanto>(&mnemonic, &options.format))
+                                .or(MoneroWallet::from_mnemonic::<N, French>(&mnemonic, &options.format))
+                                .or(MoneroWallet::from_mnemonic::<N, German>(&mnemonic, &options.format))
+                                .or(MoneroWallet::from_mnemonic::<N, Italian>(&mnemonic, &options.format))
+                                .or(MoneroWallet::from_mnemonic::<N, Japanese>(&mnemonic, &options.format))
+                                .or(MoneroWallet::from_mnemonic::<N, Lojban>(&mnemonic, &options.format))
+                                .or(MoneroWallet::from_mnemonic::<N, Portuguese>(&mnemonic, &options.format))
+                                .or(MoneroWallet::from_mnemonic::<N, Russian>(&mnemonic, &options.format))
+                                .or(MoneroWallet::from_mnemonic::<N, Spanish>(&mnemonic, &options.format))?,
+                        ]
+                    } else if let Some(private_spend_key) = options.private_spend_key {
+                        vec![MoneroWallet::from_private_spend_key::<N, W>(
+                            &private_spend_key,
+                            &options.format,
+                        )?]
+                    } else if let Some(private_view_key) = options.private_view_key {
+                        vec![MoneroWallet::from_private_view_key::<N>(
+                            &private_view_key,
+                            &options.format,
+                        )?]
+                    } else if let Some(public_spend_key) = options.public_spend_key {
+                        if let Some(public_view_key) = options.public_view_key {
+                            vec![MoneroWallet::from_public_key::<N>(
+                                &public_spend_key,
+                                &public_view_key,
                                 &options.format,
                             )?]
-                        } else if let Some(public_spend_key) = options.public_spend_key {
-                            if let Some(public_view_key) = options.public_view_key {
-                                vec![MoneroWallet::from_public_key::<N>(
-                                    &public_spend_key,
-                                    &public_view_key,
-                                    &options.format,
-                                )?]
-                            } else {
-                                vec![]
-                            }
-                        } else if let Some(address) = options.address {
-                            vec![MoneroWallet::from_address::<MoneroMainnet>(&address)
-                                .or(MoneroWallet::from_address::<MoneroTestnet>(&address))?]
                         } else {
                             vec![]
                         }
+                    } else if let Some(address) = options.address {
+                        vec![
+                            MoneroWallet::from_address::<MoneroMainnet>(&address).or(MoneroWallet::from_address::<
+                                MoneroTestnet,
+                            >(
+                                &address
+                            ))?,
+                        ]
+                    } else {
+                        vec![]
                     }
-                    _ => (0..options.count)
-                        .flat_map(|_| {
-                            match MoneroWallet::new::<N, W, _>(&mut StdRng::from_entropy(), &options.format) {
-                                Ok(wallet) => vec![wallet],
-                                _ => vec![],
-                            }
-                        })
-                        .collect(),
-                };
+                }
+                Some("payment-id") => match (
+                    options.address.clone(),
+                    options.transaction_keys.clone(),
+                    options.encrypted_payment_id.clone(),
+                    options.private_view_key.clone(),
+                    options.transaction_public_keys.clone(),
+                ) {
+                    (Some(address), Some(transaction_key), _, _, _) => {
+                        let payment_id = options.payment_id.as_deref();
+                        vec![MoneroWallet::to_encrypted_payment_id::<MoneroMainnet>(
+                            &address,
+                            payment_id,
+                            &transaction_key,
+                        )
+                        .or(MoneroWallet::to_encrypted_payment_id::<MoneroTestnet>(
+                            &address,
+                            payment_id,
+                            &transaction_key,
+                        ))
+                        .or(MoneroWallet::to_encrypted_payment_id::<MoneroStagenet>(
+                            &address,
+                            payment_id,
+                            &transaction_key,
+                        ))?]
+                    }
+                    (None, None, Some(encrypted_payment_id), Some(private_view_key), Some(transaction_public_key)) => {
+                        vec![MoneroWallet::from_encrypted_payment_id(
+                            &encrypted_payment_id,
+                            &private_view_key,
+                            &transaction_public_key,
+                        )?]
+                    }
+                    _ => vec![],
+                },
+                Some("multisig") => match options.private_spend_key.as_deref() {
+                    Some(private_spend_key) => vec![MoneroWallet::to_multisig::<N>(
+                        private_spend_key,
+                        options.threshold,
+                        options.infos.as_deref(),
+                        options.extra_infos.as_deref(),
+                    )?],
+                    None => vec![],
+                },
+                Some("prove") => match (options.transaction_id.clone(), options.address.clone()) {
+                    (Some(transaction_id), Some(address)) => {
+                        let message = options.message.clone().unwrap_or_default();
+                        let transaction_keys = options.transaction_keys.as_deref();
+                        let private_view_key = options.private_view_key.as_deref();
+                        let transaction_public_keys = options.transaction_public_keys.as_deref();
+                        if transaction_keys.is_none()
+                            && (private_view_key.is_none() || transaction_public_keys.is_none())
+                        {
+                            return Err(CLIError::MissingTransactionProofKey);
+                        }
 
-            match options.json {
-                true => println!("{}\n", serde_json::to_string_pretty(&wallets)?),
-                false => wallets.iter().for_each(|wallet| println!("{}\n", wallet)),
+                        vec![MoneroWallet::to_transaction_proof::<MoneroMainnet>(
+                            &transaction_id,
+                            &address,
+                            transaction_keys,
+                            private_view_key,
+                            transaction_public_keys,
+                            &message,
+                        )
+                        .or(MoneroWallet::to_transaction_proof::<MoneroTestnet>(
+                            &transaction_id,
+                            &address,
+                            transaction_keys,
+                            private_view_key,
+                            transaction_public_keys,
+                            &message,
+                        ))
+                        .or(MoneroWallet::to_transaction_proof::<MoneroStagenet>(
+                            &transaction_id,
+                            &address,
+                            transaction_keys,
+                            private_view_key,
+                            transaction_public_keys,
+                            &message,
+                        ))?]
+                    }
+                    _ => vec![],
+                },
+                Some("validate") => match options.address.as_deref() {
+                    Some(address) => vec![MoneroWallet::validate(address)?],
+                    None => vec![],
+                },
+                _ => (0..options.count)
+                    .flat_map(
+                        |_| match MoneroWallet::new::<N, W, _>(&mut StdRng::from_entropy(), &options.format) {
+                            Ok(wallet) => vec![wallet],
+                            _ => vec![],
+                        },
+                    )
+                    .collect(),
+            };
+
+            if options.confirm_backup {
+                confirm_backups(&wallets)?;
+            }
+
+            match &options.encrypt_to {
+                Some(recipients) => {
+                    let recipients = Recipient::from_list(recipients)?;
+                    print!(
+                        "{}",
+                        encrypt_to(serde_json::to_string_pretty(&wallets)?.as_bytes(), &recipients)?
+                    );
+                    if let Some(path) = &options.save {
+                        let file = WalletFile::new(&wallets, "monero")?;
+                        std::fs::write(
+                            path,
+                            encrypt_to(serde_json::to_string_pretty(&file)?.as_bytes(), &recipients)?,
+                        )?;
+                    }
+                }
+                None => {
+                    print_wallets(&wallets, options.json, options.mask_secrets && !options.reveal)?;
+                    if let Some(path) = &options.save {
+                        WalletFile::save(&wallets, "monero", path, options.encrypt)?;
+                    }
+                }
             };
 
+            if let Some(path) = &options.manifest {
+                Manifest::new(&wallets, "monero")?.write(path)?;
+            }
+
+            if let Some(path) = &options.restore_file {
+                let wallet = match wallets.as_slice() {
+                    [wallet] => wallet,
+                    _ => return Err(CLIError::InvalidRestoreFileCount(wallets.len())),
+                };
+                let parameters = wallet.to_restore_parameters::<N>(path, options.restore_height)?;
+                std::fs::write(path, serde_json::to_string_pretty(&parameters)?)?;
+            }
+
+            if let Some(field) = &options.copy {
+                copy_wallets(&wallets, field, options.clear_after)?;
+            }
+
+            let operation = format!("monero {}", options.subcommand.as_deref().unwrap_or("generate"));
+            AuditLog::log_wallets(&operation, &wallets)?;
+
             Ok(())
         }
 
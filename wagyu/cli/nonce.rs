@@ -0,0 +1,89 @@
+//! # Nonce
+//!
+//! A versioned file of the last used nonce of each address, for creating and signing
+//! several transactions offline without reusing a nonce. The nonces are stored by network,
+//! then by address, and a missing file is treated as empty.
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "nonces": {
+//!     "mainnet": {
+//!       "0x...": 7
+//!     }
+//!   }
+//! }
+//! ```
+
+use crate::cli::CLIError;
+
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::model::no_std::{BTreeMap, String, ToString};
+
+/// The current version of the nonce file schema
+pub const NONCE_FILE_VERSION: u32 = 1;
+
+/// Represents a nonce file of the last used nonce of each address
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct NonceFile {
+    /// The version of the file schema
+    pub version: u32,
+    /// The last used nonce of each address, by network
+    pub nonces: BTreeMap<String, BTreeMap<String, u64>>,
+}
+
+impl Default for NonceFile {
+    fn default() -> Self {
+        Self {
+            version: NONCE_FILE_VERSION,
+            nonces: BTreeMap::new(),
+        }
+    }
+}
+
+impl NonceFile {
+    /// Returns the nonce file at the given path, or an empty nonce file if none exists.
+    pub fn read(path: &str) -> Result<Self, CLIError> {
+        if !Path::new(path).exists() {
+            return Ok(Self::default());
+        }
+
+        let file: Self = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+        if file.version != NONCE_FILE_VERSION {
+            return Err(CLIError::UnsupportedFileVersion(file.version));
+        }
+        Ok(file)
+    }
+
+    /// Writes the nonce file to the given path.
+    pub fn write(&self, path: &str) -> Result<(), CLIError> {
+        Ok(std::fs::write(path, serde_json::to_string_pretty(self)?)?)
+    }
+
+    /// Returns the next unused nonce of the given address on the given network,
+    /// or `None` if the address has no recorded nonce.
+    pub fn next(&self, network: &str, address: &str) -> Option<u64> {
+        self.nonces
+            .get(network)
+            .and_then(|addresses| addresses.get(address))
+            .map(|nonce| nonce + 1)
+    }
+
+    /// Records the given nonce as the last used nonce of the given address on the given network.
+    /// Returns an error if the nonce is below the next unused nonce of the address.
+    pub fn record(&mut self, network: &str, address: &str, nonce: u64) -> Result<(), CLIError> {
+        if let Some(next) = self.next(network, address) {
+            if nonce < next {
+                return Err(CLIError::ReusedNonce(address.to_string(), nonce, next));
+            }
+        }
+
+        self.nonces
+            .entry(network.to_string())
+            .or_default()
+            .insert(address.to_string(), nonce);
+        Ok(())
+    }
+}
@@ -0,0 +1,814 @@
+//! # Nostr
+//!
+//! Generates and imports Nostr keys, the secp256k1 keys of the Nostr protocol, identified by their
+//! x-only public keys (https://github.com/nostr-protocol/nips/blob/master/01.md). Keys are derived
+//! from a BIP-39 mnemonic at `m/44'/1237'/<account>'/0/0` (NIP-06), and printed with their
+//! `nsec` and `npub` Bech32 encodings, as are event IDs with their `note` encodings (NIP-19).
+
+use crate::bitcoin::{BitcoinPublicKey, Mainnet as BitcoinMainnet};
+use crate::cli::{
+    check_clipboard_field, confirm_backups, copy_wallets, decode, encode, encrypt_to, flag, mask, object, option,
+    print_wallets, property, rederive, sanitize_option, subcommand, types::*, AuditLog, CLIError, InputKind,
+    MaskSecrets, Mismatch, Recipient, Schema, WalletFile, CLI, CLIPBOARD_CLEAR_AFTER,
+};
+use crate::ethereum::{
+    wordlist::*, EthereumDerivationPath, EthereumMnemonic, EthereumPrivateKey, Mainnet as EthereumMainnet,
+};
+use crate::model::{ExtendedPrivateKey, Mnemonic, MnemonicCount, MnemonicExtended, PrivateKey};
+
+use clap::ArgMatches;
+use colored::*;
+use core::{fmt, fmt::Display, str::FromStr};
+use rand::{rngs::StdRng, Rng};
+use rand_core::SeedableRng;
+use serde::Serialize;
+
+use crate::model::no_std::{format, vec, String, ToOwned, ToString, Vec};
+
+/// The BIP-44 coin type of Nostr keys (NIP-06)
+pub const NOSTR_COIN_TYPE: u32 = 1237;
+
+/// Represents a generic wallet to output
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct NostrWallet {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub password: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mnemonic: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub private_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub nsec: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub public_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub npub: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub event_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note: Option<String>,
+}
+
+impl MaskSecrets for NostrWallet {
+    fn mask_secrets(&mut self) {
+        mask(&mut self.password);
+        mask(&mut self.mnemonic);
+        mask(&mut self.private_key);
+        mask(&mut self.nsec);
+    }
+}
+
+impl Schema for NostrWallet {
+    fn schema() -> serde_json::Value {
+        object(
+            vec![
+                ("path", property("string", "The NIP-06 derivation path of the key")),
+                ("password", property("string", "The BIP-39 password of the mnemonic")),
+                ("mnemonic", property("string", "The BIP-39 mnemonic phrase of the key")),
+                ("private_key", property("string", "The private key in hex")),
+                ("nsec", property("string", "The Bech32 encoding of the private key")),
+                ("public_key", property("string", "The x-only public key in hex")),
+                ("npub", property("string", "The Bech32 encoding of the public key")),
+                ("event_id", property("string", "The event ID in hex")),
+                ("note", property("string", "The Bech32 encoding of the event ID")),
+            ],
+            &[],
+        )
+    }
+}
+
+impl NostrWallet {
+    pub fn new<R: Rng>(rng: &mut R) -> Result<Self, CLIError> {
+        Self::from_ethereum_private_key(&EthereumPrivateKey::new(rng)?)
+    }
+
+    pub fn new_hd<W: EthereumWordlist, R: Rng>(
+        rng: &mut R,
+        word_count: u8,
+        password: Option<&str>,
+        account: u32,
+    ) -> Result<Self, CLIError> {
+        let mnemonic = EthereumMnemonic::<EthereumMainnet, W>::new_with_count(rng, word_count)?;
+        Self::from_mnemonic::<W>(&mnemonic.to_string(), password, account)
+    }
+
+    /// Returns the key of the given NIP-06 account of the given mnemonic.
+    pub fn from_mnemonic<W: EthereumWordlist>(
+        mnemonic: &str,
+        password: Option<&str>,
+        account: u32,
+    ) -> Result<Self, CLIError> {
+        let path = to_nip06_path(account);
+        let mnemonic = EthereumMnemonic::<EthereumMainnet, W>::from_phrase(mnemonic)?;
+        let master_extended_private_key = mnemonic.to_extended_private_key(password)?;
+        let derivation_path = EthereumDerivationPath::from_str(&path)?;
+        let extended_private_key = master_extended_private_key.derive(&derivation_path)?;
+        Ok(Self {
+            path: Some(path),
+            password: password.map(String::from),
+            mnemonic: Some(mnemonic.to_string()),
+            ..Self::from_ethereum_private_key(&extended_private_key.to_private_key())?
+        })
+    }
+
+    /// Returns the key of the given private key, in hex or nsec.
+    pub fn from_private_key(private_key: &str) -> Result<Self, CLIError> {
+        let private_key = hex::encode(from_nip19("nsec", private_key)?);
+        Self::from_ethereum_private_key(&EthereumPrivateKey::from_str(&private_key)?)
+    }
+
+    /// Returns the partial key of the given x-only public key, in hex or npub.
+    pub fn from_public_key(public_key: &str) -> Result<Self, CLIError> {
+        let public_key = from_nip19("npub", public_key)?;
+
+        // An x-only public key is valid if it is the x-coordinate of a point of the curve
+        BitcoinPublicKey::<BitcoinMainnet>::from_str(&format!("02{}", hex::encode(public_key)))?;
+
+        Ok(Self {
+            public_key: Some(hex::encode(public_key)),
+            npub: Some(encode("bech32", &public_key, Some("npub"))?),
+            ..Default::default()
+        })
+    }
+
+    /// Returns the event ID and note of the given event ID, in hex or note.
+    pub fn from_event_id(event_id: &str) -> Result<Self, CLIError> {
+        let event_id = from_nip19("note", event_id)?;
+        Ok(Self {
+            event_id: Some(hex::encode(event_id)),
+            note: Some(encode("bech32", &event_id, Some("note"))?),
+            ..Default::default()
+        })
+    }
+
+    fn from_ethereum_private_key(private_key: &EthereumPrivateKey) -> Result<Self, CLIError> {
+        let public_key = private_key
+            .to_public_key()
+            .to_secp256k1_public_key()
+            .serialize_compressed();
+        let (private_key, public_key) = (private_key.to_secp256k1_secret_key().serialize(), &public_key[1..]);
+        Ok(Self {
+            private_key: Some(hex::encode(private_key)),
+            nsec: Some(encode("bech32", &private_key, Some("nsec"))?),
+            public_key: Some(hex::encode(public_key)),
+            npub: Some(encode("bech32", public_key, Some("npub"))?),
+            ..Default::default()
+        })
+    }
+}
+
+/// Returns the derivation path of the key of the given NIP-06 account.
+fn to_nip06_path(account: u32) -> String {
+    format!("m/44'/{}'/{}'/0/0", NOSTR_COIN_TYPE, account)
+}
+
+/// Returns the 32 bytes of the given value, in hex or in the Bech32 encoding of the given prefix (NIP-19).
+fn from_nip19(prefix: &str, value: &str) -> Result<[u8; 32], CLIError> {
+    let bytes = match value.to_lowercase().starts_with(&format!("{}1", prefix)) {
+        true => match decode("bech32", value)? {
+            decoded if decoded.prefix.as_deref() == Some(prefix) => hex::decode(decoded.data.unwrap_or_default())?,
+            _ => vec![],
+        },
+        false => hex::decode(value).unwrap_or_default(),
+    };
+
+    if bytes.len() != 32 {
+        return Err(CLIError::InvalidEncoding(format!(
+            "invalid {}: {} (expected 32 bytes in hex or {})",
+            prefix, value, prefix
+        )));
+    }
+    let mut bytes32 = [0u8; 32];
+    bytes32.copy_from_slice(&bytes);
+    Ok(bytes32)
+}
+
+#[cfg_attr(tarpaulin, skip)]
+impl Display for NostrWallet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let output = [
+            match &self.path {
+                Some(path) => format!("      {}                 {}\n", "Path".cyan().bold(), path),
+                _ => "".to_owned(),
+            },
+            match &self.password {
+                Some(password) => format!("      {}             {}\n", "Password".cyan().bold(), password),
+                _ => "".to_owned(),
+            },
+            match &self.mnemonic {
+                Some(mnemonic) => format!("      {}             {}\n", "Mnemonic".cyan().bold(), mnemonic),
+                _ => "".to_owned(),
+            },
+            match &self.private_key {
+                Some(private_key) => format!("      {}          {}\n", "Private Key".cyan().bold(), private_key),
+                _ => "".to_owned(),
+            },
+            match &self.nsec {
+                Some(nsec) => format!("      {}                 {}\n", "Nsec".cyan().bold(), nsec),
+                _ => "".to_owned(),
+            },
+            match &self.public_key {
+                Some(public_key) => format!("      {}           {}\n", "Public Key".cyan().bold(), public_key),
+                _ => "".to_owned(),
+            },
+            match &self.npub {
+                Some(npub) => format!("      {}                 {}\n", "Npub".cyan().bold(), npub),
+                _ => "".to_owned(),
+            },
+            match &self.event_id {
+                Some(event_id) => format!("      {}             {}\n", "Event ID".cyan().bold(), event_id),
+                _ => "".to_owned(),
+            },
+            match &self.note {
+                Some(note) => format!("      {}                 {}\n", "Note".cyan().bold(), note),
+                _ => "".to_owned(),
+            },
+        ]
+        .concat();
+
+        // Removes final new line character
+        let output = output[..output.len() - 1].to_owned();
+        write!(f, "\n{}", output)
+    }
+}
+
+/// Represents options for a Nostr key
+#[derive(Serialize, Clone, Debug)]
+pub struct NostrOptions {
+    // Standard command
+    clear_after: u64,
+    count: usize,
+    copy: Option<String>,
+    encrypt: bool,
+    encrypt_to: Option<String>,
+    json: bool,
+    mask_secrets: bool,
+    reveal: bool,
+    save: Option<String>,
+    subcommand: Option<String>,
+    // HD subcommand
+    account: u32,
+    confirm_backup: bool,
+    language: String,
+    mnemonic: Option<String>,
+    password: Option<String>,
+    word_count: u8,
+    // Import subcommand
+    private: Option<String>,
+    public: Option<String>,
+    // Note subcommand
+    event_id: Option<String>,
+}
+
+impl Default for NostrOptions {
+    fn default() -> Self {
+        Self {
+            // Standard command
+            clear_after: CLIPBOARD_CLEAR_AFTER,
+            count: 1,
+            copy: None,
+            encrypt: false,
+            encrypt_to: None,
+            json: false,
+            mask_secrets: false,
+            reveal: false,
+            save: None,
+            subcommand: None,
+            // HD subcommand
+            account: 0,
+            confirm_backup: false,
+            language: "english".into(),
+            mnemonic: None,
+            password: None,
+            word_count: 24,
+            // Import subcommand
+            private: None,
+            public: None,
+            // Note subcommand
+            event_id: None,
+        }
+    }
+}
+
+impl NostrOptions {
+    fn parse(&mut self, arguments: &ArgMatches, options: &[&str]) {
+        options.iter().for_each(|option| match *option {
+            "account" => self.account(clap::value_t!(arguments.value_of(*option), u32).ok()),
+            "clear after" => self.clear_after(clap::value_t!(arguments.value_of(*option), u64).ok()),
+            "confirm backup" => self.confirm_backup(arguments.is_present(option)),
+            "copy" => self.copy(arguments.value_of(option)),
+            "count" => self.count(clap::value_t!(arguments.value_of(*option), usize).ok()),
+            "encrypt" => self.encrypt(arguments.is_present(option)),
+            "encrypt to" => self.encrypt_to(arguments.value_of(option)),
+            "id" => self.event_id(arguments.value_of(option)),
+            "json" => self.json(arguments.is_present(option)),
+            "language" => self.language(arguments.value_of(option)),
+            "mask secrets" => self.mask_secrets(arguments.is_present(option)),
+            "mnemonic" => self.mnemonic(arguments.value_of(option)),
+            "password" => self.password(arguments.value_of(option)),
+            "private" => self.private(arguments.value_of(option)),
+            "public" => self.public(arguments.value_of(option)),
+            "reveal" => self.reveal(arguments.is_present(option)),
+            "save" => self.save(arguments.value_of(option)),
+            "word count" => self.word_count(clap::value_t!(arguments.value_of(*option), u8).ok()),
+            _ => (),
+        });
+    }
+
+    /// Sanitizes the key material of the options, with `--strict` rejecting key material in a non-canonical form.
+    fn sanitize_inputs(&mut self) -> Result<(), CLIError> {
+        sanitize_option("mnemonic", &mut self.mnemonic, InputKind::Mnemonic)?;
+        sanitize_option("private key", &mut self.private, InputKind::Text)?;
+        sanitize_option("public key", &mut self.public, InputKind::Text)
+    }
+
+    /// Sets `account` to the specified NIP-06 account number, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn account(&mut self, argument: Option<u32>) {
+        if let Some(account) = argument {
+            self.account = account;
+        }
+    }
+
+    /// Sets `clear_after` to the specified number of seconds, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn clear_after(&mut self, argument: Option<u64>) {
+        if let Some(seconds) = argument {
+            self.clear_after = seconds;
+        }
+    }
+
+    /// Sets `confirm_backup` to the specified boolean value, overriding its previous state.
+    fn confirm_backup(&mut self, argument: bool) {
+        self.confirm_backup = argument;
+    }
+
+    /// Sets `copy` to the specified wallet field, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn copy(&mut self, argument: Option<&str>) {
+        if let Some(field) = argument {
+            self.copy = Some(field.to_string());
+        }
+    }
+
+    /// Sets `count` to the specified count, overriding its previous state.
+    fn count(&mut self, argument: Option<usize>) {
+        if let Some(count) = argument {
+            self.count = count;
+        }
+    }
+
+    /// Sets `encrypt` to the specified boolean value, overriding its previous state.
+    fn encrypt(&mut self, argument: bool) {
+        if argument {
+            self.encrypt = true;
+        }
+    }
+
+    /// Sets `encrypt_to` to the specified recipients, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn encrypt_to(&mut self, argument: Option<&str>) {
+        if let Some(recipients) = argument {
+            self.encrypt_to = Some(recipients.to_string());
+        }
+    }
+
+    /// Sets `event_id` to the specified event ID or note, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn event_id(&mut self, argument: Option<&str>) {
+        if let Some(event_id) = argument {
+            self.event_id = Some(event_id.to_string());
+        }
+    }
+
+    /// Sets `json` to the specified boolean value, overriding its previous state.
+    fn json(&mut self, argument: bool) {
+        self.json = argument;
+    }
+
+    /// Sets `language` to the specified language, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn language(&mut self, argument: Option<&str>) {
+        match argument {
+            Some("chinese_simplified") => self.language = "chinese_simplified".into(),
+            Some("chinese_traditional") => self.language = "chinese_traditional".into(),
+            Some("english") => self.language = "english".into(),
+            Some("french") => self.language = "french".into(),
+            Some("italian") => self.language = "italian".into(),
+            Some("japanese") => self.language = "japanese".into(),
+            Some("korean") => self.language = "korean".into(),
+            Some("spanish") => self.language = "spanish".into(),
+            _ => (),
+        };
+    }
+
+    /// Sets `mask_secrets` to the specified boolean value, overriding its previous state.
+    fn mask_secrets(&mut self, argument: bool) {
+        self.mask_secrets = argument;
+    }
+
+    /// Sets `mnemonic` to the specified mnemonic, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn mnemonic(&mut self, argument: Option<&str>) {
+        if let Some(mnemonic) = argument {
+            self.mnemonic = Some(mnemonic.to_string());
+        }
+    }
+
+    /// Sets `password` to the specified password, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn password(&mut self, argument: Option<&str>) {
+        if let Some(password) = argument {
+            self.password = Some(password.to_string());
+        }
+    }
+
+    /// Sets `private` to the specified private key, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn private(&mut self, argument: Option<&str>) {
+        if let Some(private_key) = argument {
+            self.private = Some(private_key.to_string());
+        }
+    }
+
+    /// Sets `public` to the specified public key, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn public(&mut self, argument: Option<&str>) {
+        if let Some(public_key) = argument {
+            self.public = Some(public_key.to_string());
+        }
+    }
+
+    /// Sets `reveal` to the specified boolean value, overriding its previous state.
+    fn reveal(&mut self, argument: bool) {
+        self.reveal = argument;
+    }
+
+    /// Sets `save` to the specified wallet file, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn save(&mut self, argument: Option<&str>) {
+        if let Some(save) = argument {
+            self.save = Some(save.to_string());
+        }
+    }
+
+    /// Sets `word_count` to the specified word count, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn word_count(&mut self, argument: Option<u8>) {
+        if let Some(word_count) = argument {
+            self.word_count = word_count;
+        }
+    }
+}
+
+pub struct NostrCLI;
+
+impl CLI for NostrCLI {
+    type Options = NostrOptions;
+
+    const NAME: NameType = "nostr";
+    const ABOUT: AboutType = "Generates a Nostr key (include -h for more options)";
+    const FLAGS: &'static [FlagType] = &[
+        flag::CLEAR_AFTER,
+        flag::COPY,
+        flag::ENCRYPT,
+        flag::ENCRYPT_TO,
+        flag::JSON,
+        flag::MASK_SECRETS,
+        flag::REVEAL,
+        flag::SAVE,
+    ];
+    const OPTIONS: &'static [OptionType] = &[option::COUNT];
+    const SUBCOMMANDS: &'static [SubCommandType] =
+        &[subcommand::HD_NOSTR, subcommand::IMPORT_NOSTR, subcommand::NOTE_NOSTR];
+
+    /// Generate Nostr keys, derived from the NIP-06 accounts of a BIP-39 mnemonic if specified
+    #[cfg_attr(tarpaulin, skip)]
+    fn generate(
+        count: usize,
+        mnemonic: Option<&str>,
+        password: Option<&str>,
+    ) -> Result<Vec<serde_json::Value>, CLIError> {
+        (0..count)
+            .map(|account| {
+                let wallet = match mnemonic {
+                    Some(mnemonic) => NostrWallet::from_mnemonic::<English>(mnemonic, password, account as u32)?,
+                    None => NostrWallet::new(&mut StdRng::from_entropy())?,
+                };
+                Ok(serde_json::to_value(wallet)?)
+            })
+            .collect()
+    }
+
+    /// Describe the parameters of Nostr keys, which have no networks, with the human-readable part of the Bech32
+    /// encoding of each key (NIP-19)
+    #[cfg_attr(tarpaulin, skip)]
+    fn info() -> Result<serde_json::Value, CLIError> {
+        Ok(serde_json::json!({
+            "coin": Self::NAME,
+            "coin_type": NOSTR_COIN_TYPE,
+            "default_derivation_path": to_nip06_path(0),
+            "formats": {
+                "private_key": { "address_prefix": "nsec" },
+                "public_key": { "address_prefix": "npub" },
+                "event_id": { "address_prefix": "note" },
+            },
+        }))
+    }
+
+    /// Verify a Nostr key, recognized by its npub
+    fn verify(wallet: &serde_json::Value) -> Result<Option<Vec<Mismatch>>, CLIError> {
+        if from_nip19("npub", wallet["npub"].as_str().unwrap_or_default()).is_err() {
+            return Ok(None);
+        }
+
+        let mut mismatches = Vec::new();
+        let account = wallet["path"]
+            .as_str()
+            .and_then(|path| path.split('/').nth(3))
+            .and_then(|account| account.trim_end_matches('\'').parse::<u32>().ok());
+        if let Some(account) = account {
+            let password = wallet["password"].as_str();
+            mismatches.extend(rederive(
+                wallet,
+                "mnemonic",
+                &["private_key", "nsec", "public_key", "npub"],
+                |mnemonic| NostrWallet::from_mnemonic::<English>(mnemonic, password, account),
+            ));
+        }
+        mismatches.extend(rederive(
+            wallet,
+            "private_key",
+            &["nsec", "public_key", "npub"],
+            NostrWallet::from_private_key,
+        ));
+        mismatches.extend(rederive(wallet, "public_key", &["npub"], NostrWallet::from_public_key));
+        Ok(Some(mismatches))
+    }
+
+    /// Handle all CLI arguments and flags for Nostr
+    #[cfg_attr(tarpaulin, skip)]
+    fn parse(arguments: &ArgMatches) -> Result<Self::Options, CLIError> {
+        let mut options = NostrOptions::default();
+        options.parse(arguments, &["count", "json"]);
+
+        match arguments.subcommand() {
+            ("hd", Some(arguments)) => {
+                options.subcommand = Some("hd".into());
+                options.parse(arguments, &["count", "json"]);
+                options.parse(
+                    arguments,
+                    &[
+                        "account",
+                        "confirm backup",
+                        "language",
+                        "mnemonic",
+                        "password",
+                        "word count",
+                    ],
+                );
+            }
+            ("import", Some(arguments)) => {
+                options.subcommand = Some("import".into());
+                options.parse(arguments, &["json"]);
+                options.parse(arguments, &["private", "public"]);
+            }
+            ("note", Some(arguments)) => {
+                options.subcommand = Some("note".into());
+                options.parse(arguments, &["json"]);
+                options.parse(arguments, &["id"]);
+            }
+            _ => {}
+        };
+
+        // The save flags are global, and thus parsed from the innermost subcommand arguments
+        let arguments = arguments.subcommand().1.unwrap_or(arguments);
+        options.parse(
+            arguments,
+            &[
+                "clear after",
+                "copy",
+                "encrypt",
+                "encrypt to",
+                "mask secrets",
+                "reveal",
+                "save",
+            ],
+        );
+        check_clipboard_field(&options.copy)?;
+        // A note has no key, and a public key has no private key, to copy after the wallets are printed
+        if let Some(field) = &options.copy {
+            let copyable = match options.subcommand.as_deref() {
+                Some("note") => false,
+                Some("import") => options.private.is_some() || field == "address",
+                _ => true,
+            };
+            if !copyable {
+                return Err(CLIError::MissingClipboardField(field.clone(), 1));
+            }
+        }
+        if options.encrypt && options.save.is_none() {
+            return Err(CLIError::MissingSaveFile);
+        }
+        if options.encrypt && options.encrypt_to.is_some() {
+            return Err(CLIError::ConflictingEncryption);
+        }
+
+        options.sanitize_inputs()?;
+
+        Ok(options)
+    }
+
+    /// Generate the Nostr key and print the relevant fields
+    #[cfg_attr(tarpaulin, skip)]
+    fn print(options: Self::Options) -> Result<(), CLIError> {
+        fn output<W: EthereumWordlist>(options: NostrOptions) -> Result<(), CLIError> {
+            let wallets = match options.subcommand.as_deref() {
+                Some("hd") => {
+                    let password = options.password.as_deref();
+                    match &options.mnemonic {
+                        // The keys of a mnemonic are derived from its consecutive accounts
+                        Some(mnemonic) => (options.account..)
+                            .take(options.count)
+                            .map(|account| NostrWallet::from_mnemonic::<W>(mnemonic, password, account))
+                            .collect::<Result<Vec<_>, _>>()?,
+                        None => (0..options.count)
+                            .map(|_| {
+                                NostrWallet::new_hd::<W, _>(
+                                    &mut StdRng::from_entropy(),
+                                    options.word_count,
+                                    password,
+                                    options.account,
+                                )
+                            })
+                            .collect::<Result<Vec<_>, _>>()?,
+                    }
+                }
+                Some("import") => match (&options.private, &options.public) {
+                    (Some(private_key), _) => vec![NostrWallet::from_private_key(private_key)?],
+                    (None, Some(public_key)) => vec![NostrWallet::from_public_key(public_key)?],
+                    (None, None) => vec![],
+                },
+                Some("note") => match &options.event_id {
+                    Some(event_id) => vec![NostrWallet::from_event_id(event_id)?],
+                    None => vec![],
+                },
+                _ => (0..options.count)
+                    .map(|_| NostrWallet::new(&mut StdRng::from_entropy()))
+                    .collect::<Result<Vec<_>, _>>()?,
+            };
+
+            if options.confirm_backup {
+                confirm_backups(&wallets)?;
+            }
+
+            match &options.encrypt_to {
+                Some(recipients) => {
+                    let recipients = Recipient::from_list(recipients)?;
+                    print!(
+                        "{}",
+                        encrypt_to(serde_json::to_string_pretty(&wallets)?.as_bytes(), &recipients)?
+                    );
+                    if let Some(path) = &options.save {
+                        let file = WalletFile::new(&wallets, "nostr")?;
+                        std::fs::write(
+                            path,
+                            encrypt_to(serde_json::to_string_pretty(&file)?.as_bytes(), &recipients)?,
+                        )?;
+                    }
+                }
+                None => {
+                    print_wallets(&wallets, options.json, options.mask_secrets && !options.reveal)?;
+                    if let Some(path) = &options.save {
+                        WalletFile::save(&wallets, "nostr", path, options.encrypt)?;
+                    }
+                }
+            };
+
+            if let Some(field) = &options.copy {
+                copy_wallets(&wallets, field, options.clear_after)?;
+            }
+
+            let operation = format!("nostr {}", options.subcommand.as_deref().unwrap_or("generate"));
+            AuditLog::log_wallets(&operation, &wallets)?;
+
+            Ok(())
+        }
+
+        match options.language.as_str() {
+            "chinese_simplified" => output::<ChineseSimplified>(options),
+            "chinese_traditional" => output::<ChineseTraditional>(options),
+            "english" => output::<English>(options),
+            "french" => output::<French>(options),
+            "italian" => output::<Italian>(options),
+            "japanese" => output::<Japanese>(options),
+            "korean" => output::<Korean>(options),
+            "spanish" => output::<Spanish>(options),
+            _ => output::<English>(options),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The test vectors of NIP-06, as (mnemonic, private key, nsec, public key, npub)
+    const NIP06_VECTORS: [(&str, &str, &str, &str, &str); 2] = [
+        (
+            "leader monkey parrot ring guide accident before fence cannon height naive bean",
+            "7f7ff03d123792d6ac594bfa67bf6d0c0ab55b6b1fdb6249303fe861f1ccba9a",
+            "nsec10allq0gjx7fddtzef0ax00mdps9t2kmtrldkyjfs8l5xruwvh2dq0lhhkp",
+            "17162c921dc4d2518f9a101db33695df1afb56ab82f5ff3e5da6eec3ca5cd917",
+            "npub1zutzeysacnf9rru6zqwmxd54mud0k44tst6l70ja5mhv8jjumytsd2x7nu",
+        ),
+        (
+            "what bleak badge arrange retreat wolf trade produce cricket blur garlic valid proud rude strong choose busy staff weather area salt hollow arm fade",
+            "c15d739894c81a2fcfd3a2df85a0d2c0dbc47a280d092799f144d73d7ae78add",
+            "nsec1c9wh8xy5eqdzln7n5t0ctgxjcrdug73gp5yj0x03gntn67h83twssdfhel",
+            "d41b22899549e1f3d335a31002cfd382174006e166d3e658e3a5eecdb6463573",
+            "npub16sdj9zv4f8sl85e45vgq9n7nsgt5qphpvmf7vk8r5hhvmdjxx4es8rq74h",
+        ),
+    ];
+
+    /// The test vectors of NIP-19, as (hex, Bech32)
+    const NIP19_VECTORS: [(&str, &str); 2] = [
+        (
+            "7e7e9c42a91bfef19fa929e5fda1b72e0ebc1a4c1141673e2794234d86addf4e",
+            "npub10elfcs4fr0l0r8af98jlmgdh9c8tcxjvz9qkw038js35mp4dma8qzvjptg",
+        ),
+        (
+            "67dea2ed018072d675f5415ecfaed7d2597555e202d85b3d65ea4e58d2d92ffa",
+            "nsec1vl029mgpspedva04g90vltkh6fvh240zqtv9k0t9af8935ke9laqsnlfe5",
+        ),
+    ];
+
+    #[test]
+    fn from_mnemonic() {
+        for (mnemonic, private_key, nsec, public_key, npub) in NIP06_VECTORS.iter() {
+            let wallet = NostrWallet::from_mnemonic::<English>(mnemonic, None, 0).unwrap();
+            assert_eq!(Some("m/44'/1237'/0'/0/0"), wallet.path.as_deref());
+            assert_eq!(Some(*private_key), wallet.private_key.as_deref());
+            assert_eq!(Some(*nsec), wallet.nsec.as_deref());
+            assert_eq!(Some(*public_key), wallet.public_key.as_deref());
+            assert_eq!(Some(*npub), wallet.npub.as_deref());
+        }
+    }
+
+    #[test]
+    fn from_private_key() {
+        for (_, private_key, nsec, public_key, npub) in NIP06_VECTORS.iter() {
+            for input in [private_key, nsec].iter() {
+                let wallet = NostrWallet::from_private_key(input).unwrap();
+                assert_eq!(Some(*private_key), wallet.private_key.as_deref());
+                assert_eq!(Some(*nsec), wallet.nsec.as_deref());
+                assert_eq!(Some(*public_key), wallet.public_key.as_deref());
+                assert_eq!(Some(*npub), wallet.npub.as_deref());
+            }
+        }
+        let (private_key, nsec) = NIP19_VECTORS[1];
+        assert_eq!(
+            Some(nsec),
+            NostrWallet::from_private_key(private_key).unwrap().nsec.as_deref()
+        );
+        assert_eq!(
+            Some(private_key),
+            NostrWallet::from_private_key(nsec).unwrap().private_key.as_deref()
+        );
+    }
+
+    #[test]
+    fn from_public_key() {
+        let (public_key, npub) = NIP19_VECTORS[0];
+        for input in [public_key, npub].iter() {
+            let wallet = NostrWallet::from_public_key(input).unwrap();
+            assert_eq!(Some(public_key), wallet.public_key.as_deref());
+            assert_eq!(Some(npub), wallet.npub.as_deref());
+            assert!(wallet.private_key.is_none());
+        }
+    }
+
+    #[test]
+    fn from_event_id() {
+        let event_id = NIP19_VECTORS[0].0;
+        let note = NostrWallet::from_event_id(event_id).unwrap().note.unwrap();
+        assert!(note.starts_with("note1"));
+        assert_eq!(
+            Some(event_id),
+            NostrWallet::from_event_id(&note).unwrap().event_id.as_deref()
+        );
+    }
+
+    #[test]
+    fn invalid_nip19() {
+        let (_, nsec) = NIP19_VECTORS[1];
+        let (_, npub) = NIP19_VECTORS[0];
+        // A key of another prefix, a short key, and a mistyped checksum are rejected
+        assert!(NostrWallet::from_public_key(nsec).is_err());
+        assert!(NostrWallet::from_private_key(npub).is_err());
+        assert!(NostrWallet::from_private_key("7e7e9c42").is_err());
+        assert!(NostrWallet::from_public_key(&npub.replace("jptg", "jptq")).is_err());
+    }
+}
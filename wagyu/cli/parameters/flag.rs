@@ -1,3 +1,13 @@
 // Global
 
+pub const ENCRYPT: &str = "[encrypt] --encrypt 'Encrypts the saved wallet(s) with a passphrase (requires --save)'";
 pub const JSON: &str = "[json] -j --json 'Prints the generated wallet(s) in JSON format'";
+pub const MANIFEST: &str = "[manifest] --manifest=[file] 'Writes a manifest of the generated addresses and their SHA-256 checksum to a specified file'";
+pub const ENCRYPT_TO: &str = "[encrypt to] --encrypt-to=[recipients] 'Encrypts the output to the specified age or GPG recipient(s) (comma separated)'";
+pub const SAVE: &str = "[save] --save=[file] 'Saves the generated wallet(s) to a specified file'";
+pub const COPY: &str =
+    "[copy] --copy=[field] 'Copies the specified field (address or private_key) of the wallet(s) to the clipboard'";
+pub const CLEAR_AFTER: &str = "[clear after] --clear-after=[seconds] 'Clears the copied field from the clipboard after a specified number of seconds (default: 30)'";
+pub const MASK_SECRETS: &str =
+    "[mask secrets] --mask-secrets 'Prints the wallet(s) with their mnemonics and private keys masked'";
+pub const REVEAL: &str = "[reveal] --reveal 'Prints the masked secrets of the wallet(s) (overrides --mask-secrets)'";
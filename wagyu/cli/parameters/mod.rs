@@ -2,3 +2,70 @@ pub mod flag;
 pub mod option;
 pub mod subcommand;
 pub mod types;
+
+use types::*;
+
+use clap::{App, Arg, SubCommand};
+
+use crate::model::no_std::{String, Vec};
+
+/// Returns the name of the given argument usage, such as `count` of `[count] -c --count=[count]`.
+pub fn to_name(usage: &'static str) -> &'static str {
+    usage[1..].split(&[']', '>'][..]).next().unwrap_or_default()
+}
+
+/// Returns the argument of the given option, conflicting with the options of the given names (the
+/// arguments of its subcommand, and the global flags) among its conflicts. An option with a value hint
+/// suggests the hinted values in shell completions, in place of its possible values.
+///
+/// The conflicts of a shared option may name the options of other coins, which are never present in its
+/// subcommand, and are skipped, as shell completions must resolve each conflict to an argument.
+#[cfg_attr(tarpaulin, skip)]
+pub fn to_arg<'b>(option: &'static OptionType, names: &[&str], hints: &'b [ValueHint]) -> Arg<'static, 'b> {
+    let conflicts = option
+        .1
+        .iter()
+        .filter(|conflict| names.contains(conflict))
+        .copied()
+        .collect::<Vec<&'static str>>();
+    let values = match hints.iter().find(|(hint, _)| *hint == to_name(option.0)) {
+        Some((_, values)) => values.iter().map(String::as_str).collect::<Vec<&'b str>>(),
+        None => option.2.to_vec(),
+    };
+    match values.is_empty() {
+        true => Arg::from_usage(option.0)
+            .conflicts_with_all(&conflicts)
+            .requires_all(option.3),
+        false => Arg::from_usage(option.0)
+            .conflicts_with_all(&conflicts)
+            .possible_values(&values)
+            .requires_all(option.3),
+    }
+}
+
+/// Returns the subcommand of the given subcommand type, with the given global flags, and the given
+/// value hints for its options.
+#[cfg_attr(tarpaulin, skip)]
+pub fn to_subcommand<'b>(
+    subcommand: &'static SubCommandType,
+    globals: &[FlagType],
+    hints: &'b [ValueHint],
+) -> App<'static, 'b> {
+    let names = subcommand
+        .2
+        .iter()
+        .map(|option| option.0)
+        .chain(globals.iter().copied())
+        .map(to_name)
+        .collect::<Vec<&'static str>>();
+    SubCommand::with_name(subcommand.0)
+        .about(subcommand.1)
+        .args(
+            &subcommand
+                .2
+                .iter()
+                .map(|option| to_arg(option, &names, hints))
+                .collect::<Vec<Arg<'static, 'b>>>(),
+        )
+        .settings(subcommand.3)
+}
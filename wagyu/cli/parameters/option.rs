@@ -5,6 +5,12 @@ use crate::cli::types::*;
 
 // Global
 
+pub const CONFIRM_BACKUP: OptionType = (
+    "[confirm backup] --confirm-backup 'Verifies the written down mnemonic before printing the wallet(s)'",
+    &[],
+    &[],
+    &[],
+);
 pub const COUNT: OptionType = (
     "[count] -c --count=[count] 'Generates a specified number of wallets'",
     &[],
@@ -17,10 +23,16 @@ pub const DIVERSIFIER_ZCASH: OptionType = (
     &[],
     &[],
 );
+pub const FORCE: OptionType = (
+    "[force] --force 'Accepts entropy or mnemonics with patterns of low entropy (such as repeated words), with a warning'",
+    &[],
+    &[],
+    &[],
+);
 pub const FORMAT_BITCOIN: OptionType = (
     "[format] -f --format=[format] 'Generates a wallet with a specified format'",
     &[],
-    &["bech32", "legacy", "segwit"],
+    &["bech32", "legacy", "segwit", "taproot"],
     &[],
 );
 pub const FORMAT_ZCASH: OptionType = (
@@ -31,7 +43,7 @@ pub const FORMAT_ZCASH: OptionType = (
 );
 pub const INTEGRATED_MONERO: OptionType = (
     "[integrated] -i --integrated=[PaymentID] 'Generates a wallet with a specified payment ID'",
-    &["subaddress"],
+    &["payment id", "subaddress"],
     &[],
     &[],
 );
@@ -54,6 +66,33 @@ pub const LANGUAGE_MONERO: OptionType = (
     ],
     &[],
 );
+pub const LOCALE: OptionType = (
+    "[locale] --locale=[locale] 'Prints the prompts and messages in a specified language (default: the system locale)'",
+    &[],
+    &[
+        "chinese_simplified",
+        "chinese_traditional",
+        "english",
+        "french",
+        "italian",
+        "japanese",
+        "korean",
+        "spanish",
+    ],
+    &[],
+);
+pub const LOCK_MEMORY: OptionType = (
+    "[lock memory] --lock-memory 'Locks the memory of the process, with its seeds and private keys, against swapping and core dumps (Linux)'",
+    &[],
+    &[],
+    &[],
+);
+pub const LOG_FILE: OptionType = (
+    "[log file] --log-file=[file] 'Records each operation, its paths, and its addresses, without secrets, to a specified file'",
+    &[],
+    &[],
+    &[],
+);
 pub const NETWORK_BITCOIN: OptionType = (
     "[network] -n --network=[network] 'Generates a wallet for a specified network'",
     &[],
@@ -72,13 +111,52 @@ pub const NETWORK_ZCASH: OptionType = (
     &["mainnet", "testnet"],
     &[],
 );
+pub const PAYMENT_ID_MONERO: OptionType = (
+    "[payment id] --payment-id=[payment id] 'Generates an integrated address with a specified 8-byte payment ID (hex)'",
+    &["integrated", "subaddress"],
+    &[],
+    &[],
+);
+pub const REDACT: OptionType = (
+    "[redact] --redact=[fields] 'Redacts the specified fields (such as private_key,mnemonic) of the printed and logged wallets'",
+    &[],
+    &[],
+    &[],
+);
+pub const REDACT_MODE: OptionType = (
+    "[redact mode] --redact-mode=[mode] 'Masks or omits the redacted fields (default: mask)'",
+    &[],
+    &["mask", "omit"],
+    &[],
+);
+pub const RESTORE_FILE_MONERO: OptionType = (
+    "[restore file] --restore-file=[file] 'Writes the restore parameters of the wallet for monero-wallet-cli --generate-from-json to a specified file path'",
+    &[],
+    &[],
+    &[],
+);
+pub const RESTORE_HEIGHT_MONERO: OptionType = (
+    "[restore height] --restore-height=[height] 'Scans the restored wallet from a specified block height (default: 0)'",
+    &[],
+    &[],
+    &["restore file"],
+);
 pub const SUBADDRESS_MONERO: OptionType = (
     "[subaddress] -s --subaddress=[Major Index][Minor Index] 'Generates a wallet with a specified major and minor index'",
-    &["address", "integrated", "private view"],
+    &["address", "integrated", "payment id", "private view"],
+    &[],
+    &[],
+);
+pub const STRICT: OptionType = (
+    "[strict] --strict 'Rejects key material with surrounding whitespace, 0x prefixes, uppercase hex, or irregular spacing, instead of normalizing it'",
+    &[],
     &[],
     &[],
 );
 
+/// The options of each coin, applying to the coin and each of its subcommands
+pub const COIN: &[OptionType] = &[FORCE, LOCALE, LOCK_MEMORY, LOG_FILE, REDACT, REDACT_MODE, STRICT];
+
 // Import
 
 pub const ADDRESS: OptionType = (
@@ -87,21 +165,61 @@ pub const ADDRESS: OptionType = (
     &[],
     &[],
 );
+pub const COLUMN_IMPORT: OptionType = (
+    "[column] --column=[column] 'Imports the keys or addresses of a specified CSV column, by index (from 1) or header name (requires file)'",
+    &[],
+    &[],
+    &["file"],
+);
 pub const DIVERSIFIER_IMPORT_ZCASH: OptionType = (
     "[diversifier] --diversifier=[diversifier] 'Imports a wallet with a specified Sapling address diversifier'",
     &["address"],
     &[],
     &[],
 );
+pub const DUMPFILE_IMPORT_BITCOIN: OptionType = (
+    "[dumpfile] --dumpfile=[path] 'Imports the keys of a Bitcoin Core wallet dump (dumpwallet), verifying the addresses and HD key path of each key'",
+    &["address", "file", "private", "public"],
+    &[],
+    &[],
+);
+pub const ELECTRUM_WALLET_IMPORT_BITCOIN: OptionType = (
+    "[electrum wallet] --electrum-wallet=[path] 'Imports the seed, extended keys, and addresses of an Electrum wallet file, verifying them against the seed'",
+    &["address", "dumpfile", "file", "private", "public"],
+    &[],
+    &[],
+);
+pub const FILE_IMPORT: OptionType = (
+    "[file] --file=[path] 'Imports a wallet for each key or address of a specified file, printing each result as a line of JSON'",
+    &[
+        "address",
+        "copy",
+        "encrypt to",
+        "integrated",
+        "manifest",
+        "mnemonic",
+        "payment id",
+        "private",
+        "private spend",
+        "private view",
+        "public",
+        "public spend",
+        "public view",
+        "save",
+        "subaddress",
+    ],
+    &[],
+    &[],
+);
 pub const FORMAT_IMPORT_BITCOIN: OptionType = (
     "[format] -f --format=[format] 'Imports a wallet with a specified format'",
     &[],
-    &["bech32", "legacy", "segwit"],
+    &["bech32", "legacy", "segwit", "taproot"],
     &[],
 );
 pub const INTEGRATED_IMPORT_MONERO: OptionType = (
     "[integrated] -i --integrated=[PaymentID] 'Imports a wallet with a specified payment ID'",
-    &["address", "private view", "subaddress"],
+    &["address", "payment id", "private view", "subaddress"],
     &[],
     &[],
 );
@@ -136,6 +254,12 @@ pub const NETWORK_IMPORT_MONERO: OptionType = (
     &["mainnet", "stagenet", "testnet"],
     &[],
 );
+pub const PAYMENT_ID_IMPORT_MONERO: OptionType = (
+    "[payment id] --payment-id=[payment id] 'Imports a wallet with an integrated address of a specified 8-byte payment ID (hex)'",
+    &["address", "integrated", "private view", "subaddress"],
+    &[],
+    &[],
+);
 pub const PRIVATE: OptionType = (
     "[private] --private=[private key] 'Imports a wallet for a specified private key'",
     &["address", "count", "network", "public"],
@@ -172,23 +296,41 @@ pub const PUBLIC_VIEW_KEY_MONERO: OptionType = (
     &[],
     &["public spend"],
 );
+pub const SCRIPT_LEAF_IMPORT_BITCOIN: OptionType = (
+    "[script leaf] --script-leaf=[script]... 'Imports a taproot wallet with a script tree of the specified tapscript leaves in hex (multiple occurrences)'",
+    &["address", "file"],
+    &[],
+    &[],
+);
 pub const SUBADDRESS_IMPORT_MONERO: OptionType = (
     "[subaddress] -s --subaddress=[Major Index][Minor Index] 'Imports a wallet with a specified major and minor index'",
-    &["integrated"],
+    &["integrated", "payment id"],
     &[],
     &[],
 );
 
 // HD
 
-pub const DERIVATION_BITCOIN: OptionType = (
-    "[derivation] -d --derivation=[\"path\"] 'Generates an HD wallet for a specified derivation path (in quotes) [possible values: bip32, bip44, bip49, \"<custom path>\"]'",
+pub const ACCOUNT_HD_BITCOIN: OptionType = (
+    "[account] -a --account=[account] 'Generates an HD wallet for a specified account number of the purpose'",
+    &[],
+    &[],
+    &["purpose"],
+);
+pub const CHANGE_HD_BITCOIN: OptionType = (
+    "[change] --change=[change] 'Generates an HD wallet for a specified (external/internal) chain of the purpose'",
     &[],
+    &["0", "1"],
+    &["purpose"],
+);
+pub const DERIVATION_BITCOIN: OptionType = (
+    "[derivation] -d --derivation=[\"path\"] 'Generates an HD wallet for a specified derivation path (in quotes) [possible values: bip32, bip44, bip49, bip84, bip86, \"<custom path>\"]'",
+    &["purpose"],
     &[],
     &[],
 );
 pub const DERIVATION_ETHEREUM: OptionType = (
-    "[derivation] -d --derivation=[\"path\"] 'Generates an HD wallet for a specified derivation path (in quotes) [possible values: ethereum, keepkey, ledger-legacy, ledger-live, trezor, \"<custom path>\"]'",
+    "[derivation] -d --derivation=[\"path\"] 'Generates an HD wallet for a specified derivation preset or path (in quotes) [possible values: coinomi, ethereum, exodus, keepkey, ledger-legacy, ledger-live, metamask, myetherwallet, trezor, \"<custom path>\"]'",
     &[],
     &[],
     &[],
@@ -205,8 +347,26 @@ pub const DIVERSIFIER_HD_ZCASH: OptionType = (
     &[],
     &[],
 );
+pub const DIVERSIFIER_INDEX_HD_ZCASH: OptionType = (
+    "[diversifier index] --diversifier-index=[index] 'Generates an HD wallet with the Sapling address of a specified diversifier index (or the next valid index)'",
+    &["diversifier"],
+    &[],
+    &[],
+);
+pub const DURESS_HD: OptionType = (
+    "[duress] --duress=[PIN] 'Generates an HD wallet for the decoy account of a specified duress PIN, in place of the account number'",
+    &["account", "tree"],
+    &[],
+    &[],
+);
+pub const FORMAT_HD_BITCOIN: OptionType = (
+    "[format] -f --format=[format] 'Generates an HD wallet with a specified format, which must agree with the purpose'",
+    &["derivation"],
+    &["bech32", "legacy", "segwit", "taproot"],
+    &[],
+);
 pub const INDEX_HD: OptionType = (
-    "[index] -i --index=[index] 'Generates an HD wallet with a specified index'",
+    "[index] -i --index=[index] 'Generates an HD wallet with a specified index (or range, for derivation path templates)'",
     &[],
     &[],
     &[],
@@ -232,6 +392,12 @@ pub const LANGUAGE_HD: OptionType = (
     ],
     &[],
 );
+pub const LIST_PRESETS_ETHEREUM: OptionType = (
+    "[list presets] --list-presets 'Lists the derivation presets, including user-defined presets'",
+    &[],
+    &[],
+    &[],
+);
 pub const NETWORK_HD_BITCOIN: OptionType = (
     "[network] -n --network=[network] 'Generates an HD wallet for a specified network'",
     &[],
@@ -250,35 +416,132 @@ pub const PASSWORD_HD: OptionType = (
     &[],
     &[],
 );
+pub const PURPOSE_BITCOIN: OptionType = (
+    "[purpose] --purpose=[purpose] 'Generates an HD wallet for a specified BIP-44 (legacy), BIP-49 (segwit), BIP-84 (bech32), or BIP-86 (taproot) purpose'",
+    &["derivation"],
+    &["44", "49", "84", "86"],
+    &[],
+);
 pub const WORD_COUNT: OptionType = (
     "[word count] -w --word-count=[word count] 'Generates an HD wallet with a specified word count'",
     &[],
     &["12", "15", "18", "21", "24"],
     &[],
 );
+pub const WORDLIST_FILE_HD: OptionType = (
+    "[wordlist file] --wordlist-file=[file] 'Generates an HD wallet with a non-standard wordlist of a specified file (2048 unique words, one per line)'",
+    &["language"],
+    &[],
+    &[],
+);
+
+// HD tree
+
+pub const BREADTH_HD_TREE: OptionType = (
+    "[breadth] --breadth=[breadth] 'Prints a tree with a specified number of accounts, and of addresses of each chain (default: 5)'",
+    &[],
+    &[],
+    &["tree"],
+);
+pub const DEPTH_HD_TREE: OptionType = (
+    "[depth] --depth=[depth] 'Prints a tree with a specified number of levels below the accounts, for the chains and their addresses (default: 2)'",
+    &[],
+    &["0", "1", "2"],
+    &["tree"],
+);
+pub const MNEMONIC_HD_TREE: OptionType = (
+    "[mnemonic] -m --mnemonic=[\"mnemonic\"] 'Prints the tree of a specified mnemonic (in quotes) instead of a new HD wallet'",
+    &[],
+    &[],
+    &["tree"],
+);
+pub const TREE_HD: OptionType = (
+    "[tree] 'Prints the accounts, chains, and addresses of the HD wallet as a tree, with the fingerprint of each extended key'",
+    &[],
+    &["tree"],
+    &[],
+);
+
+// Keystore
+
+pub const KEYSTORE_ETHEREUM: OptionType = (
+    "[keystore] --keystore=[directory] 'Exports each account of the HD wallet as an encrypted keystore file to a specified directory, with an index of the accounts, in place of printing its secrets'",
+    &["extended public", "summary"],
+    &[],
+    &[],
+);
+pub const KEYSTORE_IMPORT_ETHEREUM: OptionType = (
+    "[keystore] --keystore=[path] 'Imports the private key of a specified encrypted keystore file or presale wallet'",
+    &["address", "file", "private", "public"],
+    &[],
+    &[],
+);
+
+// Summary
+
+pub const SUMMARY: OptionType = (
+    "[summary] --summary 'Prints only the master fingerprint, the account extended public key hash, and the first addresses of the HD wallet, to verify it without revealing a secret'",
+    &["extended private", "extended public"],
+    &[],
+    &["mnemonic"],
+);
+pub const SUMMARY_COUNT: OptionType = (
+    "[summary count] --summary-count=[count] 'Prints a summary with a specified number of addresses (default: 5)'",
+    &[],
+    &[],
+    &["summary"],
+);
+
+// Watch-only
+
+pub const EXPORT_FILE: OptionType = (
+    "[export file] --export-file=[file] 'Writes the watch-only wallet file to a specified file path'",
+    &[],
+    &[],
+    &["export format"],
+);
+pub const EXPORT_FORMAT: OptionType = (
+    "[export format] --export-format=[wallet] 'Exports the account of the HD wallet as a watch-only wallet file of a specified wallet, or as a Coldcard export file'",
+    &["summary"],
+    &["bluewallet", "coldcard", "coldcard-multisig", "electrum", "sparrow", "specter"],
+    &["export file"],
+);
+pub const MASTER_FINGERPRINT: OptionType = (
+    "[master fingerprint] --master-fingerprint=[hex] 'Exports an account extended public key with a specified master fingerprint'",
+    &[],
+    &[],
+    &["export format", "extended public"],
+);
 
 // Import HD
 
 pub const ACCOUNT: OptionType = (
-    "[account] -a --account=[account] 'Imports an HD wallet for a specified account number for bip44 and bip49 derivations'",
+    "[account] -a --account=[account] 'Imports an HD wallet for a specified account number for bip44, bip49, bip84, and bip86 derivations (or range, for derivation path templates)'",
+    &[],
+    &[],
+    &[],
+);
+pub const ACCOUNT_ETHEREUM: OptionType = (
+    "[account] -a --account=[account] 'Derives an HD wallet for a specified account number or range, for derivation path templates'",
     &[],
     &[],
     &[],
 );
+
 pub const CHAIN: OptionType = (
-    "[chain] -c --chain=[chain] 'Imports an HD wallet for a specified (external/internal) chain for bip44 and bip49 derivations'",
+    "[chain] -c --chain=[chain] 'Imports an HD wallet for a specified (external/internal) chain for bip44, bip49, bip84, and bip86 derivations'",
     &[],
     &["0", "1"],
     &[],
 );
 pub const DERIVATION_IMPORT_BITCOIN: OptionType = (
-    "[derivation] -d --derivation=[\"path\"] 'Imports an HD wallet for a specified derivation path (in quotes) [possible values: bip32, bip44, bip49, \"<custom path>\"]'",
+    "[derivation] -d --derivation=[\"path\"] 'Imports an HD wallet for a specified derivation path (in quotes) [possible values: bip32, bip44, bip49, bip84, bip86, \"<custom path>\"]'",
     &[],
     &[],
     &[],
 );
 pub const DERIVATION_IMPORT_ETHEREUM: OptionType = (
-    "[derivation] -d --derivation=[\"path\"] 'Imports an HD wallet for a specified derivation path (in quotes) [possible values: ethereum, keepkey, ledger-legacy, ledger-live, trezor, \"<custom path>\"]'",
+    "[derivation] -d --derivation=[\"path\"] 'Imports an HD wallet for a specified derivation preset or path (in quotes) [possible values: coinomi, ethereum, exodus, keepkey, ledger-legacy, ledger-live, metamask, myetherwallet, trezor, \"<custom path>\"]'",
     &[],
     &[],
     &[],
@@ -295,6 +558,18 @@ pub const DIVERSIFIER_IMPORT_HD_ZCASH: OptionType = (
     &[],
     &[],
 );
+pub const DIVERSIFIER_INDEX_IMPORT_HD_ZCASH: OptionType = (
+    "[diversifier index] --diversifier-index=[index] 'Imports an HD wallet with the Sapling address of a specified diversifier index (or the next valid index)'",
+    &["diversifier"],
+    &[],
+    &[],
+);
+pub const DURESS_IMPORT_HD: OptionType = (
+    "[duress] --duress=[PIN] 'Imports an HD wallet for the decoy account of a specified duress PIN, in place of the account number'",
+    &["account", "extended private", "extended public"],
+    &[],
+    &[],
+);
 pub const EXTENDED_PUBLIC: OptionType = (
     "[extended public] --extended-public=[extended public] 'Imports a partial HD wallet for a specified extended public key'",
     &["account", "count", "extended private", "index", "mnemonic", "password"],
@@ -308,6 +583,12 @@ pub const EXTENDED_PRIVATE: OptionType = (
     &[],
 );
 
+pub const FORMAT_IMPORT_HD_BITCOIN: OptionType = (
+    "[format] -f --format=[format] 'Imports an HD wallet for a specified format, overriding the format of the extended key version bytes'",
+    &["mnemonic"],
+    &["bech32", "legacy", "segwit", "taproot"],
+    &[],
+);
 pub const NETWORK_IMPORT_HD_BITCOIN: OptionType = (
     "[network] -n --network=[network] 'Imports an HD wallet for a specified network'",
     &[],
@@ -315,7 +596,7 @@ pub const NETWORK_IMPORT_HD_BITCOIN: OptionType = (
     &[],
 );
 pub const INDEX_IMPORT_HD: OptionType = (
-    "[index] -i --index=[index] 'Imports an HD wallet with a specified index'",
+    "[index] -i --index=[index] 'Imports an HD wallet with a specified index (or range, for derivation path templates)'",
     &[],
     &[],
     &[],
@@ -338,6 +619,12 @@ pub const PASSWORD_IMPORT_HD: OptionType = (
     &[],
     &[],
 );
+pub const WORDLIST_FILE_IMPORT_HD: OptionType = (
+    "[wordlist file] --wordlist-file=[file] 'Imports an HD wallet for a mnemonic of a non-standard wordlist of a specified file (2048 unique words, one per line)'",
+    &["extended private", "extended public"],
+    &[],
+    &[],
+);
 
 // Transaction
 
@@ -361,6 +648,13 @@ pub const SIGN_RAW_TRANSACTION_BITCOIN: OptionType = (
     &[],
 );
 
+pub const TRANSACTION_OP_RETURN_BITCOIN: OptionType = (
+    "[op return] --op-return=[hex|utf8] 'Adds an OP_RETURN output with the specified data, in hex with a 0x prefix or as UTF-8 text (up to 80 bytes)'",
+    &["signrawtransaction"],
+    &[],
+    &["createrawtransaction"],
+);
+
 pub const TRANSACTION_LOCK_TIME_BITCOIN: OptionType = (
     "[lock time] --lock-time=[lock time] 'Specify a Bitcoin transaction lock time'",
     &["signrawtransaction"],
@@ -437,3 +731,1608 @@ pub const TRANSACTION_VERSION_ZCASH: OptionType = (
     &["sapling"],
     &["createrawtransaction"],
 );
+
+// Dust
+
+pub const ALLOW_DUST_TRANSACTION: OptionType = (
+    "[allow dust] --allow-dust 'Allows outputs below the dust limit, printing a warning instead of an error'",
+    &[],
+    &[],
+    &[],
+);
+
+pub const DUST_LIMIT_TRANSACTION: OptionType = (
+    "[dust limit] --dust-limit=[amount] 'Rejects outputs below a specified amount in satoshis, or zatoshis for Zcash (default: 546)'",
+    &[],
+    &[],
+    &[],
+);
+
+// Air gap
+
+pub const INPUTS_CREATE_TX_BITCOIN: OptionType = (
+    "[inputs] --inputs=[inputs] 'Creates an unsigned transaction with the specified inputs
+    Inputs format: '[{\"txid\":\"txid\", \"vout\":index, \"amount\":amount, \"address\":\"address\"},...]'
+    (Optional: manually specify scriptPubKey and redeemScript)
+    '",
+    &[],
+    &[],
+    &["outputs", "unsigned tx"],
+);
+
+pub const LOCK_TIME_CREATE_TX_BITCOIN: OptionType = (
+    "[lock time] --lock-time=[lock time] 'Specify a Bitcoin transaction lock time'",
+    &[],
+    &[],
+    &[],
+);
+
+pub const NETWORK_CREATE_TX_ETHEREUM: OptionType = (
+    "[network] --network=[network] 'Creates an unsigned transaction for a specified network'",
+    &[],
+    &["goerli", "kovan", "mainnet", "rinkeby", "ropsten"],
+    &[],
+);
+
+pub const NONCE_FILE_CREATE_TX_ETHEREUM: OptionType = (
+    "[nonce file] --nonce-file=[file] 'Records the nonce of the from address in a specified nonce file, defaulting to its next unused nonce'",
+    &[],
+    &[],
+    &["parameters"],
+);
+
+pub const NONCE_FILE_SIGN_TX_ETHEREUM: OptionType = (
+    "[nonce file] --nonce-file=[file] 'Records the nonce of the signer in a specified nonce file, rejecting a reused nonce'",
+    &[],
+    &[],
+    &["unsigned tx"],
+);
+
+pub const OP_RETURN_CREATE_TX_BITCOIN: OptionType = (
+    "[op return] --op-return=[hex|utf8] 'Adds an OP_RETURN output with the specified data, in hex with a 0x prefix or as UTF-8 text (up to 80 bytes)'",
+    &[],
+    &[],
+    &["outputs"],
+);
+
+pub const OUTPUTS_CREATE_TX_BITCOIN: OptionType = (
+    "[outputs] --outputs=[outputs] 'Creates an unsigned transaction with the specified outputs
+    Outputs format: '{\"address\":amount,...}'
+    '",
+    &[],
+    &[],
+    &["inputs", "unsigned tx"],
+);
+
+pub const PARAMETERS_CREATE_TX_ETHEREUM: OptionType = (
+    "[parameters] --parameters=['{\"to\":\"address\", \"value\":\"value\", \"gas\":\"gas\", \"gasPrice\":\"gas_price\", \"nonce\":nonce}'] 'Creates an unsigned transaction with the specified parameters
+    (Optional: Add a data field, and a from field to track its nonce with --nonce-file, which may then omit the nonce)'",
+    &[],
+    &[],
+    &["unsigned tx"],
+);
+
+pub const PRIVATE_KEY_SIGN_TX_BITCOIN: OptionType = (
+    "[private key] --private-key=[private key] 'Signs the unsigned transaction with the specified private key(s) (comma separated)'",
+    &[],
+    &[],
+    &["unsigned tx"],
+);
+
+pub const PRIVATE_KEY_SIGN_TX_ETHEREUM: OptionType = (
+    "[private key] --private-key=[private key] 'Signs the unsigned transaction with a specified private key'",
+    &[],
+    &[],
+    &["unsigned tx"],
+);
+
+pub const UNSIGNED_TX_CREATE: OptionType = (
+    "[unsigned tx] --unsigned-tx=[file] 'Writes the unsigned transaction to a specified file'",
+    &[],
+    &[],
+    &[],
+);
+
+pub const UNSIGNED_TX_SIGN: OptionType = (
+    "[unsigned tx] --unsigned-tx=[file] 'Reads the unsigned transaction from a specified file'",
+    &[],
+    &[],
+    &["private key"],
+);
+
+pub const VERSION_CREATE_TX_BITCOIN: OptionType = (
+    "[version] --version=[version] 'Specify a Bitcoin transaction version'",
+    &[],
+    &[],
+    &[],
+);
+
+// Sign
+
+pub const ADDRESS_VERIFY_BITCOIN: OptionType = (
+    "[address] --address=[address] 'Verifies a signed message for a specified address'",
+    &[],
+    &[],
+    &["message", "signature"],
+);
+
+pub const CHAIN_ID_SIGN_USEROP_ETHEREUM: OptionType = (
+    "[chain id] --chain-id=[chain id] 'Signs the user operation for a specified chain id'",
+    &[],
+    &[],
+    &["entry point"],
+);
+
+pub const CHAIN_ID_PERMIT_ETHEREUM: OptionType = (
+    "[chain id] --chain-id=[chain id] 'Signs the permit for a specified chain id'",
+    &[],
+    &[],
+    &["token"],
+);
+
+pub const DEADLINE_PERMIT_ETHEREUM: OptionType = (
+    "[deadline] --deadline=[timestamp] 'Signs a permit that expires after a specified Unix timestamp'",
+    &[],
+    &[],
+    &["token"],
+);
+
+pub const DEBUG_SIGNATURE_ETHEREUM: OptionType = (
+    "[debug signature] --debug-signature 'UNSAFE: Prints the RFC 6979 nonce derivation of the signature to stderr, to audit the nonce (the nonce reveals the private key)'",
+    &[],
+    &[],
+    &[],
+);
+
+pub const ENTRY_POINT_SIGN_USEROP_ETHEREUM: OptionType = (
+    "[entry point] --entry-point=[address] 'Signs the user operation for a specified ERC-4337 entry point (v0.6 or v0.7)'",
+    &[],
+    &[],
+    &["chain id"],
+);
+
+pub const FORMAT_SIGN_BITCOIN: OptionType = (
+    "[format] -f --format=[format] 'Signs a message for the address of a specified format'",
+    &[],
+    &["bech32", "legacy", "segwit", "taproot"],
+    &[],
+);
+
+pub const MESSAGE_BITCOIN: OptionType = (
+    "[message] --message=[message] 'Signs or verifies a specified message (BIP-322)'",
+    &[],
+    &[],
+    &[],
+);
+
+pub const MESSAGE_RECOVER_ETHEREUM: OptionType = (
+    "[message] --message=[message] 'Recovers the signer of a specified message (EIP-191 personal message)'",
+    &["message hash"],
+    &[],
+    &["signature"],
+);
+
+pub const MESSAGE_HASH_RECOVER_ETHEREUM: OptionType = (
+    "[message hash] --message-hash=[message hash] 'Recovers the signer of a specified 32-byte message hash'",
+    &["message"],
+    &[],
+    &["signature"],
+);
+
+pub const NONCE_PERMIT_ETHEREUM: OptionType = (
+    "[nonce] --nonce=[nonce] 'Signs the permit with a specified permit nonce of the owner (as returned by nonces(owner))'",
+    &[],
+    &[],
+    &["token"],
+);
+
+pub const PRIVATE_KEY_SIGN_BITCOIN: OptionType = (
+    "[private key] --private-key=[private key] 'Signs with a specified private key'",
+    &[],
+    &[],
+    &["message"],
+);
+
+pub const PRIVATE_KEY_SIGN_ETHEREUM: OptionType = (
+    "[private key] --private-key=[private key] 'Signs with a specified private key'",
+    &[],
+    &[],
+    &[],
+);
+
+pub const PROOF_SIGN_BITCOIN: OptionType = (
+    "[proof] --proof=[proof] 'Signs with a specified proof type (default: simple for bech32 and taproot, full otherwise)'",
+    &[],
+    &["full", "simple"],
+    &[],
+);
+
+pub const SIGNATURE_RECOVER_ETHEREUM: OptionType = (
+    "[signature] --signature=[signature] 'Recovers the signer of a specified 65-byte signature (r || s || v)'",
+    &[],
+    &[],
+    &[],
+);
+
+pub const SIGNATURE_VERIFY_BITCOIN: OptionType = (
+    "[signature] --signature=[signature] 'Verifies a specified base64 signature (simple or full proof)'",
+    &[],
+    &[],
+    &[],
+);
+
+pub const SPENDER_PERMIT_ETHEREUM: OptionType = (
+    "[spender] --spender=[address] 'Approves a specified spender address'",
+    &[],
+    &[],
+    &["token"],
+);
+
+pub const TOKEN_PERMIT_ETHEREUM: OptionType = (
+    "[token] --token=[address] 'Signs a permit for a specified ERC-20 token address (EIP-2612)'",
+    &[],
+    &[],
+    &[
+        "chain id",
+        "deadline",
+        "nonce",
+        "private key",
+        "spender",
+        "token name",
+        "value",
+    ],
+);
+
+pub const TOKEN_NAME_PERMIT_ETHEREUM: OptionType = (
+    "[token name] --token-name=[name] 'Signs the permit with a specified token name, as in the EIP-712 domain of the token'",
+    &[],
+    &[],
+    &["token"],
+);
+
+pub const TOKEN_VERSION_PERMIT_ETHEREUM: OptionType = (
+    "[token version] --token-version=[version] 'Signs the permit with a specified token version, as in the EIP-712 domain of the token (default: 1)'",
+    &[],
+    &[],
+    &["token"],
+);
+
+pub const TYPED_DATA_ETHEREUM: OptionType = (
+    "[typed data] --typed-data=[typed data] 'Signs EIP-712 typed data from a specified JSON file (or JSON string)
+    Typed data format: '{\"types\":{...}, \"primaryType\":\"type\", \"domain\":{...}, \"message\":{...}}'
+    '",
+    &[],
+    &[],
+    &["private key"],
+);
+
+pub const USEROP_ETHEREUM: OptionType = (
+    "[userop] --userop=[userop] 'Signs an ERC-4337 user operation from a specified JSON file (or JSON string)
+    v0.6 format: '{\"sender\":\"address\", \"nonce\":\"nonce\", \"initCode\":\"hex\", \"callData\":\"hex\", \"callGasLimit\":\"gas\", \"verificationGasLimit\":\"gas\", \"preVerificationGas\":\"gas\", \"maxFeePerGas\":\"fee\", \"maxPriorityFeePerGas\":\"fee\", \"paymasterAndData\":\"hex\"}'
+    v0.7 format: '{\"sender\":\"address\", \"nonce\":\"nonce\", \"initCode\":\"hex\", \"callData\":\"hex\", \"accountGasLimits\":\"bytes32\", \"preVerificationGas\":\"gas\", \"gasFees\":\"bytes32\", \"paymasterAndData\":\"hex\"}'
+    '",
+    &[],
+    &[],
+    &["chain id", "entry point", "private key"],
+);
+
+pub const VALUE_PERMIT_ETHEREUM: OptionType = (
+    "[value] --value=[value] 'Approves a specified amount of tokens, in the smallest unit of the token'",
+    &[],
+    &[],
+    &["token"],
+);
+
+// Sign batch
+
+pub const FILE_SIGN_BATCH: OptionType = (
+    "[file] --file=[file] 'Signs the prepared transactions of a specified JSON file
+    File format: '[{\"path\":\"path\", \"transaction_hex\":\"transaction hex\"},...]'
+    (Bitcoin: Add the inputs of each transaction, in the format of create-tx)'",
+    &[],
+    &[],
+    &["extended private key"],
+);
+
+pub const KEY_SIGN_BATCH: OptionType = (
+    "[extended private key] --key=[xprv] 'Signs each transaction with the key derived at its path from a specified extended private key'",
+    &[],
+    &[],
+    &["file"],
+);
+
+pub const NETWORK_SIGN_BATCH_ETHEREUM: OptionType = (
+    "[network] --network=[network] 'Signs the transactions for a specified network'",
+    &[],
+    &["goerli", "kovan", "mainnet", "rinkeby", "ropsten"],
+    &[],
+);
+
+// Bump fee
+
+pub const CHANGE_OUTPUT_BUMP_FEE_BITCOIN: OptionType = (
+    "[change output] --change-output=[index] 'Reduces the output of a specified index to pay the fee (default: the last output to an address of the private keys)'",
+    &[],
+    &[],
+    &[],
+);
+pub const INPUTS_BUMP_FEE_BITCOIN: OptionType = (
+    "[inputs] --inputs=[inputs] 'Bumps the fee of a transaction spending the specified inputs
+    Inputs format: '[{\"txid\":\"txid\", \"vout\":index, \"amount\":amount, \"address\":\"address\"},...]'
+    (Optional: manually specify scriptPubKey and redeemScript)
+    '",
+    &[],
+    &[],
+    &[],
+);
+pub const NEW_FEERATE_BUMP_FEE_BITCOIN: OptionType = (
+    "[new feerate] --new-feerate=[sat/vB] 'Bumps the fee of the transaction to a specified fee rate'",
+    &[],
+    &[],
+    &[],
+);
+pub const PRIVATE_KEY_BUMP_FEE_BITCOIN: OptionType = (
+    "[private key] --private-key=[private key] 'Signs the replacement transaction with the specified private key(s) (comma separated)'",
+    &[],
+    &[],
+    &[],
+);
+pub const RAW_TX_BUMP_FEE_BITCOIN: OptionType = (
+    "[raw tx] --raw-tx=[transaction hex] 'Bumps the fee of a specified replaceable (BIP-125) transaction'",
+    &[],
+    &[],
+    &["inputs", "new feerate", "private key"],
+);
+
+// Sweep
+
+pub const BROADCAST_SWEEP_BITCOIN: OptionType = (
+    "[broadcast] --broadcast 'Broadcasts the sweep transaction to the network with the node'",
+    &[],
+    &[],
+    &[],
+);
+pub const FEE_RATE_SWEEP_BITCOIN: OptionType = (
+    "[fee rate] --fee-rate=[sat/vB] 'Pays a specified fee rate (default: the fee rate estimated by the node for 6 blocks)'",
+    &[],
+    &[],
+    &[],
+);
+pub const RPC_URL_SWEEP_BITCOIN: OptionType = (
+    "[rpc url] --rpc-url=[url] 'Scans the unspent outputs with the node at a specified http:// URL (default: $WAGYU_BITCOIN_RPC)'",
+    &[],
+    &[],
+    &[],
+);
+pub const TO_SWEEP_BITCOIN: OptionType = (
+    "[to] --to=[address] 'Sweeps the funds to a specified address'",
+    &[],
+    &[],
+    &["wif"],
+);
+pub const WIF_SWEEP_BITCOIN: OptionType = (
+    "[wif] --wif=[private key] 'Sweeps all funds of the legacy, segwit, and bech32 addresses of a specified private key'",
+    &[],
+    &[],
+    &["to"],
+);
+
+// Ceremony
+
+pub const CHECKS_CEREMONY: OptionType = (
+    "[checks] --checks=[count] 'Verifies a specified number of random words of the mnemonic backup (default: 3)'",
+    &["verify"],
+    &[],
+    &[],
+);
+pub const OPERATORS_CEREMONY: OptionType = (
+    "[operators] --operators=[count] 'Collects entropy from a specified number of operators (default: 2)'",
+    &["verify"],
+    &[],
+    &[],
+);
+pub const SUMMARY_CEREMONY: OptionType = (
+    "[summary] --summary=[file] 'Writes the signed ceremony summary to a specified file'",
+    &["verify"],
+    &[],
+    &[],
+);
+pub const VERIFY_CEREMONY: OptionType = (
+    "[verify] --verify=[file] 'Verifies the signature of a specified ceremony summary'",
+    &["checks", "operators", "summary", "words"],
+    &[],
+    &[],
+);
+pub const WORDS_CEREMONY: OptionType = (
+    "[words] --words=[count] 'Generates a mnemonic with a specified number of words (default: 24)'",
+    &["verify"],
+    &["12", "15", "18", "21", "24"],
+    &[],
+);
+
+// Completions
+
+pub const SHELL_COMPLETIONS: OptionType = (
+    "<shell> 'Generates completions for a specified shell'",
+    &[],
+    &["bash", "fish", "powershell", "zsh"],
+    &[],
+);
+
+// Contains
+
+pub const ADDRESS_CONTAINS: OptionType = (
+    "<address> --address=<address> 'Searches the wallets for a specified address'",
+    &[],
+    &[],
+    &[],
+);
+pub const LANGUAGE_CONTAINS: OptionType = (
+    "[language] -l --language=[language] 'Searches the wallets of a mnemonic with a specified language'",
+    &[],
+    &[
+        "chinese_simplified",
+        "chinese_traditional",
+        "english",
+        "french",
+        "italian",
+        "japanese",
+        "korean",
+        "spanish",
+    ],
+    &["mnemonic"],
+);
+pub const MNEMONIC_CONTAINS: OptionType = (
+    "[mnemonic] -m --mnemonic=[\"mnemonic\"] 'Searches the wallets of a specified mnemonic (in quotes)'",
+    &["xpub"],
+    &[],
+    &[],
+);
+pub const PASSWORD_CONTAINS: OptionType = (
+    "[password] -p --password=[password] 'Searches the wallets of a mnemonic with a specified password'",
+    &[],
+    &[],
+    &["mnemonic"],
+);
+pub const SEARCH_DEPTH_CONTAINS: OptionType = (
+    "[search depth] --search-depth=[depth] 'Searches a specified number of indices of each chain (default: 1000)'",
+    &[],
+    &[],
+    &[],
+);
+pub const XPUB_CONTAINS: OptionType = (
+    "[xpub] --xpub=[extended public key] 'Searches the wallets of a specified extended public key'",
+    &["mnemonic"],
+    &[],
+    &[],
+);
+
+// Contract address
+
+pub const CREATE3_CONTRACT_ADDRESS_ETHEREUM: OptionType = (
+    "[create3] --create3 'Computes the address of a contract deployed with CREATE3 by the factory (with a solmate CREATE3 proxy)'",
+    &["implementation", "init code", "init code hash"],
+    &[],
+    &["salt"],
+);
+pub const DEPLOYER_CONTRACT_ADDRESS_ETHEREUM: OptionType = (
+    "<deployer> --deployer=<address> 'Computes the address of a contract deployed by a specified deployer or factory address'",
+    &[],
+    &[],
+    &[],
+);
+pub const IMPLEMENTATION_CONTRACT_ADDRESS_ETHEREUM: OptionType = (
+    "[implementation] --implementation=[address] 'Computes the CREATE2 address of an EIP-1167 minimal proxy of a specified implementation address'",
+    &["create3", "init code", "init code hash"],
+    &[],
+    &["salt"],
+);
+pub const INIT_CODE_CONTRACT_ADDRESS_ETHEREUM: OptionType = (
+    "[init code] --init-code=[hex] 'Computes the CREATE2 address of a contract with a specified init code'",
+    &["create3", "implementation", "init code hash"],
+    &[],
+    &["salt"],
+);
+pub const INIT_CODE_HASH_CONTRACT_ADDRESS_ETHEREUM: OptionType = (
+    "[init code hash] --init-code-hash=[hash] 'Computes the CREATE2 address of a contract with a specified keccak256 hash of its init code'",
+    &["create3", "implementation", "init code"],
+    &[],
+    &["salt"],
+);
+pub const NONCE_CONTRACT_ADDRESS_ETHEREUM: OptionType = (
+    "[nonce] --nonce=[nonce] 'Computes the CREATE address of a contract deployed with a specified nonce of the deployer'",
+    &["salt"],
+    &[],
+    &[],
+);
+pub const SALT_CONTRACT_ADDRESS_ETHEREUM: OptionType = (
+    "[salt] --salt=[salt] 'Computes the address of a contract deployed with a specified 32-byte salt in hex'",
+    &["nonce"],
+    &[],
+    &[],
+);
+
+// Convert key
+
+pub const FROM_CONVERT_KEY: OptionType = (
+    "<from> --from=<from> 'Converts the input from a specified representation'",
+    &[],
+    &["entropy", "hex", "mnemonic", "public", "seed", "wif", "xprv", "xpub"],
+    &[],
+);
+pub const INPUT_CONVERT_KEY: OptionType = (
+    "<input> 'Converts a specified key, seed, or mnemonic (in quotes)'",
+    &[],
+    &[],
+    &[],
+);
+pub const NETWORK_CONVERT_KEY: OptionType = (
+    "[network] -n --network=[network] 'Converts the input for a specified network'",
+    &[],
+    &["mainnet", "testnet"],
+    &[],
+);
+pub const PASSWORD_CONVERT_KEY: OptionType = (
+    "[password] -p --password=[password] 'Converts a mnemonic with a specified password'",
+    &[],
+    &[],
+    &[],
+);
+pub const TO_CONVERT_KEY: OptionType = (
+    "<to> --to=<to> 'Converts the input to a specified representation'",
+    &[],
+    &[
+        "compressed",
+        "entropy",
+        "hex",
+        "mnemonic",
+        "seed",
+        "uncompressed",
+        "wif",
+        "wif-uncompressed",
+        "xprv",
+        "xpub",
+        "yprv",
+        "ypub",
+        "zprv",
+        "zpub",
+    ],
+    &[],
+);
+
+// Decrypt note
+
+pub const CIPHERTEXT_DECRYPT_NOTE_ZCASH: OptionType = (
+    "<ciphertext> --ciphertext=<note ciphertext> 'Decrypts a specified note ciphertext of a Sapling output (hex)'",
+    &[],
+    &[],
+    &[],
+);
+pub const CMU_DECRYPT_NOTE_ZCASH: OptionType = (
+    "[cmu] --cmu=[note commitment] 'Checks the note against a specified note commitment of the Sapling output (hex)'",
+    &[],
+    &[],
+    &[],
+);
+pub const EPK_DECRYPT_NOTE_ZCASH: OptionType = (
+    "<epk> --epk=<ephemeral key> 'Decrypts a note with a specified ephemeral public key of the Sapling output (hex)'",
+    &[],
+    &[],
+    &[],
+);
+pub const IVK_DECRYPT_NOTE_ZCASH: OptionType = (
+    "<ivk> --ivk=<incoming viewing key> 'Decrypts a note with a specified incoming viewing key (hex) or extended public key'",
+    &[],
+    &[],
+    &[],
+);
+pub const NETWORK_DECRYPT_NOTE_ZCASH: OptionType = (
+    "[network] -n --network=[network] 'Decrypts a note for a specified network'",
+    &[],
+    &["mainnet", "testnet"],
+    &[],
+);
+
+// Decode
+
+pub const ENCODING_DECODE: OptionType = (
+    "<encoding> -e --encoding=<encoding> 'Decodes the input from a specified encoding'",
+    &[],
+    &["base32", "base58", "base58check", "bech32", "bech32m", "cashaddr"],
+    &[],
+);
+pub const INPUT_DECODE: OptionType = ("<input> 'Decodes a specified string'", &[], &[], &[]);
+
+// Derive
+
+pub const CHARSET_DERIVE: OptionType = (
+    "[charset] --charset=[charset] 'Derives the password in a specified character set (default: base64)'",
+    &[],
+    &["base64", "base85"],
+    &[],
+);
+pub const COUNTER_DERIVE: OptionType = (
+    "[counter] --counter=[counter] 'Derives the password of a specified counter, incremented to change it (default: 1)'",
+    &[],
+    &[],
+    &[],
+);
+pub const IDENTITY_DERIVE: OptionType = (
+    "[identity] --identity=[identity] 'Derives the keys of a specified identity, such as user@host or a PGP user ID (default: wagyu)'",
+    &[],
+    &[],
+    &[],
+);
+pub const JSON_DERIVE: OptionType = ("[json] -j --json 'Prints the keys in JSON format'", &[], &[], &[]);
+pub const KEYS_DERIVE: OptionType = (
+    "<keys>... 'Derives the specified keys'",
+    &[],
+    &["age", "password", "pgp", "ssh", "totp"],
+    &[],
+);
+pub const LABEL_DERIVE: OptionType = (
+    "[label] --label=[label] 'Derives the TOTP secret of a specified label, such as github (default: wagyu)'",
+    &[],
+    &[],
+    &[],
+);
+pub const LENGTH_DERIVE: OptionType = (
+    "[length] --length=[length] 'Derives the password of a specified length (default: 20)'",
+    &[],
+    &[],
+    &[],
+);
+pub const MNEMONIC_DERIVE: OptionType = (
+    "<mnemonic> -m --mnemonic=<\"mnemonic\"> 'Derives the keys of a specified BIP-39 mnemonic (in quotes)'",
+    &[],
+    &[],
+    &[],
+);
+pub const PASSWORD_DERIVE: OptionType = (
+    "[password] -p --password=[password] 'Derives the keys with a specified BIP-39 password'",
+    &[],
+    &[],
+    &[],
+);
+pub const SITE_DERIVE: OptionType = (
+    "[site] --site=[site] 'Derives the password of a specified site, such as example.com (default: wagyu)'",
+    &[],
+    &[],
+    &[],
+);
+
+// Derive addresses
+
+pub const CHECKPOINT_DERIVE_ADDRESSES: OptionType = (
+    "[checkpoint] --checkpoint=[file] 'Writes the next index of the batch to a specified checkpoint file after each chunk, to resume an interrupted batch'",
+    &[],
+    &[],
+    &[],
+);
+pub const CHUNK_SIZE_DERIVE_ADDRESSES: OptionType = (
+    "[chunk size] --chunk-size=[size] 'Derives the addresses in chunks of a specified number of addresses, held in memory at once (default: 10000)'",
+    &[],
+    &[],
+    &[],
+);
+pub const COUNT_DERIVE_ADDRESSES: OptionType = (
+    "<count> --count=<count> 'Derives the addresses of a specified number of indices, from index 0'",
+    &[],
+    &[],
+    &[],
+);
+pub const FORMAT_DERIVE_ADDRESSES: OptionType = (
+    "[format] -f --format=[format] 'Derives the addresses in a specified format, overriding the format of the extended key version bytes'",
+    &[],
+    &["bech32", "legacy", "segwit", "taproot"],
+    &[],
+);
+pub const OUTPUT_DERIVE_ADDRESSES: OptionType = (
+    "[output] --output=[file] 'Writes the addresses to a specified file, instead of standard output'",
+    &[],
+    &[],
+    &[],
+);
+pub const PATH_DERIVE_ADDRESSES: OptionType = (
+    "[path] --path=[path] 'Derives the children of a specified non-hardened path of the extended public key, such as m/0 for the receive chain of an account (default: m)'",
+    &[],
+    &[],
+    &[],
+);
+pub const RESUME_FROM_DERIVE_ADDRESSES: OptionType = (
+    "[resume from] --resume-from=[index] 'Resumes the batch from a specified index, such as the next index of its checkpoint file, appending to the output file'",
+    &[],
+    &[],
+    &[],
+);
+pub const THREADS_DERIVE_ADDRESSES: OptionType = (
+    "[threads] --threads=[threads] 'Derives each chunk with a specified number of threads (default: the number of CPUs)'",
+    &[],
+    &[],
+    &[],
+);
+pub const XPUB_DERIVE_ADDRESSES: OptionType = (
+    "<xpub> --xpub=<extended public key> 'Derives the addresses of a specified extended public key'",
+    &[],
+    &[],
+    &[],
+);
+
+// Discover paths
+
+pub const ADDRESS_DISCOVER_PATHS: OptionType = (
+    "<address> --address=<address> 'Searches the derivation paths of common wallets for a specified address'",
+    &[],
+    &[],
+    &[],
+);
+pub const LANGUAGE_DISCOVER_PATHS: OptionType = (
+    "[language] -l --language=[language] 'Searches the derivation paths of a mnemonic with a specified language'",
+    &[],
+    &[
+        "chinese_simplified",
+        "chinese_traditional",
+        "english",
+        "french",
+        "italian",
+        "japanese",
+        "korean",
+        "spanish",
+    ],
+    &[],
+);
+pub const MNEMONIC_DISCOVER_PATHS: OptionType = (
+    "<mnemonic> -m --mnemonic=<\"mnemonic\"> 'Searches the derivation paths of a specified mnemonic (in quotes)'",
+    &[],
+    &[],
+    &[],
+);
+pub const PASSWORD_DISCOVER_PATHS: OptionType = (
+    "[password] -p --password=[password] 'Searches the derivation paths of a mnemonic with a specified password'",
+    &[],
+    &[],
+    &[],
+);
+pub const ACCOUNT_DISCOVER_PATHS_BITCOIN: OptionType = (
+    "[account] -a --account=[account] 'Searches a specified account number or range of each purpose (default: 0-4)'",
+    &[],
+    &[],
+    &[],
+);
+pub const EXTENDED_PRIVATE_KEY_DISCOVER_PATHS_BITCOIN: OptionType = (
+    "[extended private key] --extended-private-key=[extended private key] 'Searches the derivation paths of a specified master extended private key'",
+    &["mnemonic"],
+    &[],
+    &[],
+);
+pub const SEARCH_DEPTH_DISCOVER_PATHS: OptionType = (
+    "[search depth] --search-depth=[depth] 'Searches a specified number of indices of each derivation path (default: 20)'",
+    &[],
+    &[],
+    &[],
+);
+pub const MNEMONIC_DISCOVER_PATHS_BITCOIN: OptionType = (
+    "[mnemonic] -m --mnemonic=[\"mnemonic\"] 'Searches the derivation paths of a specified mnemonic (in quotes)'",
+    &["extended private key"],
+    &[],
+    &[],
+);
+pub const SEARCH_DEPTH_DISCOVER_PATHS_BITCOIN: OptionType = (
+    "[search depth] --search-depth=[depth] 'Searches a specified number of indices of each chain (default: 100)'",
+    &[],
+    &[],
+    &[],
+);
+pub const THREADS_DISCOVER_PATHS_BITCOIN: OptionType = (
+    "[threads] --threads=[threads] 'Searches with a specified number of threads (default: the number of CPUs)'",
+    &[],
+    &[],
+    &[],
+);
+
+// Encode
+
+pub const ENCODING_ENCODE: OptionType = (
+    "<encoding> -e --encoding=<encoding> 'Encodes the input in a specified encoding'",
+    &[],
+    &["base32", "base58", "base58check", "bech32", "bech32m", "cashaddr"],
+    &[],
+);
+pub const INPUT_ENCODE: OptionType = ("<input> 'Encodes a specified hex string'", &[], &[], &[]);
+pub const PREFIX_ENCODE: OptionType = (
+    "[prefix] --prefix=[prefix] 'Encodes the input with a specified human-readable part or prefix (e.g. bc, or bitcoincash)'",
+    &[],
+    &[],
+    &[],
+);
+
+// Entropy grid
+
+pub const ENTROPY_GRID_BITCOIN: OptionType = (
+    "[entropy] --entropy=[hex] 'Generates the grid of a specified entropy of 16 to 32 bytes (default: random)'",
+    &["mnemonic"],
+    &[],
+    &[],
+);
+pub const MNEMONIC_GRID_BITCOIN: OptionType = (
+    "[mnemonic] -m --mnemonic=[\"mnemonic\"] 'Restores the grid of a specified BIP-39 mnemonic (in quotes) of its entropy'",
+    &["entropy"],
+    &[],
+    &[],
+);
+pub const PATTERN_GRID_BITCOIN: OptionType = (
+    "[pattern] --pattern=[cells] 'Generates the mnemonic of a specified pattern of 11 or 23 cells (e.g. \"A1 B2 C3\"), with its valid final words'",
+    &[],
+    &[],
+    &[],
+);
+pub const PDF_GRID_BITCOIN: OptionType = (
+    "[pdf] --pdf=[file] 'Exports the grid as a PDF to a specified file path, to be printed'",
+    &[],
+    &[],
+    &[],
+);
+
+// Estimate gas
+
+pub const ESTIMATE_GAS_ETHEREUM: OptionType = (
+    "[estimate gas] --estimate-gas 'Estimates the gas limit with eth_estimateGas if an RPC URL is specified, or with a default of the operation otherwise (21000 for a transfer, 65000 for an ERC-20 call)'",
+    &[],
+    &[],
+    &[],
+);
+
+pub const RPC_URL_ETHEREUM: OptionType = (
+    "[rpc url] --rpc-url=[url] 'Estimates the gas limit with the node at a specified http:// URL (default: $WAGYU_ETHEREUM_RPC)'",
+    &[],
+    &[],
+    &["estimate gas"],
+);
+
+// Generate
+
+pub const COINS_GENERATE: OptionType = (
+    "<coins> --coins=<coins> 'Generates wallets for the specified coins (comma separated) [possible values: bitcoin, ethereum, monero, nostr, zcash]'",
+    &[],
+    &[],
+    &[],
+);
+pub const COUNT_GENERATE: OptionType = (
+    "[count] -c --count=[count] 'Generates a specified number of wallets of each coin (default: 1)'",
+    &[],
+    &[],
+    &[],
+);
+pub const MNEMONIC_GENERATE: OptionType = (
+    "[mnemonic] -m --mnemonic=[\"mnemonic\"] 'Derives the wallets of each coin from a specified BIP-39 mnemonic (in quotes)'",
+    &["same mnemonic"],
+    &[],
+    &[],
+);
+pub const SAME_MNEMONIC_GENERATE: OptionType = (
+    "[same mnemonic] --same-mnemonic 'Derives the wallets of each coin from one new BIP-39 mnemonic'",
+    &["mnemonic"],
+    &[],
+    &[],
+);
+
+// Hash
+
+pub const ALGO_HASH: OptionType = (
+    "<algo> -a --algo=<algo> 'Hashes the input with a specified algorithm'",
+    &[],
+    &["blake2b", "keccak256", "ripemd160", "sha256d"],
+    &[],
+);
+pub const INPUT_HASH: OptionType = ("<input> 'Hashes a specified hex string or file'", &[], &[], &[]);
+
+// Inheritance
+
+pub const COMBINE_INHERITANCE: OptionType = (
+    "[combine] --combine=[share]... 'Recovers the mnemonic of the specified shares of a package (multiple occurrences)'",
+    &["encrypt to", "mnemonic", "note", "output", "shares", "threshold"],
+    &[],
+    &[],
+);
+pub const ENCRYPT_TO_INHERITANCE: OptionType = (
+    "[encrypt to] --encrypt-to=[recipients] 'Encrypts the secrets to the specified age or GPG recipient(s) (comma separated), instead of a passphrase'",
+    &["combine", "shares"],
+    &[],
+    &[],
+);
+pub const JSON_INHERITANCE: OptionType = ("[json] -j --json 'Prints the package in JSON format'", &[], &[], &[]);
+pub const MNEMONIC_INHERITANCE: OptionType = (
+    "[mnemonic] -m --mnemonic=[\"mnemonic\"] 'Generates the package of a specified English BIP-39 mnemonic (in quotes)'",
+    &["combine"],
+    &[],
+    &[],
+);
+pub const NOTE_INHERITANCE: OptionType = (
+    "[note] --note=[\"note\"] 'Adds a specified note of the owner to the instructions of the package'",
+    &["combine"],
+    &[],
+    &[],
+);
+pub const OUTPUT_INHERITANCE: OptionType = (
+    "[output] -o --output=[directory] 'Writes the package to a specified directory (default: inheritance)'",
+    &["combine"],
+    &[],
+    &[],
+);
+pub const PASSWORD_INHERITANCE: OptionType = (
+    "[password] -p --password=[password] 'Generates or recovers the package with a specified BIP-39 password'",
+    &[],
+    &[],
+    &[],
+);
+pub const SHARES_INHERITANCE: OptionType = (
+    "[shares] --shares=[count] 'Splits the mnemonic into a specified number of Shamir shares, instead of encrypting it'",
+    &["combine", "encrypt to"],
+    &[],
+    &["threshold"],
+);
+pub const THRESHOLD_INHERITANCE: OptionType = (
+    "[threshold] --threshold=[count] 'Recovers the mnemonic from a specified number of the shares'",
+    &["combine"],
+    &[],
+    &["shares"],
+);
+
+// Mnemonic entropy
+
+pub const ENTROPY_FROM_ENTROPY: OptionType = (
+    "<entropy> 'Returns the mnemonic of a specified hex entropy (16, 20, 24, 28, or 32 bytes)'",
+    &[],
+    &[],
+    &[],
+);
+pub const JSON_MNEMONIC_ENTROPY: OptionType = (
+    "[json] -j --json 'Prints the mnemonic and its entropy in JSON format'",
+    &[],
+    &[],
+    &[],
+);
+pub const LANGUAGE_FROM_ENTROPY: OptionType = (
+    "[language] -l --language=[language] 'Returns the mnemonic in a specified language (default: english)'",
+    &[],
+    &[
+        "chinese_simplified",
+        "chinese_traditional",
+        "english",
+        "french",
+        "italian",
+        "japanese",
+        "korean",
+        "spanish",
+    ],
+    &[],
+);
+pub const MNEMONIC_TO_ENTROPY: OptionType = (
+    "<mnemonic> 'Returns the entropy and checksum of a specified BIP-39 mnemonic (in quotes)'",
+    &[],
+    &[],
+    &[],
+);
+
+// Mnemonic wordlist
+
+pub const INDEX_WORDLIST: OptionType = (
+    "[index] -i --index=[index] 'Prints the word of a specified index (0 to 2047)'",
+    &["prefix"],
+    &[],
+    &[],
+);
+pub const JSON_WORDLIST: OptionType = ("[json] -j --json 'Prints the words in JSON format'", &[], &[], &[]);
+pub const LANGUAGE_WORDLIST: OptionType = (
+    "[language] -l --language=[language] 'Prints the wordlist of a specified language (default: english)'",
+    &[],
+    &[
+        "chinese_simplified",
+        "chinese_traditional",
+        "english",
+        "french",
+        "italian",
+        "japanese",
+        "korean",
+        "spanish",
+    ],
+    &[],
+);
+pub const PREFIX_WORDLIST: OptionType = (
+    "[prefix] -p --prefix=[prefix] 'Prints the words starting with a specified prefix'",
+    &["index"],
+    &[],
+    &[],
+);
+
+// Open
+
+pub const FILE_OPEN: OptionType = ("<file> 'Opens a specified wallet file'", &[], &[], &[]);
+
+// Portfolio
+
+pub const JSON_PORTFOLIO: OptionType = ("[json] -j --json 'Prints the portfolio in JSON format'", &[], &[], &[]);
+pub const MNEMONIC_PORTFOLIO: OptionType = (
+    "<mnemonic> -m --mnemonic=<\"mnemonic\"> 'Derives the first address of each coin of a specified BIP-39 mnemonic (in quotes)'",
+    &[],
+    &[],
+    &[],
+);
+pub const PASSWORD_PORTFOLIO: OptionType = (
+    "[password] -p --password=[password] 'Derives the addresses with a specified BIP-39 password'",
+    &[],
+    &[],
+    &[],
+);
+
+// Schema
+
+pub const OUTPUT_SCHEMA: OptionType = (
+    "<output> 'Prints the JSON Schema of a specified output'",
+    &[],
+    &[
+        "bitcoin",
+        "ceremony-summary",
+        "ethereum",
+        "manifest",
+        "monero",
+        "nostr",
+        "unsigned-transaction",
+        "wallet-file",
+        "zcash",
+    ],
+    &[],
+);
+
+// SeedQR
+
+pub const COMPACT_SEEDQR: OptionType = (
+    "[compact] --compact 'Exports the mnemonic as a CompactSeedQR'",
+    &["payload"],
+    &[],
+    &[],
+);
+pub const JSON_SEEDQR: OptionType = ("[json] -j --json 'Prints the SeedQR in JSON format'", &[], &[], &[]);
+pub const MNEMONIC_SEEDQR: OptionType = (
+    "[mnemonic] -m --mnemonic=[\"mnemonic\"] 'Exports a specified BIP-39 mnemonic (in quotes) as a SeedQR'",
+    &["payload"],
+    &[],
+    &[],
+);
+pub const PAYLOAD_SEEDQR: OptionType = (
+    "[payload] --payload=[payload] 'Imports the mnemonic of a specified scanned payload (SeedQR digits, or CompactSeedQR hex)'",
+    &["mnemonic"],
+    &[],
+    &[],
+);
+
+// Self-test
+
+pub const JSON_SELFTEST: OptionType = ("[json] -j --json 'Prints the results in JSON format'", &[], &[], &[]);
+
+// Serve
+
+pub const AUDIT_LOG_SERVE: OptionType = (
+    "[audit log] --audit-log=[file] 'Records each signing request, without secrets, to a specified file'",
+    &[],
+    &[],
+    &[],
+);
+pub const LISTEN_SERVE: OptionType = (
+    "[listen] --listen=[address] 'Serves the HTTP API on a specified address (default: 127.0.0.1:8080)'",
+    &[],
+    &[],
+    &[],
+);
+pub const TOKEN_SERVE: OptionType = (
+    "[token] --token=[token] 'Requires a specified bearer token on each request (default: $WAGYU_TOKEN)'",
+    &[],
+    &[],
+    &[],
+);
+pub const WALLET_FILE_SERVE: OptionType = (
+    "[wallet file] --wallet-file=[file] 'Signs transactions with the private keys of a specified wallet file'",
+    &[],
+    &[],
+    &[],
+);
+
+// Multisig
+
+pub const EXTRA_INFO_MULTISIG_MONERO: OptionType = (
+    "[extra info] --extra-info=[multisig info] 'Completes an (N-1)-of-N multisig wallet with the second round multisig info of the other participants (comma separated)'",
+    &[],
+    &[],
+    &["info"],
+);
+pub const INFO_MULTISIG_MONERO: OptionType = (
+    "[info] --info=[multisig info] 'Exchanges keys with the first round multisig info of the other participants (comma separated)'",
+    &[],
+    &[],
+    &["private spend"],
+);
+pub const PRIVATE_SPEND_KEY_MULTISIG_MONERO: OptionType = (
+    "[private spend] --private-spend=[private spend key] 'Generates the multisig info of a participant with a specified private spend key'",
+    &[],
+    &[],
+    &[],
+);
+pub const THRESHOLD_MULTISIG_MONERO: OptionType = (
+    "[threshold] --threshold=[threshold] 'Generates an M-of-N multisig wallet with a specified threshold (N-of-N or (N-1)-of-N, defaults to N)'",
+    &[],
+    &[],
+    &["info"],
+);
+
+// Node ID
+
+pub const IP_NODE_ID_ETHEREUM: OptionType = (
+    "[ip] --ip=[ip] 'Generates the enode URL and node record of a node with a specified IPv4 address'",
+    &[],
+    &[],
+    &[],
+);
+pub const PRIVATE_KEY_NODE_ID_ETHEREUM: OptionType = (
+    "<private key> --private-key=<private key> 'Derives the node ID, node record, and peer ID of a specified node private key'",
+    &[],
+    &[],
+    &[],
+);
+pub const SEQ_NODE_ID_ETHEREUM: OptionType = (
+    "[seq] --seq=[seq] 'Signs the node record with a specified sequence number (default: 1)'",
+    &[],
+    &[],
+    &[],
+);
+pub const TCP_PORT_NODE_ID_ETHEREUM: OptionType = (
+    "[tcp port] --tcp-port=[port] 'Generates the enode URL and node record of a node with a specified TCP port (default: 30303 with --ip)'",
+    &[],
+    &[],
+    &[],
+);
+pub const UDP_PORT_NODE_ID_ETHEREUM: OptionType = (
+    "[udp port] --udp-port=[port] 'Generates the enode URL and node record of a node with a specified UDP discovery port (default: the TCP port)'",
+    &[],
+    &[],
+    &[],
+);
+
+// Nostr
+
+pub const ACCOUNT_NOSTR: OptionType = (
+    "[account] -a --account=[account] 'Derives the key of a specified NIP-06 account number (default: 0)'",
+    &[],
+    &[],
+    &[],
+);
+pub const ID_NOTE_NOSTR: OptionType = (
+    "<id> --id=<event id> 'Encodes a specified event ID (in hex) as a note, or decodes a specified note'",
+    &[],
+    &[],
+    &[],
+);
+pub const MNEMONIC_HD_NOSTR: OptionType = (
+    "[mnemonic] -m --mnemonic=[\"mnemonic\"] 'Derives the key of a specified BIP-39 mnemonic (in quotes) instead of a new mnemonic'",
+    &["word count"],
+    &[],
+    &[],
+);
+pub const PRIVATE_NOSTR: OptionType = (
+    "[private] --private=[private key] 'Imports a key for a specified private key (in hex or nsec)'",
+    &["public"],
+    &[],
+    &[],
+);
+pub const PUBLIC_NOSTR: OptionType = (
+    "[public] --public=[public key] 'Imports a partial key for a specified x-only public key (in hex or npub)'",
+    &["private"],
+    &[],
+    &[],
+);
+
+// Payment ID
+
+pub const ADDRESS_PAYMENT_ID_MONERO: OptionType = (
+    "[address] --address=[address] 'Encrypts the payment ID of a transaction to a specified recipient (integrated) address'",
+    &["encrypted"],
+    &[],
+    &["tx key"],
+);
+pub const ENCRYPTED_PAYMENT_ID_MONERO: OptionType = (
+    "[encrypted] --encrypted=[encrypted payment id] 'Decrypts a specified encrypted payment ID of a transaction'",
+    &["address", "payment id"],
+    &[],
+    &["private view", "tx public key"],
+);
+pub const PAYMENT_ID_ENCRYPT_MONERO: OptionType = (
+    "[payment id] --payment-id=[payment id] 'Encrypts a specified payment ID (defaults to the payment ID of an integrated address)'",
+    &["encrypted"],
+    &[],
+    &["address"],
+);
+pub const PRIVATE_VIEW_KEY_PAYMENT_ID_MONERO: OptionType = (
+    "[private view] --private-view=[private view key] 'Decrypts a payment ID with the specified private view key of the recipient'",
+    &["address", "tx key"],
+    &[],
+    &["encrypted"],
+);
+pub const TX_KEY_PAYMENT_ID_MONERO: OptionType = (
+    "[tx key] --tx-key=[transaction private key] 'Encrypts a payment ID with the specified transaction private key'",
+    &["encrypted", "private view"],
+    &[],
+    &["address"],
+);
+pub const TX_PUBLIC_KEY_PAYMENT_ID_MONERO: OptionType = (
+    "[tx public key] --tx-public-key=[transaction public key] 'Decrypts a payment ID with the specified transaction public key'",
+    &["address", "tx key"],
+    &[],
+    &["encrypted"],
+);
+
+// Prove
+
+pub const ADDRESS_PROVE_MONERO: OptionType = (
+    "[address] --address=[address] 'Proves or checks a transaction to a specified recipient address'",
+    &[],
+    &[],
+    &["tx id"],
+);
+
+pub const MESSAGE_PROVE_MONERO: OptionType = (
+    "[message] --message=[message] 'Proves or checks a transaction with a specified message (optional)'",
+    &[],
+    &[],
+    &[],
+);
+
+pub const PRIVATE_VIEW_KEY_PROVE_MONERO: OptionType = (
+    "[private view] --private-view=[private view key] 'Proves an inbound transaction with a specified private view key'",
+    &["tx key"],
+    &[],
+    &["tx public key"],
+);
+
+pub const SIGNATURE_CHECK_MONERO: OptionType = (
+    "[signature] --signature=[signature] 'Checks a specified transaction proof (OutProofV2 or InProofV2)'",
+    &[],
+    &[],
+    &["tx public key"],
+);
+
+pub const TX_ID_PROVE_MONERO: OptionType = (
+    "[tx id] --tx-id=[transaction id] 'Proves or checks a transaction with a specified transaction id'",
+    &[],
+    &[],
+    &["address"],
+);
+
+pub const TX_KEY_PROVE_MONERO: OptionType = (
+    "[tx key] --tx-key=[transaction private key] 'Proves an outbound transaction with the specified transaction private key(s) (comma separated)'",
+    &["private view"],
+    &[],
+    &[],
+);
+
+pub const TX_PUBLIC_KEY_MONERO: OptionType = (
+    "[tx public key] --tx-public-key=[transaction public key] 'Proves or checks a transaction with the specified transaction public key(s) (comma separated)'",
+    &[],
+    &[],
+    &[],
+);
+
+// Recover
+
+pub const NETWORK_RECOVER_BITCOIN: OptionType = (
+    "[network] -n --network=[network] 'Recovers the keys for a specified network'",
+    &[],
+    &["mainnet", "testnet"],
+    &[],
+);
+pub const PASSPHRASE_RECOVER_BITCOIN: OptionType = (
+    "[passphrase] --passphrase=[passphrase] 'Decrypts the encrypted keys with a specified wallet passphrase (default: prompted for)'",
+    &[],
+    &[],
+    &[],
+);
+pub const WALLET_DAT_RECOVER_BITCOIN: OptionType = (
+    "<wallet dat> --wallet-dat=<path> 'Recovers the private keys of a specified legacy Bitcoin Core wallet.dat'",
+    &[],
+    &[],
+    &[],
+);
+
+// Sprout
+
+pub const DESTINATION_SPROUT_ZCASH: OptionType = (
+    "[destination] --destination=[address] 'Migrates the funds of the Sprout keys to a specified Sapling address'",
+    &[],
+    &[],
+    &[],
+);
+pub const DUMPFILE_SPROUT_ZCASH: OptionType = (
+    "[dumpfile] --dumpfile=[path] 'Reads the Sprout keys of a specified zcashd wallet dump (z_exportwallet), verifying the address of each key'",
+    &["spending key"],
+    &[],
+    &[],
+);
+pub const NETWORK_SPROUT_ZCASH: OptionType = (
+    "[network] -n --network=[network] 'Reads a raw spending key in hex for a specified network'",
+    &[],
+    &["mainnet", "testnet"],
+    &[],
+);
+pub const SPENDING_KEY_SPROUT_ZCASH: OptionType = (
+    "[spending key] --spending-key=[key] 'Reads a specified Sprout spending key (z_exportkey), or a raw 252-bit spending key in hex'",
+    &["dumpfile"],
+    &[],
+    &[],
+);
+
+// FROST
+
+pub const IDENTIFIER_FROST_BITCOIN: OptionType = (
+    "[identifier] --identifier=[identifier] 'Generates the secret package of a participant with a specified identifier (from 1 to N)'",
+    &["secret package", "key package"],
+    &[],
+    &["threshold", "participants"],
+);
+pub const KEY_PACKAGE_FROST_BITCOIN: OptionType = (
+    "[key package] --key-package=[key package] 'Generates a nonce or signs with a specified key package of a participant'",
+    &["secret package"],
+    &[],
+    &[],
+);
+pub const MESSAGE_FROST_BITCOIN: OptionType = (
+    "[message] --message=[message] 'Generates a nonce or signs a specified message in hex, such as the signature hash of a taproot input'",
+    &[],
+    &[],
+    &["key package"],
+);
+pub const NETWORK_FROST_BITCOIN: OptionType = (
+    "[network] -n --network=[network] 'Generates a threshold address for a specified network'",
+    &[],
+    &["mainnet", "testnet"],
+    &[],
+);
+pub const NONCES_FROST_BITCOIN: OptionType = (
+    "[nonces] --nonces=[public nonces] 'Signs or aggregates with the specified public nonces of the signers (comma separated)'",
+    &[],
+    &[],
+    &["message"],
+);
+pub const PACKAGES_FROST_BITCOIN: OptionType = (
+    "[packages] --packages=[public packages] 'Generates the secret shares of the other participants, after verifying their specified public packages (comma separated)'",
+    &[],
+    &[],
+    &["secret package"],
+);
+pub const PARTIAL_SIGNATURES_FROST_BITCOIN: OptionType = (
+    "[partial signatures] --partial-signatures=[partial signatures] 'Aggregates the specified partial signatures of the signers into a Schnorr signature (comma separated)'",
+    &["secret nonce file"],
+    &[],
+    &["nonces"],
+);
+pub const PARTICIPANTS_FROST_BITCOIN: OptionType = (
+    "[participants] --participants=[participants] 'Generates a threshold key with a specified number of participants'",
+    &[],
+    &[],
+    &["identifier"],
+);
+pub const SECRET_NONCE_FILE_FROST_BITCOIN: OptionType = (
+    "[secret nonce file] --secret-nonce-file=[file] 'Writes the secret nonce of the participant to a specified new file, or signs with the secret nonce of the file, which is then marked used'",
+    &[],
+    &[],
+    &["message"],
+);
+pub const SECRET_PACKAGE_FROST_BITCOIN: OptionType = (
+    "[secret package] --secret-package=[secret package] 'Generates the secret shares, or the key package, of a participant with its specified secret package'",
+    &[],
+    &[],
+    &["packages"],
+);
+pub const SHARES_FROST_BITCOIN: OptionType = (
+    "[shares] --shares=[secret shares] 'Generates the key package of a participant with the specified secret shares sent to it by the other participants (comma separated)'",
+    &[],
+    &[],
+    &["packages"],
+);
+pub const THRESHOLD_FROST_BITCOIN: OptionType = (
+    "[threshold] --threshold=[threshold] 'Generates an M-of-N threshold key with a specified threshold (from 2 to N)'",
+    &[],
+    &[],
+    &["identifier"],
+);
+
+// HTLC
+
+pub const HASH_HTLC_BITCOIN: OptionType = (
+    "[hash] --hash=[hash] 'Generates a contract claimable with the preimage of a specified SHA-256 hash in hex'",
+    &[],
+    &[],
+    &["locktime", "recipient pubkey", "refund pubkey"],
+);
+pub const LOCKTIME_HTLC_BITCOIN: OptionType = (
+    "[locktime] --locktime=[height|time] 'Refunds the contract after a specified block height or Unix timestamp, or after a specified number of blocks or seconds (with an s suffix) if relative'",
+    &[],
+    &[],
+    &[],
+);
+pub const NETWORK_HTLC_BITCOIN: OptionType = (
+    "[network] -n --network=[network] 'Generates a contract address for a specified network'",
+    &[],
+    &["mainnet", "testnet"],
+    &[],
+);
+pub const RECIPIENT_PUBKEY_HTLC_BITCOIN: OptionType = (
+    "[recipient pubkey] --recipient-pubkey=[public key] 'Generates a contract claimable by a specified public key with the preimage of the hash'",
+    &[],
+    &[],
+    &[],
+);
+pub const REFUND_PUBKEY_HTLC_BITCOIN: OptionType = (
+    "[refund pubkey] --refund-pubkey=[public key] 'Generates a contract refundable to a specified public key after the locktime'",
+    &[],
+    &[],
+    &[],
+);
+pub const RELATIVE_HTLC_BITCOIN: OptionType = (
+    "[relative] --relative 'Refunds the contract after a relative locktime (CHECKSEQUENCEVERIFY), instead of an absolute locktime (CHECKLOCKTIMEVERIFY)'",
+    &[],
+    &[],
+    &["locktime"],
+);
+
+// Miniscript
+
+pub const KEYS_MINISCRIPT_BITCOIN: OptionType = (
+    "[keys] --keys=[name=public key] 'Names the public keys of the policy, such as A=02...,B=03... (comma separated)'",
+    &[],
+    &[],
+    &["policy"],
+);
+pub const NETWORK_MINISCRIPT_BITCOIN: OptionType = (
+    "[network] -n --network=[network] 'Generates a miniscript address for a specified network'",
+    &[],
+    &["mainnet", "testnet"],
+    &[],
+);
+pub const POLICY_MINISCRIPT_BITCOIN: OptionType = (
+    "[policy] --policy=[policy] 'Compiles a specified spending policy, such as and(pk(A),or(pk(B),after(1000)))'",
+    &[],
+    &[],
+    &[],
+);
+
+// MuSig
+
+pub const MESSAGE_MUSIG_BITCOIN: OptionType = (
+    "[message] --message=[message] 'Generates a nonce or signs a specified message in hex, such as the signature hash of a taproot input'",
+    &[],
+    &[],
+    &["public keys"],
+);
+pub const NETWORK_MUSIG_BITCOIN: OptionType = (
+    "[network] -n --network=[network] 'Generates an aggregate address for a specified network'",
+    &[],
+    &["mainnet", "testnet"],
+    &[],
+);
+pub const NONCES_MUSIG_BITCOIN: OptionType = (
+    "[nonces] --nonces=[public nonces] 'Signs or aggregates with the specified public nonces of the signers (comma separated)'",
+    &[],
+    &[],
+    &["message"],
+);
+pub const PARTIAL_SIGNATURES_MUSIG_BITCOIN: OptionType = (
+    "[partial signatures] --partial-signatures=[partial signatures] 'Aggregates the specified partial signatures of the signers into a Schnorr signature (comma separated)'",
+    &["private key", "secret nonce file"],
+    &[],
+    &["nonces"],
+);
+pub const PRIVATE_KEY_MUSIG_BITCOIN: OptionType = (
+    "[private key] --private-key=[private key] 'Generates a nonce, or signs with a secret nonce, with a specified private key of a signer'",
+    &[],
+    &[],
+    &["message", "secret nonce file"],
+);
+pub const PUBLIC_KEYS_MUSIG_BITCOIN: OptionType = (
+    "[public keys] --public-keys=[public keys] 'Aggregates the specified public keys of the signers into a taproot key (comma separated)'",
+    &[],
+    &[],
+    &[],
+);
+pub const SECRET_NONCE_FILE_MUSIG_BITCOIN: OptionType = (
+    "[secret nonce file] --secret-nonce-file=[file] 'Writes the secret nonce of the signer to a specified new file, or signs with the secret nonce of the file, which is then marked used'",
+    &[],
+    &[],
+    &["private key"],
+);
+
+// Timelock
+
+pub const LOCKTIME_TIMELOCK_BITCOIN: OptionType = (
+    "[locktime] --locktime=[height|time] 'Locks the script until a specified block height or Unix timestamp, or for a specified number of blocks or seconds (with an s suffix) if relative'",
+    &[],
+    &[],
+    &[],
+);
+pub const NETWORK_TIMELOCK_BITCOIN: OptionType = (
+    "[network] -n --network=[network] 'Generates a time-locked address for a specified network'",
+    &[],
+    &["mainnet", "testnet"],
+    &[],
+);
+pub const PUBKEY_TIMELOCK_BITCOIN: OptionType = (
+    "[pubkey] --pubkey=[public key] 'Generates a time-locked script spendable by a specified public key'",
+    &[],
+    &[],
+    &[],
+);
+pub const RELATIVE_TIMELOCK_BITCOIN: OptionType = (
+    "[relative] --relative 'Locks the script with a relative locktime (CHECKSEQUENCEVERIFY), instead of an absolute locktime (CHECKLOCKTIMEVERIFY)'",
+    &[],
+    &[],
+    &["locktime"],
+);
+
+// Validate
+
+pub const ADDRESS_VALIDATE_MONERO: OptionType = ("<address> 'Validates a specified address'", &[], &[], &[]);
+
+// Verify
+
+pub const INPUT_VERIFY: OptionType = (
+    "<input> -i --input=<file> 'Verifies the wallets of a specified JSON output of wagyu'",
+    &[],
+    &[],
+    &[],
+);
+pub const JSON_VERIFY: OptionType = (
+    "[json] -j --json 'Prints the verification in JSON format'",
+    &[],
+    &[],
+    &[],
+);
+
+// Verify dice
+
+pub const JSON_VERIFY_DICE: OptionType = (
+    "[json] -j --json 'Prints the verification in JSON format'",
+    &[],
+    &[],
+    &[],
+);
+pub const METHOD_VERIFY_DICE: OptionType = (
+    "[method] --method=[method] 'Recomputes the entropy with a specified method: the SHA-256 hash of the rolls, or the rolls as a number in base 6 (default: raw)'",
+    &[],
+    &["base6", "raw"],
+    &[],
+);
+pub const MNEMONIC_VERIFY_DICE: OptionType = (
+    "<mnemonic> -m --mnemonic=<\"mnemonic\"> 'Verifies a specified BIP-39 mnemonic (in quotes) of a dice-roll worksheet'",
+    &[],
+    &[],
+    &[],
+);
+pub const ROLLS_VERIFY_DICE: OptionType = (
+    "<rolls> --rolls=<rolls> 'Recomputes the mnemonic from a specified string of dice rolls (1 to 6)'",
+    &[],
+    &[],
+    &[],
+);
+
+// Xpub
+
+pub const EXTENDED_PRIVATE_XPUB: OptionType = (
+    "[extended private key] --extended-private-key=[extended private key] 'Derives the extended public key of a specified extended private key'",
+    &[],
+    &[],
+    &[],
+);
+
+pub const PATH_XPUB: OptionType = (
+    "[path] --path=[path] 'Derives the extended public key at a specified derivation path (optional)'",
+    &[],
+    &[],
+    &["extended private key"],
+);
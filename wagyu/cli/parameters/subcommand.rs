@@ -5,16 +5,360 @@ use clap::AppSettings;
 // Format
 // (name, about, options, settings)
 
+/// The subcommands of wagyu besides the subcommands of its coins
+#[cfg(not(feature = "serve"))]
+pub const COMMANDS: &[SubCommandType] = &[
+    CEREMONY,
+    COMPLETIONS,
+    CONVERT_KEY,
+    DECODE,
+    DERIVE,
+    ENCODE,
+    GENERATE,
+    HASH,
+    INHERITANCE,
+    MNEMONIC,
+    OPEN,
+    PORTFOLIO,
+    SCHEMA,
+    SEEDQR,
+    SELFTEST,
+    VERIFY,
+    VERIFY_DICE,
+];
+/// The subcommands of wagyu besides the subcommands of its coins
+#[cfg(feature = "serve")]
+pub const COMMANDS: &[SubCommandType] = &[
+    CEREMONY,
+    COMPLETIONS,
+    CONVERT_KEY,
+    DECODE,
+    DERIVE,
+    ENCODE,
+    GENERATE,
+    HASH,
+    INHERITANCE,
+    MNEMONIC,
+    OPEN,
+    PORTFOLIO,
+    SCHEMA,
+    SEEDQR,
+    SELFTEST,
+    SERVE,
+    VERIFY,
+    VERIFY_DICE,
+];
+
+pub const BUMP_FEE_BITCOIN: SubCommandType = (
+    "bump-fee",
+    "Replaces a transaction with a higher fee, reducing its change (include -h for more options)",
+    &[
+        option::CHANGE_OUTPUT_BUMP_FEE_BITCOIN,
+        option::INPUTS_BUMP_FEE_BITCOIN,
+        option::NEW_FEERATE_BUMP_FEE_BITCOIN,
+        option::PRIVATE_KEY_BUMP_FEE_BITCOIN,
+        option::RAW_TX_BUMP_FEE_BITCOIN,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
+pub const CEREMONY: SubCommandType = (
+    "ceremony",
+    "Generates a mnemonic in a key ceremony with entropy from multiple operators and backup verification",
+    &[
+        option::CHECKS_CEREMONY,
+        option::LOCALE,
+        option::LOCK_MEMORY,
+        option::LOG_FILE,
+        option::OPERATORS_CEREMONY,
+        option::SUMMARY_CEREMONY,
+        option::VERIFY_CEREMONY,
+        option::WORDS_CEREMONY,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+    ],
+);
+
+pub const CHECK_PROOF_MONERO: SubCommandType = (
+    "check-proof",
+    "Checks a transaction proof (include -h for more options)",
+    &[
+        option::ADDRESS_PROVE_MONERO,
+        option::MESSAGE_PROVE_MONERO,
+        option::SIGNATURE_CHECK_MONERO,
+        option::TX_ID_PROVE_MONERO,
+        option::TX_PUBLIC_KEY_MONERO,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
+pub const COMPLETIONS: SubCommandType = (
+    "completions",
+    "Generates shell completions for wagyu, with its coins, subcommands, options, and derivation presets",
+    &[option::SHELL_COMPLETIONS],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
+pub const CONTAINS: SubCommandType = (
+    "contains",
+    "Searches an extended public key or mnemonic for an address, printing its derivation path (include -h for more options)",
+    &[
+        option::ADDRESS_CONTAINS,
+        option::LANGUAGE_CONTAINS,
+        option::MNEMONIC_CONTAINS,
+        option::PASSWORD_CONTAINS,
+        option::SEARCH_DEPTH_CONTAINS,
+        option::XPUB_CONTAINS,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
+pub const CONTRACT_ADDRESS_ETHEREUM: SubCommandType = (
+    "contract-address",
+    "Computes the address of a contract deployed with CREATE, CREATE2, an EIP-1167 minimal proxy, or CREATE3 (include -h for more options)",
+    &[
+        option::CREATE3_CONTRACT_ADDRESS_ETHEREUM,
+        option::DEPLOYER_CONTRACT_ADDRESS_ETHEREUM,
+        option::IMPLEMENTATION_CONTRACT_ADDRESS_ETHEREUM,
+        option::INIT_CODE_CONTRACT_ADDRESS_ETHEREUM,
+        option::INIT_CODE_HASH_CONTRACT_ADDRESS_ETHEREUM,
+        option::NONCE_CONTRACT_ADDRESS_ETHEREUM,
+        option::SALT_CONTRACT_ADDRESS_ETHEREUM,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
+pub const CONVERT_KEY: SubCommandType = (
+    "convert-key",
+    "Converts a key between entropy, mnemonic, seed, WIF, hex, public key, and SLIP-132 representations",
+    &[
+        option::FORCE,
+        option::FROM_CONVERT_KEY,
+        option::INPUT_CONVERT_KEY,
+        option::LOCALE,
+        option::LOCK_MEMORY,
+        option::NETWORK_CONVERT_KEY,
+        option::PASSWORD_CONVERT_KEY,
+        option::STRICT,
+        option::TO_CONVERT_KEY,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
+pub const CREATE_TX_BITCOIN: SubCommandType = (
+    "create-tx",
+    "Creates an unsigned transaction file for offline signing (include -h for more options)",
+    &[
+        option::ALLOW_DUST_TRANSACTION,
+        option::DUST_LIMIT_TRANSACTION,
+        option::INPUTS_CREATE_TX_BITCOIN,
+        option::LOCK_TIME_CREATE_TX_BITCOIN,
+        option::OP_RETURN_CREATE_TX_BITCOIN,
+        option::OUTPUTS_CREATE_TX_BITCOIN,
+        option::UNSIGNED_TX_CREATE,
+        option::VERSION_CREATE_TX_BITCOIN,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
+pub const CREATE_TX_ETHEREUM: SubCommandType = (
+    "create-tx",
+    "Creates an unsigned transaction file for offline signing (include -h for more options)",
+    &[
+        option::ESTIMATE_GAS_ETHEREUM,
+        option::NETWORK_CREATE_TX_ETHEREUM,
+        option::NONCE_FILE_CREATE_TX_ETHEREUM,
+        option::PARAMETERS_CREATE_TX_ETHEREUM,
+        option::RPC_URL_ETHEREUM,
+        option::UNSIGNED_TX_CREATE,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
+pub const DECRYPT_NOTE_ZCASH: SubCommandType = (
+    "decrypt-note",
+    "Decrypts a Sapling output note with an incoming viewing key (include -h for more options)",
+    &[
+        option::CIPHERTEXT_DECRYPT_NOTE_ZCASH,
+        option::CMU_DECRYPT_NOTE_ZCASH,
+        option::EPK_DECRYPT_NOTE_ZCASH,
+        option::IVK_DECRYPT_NOTE_ZCASH,
+        option::NETWORK_DECRYPT_NOTE_ZCASH,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
+pub const DERIVE_ADDRESSES_BITCOIN: SubCommandType = (
+    "derive-addresses",
+    "Derives the addresses of an extended public key as NDJSON, in chunks of bounded memory with checkpoints to resume an interrupted batch (include -h for more options)",
+    &[
+        option::CHECKPOINT_DERIVE_ADDRESSES,
+        option::CHUNK_SIZE_DERIVE_ADDRESSES,
+        option::COUNT_DERIVE_ADDRESSES,
+        option::FORMAT_DERIVE_ADDRESSES,
+        option::OUTPUT_DERIVE_ADDRESSES,
+        option::PATH_DERIVE_ADDRESSES,
+        option::RESUME_FROM_DERIVE_ADDRESSES,
+        option::THREADS_DERIVE_ADDRESSES,
+        option::XPUB_DERIVE_ADDRESSES,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
+pub const DISCOVER_PATHS_BITCOIN: SubCommandType = (
+    "discover-paths",
+    "Searches the accounts and chains of each purpose of a mnemonic or master extended private key for an address in any script format (include -h for more options)",
+    &[
+        option::ACCOUNT_DISCOVER_PATHS_BITCOIN,
+        option::ADDRESS_DISCOVER_PATHS,
+        option::EXTENDED_PRIVATE_KEY_DISCOVER_PATHS_BITCOIN,
+        option::LANGUAGE_DISCOVER_PATHS,
+        option::MNEMONIC_DISCOVER_PATHS_BITCOIN,
+        option::PASSWORD_DISCOVER_PATHS,
+        option::SEARCH_DEPTH_DISCOVER_PATHS_BITCOIN,
+        option::THREADS_DISCOVER_PATHS_BITCOIN,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
+pub const DISCOVER_PATHS_ETHEREUM: SubCommandType = (
+    "discover-paths",
+    "Searches the derivation paths of common wallets (the derivation presets) of a mnemonic for an address, printing the wallets that derive it (include -h for more options)",
+    &[
+        option::ADDRESS_DISCOVER_PATHS,
+        option::LANGUAGE_DISCOVER_PATHS,
+        option::MNEMONIC_DISCOVER_PATHS,
+        option::PASSWORD_DISCOVER_PATHS,
+        option::SEARCH_DEPTH_DISCOVER_PATHS,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
+pub const ENTROPY_GRID_BITCOIN: SubCommandType = (
+    "entropy-grid",
+    "Generates a Border Wallet entropy grid, and the mnemonic of a pattern of its cells (include -h for more options)",
+    &[
+        option::ENTROPY_GRID_BITCOIN,
+        option::MNEMONIC_GRID_BITCOIN,
+        option::PATTERN_GRID_BITCOIN,
+        option::PDF_GRID_BITCOIN,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+    ],
+);
+
+pub const FROST_BITCOIN: SubCommandType = (
+    "frost",
+    "Generates a threshold taproot key with FROST, and its nonces and signatures (include -h for more options)",
+    &[
+        option::IDENTIFIER_FROST_BITCOIN,
+        option::KEY_PACKAGE_FROST_BITCOIN,
+        option::MESSAGE_FROST_BITCOIN,
+        option::NETWORK_FROST_BITCOIN,
+        option::NONCES_FROST_BITCOIN,
+        option::PACKAGES_FROST_BITCOIN,
+        option::PARTIAL_SIGNATURES_FROST_BITCOIN,
+        option::PARTICIPANTS_FROST_BITCOIN,
+        option::SECRET_NONCE_FILE_FROST_BITCOIN,
+        option::SECRET_PACKAGE_FROST_BITCOIN,
+        option::SHARES_FROST_BITCOIN,
+        option::THRESHOLD_FROST_BITCOIN,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
 pub const HD_BITCOIN: SubCommandType = (
     "hd",
     "Generates an HD wallet (include -h for more options)",
     &[
+        option::ACCOUNT_HD_BITCOIN,
+        option::BREADTH_HD_TREE,
+        option::CHANGE_HD_BITCOIN,
+        option::CONFIRM_BACKUP,
         option::COUNT,
+        option::DEPTH_HD_TREE,
         option::DERIVATION_BITCOIN,
+        option::DURESS_HD,
+        option::FORMAT_HD_BITCOIN,
         option::LANGUAGE_HD,
+        option::MNEMONIC_HD_TREE,
         option::NETWORK_HD_BITCOIN,
         option::PASSWORD_HD,
+        option::PURPOSE_BITCOIN,
+        option::TREE_HD,
         option::WORD_COUNT,
+        option::WORDLIST_FILE_HD,
     ],
     &[
         AppSettings::ColoredHelp,
@@ -27,11 +371,38 @@ pub const HD_ETHEREUM: SubCommandType = (
     "hd",
     "Generates an HD wallet (include -h for more options)",
     &[
+        option::ACCOUNT_ETHEREUM,
+        option::BREADTH_HD_TREE,
+        option::CONFIRM_BACKUP,
         option::COUNT,
+        option::DEPTH_HD_TREE,
         option::DERIVATION_ETHEREUM,
         option::INDEX_HD,
         option::INDICES_HD,
         option::LANGUAGE_HD,
+        option::LIST_PRESETS_ETHEREUM,
+        option::MNEMONIC_HD_TREE,
+        option::PASSWORD_HD,
+        option::TREE_HD,
+        option::WORD_COUNT,
+        option::WORDLIST_FILE_HD,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+    ],
+);
+
+pub const HD_NOSTR: SubCommandType = (
+    "hd",
+    "Generates a key derived from a BIP-39 mnemonic per NIP-06 (include -h for more options)",
+    &[
+        option::ACCOUNT_NOSTR,
+        option::CONFIRM_BACKUP,
+        option::COUNT,
+        option::LANGUAGE_HD,
+        option::MNEMONIC_HD_NOSTR,
         option::PASSWORD_HD,
         option::WORD_COUNT,
     ],
@@ -49,6 +420,7 @@ pub const HD_ZCASH: SubCommandType = (
         option::COUNT,
         option::DERIVATION_ZCASH,
         option::DIVERSIFIER_IMPORT_ZCASH,
+        option::DIVERSIFIER_INDEX_HD_ZCASH,
         option::NETWORK_HD_ZCASH,
     ],
     &[
@@ -58,15 +430,39 @@ pub const HD_ZCASH: SubCommandType = (
     ],
 );
 
+pub const HTLC_BITCOIN: SubCommandType = (
+    "htlc",
+    "Generates a hash time-locked contract of an atomic swap, its P2WSH address, and its claim and refund templates (include -h for more options)",
+    &[
+        option::HASH_HTLC_BITCOIN,
+        option::LOCKTIME_HTLC_BITCOIN,
+        option::NETWORK_HTLC_BITCOIN,
+        option::RECIPIENT_PUBKEY_HTLC_BITCOIN,
+        option::REFUND_PUBKEY_HTLC_BITCOIN,
+        option::RELATIVE_HTLC_BITCOIN,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
 pub const IMPORT_BITCOIN: SubCommandType = (
     "import",
     "Imports a wallet (include -h for more options)",
     &[
         option::ADDRESS,
+        option::COLUMN_IMPORT,
+        option::DUMPFILE_IMPORT_BITCOIN,
+        option::ELECTRUM_WALLET_IMPORT_BITCOIN,
+        option::FILE_IMPORT,
         option::FORMAT_IMPORT_BITCOIN,
         option::NETWORK_IMPORT_BITCOIN,
         option::PRIVATE,
         option::PUBLIC,
+        option::SCRIPT_LEAF_IMPORT_BITCOIN,
     ],
     &[
         AppSettings::ColoredHelp,
@@ -79,7 +475,14 @@ pub const IMPORT_BITCOIN: SubCommandType = (
 pub const IMPORT_ETHEREUM: SubCommandType = (
     "import",
     "Imports a wallet (include -h for more options)",
-    &[option::ADDRESS, option::PRIVATE, option::PUBLIC],
+    &[
+        option::ADDRESS,
+        option::COLUMN_IMPORT,
+        option::FILE_IMPORT,
+        option::KEYSTORE_IMPORT_ETHEREUM,
+        option::PRIVATE,
+        option::PUBLIC,
+    ],
     &[
         AppSettings::ColoredHelp,
         AppSettings::DisableHelpSubcommand,
@@ -93,14 +496,19 @@ pub const IMPORT_MONERO: SubCommandType = (
     "Imports a wallet (include -h for more options)",
     &[
         option::ADDRESS,
+        option::COLUMN_IMPORT,
+        option::FILE_IMPORT,
         option::INTEGRATED_IMPORT_MONERO,
         option::LANGUAGE_IMPORT_MONERO,
         option::MNEMONIC_IMPORT_MONERO,
         option::NETWORK_IMPORT_MONERO,
+        option::PAYMENT_ID_IMPORT_MONERO,
         option::PRIVATE_SPEND_KEY_MONERO,
         option::PRIVATE_VIEW_KEY_MONERO,
         option::PUBLIC_SPEND_KEY_MONERO,
         option::PUBLIC_VIEW_KEY_MONERO,
+        option::RESTORE_FILE_MONERO,
+        option::RESTORE_HEIGHT_MONERO,
         option::SUBADDRESS_IMPORT_MONERO,
     ],
     &[
@@ -111,12 +519,26 @@ pub const IMPORT_MONERO: SubCommandType = (
     ],
 );
 
+pub const IMPORT_NOSTR: SubCommandType = (
+    "import",
+    "Imports a key (include -h for more options)",
+    &[option::PRIVATE_NOSTR, option::PUBLIC_NOSTR],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
 pub const IMPORT_ZCASH: SubCommandType = (
     "import",
     "Imports a wallet (include -h for more options)",
     &[
         option::ADDRESS,
+        option::COLUMN_IMPORT,
         option::DIVERSIFIER_IMPORT_ZCASH,
+        option::FILE_IMPORT,
         option::PRIVATE,
         option::PUBLIC,
     ],
@@ -135,12 +557,20 @@ pub const IMPORT_HD_BITCOIN: SubCommandType = (
         option::ACCOUNT,
         option::CHAIN,
         option::DERIVATION_IMPORT_BITCOIN,
+        option::DURESS_IMPORT_HD,
+        option::EXPORT_FILE,
+        option::EXPORT_FORMAT,
         option::EXTENDED_PUBLIC,
         option::EXTENDED_PRIVATE,
+        option::FORMAT_IMPORT_HD_BITCOIN,
         option::NETWORK_IMPORT_HD_BITCOIN,
         option::INDEX_IMPORT_HD,
+        option::MASTER_FINGERPRINT,
         option::MNEMONIC,
         option::PASSWORD_IMPORT_HD,
+        option::SUMMARY,
+        option::SUMMARY_COUNT,
+        option::WORDLIST_FILE_IMPORT_HD,
     ],
     &[
         AppSettings::ColoredHelp,
@@ -154,13 +584,18 @@ pub const IMPORT_HD_ETHEREUM: SubCommandType = (
     "import-hd",
     "Imports an HD wallet (include -h for more options)",
     &[
+        option::ACCOUNT_ETHEREUM,
         option::DERIVATION_IMPORT_ETHEREUM,
         option::EXTENDED_PUBLIC,
         option::EXTENDED_PRIVATE,
         option::INDEX_IMPORT_HD,
         option::INDICES_IMPORT_HD,
+        option::KEYSTORE_ETHEREUM,
         option::MNEMONIC,
         option::PASSWORD_IMPORT_HD,
+        option::SUMMARY,
+        option::SUMMARY_COUNT,
+        option::WORDLIST_FILE_IMPORT_HD,
     ],
     &[
         AppSettings::ColoredHelp,
@@ -177,6 +612,7 @@ pub const IMPORT_HD_ZCASH: SubCommandType = (
         option::ACCOUNT,
         option::DERIVATION_IMPORT_ZCASH,
         option::DIVERSIFIER_IMPORT_HD_ZCASH,
+        option::DIVERSIFIER_INDEX_IMPORT_HD_ZCASH,
         option::EXTENDED_PUBLIC,
         option::EXTENDED_PRIVATE,
         option::INDEX_IMPORT_HD,
@@ -189,14 +625,13 @@ pub const IMPORT_HD_ZCASH: SubCommandType = (
     ],
 );
 
-pub const TRANSACTION_BITCOIN: SubCommandType = (
-    "transaction",
-    "Generates a Bitcoin transaction (include -h for more options)",
+pub const MINISCRIPT_BITCOIN: SubCommandType = (
+    "miniscript",
+    "Compiles a spending policy to miniscript, its witness script, and its P2WSH address (include -h for more options)",
     &[
-        option::CREATE_RAW_TRANSACTION_BITCOIN,
-        option::SIGN_RAW_TRANSACTION_BITCOIN,
-        option::TRANSACTION_LOCK_TIME_BITCOIN,
-        option::TRANSACTION_VERSION_BITCOIN,
+        option::KEYS_MINISCRIPT_BITCOIN,
+        option::NETWORK_MINISCRIPT_BITCOIN,
+        option::POLICY_MINISCRIPT_BITCOIN,
     ],
     &[
         AppSettings::ColoredHelp,
@@ -206,13 +641,17 @@ pub const TRANSACTION_BITCOIN: SubCommandType = (
     ],
 );
 
-pub const TRANSACTION_ETHEREUM: SubCommandType = (
-    "transaction",
-    "Generates a Ethereum transaction (include -h for more options)",
+pub const MUSIG_BITCOIN: SubCommandType = (
+    "musig",
+    "Aggregates public keys into a taproot key, and generates MuSig2 nonces and signatures (include -h for more options)",
     &[
-        option::CREATE_RAW_TRANSACTION_ETHEREUM,
-        option::SIGN_RAW_TRANSACTION_ETHEREUM,
-        option::TRANSACTION_NETWORK_ETHEREUM,
+        option::MESSAGE_MUSIG_BITCOIN,
+        option::NETWORK_MUSIG_BITCOIN,
+        option::NONCES_MUSIG_BITCOIN,
+        option::PARTIAL_SIGNATURES_MUSIG_BITCOIN,
+        option::PRIVATE_KEY_MUSIG_BITCOIN,
+        option::PUBLIC_KEYS_MUSIG_BITCOIN,
+        option::SECRET_NONCE_FILE_MUSIG_BITCOIN,
     ],
     &[
         AppSettings::ColoredHelp,
@@ -222,16 +661,736 @@ pub const TRANSACTION_ETHEREUM: SubCommandType = (
     ],
 );
 
-pub const TRANSACTION_ZCASH: SubCommandType = (
-    "transaction",
-    "Generates a Zcash transaction (include -h for more options)",
+pub const DECODE: SubCommandType = (
+    "decode",
+    "Decodes a Base58, Base58Check, Bech32, Bech32m, or CashAddr string, locating a mistyped character",
+    &[option::ENCODING_DECODE, option::INPUT_DECODE],
     &[
-        option::CREATE_RAW_TRANSACTION_ZCASH,
-        option::SIGN_RAW_TRANSACTION_ZCASH,
-        option::TRANSACTION_EXPIRY_HEIGHT_ZCASH,
-        option::TRANSACTION_LOCK_TIME_ZCASH,
-        option::TRANSACTION_VERSION_ZCASH,
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
+pub const DERIVE: SubCommandType = (
+    "derive",
+    "Derives the SSH key, age identity, OpenPGP key, TOTP secrets, and passwords of a mnemonic, at the SLIP-13 path of an identity or the BIP-85 path of a label or site",
+    &[
+        option::CHARSET_DERIVE,
+        option::COUNTER_DERIVE,
+        option::FORCE,
+        option::IDENTITY_DERIVE,
+        option::JSON_DERIVE,
+        option::KEYS_DERIVE,
+        option::LABEL_DERIVE,
+        option::LENGTH_DERIVE,
+        option::LOCALE,
+        option::LOCK_MEMORY,
+        option::LOG_FILE,
+        option::MNEMONIC_DERIVE,
+        option::PASSWORD_DERIVE,
+        option::REDACT,
+        option::REDACT_MODE,
+        option::SITE_DERIVE,
+        option::STRICT,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
+pub const ENCODE: SubCommandType = (
+    "encode",
+    "Encodes a hex string in Base58, Base58Check, Bech32, Bech32m, or CashAddr",
+    &[option::ENCODING_ENCODE, option::INPUT_ENCODE, option::PREFIX_ENCODE],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
+pub const GENERATE: SubCommandType = (
+    "generate",
+    "Generates wallets for several coins in one document, optionally from the same mnemonic",
+    &[
+        option::COINS_GENERATE,
+        option::COUNT_GENERATE,
+        option::FORCE,
+        option::LOCALE,
+        option::LOCK_MEMORY,
+        option::LOG_FILE,
+        option::MNEMONIC_GENERATE,
+        option::SAME_MNEMONIC_GENERATE,
+        option::STRICT,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
+pub const HASH: SubCommandType = (
+    "hash",
+    "Hashes a hex string or file with the hash functions of address derivations",
+    &[option::ALGO_HASH, option::INPUT_HASH],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
+pub const INFO: SubCommandType = (
+    "info",
+    "Prints the coin type, address formats, default derivation path, network prefixes, and extended key versions in JSON",
+    &[],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
     ],
+);
+
+pub const INHERITANCE: SubCommandType = (
+    "inheritance",
+    "Generates the inheritance package of a Bitcoin wallet, its recovery information apart from its encrypted or split secrets",
+    &[
+        option::COMBINE_INHERITANCE,
+        option::ENCRYPT_TO_INHERITANCE,
+        option::FORCE,
+        option::JSON_INHERITANCE,
+        option::LOCALE,
+        option::LOCK_MEMORY,
+        option::LOG_FILE,
+        option::MNEMONIC_INHERITANCE,
+        option::NOTE_INHERITANCE,
+        option::OUTPUT_INHERITANCE,
+        option::PASSWORD_INHERITANCE,
+        option::REDACT,
+        option::REDACT_MODE,
+        option::SHARES_INHERITANCE,
+        option::STRICT,
+        option::THRESHOLD_INHERITANCE,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
+pub const MNEMONIC: SubCommandType = (
+    "mnemonic",
+    "Converts BIP-39 mnemonics to and from their entropy, and inspects their wordlists (include -h for more options)",
+    &[],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::SubcommandRequiredElseHelp,
+    ],
+);
+
+/// The subcommands of `wagyu mnemonic`
+pub const MNEMONIC_COMMANDS: &[SubCommandType] = &[FROM_ENTROPY_MNEMONIC, TO_ENTROPY_MNEMONIC, WORDLIST_MNEMONIC];
+
+pub const FROM_ENTROPY_MNEMONIC: SubCommandType = (
+    "from-entropy",
+    "Returns the mnemonic of a hex entropy, with its checksum",
+    &[
+        option::ENTROPY_FROM_ENTROPY,
+        option::FORCE,
+        option::JSON_MNEMONIC_ENTROPY,
+        option::LANGUAGE_FROM_ENTROPY,
+        option::LOCALE,
+        option::LOCK_MEMORY,
+        option::REDACT,
+        option::REDACT_MODE,
+        option::STRICT,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
+pub const TO_ENTROPY_MNEMONIC: SubCommandType = (
+    "to-entropy",
+    "Returns the entropy and checksum of a mnemonic, detecting its language",
+    &[
+        option::FORCE,
+        option::JSON_MNEMONIC_ENTROPY,
+        option::LOCALE,
+        option::LOCK_MEMORY,
+        option::MNEMONIC_TO_ENTROPY,
+        option::REDACT,
+        option::REDACT_MODE,
+        option::STRICT,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
+pub const WORDLIST_MNEMONIC: SubCommandType = (
+    "wordlist",
+    "Prints a BIP-39 wordlist, or the word of an index or the words of a prefix, with their indices",
+    &[
+        option::INDEX_WORDLIST,
+        option::JSON_WORDLIST,
+        option::LANGUAGE_WORDLIST,
+        option::PREFIX_WORDLIST,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+    ],
+);
+
+pub const OPEN: SubCommandType = (
+    "open",
+    "Opens a saved wallet file, decrypting it with a passphrase if encrypted",
+    &[option::FILE_OPEN, option::LOCALE, option::LOCK_MEMORY, option::LOG_FILE],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
+pub const PORTFOLIO: SubCommandType = (
+    "portfolio",
+    "Derives the first address of each coin of a mnemonic at its standard BIP-44 path",
+    &[
+        option::FORCE,
+        option::JSON_PORTFOLIO,
+        option::LOCALE,
+        option::LOCK_MEMORY,
+        option::LOG_FILE,
+        option::MNEMONIC_PORTFOLIO,
+        option::PASSWORD_PORTFOLIO,
+        option::REDACT,
+        option::REDACT_MODE,
+        option::STRICT,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
+pub const MULTISIG_MONERO: SubCommandType = (
+    "multisig",
+    "Exchanges keys to generate a shared multisig wallet (include -h for more options)",
+    &[
+        option::EXTRA_INFO_MULTISIG_MONERO,
+        option::INFO_MULTISIG_MONERO,
+        option::NETWORK_MONERO,
+        option::PRIVATE_SPEND_KEY_MULTISIG_MONERO,
+        option::THRESHOLD_MULTISIG_MONERO,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
+pub const NODE_ID_ETHEREUM: SubCommandType = (
+    "node-id",
+    "Derives the devp2p node ID (enode), node record (ENR), and libp2p peer ID of a node private key (include -h for more options)",
+    &[
+        option::IP_NODE_ID_ETHEREUM,
+        option::PRIVATE_KEY_NODE_ID_ETHEREUM,
+        option::SEQ_NODE_ID_ETHEREUM,
+        option::TCP_PORT_NODE_ID_ETHEREUM,
+        option::UDP_PORT_NODE_ID_ETHEREUM,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
+pub const NOTE_NOSTR: SubCommandType = (
+    "note",
+    "Encodes an event ID as a note, or decodes a note (include -h for more options)",
+    &[option::ID_NOTE_NOSTR],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
+pub const PAYMENT_ID_MONERO: SubCommandType = (
+    "payment-id",
+    "Encrypts or decrypts the short payment ID of a transaction (include -h for more options)",
+    &[
+        option::ADDRESS_PAYMENT_ID_MONERO,
+        option::ENCRYPTED_PAYMENT_ID_MONERO,
+        option::PAYMENT_ID_ENCRYPT_MONERO,
+        option::PRIVATE_VIEW_KEY_PAYMENT_ID_MONERO,
+        option::TX_KEY_PAYMENT_ID_MONERO,
+        option::TX_PUBLIC_KEY_PAYMENT_ID_MONERO,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
+pub const PERMIT_ETHEREUM: SubCommandType = (
+    "permit",
+    "Signs an ERC-20 permit (EIP-2612) for on-chain submission (include -h for more options)",
+    &[
+        option::CHAIN_ID_PERMIT_ETHEREUM,
+        option::DEADLINE_PERMIT_ETHEREUM,
+        option::DEBUG_SIGNATURE_ETHEREUM,
+        option::NONCE_PERMIT_ETHEREUM,
+        option::PRIVATE_KEY_SIGN_ETHEREUM,
+        option::SPENDER_PERMIT_ETHEREUM,
+        option::TOKEN_PERMIT_ETHEREUM,
+        option::TOKEN_NAME_PERMIT_ETHEREUM,
+        option::TOKEN_VERSION_PERMIT_ETHEREUM,
+        option::VALUE_PERMIT_ETHEREUM,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
+pub const PROVE_MONERO: SubCommandType = (
+    "prove",
+    "Generates a transaction proof (include -h for more options)",
+    &[
+        option::ADDRESS_PROVE_MONERO,
+        option::MESSAGE_PROVE_MONERO,
+        option::PRIVATE_VIEW_KEY_PROVE_MONERO,
+        option::TX_ID_PROVE_MONERO,
+        option::TX_KEY_PROVE_MONERO,
+        option::TX_PUBLIC_KEY_MONERO,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
+pub const RECOVER_BITCOIN: SubCommandType = (
+    "recover",
+    "Recovers the private keys of a legacy wallet.dat (include -h for more options)",
+    &[
+        option::NETWORK_RECOVER_BITCOIN,
+        option::PASSPHRASE_RECOVER_BITCOIN,
+        option::WALLET_DAT_RECOVER_BITCOIN,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
+pub const RECOVER_ETHEREUM: SubCommandType = (
+    "recover",
+    "Recovers the signer of a signed message (include -h for more options)",
+    &[
+        option::MESSAGE_RECOVER_ETHEREUM,
+        option::MESSAGE_HASH_RECOVER_ETHEREUM,
+        option::SIGNATURE_RECOVER_ETHEREUM,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
+pub const SCHEMA: SubCommandType = (
+    "schema",
+    "Prints the JSON Schema of the JSON output of a command",
+    &[option::OUTPUT_SCHEMA],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
+pub const SEEDQR: SubCommandType = (
+    "seedqr",
+    "Exports a mnemonic as a SeedQR or CompactSeedQR, or imports the mnemonic of a scanned SeedQR payload",
+    &[
+        option::COMPACT_SEEDQR,
+        option::FORCE,
+        option::JSON_SEEDQR,
+        option::LOCALE,
+        option::LOCK_MEMORY,
+        option::LOG_FILE,
+        option::MNEMONIC_SEEDQR,
+        option::PAYLOAD_SEEDQR,
+        option::REDACT,
+        option::REDACT_MODE,
+        option::STRICT,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
+pub const SELFTEST: SubCommandType = (
+    "selftest",
+    "Runs the embedded BIP-32, BIP-39, BIP-44, EIP-55, Bech32, and coin test vectors to verify the binary",
+    &[option::JSON_SELFTEST],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+    ],
+);
+
+pub const SERVE: SubCommandType = (
+    "serve",
+    "Serves a local HTTP API to derive addresses, and sign and verify transactions and messages",
+    &[
+        option::AUDIT_LOG_SERVE,
+        option::LISTEN_SERVE,
+        option::LOCALE,
+        option::LOCK_MEMORY,
+        option::TOKEN_SERVE,
+        option::WALLET_FILE_SERVE,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+    ],
+);
+
+pub const SIGN_BATCH_BITCOIN: SubCommandType = (
+    "sign-batch",
+    "Signs the prepared transactions of a batch file with an extended private key (include -h for more options)",
+    &[option::FILE_SIGN_BATCH, option::KEY_SIGN_BATCH],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
+pub const SIGN_MESSAGE_BITCOIN: SubCommandType = (
+    "sign-message",
+    "Signs a message with BIP-322 (include -h for more options)",
+    &[
+        option::FORMAT_SIGN_BITCOIN,
+        option::MESSAGE_BITCOIN,
+        option::PRIVATE_KEY_SIGN_BITCOIN,
+        option::PROOF_SIGN_BITCOIN,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
+pub const SIGN_TX_BITCOIN: SubCommandType = (
+    "sign-tx",
+    "Signs an unsigned transaction file (include -h for more options)",
+    &[option::PRIVATE_KEY_SIGN_TX_BITCOIN, option::UNSIGNED_TX_SIGN],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
+pub const SIGN_BATCH_ETHEREUM: SubCommandType = (
+    "sign-batch",
+    "Signs the prepared transactions of a batch file with an extended private key (include -h for more options)",
+    &[
+        option::FILE_SIGN_BATCH,
+        option::KEY_SIGN_BATCH,
+        option::NETWORK_SIGN_BATCH_ETHEREUM,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
+pub const SIGN_TX_ETHEREUM: SubCommandType = (
+    "sign-tx",
+    "Signs an unsigned transaction file (include -h for more options)",
+    &[
+        option::DEBUG_SIGNATURE_ETHEREUM,
+        option::NONCE_FILE_SIGN_TX_ETHEREUM,
+        option::PRIVATE_KEY_SIGN_TX_ETHEREUM,
+        option::UNSIGNED_TX_SIGN,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
+pub const SIGN_TYPED_DATA_ETHEREUM: SubCommandType = (
+    "sign-typed-data",
+    "Signs EIP-712 typed data (include -h for more options)",
+    &[
+        option::DEBUG_SIGNATURE_ETHEREUM,
+        option::PRIVATE_KEY_SIGN_ETHEREUM,
+        option::TYPED_DATA_ETHEREUM,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
+pub const SIGN_USEROP_ETHEREUM: SubCommandType = (
+    "sign-userop",
+    "Signs an ERC-4337 user operation for a smart account (include -h for more options)",
+    &[
+        option::CHAIN_ID_SIGN_USEROP_ETHEREUM,
+        option::DEBUG_SIGNATURE_ETHEREUM,
+        option::ENTRY_POINT_SIGN_USEROP_ETHEREUM,
+        option::PRIVATE_KEY_SIGN_ETHEREUM,
+        option::USEROP_ETHEREUM,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
+pub const SPROUT_ZCASH: SubCommandType = (
+    "sprout",
+    "Derives the keys and addresses of a legacy Sprout backup, to migrate its funds to Sapling (include -h for more options)",
+    &[
+        option::DESTINATION_SPROUT_ZCASH,
+        option::DUMPFILE_SPROUT_ZCASH,
+        option::NETWORK_SPROUT_ZCASH,
+        option::SPENDING_KEY_SPROUT_ZCASH,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
+pub const SWEEP_BITCOIN: SubCommandType = (
+    "sweep",
+    "Sweeps all funds of a private key to an address with a Bitcoin node (include -h for more options)",
+    &[
+        option::BROADCAST_SWEEP_BITCOIN,
+        option::FEE_RATE_SWEEP_BITCOIN,
+        option::RPC_URL_SWEEP_BITCOIN,
+        option::TO_SWEEP_BITCOIN,
+        option::WIF_SWEEP_BITCOIN,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
+pub const TIMELOCK_BITCOIN: SubCommandType = (
+    "timelock",
+    "Generates a time-locked (CLTV/CSV) witness script, its P2WSH address, and its spending template (include -h for more options)",
+    &[
+        option::LOCKTIME_TIMELOCK_BITCOIN,
+        option::NETWORK_TIMELOCK_BITCOIN,
+        option::PUBKEY_TIMELOCK_BITCOIN,
+        option::RELATIVE_TIMELOCK_BITCOIN,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
+pub const TRANSACTION_BITCOIN: SubCommandType = (
+    "transaction",
+    "Generates a Bitcoin transaction (include -h for more options)",
+    &[
+        option::ALLOW_DUST_TRANSACTION,
+        option::CREATE_RAW_TRANSACTION_BITCOIN,
+        option::DUST_LIMIT_TRANSACTION,
+        option::SIGN_RAW_TRANSACTION_BITCOIN,
+        option::TRANSACTION_LOCK_TIME_BITCOIN,
+        option::TRANSACTION_OP_RETURN_BITCOIN,
+        option::TRANSACTION_VERSION_BITCOIN,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
+pub const TRANSACTION_ETHEREUM: SubCommandType = (
+    "transaction",
+    "Generates a Ethereum transaction (include -h for more options)",
+    &[
+        option::CREATE_RAW_TRANSACTION_ETHEREUM,
+        option::DEBUG_SIGNATURE_ETHEREUM,
+        option::ESTIMATE_GAS_ETHEREUM,
+        option::RPC_URL_ETHEREUM,
+        option::SIGN_RAW_TRANSACTION_ETHEREUM,
+        option::TRANSACTION_NETWORK_ETHEREUM,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
+pub const TRANSACTION_ZCASH: SubCommandType = (
+    "transaction",
+    "Generates a Zcash transaction (include -h for more options)",
+    &[
+        option::ALLOW_DUST_TRANSACTION,
+        option::CREATE_RAW_TRANSACTION_ZCASH,
+        option::DUST_LIMIT_TRANSACTION,
+        option::SIGN_RAW_TRANSACTION_ZCASH,
+        option::TRANSACTION_EXPIRY_HEIGHT_ZCASH,
+        option::TRANSACTION_LOCK_TIME_ZCASH,
+        option::TRANSACTION_VERSION_ZCASH,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
+pub const VALIDATE_MONERO: SubCommandType = (
+    "validate",
+    "Validates an address, and reports its network and type, or where a malformed address is invalid",
+    &[option::ADDRESS_VALIDATE_MONERO],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
+pub const VERIFY: SubCommandType = (
+    "verify",
+    "Re-derives the wallets of a prior JSON output, and reports the fields that do not match",
+    &[option::INPUT_VERIFY, option::JSON_VERIFY, option::LOCK_MEMORY],
+    &[
+        AppSettings::ArgRequiredElseHelp,
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+    ],
+);
+
+pub const VERIFY_DICE: SubCommandType = (
+    "verify-dice",
+    "Recomputes the mnemonic of a dice-roll worksheet from its rolls, and reports the words that do not match",
+    &[
+        option::FORCE,
+        option::JSON_VERIFY_DICE,
+        option::LOCALE,
+        option::LOCK_MEMORY,
+        option::LOG_FILE,
+        option::METHOD_VERIFY_DICE,
+        option::MNEMONIC_VERIFY_DICE,
+        option::REDACT,
+        option::REDACT_MODE,
+        option::ROLLS_VERIFY_DICE,
+        option::STRICT,
+    ],
+    &[
+        AppSettings::ArgRequiredElseHelp,
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+    ],
+);
+
+pub const VERIFY_MESSAGE_BITCOIN: SubCommandType = (
+    "verify-message",
+    "Verifies a BIP-322 signed message (include -h for more options)",
+    &[
+        option::ADDRESS_VERIFY_BITCOIN,
+        option::MESSAGE_BITCOIN,
+        option::SIGNATURE_VERIFY_BITCOIN,
+    ],
+    &[
+        AppSettings::ColoredHelp,
+        AppSettings::DisableHelpSubcommand,
+        AppSettings::DisableVersion,
+        AppSettings::ArgRequiredElseHelp,
+    ],
+);
+
+pub const XPUB: SubCommandType = (
+    "xpub",
+    "Derives the extended public key of an extended private key (include -h for more options)",
+    &[option::EXTENDED_PRIVATE_XPUB, option::PATH_XPUB],
     &[
         AppSettings::ColoredHelp,
         AppSettings::DisableHelpSubcommand,
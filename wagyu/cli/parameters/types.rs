@@ -1,5 +1,7 @@
 use clap::AppSettings;
 
+use crate::model::no_std::{String, Vec};
+
 pub type NameType = &'static str;
 pub type AboutType = &'static str;
 pub type FlagType = &'static str;
@@ -10,3 +12,4 @@ pub type OptionType = (
     &'static [&'static str],
 );
 pub type SubCommandType = (NameType, AboutType, &'static [OptionType], &'static [AppSettings]);
+pub type ValueHint = (NameType, Vec<String>);
@@ -0,0 +1,67 @@
+//! # PDF
+//!
+//! Writes pages of lines of text as a PDF of A4 pages in Courier, for the printable backups
+//! of an entropy grid or an inheritance package. The lines are not wrapped, so each line
+//! fits the width of a page in 90 characters.
+
+use crate::model::no_std::{format, vec, String, ToString, Vec};
+
+/// The number of characters of a line that fit the width of a page
+pub const PDF_LINE_WIDTH: usize = 90;
+
+/// Returns the PDF of the given pages of lines of text.
+pub fn to_text_pdf(pages: &[Vec<String>]) -> Vec<u8> {
+    // The catalog, the pages, and the font are followed by each page and its content stream
+    let mut objects = vec![
+        "<< /Type /Catalog /Pages 2 0 R >>".to_string(),
+        format!(
+            "<< /Type /Pages /Kids [{}] /Count {} >>",
+            (0..pages.len())
+                .map(|page| format!("{} 0 R", 4 + page * 2))
+                .collect::<Vec<_>>()
+                .join(" "),
+            pages.len()
+        ),
+        "<< /Type /Font /Subtype /Type1 /BaseFont /Courier >>".to_string(),
+    ];
+    for (page, lines) in pages.iter().enumerate() {
+        let text = lines
+            .iter()
+            .map(|line| format!("({}) Tj T*", to_pdf_string(line)))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let content = format!("BT /F1 9 Tf 11 TL 50 800 Td\n{}\nET", text);
+        objects.push(format!(
+            "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 595 842] /Resources << /Font << /F1 3 0 R >> >> /Contents {} 0 R >>",
+            5 + page * 2
+        ));
+        objects.push(format!(
+            "<< /Length {} >>\nstream\n{}\nendstream",
+            content.len(),
+            content
+        ));
+    }
+
+    let mut pdf = "%PDF-1.4\n".to_string();
+    let mut offsets = Vec::new();
+    for (i, object) in objects.iter().enumerate() {
+        offsets.push(pdf.len());
+        pdf.push_str(&format!("{} 0 obj\n{}\nendobj\n", i + 1, object));
+    }
+    let xref = pdf.len();
+    pdf.push_str(&format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1));
+    offsets
+        .iter()
+        .for_each(|offset| pdf.push_str(&format!("{:010} 00000 n \n", offset)));
+    pdf.push_str(&format!(
+        "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+        objects.len() + 1,
+        xref
+    ));
+    pdf.into_bytes()
+}
+
+/// Returns the given line as the contents of a PDF literal string, with its backslashes and parentheses escaped.
+fn to_pdf_string(line: &str) -> String {
+    line.replace('\\', "\\\\").replace('(', "\\(").replace(')', "\\)")
+}
@@ -0,0 +1,298 @@
+//! # PGP
+//!
+//! Encodes deterministic OpenPGP keys (RFC 4880, with the EdDSA and Curve25519 ECDH algorithms of
+//! RFC 6637 and RFC 4880bis): an Ed25519 primary key that certifies, with an Ed25519 signing subkey
+//! and a Cv25519 encryption subkey. The keys are created and self-signed at a fixed time, so that the
+//! same secrets always encode the same key and fingerprint.
+
+use crypto::{curve25519::curve25519_base, digest::Digest, ed25519, sha1::Sha1, sha2::Sha256};
+use rustc_serialize::base64::{Config, Newline, ToBase64, STANDARD};
+
+use crate::model::no_std::{format, vec, String, ToString, Vec};
+
+/// The fixed creation time of deterministic keys and their signatures (the timestamp of the Bitcoin genesis block)
+pub const PGP_CREATION_TIME: u32 = 1_231_006_505;
+
+/// The OID of the Ed25519 curve of EdDSA keys
+const ED25519_OID: [u8; 9] = [0x2b, 0x06, 0x01, 0x04, 0x01, 0xda, 0x47, 0x0f, 0x01];
+/// The OID of the Curve25519 curve of ECDH keys
+const CV25519_OID: [u8; 10] = [0x2b, 0x06, 0x01, 0x04, 0x01, 0x97, 0x55, 0x01, 0x05, 0x01];
+
+/// The public key algorithms of EdDSA and ECDH keys
+const EDDSA: u8 = 22;
+const ECDH: u8 = 18;
+/// The hash algorithm (SHA-256) and symmetric algorithm (AES-256) of signatures and ECDH keys
+const SHA256: u8 = 8;
+const AES256: u8 = 9;
+
+/// The key flags of the primary key and subkeys
+const CERTIFY: u8 = 0x01;
+const SIGN: u8 = 0x02;
+const ENCRYPT: u8 = 0x0c;
+
+/// Represents a deterministic OpenPGP key with a signing and an encryption subkey
+#[derive(Debug, Clone)]
+pub struct PgpKey {
+    /// The user ID of the key, such as `Alice <alice@example.com>`
+    pub user_id: String,
+    /// The Ed25519 secret keys of the primary key and signing subkey
+    primary: [u8; 32],
+    signing: [u8; 32],
+    /// The Curve25519 secret key of the encryption subkey, clamped
+    encryption: [u8; 32],
+}
+
+impl PgpKey {
+    /// Returns the key of the given user ID, with the given secrets of its primary key,
+    /// signing subkey, and encryption subkey.
+    pub fn new(user_id: &str, primary: [u8; 32], signing: [u8; 32], encryption: [u8; 32]) -> Self {
+        let mut encryption = encryption;
+        encryption[0] &= 248;
+        encryption[31] &= 127;
+        encryption[31] |= 64;
+        Self {
+            user_id: user_id.to_string(),
+            primary,
+            signing,
+            encryption,
+        }
+    }
+
+    /// Returns the v4 fingerprint of the primary key.
+    pub fn to_fingerprint(&self) -> [u8; 20] {
+        to_fingerprint(&to_eddsa_public_key(&self.primary))
+    }
+
+    /// Returns the ASCII-armored transferable secret key.
+    pub fn to_armored_secret_key(&self) -> String {
+        to_armor("PRIVATE KEY BLOCK", &self.to_packets(true))
+    }
+
+    /// Returns the ASCII-armored transferable public key.
+    pub fn to_armored_public_key(&self) -> String {
+        to_armor("PUBLIC KEY BLOCK", &self.to_packets(false))
+    }
+
+    /// Returns the packets of the key, its user ID, and its subkeys, each followed by its self-signature.
+    fn to_packets(&self, secret: bool) -> Vec<u8> {
+        let primary = to_eddsa_public_key(&self.primary);
+        let signing = to_eddsa_public_key(&self.signing);
+        let encryption = to_ecdh_public_key(&self.encryption);
+        let (key_tag, subkey_tag) = match secret {
+            true => (5, 7),
+            false => (6, 14),
+        };
+        let key = |public_key: &[u8], secret_key: &[u8]| match secret {
+            true => to_secret_key(public_key, secret_key),
+            false => public_key.to_vec(),
+        };
+
+        // The user ID is certified with the preferred algorithms of the key (AES-256, SHA-256, and uncompressed)
+        let user_id = self.user_id.as_bytes();
+        let certification = to_signature(
+            &self.primary,
+            0x13,
+            &[
+                &to_key_hash_data(&primary)[..],
+                &[0xb4],
+                &(user_id.len() as u32).to_be_bytes(),
+                user_id,
+            ]
+            .concat(),
+            &[
+                to_subpacket(27, &[CERTIFY]),
+                to_subpacket(11, &[AES256]),
+                to_subpacket(21, &[SHA256]),
+                to_subpacket(22, &[0]),
+                to_subpacket(30, &[0x01]),
+            ],
+        );
+
+        // The signing subkey signs the primary key in turn, embedded in its binding signature
+        let signing_data = [to_key_hash_data(&primary), to_key_hash_data(&signing)].concat();
+        let back_signature = to_signature(&self.signing, 0x19, &signing_data, &[]);
+        let signing_binding = to_signature(
+            &self.primary,
+            0x18,
+            &signing_data,
+            &[to_subpacket(27, &[SIGN]), to_subpacket(32, &back_signature)],
+        );
+
+        let encryption_data = [to_key_hash_data(&primary), to_key_hash_data(&encryption)].concat();
+        let encryption_binding = to_signature(&self.primary, 0x18, &encryption_data, &[to_subpacket(27, &[ENCRYPT])]);
+
+        // The ECDH secret key is stored as a big-endian integer, in the reverse order of its native encoding
+        let mut encryption_secret = self.encryption;
+        encryption_secret.reverse();
+
+        [
+            to_packet(key_tag, &key(&primary, &self.primary)),
+            to_packet(13, user_id),
+            to_packet(2, &certification),
+            to_packet(subkey_tag, &key(&signing, &self.signing)),
+            to_packet(2, &signing_binding),
+            to_packet(subkey_tag, &key(&encryption, &encryption_secret)),
+            to_packet(2, &encryption_binding),
+        ]
+        .concat()
+    }
+}
+
+/// Returns the v4 signature of the given type of the given data with the given Ed25519 secret key,
+/// with the given hashed subpackets besides its issuer and creation time.
+fn to_signature(secret_key: &[u8; 32], signature_type: u8, data: &[u8], subpackets: &[Vec<u8>]) -> Vec<u8> {
+    let (private_key, _) = ed25519::keypair(secret_key);
+    let fingerprint = to_fingerprint(&to_eddsa_public_key(secret_key));
+
+    let mut hashed = vec![
+        to_subpacket(33, &[&[4u8][..], &fingerprint].concat()),
+        to_subpacket(2, &PGP_CREATION_TIME.to_be_bytes()),
+    ];
+    hashed.extend_from_slice(subpackets);
+    let hashed = hashed.concat();
+    let unhashed = to_subpacket(16, &fingerprint[12..]);
+
+    let mut signature = vec![4, signature_type, EDDSA, SHA256];
+    signature.extend_from_slice(&(hashed.len() as u16).to_be_bytes());
+    signature.extend_from_slice(&hashed);
+
+    let mut sha256 = Sha256::new();
+    sha256.input(data);
+    sha256.input(&signature);
+    sha256.input(&[4, 0xff]);
+    sha256.input(&(signature.len() as u32).to_be_bytes());
+    let mut digest = [0u8; 32];
+    sha256.result(&mut digest);
+
+    let rs = ed25519::signature(&digest, &private_key);
+    signature.extend_from_slice(&(unhashed.len() as u16).to_be_bytes());
+    signature.extend_from_slice(&unhashed);
+    signature.extend_from_slice(&digest[..2]);
+    signature.extend(to_mpi(&rs[..32]));
+    signature.extend(to_mpi(&rs[32..]));
+    signature
+}
+
+/// Returns the body of the public key packet of the given Ed25519 secret key.
+fn to_eddsa_public_key(secret_key: &[u8; 32]) -> Vec<u8> {
+    let (_, public_key) = ed25519::keypair(secret_key);
+    let mut key = vec![4];
+    key.extend_from_slice(&PGP_CREATION_TIME.to_be_bytes());
+    key.push(EDDSA);
+    key.push(ED25519_OID.len() as u8);
+    key.extend_from_slice(&ED25519_OID);
+    key.extend(to_mpi(&[&[0x40u8][..], &public_key].concat()));
+    key
+}
+
+/// Returns the body of the public key packet of the given Curve25519 secret key,
+/// with the KDF parameters of its key wrapping (SHA-256 and AES-256).
+fn to_ecdh_public_key(secret_key: &[u8; 32]) -> Vec<u8> {
+    let mut key = vec![4];
+    key.extend_from_slice(&PGP_CREATION_TIME.to_be_bytes());
+    key.push(ECDH);
+    key.push(CV25519_OID.len() as u8);
+    key.extend_from_slice(&CV25519_OID);
+    key.extend(to_mpi(&[&[0x40u8][..], &curve25519_base(secret_key)].concat()));
+    key.extend_from_slice(&[3, 1, SHA256, AES256]);
+    key
+}
+
+/// Returns the body of the unencrypted secret key packet of the given public key packet and secret key,
+/// followed by the two-octet checksum of the secret key.
+fn to_secret_key(public_key: &[u8], secret_key: &[u8]) -> Vec<u8> {
+    let mpi = to_mpi(secret_key);
+    let checksum = mpi.iter().fold(0u16, |sum, byte| sum.wrapping_add(*byte as u16));
+    [public_key, &[0], &mpi, &checksum.to_be_bytes()].concat()
+}
+
+/// Returns the v4 fingerprint of the given public key packet, the SHA-1 hash of its key hash data.
+fn to_fingerprint(public_key: &[u8]) -> [u8; 20] {
+    let mut sha1 = Sha1::new();
+    sha1.input(&to_key_hash_data(public_key));
+    let mut fingerprint = [0u8; 20];
+    sha1.result(&mut fingerprint);
+    fingerprint
+}
+
+/// Returns the data of the given public key packet as hashed by fingerprints and signatures.
+fn to_key_hash_data(public_key: &[u8]) -> Vec<u8> {
+    [&[0x99][..], &(public_key.len() as u16).to_be_bytes(), public_key].concat()
+}
+
+/// Returns the given big-endian integer as a multiprecision integer, prefixed with its length in bits.
+fn to_mpi(integer: &[u8]) -> Vec<u8> {
+    let integer = match integer.iter().position(|byte| *byte != 0) {
+        Some(start) => &integer[start..],
+        None => &[],
+    };
+    let bits = match integer.first() {
+        Some(first) => (integer.len() as u16 - 1) * 8 + (8 - first.leading_zeros() as u16),
+        None => 0,
+    };
+    [&bits.to_be_bytes()[..], integer].concat()
+}
+
+/// Returns the signature subpacket of the given type and body.
+fn to_subpacket(subpacket_type: u8, body: &[u8]) -> Vec<u8> {
+    [&to_length(body.len() + 1)[..], &[subpacket_type], body].concat()
+}
+
+/// Returns the packet of the given tag and body, with a new format header.
+fn to_packet(tag: u8, body: &[u8]) -> Vec<u8> {
+    [&[0xc0 | tag][..], &to_length(body.len()), body].concat()
+}
+
+/// Returns the new format encoding of the given length.
+fn to_length(length: usize) -> Vec<u8> {
+    match length {
+        0..=191 => vec![length as u8],
+        192..=8383 => vec![(((length - 192) >> 8) + 192) as u8, ((length - 192) & 0xff) as u8],
+        _ => [&[0xff][..], &(length as u32).to_be_bytes()].concat(),
+    }
+}
+
+/// Returns the ASCII armor of the given data, with the given block type and the CRC-24 checksum of the data.
+fn to_armor(block: &str, data: &[u8]) -> String {
+    let crc = data.iter().fold(0xb7_04ce_u32, |crc, byte| {
+        (0..8).fold(crc ^ ((*byte as u32) << 16), |crc, _| match crc & 0x80_0000 {
+            0 => crc << 1,
+            _ => (crc << 1) ^ 0x186_4cfb,
+        })
+    }) & 0xff_ffff;
+
+    format!(
+        "-----BEGIN PGP {}-----\n\n{}\n={}\n-----END PGP {}-----\n",
+        block,
+        data.to_base64(Config {
+            newline: Newline::LF,
+            line_length: Some(64),
+            ..STANDARD
+        }),
+        crc.to_be_bytes()[1..].to_base64(STANDARD),
+        block
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fingerprints() {
+        // The fingerprints listed by gpg on import of the secret key, with each self-signature valid
+        let key = PgpKey::new("Wagyu <wagyu@example.com>", [1u8; 32], [2u8; 32], [3u8; 32]);
+        assert_eq!(
+            "13F64BEB0D2D395515394FFC1CD353410FC2015A",
+            hex::encode_upper(key.to_fingerprint())
+        );
+        assert_eq!(
+            "55D1166709B4A028DCCFA22E9943DAD52B37F420",
+            hex::encode_upper(to_fingerprint(&to_eddsa_public_key(&key.signing)))
+        );
+        assert_eq!(
+            "7F7934A02DA48E31CDE38FBF1CA1BD0AE5CA027F",
+            hex::encode_upper(to_fingerprint(&to_ecdh_public_key(&key.encryption)))
+        );
+    }
+}
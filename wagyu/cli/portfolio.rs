@@ -0,0 +1,85 @@
+//! # Portfolio
+//!
+//! The first address of each coin of a BIP-39 mnemonic, at the standard BIP-44 path of the coin,
+//! for checking what a recovered mnemonic controls. The addresses are derived by each coin of the
+//! registry, and the coins that do not derive wallets from a BIP-39 mnemonic
+//! (Monero and Zcash) are listed as unsupported. A Nostr key is listed with its npub.
+
+use crate::cli::{CLIError, MaskSecrets, Registry};
+
+use colored::*;
+use core::{fmt, fmt::Display};
+use serde::Serialize;
+
+use crate::model::no_std::{format, String, ToString, Vec};
+
+/// Represents the first address of a coin of a portfolio
+#[derive(Serialize, Clone, Debug)]
+pub struct PortfolioAddress {
+    pub coin: String,
+    pub path: String,
+    pub address: String,
+}
+
+/// Represents the first addresses of the coins of a mnemonic
+#[derive(Serialize, Clone, Debug)]
+pub struct Portfolio {
+    pub addresses: Vec<PortfolioAddress>,
+    pub unsupported: Vec<String>,
+}
+
+impl Portfolio {
+    /// Returns the portfolio of the given mnemonic and password, with the first address of each coin of the registry.
+    pub fn new(registry: &Registry, mnemonic: &str, password: Option<&str>) -> Result<Self, CLIError> {
+        let mut addresses = Vec::new();
+        let mut unsupported = Vec::new();
+        for backend in registry.backends() {
+            let coin = backend.name();
+            match backend.generate(1, Some(mnemonic), password) {
+                Ok(wallets) => {
+                    let wallet = wallets.first().cloned().unwrap_or_default();
+                    // A Nostr key has no address, and is identified by its npub
+                    let address = wallet["address"].as_str().or_else(|| wallet["npub"].as_str());
+                    addresses.push(PortfolioAddress {
+                        coin: coin.to_string(),
+                        path: wallet["path"].as_str().unwrap_or_default().to_string(),
+                        address: address.unwrap_or_default().to_string(),
+                    });
+                }
+                Err(CLIError::UnsupportedMnemonic(_)) => unsupported.push(coin.to_string()),
+                Err(error) => return Err(error),
+            }
+        }
+        Ok(Self { addresses, unsupported })
+    }
+}
+
+impl MaskSecrets for Portfolio {
+    fn mask_secrets(&mut self) {}
+}
+
+#[cfg_attr(tarpaulin, skip)]
+impl Display for Portfolio {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f)?;
+        for address in &self.addresses {
+            let label = format!("{:<21}", address.coin);
+            writeln!(
+                f,
+                "      {}{:<18} {}",
+                label.cyan().bold(),
+                address.path,
+                address.address
+            )?;
+        }
+        match self.unsupported.is_empty() {
+            true => Ok(()),
+            false => write!(
+                f,
+                "      {}{}",
+                format!("{:<21}", "Unsupported").cyan().bold(),
+                self.unsupported.join(", ")
+            ),
+        }
+    }
+}
@@ -0,0 +1,85 @@
+//! # Preset
+//!
+//! A registry of named derivation presets for each coin, such as the derivation paths of popular wallets.
+//! Each preset is a derivation path template, expanded with the `{account}` and `{index}` placeholders.
+//!
+//! User-defined presets are read from the JSON file at the `WAGYU_PRESETS` environment variable, if set,
+//! and take precedence over the built-in presets of the same name.
+//!
+//! ```json
+//! {
+//!   "ethereum": {
+//!     "my-wallet": "m/44'/60'/{account}'/0/{index}"
+//!   }
+//! }
+//! ```
+
+use crate::cli::CLIError;
+use crate::model::DerivationPathTemplate;
+
+use core::str::FromStr;
+
+use crate::model::no_std::{BTreeMap, String, ToString};
+
+/// The environment variable to read user-defined derivation presets from
+pub const DERIVATION_PRESETS: &str = "WAGYU_PRESETS";
+
+/// The built-in Ethereum derivation presets
+const ETHEREUM_PRESETS: &[(&str, &str)] = &[
+    ("coinomi", "m/44'/60'/0'/0/{index}"),
+    ("ethereum", "m/44'/60'/0'/{index}"),
+    ("exodus", "m/44'/60'/0'/0/{index}"),
+    ("jaxx", "m/44'/60'/0'/0/{index}"),
+    ("keepkey", "m/44'/60'/{index}'/0"),
+    ("ledger-legacy", "m/44'/60'/0'/{index}"),
+    ("ledger-live", "m/44'/60'/{index}'/0/0"),
+    ("metamask", "m/44'/60'/0'/0/{index}"),
+    ("myetherwallet", "m/44'/60'/0'/0/{index}"),
+    ("trezor", "m/44'/60'/0'/{index}"),
+];
+
+/// Represents the derivation presets of a coin
+#[derive(Debug, Clone)]
+pub struct DerivationPresets {
+    presets: BTreeMap<String, String>,
+}
+
+impl DerivationPresets {
+    /// Returns the built-in and user-defined derivation presets of the given coin.
+    pub fn load(coin: &str) -> Result<Self, CLIError> {
+        let builtin = match coin {
+            "ethereum" => ETHEREUM_PRESETS,
+            _ => &[],
+        };
+        let mut presets: BTreeMap<String, String> = builtin
+            .iter()
+            .map(|(name, template)| (name.to_string(), template.to_string()))
+            .collect();
+
+        if let Ok(path) = std::env::var(DERIVATION_PRESETS) {
+            let config: BTreeMap<String, BTreeMap<String, String>> =
+                serde_json::from_str(&std::fs::read_to_string(path)?)?;
+            if let Some(user_presets) = config.get(coin) {
+                for (name, template) in user_presets {
+                    DerivationPathTemplate::from_str(template)?;
+                    presets.insert(name.clone(), template.clone());
+                }
+            }
+        }
+
+        Ok(Self { presets })
+    }
+
+    /// Returns the derivation path template of the given preset name.
+    pub fn get(&self, name: &str) -> Result<DerivationPathTemplate, CLIError> {
+        match self.presets.get(name) {
+            Some(template) => Ok(DerivationPathTemplate::from_str(template)?),
+            None => Err(CLIError::UnknownDerivationPreset(name.to_string())),
+        }
+    }
+
+    /// Returns the preset names and derivation path templates, ordered by name.
+    pub fn presets(&self) -> &BTreeMap<String, String> {
+        &self.presets
+    }
+}
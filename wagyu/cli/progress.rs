@@ -0,0 +1,55 @@
+//! # Progress
+//!
+//! A progress bar of a long-running search, redrawn in place on standard error. The bar is only
+//! drawn when standard error is a terminal, so the output of a redirected or piped search is unchanged.
+
+use colored::*;
+
+use crate::model::no_std::{format, String};
+
+/// The number of characters of a progress bar
+const PROGRESS_BAR_WIDTH: u64 = 40;
+
+/// Represents a progress bar of a search of a total number of items
+pub struct ProgressBar {
+    label: String,
+    total: u64,
+    enabled: bool,
+}
+
+impl ProgressBar {
+    /// Returns a progress bar of the given total number of items, drawn if standard error is a terminal.
+    pub fn new(label: &str, total: u64) -> Self {
+        Self {
+            label: label.into(),
+            total,
+            enabled: atty::is(atty::Stream::Stderr),
+        }
+    }
+
+    /// Redraws the progress bar with the given number of items searched.
+    pub fn update(&self, searched: u64) {
+        if !self.enabled {
+            return;
+        }
+        let searched = searched.min(self.total);
+        let filled = match self.total {
+            0 => PROGRESS_BAR_WIDTH,
+            total => searched * PROGRESS_BAR_WIDTH / total,
+        };
+        let bar = format!(
+            "{}{}",
+            "#".repeat(filled as usize),
+            " ".repeat((PROGRESS_BAR_WIDTH - filled) as usize)
+        );
+        eprint!("\r{} [{}] {}/{}", self.label.cyan().bold(), bar, searched, self.total);
+    }
+
+    /// Ends the progress bar with the given number of items searched, on its own line.
+    pub fn finish(&self, searched: u64) {
+        if self.enabled {
+            self.update(searched);
+            eprintln!();
+        }
+    }
+}
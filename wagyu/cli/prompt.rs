@@ -0,0 +1,75 @@
+//! # Prompt
+//!
+//! Interactive prompts on the terminal, and the read-back verification of a mnemonic backup.
+//! Prompts are written to standard error, so that they stay out of the printed output.
+
+use crate::cli::{localize, CLIError};
+
+use rand::{rngs::StdRng, seq::index::sample, Rng};
+use rand_core::SeedableRng;
+use serde::Serialize;
+use serde_json::Value;
+use std::io::{self, BufRead, Write};
+
+use crate::model::no_std::{format, String, ToString, Vec};
+use crate::model::utilities::unicode::nfkd;
+
+/// The number of attempts to enter each word of a mnemonic backup
+pub const BACKUP_ATTEMPTS: usize = 3;
+
+/// The number of randomly selected words to verify of a mnemonic backup
+pub const BACKUP_CHECKS: usize = 3;
+
+/// Returns the line entered on the terminal after the given message (in the language of the locale),
+/// without its line ending.
+pub fn prompt(message: &str) -> Result<String, CLIError> {
+    eprint!("{}", localize(message));
+    io::stderr().flush()?;
+    let mut line = String::new();
+    io::stdin().lock().read_line(&mut line)?;
+    Ok(line.trim_end_matches(&['\r', '\n'][..]).to_string())
+}
+
+/// Returns whether `y` or `yes` is entered on the terminal after the given message.
+pub fn confirm(message: &str) -> Result<bool, CLIError> {
+    let answer = prompt(&format!("{} [y/N]: ", message))?;
+    Ok(["y", "yes"].contains(&answer.trim().to_lowercase().as_str()))
+}
+
+/// Verifies that the given mnemonic is written down, by clearing the terminal and
+/// prompting for the given number of randomly selected words of the mnemonic.
+pub fn verify_backup<R: Rng>(rng: &mut R, mnemonic: &str, checks: usize) -> Result<(), CLIError> {
+    let words: Vec<&str> = mnemonic.split_whitespace().collect();
+    let mut positions = sample(rng, words.len(), checks.min(words.len())).into_vec();
+    positions.sort_unstable();
+
+    prompt("Write down the mnemonic, then press Enter to hide it and verify the backup: ")?;
+    eprint!("\x1B[2J\x1B[1;1H");
+
+    for position in positions {
+        let mut attempt = 1;
+        // A word is compared in its decomposed form, as an input method enters composed kana
+        let word = nfkd(words[position]);
+        while nfkd(prompt(&format!("Enter word #{} of the mnemonic: ", position + 1))?.trim()) != word {
+            if attempt == BACKUP_ATTEMPTS {
+                return Err(CLIError::FailedBackupVerification);
+            }
+            eprintln!("{}", localize("Incorrect word, try again"));
+            attempt += 1;
+        }
+    }
+    Ok(())
+}
+
+/// Shows the mnemonic of each of the given wallets alone, and verifies its backup,
+/// so that the wallets (and their keys and addresses) are only printed once every backup is verified.
+pub fn confirm_backups<T: Serialize>(wallets: &[T]) -> Result<(), CLIError> {
+    for wallet in wallets {
+        let wallet = serde_json::to_value(wallet)?;
+        if let Some(mnemonic) = wallet.get("mnemonic").and_then(Value::as_str) {
+            eprintln!("\n      {}\n", mnemonic);
+            verify_backup(&mut StdRng::from_entropy(), mnemonic, BACKUP_CHECKS)?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,497 @@
+//! # QR
+//!
+//! Encodes the QR codes (ISO/IEC 18004) of byte strings, such as the URIs of secrets, to be printed
+//! on a terminal and scanned by a phone. A code is encoded in byte mode at the medium error correction
+//! level, or in the mode and level of a format such as SeedQR, in the smallest version (1 to 10)
+//! that fits the data, with the mask of the lowest penalty.
+
+use crate::cli::CLIError;
+
+use core::{fmt, fmt::Display};
+
+use crate::model::no_std::{vec, String, Vec};
+
+/// The largest supported version of a QR code (57 x 57 modules)
+pub const QR_MAX_VERSION: usize = 10;
+
+/// The width of the quiet zone of a printed QR code, in modules
+const QUIET_ZONE: usize = 2;
+
+/// The error correction codewords per block and the number of blocks of each version,
+/// at the low and medium error correction levels
+const ECC_CODEWORDS_PER_BLOCK: [[usize; QR_MAX_VERSION + 1]; 2] = [
+    [0, 7, 10, 15, 20, 26, 18, 20, 24, 30, 18],
+    [0, 10, 16, 26, 18, 24, 16, 18, 22, 22, 26],
+];
+const ECC_BLOCKS: [[usize; QR_MAX_VERSION + 1]; 2] =
+    [[0, 1, 1, 1, 1, 1, 2, 2, 2, 2, 4], [0, 1, 1, 1, 2, 2, 4, 4, 4, 5, 5]];
+
+/// The error correction levels of a QR code, by the share of its codewords that can be restored
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QrLevel {
+    /// About 7% of the codewords
+    Low,
+    /// About 15% of the codewords
+    Medium,
+}
+
+/// The modes of the data of a QR code
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QrMode {
+    /// Decimal digits, three in ten bits
+    Numeric,
+    /// Bytes, each in eight bits
+    Byte,
+}
+
+/// Represents a QR code, as a grid of dark (`true`) and light (`false`) modules
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QrCode {
+    /// The version of the code, from 1 to 10
+    pub version: usize,
+    /// The error correction level of the code
+    pub level: QrLevel,
+    /// The modules of the code, by row
+    modules: Vec<Vec<bool>>,
+    /// The function modules of the code (finder, timing, alignment, format, and version patterns)
+    function: Vec<Vec<bool>>,
+}
+
+impl QrCode {
+    /// Returns the QR code of the given data in byte mode, at the medium error correction level.
+    pub fn new(data: &[u8]) -> Result<Self, CLIError> {
+        Self::encode(data, QrMode::Byte, QrLevel::Medium)
+    }
+
+    /// Returns the QR code of the given data in the given mode, at the given error correction level.
+    /// The data of numeric mode is a string of decimal digits.
+    pub fn encode(data: &[u8], mode: QrMode, level: QrLevel) -> Result<Self, CLIError> {
+        if mode == QrMode::Numeric && !data.iter().all(u8::is_ascii_digit) {
+            return Err(CLIError::InvalidEncoding(
+                "non-numeric data of a numeric QR code".into(),
+            ));
+        }
+
+        let bits = to_bits(data, mode);
+        let version = (1..=QR_MAX_VERSION)
+            .find(|version| 4 + count_bits(*version, mode) + bits.len() <= data_codewords(*version, level) * 8)
+            .ok_or(CLIError::QrCodeTooLong(data.len()))?;
+
+        let size = version * 4 + 17;
+        let mut code = Self {
+            version,
+            level,
+            modules: vec![vec![false; size]; size],
+            function: vec![vec![false; size]; size],
+        };
+        code.draw_function_patterns();
+        code.draw_codewords(&to_codewords(version, level, mode, data.len(), &bits));
+
+        let mask = (0..8)
+            .min_by_key(|mask| {
+                let mut masked = code.clone();
+                masked.apply_mask(*mask);
+                masked.draw_format_bits(*mask);
+                masked.penalty()
+            })
+            .unwrap_or_default();
+        code.apply_mask(mask);
+        code.draw_format_bits(mask);
+        Ok(code)
+    }
+
+    /// Returns the number of modules on each side of the code.
+    pub fn size(&self) -> usize {
+        self.modules.len()
+    }
+
+    /// Returns whether the module at the given column and row is dark.
+    pub fn is_dark(&self, x: usize, y: usize) -> bool {
+        self.modules[y][x]
+    }
+
+    /// Sets the function module at the given column and row.
+    fn set_function(&mut self, x: usize, y: usize, dark: bool) {
+        self.modules[y][x] = dark;
+        self.function[y][x] = true;
+    }
+
+    /// Draws the finder, timing, alignment, and version patterns, and reserves the format modules.
+    fn draw_function_patterns(&mut self) {
+        let size = self.size();
+        for i in 0..size {
+            self.set_function(6, i, i % 2 == 0);
+            self.set_function(i, 6, i % 2 == 0);
+        }
+
+        // The finder patterns and their separators, at three corners
+        for (x, y) in [(3, 3), (size - 4, 3), (3, size - 4)].iter() {
+            for dy in -4i32..=4 {
+                for dx in -4i32..=4 {
+                    let (x, y) = (*x as i32 + dx, *y as i32 + dy);
+                    if x >= 0 && y >= 0 && (x as usize) < size && (y as usize) < size {
+                        let distance = dx.abs().max(dy.abs());
+                        self.set_function(x as usize, y as usize, distance != 2 && distance != 4);
+                    }
+                }
+            }
+        }
+
+        // The alignment patterns, except at the corners of the finder patterns
+        let positions = alignment_positions(self.version);
+        for (i, y) in positions.iter().enumerate() {
+            for (j, x) in positions.iter().enumerate() {
+                let last = positions.len() - 1;
+                if (i, j) == (0, 0) || (i, j) == (0, last) || (i, j) == (last, 0) {
+                    continue;
+                }
+                for dy in -2i32..=2 {
+                    for dx in -2i32..=2 {
+                        let (x, y) = ((*x as i32 + dx) as usize, (*y as i32 + dy) as usize);
+                        self.set_function(x, y, dx.abs().max(dy.abs()) != 1);
+                    }
+                }
+            }
+        }
+
+        // The version information of versions 7 and up, next to the top-right and bottom-left finders
+        if self.version >= 7 {
+            let bits = (self.version << 12) | bch_remainder(self.version, 12, 0x1f25);
+            for i in 0..18 {
+                let dark = (bits >> i) & 1 == 1;
+                let (a, b) = (size - 11 + i % 3, i / 3);
+                self.set_function(a, b, dark);
+                self.set_function(b, a, dark);
+            }
+        }
+
+        self.draw_format_bits(0);
+    }
+
+    /// Draws the two copies of the format information of the error correction level of the code
+    /// and the given mask, and the dark module.
+    fn draw_format_bits(&mut self, mask: usize) {
+        let size = self.size();
+        let format = match self.level {
+            QrLevel::Low => 0b01 << 3,
+            QrLevel::Medium => 0b00 << 3,
+        } | mask;
+        let bits = ((format << 10) | bch_remainder(format, 10, 0x537)) ^ 0x5412;
+        let bit = |i: usize| (bits >> i) & 1 == 1;
+
+        for i in 0..6 {
+            self.set_function(8, i, bit(i));
+        }
+        self.set_function(8, 7, bit(6));
+        self.set_function(8, 8, bit(7));
+        self.set_function(7, 8, bit(8));
+        for i in 9..15 {
+            self.set_function(14 - i, 8, bit(i));
+        }
+
+        for i in 0..8 {
+            self.set_function(size - 1 - i, 8, bit(i));
+        }
+        for i in 8..15 {
+            self.set_function(8, size - 15 + i, bit(i));
+        }
+        self.set_function(8, size - 8, true);
+    }
+
+    /// Draws the given codewords in the data modules, in pairs of columns from the bottom-right corner,
+    /// alternately upward and downward, skipping the vertical timing pattern.
+    fn draw_codewords(&mut self, codewords: &[u8]) {
+        let size = self.size();
+        let mut i = 0;
+        let mut right = size - 1;
+        loop {
+            if right == 6 {
+                right = 5;
+            }
+            for vertical in 0..size {
+                for x in [right, right - 1].iter() {
+                    let upward = (right + 1) & 2 == 0;
+                    let y = match upward {
+                        true => size - 1 - vertical,
+                        false => vertical,
+                    };
+                    if !self.function[y][*x] && i < codewords.len() * 8 {
+                        self.modules[y][*x] = (codewords[i / 8] >> (7 - i % 8)) & 1 == 1;
+                        i += 1;
+                    }
+                }
+            }
+            match right {
+                1 => break,
+                _ => right -= 2,
+            }
+        }
+    }
+
+    /// Inverts the data modules selected by the given mask.
+    fn apply_mask(&mut self, mask: usize) {
+        let size = self.size();
+        for y in 0..size {
+            for x in 0..size {
+                let invert = match mask {
+                    0 => (x + y) % 2 == 0,
+                    1 => y % 2 == 0,
+                    2 => x % 3 == 0,
+                    3 => (x + y) % 3 == 0,
+                    4 => (x / 3 + y / 2) % 2 == 0,
+                    5 => x * y % 2 + x * y % 3 == 0,
+                    6 => (x * y % 2 + x * y % 3) % 2 == 0,
+                    _ => ((x + y) % 2 + x * y % 3) % 2 == 0,
+                };
+                self.modules[y][x] ^= invert && !self.function[y][x];
+            }
+        }
+    }
+
+    /// Returns the penalty of the code, of its runs of modules of the same color, its 2 x 2 blocks
+    /// of the same color, its patterns that look like finders, and its imbalance of dark modules.
+    fn penalty(&self) -> usize {
+        let size = self.size();
+        let lines = (0..size)
+            .map(|y| self.modules[y].clone())
+            .chain((0..size).map(|x| (0..size).map(|y| self.modules[y][x]).collect()))
+            .collect::<Vec<Vec<bool>>>();
+
+        let mut penalty = 0;
+        for line in &lines {
+            let mut run = 1;
+            for i in 1..=size {
+                match i < size && line[i] == line[i - 1] {
+                    true => run += 1,
+                    false => {
+                        if run >= 5 {
+                            penalty += run - 2;
+                        }
+                        run = 1;
+                    }
+                }
+            }
+
+            let finder = [true, false, true, true, true, false, true, false, false, false, false];
+            for window in line.windows(finder.len()) {
+                if window.iter().eq(finder.iter()) || window.iter().eq(finder.iter().rev()) {
+                    penalty += 40;
+                }
+            }
+        }
+
+        for y in 1..size {
+            for x in 1..size {
+                let color = self.modules[y][x];
+                if self.modules[y - 1][x] == color
+                    && self.modules[y][x - 1] == color
+                    && self.modules[y - 1][x - 1] == color
+                {
+                    penalty += 3;
+                }
+            }
+        }
+
+        let dark = self.modules.iter().flatten().filter(|dark| **dark).count();
+        let percent = (dark * 100 / (size * size)) as i32;
+        penalty + ((percent - 50).abs() / 5 * 10) as usize
+    }
+}
+
+/// Returns the number of bits of the character count of the given mode in the given version.
+fn count_bits(version: usize, mode: QrMode) -> usize {
+    match (mode, version) {
+        (QrMode::Numeric, 1..=9) => 10,
+        (QrMode::Numeric, _) => 12,
+        (QrMode::Byte, 1..=9) => 8,
+        (QrMode::Byte, _) => 16,
+    }
+}
+
+/// Returns the number of data codewords of the given version and error correction level,
+/// its codewords without its error correction.
+fn data_codewords(version: usize, level: QrLevel) -> usize {
+    let (ecc_length, blocks) = (ECC_CODEWORDS_PER_BLOCK[level as usize], ECC_BLOCKS[level as usize]);
+    raw_codewords(version) - ecc_length[version] * blocks[version]
+}
+
+/// Returns the number of codewords of the given version, the modules that are not function modules.
+fn raw_codewords(version: usize) -> usize {
+    let mut modules = (16 * version + 128) * version + 64;
+    if version >= 2 {
+        let alignments = version / 7 + 2;
+        modules -= (25 * alignments - 10) * alignments - 55;
+        if version >= 7 {
+            modules -= 36;
+        }
+    }
+    modules / 8
+}
+
+/// Returns the row and column positions of the centers of the alignment patterns of the given version.
+fn alignment_positions(version: usize) -> Vec<usize> {
+    match version {
+        1 => vec![],
+        2..=6 => vec![6, version * 4 + 10],
+        _ => vec![6, version * 2 + 8, version * 4 + 10],
+    }
+}
+
+/// Returns the BCH remainder of the given value shifted by the given number of bits,
+/// divided by the given generator polynomial.
+fn bch_remainder(value: usize, bits: usize, generator: usize) -> usize {
+    (0..bits).fold(value, |remainder, _| {
+        (remainder << 1) ^ ((remainder >> (bits - 1)) * generator)
+    })
+}
+
+/// Returns the given value as bits of the given length, from its most significant bit.
+fn to_bits_of_length(value: usize, length: usize) -> Vec<bool> {
+    (0..length).rev().map(|i| (value >> i) & 1 == 1).collect()
+}
+
+/// Returns the bits of the given data in the given mode, without its mode and character count.
+fn to_bits(data: &[u8], mode: QrMode) -> Vec<bool> {
+    match mode {
+        // Each group of three digits is encoded in ten bits, and a last group of one or two digits in four or seven bits
+        QrMode::Numeric => data
+            .chunks(3)
+            .flat_map(|digits| {
+                let value = digits
+                    .iter()
+                    .fold(0, |value, digit| value * 10 + (digit - b'0') as usize);
+                to_bits_of_length(value, digits.len() * 3 + 1)
+            })
+            .collect(),
+        QrMode::Byte => data
+            .iter()
+            .flat_map(|byte| to_bits_of_length(*byte as usize, 8))
+            .collect(),
+    }
+}
+
+/// Returns the codewords of the given data bits in the given mode, of the given number of characters,
+/// in the given version and error correction level, split in blocks with their Reed-Solomon error
+/// correction codewords, and interleaved.
+fn to_codewords(version: usize, level: QrLevel, mode: QrMode, characters: usize, data: &[bool]) -> Vec<u8> {
+    let mode_indicator = match mode {
+        QrMode::Numeric => 0b0001,
+        QrMode::Byte => 0b0100,
+    };
+    let mut bits = to_bits_of_length(mode_indicator, 4);
+    bits.extend(to_bits_of_length(characters, count_bits(version, mode)));
+    bits.extend_from_slice(data);
+
+    // The data is terminated with up to four zero bits, and padded to the capacity of the version
+    let capacity = data_codewords(version, level) * 8;
+    let terminator = (capacity - bits.len()).min(4);
+    bits.extend(vec![false; terminator]);
+    bits.extend(vec![false; (8 - bits.len() % 8) % 8]);
+    let mut codewords = bits
+        .chunks(8)
+        .map(|byte| byte.iter().fold(0u8, |value, bit| (value << 1) | *bit as u8))
+        .collect::<Vec<u8>>();
+    codewords.extend([0xec, 0x11].iter().cycle().take(capacity / 8 - codewords.len()));
+
+    // The first blocks are one codeword shorter than the last blocks if the codewords are not evenly split
+    let (blocks, ecc_length) = (
+        ECC_BLOCKS[level as usize][version],
+        ECC_CODEWORDS_PER_BLOCK[level as usize][version],
+    );
+    let short_blocks = blocks - raw_codewords(version) % blocks;
+    let short_length = raw_codewords(version) / blocks - ecc_length;
+    let mut start = 0;
+    let mut data_blocks = Vec::new();
+    for i in 0..blocks {
+        let length = short_length + (i >= short_blocks) as usize;
+        data_blocks.push(&codewords[start..start + length]);
+        start += length;
+    }
+    let generator = reed_solomon_generator(ecc_length);
+    let ecc_blocks = data_blocks
+        .iter()
+        .map(|block| reed_solomon_remainder(block, &generator))
+        .collect::<Vec<_>>();
+
+    let mut interleaved = Vec::new();
+    for i in 0..=short_length {
+        data_blocks
+            .iter()
+            .filter_map(|block| block.get(i))
+            .for_each(|codeword| interleaved.push(*codeword));
+    }
+    for i in 0..ecc_length {
+        ecc_blocks.iter().for_each(|block| interleaved.push(block[i]));
+    }
+    interleaved
+}
+
+/// Returns the product of the given elements of GF(2^8), modulo the polynomial x^8 + x^4 + x^3 + x^2 + 1.
+fn gf_multiply(x: u8, y: u8) -> u8 {
+    (0..8).rev().fold(0u8, |product, i| {
+        let product = (product << 1) ^ ((product >> 7) * 0x1d);
+        product ^ (((y >> i) & 1) * x)
+    })
+}
+
+/// Returns the coefficients of the Reed-Solomon generator polynomial of the given degree,
+/// the product of (x - 2^i) for i from 0 to degree - 1, without its leading coefficient.
+fn reed_solomon_generator(degree: usize) -> Vec<u8> {
+    let mut coefficients = vec![0u8; degree];
+    coefficients[degree - 1] = 1;
+    let mut root = 1u8;
+    for _ in 0..degree {
+        for j in 0..degree {
+            coefficients[j] = gf_multiply(coefficients[j], root);
+            if j + 1 < degree {
+                coefficients[j] ^= coefficients[j + 1];
+            }
+        }
+        root = gf_multiply(root, 0x02);
+    }
+    coefficients
+}
+
+/// Returns the remainder of the given data divided by the given Reed-Solomon generator polynomial.
+fn reed_solomon_remainder(data: &[u8], generator: &[u8]) -> Vec<u8> {
+    let mut remainder = vec![0u8; generator.len()];
+    for byte in data {
+        let factor = byte ^ remainder.remove(0);
+        remainder.push(0);
+        remainder
+            .iter_mut()
+            .zip(generator)
+            .for_each(|(coefficient, generator)| *coefficient ^= gf_multiply(*generator, factor));
+    }
+    remainder
+}
+
+/// Prints the code with half blocks, two rows of modules per line, with its light modules
+/// and quiet zone filled, so that it scans on the dark background of a terminal.
+#[cfg_attr(tarpaulin, skip)]
+impl Display for QrCode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let size = self.size() + QUIET_ZONE * 2;
+        let light = |x: usize, y: usize| {
+            y < size
+                && (x < QUIET_ZONE
+                    || y < QUIET_ZONE
+                    || x >= size - QUIET_ZONE
+                    || y >= size - QUIET_ZONE
+                    || !self.is_dark(x - QUIET_ZONE, y - QUIET_ZONE))
+        };
+
+        for y in (0..size).step_by(2) {
+            let line = (0..size)
+                .map(|x| match (light(x, y), light(x, y + 1)) {
+                    (true, true) => '█',
+                    (true, false) => '▀',
+                    (false, true) => '▄',
+                    (false, false) => ' ',
+                })
+                .collect::<String>();
+            writeln!(f, "{}", line)?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,396 @@
+//! # Recipient
+//!
+//! Encrypts output to one or more public key recipients before it is written,
+//! so that only the holders of the corresponding private keys can read it.
+//!
+//! Recipients are either age X25519 recipients (`age1...`), encrypted natively to the
+//! armored age v1 format (https://age-encryption.org/v1), or GPG key identifiers,
+//! encrypted by the local `gpg` installation. Encrypted output can be decrypted with
+//! `age --decrypt` or `gpg --decrypt` respectively.
+
+use crate::cli::CLIError;
+use crate::model::crypto::constant_time_eq;
+
+use bech32::{Bech32, FromBase32};
+use crypto::{
+    chacha20::ChaCha20,
+    curve25519::{curve25519, curve25519_base},
+    hkdf::{hkdf_expand, hkdf_extract},
+    hmac::Hmac,
+    mac::Mac,
+    poly1305::Poly1305,
+    sha2::Sha256,
+    symmetriccipher::SynchronousStreamCipher,
+};
+use rand::{rngs::StdRng, Rng};
+use rand_core::SeedableRng;
+use rustc_serialize::base64::{CharacterSet, Config, Newline, ToBase64, STANDARD};
+use std::{
+    io::Write,
+    process::{Command, Stdio},
+    str::FromStr,
+};
+
+use crate::model::no_std::{format, vec, String, ToString, Vec};
+
+/// The size of each encrypted chunk of an age payload
+const AGE_CHUNK_SIZE: usize = 64 * 1024;
+
+/// The unpadded base64 encoding of age headers
+const AGE_BASE64: Config = Config {
+    char_set: CharacterSet::Standard,
+    newline: Newline::LF,
+    pad: false,
+    line_length: None,
+};
+
+/// Represents a public key recipient of encrypted output
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Recipient {
+    /// An age X25519 recipient
+    Age([u8; 32]),
+    /// A GPG key identifier (key id, fingerprint, or user id)
+    Gpg(String),
+}
+
+impl Recipient {
+    /// Returns the recipients of the given comma separated recipient list.
+    pub fn from_list(recipients: &str) -> Result<Vec<Self>, CLIError> {
+        let recipients = recipients
+            .split(',')
+            .map(|recipient| Self::from_str(recipient.trim()))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let age = recipients.iter().filter(|recipient| recipient.is_age()).count();
+        if age != 0 && age != recipients.len() {
+            return Err(CLIError::InvalidRecipient(
+                "age and GPG recipients cannot be mixed".into(),
+            ));
+        }
+        Ok(recipients)
+    }
+
+    /// Returns `true` if the recipient is an age recipient.
+    pub fn is_age(&self) -> bool {
+        match self {
+            Recipient::Age(_) => true,
+            Recipient::Gpg(_) => false,
+        }
+    }
+}
+
+impl FromStr for Recipient {
+    type Err = CLIError;
+
+    fn from_str(recipient: &str) -> Result<Self, Self::Err> {
+        if recipient.is_empty() {
+            return Err(CLIError::InvalidRecipient(recipient.into()));
+        }
+        if !recipient.starts_with("age1") {
+            return Ok(Recipient::Gpg(recipient.to_string()));
+        }
+
+        let bech32 = Bech32::from_str(recipient).map_err(|_| CLIError::InvalidRecipient(recipient.into()))?;
+        let data = Vec::<u8>::from_base32(bech32.data()).map_err(|_| CLIError::InvalidRecipient(recipient.into()))?;
+        if bech32.hrp() != "age" || data.len() != 32 {
+            return Err(CLIError::InvalidRecipient(recipient.into()));
+        }
+
+        let mut public_key = [0u8; 32];
+        public_key.copy_from_slice(&data);
+        Ok(Recipient::Age(public_key))
+    }
+}
+
+/// Returns the given plaintext encrypted to the given recipients, in ASCII armor.
+pub fn encrypt_to(plaintext: &[u8], recipients: &[Recipient]) -> Result<String, CLIError> {
+    let mut public_keys = vec![];
+    let mut key_ids = vec![];
+    for recipient in recipients {
+        match recipient {
+            Recipient::Age(public_key) => public_keys.push(*public_key),
+            Recipient::Gpg(key_id) => key_ids.push(key_id.as_str()),
+        }
+    }
+
+    match public_keys.is_empty() {
+        true => gpg_encrypt(plaintext, &key_ids),
+        false => Ok(age_armor(&age_encrypt(
+            &mut StdRng::from_entropy(),
+            plaintext,
+            &public_keys,
+        )?)),
+    }
+}
+
+/// Returns the given plaintext encrypted by `gpg` to the given key identifiers.
+fn gpg_encrypt(plaintext: &[u8], key_ids: &[&str]) -> Result<String, CLIError> {
+    let mut command = Command::new("gpg");
+    command.args(["--batch", "--armor", "--encrypt"]);
+    key_ids.iter().for_each(|key_id| {
+        command.args(["--recipient", key_id]);
+    });
+
+    let mut gpg = command
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    if let Some(stdin) = gpg.stdin.as_mut() {
+        stdin.write_all(plaintext)?;
+    }
+
+    let output = gpg.wait_with_output()?;
+    match output.status.success() {
+        true => Ok(String::from_utf8_lossy(&output.stdout).to_string()),
+        false => Err(CLIError::Crate(
+            "gpg",
+            String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        )),
+    }
+}
+
+/// Returns the binary age v1 encryption of the given plaintext to the given X25519 public keys.
+fn age_encrypt<R: Rng>(rng: &mut R, plaintext: &[u8], public_keys: &[[u8; 32]]) -> Result<Vec<u8>, CLIError> {
+    let file_key: [u8; 16] = rng.gen();
+
+    // Wrap the file key to each recipient with an ephemeral X25519 share
+    let mut header = String::from("age-encryption.org/v1\n");
+    for public_key in public_keys {
+        let ephemeral_secret: [u8; 32] = rng.gen();
+        let ephemeral_share = curve25519_base(&ephemeral_secret);
+        let shared_secret = curve25519(&ephemeral_secret, public_key);
+        if constant_time_eq(&shared_secret, &[0u8; 32]) {
+            return Err(CLIError::InvalidRecipient("low order X25519 public key".into()));
+        }
+
+        let mut salt = ephemeral_share.to_vec();
+        salt.extend_from_slice(public_key);
+        let wrap_key = hkdf_sha256(&shared_secret, &salt, b"age-encryption.org/v1/X25519");
+        let wrapped_key = chacha20_poly1305(&wrap_key, &[0u8; 12], &file_key);
+
+        header.push_str(&format!("-> X25519 {}\n", ephemeral_share.to_base64(AGE_BASE64)));
+        header.push_str(&age_wrap(&wrapped_key.to_base64(AGE_BASE64)));
+    }
+    header.push_str("---");
+
+    let mut mac = Hmac::new(Sha256::new(), &hkdf_sha256(&file_key, &[], b"header"));
+    mac.input(header.as_bytes());
+    let mut file = format!("{} {}\n", header, mac.result().code().to_base64(AGE_BASE64)).into_bytes();
+
+    // Encrypt the payload in chunks with the STREAM construction
+    let nonce: [u8; 16] = rng.gen();
+    let payload_key = hkdf_sha256(&file_key, &nonce, b"payload");
+    file.extend_from_slice(&nonce);
+
+    let chunks = match plaintext.is_empty() {
+        true => vec![plaintext],
+        false => plaintext.chunks(AGE_CHUNK_SIZE).collect(),
+    };
+    for (counter, chunk) in chunks.iter().enumerate() {
+        let mut chunk_nonce = [0u8; 12];
+        chunk_nonce[3..11].copy_from_slice(&(counter as u64).to_be_bytes());
+        chunk_nonce[11] = (counter == chunks.len() - 1) as u8;
+        file.extend_from_slice(&chacha20_poly1305(&payload_key, &chunk_nonce, chunk));
+    }
+
+    Ok(file)
+}
+
+/// Returns the given base64 string wrapped in 64 column lines, terminated by a short line.
+fn age_wrap(base64: &str) -> String {
+    let mut lines = String::new();
+    let mut remaining = base64;
+    while remaining.len() >= 64 {
+        lines.push_str(&remaining[..64]);
+        lines.push('\n');
+        remaining = &remaining[64..];
+    }
+    lines.push_str(remaining);
+    lines.push('\n');
+    lines
+}
+
+/// Returns the ASCII armor of the given binary age file.
+fn age_armor(file: &[u8]) -> String {
+    let base64 = Config {
+        newline: Newline::LF,
+        line_length: Some(64),
+        ..STANDARD
+    };
+    format!(
+        "-----BEGIN AGE ENCRYPTED FILE-----\n{}\n-----END AGE ENCRYPTED FILE-----\n",
+        file.to_base64(base64)
+    )
+}
+
+/// Returns the 32-byte HKDF-SHA256 output of the given input key material, salt, and info.
+fn hkdf_sha256(ikm: &[u8], salt: &[u8], info: &[u8]) -> [u8; 32] {
+    let mut prk = [0u8; 32];
+    hkdf_extract(Sha256::new(), salt, ikm, &mut prk);
+    let mut okm = [0u8; 32];
+    hkdf_expand(Sha256::new(), &prk, info, &mut okm);
+    okm
+}
+
+/// Returns the ChaCha20-Poly1305 (RFC 8439) ciphertext and tag of the given plaintext, without additional data.
+fn chacha20_poly1305(key: &[u8; 32], nonce: &[u8; 12], plaintext: &[u8]) -> Vec<u8> {
+    let mut cipher = ChaCha20::new(key, nonce);
+
+    // The first keystream block (counter 0) derives the one-time Poly1305 key
+    let mut block = [0u8; 64];
+    cipher.process(&[0u8; 64], &mut block);
+
+    let mut ciphertext = vec![0u8; plaintext.len()];
+    cipher.process(plaintext, &mut ciphertext);
+
+    let mut poly1305 = Poly1305::new(&block[..32]);
+    poly1305.input(&ciphertext);
+    poly1305.input(&vec![0u8; (16 - ciphertext.len() % 16) % 16]);
+    poly1305.input(&0u64.to_le_bytes());
+    poly1305.input(&(ciphertext.len() as u64).to_le_bytes());
+
+    let mut tag = [0u8; 16];
+    poly1305.raw_result(&mut tag);
+    ciphertext.extend_from_slice(&tag);
+    ciphertext
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crypto::digest::Digest;
+    use rustc_serialize::base64::FromBase64;
+
+    /// Returns the hex SHA-256 hash of the given bytes.
+    fn sha256_hex(bytes: &[u8]) -> String {
+        let mut sha256 = Sha256::new();
+        sha256.input(bytes);
+        sha256.result_str()
+    }
+
+    /// The X25519 secret key of the test identity,
+    /// `AGE-SECRET-KEY-1QURSWPC8QURSWPC8QURSWPC8QURSWPC8QURSWPC8QURSWPC8QURSKMP32K`
+    const IDENTITY: [u8; 32] = [7u8; 32];
+
+    /// The recipient of the test identity
+    const RECIPIENT: &str = "age1zwlyl6h27gzv0lfntr7fcqrjrzqazap8sy5zylkxwneh7llf0dksu37mwt";
+
+    /// Returns the plaintext of the given ChaCha20-Poly1305 ciphertext and tag, checking its tag.
+    fn chacha20_poly1305_open(key: &[u8; 32], nonce: &[u8; 12], sealed: &[u8]) -> Vec<u8> {
+        let mut cipher = ChaCha20::new(key, nonce);
+        cipher.process(&[0u8; 64], &mut [0u8; 64]);
+        let mut plaintext = vec![0u8; sealed.len() - 16];
+        cipher.process(&sealed[..sealed.len() - 16], &mut plaintext);
+        assert_eq!(sealed, &chacha20_poly1305(key, nonce, &plaintext)[..]);
+        plaintext
+    }
+
+    /// Returns the plaintext of the given binary age file to the test identity, checking the MAC of its header,
+    /// and that its last chunk, and only its last chunk, is final and is empty only if the plaintext is empty.
+    fn age_decrypt(file: &[u8]) -> Vec<u8> {
+        let header_end = file.windows(4).position(|window| window == b"\n---").unwrap() + 4;
+        let mac_end = header_end + file[header_end..].iter().position(|byte| *byte == b'\n').unwrap();
+        let header = String::from_utf8(file[..header_end].to_vec()).unwrap();
+        let lines = header.lines().collect::<Vec<_>>();
+        assert_eq!("age-encryption.org/v1", lines[0]);
+        assert_eq!(4, lines.len());
+
+        let public_key = curve25519_base(&IDENTITY);
+        let ephemeral_share = lines[1].strip_prefix("-> X25519 ").unwrap().from_base64().unwrap();
+        let shared_secret = curve25519(&IDENTITY, &ephemeral_share);
+        let salt = [&ephemeral_share[..], &public_key[..]].concat();
+        let wrap_key = hkdf_sha256(&shared_secret, &salt, b"age-encryption.org/v1/X25519");
+        let file_key = chacha20_poly1305_open(&wrap_key, &[0u8; 12], &lines[2].from_base64().unwrap());
+
+        let mut mac = Hmac::new(Sha256::new(), &hkdf_sha256(&file_key, &[], b"header"));
+        mac.input(header.as_bytes());
+        let expected_mac = std::str::from_utf8(&file[header_end + 1..mac_end]).unwrap();
+        assert_eq!(expected_mac, mac.result().code().to_base64(AGE_BASE64));
+
+        let (nonce, payload) = file[mac_end + 1..].split_at(16);
+        let payload_key = hkdf_sha256(&file_key, nonce, b"payload");
+        let chunks = payload.chunks(AGE_CHUNK_SIZE + 16).collect::<Vec<_>>();
+        let mut plaintext = vec![];
+        for (counter, chunk) in chunks.iter().enumerate() {
+            let mut chunk_nonce = [0u8; 12];
+            chunk_nonce[3..11].copy_from_slice(&(counter as u64).to_be_bytes());
+            chunk_nonce[11] = (counter == chunks.len() - 1) as u8;
+            let chunk = chacha20_poly1305_open(&payload_key, &chunk_nonce, chunk);
+            assert!(!chunk.is_empty() || chunks.len() == 1);
+            plaintext.extend_from_slice(&chunk);
+        }
+        plaintext
+    }
+
+    fn test_age_encrypt(seed: u8, length: usize, expected_chunks: usize) -> Vec<u8> {
+        let plaintext = (0..length).map(|index| (index % 251) as u8).collect::<Vec<_>>();
+        let recipients = Recipient::from_list(RECIPIENT).unwrap();
+        assert_eq!(vec![Recipient::Age(curve25519_base(&IDENTITY))], recipients);
+
+        let public_keys = [curve25519_base(&IDENTITY)];
+        let file = age_encrypt(&mut StdRng::from_seed([seed; 32]), &plaintext, &public_keys).unwrap();
+        let header_end = file.windows(4).position(|window| window == b"\n---").unwrap();
+        let payload_start = header_end + file[header_end + 1..].iter().position(|byte| *byte == b'\n').unwrap() + 2;
+        assert_eq!(16 + length + 16 * expected_chunks, file.len() - payload_start);
+        assert_eq!(plaintext, age_decrypt(&file));
+        file
+    }
+
+    #[test]
+    fn age_encrypt_empty() {
+        // Decrypts to the empty plaintext with an independent age decrypter (on Python's cryptography)
+        let expected_armor = "\
+-----BEGIN AGE ENCRYPTED FILE-----\n\
+YWdlLWVuY3J5cHRpb24ub3JnL3YxCi0+IFgyNTUxOSBXSm4wdkErOTJXYWVkS1B3\n\
+NmNTYjNkQW1rUENqWk1ZeENmdWZMa0dIV1JjCjVJdzNVSno2SUt0V1hLNXlnQWVW\n\
+SnlhK0w4NmpYUElEMWRPQ0VZd2N3eTAKLS0tIHlLUGNrcnBSRGJWaTR0TVFYZnYz\n\
+VVJpWU03bjFSZVE4bWlIOGVKZkVoeEEK9mUG+7g2Q3vXRVgNOr8h+UHfmC073mC3\n\
+LV9XgD/q7h0=\n\
+-----END AGE ENCRYPTED FILE-----\n";
+        let file = test_age_encrypt(1, 0, 1);
+        assert_eq!(expected_armor, age_armor(&file));
+    }
+
+    #[test]
+    fn age_encrypt_chunk_boundaries() {
+        // Each file decrypts to its plaintext with an independent age decrypter (on Python's cryptography)
+        let files = [
+            (
+                2,
+                1,
+                1,
+                "ae11925a5e5b45579c59bee5fb26cda55e27ff7e234be5fd190a02c8a6b14e5c",
+            ),
+            (
+                3,
+                AGE_CHUNK_SIZE,
+                1,
+                "eb8b74b07235bc47978806b4cfb9571216767e99929c4114cd8b9c4087f2f324",
+            ),
+            (
+                4,
+                AGE_CHUNK_SIZE + 1,
+                2,
+                "d2a075347721bcda07fd67ebfcaeb6c49e8cccd57062815faa6438bb29d68bc5",
+            ),
+            (
+                5,
+                2 * AGE_CHUNK_SIZE,
+                2,
+                "e9bfe04e470eb11a5ebcde912ad082c86965d46ba843600d80905879336c428d",
+            ),
+        ];
+        for (seed, length, chunks, expected_hash) in files.iter() {
+            assert_eq!(*expected_hash, sha256_hex(&test_age_encrypt(*seed, *length, *chunks)));
+        }
+    }
+
+    #[test]
+    fn invalid_recipient() {
+        assert!(Recipient::from_str("").is_err());
+        assert!(Recipient::from_str("age1zwlyl6h27gzv0lfntr7fcqrjrzqazap8sy5zylkxwneh7llf0dksu37mwq").is_err());
+        assert!(Recipient::from_list(&format!("{},0xDEADBEEF", RECIPIENT)).is_err());
+    }
+}
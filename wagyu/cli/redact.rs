@@ -0,0 +1,252 @@
+//! # Redact
+//!
+//! A redaction policy of the printed and logged wallets, which masks or omits chosen fields (such as
+//! `private_key` or `mnemonic`) in the text output, the JSON output, and the audit log. The policy is
+//! applied where the wallets are serialized, so it holds for the wallets of every coin and subcommand.
+//!
+//! The fields are set with `--redact` and `--redact-mode`, or by default with the `WAGYU_REDACT` and
+//! `WAGYU_REDACT_MODE` environment variables. A field of the text output is matched by its label,
+//! as `Private Key` for `private_key`.
+
+use crate::cli::{CLIError, SECRET_MASK};
+
+use core::fmt;
+use lazy_static::lazy_static;
+use serde::{
+    de::{self, Deserialize, Deserializer, MapAccess, SeqAccess, Visitor},
+    ser::{Serialize, SerializeMap, Serializer},
+};
+use serde_json::Value;
+use std::{env, sync::RwLock};
+
+use crate::model::no_std::{String, ToString, Vec};
+
+/// The environment variable of the default fields to redact, separated by commas
+pub const REDACT_FIELDS: &str = "WAGYU_REDACT";
+
+/// The environment variable of the default redaction mode, `mask` or `omit`
+pub const REDACT_MODE: &str = "WAGYU_REDACT_MODE";
+
+lazy_static! {
+    /// The redaction policy of the process, set with `--redact` and `--redact-mode`
+    static ref POLICY: RwLock<RedactionPolicy> = RwLock::new(RedactionPolicy::default());
+}
+
+/// Represents how a redacted field is printed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RedactMode {
+    /// The value of the field is replaced with `SECRET_MASK`
+    Mask,
+    /// The field is removed
+    Omit,
+}
+
+/// Represents the fields to redact, and how they are redacted
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RedactionPolicy {
+    fields: Vec<String>,
+    mode: RedactMode,
+}
+
+impl Default for RedactionPolicy {
+    fn default() -> Self {
+        Self {
+            fields: Vec::new(),
+            mode: RedactMode::Mask,
+        }
+    }
+}
+
+impl RedactionPolicy {
+    /// Returns the policy redacting the given fields, separated by commas, with the given mode.
+    pub fn new(fields: &str, mode: &str) -> Result<Self, CLIError> {
+        let mode = match mode {
+            "mask" => RedactMode::Mask,
+            "omit" => RedactMode::Omit,
+            _ => return Err(CLIError::InvalidRedactMode(mode.into())),
+        };
+        let fields = fields
+            .split(',')
+            .map(|field| field.trim().to_lowercase().replace([' ', '-'], "_"))
+            .filter(|field| !field.is_empty())
+            .collect();
+        Ok(Self { fields, mode })
+    }
+
+    /// Sets the policy of the process to the given fields and mode, or to those of the
+    /// `WAGYU_REDACT` and `WAGYU_REDACT_MODE` environment variables if none are specified.
+    pub fn set(fields: Option<&str>, mode: Option<&str>) -> Result<(), CLIError> {
+        let fields = fields.map(String::from).or_else(|| env::var(REDACT_FIELDS).ok());
+        let mode = mode.map(String::from).or_else(|| env::var(REDACT_MODE).ok());
+        let policy = Self::new(&fields.unwrap_or_default(), mode.as_deref().unwrap_or("mask"))?;
+        let mut current = POLICY
+            .write()
+            .map_err(|error| CLIError::Crate("redact", error.to_string()))?;
+        *current = policy;
+        Ok(())
+    }
+
+    /// Returns the policy of the process.
+    pub fn current() -> Self {
+        POLICY.read().map(|policy| policy.clone()).unwrap_or_default()
+    }
+
+    /// Returns `true` if the policy redacts no fields.
+    pub fn is_empty(&self) -> bool {
+        self.fields.is_empty()
+    }
+
+    /// Returns the given value with its redacted fields masked or omitted, at any depth,
+    /// and with the order of its fields preserved.
+    pub fn redact<T: Serialize>(&self, value: &T) -> Result<RedactedValue, CLIError> {
+        let mut value: RedactedValue = serde_json::from_str(&serde_json::to_string(value)?)?;
+        self.redact_value(&mut value);
+        Ok(value)
+    }
+
+    /// Masks or omits the redacted fields of the given value, at any depth.
+    fn redact_value(&self, value: &mut RedactedValue) {
+        match value {
+            RedactedValue::Object(fields) => {
+                if self.mode == RedactMode::Omit {
+                    fields.retain(|(name, _)| !self.fields.contains(name));
+                }
+                for (name, value) in fields.iter_mut() {
+                    match value {
+                        RedactedValue::Scalar(Value::Null) => {}
+                        _ if self.fields.contains(name) => *value = RedactedValue::Scalar(SECRET_MASK.into()),
+                        _ => self.redact_value(value),
+                    }
+                }
+            }
+            RedactedValue::Array(values) => values.iter_mut().for_each(|value| self.redact_value(value)),
+            RedactedValue::Scalar(_) => {}
+        }
+    }
+
+    /// Returns the given text output with the lines of its redacted fields masked or omitted.
+    pub fn redact_text(&self, text: &str) -> String {
+        if self.is_empty() {
+            return text.into();
+        }
+        text.split('\n')
+            .filter_map(|line| {
+                // A field is redacted on a line of its label, followed by its value
+                let plain = strip_colors(line);
+                let plain = plain.trim_start();
+                let value = self.fields.iter().find_map(|field| {
+                    let label = field.replace('_', " ");
+                    match plain.get(..label.len()) {
+                        Some(prefix) if prefix.eq_ignore_ascii_case(&label) => {
+                            plain[label.len()..].strip_prefix(' ').map(|value| value.trim_start())
+                        }
+                        _ => None,
+                    }
+                });
+                match (value, self.mode) {
+                    (None, _) => Some(line.to_string()),
+                    // The value of a field ends its line, after its (colored) label
+                    (Some(value), RedactMode::Mask) => Some([&line[..line.len() - value.len()], SECRET_MASK].concat()),
+                    (Some(_), RedactMode::Omit) => None,
+                }
+            })
+            .collect::<Vec<String>>()
+            .join("\n")
+    }
+}
+
+/// Returns the given line without its ANSI color codes.
+fn strip_colors(line: &str) -> String {
+    let mut plain = String::with_capacity(line.len());
+    let mut characters = line.chars();
+    while let Some(character) = characters.next() {
+        match character {
+            '\x1b' => {
+                characters.find(|character| *character == 'm');
+            }
+            _ => plain.push(character),
+        }
+    }
+    plain
+}
+
+/// Represents a JSON value with the order of the fields of its objects preserved,
+/// so a redacted wallet is printed with its fields in the order of its type
+#[derive(Debug, Clone)]
+pub enum RedactedValue {
+    Object(Vec<(String, RedactedValue)>),
+    Array(Vec<RedactedValue>),
+    Scalar(Value),
+}
+
+impl Serialize for RedactedValue {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            RedactedValue::Object(fields) => {
+                let mut map = serializer.serialize_map(Some(fields.len()))?;
+                for (name, value) in fields {
+                    map.serialize_entry(name, value)?;
+                }
+                map.end()
+            }
+            RedactedValue::Array(values) => serializer.collect_seq(values),
+            RedactedValue::Scalar(value) => value.serialize(serializer),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for RedactedValue {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct RedactedValueVisitor;
+
+        impl<'de> Visitor<'de> for RedactedValueVisitor {
+            type Value = RedactedValue;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("a JSON value")
+            }
+
+            fn visit_bool<E: de::Error>(self, value: bool) -> Result<Self::Value, E> {
+                Ok(RedactedValue::Scalar(value.into()))
+            }
+
+            fn visit_i64<E: de::Error>(self, value: i64) -> Result<Self::Value, E> {
+                Ok(RedactedValue::Scalar(value.into()))
+            }
+
+            fn visit_u64<E: de::Error>(self, value: u64) -> Result<Self::Value, E> {
+                Ok(RedactedValue::Scalar(value.into()))
+            }
+
+            fn visit_f64<E: de::Error>(self, value: f64) -> Result<Self::Value, E> {
+                Ok(RedactedValue::Scalar(value.into()))
+            }
+
+            fn visit_str<E: de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                Ok(RedactedValue::Scalar(value.into()))
+            }
+
+            fn visit_unit<E: de::Error>(self) -> Result<Self::Value, E> {
+                Ok(RedactedValue::Scalar(Value::Null))
+            }
+
+            fn visit_seq<A: SeqAccess<'de>>(self, mut sequence: A) -> Result<Self::Value, A::Error> {
+                let mut values = Vec::new();
+                while let Some(value) = sequence.next_element()? {
+                    values.push(value);
+                }
+                Ok(RedactedValue::Array(values))
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Self::Value, A::Error> {
+                let mut fields = Vec::new();
+                while let Some(field) = map.next_entry()? {
+                    fields.push(field);
+                }
+                Ok(RedactedValue::Object(fields))
+            }
+        }
+
+        deserializer.deserialize_any(RedactedValueVisitor)
+    }
+}
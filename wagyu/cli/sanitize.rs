@@ -0,0 +1,172 @@
+//! # Sanitize
+//!
+//! A shared sanitization layer for user-supplied key material, applied before it is parsed. Surrounding
+//! whitespace, the `0x` prefixes and uppercase digits of hex keys, and the irregular spacing of mnemonics
+//! are normalized, and each normalization is reported. With `--strict`, such inputs are rejected instead.
+//!
+//! Base58 and Bech32 inputs (such as WIFs, extended keys, and addresses) are only trimmed, as their case
+//! is significant.
+//!
+//! Entropy and mnemonics are then analyzed for patterns of low entropy, and rejected unless forced.
+
+use crate::cli::{analyze_entropy, analyze_mnemonic, check_entropy, localize, CLIError};
+
+use core::sync::atomic::{AtomicBool, Ordering};
+
+use crate::model::no_std::{String, ToString, Vec};
+use crate::model::utilities::unicode::IDEOGRAPHIC_SPACE;
+
+/// Whether key material in a non-canonical form is rejected, set with `--strict`
+static STRICT: AtomicBool = AtomicBool::new(false);
+
+/// Represents the kind of an input of key material
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputKind {
+    /// A hex string of entropy, in lowercase without a `0x` prefix
+    Entropy,
+    /// A hex string, in lowercase without a `0x` prefix
+    Hex,
+    /// A mnemonic, with its words separated by single spaces, or by single ideographic spaces if Japanese
+    Mnemonic,
+    /// A case-sensitive string, such as a WIF, an extended key, or an address
+    Text,
+}
+
+/// Sets whether key material in a non-canonical form is rejected, instead of normalized.
+pub fn set_strict(strict: bool) {
+    STRICT.store(strict, Ordering::Relaxed);
+}
+
+/// Returns the canonical form of the given input of the given kind, and what was normalized.
+pub fn normalize(input: &str, kind: InputKind) -> (String, Vec<&'static str>) {
+    let mut normalizations = Vec::new();
+
+    let mut canonical = input.trim();
+    if canonical.len() != input.len() {
+        normalizations.push("surrounding whitespace");
+    }
+
+    match kind {
+        InputKind::Entropy | InputKind::Hex => {
+            if canonical.starts_with("0x") || canonical.starts_with("0X") {
+                canonical = &canonical[2..];
+                normalizations.push("0x prefix");
+            }
+            if canonical.chars().any(|c| c.is_ascii_uppercase()) {
+                normalizations.push("uppercase hex");
+            }
+            (canonical.to_ascii_lowercase(), normalizations)
+        }
+        InputKind::Mnemonic => {
+            // The words of a Japanese mnemonic are separated by ideographic spaces, as in BIP-39
+            let separator = match canonical.contains(IDEOGRAPHIC_SPACE) {
+                true => IDEOGRAPHIC_SPACE.to_string(),
+                false => " ".to_string(),
+            };
+            let words = canonical.split_whitespace().collect::<Vec<&str>>().join(&separator);
+            if words != canonical {
+                normalizations.push("irregular spacing");
+            }
+            (words, normalizations)
+        }
+        InputKind::Text => (canonical.to_string(), normalizations),
+    }
+}
+
+/// Returns the canonical form of the given input of key material, printing what was normalized,
+/// or returning an error with `--strict` if the input is not canonical, or without `--force` if
+/// the entropy or mnemonic has patterns of low entropy.
+pub fn sanitize(field: &str, input: &str, kind: InputKind) -> Result<String, CLIError> {
+    let (canonical, normalizations) = normalize(input, kind);
+    match (normalizations.is_empty(), STRICT.load(Ordering::Relaxed)) {
+        (true, _) => {}
+        (false, true) => return Err(CLIError::NonCanonicalInput(field.into(), normalizations.join(", "))),
+        (false, false) => eprintln!(
+            "{}",
+            localize(&format!("Normalized {} ({})", field, normalizations.join(", ")))
+        ),
+    };
+    match kind {
+        InputKind::Entropy => check_entropy(field, &analyze_entropy(&hex::decode(&canonical).unwrap_or_default()))?,
+        InputKind::Mnemonic => check_entropy(field, &analyze_mnemonic(&canonical))?,
+        InputKind::Hex | InputKind::Text => {}
+    };
+    Ok(canonical)
+}
+
+/// Sanitizes the given optional input of key material in place.
+pub fn sanitize_option(field: &str, input: &mut Option<String>, kind: InputKind) -> Result<(), CLIError> {
+    if let Some(value) = input {
+        *value = sanitize(field, value, kind)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalize_inputs() {
+        let inputs = [
+            ("0123abcd", InputKind::Hex, "0123abcd", vec![]),
+            (
+                " 0x0123ABCD\n",
+                InputKind::Hex,
+                "0123abcd",
+                vec!["surrounding whitespace", "0x prefix", "uppercase hex"],
+            ),
+            ("0X0123abcd", InputKind::Entropy, "0123abcd", vec!["0x prefix"]),
+            (
+                "abandon  ability\table",
+                InputKind::Mnemonic,
+                "abandon ability able",
+                vec!["irregular spacing"],
+            ),
+            (
+                "あいこくしん\u{3000}\u{3000}あいこくしん",
+                InputKind::Mnemonic,
+                "あいこくしん\u{3000}あいこくしん",
+                vec!["irregular spacing"],
+            ),
+            (
+                " KwDiBf89QgGbjEhKnhXJuH7LrciVrZi3qYjgd9M7rFU73sVHnoWn ",
+                InputKind::Text,
+                "KwDiBf89QgGbjEhKnhXJuH7LrciVrZi3qYjgd9M7rFU73sVHnoWn",
+                vec!["surrounding whitespace"],
+            ),
+        ];
+        for (input, kind, canonical, normalizations) in inputs.iter() {
+            assert_eq!((canonical.to_string(), normalizations.clone()), normalize(input, *kind));
+        }
+    }
+
+    #[test]
+    fn strict() {
+        // The strict mode is global, so it is only set by this test
+        set_strict(true);
+        let result = sanitize("private key", "0xABCD", InputKind::Hex);
+        let canonical = sanitize("private key", "abcd", InputKind::Hex);
+        set_strict(false);
+
+        match result {
+            Err(CLIError::NonCanonicalInput(field, normalizations)) => {
+                assert_eq!("private key", field);
+                assert_eq!("0x prefix, uppercase hex", normalizations);
+            }
+            result => panic!("unexpected result {:?}", result),
+        }
+        assert_eq!("abcd", canonical.unwrap());
+        assert_eq!("abcd", sanitize("private key", "0xABCD", InputKind::Hex).unwrap());
+    }
+
+    #[test]
+    fn reject_low_entropy() {
+        match sanitize("entropy", &"00".repeat(16), InputKind::Entropy) {
+            Err(CLIError::LowEntropyInput(field, _)) => assert_eq!("entropy", field),
+            result => panic!("unexpected result {:?}", result),
+        }
+        let entropy = "9e885d952ad362caeb4efe34a8e91bd2";
+        assert_eq!(entropy, sanitize("entropy", entropy, InputKind::Entropy).unwrap());
+    }
+}
@@ -0,0 +1,80 @@
+//! # Schema
+//!
+//! JSON Schemas (draft-07) of the JSON output of each command, so that downstream tooling
+//! can validate and generate code against it. The schemas are written alongside each
+//! serialized structure, and are printed with `wagyu schema <output>`.
+
+use crate::cli::bitcoin::BitcoinWallet;
+use crate::cli::ethereum::EthereumWallet;
+use crate::cli::monero::MoneroWallet;
+use crate::cli::nostr::NostrWallet;
+use crate::cli::zcash::ZcashWallet;
+use crate::cli::{CLIError, CeremonySummary, Manifest, UnsignedTransactionFile, WalletFile};
+
+use serde_json::{json, Map, Value};
+
+use crate::model::no_std::{String, ToString, Vec};
+
+/// The JSON Schema dialect of the schemas
+pub const SCHEMA_DIALECT: &str = "http://json-schema.org/draft-07/schema#";
+
+/// The interface for a serialized output with a JSON Schema.
+pub trait Schema {
+    /// Returns the JSON Schema of the serialized output.
+    fn schema() -> Value;
+}
+
+/// Returns the JSON Schema of the given output.
+pub fn schema(output: &str) -> Result<Value, CLIError> {
+    let (title, schema) = match output {
+        "bitcoin" => ("Bitcoin wallets", wallets(BitcoinWallet::schema())),
+        "ceremony-summary" => ("Ceremony summary", CeremonySummary::schema()),
+        "ethereum" => ("Ethereum wallets", wallets(EthereumWallet::schema())),
+        "manifest" => ("Batch manifest", Manifest::schema()),
+        "monero" => ("Monero wallets", wallets(MoneroWallet::schema())),
+        "nostr" => ("Nostr keys", wallets(NostrWallet::schema())),
+        "unsigned-transaction" => ("Unsigned transaction file", UnsignedTransactionFile::<Value>::schema()),
+        "wallet-file" => ("Wallet file", WalletFile::schema()),
+        "zcash" => ("Zcash wallets", wallets(ZcashWallet::schema())),
+        _ => return Err(CLIError::UnknownSchema(output.into())),
+    };
+
+    let mut root = Map::new();
+    root.insert("$schema".into(), SCHEMA_DIALECT.into());
+    root.insert("title".into(), title.into());
+    if let Value::Object(schema) = schema {
+        root.extend(schema);
+    }
+    Ok(Value::Object(root))
+}
+
+/// Returns the schema of a JSON array of the given wallet schema, as printed with `--json`.
+fn wallets(wallet: Value) -> Value {
+    json!({ "type": "array", "items": wallet })
+}
+
+/// Returns the schema of a property of the given type and description,
+/// where a type of `string|null` is either a string or null.
+pub fn property(kind: &str, description: &str) -> Value {
+    let kinds: Vec<&str> = kind.split('|').collect();
+    match kinds.len() {
+        1 => json!({ "type": kind, "description": description }),
+        _ => json!({ "type": kinds, "description": description }),
+    }
+}
+
+/// Returns the schema of a JSON object of the given named properties,
+/// requiring the given property names and rejecting unknown properties.
+pub fn object(properties: Vec<(&str, Value)>, required: &[&str]) -> Value {
+    let properties: Map<String, Value> = properties
+        .into_iter()
+        .map(|(name, property)| (name.to_string(), property))
+        .collect();
+
+    json!({
+        "type": "object",
+        "properties": properties,
+        "required": required,
+        "additionalProperties": false,
+    })
+}
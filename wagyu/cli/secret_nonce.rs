@@ -0,0 +1,160 @@
+//! # Secret Nonce
+//!
+//! A versioned file of the secret nonce of a signer of a two-round Schnorr signature (such as MuSig2),
+//! so that the secret nonce is never printed or passed as an argument. The first round creates the file,
+//! which must not exist, and the second round consumes it, marking it used and erasing its secret nonce
+//! before signing, so that a secret nonce signs at most once.
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "scheme": "musig2",
+//!   "message": "...",
+//!   "public_nonce": "...",
+//!   "secret_nonce": "...",
+//!   "used": false
+//! }
+//! ```
+
+use crate::cli::CLIError;
+
+use serde::{Deserialize, Serialize};
+use std::{fs::OpenOptions, io::Write};
+
+use crate::model::no_std::{format, String, ToString, Vec};
+
+/// The current version of the secret nonce file schema
+pub const SECRET_NONCE_FILE_VERSION: u32 = 1;
+
+/// Represents a secret nonce file of a signer of a signing session
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SecretNonceFile {
+    /// The version of the file schema
+    pub version: u32,
+    /// The signature scheme of the nonce
+    pub scheme: String,
+    /// The message to sign in hex
+    pub message: String,
+    /// The public nonce of the signer in hex
+    pub public_nonce: String,
+    /// The secret nonce of the signer in hex, erased once used
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub secret_nonce: Option<String>,
+    /// Whether the secret nonce is used
+    pub used: bool,
+}
+
+impl SecretNonceFile {
+    /// Returns a new secret nonce file of the given scheme, message, and nonces.
+    pub fn new(scheme: &str, message: &[u8], public_nonce: &[u8], secret_nonce: &[u8]) -> Self {
+        Self {
+            version: SECRET_NONCE_FILE_VERSION,
+            scheme: scheme.to_string(),
+            message: hex::encode(message),
+            public_nonce: hex::encode(public_nonce),
+            secret_nonce: Some(hex::encode(secret_nonce)),
+            used: false,
+        }
+    }
+
+    /// Writes the secret nonce file to the given path, which must not exist, readable only by its owner.
+    pub fn create(&self, path: &str) -> Result<(), CLIError> {
+        let mut options = OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        std::os::unix::fs::OpenOptionsExt::mode(&mut options, 0o600);
+
+        let mut file = options.open(path).map_err(|error| match error.kind() {
+            std::io::ErrorKind::AlreadyExists => CLIError::ExistingSecretNonceFile(path.to_string()),
+            _ => error.into(),
+        })?;
+        Ok(file.write_all(serde_json::to_string_pretty(self)?.as_bytes())?)
+    }
+
+    /// Returns the public nonce and secret nonce of the secret nonce file at the given path, of the given scheme
+    /// and message, after marking the file used and erasing its secret nonce. Returns an error if the secret
+    /// nonce is already used.
+    pub fn consume(path: &str, scheme: &str, message: &[u8]) -> Result<(Vec<u8>, Vec<u8>), CLIError> {
+        let mut file: Self = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+        if file.version != SECRET_NONCE_FILE_VERSION {
+            return Err(CLIError::UnsupportedFileVersion(file.version));
+        }
+        if file.scheme != scheme {
+            let reason = format!("expected a {} nonce, found a {} nonce", scheme, file.scheme);
+            return Err(CLIError::InvalidSecretNonceFile(path.to_string(), reason));
+        }
+        let secret_nonce = match (file.used, file.secret_nonce.take()) {
+            (false, Some(secret_nonce)) => secret_nonce,
+            _ => return Err(CLIError::ReusedSecretNonce(path.to_string())),
+        };
+        if file.message != hex::encode(message) {
+            let reason = "the nonce is of another message".to_string();
+            return Err(CLIError::InvalidSecretNonceFile(path.to_string(), reason));
+        }
+
+        // The file is marked used before the secret nonce signs, so that a failed write never signs
+        file.used = true;
+        std::fs::write(path, serde_json::to_string_pretty(&file)?)?;
+        Ok((hex::decode(&file.public_nonce)?, hex::decode(&secret_nonce)?))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn path(name: &str) -> String {
+        std::env::temp_dir()
+            .join(format!("wagyu_secret_nonce_{}_{}.json", name, std::process::id()))
+            .to_string_lossy()
+            .to_string()
+    }
+
+    #[test]
+    fn consume_once() {
+        let path = path("consume_once");
+        let _ = std::fs::remove_file(&path);
+        SecretNonceFile::new("musig2", &[1, 2], &[3; 66], &[4; 97])
+            .create(&path)
+            .unwrap();
+
+        let (public_nonce, secret_nonce) = SecretNonceFile::consume(&path, "musig2", &[1, 2]).unwrap();
+        assert_eq!(vec![3; 66], public_nonce);
+        assert_eq!(vec![4; 97], secret_nonce);
+
+        // The secret nonce is erased from the file, and is never returned again
+        assert!(!std::fs::read_to_string(&path).unwrap().contains(&hex::encode([4; 97])));
+        match SecretNonceFile::consume(&path, "musig2", &[1, 2]) {
+            Err(CLIError::ReusedSecretNonce(_)) => {}
+            result => panic!("unexpected result {:?}", result),
+        }
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn reject_existing_file() {
+        let path = path("reject_existing_file");
+        std::fs::write(&path, "").unwrap();
+        match SecretNonceFile::new("musig2", &[1], &[3; 66], &[4; 97]).create(&path) {
+            Err(CLIError::ExistingSecretNonceFile(_)) => {}
+            result => panic!("unexpected result {:?}", result),
+        }
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn reject_mismatched_session() {
+        let path = path("reject_mismatched_session");
+        let _ = std::fs::remove_file(&path);
+        SecretNonceFile::new("musig2", &[1], &[3; 66], &[4; 97])
+            .create(&path)
+            .unwrap();
+
+        assert!(SecretNonceFile::consume(&path, "frost", &[1]).is_err());
+        assert!(SecretNonceFile::consume(&path, "musig2", &[2]).is_err());
+
+        // A mismatched session leaves the secret nonce unused
+        assert!(SecretNonceFile::consume(&path, "musig2", &[1]).is_ok());
+        std::fs::remove_file(&path).unwrap();
+    }
+}
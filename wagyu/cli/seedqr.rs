@@ -0,0 +1,191 @@
+//! # SeedQR
+//!
+//! Exports and imports the SeedQR and CompactSeedQR encodings of BIP-39 mnemonics, as scanned by
+//! SeedSigner and other airgapped signers. A SeedQR is the numeric QR code of the four-digit index
+//! of each word of a mnemonic, and a CompactSeedQR is the byte QR code of the entropy of a mnemonic,
+//! both at the low error correction level, for mnemonics of 12 or 24 English words.
+
+use crate::bitcoin::{wordlist::English, BitcoinMnemonic, BitcoinWordlist, Mainnet};
+use crate::cli::{CLIError, MaskSecrets, QrCode, QrLevel, QrMode, SECRET_MASK};
+use crate::model::{Mnemonic, MnemonicError};
+
+use colored::*;
+use core::{fmt, fmt::Display};
+use serde::Serialize;
+
+use crate::model::no_std::{format, String, ToString, Vec};
+
+/// The word counts of the mnemonics of a SeedQR
+pub const SEEDQR_WORD_COUNTS: [usize; 2] = [12, 24];
+
+/// Represents the SeedQR of a mnemonic
+#[derive(Serialize, Clone, Debug)]
+pub struct SeedQr {
+    /// The format of the SeedQR, `standard` or `compact`
+    pub format: String,
+    /// The mnemonic of the SeedQR
+    pub mnemonic: String,
+    /// The payload of the QR code, the digits of a SeedQR or the entropy of a CompactSeedQR in hex
+    pub payload: String,
+}
+
+impl SeedQr {
+    /// Returns the SeedQR of the given mnemonic, or its CompactSeedQR if `compact` is set.
+    pub fn from_mnemonic(mnemonic: &str, compact: bool) -> Result<Self, CLIError> {
+        let mnemonic = BitcoinMnemonic::<Mainnet, English>::from_phrase(mnemonic)?;
+        let phrase = mnemonic.to_phrase()?;
+        let words = phrase.split(' ').collect::<Vec<&str>>();
+        if !SEEDQR_WORD_COUNTS.contains(&words.len()) {
+            return Err(CLIError::UnsupportedSeedQr(words.len()));
+        }
+
+        let (format, payload) = match compact {
+            true => ("compact", hex::encode(mnemonic.to_entropy())),
+            false => (
+                "standard",
+                words
+                    .iter()
+                    .map(|word| Ok(format!("{:04}", English::get_index(word).map_err(MnemonicError::from)?)))
+                    .collect::<Result<String, CLIError>>()?,
+            ),
+        };
+        Ok(Self {
+            format: format.into(),
+            mnemonic: phrase,
+            payload,
+        })
+    }
+
+    /// Returns the SeedQR of the given scanned payload, the digits of a SeedQR
+    /// or the entropy of a CompactSeedQR in hex.
+    pub fn from_payload(payload: &str) -> Result<Self, CLIError> {
+        let payload = payload.trim().trim_start_matches("0x");
+        let invalid = || CLIError::InvalidSeedQr(payload.into());
+
+        let (format, mnemonic) = match payload.len() {
+            // The digits of 12 or 24 words, which are not the lengths of the hex entropy of 12 or 24 words
+            48 | 96 if payload.bytes().all(|byte| byte.is_ascii_digit()) => {
+                let words = payload
+                    .as_bytes()
+                    .chunks(4)
+                    .map(|digits| {
+                        let index = String::from_utf8_lossy(digits).parse().map_err(|_| invalid())?;
+                        English::get(index).map_err(|_| invalid())
+                    })
+                    .collect::<Result<Vec<String>, CLIError>>()?;
+                (
+                    "standard",
+                    BitcoinMnemonic::<Mainnet, English>::from_phrase(&words.join(" "))?,
+                )
+            }
+            32 | 64 => {
+                let entropy = hex::decode(payload).map_err(|_| invalid())?;
+                ("compact", BitcoinMnemonic::<Mainnet, English>::from_entropy(&entropy)?)
+            }
+            _ => return Err(invalid()),
+        };
+        Ok(Self {
+            format: format.into(),
+            mnemonic: mnemonic.to_phrase()?,
+            payload: payload.to_lowercase(),
+        })
+    }
+
+    /// Returns the QR code of the SeedQR, in numeric mode for a SeedQR and in byte mode for a CompactSeedQR.
+    pub fn to_qr_code(&self) -> Result<QrCode, CLIError> {
+        match self.format.as_str() {
+            "compact" => QrCode::encode(&hex::decode(&self.payload)?, QrMode::Byte, QrLevel::Low),
+            _ => QrCode::encode(self.payload.as_bytes(), QrMode::Numeric, QrLevel::Low),
+        }
+    }
+}
+
+impl MaskSecrets for SeedQr {
+    fn mask_secrets(&mut self) {
+        self.mnemonic = SECRET_MASK.into();
+        self.payload = SECRET_MASK.into();
+    }
+}
+
+#[cfg_attr(tarpaulin, skip)]
+impl Display for SeedQr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let output = [
+            format!("      {}               {}\n", "Format".cyan().bold(), self.format),
+            format!("      {}             {}\n", "Mnemonic".cyan().bold(), self.mnemonic),
+            format!("      {}              {}\n", "Payload".cyan().bold(), self.payload),
+            // The QR code is printed unindented below the payload, to be scanned by a signer
+            match self.to_qr_code() {
+                Ok(qr_code) if self.payload != SECRET_MASK => qr_code.to_string(),
+                _ => "".to_string(),
+            },
+        ]
+        .concat();
+
+        // Removes final new line character
+        let output = output[..output.len() - 1].to_string();
+        write!(f, "\n{}", output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The mnemonics of the SeedQR examples of SeedSigner, with their digit streams, their compact entropy,
+    /// and the versions of their SeedQR and CompactSeedQR (25x25 and 21x21, and 29x29 and 25x25)
+    const EXAMPLES: [(&str, &str, &str, usize, usize); 2] = [
+        (
+            "forum undo fragile fade shy sign arrest garment culture tube off merit",
+            "073318950739065415961602009907670428187212261116",
+            "5bbd9d71a8ec7990831aff359d426545",
+            2,
+            1,
+        ),
+        (
+            "attack pizza motion avocado network gather crop fresh patrol unusual wild holiday candy pony ranch winter \
+             theme error hybrid van cereal salon goddess expire",
+            "011513251154012711900771041507421289190620080870026613431420201617920614089619290300152408010643",
+            "0e74b64107f94cc0ccfae6a13dcbec3662154fec67e0e00999c07892597d190a",
+            3,
+            2,
+        ),
+    ];
+
+    #[test]
+    fn from_mnemonic() {
+        for (mnemonic, digits, entropy, version, compact_version) in EXAMPLES.iter() {
+            let seed_qr = SeedQr::from_mnemonic(mnemonic, false).unwrap();
+            assert_eq!(*digits, seed_qr.payload);
+            assert_eq!(*version, seed_qr.to_qr_code().unwrap().version);
+
+            let compact_seed_qr = SeedQr::from_mnemonic(mnemonic, true).unwrap();
+            assert_eq!(*entropy, compact_seed_qr.payload);
+            assert_eq!(*compact_version, compact_seed_qr.to_qr_code().unwrap().version);
+        }
+    }
+
+    #[test]
+    fn from_payload() {
+        for (mnemonic, digits, entropy, _, _) in EXAMPLES.iter() {
+            let seed_qr = SeedQr::from_payload(digits).unwrap();
+            assert_eq!(
+                ("standard", *mnemonic),
+                (seed_qr.format.as_str(), seed_qr.mnemonic.as_str())
+            );
+
+            let compact_seed_qr = SeedQr::from_payload(entropy).unwrap();
+            assert_eq!(
+                ("compact", *mnemonic),
+                (compact_seed_qr.format.as_str(), compact_seed_qr.mnemonic.as_str())
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_payload() {
+        // A word index beyond the wordlist, and the digits of a word count other than 12 or 24
+        assert!(SeedQr::from_payload(&format!("2048{}", &EXAMPLES[0].1[4..])).is_err());
+        assert!(SeedQr::from_payload(&EXAMPLES[0].1[..44]).is_err());
+    }
+}
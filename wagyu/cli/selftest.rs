@@ -0,0 +1,250 @@
+//! # Self-test
+//!
+//! Embedded test vectors of BIP-32, BIP-39, BIP-44 (and BIP-49, BIP-84, and BIP-86), EIP-55, Bech32,
+//! and the keys of each coin, run against the binary itself, so that a user can verify that a downloaded
+//! binary derives keys and addresses correctly before trusting it with funds.
+
+use crate::bitcoin::{
+    wordlist::English as BitcoinEnglish, BitcoinDerivationPath, BitcoinExtendedPrivateKey, BitcoinFormat,
+    BitcoinMnemonic, BitcoinPublicKey, Mainnet as BitcoinMainnet,
+};
+use crate::cli::bitcoin::BitcoinWallet;
+use crate::cli::ethereum::EthereumWallet;
+use crate::cli::{CLIError, MaskSecrets};
+use crate::ethereum::{
+    wordlist::English as EthereumEnglish, EthereumAddress, EthereumFormat, EthereumPrivateKey,
+    Mainnet as EthereumMainnet,
+};
+use crate::model::{ExtendedPrivateKey, ExtendedPublicKey, Mnemonic, MnemonicExtended, PrivateKey, PublicKey};
+use crate::monero::{Mainnet as MoneroMainnet, MoneroFormat, MoneroPrivateKey};
+use crate::zcash::{Mainnet as ZcashMainnet, ZcashAddress, ZcashFormat, ZcashPrivateKey};
+
+use colored::*;
+use core::{fmt, fmt::Display, str::FromStr};
+use serde::Serialize;
+
+use crate::model::no_std::{format, String, ToString, Vec};
+
+/// The mnemonic of the BIP-39, BIP-44, BIP-49, BIP-84, and BIP-86 test vectors
+const MNEMONIC: &str = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about";
+
+/// The seed of BIP-32 test vector 1
+const BIP32_SEED: &str = "000102030405060708090a0b0c0d0e0f";
+
+/// A test vector, by its standard, its description, its expected output, and the derivation of its output
+type TestVector = (
+    &'static str,
+    &'static str,
+    &'static str,
+    fn() -> Result<String, CLIError>,
+);
+
+/// The embedded test vectors, in the order they are run
+const TEST_VECTORS: &[TestVector] = &[
+    ("BIP-39", "Entropy of a mnemonic", "00000000000000000000000000000000", || {
+        Ok(hex::encode(BitcoinMnemonic::<BitcoinMainnet, BitcoinEnglish>::from_phrase(MNEMONIC)?.to_entropy()))
+    }),
+    (
+        "BIP-39",
+        "Seed of a mnemonic with a password",
+        "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04",
+        || {
+            let mnemonic = BitcoinMnemonic::<BitcoinMainnet, BitcoinEnglish>::from_phrase(MNEMONIC)?;
+            Ok(hex::encode(mnemonic.to_seed(Some("TREZOR"))?))
+        },
+    ),
+    (
+        "BIP-32",
+        "Master key of test vector 1",
+        "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi",
+        || bip32_extended_private_key("m"),
+    ),
+    (
+        "BIP-32",
+        "Chain m/0'/1/2'/2/1000000000 of test vector 1",
+        "xprvA41z7zogVVwxVSgdKUHDy1SKmdb533PjDz7J6N6mV6uS3ze1ai8FHa8kmHScGpWmj4WggLyQjgPie1rFSruoUihUZREPSL39UNdE3BBDu76",
+        || bip32_extended_private_key("m/0'/1/2'/2/1000000000"),
+    ),
+    (
+        "BIP-44",
+        "Bitcoin address at m/44'/0'/0'/0/0",
+        "1LqBGSKuX5yYUonjxT5qGfpUsXKYYWeabA",
+        || {
+            let wallet = BitcoinWallet::from_mnemonic::<BitcoinMainnet, BitcoinEnglish>(MNEMONIC, &None, "m/44'/0'/0'/0/0")?;
+            Ok(wallet.address.unwrap_or_default())
+        },
+    ),
+    (
+        "BIP-44",
+        "Ethereum address at m/44'/60'/0'/0/0",
+        "0x9858EfFD232B4033E47d90003D41EC34EcaEda94",
+        || {
+            let wallet =
+                EthereumWallet::from_mnemonic::<EthereumMainnet, EthereumEnglish>(MNEMONIC, None, "m/44'/60'/0'/0/0")?;
+            Ok(wallet.address.unwrap_or_default())
+        },
+    ),
+    ("BIP-49", "Bitcoin address at m/49'/0'/0'/0/0", "37VucYSaXLCAsxYyAPfbSi9eh4iEcbShgf", || {
+        bip44_address("m/49'/0'/0'/0/0", &BitcoinFormat::P2SH_P2WPKH)
+    }),
+    ("BIP-84", "Bitcoin address at m/84'/0'/0'/0/0", "bc1qcr8te4kr609gcawutmrza0j4xv80jy8z306fyu", || {
+        bip44_address("m/84'/0'/0'/0/0", &BitcoinFormat::Bech32)
+    }),
+    (
+        "BIP-86",
+        "Bitcoin address at m/86'/0'/0'/0/0",
+        "bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr",
+        || bip44_address("m/86'/0'/0'/0/0", &BitcoinFormat::P2TR),
+    ),
+    ("EIP-55", "Checksum of an address", "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed", || {
+        Ok(EthereumAddress::from_str("0x5aaeb6053f3e94c9b9a09f33669435e7ef1beaed")?.to_string())
+    }),
+    ("EIP-55", "Checksum of an address", "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359", || {
+        Ok(EthereumAddress::from_str("0xfb6916095ca1df60bb79ce92ce3ea74c37c5d359")?.to_string())
+    }),
+    ("Bech32", "Address of the generator point", "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4", || {
+        generator_address(&BitcoinFormat::Bech32)
+    }),
+    ("Bitcoin", "Address of the generator point", "1BgGZ9tcN4rm9KBzDn7KprQz87SZ26SAMH", || {
+        generator_address(&BitcoinFormat::P2PKH)
+    }),
+    ("Ethereum", "Address of a private key", "0x9141B7539E7902872095C408BfA294435e2b8c8a", || {
+        let private_key = EthereumPrivateKey::from_str("f89f23eaeac18252fedf81bb8318d3c111d48c19b0680dcf6e0a8d5136caf287")?;
+        Ok(private_key.to_address(&EthereumFormat::Standard)?.to_string())
+    }),
+    (
+        "Monero",
+        "Address of a seed",
+        "42yuCfeWRoe4aRLYS82WNXfgY1eK8XH2V4hgwPjyuAEE56M4tbxqyLATxSrKPtxxEQETnhmFxW741RMYTaM9neiWCK2uvkW",
+        || {
+            let seed = "f6aceb9caa1d04bb3a6a3d5614a731dd58d24da957f33448fa50600c3d928404";
+            let private_key = MoneroPrivateKey::<MoneroMainnet>::from_seed(seed, &MoneroFormat::Standard)?;
+            Ok(private_key.to_address(&MoneroFormat::Standard)?.to_string())
+        },
+    ),
+    ("Zcash", "Transparent address of a private key", "t1MoMR1XdnPqLBWf5XkchWTkGNrveYLCaiM", || {
+        let private_key = ZcashPrivateKey::<ZcashMainnet>::from_str("KxYzZuBPkE3rnEEGCdsB6dCzxN1D4xoY5ogKoxbdUdkxbRzvgbij")?;
+        Ok(private_key.to_address(&ZcashFormat::P2PKH)?.to_string())
+    }),
+    (
+        "Zcash",
+        "Sapling address of a spending key",
+        "zs1dq9dlh6u6hna0u96aqtynxt3acddtgkgdx4re65500nmc2aze0my65ky36vaqvj4hkc9ut66eyf",
+        || {
+            let address = "zs1dq9dlh6u6hna0u96aqtynxt3acddtgkgdx4re65500nmc2aze0my65ky36vaqvj4hkc9ut66eyf";
+            let private_key = ZcashPrivateKey::<ZcashMainnet>::from_str(
+                "secret-spending-key-main1hd5umd08pc4m6f8hw8x3tgv26kxn4w0p4g72kxrtndjazlm64hhsnczrtx",
+            )?;
+            let diversifier = ZcashAddress::<ZcashMainnet>::get_diversifier(address)?;
+            Ok(private_key.to_address(&ZcashFormat::Sapling(Some(diversifier)))?.to_string())
+        },
+    ),
+];
+
+/// Returns the extended private key of BIP-32 test vector 1 at the given path.
+fn bip32_extended_private_key(path: &str) -> Result<String, CLIError> {
+    let master_extended_private_key =
+        BitcoinExtendedPrivateKey::<BitcoinMainnet>::new_master(&hex::decode(BIP32_SEED)?, &BitcoinFormat::P2PKH)?;
+    let extended_private_key = master_extended_private_key.derive(&BitcoinDerivationPath::from_str(path)?)?;
+    Ok(extended_private_key.to_string())
+}
+
+/// Returns the Bitcoin address of the test mnemonic at the given path, in the given format.
+fn bip44_address(path: &str, format: &BitcoinFormat) -> Result<String, CLIError> {
+    let mnemonic = BitcoinMnemonic::<BitcoinMainnet, BitcoinEnglish>::from_phrase(MNEMONIC)?;
+    let extended_private_key = mnemonic
+        .to_extended_private_key(None)?
+        .derive(&BitcoinDerivationPath::from_str(path)?)?;
+    let public_key = extended_private_key.to_extended_public_key().to_public_key();
+    Ok(public_key.to_address(format)?.to_string())
+}
+
+/// Returns the Bitcoin address of the generator point of secp256k1 (the public key of the private key 1),
+/// in the given format.
+fn generator_address(format: &BitcoinFormat) -> Result<String, CLIError> {
+    let public_key = BitcoinPublicKey::<BitcoinMainnet>::from_str(
+        "0279be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+    )?;
+    Ok(public_key.to_address(format)?.to_string())
+}
+
+/// Represents the result of a test vector
+#[derive(Serialize, Clone, Debug)]
+pub struct SelfTestResult {
+    pub standard: String,
+    pub description: String,
+    pub passed: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// Represents the results of the embedded test vectors
+#[derive(Serialize, Clone, Debug)]
+pub struct SelfTest {
+    pub results: Vec<SelfTestResult>,
+    pub passed: usize,
+    pub failed: usize,
+}
+
+impl SelfTest {
+    /// Returns the results of running each of the embedded test vectors.
+    pub fn run() -> Self {
+        let results = TEST_VECTORS
+            .iter()
+            .map(|(standard, description, expected, derive)| {
+                let error = match derive() {
+                    Ok(found) if found == *expected => None,
+                    Ok(found) => Some(format!("expected {}, found {}", expected, found)),
+                    Err(error) => Some(error.to_string()),
+                };
+                SelfTestResult {
+                    standard: standard.to_string(),
+                    description: description.to_string(),
+                    passed: error.is_none(),
+                    error,
+                }
+            })
+            .collect::<Vec<SelfTestResult>>();
+        let passed = results.iter().filter(|result| result.passed).count();
+        let failed = results.len() - passed;
+        Self {
+            results,
+            passed,
+            failed,
+        }
+    }
+}
+
+impl MaskSecrets for SelfTest {
+    fn mask_secrets(&mut self) {}
+}
+
+#[cfg_attr(tarpaulin, skip)]
+impl Display for SelfTest {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f)?;
+        for result in &self.results {
+            let standard = format!("{:<11}", result.standard);
+            let status = match result.passed {
+                true => "pass".green().bold(),
+                false => "FAIL".red().bold(),
+            };
+            writeln!(
+                f,
+                "      {}{:<46} {}",
+                standard.cyan().bold(),
+                result.description,
+                status
+            )?;
+            if let Some(error) = &result.error {
+                writeln!(f, "      {:<11}{}", "", error)?;
+            }
+        }
+        write!(
+            f,
+            "\n      {} of {} test vectors passed",
+            self.passed,
+            self.passed + self.failed
+        )
+    }
+}
@@ -0,0 +1,665 @@
+//! # Server
+//!
+//! A local HTTP API for payment backends to derive addresses from extended public keys
+//! and to sign transactions, without shelling out to the command-line tool.
+//!
+//! Requests and responses are JSON. If the server is started with a token, every request
+//! other than `GET /health` must carry an `Authorization: Bearer <token>` header.
+//! If the server is started with a wallet file, its private keys sign transactions
+//! for the addresses of the wallet file, so requests need not carry private keys.
+//! A server with a wallet file, or on an address other than a loopback address, requires a token.
+//!
+//! Requests with an `Origin` header, which browsers send with cross-site requests, are rejected,
+//! as are `POST` requests with a body other than `application/json`.
+//!
+//! | Endpoint                | Body                                                          |
+//! |-------------------------|---------------------------------------------------------------|
+//! | `GET /health`           |                                                               |
+//! | `POST /bitcoin/derive`  | `{ "extended_public_key": "xpub...", "path": "m/0/0" }`       |
+//! | `POST /ethereum/derive` | `{ "extended_public_key": "xpub...", "path": "m/0/0" }`       |
+//! | `POST /bitcoin/sign`    | `{ "transaction_hex": "...", "inputs": [...], "network": "mainnet" }` |
+//! | `POST /ethereum/sign`   | `{ "transaction_hex": "0x...", "address": "0x...", "network": "mainnet" }` |
+//! | `POST /bitcoin/verify`  | `{ "address": "1...", "message": "...", "signature": "..." }` |
+//! | `POST /ethereum/verify` | `{ "message": "...", "signature": "0x..." }`                  |
+//!
+//! The API is served over plain HTTP. It has no gRPC service and no TLS, as neither `tonic` nor `rustls`
+//! is a dependency of wagyu, so a server on another host should be reached through a TLS-terminating proxy.
+
+use crate::bitcoin::{BitcoinDerivationPath, Mainnet as BitcoinMainnet, Testnet as BitcoinTestnet};
+use crate::cli::bitcoin::{BitcoinInput, BitcoinWallet};
+use crate::cli::ethereum::EthereumWallet;
+use crate::cli::{AuditLog, CLIError, WalletFile};
+use crate::ethereum::{Goerli, Kovan, Mainnet as EthereumMainnet, Rinkeby, Ropsten};
+use crate::model::{crypto::constant_time_eq, DerivationPath, Network};
+
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::{
+    collections::BTreeMap,
+    io::{BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    str::FromStr,
+    time::Duration,
+};
+
+use crate::model::no_std::{format, vec, String, ToString, Vec};
+
+/// The environment variable to read the authentication token from
+pub const SERVER_TOKEN: &str = "WAGYU_TOKEN";
+
+/// The endpoints of the API, other than `GET /health`
+const ENDPOINTS: [&str; 6] = [
+    "/bitcoin/derive",
+    "/bitcoin/sign",
+    "/bitcoin/verify",
+    "/ethereum/derive",
+    "/ethereum/sign",
+    "/ethereum/verify",
+];
+
+/// The maximum size of a request body
+const MAX_BODY_SIZE: usize = 1024 * 1024;
+
+/// The maximum size of the request line or of a header line
+const MAX_LINE_SIZE: u64 = 8 * 1024;
+
+/// The maximum number of headers of a request
+const MAX_HEADERS: usize = 64;
+
+/// The read timeout of a connection
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Represents an HTTP request
+#[derive(Debug, Clone)]
+struct Request {
+    method: String,
+    path: String,
+    headers: BTreeMap<String, String>,
+    body: Vec<u8>,
+}
+
+/// Represents a request to derive a wallet from an extended public key
+#[derive(Debug, Deserialize)]
+struct DeriveRequest {
+    extended_public_key: String,
+    path: Option<String>,
+}
+
+/// Represents a request to sign a Bitcoin transaction
+#[derive(Debug, Deserialize)]
+struct BitcoinSignRequest {
+    transaction_hex: String,
+    inputs: Vec<BitcoinInput>,
+    network: Option<String>,
+}
+
+/// Represents a request to sign an Ethereum transaction
+#[derive(Debug, Deserialize)]
+struct EthereumSignRequest {
+    transaction_hex: String,
+    private_key: Option<String>,
+    address: Option<String>,
+    network: Option<String>,
+}
+
+/// Represents a request to verify a message signature
+#[derive(Debug, Deserialize)]
+struct VerifyRequest {
+    address: Option<String>,
+    message: Option<String>,
+    message_hash: Option<String>,
+    signature: String,
+}
+
+/// Represents a local HTTP server
+pub struct Server {
+    listener: TcpListener,
+    token: Option<String>,
+    keys: BTreeMap<String, String>,
+    audit_log: Option<AuditLog>,
+}
+
+impl Server {
+    /// Returns a server listening on the given address, authenticating requests with
+    /// the given token (if any), signing with the private keys of the given wallet file (if any),
+    /// and recording each signing request to the given audit log (if any).
+    ///
+    /// A token is required for a wallet file, or for an address other than a loopback address.
+    pub fn bind(
+        address: &str,
+        token: Option<String>,
+        wallet_file: Option<&str>,
+        audit_log: Option<&str>,
+    ) -> Result<Self, CLIError> {
+        let mut keys = BTreeMap::new();
+        if let Some(path) = wallet_file {
+            let (_, wallets) = WalletFile::open(path)?;
+            for wallet in wallets.as_array().into_iter().flatten() {
+                if let (Some(address), Some(private_key)) = (
+                    wallet.get("address").and_then(Value::as_str),
+                    wallet.get("private_key").and_then(Value::as_str),
+                ) {
+                    keys.insert(to_key_address(address), private_key.to_string());
+                }
+            }
+        }
+
+        let listener = TcpListener::bind(address)?;
+        let token = token
+            .or_else(|| std::env::var(SERVER_TOKEN).ok())
+            .filter(|token| !token.is_empty());
+        if token.is_none() {
+            if !keys.is_empty() {
+                return Err(CLIError::MissingServerToken(
+                    "with a wallet file".into(),
+                    SERVER_TOKEN.into(),
+                ));
+            }
+            if !listener.local_addr()?.ip().is_loopback() {
+                return Err(CLIError::MissingServerToken(
+                    format!("on the non-loopback address {}", address),
+                    SERVER_TOKEN.into(),
+                ));
+            }
+        }
+
+        Ok(Self {
+            listener,
+            token,
+            keys,
+            audit_log: audit_log.map(AuditLog::open).transpose()?,
+        })
+    }
+
+    /// Serves requests until the process is stopped, one connection at a time.
+    pub fn run(&self) -> Result<(), CLIError> {
+        println!(
+            "Listening on http://{} ({} signing keys loaded)",
+            self.listener.local_addr()?,
+            self.keys.len()
+        );
+
+        for stream in self.listener.incoming() {
+            // A failed connection is reported, but does not stop the server
+            let mut stream = match stream {
+                Ok(stream) => stream,
+                Err(error) => {
+                    eprintln!("accept: {}", error);
+                    continue;
+                }
+            };
+            let (status, body) = match read_request(&mut stream) {
+                Ok(request) => self.route(&request),
+                Err(error) => (400, json!({ "error": error.to_string() })),
+            };
+            // A failed response only affects its own connection
+            let _ = write_response(&mut stream, status, &body);
+        }
+        Ok(())
+    }
+
+    /// Returns the status and body of the response to the given request.
+    fn route(&self, request: &Request) -> (u16, Value) {
+        // A browser sends the origin of a cross-site request, which a local API never serves
+        if request.headers.contains_key("origin") {
+            return (403, json!({ "error": "cross-origin request" }));
+        }
+        if (request.method.as_str(), request.path.as_str()) == ("GET", "/health") {
+            return (200, json!({ "status": "ok" }));
+        }
+        if !self.is_authorized(request) {
+            return (401, json!({ "error": "unauthorized" }));
+        }
+        let media_type = match request.headers.get("content-type") {
+            Some(content_type) => content_type.split(';').next().unwrap_or_default().trim(),
+            None => "",
+        };
+        let is_json = media_type.eq_ignore_ascii_case("application/json");
+        if request.method == "POST" && !is_json {
+            return (
+                415,
+                json!({ "error": "unsupported media type: expected application/json" }),
+            );
+        }
+
+        let response = match (request.method.as_str(), request.path.as_str()) {
+            ("POST", "/bitcoin/derive") => self.derive_bitcoin(&request.body),
+            ("POST", "/bitcoin/sign") => self.sign_bitcoin(&request.body),
+            ("POST", "/ethereum/derive") => self.derive_ethereum(&request.body),
+            ("POST", "/ethereum/sign") => self.sign_ethereum(&request.body),
+            ("POST", "/bitcoin/verify") => self.verify_bitcoin(&request.body),
+            ("POST", "/ethereum/verify") => self.verify_ethereum(&request.body),
+            (_, path) if ENDPOINTS.contains(&path) => return (405, json!({ "error": "method not allowed" })),
+            _ => return (404, json!({ "error": "not found" })),
+        };
+
+        let (status, body) = match response {
+            Ok(body) => (200, body),
+            Err(error) => (400, json!({ "error": error.to_string() })),
+        };
+        if request.path.ends_with("/sign") {
+            self.audit(request, status, &body);
+        }
+        (status, body)
+    }
+
+    /// Records the given signing request and its response to the audit log, if any,
+    /// with only the addresses of the request and the transaction id of the response.
+    fn audit(&self, request: &Request, status: u16, response: &Value) {
+        if let Some(audit_log) = &self.audit_log {
+            let body: Value = serde_json::from_slice(&request.body).unwrap_or_default();
+            let addresses: Vec<&Value> = match body.get("inputs").and_then(Value::as_array) {
+                Some(inputs) => inputs.iter().filter_map(|input| input.get("address")).collect(),
+                None => body.get("address").into_iter().collect(),
+            };
+
+            // A failed audit record is reported, but does not fail the signing request
+            if let Err(error) = audit_log.record(
+                "sign",
+                json!({
+                    "endpoint": request.path,
+                    "status": status,
+                    "network": body.get("network"),
+                    "addresses": addresses,
+                    "transaction_id": response.get("transaction_id"),
+                    "error": response.get("error"),
+                }),
+            ) {
+                eprintln!("audit log: {}", error);
+            }
+        }
+    }
+
+    /// Returns `true` if the server has no token, or the request carries the token.
+    fn is_authorized(&self, request: &Request) -> bool {
+        match &self.token {
+            Some(token) => match request.headers.get("authorization") {
+                Some(authorization) => {
+                    constant_time_eq(authorization.as_bytes(), format!("Bearer {}", token).as_bytes())
+                }
+                None => false,
+            },
+            None => true,
+        }
+    }
+
+    fn derive_bitcoin(&self, body: &[u8]) -> Result<Value, CLIError> {
+        let request: DeriveRequest = serde_json::from_slice(body)?;
+        let key = &request.extended_public_key;
+
+        // The path is checked up front, as the network fallback would mask its error
+        if let Some(path) = &request.path {
+            BitcoinDerivationPath::<BitcoinMainnet>::from_str(path)?.non_hardened_only()?;
+        }
+        let wallet = BitcoinWallet::from_extended_public_key::<BitcoinMainnet>(key, &request.path, &None)
+            .or_else(|_| BitcoinWallet::from_extended_public_key::<BitcoinTestnet>(key, &request.path, &None))?;
+        Ok(serde_json::to_value(wallet)?)
+    }
+
+    fn derive_ethereum(&self, body: &[u8]) -> Result<Value, CLIError> {
+        let request: DeriveRequest = serde_json::from_slice(body)?;
+        let wallet =
+            EthereumWallet::from_extended_public_key::<EthereumMainnet>(&request.extended_public_key, &request.path)?;
+        Ok(serde_json::to_value(wallet)?)
+    }
+
+    fn verify_bitcoin(&self, body: &[u8]) -> Result<Value, CLIError> {
+        let request: VerifyRequest = serde_json::from_slice(body)?;
+        let (address, message, signature) = match (&request.address, &request.message) {
+            (Some(address), Some(message)) => (address, message, &request.signature),
+            _ => return Err(CLIError::InvalidRequest("missing address or message".into())),
+        };
+        let wallet = BitcoinWallet::from_signed_message::<BitcoinMainnet>(address, message, signature)
+            .or_else(|_| BitcoinWallet::from_signed_message::<BitcoinTestnet>(address, message, signature))?;
+        Ok(serde_json::to_value(wallet)?)
+    }
+
+    fn verify_ethereum(&self, body: &[u8]) -> Result<Value, CLIError> {
+        let request: VerifyRequest = serde_json::from_slice(body)?;
+        let wallet = EthereumWallet::from_signature(
+            request.message.as_deref(),
+            request.message_hash.as_deref(),
+            &request.signature,
+        )?;
+        Ok(serde_json::to_value(wallet)?)
+    }
+
+    fn sign_bitcoin(&self, body: &[u8]) -> Result<Value, CLIError> {
+        let mut request: BitcoinSignRequest = serde_json::from_slice(body)?;
+
+        // Inputs without a private key are signed with the loaded key of their address
+        for input in request.inputs.iter_mut() {
+            if input.private_key.is_none() {
+                if let Some(address) = &input.address {
+                    input.private_key = self.keys.get(&to_key_address(address)).cloned();
+                }
+            }
+        }
+
+        let (hex, inputs) = (&request.transaction_hex, &request.inputs);
+        let wallet = match request.network.as_deref() {
+            None | Some(BitcoinMainnet::NAME) => BitcoinWallet::to_signed_transaction::<BitcoinMainnet>(hex, inputs)?,
+            Some(BitcoinTestnet::NAME) => BitcoinWallet::to_signed_transaction::<BitcoinTestnet>(hex, inputs)?,
+            Some(network) => return Err(CLIError::UnsupportedNetwork(network.to_string())),
+        };
+        Ok(serde_json::to_value(wallet)?)
+    }
+
+    fn sign_ethereum(&self, body: &[u8]) -> Result<Value, CLIError> {
+        let request: EthereumSignRequest = serde_json::from_slice(body)?;
+
+        // A request without a private key is signed with the loaded key of its address
+        let private_key = match (request.private_key, &request.address) {
+            (Some(private_key), _) => private_key,
+            (None, Some(address)) => match self.keys.get(&to_key_address(address)) {
+                Some(private_key) => private_key.clone(),
+                None => return Err(CLIError::MissingSigningKey(address.clone())),
+            },
+            (None, None) => return Err(CLIError::InvalidRequest("missing private_key or address".into())),
+        };
+
+        let hex = request.transaction_hex;
+        let wallet = match request.network.as_deref() {
+            None | Some(EthereumMainnet::NAME) => {
+                EthereumWallet::to_signed_transaction::<EthereumMainnet>(hex, private_key, false)?
+            }
+            Some(Goerli::NAME) => EthereumWallet::to_signed_transaction::<Goerli>(hex, private_key, false)?,
+            Some(Kovan::NAME) => EthereumWallet::to_signed_transaction::<Kovan>(hex, private_key, false)?,
+            Some(Rinkeby::NAME) => EthereumWallet::to_signed_transaction::<Rinkeby>(hex, private_key, false)?,
+            Some(Ropsten::NAME) => EthereumWallet::to_signed_transaction::<Ropsten>(hex, private_key, false)?,
+            Some(network) => return Err(CLIError::UnsupportedNetwork(network.to_string())),
+        };
+        Ok(serde_json::to_value(wallet)?)
+    }
+}
+
+/// Returns the address under which the private key of the given address is loaded. Ethereum addresses
+/// and Bech32 addresses are case-insensitive, and are lowercased, while Base58 addresses are case-sensitive.
+fn to_key_address(address: &str) -> String {
+    let lowercase = address.to_lowercase();
+    let is_bech32 = ["bc1", "tb1", "bcrt1"].iter().any(|hrp| lowercase.starts_with(hrp))
+        && (address == lowercase || address == address.to_uppercase());
+    match lowercase.starts_with("0x") || is_bech32 {
+        true => lowercase,
+        false => address.to_string(),
+    }
+}
+
+/// Reads a line of at most `MAX_LINE_SIZE` bytes from the given reader into the given line.
+fn read_line<R: BufRead>(reader: &mut R, line: &mut String) -> Result<(), CLIError> {
+    line.clear();
+    reader.take(MAX_LINE_SIZE).read_line(line)?;
+    match line.ends_with('\n') {
+        true => Ok(()),
+        false if line.len() as u64 >= MAX_LINE_SIZE => Err(CLIError::InvalidRequest("line too long".into())),
+        false => Err(CLIError::InvalidRequest("incomplete request".into())),
+    }
+}
+
+/// Returns the HTTP/1.1 request read from the given stream.
+fn read_request(stream: &mut TcpStream) -> Result<Request, CLIError> {
+    stream.set_read_timeout(Some(READ_TIMEOUT))?;
+    parse_request(&mut BufReader::new(stream))
+}
+
+/// Returns the HTTP/1.1 request read from the given reader.
+fn parse_request<R: BufRead>(reader: &mut R) -> Result<Request, CLIError> {
+    let mut line = String::new();
+    read_line(reader, &mut line)?;
+    let mut request_line = line.split_whitespace();
+    let (method, path) = match (request_line.next(), request_line.next()) {
+        (Some(method), Some(path)) => (method.to_string(), path.to_string()),
+        _ => return Err(CLIError::InvalidRequest("malformed request line".into())),
+    };
+
+    let mut headers = BTreeMap::new();
+    for count in 0.. {
+        read_line(reader, &mut line)?;
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if count == MAX_HEADERS {
+            return Err(CLIError::InvalidRequest("too many headers".into()));
+        }
+        if let Some(separator) = header.find(':') {
+            headers.insert(
+                header[..separator].trim().to_lowercase(),
+                header[separator + 1..].trim().to_string(),
+            );
+        }
+    }
+
+    let length = match headers.get("content-length") {
+        Some(length) => length
+            .parse::<usize>()
+            .map_err(|_| CLIError::InvalidRequest("invalid content length".into()))?,
+        None => 0,
+    };
+    if length > MAX_BODY_SIZE {
+        return Err(CLIError::InvalidRequest("request body too large".into()));
+    }
+
+    let mut body = vec![0u8; length];
+    reader.read_exact(&mut body)?;
+
+    Ok(Request {
+        method,
+        path,
+        headers,
+        body,
+    })
+}
+
+/// Writes the HTTP/1.1 response of the given status and JSON body to the given stream.
+fn write_response(stream: &mut TcpStream, status: u16, body: &Value) -> Result<(), CLIError> {
+    let reason = match status {
+        200 => "OK",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        415 => "Unsupported Media Type",
+        _ => "Bad Request",
+    };
+    let body = serde_json::to_string(body)?;
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        reason,
+        body.len(),
+        body
+    )?;
+    Ok(stream.flush()?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The private key, address, and unsigned and signed transactions of the example of EIP-155
+    const PRIVATE_KEY: &str = "4646464646464646464646464646464646464646464646464646464646464646";
+    const ADDRESS: &str = "0x9d8A62f656a8d1615C1294fd71e9CFb3E4855A4F";
+    const TRANSACTION: &str =
+        "0xec098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a764000080018080";
+    const SIGNED_TRANSACTION: &str = "0xf86c098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a76400008025a028ef61340bd939bc2195fe537567866003e1a15d3c71ff63e1590620aa636276a067cbe9d8997f761aecb703304b3800ccf555c9f3dc64214b297fb1966a3b6d83";
+
+    /// The extended public key of test vector 1 of BIP-32
+    const XPUB: &str = "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8";
+
+    const TOKEN: &str = "secret";
+
+    fn server(keys: &[(&str, &str)]) -> Server {
+        Server {
+            listener: TcpListener::bind("127.0.0.1:0").unwrap(),
+            token: Some(TOKEN.into()),
+            keys: keys
+                .iter()
+                .map(|(address, key)| (to_key_address(address), key.to_string()))
+                .collect(),
+            audit_log: None,
+        }
+    }
+
+    fn request(method: &str, path: &str, headers: &[(&str, &str)], body: Value) -> Request {
+        let mut all_headers = BTreeMap::new();
+        all_headers.insert("authorization".into(), format!("Bearer {}", TOKEN));
+        all_headers.insert("content-type".into(), "application/json".into());
+        for (name, value) in headers {
+            all_headers.insert(name.to_string(), value.to_string());
+        }
+        Request {
+            method: method.into(),
+            path: path.into(),
+            headers: all_headers,
+            body: serde_json::to_vec(&body).unwrap(),
+        }
+    }
+
+    #[test]
+    fn bind_requires_token() {
+        std::env::remove_var(SERVER_TOKEN);
+        assert!(Server::bind("127.0.0.1:0", None, None, None).is_ok());
+        assert!(Server::bind("127.0.0.1:0", Some(TOKEN.into()), None, None).is_ok());
+        match Server::bind("0.0.0.0:0", None, None, None) {
+            Err(CLIError::MissingServerToken(_, _)) => (),
+            _ => panic!("bound a non-loopback address without a token"),
+        };
+
+        let path = std::env::temp_dir().join(format!("wagyu_server_{}.json", std::process::id()));
+        let path = path.to_str().unwrap();
+        let wallets = json!([{ "address": ADDRESS, "private_key": PRIVATE_KEY }]);
+        WalletFile::save(&wallets, "ethereum", path, false).unwrap();
+        let result = Server::bind("127.0.0.1:0", None, Some(path), None);
+        let with_token = Server::bind("127.0.0.1:0", Some(TOKEN.into()), Some(path), None);
+        std::fs::remove_file(path).unwrap();
+        match result {
+            Err(CLIError::MissingServerToken(_, _)) => (),
+            _ => panic!("loaded signing keys without a token"),
+        };
+        assert_eq!(1, with_token.unwrap().keys.len());
+    }
+
+    #[test]
+    fn reject_unauthorized() {
+        let server = server(&[]);
+        let body = json!({ "extended_public_key": XPUB, "path": "m/0" });
+
+        let mut unauthorized = request("POST", "/bitcoin/derive", &[], body.clone());
+        unauthorized.headers.remove("authorization");
+        assert_eq!(401, server.route(&unauthorized).0);
+
+        let wrong_token = request(
+            "POST",
+            "/bitcoin/derive",
+            &[("authorization", "Bearer wrong")],
+            body.clone(),
+        );
+        assert_eq!(401, server.route(&wrong_token).0);
+
+        let mut health = request("GET", "/health", &[], Value::Null);
+        health.headers.remove("authorization");
+        assert_eq!((200, json!({ "status": "ok" })), server.route(&health));
+    }
+
+    #[test]
+    fn reject_cross_origin() {
+        let server = server(&[]);
+        let body = json!({ "extended_public_key": XPUB, "path": "m/0" });
+
+        let cross_origin = request(
+            "POST",
+            "/bitcoin/derive",
+            &[("origin", "https://example.com")],
+            body.clone(),
+        );
+        assert_eq!(403, server.route(&cross_origin).0);
+
+        for content_type in ["text/plain", "application/x-www-form-urlencoded"].iter() {
+            let form = request(
+                "POST",
+                "/bitcoin/derive",
+                &[("content-type", content_type)],
+                body.clone(),
+            );
+            assert_eq!(415, server.route(&form).0);
+        }
+        let charset = request(
+            "POST",
+            "/bitcoin/derive",
+            &[("content-type", "application/json; charset=utf-8")],
+            body,
+        );
+        assert_eq!(200, server.route(&charset).0);
+    }
+
+    #[test]
+    fn route() {
+        let server = server(&[]);
+        let body = json!({ "extended_public_key": XPUB, "path": "m/0" });
+
+        let (status, response) = server.route(&request("POST", "/bitcoin/derive", &[], body.clone()));
+        let path = Some("m/0".to_string());
+        let wallet = BitcoinWallet::from_extended_public_key::<BitcoinMainnet>(XPUB, &path, &None).unwrap();
+        assert_eq!((200, serde_json::to_value(wallet).unwrap()), (status, response));
+
+        assert_eq!(
+            405,
+            server.route(&request("GET", "/bitcoin/derive", &[], Value::Null)).0
+        );
+        assert_eq!(404, server.route(&request("POST", "/monero/derive", &[], body)).0);
+        let invalid = json!({ "extended_public_key": "xpub", "path": "m/0" });
+        assert_eq!(400, server.route(&request("POST", "/bitcoin/derive", &[], invalid)).0);
+    }
+
+    #[test]
+    fn sign_with_loaded_key() {
+        let server = server(&[(ADDRESS, PRIVATE_KEY)]);
+
+        // The key of an Ethereum address is found regardless of its checksum case
+        for address in [ADDRESS.to_string(), ADDRESS.to_lowercase()].iter() {
+            let body = json!({ "transaction_hex": TRANSACTION, "address": address, "network": "mainnet" });
+            let (status, response) = server.route(&request("POST", "/ethereum/sign", &[], body));
+            assert_eq!(200, status);
+            assert_eq!(SIGNED_TRANSACTION, response["transaction_hex"]);
+        }
+
+        let body = json!({ "transaction_hex": TRANSACTION, "address": "0x3535353535353535353535353535353535353535" });
+        let (status, response) = server.route(&request("POST", "/ethereum/sign", &[], body));
+        assert_eq!(400, status);
+        assert!(response["error"].as_str().unwrap().starts_with("missing private key"));
+    }
+
+    #[test]
+    fn key_address() {
+        // Base58 addresses are case-sensitive, while Ethereum and Bech32 addresses are not
+        let address = "1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2";
+        assert_eq!(address, to_key_address(address));
+        assert_ne!(to_key_address(address), to_key_address(&address.to_lowercase()));
+        let address = "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4";
+        assert_eq!(address, to_key_address(&address.to_uppercase()));
+        assert_eq!(ADDRESS.to_lowercase(), to_key_address(ADDRESS));
+    }
+
+    #[test]
+    fn parse() {
+        let request = "POST /bitcoin/derive HTTP/1.1\r\nContent-Type: application/json\r\nContent-Length: 2\r\n\r\n{}";
+        let request = parse_request(&mut request.as_bytes()).unwrap();
+        assert_eq!(
+            ("POST", "/bitcoin/derive"),
+            (request.method.as_str(), request.path.as_str())
+        );
+        assert_eq!(
+            Some(&"application/json".to_string()),
+            request.headers.get("content-type")
+        );
+        assert_eq!(b"{}".to_vec(), request.body);
+
+        let long_line = format!("GET /{} HTTP/1.1\r\n\r\n", "a".repeat(MAX_LINE_SIZE as usize));
+        assert!(parse_request(&mut long_line.as_bytes()).is_err());
+        let many_headers = format!("GET /health HTTP/1.1\r\n{}\r\n", "X-A: b\r\n".repeat(MAX_HEADERS + 1));
+        assert!(parse_request(&mut many_headers.as_bytes()).is_err());
+        assert!(parse_request(&mut "GET /health HTTP/1.1\r\n".as_bytes()).is_err());
+    }
+}
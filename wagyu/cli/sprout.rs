@@ -0,0 +1,258 @@
+//! # Sprout
+//!
+//! Derives the keys and addresses of a legacy Zcash Sprout backup, to migrate its funds to Sapling. A backup is a
+//! Sprout spending key (`SK` on mainnet, `ST` on testnet) of `z_exportkey`, a raw 252-bit spending key in hex, or
+//! a wallet dump of `z_exportwallet`, of which the Sprout keys are read and the transparent and Sapling keys are
+//! skipped. Each Sprout key is printed with its viewing key, to watch its balance, and its address, with the
+//! Sapling address that its funds migrate to.
+//!
+//! The address of each key of a wallet dump is recomputed from its spending key, so a key that does not match the
+//! address of its comment (`zaddr=`) is reported as invalid.
+
+use crate::cli::{CLIError, MaskSecrets, SECRET_MASK};
+use crate::model::PublicKey;
+use crate::zcash::{
+    format::ZcashFormat, Mainnet as ZcashMainnet, SproutSpendingKey, Testnet as ZcashTestnet, ZcashAddress,
+    ZcashNetwork, ZcashPrivateKey, ZcashPublicKey,
+};
+
+use colored::*;
+use core::{fmt, fmt::Display, str::FromStr};
+use serde::Serialize;
+use std::fs;
+
+use crate::model::no_std::{format, vec, String, ToString, Vec};
+
+/// Represents a Sprout key of a legacy backup, with the data to migrate its funds to Sapling
+#[derive(Serialize, Clone, Debug)]
+pub struct SproutKey {
+    /// The line of the key in the wallet dump
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created: Option<String>,
+    pub spending_key: String,
+    pub viewing_key: String,
+    pub address: String,
+    pub network: String,
+    /// The Sapling address that the funds of the key migrate to
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub destination_address: Option<String>,
+    /// Whether the key matches the address of the wallet dump
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub valid: Option<bool>,
+}
+
+impl SproutKey {
+    /// Returns the Sprout key of the given spending key, on the network of its prefix or, for a raw spending key
+    /// in hex, on the given network, with the given Sapling destination address.
+    pub fn from_backup<N: ZcashNetwork>(spending_key: &str, destination: Option<&str>) -> Result<Self, CLIError> {
+        match spending_key.get(..2) {
+            Some("SK") => Self::new::<ZcashMainnet>(spending_key, destination),
+            Some("ST") => Self::new::<ZcashTestnet>(spending_key, destination),
+            _ => Self::new::<N>(spending_key, destination),
+        }
+    }
+
+    fn new<N: ZcashNetwork>(spending_key: &str, destination: Option<&str>) -> Result<Self, CLIError> {
+        let private_key = match hex::decode(spending_key) {
+            Ok(bytes) if bytes.len() == 32 => {
+                // The 252-bit spending key is encoded with its 4 high bits cleared
+                if bytes[0] & 0xf0 != 0 {
+                    return Err(CLIError::InvalidSproutSpendingKey);
+                }
+                let mut key = [0u8; 32];
+                key.copy_from_slice(&bytes);
+                ZcashPrivateKey::<N>::Sprout(SproutSpendingKey::<N>::new(key))
+            }
+            _ => match ZcashPrivateKey::<N>::from_str(spending_key) {
+                Ok(private_key @ ZcashPrivateKey::<N>::Sprout(_)) => private_key,
+                _ => return Err(CLIError::InvalidSproutSpendingKey),
+            },
+        };
+        let public_key = ZcashPublicKey::<N>::from_private_key(&private_key);
+
+        let destination_address = match destination {
+            Some(destination) => match ZcashAddress::<N>::from_str(destination)?.format() {
+                ZcashFormat::Sapling(_) => Some(destination.to_string()),
+                format => return Err(CLIError::InvalidMigrationDestination(format.to_string())),
+            },
+            None => None,
+        };
+
+        Ok(Self {
+            line: None,
+            created: None,
+            spending_key: private_key.to_string(),
+            viewing_key: public_key.to_string(),
+            address: public_key.to_address(&ZcashFormat::Sprout)?.to_string(),
+            network: N::NAME.to_string(),
+            destination_address,
+            valid: None,
+        })
+    }
+}
+
+/// Returns the Sprout keys of the wallet dump of `z_exportwallet` at the given path, with the given Sapling
+/// destination address, verified against the address of each key.
+pub fn read_sprout_dump<N: ZcashNetwork>(path: &str, destination: Option<&str>) -> Result<Vec<SproutKey>, CLIError> {
+    let contents = fs::read_to_string(path)?;
+
+    let mut keys = vec![];
+    for (line, text) in contents
+        .lines()
+        .enumerate()
+        .map(|(index, text)| (index + 1, text.trim()))
+    {
+        if text.is_empty() || text.starts_with('#') {
+            continue;
+        }
+
+        // Each line of a key is its spending key, its creation time, and a comment of its address
+        let (fields, comment) = text.split_once('#').unwrap_or((text, ""));
+        let fields = fields.split_whitespace().collect::<Vec<&str>>();
+        let spending_key = fields.first().copied().unwrap_or_default();
+        if !spending_key.starts_with("SK") && !spending_key.starts_with("ST") {
+            continue;
+        }
+
+        let mut key = SproutKey::from_backup::<N>(spending_key, destination).map_err(|error| match error {
+            CLIError::InvalidSproutSpendingKey => CLIError::InvalidWalletDumpLine(line),
+            error => error,
+        })?;
+        key.line = Some(line);
+        key.created = fields.get(1).map(|created| created.to_string());
+        key.valid = comment
+            .split_whitespace()
+            .find_map(|field| field.strip_prefix("zaddr="))
+            .map(|address| address == key.address);
+        keys.push(key);
+    }
+
+    match keys.is_empty() {
+        true => Err(CLIError::MissingSproutKeys),
+        false => Ok(keys),
+    }
+}
+
+impl MaskSecrets for SproutKey {
+    fn mask_secrets(&mut self) {
+        self.spending_key = SECRET_MASK.into();
+    }
+}
+
+#[cfg_attr(tarpaulin, skip)]
+impl Display for SproutKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let output = [
+            match self.line {
+                Some(line) => format!("      {}                 {}\n", "Line".cyan().bold(), line),
+                _ => "".to_string(),
+            },
+            match &self.created {
+                Some(created) => format!("      {}              {}\n", "Created".cyan().bold(), created),
+                _ => "".to_string(),
+            },
+            format!("      {}         {}\n", "Spending Key".cyan().bold(), self.spending_key),
+            format!("      {}          {}\n", "Viewing Key".cyan().bold(), self.viewing_key),
+            format!("      {}              {}\n", "Address".cyan().bold(), self.address),
+            format!("      {}              {}\n", "Network".cyan().bold(), self.network),
+            match &self.destination_address {
+                Some(destination_address) => format!(
+                    "      {}  {}\n",
+                    "Destination Address".cyan().bold(),
+                    destination_address
+                ),
+                _ => "".to_string(),
+            },
+            match self.valid {
+                Some(valid) => format!("      {}                {}\n", "Valid".cyan().bold(), valid),
+                _ => "".to_string(),
+            },
+        ]
+        .concat();
+
+        // Removes final new line character
+        let output = output[..output.len() - 1].to_string();
+        write!(f, "\n{}", output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The Sprout spending keys of zcashd, with their viewing keys and addresses
+    const KEYS: [(&str, &str, &str); 2] = [
+        (
+            "SKxt8pwrQipUL5KgZUcBAqyLj9R1YwMuRRR3ijGMCwCCqchmi8ut",
+            "ZiVKYQyUcyAJLKwcosSeDxkGRhygFdAPWsr3m8UgjC5X85yqNyLTtJJJYNH83Wf2AQKU6TZsd65MXBZLFj6eSCAFcnCFuVCFS",
+            "zcJLC7a3aRJohMNCVjSZQ8jFuofhAHJNAY4aX5soDkYfgNejzKnEZbucJmVibLWCwK8dyyfDhNhf3foXDDTouweC382LcX5",
+        ),
+        (
+            "SKxoo5QkFQgTbdc6EWRKyHPMdmtNDJhqudrAVhen9b4kjCwN6CeV",
+            "ZiVKfdhhmQ1fpXaxyW5zRXw4Dhg9cbKRgK7mNFoBLiKjiBZiHJYJTpV2gNMDMPY9sRC96vnKZcnTMSi65SKPyL4WNQNm9PT5H",
+            "zcRYvLiURno1LhXq95e8avXFcH2fKKToSFfhqaVKTy8mGH7i6SJbfuWcm4h9rEA6DvswrbxDhFGDQgpdDYV8zwUoHvwNvFX",
+        ),
+    ];
+
+    /// The raw 252-bit spending key of the first Sprout spending key
+    const RAW_SPENDING_KEY: &str = "0bbba5712bf43c9df6a72f036266affbca9ca1c2499a931d817df1dd5e4a08d1";
+
+    const DESTINATION: &str = "zs1dq9dlh6u6hna0u96aqtynxt3acddtgkgdx4re65500nmc2aze0my65ky36vaqvj4hkc9ut66eyf";
+
+    /// A wallet dump of `z_exportwallet` of a transparent key, the Sprout spending keys, and a Sapling key
+    const WALLET_DUMP: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/wagyu/cli/fixtures/zcash_wallet_dump.txt");
+
+    #[test]
+    fn from_backup() {
+        for (spending_key, viewing_key, address) in KEYS.iter() {
+            let key = SproutKey::from_backup::<ZcashTestnet>(spending_key, Some(DESTINATION)).unwrap();
+            assert_eq!(*spending_key, key.spending_key);
+            assert_eq!(*viewing_key, key.viewing_key);
+            assert_eq!(*address, key.address);
+            assert_eq!("mainnet", key.network);
+            assert_eq!(Some(DESTINATION), key.destination_address.as_deref());
+        }
+
+        let key = SproutKey::from_backup::<ZcashMainnet>(RAW_SPENDING_KEY, None).unwrap();
+        assert_eq!(KEYS[0].0, key.spending_key);
+        assert_eq!(KEYS[0].2, key.address);
+    }
+
+    #[test]
+    fn from_invalid_backup() {
+        // A raw spending key with its 4 high bits set, and a transparent private key
+        match SproutKey::from_backup::<ZcashMainnet>(&format!("f{}", &RAW_SPENDING_KEY[1..]), None) {
+            Err(CLIError::InvalidSproutSpendingKey) => {}
+            _ => panic!("expected an invalid spending key"),
+        };
+        match SproutKey::from_backup::<ZcashMainnet>("KxYzZuBPkE3rnEEGCdsB6dCzxN1D4xoY5ogKoxbdUdkxbRzvgbij", None) {
+            Err(CLIError::InvalidSproutSpendingKey) => {}
+            _ => panic!("expected an invalid spending key"),
+        };
+
+        // A destination of a Sprout address
+        match SproutKey::from_backup::<ZcashMainnet>(KEYS[0].0, Some(KEYS[1].2)) {
+            Err(CLIError::InvalidMigrationDestination(_)) => {}
+            _ => panic!("expected an invalid destination"),
+        };
+    }
+
+    #[test]
+    fn read_fixture() {
+        let keys = read_sprout_dump::<ZcashMainnet>(WALLET_DUMP, Some(DESTINATION)).unwrap();
+        let summary = keys
+            .iter()
+            .map(|key| (key.line, key.address.as_str(), key.created.as_deref(), key.valid))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                (Some(8), KEYS[0].2, Some("2018-03-01T12:00:00Z"), Some(true)),
+                (Some(9), KEYS[1].2, Some("2018-03-02T12:00:00Z"), Some(true)),
+            ],
+            summary
+        );
+        assert_eq!(KEYS[1].1, keys[1].viewing_key);
+    }
+}
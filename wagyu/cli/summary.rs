@@ -0,0 +1,135 @@
+//! # Summary
+//!
+//! A "fingerprint card" of an HD wallet, with only its identifying data: the fingerprint of its master key,
+//! a hash of its account extended public key, and its first addresses. Two parties can read their cards
+//! to each other (e.g. over the phone) to verify that they derived the same wallet, without revealing a secret.
+//!
+//! The account hash is the first 8 bytes of the SHA-256 of the account extended public key, in hex.
+
+use crate::cli::{CLIError, MaskSecrets};
+use crate::model::{ChildIndex, DerivationPath, ExtendedPrivateKey, ExtendedPublicKey};
+
+use colored::*;
+use core::{fmt, fmt::Display};
+use crypto::{digest::Digest, sha2::Sha256};
+use serde::Serialize;
+
+use crate::model::no_std::{format, vec, String, ToString, Vec};
+
+/// The default number of addresses of a summary
+pub const SUMMARY_COUNT: u32 = 5;
+
+/// Represents an address of a wallet summary
+#[derive(Serialize, Clone, Debug)]
+pub struct SummaryAddress {
+    pub path: String,
+    pub address: String,
+}
+
+/// Represents the non-sensitive identifying data of an HD wallet
+#[derive(Serialize, Clone, Debug)]
+pub struct WalletSummary {
+    pub master_fingerprint: String,
+    pub account_path: String,
+    pub account_hash: String,
+    pub addresses: Vec<SummaryAddress>,
+    pub network: String,
+}
+
+impl WalletSummary {
+    /// Returns the summary of the wallet at the given path of the given master extended private key.
+    /// The account is the deepest hardened key of the path (or the master key, if the path has none),
+    /// and the addresses are the first `count` siblings of the wallet, starting from index 0.
+    pub fn new<K: ExtendedPrivateKey>(
+        master_extended_private_key: &K,
+        path: &K::DerivationPath,
+        format: &<K::ExtendedPublicKey as ExtendedPublicKey>::Format,
+        count: u32,
+        network: &str,
+        fingerprint: fn(&K::ExtendedPublicKey) -> [u8; 4],
+    ) -> Result<Self, CLIError> {
+        let indices = path.to_vec()?;
+
+        let account_depth = indices.iter().rposition(ChildIndex::is_hardened).map_or(0, |i| i + 1);
+        let account_path = K::DerivationPath::from_vec(&indices[..account_depth].to_vec())?;
+        let account_key = master_extended_private_key
+            .derive(&account_path)?
+            .to_extended_public_key();
+        let mut sha256 = Sha256::new();
+        sha256.input_str(&account_key.to_string());
+        let mut account_hash = [0u8; 32];
+        sha256.result(&mut account_hash);
+
+        // The siblings of a wallet share its parent, and the hardness of its index
+        let siblings = match indices.split_last() {
+            Some((last, parent)) => (0..count)
+                .map(|index| {
+                    let mut sibling = parent.to_vec();
+                    sibling.push(match last.is_hardened() {
+                        true => ChildIndex::hardened(index)?,
+                        false => ChildIndex::normal(index)?,
+                    });
+                    Ok(K::DerivationPath::from_vec(&sibling)?)
+                })
+                .collect::<Result<Vec<_>, CLIError>>()?,
+            None => vec![path.clone()],
+        };
+
+        let mut addresses = Vec::with_capacity(siblings.len());
+        for sibling in siblings {
+            let address = master_extended_private_key
+                .derive(&sibling)?
+                .to_extended_public_key()
+                .to_address(format)?;
+            addresses.push(SummaryAddress {
+                path: sibling.to_string(),
+                address: address.to_string(),
+            });
+        }
+
+        Ok(Self {
+            master_fingerprint: hex::encode(fingerprint(&master_extended_private_key.to_extended_public_key())),
+            account_path: account_path.to_string(),
+            account_hash: hex::encode(&account_hash[..8]),
+            addresses,
+            network: network.to_string(),
+        })
+    }
+}
+
+impl MaskSecrets for WalletSummary {
+    fn mask_secrets(&mut self) {}
+}
+
+#[cfg_attr(tarpaulin, skip)]
+impl Display for WalletSummary {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f)?;
+        writeln!(
+            f,
+            "      {}   {}",
+            "Master Fingerprint".cyan().bold(),
+            self.master_fingerprint
+        )?;
+        writeln!(
+            f,
+            "      {}              {}",
+            "Account".cyan().bold(),
+            self.account_path
+        )?;
+        writeln!(
+            f,
+            "      {}         {}",
+            "Account Hash".cyan().bold(),
+            self.account_hash
+        )?;
+        for (i, address) in self.addresses.iter().enumerate() {
+            let label = match i {
+                0 => format!("{:<21}", "Addresses"),
+                _ => format!("{:<21}", ""),
+            };
+            writeln!(f, "      {}{} {}", label.cyan().bold(), address.path, address.address)?;
+        }
+        write!(f, "      {}              {}", "Network".cyan().bold(), self.network)
+    }
+}
@@ -0,0 +1,171 @@
+//! # Tree
+//!
+//! Derives the structure of an HD wallet as a tree of its accounts, chains, and addresses,
+//! with the fingerprint of each extended key, to show what a seed contains at a glance.
+
+use crate::cli::{mask, CLIError, MaskSecrets};
+use crate::model::{ChildIndex, DerivationPath, ExtendedPrivateKey, ExtendedPublicKey};
+
+use colored::*;
+use core::{fmt, fmt::Display};
+use serde::Serialize;
+
+use crate::model::no_std::{format, vec, String, ToString, Vec};
+
+/// The number of levels below an account, its chains and their addresses
+pub const MAX_TREE_DEPTH: u32 = 2;
+
+/// Represents a node of an HD wallet tree, which is either an extended key with its child nodes, or an address
+#[derive(Serialize, Clone, Debug, Default)]
+pub struct HDTreeNode {
+    pub path: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fingerprint: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub extended_public_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub children: Vec<HDTreeNode>,
+}
+
+impl HDTreeNode {
+    /// Returns the tree of the given master extended private key, with `breadth` accounts under the
+    /// given path prefix (such as m/44'/0'), and under each account, its given chains (with their names)
+    /// and `breadth` addresses of each chain, down to `depth` levels below the accounts.
+    pub fn new<K: ExtendedPrivateKey>(
+        master_extended_private_key: &K,
+        prefix: &[ChildIndex],
+        chains: &[(u32, &str)],
+        format: &<K::ExtendedPublicKey as ExtendedPublicKey>::Format,
+        depth: u32,
+        breadth: u32,
+        fingerprint: fn(&K::ExtendedPublicKey) -> [u8; 4],
+    ) -> Result<HDTreeNode, CLIError> {
+        type PublicPath<K> = <<K as ExtendedPrivateKey>::ExtendedPublicKey as ExtendedPublicKey>::DerivationPath;
+
+        let depth = depth.min(MAX_TREE_DEPTH);
+        let mut accounts = vec![];
+        for account in 0..breadth {
+            let mut account_path = prefix.to_vec();
+            account_path.push(ChildIndex::hardened(account)?);
+            let account_path = K::DerivationPath::from_vec(&account_path)?;
+            let account_key = master_extended_private_key
+                .derive(&account_path)?
+                .to_extended_public_key();
+
+            let mut chain_nodes = vec![];
+            for (chain, name) in chains.iter().filter(|_| depth > 0) {
+                let chain_path = format!("{}/{}", account_path, chain);
+                let chain_key = account_key.derive(&PublicPath::<K>::from_vec(&vec![ChildIndex::normal(*chain)?])?)?;
+
+                let mut address_nodes = vec![];
+                for index in (0..breadth).filter(|_| depth > 1) {
+                    let address = chain_key
+                        .derive(&PublicPath::<K>::from_vec(&vec![ChildIndex::normal(index)?])?)?
+                        .to_address(format)?;
+                    address_nodes.push(HDTreeNode {
+                        path: format!("{}/{}", chain_path, index),
+                        address: Some(address.to_string()),
+                        ..Default::default()
+                    });
+                }
+
+                chain_nodes.push(HDTreeNode {
+                    path: chain_path,
+                    name: Some(name.to_string()),
+                    fingerprint: Some(hex::encode(fingerprint(&chain_key))),
+                    children: address_nodes,
+                    ..Default::default()
+                });
+            }
+
+            accounts.push(HDTreeNode {
+                path: account_path.to_string(),
+                fingerprint: Some(hex::encode(fingerprint(&account_key))),
+                extended_public_key: Some(account_key.to_string()),
+                children: chain_nodes,
+                ..Default::default()
+            });
+        }
+
+        Ok(HDTreeNode {
+            path: "m".into(),
+            fingerprint: Some(hex::encode(fingerprint(
+                &master_extended_private_key.to_extended_public_key(),
+            ))),
+            children: accounts,
+            ..Default::default()
+        })
+    }
+
+    /// Returns the address nodes of the tree, in order.
+    pub fn to_addresses(&self) -> Vec<HDTreeNode> {
+        match self.address {
+            Some(_) => vec![self.clone()],
+            None => self.children.iter().flat_map(HDTreeNode::to_addresses).collect(),
+        }
+    }
+
+    /// Writes the child nodes of the node, each under the given prefix, with their paths
+    /// relative to the node, except for the accounts under the root.
+    fn write_children(&self, f: &mut fmt::Formatter, prefix: &str) -> fmt::Result {
+        for (i, child) in self.children.iter().enumerate() {
+            let (branch, indent) = match i + 1 == self.children.len() {
+                true => ("└── ", "    "),
+                false => ("├── ", "│   "),
+            };
+            let path = match prefix.is_empty() {
+                true => child.path.as_str(),
+                false => child.path.rsplit('/').next().unwrap_or_default(),
+            };
+
+            write!(f, "\n{}{}{}", prefix, branch, path.cyan().bold())?;
+            if let Some(name) = &child.name {
+                write!(f, " ({})", name)?;
+            }
+            if let Some(fingerprint) = &child.fingerprint {
+                write!(f, " [{}]", fingerprint)?;
+            }
+            if let Some(key) = child.extended_public_key.as_ref().or(child.address.as_ref()) {
+                write!(f, " {}", key)?;
+            }
+            child.write_children(f, &format!("{}{}", prefix, indent))?;
+        }
+        Ok(())
+    }
+}
+
+/// Represents an HD wallet tree, rooted at the master key of a mnemonic
+#[derive(Serialize, Clone, Debug)]
+pub struct HDTree {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mnemonic: Option<String>,
+    pub network: String,
+    pub tree: HDTreeNode,
+}
+
+impl MaskSecrets for HDTree {
+    fn mask_secrets(&mut self) {
+        mask(&mut self.mnemonic);
+    }
+}
+
+#[cfg_attr(tarpaulin, skip)]
+impl Display for HDTree {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f)?;
+        if let Some(mnemonic) = &self.mnemonic {
+            writeln!(f, "      {}             {}", "Mnemonic".cyan().bold(), mnemonic)?;
+        }
+        writeln!(f, "      {}              {}\n", "Network".cyan().bold(), self.network)?;
+
+        write!(f, "{}", self.tree.path.cyan().bold())?;
+        if let Some(fingerprint) = &self.tree.fingerprint {
+            write!(f, " [{}]", fingerprint)?;
+        }
+        self.tree.write_children(f, "")
+    }
+}
@@ -0,0 +1,230 @@
+//! # Unsigned Transaction
+//!
+//! A versioned file format for moving an unsigned transaction from an online machine
+//! (`create-tx`) to an offline machine (`sign-tx`). The schema is shared across coins,
+//! with the coin-specific signing context stored in the `context` field: an array of the
+//! spent outputs of a Bitcoin transaction, or the parameters of an Ethereum transaction.
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "coin": "bitcoin",
+//!   "network": "mainnet",
+//!   "transaction_hex": "0100000001...",
+//!   "context": [...]
+//! }
+//! ```
+
+use crate::cli::bitcoin::BitcoinInput;
+use crate::cli::ethereum::EthereumInput;
+use crate::cli::{object, property, CLIError, Schema};
+
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use serde_json::json;
+
+use crate::model::no_std::{String, ToString};
+
+/// The current version of the unsigned transaction file schema
+pub const UNSIGNED_TRANSACTION_VERSION: u32 = 1;
+
+/// Represents an unsigned transaction file with its signing context
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct UnsignedTransactionFile<C> {
+    /// The version of the file schema
+    pub version: u32,
+    /// The coin of the transaction
+    pub coin: String,
+    /// The network of the transaction
+    pub network: String,
+    /// The unsigned transaction in hex
+    pub transaction_hex: String,
+    /// The coin-specific context required to sign the transaction offline
+    pub context: C,
+}
+
+impl<C> Schema for UnsignedTransactionFile<C> {
+    fn schema() -> serde_json::Value {
+        object(
+            vec![
+                ("version", property("integer", "The version of the file schema")),
+                ("coin", property("string", "The coin of the transaction")),
+                ("network", property("string", "The network of the transaction")),
+                ("transaction_hex", property("string", "The unsigned transaction in hex")),
+                (
+                    "context",
+                    json!({
+                        "description": "The coin-specific context required to sign the transaction offline",
+                        "oneOf": [
+                            { "type": "array", "items": BitcoinInput::schema() },
+                            EthereumInput::schema(),
+                        ],
+                    }),
+                ),
+            ],
+            &["version", "coin", "network", "transaction_hex", "context"],
+        )
+    }
+}
+
+impl<C: Serialize + DeserializeOwned> UnsignedTransactionFile<C> {
+    /// Returns a new unsigned transaction file of the current schema version.
+    pub fn new(coin: &str, network: &str, transaction_hex: &str, context: C) -> Self {
+        Self {
+            version: UNSIGNED_TRANSACTION_VERSION,
+            coin: coin.to_string(),
+            network: network.to_string(),
+            transaction_hex: transaction_hex.to_string(),
+            context,
+        }
+    }
+
+    /// Returns the unsigned transaction file at the given path for the given coin.
+    pub fn read(path: &str, coin: &str) -> Result<Self, CLIError> {
+        let file: UnsignedTransactionFile<serde_json::Value> = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+
+        // The version and coin are checked before the coin-specific context is parsed
+        if file.version != UNSIGNED_TRANSACTION_VERSION {
+            return Err(CLIError::UnsupportedFileVersion(file.version));
+        }
+        if file.coin != coin {
+            return Err(CLIError::MismatchedCoin(coin.to_string(), file.coin));
+        }
+
+        Ok(Self {
+            version: file.version,
+            coin: file.coin,
+            network: file.network,
+            transaction_hex: file.transaction_hex,
+            context: serde_json::from_value(file.context)?,
+        })
+    }
+
+    /// Writes the unsigned transaction file to the given path.
+    pub fn write(&self, path: &str) -> Result<(), CLIError> {
+        Ok(std::fs::write(path, serde_json::to_string_pretty(self)?)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bitcoin::Mainnet as BitcoinMainnet;
+    use crate::cli::bitcoin::BitcoinWallet;
+    use crate::cli::ethereum::EthereumWallet;
+    use crate::cli::schema::schema;
+    use crate::ethereum::Mainnet as EthereumMainnet;
+
+    use serde_json::Value;
+
+    /// Returns whether the given value is valid for the given schema, for the keywords used by the schemas
+    /// of this crate: `type`, `properties`, `required`, `additionalProperties`, `items`, and `oneOf`.
+    fn is_valid(schema: &Value, value: &Value) -> bool {
+        let is_type = |kind: &Value| match (kind.as_str(), value) {
+            (Some("array"), Value::Array(_)) | (Some("object"), Value::Object(_)) => true,
+            (Some("string"), Value::String(_)) | (Some("boolean"), Value::Bool(_)) => true,
+            (Some("integer"), Value::Number(number)) => number.is_u64() || number.is_i64(),
+            (Some("number"), Value::Number(_)) | (Some("null"), Value::Null) => true,
+            _ => false,
+        };
+        let valid_type = match &schema["type"] {
+            Value::Null => true,
+            Value::Array(kinds) => kinds.iter().any(is_type),
+            kind => is_type(kind),
+        };
+        let valid_one_of = match schema["oneOf"].as_array() {
+            Some(schemas) => schemas.iter().filter(|schema| is_valid(schema, value)).count() == 1,
+            None => true,
+        };
+        let valid_members = match value {
+            Value::Object(members) => {
+                let properties = schema["properties"].as_object();
+                let required = schema["required"].as_array().cloned().unwrap_or_default();
+                required
+                    .iter()
+                    .all(|name| members.contains_key(name.as_str().unwrap_or_default()))
+                    && members
+                        .iter()
+                        .all(|(name, member)| match properties.and_then(|p| p.get(name)) {
+                            Some(property) => is_valid(property, member),
+                            None => schema["additionalProperties"] != Value::Bool(false),
+                        })
+            }
+            Value::Array(items) if !schema["items"].is_null() => {
+                items.iter().all(|item| is_valid(&schema["items"], item))
+            }
+            _ => true,
+        };
+        valid_type && valid_one_of && valid_members
+    }
+
+    fn read_json(path: &std::path::Path) -> Value {
+        let value = serde_json::from_str(&std::fs::read_to_string(path).unwrap()).unwrap();
+        std::fs::remove_file(path).unwrap();
+        value
+    }
+
+    #[test]
+    fn test_bitcoin_file_schema() {
+        let path = std::env::temp_dir().join(format!("wagyu-unsigned-bitcoin-{}.json", std::process::id()));
+        let input = BitcoinInput {
+            txid: "61d520ccb74288c96bc1a2b20ea1c0d5a704776dd0164a396efec3ea7040349d".into(),
+            vout: 0,
+            amount: Some(100_000),
+            address: Some("1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2".into()),
+            private_key: None,
+            script_pub_key: None,
+            redeem_script: None,
+        };
+        BitcoinWallet::to_unsigned_transaction_file::<BitcoinMainnet>(
+            &vec![input],
+            &vec!["1BvBMSEYstWetqTFn5Au4m4GFg7xJaNVN2:90000"],
+            None,
+            1,
+            0,
+            path.to_str().unwrap(),
+        )
+        .unwrap();
+
+        let file = read_json(&path);
+        assert!(file["context"].is_array());
+        assert!(is_valid(&schema("unsigned-transaction").unwrap(), &file));
+    }
+
+    #[test]
+    fn test_ethereum_file_schema() {
+        let path = std::env::temp_dir().join(format!("wagyu-unsigned-ethereum-{}.json", std::process::id()));
+        let input = EthereumInput {
+            to: "0xE9E5F1de1e1d5b4D59A4E8Ba4B8f7A2B6a6B3D50".into(),
+            value: "1000000000000000000".into(),
+            gas: Some("21000".into()),
+            gas_price: "20000000000".into(),
+            nonce: Some(0),
+            from: None,
+            data: None,
+        };
+        EthereumWallet::to_unsigned_transaction_file::<EthereumMainnet>(input, path.to_str().unwrap()).unwrap();
+
+        let file = read_json(&path);
+        assert!(file["context"].is_object());
+        assert!(is_valid(&schema("unsigned-transaction").unwrap(), &file));
+    }
+
+    #[test]
+    fn test_invalid_file_schema() {
+        let schema = schema("unsigned-transaction").unwrap();
+        let file = json!({
+            "version": 1,
+            "coin": "bitcoin",
+            "network": "mainnet",
+            "transaction_hex": "",
+            "context": [],
+        });
+        assert!(is_valid(&schema, &file));
+
+        for context in [json!("inputs"), json!([{ "txid": "" }]), json!({ "to": "0x" })].iter() {
+            let mut file = file.clone();
+            file["context"] = context.clone();
+            assert!(!is_valid(&schema, &file));
+        }
+    }
+}
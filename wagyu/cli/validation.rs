@@ -0,0 +1,63 @@
+//! # Validation
+//!
+//! Sanity checks of the outputs of a transaction before it is built, shared by the transaction
+//! subcommands of Bitcoin and Zcash. An output below the dust limit is not relayed by nodes, so
+//! it is rejected unless dust is allowed with the `--allow-dust` flag, while a fee that exceeds a
+//! percentage of the amount sent is likely a mistake, and is warned about.
+
+use crate::cli::CLIError;
+
+use core::str::FromStr;
+
+/// The default dust limit of an output in satoshis (or zatoshis), for a P2PKH output at the minimum relay fee rate
+pub const DUST_LIMIT: u64 = 546;
+
+/// The percentage of the amount sent above which the fee of a transaction is warned about
+pub const HIGH_FEE_PERCENT: u64 = 10;
+
+/// Checks the given outputs (`address:amount`) against the given dust limit, returning an error for an output
+/// below it, or printing a warning if dust is allowed.
+pub fn check_dust(outputs: &[&str], dust_limit: u64, allow_dust: bool) -> Result<(), CLIError> {
+    for (address, amount) in outputs.iter().filter_map(|output| to_amount(output)) {
+        if amount < dust_limit {
+            match allow_dust {
+                true => eprintln!(
+                    "Warning: the output of {} to {} is below the dust limit of {}, and may not be relayed",
+                    amount, address, dust_limit
+                ),
+                false => return Err(CLIError::DustOutput(address.into(), amount, dust_limit)),
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Prints a warning if the fee of the given inputs and outputs (`address:amount`) exceeds `HIGH_FEE_PERCENT`
+/// of the amount sent. The fee is only checked if the amounts of all of the inputs are known.
+pub fn check_fee(input_amounts: &[Option<u64>], outputs: &[&str]) {
+    let amount_sent = outputs
+        .iter()
+        .filter_map(|output| to_amount(output))
+        .map(|(_, amount)| amount)
+        .sum::<u64>();
+    let amount_received = input_amounts.iter().copied().sum::<Option<u64>>();
+
+    if let Some(fee) = amount_received.and_then(|amount| amount.checked_sub(amount_sent)) {
+        if amount_sent > 0 && fee * 100 > amount_sent * HIGH_FEE_PERCENT {
+            eprintln!(
+                "Warning: the fee of {} is {:.1}% of the amount sent ({}), above {}%",
+                fee,
+                fee as f64 * 100.0 / amount_sent as f64,
+                amount_sent,
+                HIGH_FEE_PERCENT
+            );
+        }
+    }
+}
+
+/// Returns the address and the amount of the given output (`address:amount`).
+/// A malformed output is skipped, as it is reported when the transaction is built.
+fn to_amount(output: &str) -> Option<(&str, u64)> {
+    let (address, amount) = output.split_once(':')?;
+    Some((address, u64::from_str(amount).ok()?))
+}
@@ -0,0 +1,181 @@
+//! # Verify
+//!
+//! The verification of the wallets of a prior JSON output, for catching corrupted backups and
+//! transcription errors. Each wallet is recognized by the coin of the registry that parses its
+//! address, and everything derivable from its fields (such as the addresses of an extended public
+//! key, or the keys of a mnemonic) is re-derived and compared with the stored fields.
+//!
+//! Mnemonics are re-derived as English mnemonics.
+
+use crate::cli::{CLIError, MaskSecrets, Registry};
+
+use colored::*;
+use core::{fmt, fmt::Display};
+use serde::Serialize;
+use serde_json::Value;
+use std::fs;
+
+use crate::model::no_std::{format, String, ToString, Vec};
+
+/// Represents a stored field of a wallet that differs from the field re-derived from another of its fields
+#[derive(Serialize, Clone, Debug)]
+pub struct Mismatch {
+    pub field: String,
+    pub source: String,
+    pub stored: String,
+    pub derived: String,
+}
+
+/// Returns the mismatches between the given stored fields of the given wallet and the fields of the
+/// wallet re-derived from its given source field, or a mismatch of the source field if it fails to
+/// derive a wallet. A wallet without the source field has no mismatches.
+pub fn rederive<T: Serialize, F: FnOnce(&str) -> Result<T, CLIError>>(
+    wallet: &Value,
+    source: &str,
+    fields: &[&str],
+    derive: F,
+) -> Vec<Mismatch> {
+    let value = match wallet[source].as_str() {
+        Some(value) => value,
+        None => return Vec::new(),
+    };
+
+    let derived = match derive(value).and_then(|derived| Ok(serde_json::to_value(derived)?)) {
+        Ok(derived) => derived,
+        Err(error) => {
+            return vec![Mismatch {
+                field: source.into(),
+                source: source.into(),
+                stored: value.into(),
+                derived: error.to_string(),
+            }]
+        }
+    };
+
+    fields
+        .iter()
+        .filter_map(|field| match (wallet[*field].as_str(), derived[*field].as_str()) {
+            (Some(stored), Some(derived)) if stored != derived => Some(Mismatch {
+                field: field.to_string(),
+                source: source.into(),
+                stored: stored.into(),
+                derived: derived.into(),
+            }),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Represents the verification of a wallet of a prior output
+#[derive(Serialize, Clone, Debug)]
+pub struct WalletVerification {
+    pub index: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub coin: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub address: Option<String>,
+    pub mismatches: Vec<Mismatch>,
+}
+
+impl WalletVerification {
+    /// Returns whether the wallet is recognized by a coin, and matches its re-derived fields.
+    pub fn verified(&self) -> bool {
+        self.coin.is_some() && self.mismatches.is_empty()
+    }
+}
+
+/// Represents the verification of the wallets of a prior output
+#[derive(Serialize, Clone, Debug)]
+pub struct Verification {
+    pub wallets: Vec<WalletVerification>,
+    pub verified: usize,
+    pub failed: usize,
+}
+
+impl Verification {
+    /// Returns the verification of the wallets of the given prior JSON output (a wallet, or an array
+    /// of wallets), with each wallet re-derived by the coin of the registry that recognizes it.
+    pub fn new(registry: &Registry, output: &Value) -> Result<Self, CLIError> {
+        let wallets = match output {
+            Value::Array(wallets) => wallets.clone(),
+            Value::Object(_) => vec![output.clone()],
+            _ => return Err(CLIError::InvalidVerificationInput),
+        };
+
+        let mut verifications = Vec::with_capacity(wallets.len());
+        for (index, wallet) in wallets.iter().enumerate() {
+            let mut verification = WalletVerification {
+                index,
+                coin: None,
+                address: wallet["address"].as_str().map(|address| address.to_string()),
+                mismatches: Vec::new(),
+            };
+            for backend in registry.backends() {
+                if let Some(mismatches) = backend.verify(wallet)? {
+                    verification.coin = Some(backend.name().to_string());
+                    verification.mismatches = mismatches;
+                    break;
+                }
+            }
+            verifications.push(verification);
+        }
+
+        let verified = verifications.iter().filter(|wallet| wallet.verified()).count();
+        Ok(Self {
+            failed: verifications.len() - verified,
+            verified,
+            wallets: verifications,
+        })
+    }
+
+    /// Returns the verification of the wallets of the prior JSON output in the given file.
+    pub fn from_file(registry: &Registry, path: &str) -> Result<Self, CLIError> {
+        Self::new(registry, &serde_json::from_str(&fs::read_to_string(path)?)?)
+    }
+}
+
+impl MaskSecrets for Verification {
+    fn mask_secrets(&mut self) {}
+}
+
+#[cfg_attr(tarpaulin, skip)]
+impl Display for Verification {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f)?;
+        for wallet in &self.wallets {
+            let label = format!("{:<21}", format!("Wallet {}", wallet.index));
+            let status = match (&wallet.coin, wallet.mismatches.is_empty()) {
+                (None, _) => "UNRECOGNIZED".red().bold(),
+                (Some(_), true) => "verified".green().bold(),
+                (Some(_), false) => "MISMATCH".red().bold(),
+            };
+            writeln!(
+                f,
+                "      {}{:<10} {:<10} {}",
+                label.cyan().bold(),
+                status,
+                wallet.coin.as_deref().unwrap_or("-"),
+                wallet.address.as_deref().unwrap_or("-")
+            )?;
+            for mismatch in &wallet.mismatches {
+                writeln!(
+                    f,
+                    "      {:<21}{} (from {})\n      {:<21}  stored:  {}\n      {:<21}  derived: {}",
+                    "",
+                    mismatch.field.red(),
+                    mismatch.source,
+                    "",
+                    mismatch.stored,
+                    "",
+                    mismatch.derived
+                )?;
+            }
+        }
+        write!(
+            f,
+            "\n      {} of {} wallets verified",
+            self.verified,
+            self.wallets.len()
+        )
+    }
+}
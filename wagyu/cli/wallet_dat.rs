@@ -0,0 +1,435 @@
+//! # Wallet Dat
+//!
+//! Recovers the private keys of a legacy Bitcoin Core `wallet.dat`, a Berkeley DB B-tree of serialized
+//! records. The records are read from each leaf page of the database, so the keys of a wallet with a
+//! corrupted page are still recovered from the pages that remain:
+//!
+//! - `key`, an unencrypted private key (in DER), of its public key
+//! - `ckey`, a private key encrypted with AES-256-CBC under the master key, of its public key
+//! - `mkey`, the master key, encrypted with AES-256-CBC under a key derived from the wallet passphrase
+//!   with iterations of SHA-512 (as `EVP_BytesToKey` of OpenSSL)
+//! - `name` and `keymeta`, the label of an address and the HD key path of a public key
+//!
+//! Each private key is recovered if it matches its public key. Descriptor wallets (SQLite) are not supported.
+
+use crate::bitcoin::{BitcoinFormat, BitcoinNetwork, BitcoinPrivateKey};
+use crate::cli::{decrypt_cbc, mask, read_passphrase, CLIError, MaskSecrets};
+use crate::model::{crypto::checksum, PrivateKey};
+
+use base58::ToBase58;
+use colored::*;
+use core::{fmt, fmt::Display, str::FromStr};
+use crypto::{digest::Digest, sha2::Sha512};
+use serde::Serialize;
+use std::{collections::HashMap, fs};
+
+use crate::model::no_std::{format, vec, String, ToString, Vec};
+
+/// The magic number of the metadata page of a Berkeley DB B-tree
+const BTREE_MAGIC: u32 = 0x0005_3162;
+
+/// The page type of a leaf page of a Berkeley DB B-tree, and the item type of an item stored on its page
+const LEAF_PAGE: u8 = 5;
+const KEY_DATA_ITEM: u8 = 1;
+
+/// The size of the header of a page of a Berkeley DB B-tree, followed by the offsets of its items
+const PAGE_HEADER_SIZE: usize = 26;
+
+/// The prefix of the secret key of a DER private key of OpenSSL (version 1, followed by the 32-byte key)
+const DER_SECRET_KEY_PREFIX: [u8; 5] = [0x02, 0x01, 0x01, 0x04, 0x20];
+
+/// The key and data of a record of a Berkeley DB B-tree
+type WalletDatRecord = (Vec<u8>, Vec<u8>);
+
+/// Represents a key of a wallet.dat
+#[derive(Serialize, Clone, Debug)]
+pub struct WalletDatKey {
+    pub public_key: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub private_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    pub addresses: Vec<String>,
+    pub network: String,
+    /// Whether the private key is encrypted with the wallet passphrase
+    pub encrypted: bool,
+    /// Whether the private key is recovered, and matches the public key
+    pub recovered: bool,
+}
+
+/// Represents the master key of an encrypted wallet.dat
+struct WalletDatMasterKey {
+    encrypted_key: Vec<u8>,
+    salt: Vec<u8>,
+    derivation_method: u32,
+    iterations: u32,
+}
+
+/// Returns the keys of the wallet.dat at the given path on the given network, with their private keys
+/// decrypted with the given passphrase, or with a passphrase that is read if the keys are encrypted.
+pub fn read_wallet_dat<N: BitcoinNetwork>(path: &str, passphrase: Option<&str>) -> Result<Vec<WalletDatKey>, CLIError> {
+    let mut keys = vec![];
+    let mut master_keys = vec![];
+    let mut labels = HashMap::new();
+    let mut paths = HashMap::new();
+    for (key, value) in read_records(&fs::read(path)?)? {
+        let mut offset = 0;
+        let mut value_offset = 0;
+        let record = match read_bytes(&key, &mut offset) {
+            Some(record) => record,
+            None => continue,
+        };
+        match record {
+            b"key" | b"ckey" => {
+                let public_key = match read_bytes(&key, &mut offset) {
+                    Some(public_key) => public_key.to_vec(),
+                    None => continue,
+                };
+                let secret = read_bytes(&value, &mut value_offset).map(|secret| secret.to_vec());
+                keys.push((public_key, secret, record == b"ckey"));
+            }
+            b"mkey" => {
+                let encrypted_key = read_bytes(&value, &mut value_offset).map(|key| key.to_vec());
+                let salt = read_bytes(&value, &mut value_offset).map(|salt| salt.to_vec());
+                let derivation_method = read_u32(&value, &mut value_offset);
+                let iterations = read_u32(&value, &mut value_offset);
+                if let (Some(encrypted_key), Some(salt), Some(derivation_method), Some(iterations)) =
+                    (encrypted_key, salt, derivation_method, iterations)
+                {
+                    master_keys.push(WalletDatMasterKey {
+                        encrypted_key,
+                        salt,
+                        derivation_method,
+                        iterations,
+                    });
+                }
+            }
+            b"name" => {
+                if let (Some(address), Some(label)) =
+                    (read_bytes(&key, &mut offset), read_bytes(&value, &mut value_offset))
+                {
+                    labels.insert(
+                        String::from_utf8_lossy(address).to_string(),
+                        String::from_utf8_lossy(label).to_string(),
+                    );
+                }
+            }
+            // The metadata of a key has its HD key path from version 10
+            b"keymeta" => {
+                let public_key = read_bytes(&key, &mut offset);
+                let version = read_u32(&value, &mut value_offset);
+                value_offset += 8;
+                if let (Some(public_key), Some(10..=u32::MAX)) = (public_key, version) {
+                    if let Some(path) = read_bytes(&value, &mut value_offset).filter(|path| !path.is_empty()) {
+                        paths.insert(public_key.to_vec(), String::from_utf8_lossy(path).to_string());
+                    }
+                }
+            }
+            _ => {}
+        };
+    }
+
+    // The master key is the first that decrypts the first encrypted key into its public key
+    let master_key = match keys.iter().find(|(_, _, encrypted)| *encrypted) {
+        Some((public_key, Some(encrypted_secret), _)) => {
+            let passphrase = match passphrase {
+                Some(passphrase) => passphrase.to_string(),
+                None => read_passphrase(false)?,
+            };
+            let mut decrypted_master_key = None;
+            for master_key in &master_keys {
+                let key = decrypt_master_key(master_key, &passphrase)?;
+                if let Some(key) = key.filter(|key| decrypt_key::<N>(key, public_key, encrypted_secret).is_some()) {
+                    decrypted_master_key = Some(key);
+                    break;
+                }
+            }
+            Some(decrypted_master_key.ok_or(CLIError::InvalidPassphrase)?)
+        }
+        _ => None,
+    };
+
+    keys.iter()
+        .map(|(public_key, secret, encrypted)| {
+            let private_key = match (secret, encrypted, &master_key) {
+                (Some(secret), true, Some(master_key)) => decrypt_key::<N>(master_key, public_key, secret),
+                (Some(secret), false, _) => secret
+                    .windows(DER_SECRET_KEY_PREFIX.len())
+                    .position(|window| window == DER_SECRET_KEY_PREFIX)
+                    .and_then(|index| secret.get(index + 5..index + 37))
+                    .and_then(|secret| to_private_key::<N>(secret, public_key)),
+                _ => None,
+            };
+
+            // A compressed key has an address of each script type of a legacy wallet
+            let formats = match public_key.len() {
+                33 => vec![BitcoinFormat::P2PKH, BitcoinFormat::P2SH_P2WPKH, BitcoinFormat::Bech32],
+                _ => vec![BitcoinFormat::P2PKH],
+            };
+            let addresses = match &private_key {
+                Some(private_key) => formats
+                    .iter()
+                    .map(|format| Ok(private_key.to_address(format)?.to_string()))
+                    .collect::<Result<Vec<String>, CLIError>>()?,
+                None => vec![],
+            };
+
+            Ok(WalletDatKey {
+                public_key: hex::encode(public_key),
+                private_key: private_key.as_ref().map(|private_key| private_key.to_string()),
+                path: paths.get(public_key).cloned(),
+                label: addresses.iter().find_map(|address| labels.get(address).cloned()),
+                addresses,
+                network: N::NAME.into(),
+                encrypted: *encrypted,
+                recovered: private_key.is_some(),
+            })
+        })
+        .collect()
+}
+
+/// Returns the key and data of each record of the leaf pages of the given Berkeley DB B-tree.
+fn read_records(database: &[u8]) -> Result<Vec<WalletDatRecord>, CLIError> {
+    let mut offset = 12;
+    if read_u32(database, &mut offset) != Some(BTREE_MAGIC) {
+        return Err(CLIError::InvalidWalletDat("expected a Berkeley DB B-tree".into()));
+    }
+    let mut offset = 20;
+    let page_size = match read_u32(database, &mut offset) {
+        Some(page_size) if page_size.is_power_of_two() && (512..=65536).contains(&page_size) => page_size as usize,
+        _ => return Err(CLIError::InvalidWalletDat("invalid page size".into())),
+    };
+
+    let mut records = vec![];
+    for page in database.chunks_exact(page_size).skip(1) {
+        if page[25] != LEAF_PAGE {
+            continue;
+        }
+
+        // The items of a leaf page are the key and data of each record, in turn
+        let entries = u16::from_le_bytes([page[20], page[21]]) as usize;
+        let items = (0..entries)
+            .map(|index| {
+                let offset = page.get(PAGE_HEADER_SIZE + index * 2..PAGE_HEADER_SIZE + index * 2 + 2)?;
+                let offset = u16::from_le_bytes([offset[0], offset[1]]) as usize;
+                let length = u16::from_le_bytes([*page.get(offset)?, *page.get(offset + 1)?]) as usize;
+                match page.get(offset + 2)? & 0x7f {
+                    KEY_DATA_ITEM => page.get(offset + 3..offset + 3 + length).map(|item| item.to_vec()),
+                    _ => None,
+                }
+            })
+            .collect::<Vec<Option<Vec<u8>>>>();
+        for record in items.chunks_exact(2) {
+            if let [Some(key), Some(data)] = record {
+                records.push((key.clone(), data.clone()));
+            }
+        }
+    }
+    Ok(records)
+}
+
+/// Returns the given master key decrypted with the given passphrase, or `None` if its padding is invalid.
+fn decrypt_master_key(master_key: &WalletDatMasterKey, passphrase: &str) -> Result<Option<Vec<u8>>, CLIError> {
+    if master_key.derivation_method != 0 {
+        return Err(CLIError::UnsupportedEncryption(format!(
+            "wallet.dat derivation method {}",
+            master_key.derivation_method
+        )));
+    }
+
+    // The key and IV are the iterated SHA-512 hash of the passphrase and salt
+    let mut hash = [0u8; 64];
+    let mut sha512 = Sha512::new();
+    sha512.input(passphrase.as_bytes());
+    sha512.input(&master_key.salt);
+    sha512.result(&mut hash);
+    for _ in 1..master_key.iterations {
+        sha512.reset();
+        sha512.input(&hash);
+        sha512.result(&mut hash);
+    }
+    Ok(decrypt_cbc(&hash[..32], &hash[32..48], &master_key.encrypted_key).ok())
+}
+
+/// Returns the private key of the given public key, decrypted from the given encrypted secret with the given master key.
+fn decrypt_key<N: BitcoinNetwork>(
+    master_key: &[u8],
+    public_key: &[u8],
+    encrypted_secret: &[u8],
+) -> Option<BitcoinPrivateKey<N>> {
+    // The IV is the double SHA-256 hash of the public key
+    let iv = checksum(public_key);
+    let secret = decrypt_cbc(master_key, &iv[..16], encrypted_secret).ok()?;
+    to_private_key::<N>(&secret, public_key)
+}
+
+/// Returns the private key of the given secret key, if it matches the given public key.
+fn to_private_key<N: BitcoinNetwork>(secret_key: &[u8], public_key: &[u8]) -> Option<BitcoinPrivateKey<N>> {
+    if secret_key.len() != 32 {
+        return None;
+    }
+    let mut wif = vec![N::to_private_key_prefix()];
+    wif.extend_from_slice(secret_key);
+    if public_key.len() == 33 {
+        wif.push(0x01);
+    }
+    let checksum = checksum(&wif);
+    wif.extend_from_slice(&checksum[..4]);
+
+    let private_key = BitcoinPrivateKey::<N>::from_str(&wif.to_base58()).ok()?;
+    match private_key.to_public_key().to_string() == hex::encode(public_key) {
+        true => Some(private_key),
+        false => None,
+    }
+}
+
+/// Returns the length-prefixed bytes at the given offset of the given record, advancing the offset.
+fn read_bytes<'a>(record: &'a [u8], offset: &mut usize) -> Option<&'a [u8]> {
+    let start = *offset;
+    let (length, size) = match *record.get(start)? {
+        0xfd => {
+            let length = record.get(start + 1..start + 3)?;
+            (u16::from_le_bytes([length[0], length[1]]) as usize, 3)
+        }
+        0xfe => {
+            let length = record.get(start + 1..start + 5)?;
+            (
+                u32::from_le_bytes([length[0], length[1], length[2], length[3]]) as usize,
+                5,
+            )
+        }
+        length => (length as usize, 1),
+    };
+    let bytes = record.get(start + size..start + size + length)?;
+    *offset = start + size + length;
+    Some(bytes)
+}
+
+/// Returns the little-endian integer at the given offset of the given record, advancing the offset.
+fn read_u32(record: &[u8], offset: &mut usize) -> Option<u32> {
+    let bytes = record.get(*offset..*offset + 4)?;
+    *offset += 4;
+    Some(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+}
+
+impl MaskSecrets for WalletDatKey {
+    fn mask_secrets(&mut self) {
+        mask(&mut self.private_key);
+    }
+}
+
+#[cfg_attr(tarpaulin, skip)]
+impl Display for WalletDatKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let output = [
+            format!("      {}           {}\n", "Public Key".cyan().bold(), self.public_key),
+            match &self.private_key {
+                Some(private_key) => format!("      {}          {}\n", "Private Key".cyan().bold(), private_key),
+                _ => "".to_string(),
+            },
+            match &self.path {
+                Some(path) => format!("      {}                 {}\n", "Path".cyan().bold(), path),
+                _ => "".to_string(),
+            },
+            match &self.label {
+                Some(label) => format!("      {}                {}\n", "Label".cyan().bold(), label),
+                _ => "".to_string(),
+            },
+            match self.addresses.is_empty() {
+                true => "".to_string(),
+                false => format!(
+                    "      {}            {}\n",
+                    "Addresses".cyan().bold(),
+                    self.addresses.join(", ")
+                ),
+            },
+            format!("      {}              {}\n", "Network".cyan().bold(), self.network),
+            format!("      {}            {}\n", "Encrypted".cyan().bold(), self.encrypted),
+            format!("      {}            {}\n", "Recovered".cyan().bold(), self.recovered),
+        ]
+        .concat();
+
+        // Removes final new line character
+        let output = output[..output.len() - 1].to_string();
+        write!(f, "\n{}", output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bitcoin::Mainnet;
+
+    /// A wallet.dat of the uncompressed key of the WIF example of the Bitcoin wiki and of the compressed
+    /// master key of BIP-32 test vector 1, with the label of an address of each and the HD key path of the latter
+    const WALLET_DAT: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/wagyu/cli/fixtures/wallet.dat");
+
+    /// A wallet.dat of the compressed key, encrypted with the passphrase `wagyu`
+    const WALLET_DAT_ENCRYPTED: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/wagyu/cli/fixtures/wallet_encrypted.dat");
+
+    #[test]
+    fn read_fixture() {
+        let keys = read_wallet_dat::<Mainnet>(WALLET_DAT, None).unwrap();
+        assert_eq!(2, keys.len());
+
+        let key = keys.iter().find(|key| key.public_key.starts_with("04")).unwrap();
+        assert_eq!(
+            Some("5HueCGU8rMjxEXxiPuD5BDku4MkFqeZyd4dZ1jvhTVqvbTLvyTJ"),
+            key.private_key.as_deref()
+        );
+        assert_eq!(vec!["1GAehh7TsJAHuUAeKZcXf5CnwuGuGgyX2S"], key.addresses);
+        assert_eq!(Some("Wiki"), key.label.as_deref());
+        assert_eq!(None, key.path);
+        assert!(key.recovered && !key.encrypted);
+
+        let key = keys.iter().find(|key| !key.public_key.starts_with("04")).unwrap();
+        assert_eq!(
+            "0339a36013301597daef41fbe593a02cc513d0b55527ec2df1050e2e8ff49c85c2",
+            key.public_key
+        );
+        assert_eq!(
+            Some("L52XzL2cMkHxqxBXRyEpnPQZGUs3uKiL3R11XbAdHigRzDozKZeW"),
+            key.private_key.as_deref()
+        );
+        assert_eq!(
+            vec![
+                "15mKKb2eos1hWa6tisdPwwDC1a5J1y9nma",
+                "3PpgpssV7mcAGpZRWiCWhodUTnjpoSZg7a",
+                "bc1qx3ppj0smkuy3d6g525sh9n2w9k7fm7q3x30rtg"
+            ],
+            key.addresses
+        );
+        assert_eq!(Some("Savings"), key.label.as_deref());
+        assert_eq!(Some("m"), key.path.as_deref());
+        assert!(key.recovered && !key.encrypted);
+    }
+
+    #[test]
+    fn read_encrypted_fixture() {
+        let keys = read_wallet_dat::<Mainnet>(WALLET_DAT_ENCRYPTED, Some("wagyu")).unwrap();
+        assert_eq!(1, keys.len());
+        assert_eq!(
+            Some("L52XzL2cMkHxqxBXRyEpnPQZGUs3uKiL3R11XbAdHigRzDozKZeW"),
+            keys[0].private_key.as_deref()
+        );
+        assert_eq!(Some("Savings"), keys[0].label.as_deref());
+        assert_eq!(Some("m"), keys[0].path.as_deref());
+        assert!(keys[0].recovered && keys[0].encrypted);
+
+        match read_wallet_dat::<Mainnet>(WALLET_DAT_ENCRYPTED, Some("wagyu!")) {
+            Err(CLIError::InvalidPassphrase) => {}
+            _ => panic!("expected an invalid passphrase"),
+        };
+    }
+
+    #[test]
+    fn read_invalid() {
+        let path = concat!(env!("CARGO_MANIFEST_DIR"), "/wagyu/cli/fixtures/wallet_dump.txt");
+        match read_wallet_dat::<Mainnet>(path, None) {
+            Err(CLIError::InvalidWalletDat(_)) => {}
+            _ => panic!("expected an invalid wallet.dat"),
+        };
+    }
+}
@@ -0,0 +1,351 @@
+//! # Wallet Dump
+//!
+//! Reads the keys of a Bitcoin Core wallet dump, the output of `dumpwallet` of a legacy wallet, to audit
+//! or migrate them. Each line of a dump is a WIF private key (or a script in hex), its creation time, and
+//! its kind (a label, `hdseed`, `inactivehdseed`, `reserve`, `change`, or `script`), followed by a comment
+//! of its addresses and HD key path.
+//!
+//! The addresses of each key are recomputed from its private key, each key with an HD key path is
+//! rederived from the extended private master key of the dump, and the master key from the HD seed,
+//! so a key that does not match its addresses or its path is reported as invalid.
+
+use crate::bitcoin::{
+    BitcoinDerivationPath, BitcoinExtendedPrivateKey, BitcoinFormat, BitcoinNetwork, BitcoinPrivateKey,
+    Mainnet as BitcoinMainnet, Testnet as BitcoinTestnet,
+};
+use crate::cli::{mask, CLIError, MaskSecrets};
+use crate::model::{crypto::constant_time_eq, ExtendedPrivateKey, PrivateKey, PublicKey};
+
+use colored::*;
+use core::{fmt, fmt::Display, str::FromStr};
+use serde::Serialize;
+use std::fs;
+
+use crate::model::no_std::{format, vec, String, ToString, Vec};
+
+/// The kinds of the keys of a wallet dump, other than a labeled key
+const WALLET_DUMP_KINDS: [&str; 5] = ["change", "hdseed", "inactivehdseed", "reserve", "script"];
+
+/// The prefix of the line of the extended private master key of a wallet dump
+const MASTER_KEY_PREFIX: &str = "# extended private masterkey:";
+
+/// Represents a key or script of a wallet dump
+#[derive(Serialize, Clone, Debug)]
+pub struct WalletDumpKey {
+    /// The line of the key in the wallet dump
+    pub line: usize,
+    /// The kind of the key, as `label`, `hdseed`, or `masterkey` (for the extended private master key)
+    pub kind: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub path: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub private_key: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub script: Option<String>,
+    pub addresses: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub network: Option<String>,
+    /// Whether the key matches its addresses and path, if it is a key
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub valid: Option<bool>,
+}
+
+/// Returns the keys of the wallet dump at the given path, with the extended private master key first.
+pub fn read_wallet_dump(path: &str) -> Result<Vec<WalletDumpKey>, CLIError> {
+    let contents = fs::read_to_string(path)?;
+
+    let mut keys = vec![];
+    for (line, text) in contents
+        .lines()
+        .enumerate()
+        .map(|(index, text)| (index + 1, text.trim()))
+    {
+        if let Some(master_key) = text.strip_prefix(MASTER_KEY_PREFIX) {
+            keys.insert(
+                0,
+                WalletDumpKey {
+                    line,
+                    kind: "masterkey".into(),
+                    label: None,
+                    path: Some("m".into()),
+                    created: None,
+                    private_key: Some(master_key.trim().into()),
+                    script: None,
+                    addresses: vec![],
+                    network: None,
+                    valid: None,
+                },
+            );
+            continue;
+        }
+        if text.is_empty() || text.starts_with('#') {
+            continue;
+        }
+
+        let (fields, comment) = text.split_once('#').unwrap_or((text, ""));
+        let fields = fields.split_whitespace().collect::<Vec<&str>>();
+        let (kind, label) = match fields.get(2).and_then(|field| field.split_once('=')) {
+            Some(("label", label)) => ("label", Some(decode_label(label))),
+            Some((kind, "1")) if WALLET_DUMP_KINDS.contains(&kind) => (kind, None),
+            _ => return Err(CLIError::InvalidWalletDumpLine(line)),
+        };
+
+        let mut key = WalletDumpKey {
+            line,
+            kind: kind.into(),
+            label,
+            path: None,
+            created: fields.get(1).map(|created| created.to_string()),
+            private_key: None,
+            script: None,
+            addresses: vec![],
+            network: None,
+            valid: None,
+        };
+        match kind {
+            "script" => key.script = Some(fields[0].into()),
+            _ => key.private_key = Some(fields[0].into()),
+        };
+        for field in comment.split_whitespace() {
+            match field.split_once('=') {
+                Some(("addr", addresses)) => key.addresses = addresses.split(',').map(String::from).collect(),
+                Some(("hdkeypath", path)) => key.path = Some(path.into()),
+                _ => {}
+            }
+        }
+        keys.push(key);
+    }
+
+    // The keys are verified on the network of the first key
+    match keys
+        .iter()
+        .filter(|key| key.kind != "masterkey")
+        .find_map(|key| key.private_key.as_deref())
+        .map(BitcoinPrivateKey::<BitcoinMainnet>::from_str)
+    {
+        Some(Err(_)) => verify_keys::<BitcoinTestnet>(&mut keys)?,
+        _ => verify_keys::<BitcoinMainnet>(&mut keys)?,
+    };
+    Ok(keys)
+}
+
+/// Verifies the given keys of a wallet dump on the given network, against their addresses and paths.
+fn verify_keys<N: BitcoinNetwork>(keys: &mut [WalletDumpKey]) -> Result<(), CLIError> {
+    let master_key = match keys.first() {
+        Some(key) if key.kind == "masterkey" => Some(
+            BitcoinExtendedPrivateKey::<N>::from_str(key.private_key.as_deref().unwrap_or_default())
+                .map_err(|_| CLIError::InvalidWalletDumpLine(key.line))?,
+        ),
+        _ => None,
+    };
+
+    let mut master_key_valid = false;
+    for key in keys.iter_mut().filter(|key| key.kind != "masterkey") {
+        key.network = Some(N::NAME.into());
+        let private_key = match &key.private_key {
+            Some(private_key) => {
+                BitcoinPrivateKey::<N>::from_str(private_key).map_err(|_| CLIError::InvalidWalletDumpLine(key.line))?
+            }
+            None => continue,
+        };
+
+        // A compressed key has an address of each script type of a legacy wallet
+        let public_key = private_key.to_public_key();
+        let formats = match private_key.is_compressed() {
+            true => vec![BitcoinFormat::P2PKH, BitcoinFormat::P2SH_P2WPKH, BitcoinFormat::Bech32],
+            false => vec![BitcoinFormat::P2PKH],
+        };
+        let addresses = formats
+            .iter()
+            .map(|format| Ok(public_key.to_address(format)?.to_string()))
+            .collect::<Result<Vec<String>, CLIError>>()?;
+        let mut valid = key.addresses.iter().all(|address| addresses.contains(address));
+
+        // The master key is derived from the HD seed, and each HD key from the master key at its path
+        match (&master_key, key.path.as_deref(), key.kind.as_str()) {
+            (Some(master_key), Some(path), _) => {
+                let derived_key = master_key
+                    .derive(&BitcoinDerivationPath::<N>::from_str(path)?)?
+                    .to_private_key();
+                valid &= constant_time_eq(
+                    &derived_key.to_secp256k1_secret_key().serialize(),
+                    &private_key.to_secp256k1_secret_key().serialize(),
+                );
+            }
+            (Some(master_key), None, "hdseed") => {
+                let seed = private_key.to_secp256k1_secret_key().serialize();
+                let seed_master_key = BitcoinExtendedPrivateKey::<N>::new_master(&seed, &master_key.format())?;
+                master_key_valid = seed_master_key == *master_key;
+            }
+            _ => {}
+        };
+        key.valid = Some(valid);
+    }
+
+    if let Some(key) = keys.first_mut().filter(|key| key.kind == "masterkey") {
+        key.network = Some(N::NAME.into());
+        key.valid = Some(master_key_valid);
+    }
+    Ok(())
+}
+
+/// Returns the given label of a wallet dump, with its percent-encoded characters decoded.
+fn decode_label(label: &str) -> String {
+    let mut bytes = Vec::with_capacity(label.len());
+    let mut encoded = label.bytes();
+    while let Some(byte) = encoded.next() {
+        let decoded = match byte {
+            b'%' => {
+                let hex = [encoded.next().unwrap_or_default(), encoded.next().unwrap_or_default()];
+                hex::decode(hex).ok().map(|decoded| decoded[0])
+            }
+            _ => None,
+        };
+        bytes.push(decoded.unwrap_or(byte));
+    }
+    String::from_utf8_lossy(&bytes).to_string()
+}
+
+impl MaskSecrets for WalletDumpKey {
+    fn mask_secrets(&mut self) {
+        mask(&mut self.private_key);
+    }
+}
+
+#[cfg_attr(tarpaulin, skip)]
+impl Display for WalletDumpKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let output = [
+            format!("      {}                 {}\n", "Kind".cyan().bold(), self.kind),
+            match &self.label {
+                Some(label) => format!("      {}                {}\n", "Label".cyan().bold(), label),
+                _ => "".to_string(),
+            },
+            match &self.path {
+                Some(path) => format!("      {}                 {}\n", "Path".cyan().bold(), path),
+                _ => "".to_string(),
+            },
+            match &self.created {
+                Some(created) => format!("      {}              {}\n", "Created".cyan().bold(), created),
+                _ => "".to_string(),
+            },
+            match &self.private_key {
+                Some(private_key) => format!("      {}          {}\n", "Private Key".cyan().bold(), private_key),
+                _ => "".to_string(),
+            },
+            match &self.script {
+                Some(script) => format!("      {}               {}\n", "Script".cyan().bold(), script),
+                _ => "".to_string(),
+            },
+            match self.addresses.is_empty() {
+                true => "".to_string(),
+                false => format!(
+                    "      {}            {}\n",
+                    "Addresses".cyan().bold(),
+                    self.addresses.join(", ")
+                ),
+            },
+            match &self.network {
+                Some(network) => format!("      {}              {}\n", "Network".cyan().bold(), network),
+                _ => "".to_string(),
+            },
+            match self.valid {
+                Some(valid) => format!("      {}                {}\n", "Valid".cyan().bold(), valid),
+                _ => "".to_string(),
+            },
+        ]
+        .concat();
+
+        // Removes final new line character
+        let output = output[..output.len() - 1].to_string();
+        write!(f, "\n{}", output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A `dumpwallet` of a legacy wallet with the HD seed of BIP-32 test vector 4, with its labeled,
+    /// reserve, and change keys, an imported key with a percent-encoded label, and a P2SH-P2WPKH script
+    const WALLET_DUMP: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/wagyu/cli/fixtures/wallet_dump.txt");
+
+    /// Returns the keys of the fixture with the given replacement, read from a temporary file.
+    fn read_modified_wallet_dump(from: &str, to: &str) -> Result<Vec<WalletDumpKey>, CLIError> {
+        let contents = fs::read_to_string(WALLET_DUMP).unwrap();
+        assert!(contents.contains(from));
+        let path = std::env::temp_dir().join(format!("wagyu_wallet_dump_{}.txt", std::process::id()));
+        fs::write(&path, contents.replacen(from, to, 1)).unwrap();
+        let keys = read_wallet_dump(path.to_str().unwrap());
+        fs::remove_file(&path).unwrap();
+        keys
+    }
+
+    #[test]
+    fn read_fixture() {
+        let keys = read_wallet_dump(WALLET_DUMP).unwrap();
+        let summary = keys
+            .iter()
+            .map(|key| {
+                (
+                    key.line,
+                    key.kind.as_str(),
+                    key.label.as_deref(),
+                    key.path.as_deref(),
+                    key.valid,
+                )
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                (6, "masterkey", None, Some("m"), Some(true)),
+                (8, "hdseed", None, None, Some(true)),
+                (9, "label", Some("Savings account"), Some("m/0'/0'/0'"), Some(true)),
+                (10, "label", Some(""), Some("m/0'/0'/1'"), Some(true)),
+                (11, "reserve", None, Some("m/0'/0'/2'"), Some(true)),
+                (12, "change", None, Some("m/0'/1'/0'"), Some(true)),
+                (13, "label", Some("₿ cold"), None, Some(true)),
+                (14, "script", None, None, None),
+            ],
+            summary
+        );
+
+        assert!(keys.iter().all(|key| key.network.as_deref() == Some("mainnet")));
+        assert_eq!(Some("2021-05-01T09:00:00Z"), keys[2].created.as_deref());
+        assert_eq!(
+            vec![
+                "1EJYvrcC7jU55BkSE5WGJerKdHBnS9FC6x",
+                "3FSiRH2w8n7w53C6bEQrdY5F61ZY5ukAQ3",
+                "bc1qj844hu9m620g9d4ylsxn2nmnrutlmvt05ua07n"
+            ],
+            keys[2].addresses
+        );
+        assert_eq!(
+            Some("001491eb5bf0bbd29e82b6a4fc0d354f731f17fdb16f"),
+            keys[7].script.as_deref()
+        );
+    }
+
+    #[test]
+    fn read_modified_fixture() {
+        // A key at another path, and a master key of another seed (BIP-32 test vector 1)
+        let keys = read_modified_wallet_dump("hdkeypath=m/0'/0'/1'", "hdkeypath=m/0'/0'/3'").unwrap();
+        assert_eq!(Some(false), keys[3].valid);
+        let keys = read_modified_wallet_dump(
+            "xprv9s21ZrQH143K48vGoLGRPxgo2JNkJ3J3fqkirQC2zVdk5Dgd5w14S7fRDyHH4dWNHUgkvsvNDCkvAwcSHNAQwhwgNMgZhLtQC63zxwhQmRv",
+            "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi",
+        )
+        .unwrap();
+        assert_eq!(Some(false), keys[0].valid);
+        assert_eq!(Some(false), keys[2].valid);
+
+        match read_modified_wallet_dump("reserve=1", "reserved") {
+            Err(CLIError::InvalidWalletDumpLine(11)) => {}
+            _ => panic!("expected an invalid line"),
+        };
+    }
+}
@@ -0,0 +1,294 @@
+//! # Wallet File
+//!
+//! A versioned file format for saving generated wallets, with optional encryption.
+//! Encrypted wallet files derive a 256-bit key from a passphrase with scrypt,
+//! and encrypt the wallets with AES-256-GCM, authenticating the file version and coin.
+//! The key is derived with scrypt rather than Argon2, as no Argon2 implementation is a
+//! dependency of wagyu. The scrypt parameters of a file are read from the file, and are
+//! bounded so that a malformed file cannot exhaust the memory of the key derivation.
+//!
+//! The passphrase is read from the `WAGYU_PASSPHRASE` environment variable if set,
+//! and otherwise prompted for on the terminal.
+
+use crate::cli::{object, prompt, property, CLIError, Schema};
+use crate::model::crypto::constant_time_eq;
+
+use core::ops::RangeInclusive;
+use crypto::{
+    aead::{AeadDecryptor, AeadEncryptor},
+    aes::KeySize,
+    aes_gcm::AesGcm,
+    scrypt::{scrypt, ScryptParams},
+};
+use rand::{rngs::StdRng, Rng};
+use rand_core::SeedableRng;
+use serde::{Deserialize, Serialize};
+
+use crate::model::no_std::{format, vec, String, ToString, Vec};
+
+/// The current version of the wallet file schema
+pub const WALLET_FILE_VERSION: u32 = 1;
+
+/// The environment variable to read the wallet file passphrase from
+pub const WALLET_FILE_PASSPHRASE: &str = "WAGYU_PASSPHRASE";
+
+/// The scrypt cost parameters of newly encrypted wallet files
+const SCRYPT_LOG_N: u8 = 15;
+const SCRYPT_R: u32 = 8;
+const SCRYPT_P: u32 = 1;
+
+/// The ranges of the scrypt parameters of an encrypted file, bounding its memory (128 * r * 2^log_n bytes) to 2 GiB
+const SCRYPT_LOG_N_RANGE: RangeInclusive<u8> = 1..=20;
+const SCRYPT_R_RANGE: RangeInclusive<u32> = 1..=16;
+const SCRYPT_P_RANGE: RangeInclusive<u32> = 1..=16;
+
+/// Represents a saved wallet file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalletFile {
+    /// The version of the file schema
+    pub version: u32,
+    /// The coin of the wallets
+    pub coin: String,
+    /// The plaintext wallets, if the file is not encrypted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wallets: Option<serde_json::Value>,
+    /// The encrypted wallets, if the file is encrypted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub encryption: Option<WalletEncryption>,
+}
+
+/// Represents the encrypted wallets of a wallet file
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalletEncryption {
+    pub cipher: String,
+    pub kdf: String,
+    pub log_n: u8,
+    pub r: u32,
+    pub p: u32,
+    pub salt: String,
+    pub nonce: String,
+    pub ciphertext: String,
+    pub tag: String,
+}
+
+impl Schema for WalletFile {
+    fn schema() -> serde_json::Value {
+        let encryption = object(
+            vec![
+                ("cipher", property("string", "The cipher of the encrypted wallets")),
+                (
+                    "kdf",
+                    property("string", "The key derivation function of the passphrase"),
+                ),
+                ("log_n", property("integer", "The scrypt cost parameter log2(N)")),
+                ("r", property("integer", "The scrypt block size parameter")),
+                ("p", property("integer", "The scrypt parallelization parameter")),
+                ("salt", property("string", "The scrypt salt in hex")),
+                ("nonce", property("string", "The cipher nonce in hex")),
+                ("ciphertext", property("string", "The encrypted wallets in hex")),
+                ("tag", property("string", "The authentication tag in hex")),
+            ],
+            &["cipher", "kdf", "log_n", "r", "p", "salt", "nonce", "ciphertext", "tag"],
+        );
+
+        object(
+            vec![
+                ("version", property("integer", "The version of the file schema")),
+                ("coin", property("string", "The coin of the wallets")),
+                (
+                    "wallets",
+                    property("array", "The plaintext wallets, if the file is not encrypted"),
+                ),
+                ("encryption", encryption),
+            ],
+            &["version", "coin"],
+        )
+    }
+}
+
+impl WalletFile {
+    /// Returns a new plaintext wallet file of the given wallets of the given coin.
+    pub fn new<T: Serialize>(wallets: &T, coin: &str) -> Result<Self, CLIError> {
+        Ok(Self {
+            version: WALLET_FILE_VERSION,
+            coin: coin.to_string(),
+            wallets: Some(serde_json::to_value(wallets)?),
+            encryption: None,
+        })
+    }
+
+    /// Writes the given wallets of the given coin to the given path,
+    /// encrypted with a passphrase if `encrypt` is set.
+    pub fn save<T: Serialize>(wallets: &T, coin: &str, path: &str, encrypt: bool) -> Result<(), CLIError> {
+        let file = match encrypt {
+            true => {
+                let passphrase = read_passphrase(true)?;
+                let rng = &mut StdRng::from_entropy();
+                let salt: [u8; 32] = rng.gen();
+                let nonce: [u8; 12] = rng.gen();
+
+                let key = derive_key(&passphrase, &salt, SCRYPT_LOG_N, SCRYPT_R, SCRYPT_P)?;
+                let plaintext = serde_json::to_vec(wallets)?;
+                let mut ciphertext = vec![0u8; plaintext.len()];
+                let mut tag = [0u8; 16];
+                AesGcm::new(KeySize::KeySize256, &key, &nonce, to_aad(coin).as_bytes()).encrypt(
+                    &plaintext,
+                    &mut ciphertext,
+                    &mut tag,
+                );
+
+                Self {
+                    version: WALLET_FILE_VERSION,
+                    coin: coin.to_string(),
+                    wallets: None,
+                    encryption: Some(WalletEncryption {
+                        cipher: "aes-256-gcm".into(),
+                        kdf: "scrypt".into(),
+                        log_n: SCRYPT_LOG_N,
+                        r: SCRYPT_R,
+                        p: SCRYPT_P,
+                        salt: hex::encode(salt),
+                        nonce: hex::encode(nonce),
+                        ciphertext: hex::encode(ciphertext),
+                        tag: hex::encode(tag),
+                    }),
+                }
+            }
+            false => Self::new(wallets, coin)?,
+        };
+
+        Ok(std::fs::write(path, serde_json::to_string_pretty(&file)?)?)
+    }
+
+    /// Returns the coin and wallets of the wallet file at the given path,
+    /// prompting for the passphrase if the file is encrypted.
+    pub fn open(path: &str) -> Result<(String, serde_json::Value), CLIError> {
+        let file: Self = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+        if file.version != WALLET_FILE_VERSION {
+            return Err(CLIError::UnsupportedFileVersion(file.version));
+        }
+
+        match (file.wallets, file.encryption) {
+            (Some(wallets), None) => Ok((file.coin, wallets)),
+            (None, Some(encryption)) => {
+                if encryption.cipher != "aes-256-gcm" || encryption.kdf != "scrypt" {
+                    return Err(CLIError::UnsupportedEncryption(format!(
+                        "{}, {}",
+                        encryption.cipher, encryption.kdf
+                    )));
+                }
+
+                let salt = hex::decode(&encryption.salt)?;
+                let nonce = hex::decode(&encryption.nonce)?;
+                let ciphertext = hex::decode(&encryption.ciphertext)?;
+                let tag = hex::decode(&encryption.tag)?;
+                if nonce.len() != 12 || tag.len() != 16 {
+                    return Err(CLIError::InvalidWalletFile);
+                }
+
+                // The parameters of the file are checked before the passphrase is prompted for
+                to_scrypt_params(encryption.log_n, encryption.r, encryption.p)?;
+                let passphrase = read_passphrase(false)?;
+                let key = derive_key(&passphrase, &salt, encryption.log_n, encryption.r, encryption.p)?;
+                let mut plaintext = vec![0u8; ciphertext.len()];
+                match AesGcm::new(KeySize::KeySize256, &key, &nonce, to_aad(&file.coin).as_bytes()).decrypt(
+                    &ciphertext,
+                    &mut plaintext,
+                    &tag,
+                ) {
+                    true => Ok((file.coin, serde_json::from_slice(&plaintext)?)),
+                    false => Err(CLIError::InvalidPassphrase),
+                }
+            }
+            _ => Err(CLIError::InvalidWalletFile),
+        }
+    }
+}
+
+/// Returns the additional authenticated data of an encrypted wallet file for the given coin.
+fn to_aad(coin: &str) -> String {
+    format!("wagyu-wallet-v{}:{}", WALLET_FILE_VERSION, coin)
+}
+
+/// Returns the 256-bit key derived from the given passphrase and salt with scrypt.
+fn derive_key(passphrase: &str, salt: &[u8], log_n: u8, r: u32, p: u32) -> Result<Vec<u8>, CLIError> {
+    let mut key = vec![0u8; 32];
+    scrypt(passphrase.as_bytes(), salt, &to_scrypt_params(log_n, r, p)?, &mut key);
+    Ok(key)
+}
+
+/// Returns the scrypt parameters of the given cost parameters of an encrypted file,
+/// if they are in the supported ranges.
+pub(crate) fn to_scrypt_params(log_n: u8, r: u32, p: u32) -> Result<ScryptParams, CLIError> {
+    // The cost parameter N is less than 2^(16 * r) (RFC 7914)
+    match SCRYPT_LOG_N_RANGE.contains(&log_n)
+        && SCRYPT_R_RANGE.contains(&r)
+        && SCRYPT_P_RANGE.contains(&p)
+        && u32::from(log_n) < r * 16
+    {
+        true => Ok(ScryptParams::new(log_n, r, p)),
+        false => Err(CLIError::InvalidKdfParameters(format!(
+            "scrypt log_n = {}, r = {}, p = {} (expected log_n of {} to {} and below 16 * r, r of {} to {}, and p of {} to {})",
+            log_n,
+            r,
+            p,
+            SCRYPT_LOG_N_RANGE.start(),
+            SCRYPT_LOG_N_RANGE.end(),
+            SCRYPT_R_RANGE.start(),
+            SCRYPT_R_RANGE.end(),
+            SCRYPT_P_RANGE.start(),
+            SCRYPT_P_RANGE.end()
+        ))),
+    }
+}
+
+/// Returns the wallet file passphrase from the environment, or from the terminal.
+/// If `confirm` is set, the passphrase is prompted for twice.
+pub(crate) fn read_passphrase(confirm: bool) -> Result<String, CLIError> {
+    if let Ok(passphrase) = std::env::var(WALLET_FILE_PASSPHRASE) {
+        return match passphrase.is_empty() {
+            true => Err(CLIError::MissingPassphrase),
+            false => Ok(passphrase),
+        };
+    }
+
+    let passphrase = prompt("Enter passphrase: ")?;
+    if passphrase.is_empty() {
+        return Err(CLIError::MissingPassphrase);
+    }
+    if confirm && !constant_time_eq(prompt("Confirm passphrase: ")?.as_bytes(), passphrase.as_bytes()) {
+        return Err(CLIError::MismatchedPassphrase);
+    }
+    Ok(passphrase)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scrypt_params() {
+        assert!(to_scrypt_params(SCRYPT_LOG_N, SCRYPT_R, SCRYPT_P).is_ok());
+        assert!(to_scrypt_params(1, 1, 1).is_ok());
+        assert!(to_scrypt_params(15, 1, 1).is_ok());
+        assert!(to_scrypt_params(20, 16, 16).is_ok());
+    }
+
+    #[test]
+    fn test_invalid_scrypt_params() {
+        for (log_n, r, p) in [
+            (0, 8, 1),
+            (15, 0, 1),
+            (15, 8, 0),
+            (21, 8, 1),
+            (15, 17, 1),
+            (15, 8, 17),
+            (16, 1, 1),
+        ] {
+            match to_scrypt_params(log_n, r, p) {
+                Err(CLIError::InvalidKdfParameters(_)) => {}
+                _ => panic!("expected invalid scrypt parameters: {}, {}, {}", log_n, r, p),
+            }
+        }
+    }
+}
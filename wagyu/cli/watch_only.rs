@@ -0,0 +1,264 @@
+//! # Watch-only
+//!
+//! Exports the account extended public key of an HD wallet as an import-ready watch-only wallet file
+//! of the following wallet software, with the master fingerprint and account path of the key:
+//!
+//! - `bluewallet`, the JSON of an account key as exported by Cobo Vault
+//! - `electrum`, an Electrum wallet file with a BIP-32 keystore of the SLIP-132 extended public key
+//! - `sparrow`, the output descriptors of the account (BIP-389, and the receive and change descriptors)
+//! - `specter`, the JSON of a Specter Desktop wallet, with the descriptor of its receive chain
+//!
+//! The script type of the wallet is the format of the key: `pkh` (legacy), `sh(wpkh)` (segwit),
+//! `wpkh` (bech32), or `tr` (taproot), which Electrum and BlueWallet do not support.
+
+use crate::bitcoin::{BitcoinExtendedPublicKey, BitcoinFormat, BitcoinNetwork};
+use crate::cli::{CLIError, MaskSecrets};
+
+use colored::*;
+use core::{fmt, fmt::Display};
+use serde::Serialize;
+use serde_json::json;
+
+use crate::model::no_std::{format, String, ToString, Vec};
+
+/// The character sets of the input and checksum of output descriptors (BIP-380)
+const DESCRIPTOR_INPUT_CHARSET: &str =
+    "0123456789()[],'/*abcdefgh@:$%{}IJKLMNOPQRSTUVWXYZ&+-.;<=>?!^_|~ijklmnopqrstuvwxyzABCDEFGH`#\"\\ ";
+const DESCRIPTOR_CHECKSUM_CHARSET: &[u8] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+/// Represents the watch-only wallet file of an account of an HD wallet
+#[derive(Serialize, Clone, Debug)]
+pub struct WatchOnlyWallet {
+    /// The wallet software of the file
+    pub wallet: String,
+    pub master_fingerprint: String,
+    pub account_path: String,
+    /// The account extended public key, with the SLIP-132 version bytes of its format
+    pub extended_public_key: String,
+    /// The descriptor of the receive chain of the account
+    pub descriptor: String,
+    /// The contents of the file
+    #[serde(skip)]
+    pub contents: String,
+}
+
+impl WatchOnlyWallet {
+    /// Returns the watch-only wallet file of the given wallet software, of the given account extended
+    /// public key at the given account path of the master key of the given fingerprint.
+    pub fn new<N: BitcoinNetwork>(
+        wallet: &str,
+        master_fingerprint: &[u8; 4],
+        account_path: &str,
+        account_key: &BitcoinExtendedPublicKey<N>,
+    ) -> Result<Self, CLIError> {
+        let master_fingerprint = hex::encode(master_fingerprint);
+        let format = account_key.format();
+        let extended_public_key = account_key.to_string();
+
+        // Descriptors take the key with its standard version bytes, after its origin in hardened `h` notation
+        let origin = format!(
+            "[{}{}]{}",
+            master_fingerprint,
+            account_path.trim_start_matches('m').replace('\'', "h"),
+            account_key.with_format(&BitcoinFormat::P2PKH)
+        );
+        let descriptor = |chain: &str| {
+            to_descriptor(&match format {
+                BitcoinFormat::P2SH_P2WPKH => format!("sh(wpkh({}/{}/*))", origin, chain),
+                BitcoinFormat::Bech32 => format!("wpkh({}/{}/*)", origin, chain),
+                BitcoinFormat::P2TR => format!("tr({}/{}/*)", origin, chain),
+                _ => format!("pkh({}/{}/*)", origin, chain),
+            })
+        };
+
+        let unsupported = || CLIError::UnsupportedWatchOnlyFormat(wallet.into(), format.to_string());
+        let contents = match wallet {
+            "bluewallet" => match format {
+                BitcoinFormat::P2TR => return Err(unsupported()),
+                _ => serde_json::to_string_pretty(&json!({
+                    "ExtPubKey": extended_public_key,
+                    "MasterFingerprint": master_fingerprint.to_uppercase(),
+                    "AccountKeyPath": account_path.trim_start_matches("m/"),
+                }))?,
+            },
+            "electrum" => match format {
+                BitcoinFormat::P2TR => return Err(unsupported()),
+                _ => serde_json::to_string_pretty(&json!({
+                    "keystore": {
+                        "type": "bip32",
+                        "xpub": extended_public_key,
+                        "root_fingerprint": master_fingerprint,
+                        "derivation": account_path,
+                        "label": "wagyu",
+                    },
+                    "wallet_type": "standard",
+                    "use_encryption": false,
+                    "seed_version": 17,
+                }))?,
+            },
+            "sparrow" => format!(
+                "# Receive and change descriptor (BIP389):\n{}\n\n# Receive descriptor (Bitcoin Core):\n{}\n\n# Change descriptor (Bitcoin Core):\n{}",
+                descriptor("<0;1>"),
+                descriptor("0"),
+                descriptor("1")
+            ),
+            "specter" => serde_json::to_string_pretty(&json!({
+                "label": "wagyu",
+                "blockheight": 0,
+                "descriptor": descriptor("0"),
+                "devices": [{ "type": "other", "label": "wagyu" }],
+            }))?,
+            _ => return Err(CLIError::UnknownWatchOnlyWallet(wallet.into())),
+        };
+
+        Ok(Self {
+            wallet: wallet.into(),
+            master_fingerprint,
+            account_path: account_path.into(),
+            extended_public_key,
+            descriptor: descriptor("0"),
+            contents: format!("{}\n", contents),
+        })
+    }
+}
+
+/// Returns the given output descriptor, followed by its checksum (BIP-380).
+pub fn to_descriptor(descriptor: &str) -> String {
+    let polymod = |symbols: &[u64]| {
+        symbols.iter().fold(1u64, |checksum, value| {
+            let top = checksum >> 35;
+            [0xf5dee51989, 0xa9fdca3312, 0x1bab10e32d, 0x3706b1677a, 0x644d626ffd]
+                .iter()
+                .enumerate()
+                .filter(|(i, _)| (top >> i) & 1 == 1)
+                .fold(((checksum & 0x7ffffffff) << 5) ^ value, |checksum, (_, generator)| {
+                    checksum ^ generator
+                })
+        })
+    };
+
+    // Each character is a symbol of its position in its group of 32, and the groups of each 3 characters a symbol
+    let mut symbols = Vec::new();
+    let mut groups = Vec::new();
+    for c in descriptor.chars() {
+        let position = DESCRIPTOR_INPUT_CHARSET.find(c).unwrap_or_default() as u64;
+        symbols.push(position & 31);
+        groups.push(position >> 5);
+        if groups.len() == 3 {
+            symbols.push(groups[0] * 9 + groups[1] * 3 + groups[2]);
+            groups.clear();
+        }
+    }
+    match groups.len() {
+        1 => symbols.push(groups[0]),
+        2 => symbols.push(groups[0] * 3 + groups[1]),
+        _ => {}
+    };
+    symbols.extend_from_slice(&[0; 8]);
+
+    let checksum = polymod(&symbols) ^ 1;
+    let checksum = (0..8)
+        .map(|i| DESCRIPTOR_CHECKSUM_CHARSET[((checksum >> (5 * (7 - i))) & 31) as usize] as char)
+        .collect::<String>();
+    format!("{}#{}", descriptor, checksum)
+}
+
+impl MaskSecrets for WatchOnlyWallet {
+    fn mask_secrets(&mut self) {}
+}
+
+#[cfg_attr(tarpaulin, skip)]
+impl Display for WatchOnlyWallet {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let output = [
+            format!("      {}               {}\n", "Wallet".cyan().bold(), self.wallet),
+            format!(
+                "      {}   {}\n",
+                "Master Fingerprint".cyan().bold(),
+                self.master_fingerprint
+            ),
+            format!("      {}              {}\n", "Account".cyan().bold(), self.account_path),
+            format!(
+                "      {}  {}\n",
+                "Extended Public Key".cyan().bold(),
+                self.extended_public_key
+            ),
+            format!("      {}           {}\n", "Descriptor".cyan().bold(), self.descriptor),
+        ]
+        .concat();
+
+        // Removes final new line character
+        let output = output[..output.len() - 1].to_string();
+        write!(f, "\n{}", output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bitcoin::Mainnet;
+    use core::str::FromStr;
+
+    // The BIP-84 account key of the mnemonic of `abandon` 11 times and `about`
+    const ZPUB: &str =
+        "zpub6rFR7y4Q2AijBEqTUquhVz398htDFrtymD9xYYfG1m4wAcvPhXNfE3EfH1r1ADqtfSdVCToUG868RvUUkgDKf31mGDtKsAYz2oz2AGutZYs";
+    const XPUB: &str =
+        "xpub6CatWdiZiodmUeTDp8LT5or8nmbKNcuyvz7WyksVFkKB4RHwCD3XyuvPEbvqAQY3rAPshWcMLoP2fMFMKHPJ4ZeZXYVUhLv1VMrjPC7PW6V";
+
+    fn account_key() -> BitcoinExtendedPublicKey<Mainnet> {
+        BitcoinExtendedPublicKey::from_str(ZPUB).unwrap()
+    }
+
+    #[test]
+    fn descriptor_checksum() {
+        // The test vectors of BIP-380
+        assert_eq!("raw(deadbeef)#89f8spxm", to_descriptor("raw(deadbeef)"));
+        assert_eq!(
+            "addr(mkmZxiEcEd8ZqjQWVZuC6so5dFMKEFpN2j)#02wpgw69",
+            to_descriptor("addr(mkmZxiEcEd8ZqjQWVZuC6so5dFMKEFpN2j)")
+        );
+    }
+
+    #[test]
+    fn sparrow() {
+        let wallet = WatchOnlyWallet::new("sparrow", &[0x73, 0xc5, 0xda, 0x0a], "m/84'/0'/0'", &account_key()).unwrap();
+        let descriptor =
+            |chain: &str, checksum: &str| format!("wpkh([73c5da0a/84h/0h/0h]{}/{}/*)#{}", XPUB, chain, checksum);
+        assert_eq!(descriptor("0", "afwvtk2s"), wallet.descriptor);
+        assert!(wallet.contents.contains(&descriptor("<0;1>", "qf45pmyh")));
+        assert!(wallet.contents.contains(&descriptor("1", "vatdkr6g")));
+    }
+
+    #[test]
+    fn json_wallets() {
+        let fingerprint = [0x73, 0xc5, 0xda, 0x0a];
+        let wallet = WatchOnlyWallet::new("electrum", &fingerprint, "m/84'/0'/0'", &account_key()).unwrap();
+        let contents: serde_json::Value = serde_json::from_str(&wallet.contents).unwrap();
+        assert_eq!(ZPUB, contents["keystore"]["xpub"]);
+        assert_eq!("73c5da0a", contents["keystore"]["root_fingerprint"]);
+        assert_eq!("m/84'/0'/0'", contents["keystore"]["derivation"]);
+
+        let wallet = WatchOnlyWallet::new("bluewallet", &fingerprint, "m/84'/0'/0'", &account_key()).unwrap();
+        let contents: serde_json::Value = serde_json::from_str(&wallet.contents).unwrap();
+        assert_eq!(ZPUB, contents["ExtPubKey"]);
+        assert_eq!("73C5DA0A", contents["MasterFingerprint"]);
+        assert_eq!("84'/0'/0'", contents["AccountKeyPath"]);
+
+        let wallet = WatchOnlyWallet::new("specter", &fingerprint, "m/84'/0'/0'", &account_key()).unwrap();
+        let contents: serde_json::Value = serde_json::from_str(&wallet.contents).unwrap();
+        assert_eq!(wallet.descriptor, contents["descriptor"]);
+    }
+
+    #[test]
+    fn unsupported() {
+        let taproot = account_key().with_format(&BitcoinFormat::P2TR);
+        match WatchOnlyWallet::new("electrum", &[0; 4], "m/86'/0'/0'", &taproot) {
+            Err(CLIError::UnsupportedWatchOnlyFormat(..)) => {}
+            result => panic!("unexpected result {:?}", result),
+        }
+        match WatchOnlyWallet::new("wasabi", &[0; 4], "m/84'/0'/0'", &account_key()) {
+            Err(CLIError::UnknownWatchOnlyWallet(_)) => {}
+            result => panic!("unexpected result {:?}", result),
+        }
+    }
+}
@@ -0,0 +1,103 @@
+//! # Wordlist
+//!
+//! Introspection of the BIP-39 wordlists, which prints a wordlist, the word of an index, or the words
+//! starting with a prefix, each with its index. This helps to reconstruct a mnemonic from partial notes,
+//! such as the first four letters of each word (which identify a word of the English wordlist).
+
+use crate::cli::{CLIError, MaskSecrets};
+use crate::model::{bip39, utilities::unicode::nfkd, wordlist::WordlistError, MnemonicError};
+
+use colored::*;
+use core::{fmt, fmt::Display};
+use serde::Serialize;
+
+use crate::model::no_std::{format, String, ToString, Vec};
+
+/// Represents a word of a wordlist
+#[derive(Serialize, Clone, Debug)]
+pub struct WordlistEntry {
+    /// The index of the word in its wordlist
+    pub index: usize,
+    /// The word
+    pub word: String,
+}
+
+/// Represents the words of a BIP-39 wordlist, or those of an index or prefix
+#[derive(Serialize, Clone, Debug)]
+pub struct WordlistLookup {
+    /// The language of the wordlist
+    pub language: String,
+    /// The words, in the order of the wordlist
+    pub words: Vec<WordlistEntry>,
+}
+
+impl WordlistLookup {
+    /// Returns the words of the BIP-39 wordlist of the given language, or only the word of the given
+    /// index, or only the words starting with the given prefix.
+    pub fn new(language: &str, index: Option<usize>, prefix: Option<&str>) -> Result<Self, CLIError> {
+        let wordlist = match language {
+            "chinese_simplified" => bip39::CHINESE_SIMPLIFIED,
+            "chinese_traditional" => bip39::CHINESE_TRADITIONAL,
+            "english" => bip39::ENGLISH,
+            "french" => bip39::FRENCH,
+            "italian" => bip39::ITALIAN,
+            "japanese" => bip39::JAPANESE,
+            "korean" => bip39::KOREAN,
+            "spanish" => bip39::SPANISH,
+            _ => return Err(CLIError::UnsupportedLanguage),
+        };
+        let mut words = wordlist.lines().enumerate().map(|(index, word)| WordlistEntry {
+            index,
+            word: word.into(),
+        });
+
+        let words = match (index, prefix) {
+            (Some(index), _) => match words.nth(index) {
+                Some(word) => vec![word],
+                None => return Err(MnemonicError::from(WordlistError::InvalidIndex(index)).into()),
+            },
+            (None, Some(prefix)) => {
+                // The prefix is decomposed as the words are, to match the composed kana of an input method
+                let prefix = nfkd(&prefix.trim().to_lowercase());
+                let words = words
+                    .filter(|entry| entry.word.starts_with(&prefix))
+                    .collect::<Vec<WordlistEntry>>();
+                if words.is_empty() {
+                    return Err(CLIError::InvalidWordPrefix(language.into(), prefix));
+                }
+                words
+            }
+            (None, None) => words.collect(),
+        };
+
+        Ok(Self {
+            language: language.into(),
+            words,
+        })
+    }
+}
+
+impl MaskSecrets for WordlistLookup {
+    fn mask_secrets(&mut self) {}
+}
+
+#[cfg_attr(tarpaulin, skip)]
+impl Display for WordlistLookup {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let output = [
+            format!("      {}             {}\n", "Language".cyan().bold(), self.language),
+            self.words
+                .iter()
+                .map(|entry| {
+                    let index = format!("{:<21}", entry.index);
+                    format!("      {}{}\n", index.cyan().bold(), entry.word)
+                })
+                .collect::<String>(),
+        ]
+        .concat();
+
+        // Removes final new line character
+        let output = output[..output.len() - 1].to_string();
+        write!(f, "\n{}", output)
+    }
+}
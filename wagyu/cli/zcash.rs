@@ -1,10 +1,18 @@
-use crate::cli::{flag, option, subcommand, types::*, CLIError, CLI};
-use crate::model::{ExtendedPrivateKey, ExtendedPublicKey, PrivateKey, PublicKey, Transaction};
+use crate::cli::{
+    check_clipboard_field, check_dust, check_fee, copy_wallets, encrypt_to, flag, import_batch, mask, object, option,
+    print_wallets, property, read_batch_file, read_sprout_dump, rederive, sanitize_option, subcommand, to_coin_type,
+    types::*, AuditLog, CLIError, InputKind, Manifest, MaskSecrets, Mismatch, Recipient, Schema, SproutKey, WalletFile,
+    CLI, CLIPBOARD_CLEAR_AFTER, DUST_LIMIT,
+};
+use crate::model::{
+    ChildNumberRange, DerivationPath, DerivationPathTemplate, ExtendedPrivateKey, ExtendedPublicKey, PrivateKey,
+    PublicKey, Transaction,
+};
 use crate::zcash::{
     format::ZcashFormat, initialize_proving_context, initialize_verifying_context, load_sapling_parameters,
     Mainnet as ZcashMainnet, Outpoint, SignatureHash, Testnet as ZcashTestnet, ZcashAddress, ZcashAmount,
     ZcashDerivationPath, ZcashExtendedPrivateKey, ZcashExtendedPublicKey, ZcashNetwork, ZcashPrivateKey,
-    ZcashPublicKey, ZcashTransaction, ZcashTransactionParameters,
+    ZcashPublicKey, ZcashSaplingNote, ZcashTransaction, ZcashTransactionParameters,
 };
 
 use clap::{ArgMatches, Values};
@@ -36,8 +44,8 @@ pub struct HdValues {
 }
 
 /// Represents a generic wallet to output
-#[derive(Serialize, Debug, Default)]
-struct ZcashWallet {
+#[derive(Serialize, Clone, Debug, Default)]
+pub(crate) struct ZcashWallet {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub path: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
@@ -55,6 +63,14 @@ struct ZcashWallet {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub diversifier: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
+    pub diversifier_index: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub amount: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub memo: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub note_commitment: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub network: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub outgoing_view_key: Option<String>,
@@ -64,6 +80,58 @@ struct ZcashWallet {
     pub transaction_hex: Option<String>,
 }
 
+impl MaskSecrets for ZcashWallet {
+    fn mask_secrets(&mut self) {
+        mask(&mut self.extended_private_key);
+        mask(&mut self.outgoing_view_key);
+        mask(&mut self.private_key);
+    }
+}
+
+impl Schema for ZcashWallet {
+    fn schema() -> serde_json::Value {
+        object(
+            vec![
+                ("path", property("string", "The derivation path of the wallet")),
+                (
+                    "extended_private_key",
+                    property("string", "The extended private key of the wallet"),
+                ),
+                (
+                    "extended_public_key",
+                    property("string", "The extended public key of the wallet"),
+                ),
+                ("private_key", property("string", "The private key of the wallet")),
+                ("public_key", property("string", "The public key of the wallet")),
+                ("address", property("string", "The address of the wallet")),
+                ("format", property("string", "The address format of the wallet")),
+                ("diversifier", property("string", "The Sapling address diversifier")),
+                (
+                    "diversifier_index",
+                    property("integer", "The ZIP 32 diversifier index of the Sapling address"),
+                ),
+                (
+                    "amount",
+                    property("integer", "The amount of a decrypted note in zatoshis"),
+                ),
+                (
+                    "memo",
+                    property("string", "The memo of a decrypted note, as text or hex"),
+                ),
+                (
+                    "note_commitment",
+                    property("string", "The note commitment of a decrypted note"),
+                ),
+                ("network", property("string", "The network of the wallet")),
+                ("outgoing_view_key", property("string", "The Sapling outgoing view key")),
+                ("transaction_id", property("string", "The transaction id")),
+                ("transaction_hex", property("string", "The transaction in hex")),
+            ],
+            &[],
+        )
+    }
+}
+
 impl ZcashWallet {
     pub fn new<N: ZcashNetwork, R: Rng>(rng: &mut R, format: &ZcashFormat) -> Result<Self, CLIError> {
         let private_key = match format {
@@ -85,7 +153,12 @@ impl ZcashWallet {
         })
     }
 
-    pub fn new_hd<N: ZcashNetwork, R: Rng>(rng: &mut R, path: &str, format: &ZcashFormat) -> Result<Self, CLIError> {
+    pub fn new_hd<N: ZcashNetwork, R: Rng>(
+        rng: &mut R,
+        path: &str,
+        format: &ZcashFormat,
+        diversifier_index: Option<u64>,
+    ) -> Result<Self, CLIError> {
         let seed: [u8; 32] = rng.gen();
         let master_extended_private_key = ZcashExtendedPrivateKey::<N>::new_master(&seed, format)?;
         let derivation_path = ZcashDerivationPath::from_str(path)?;
@@ -93,7 +166,7 @@ impl ZcashWallet {
         let extended_public_key = extended_private_key.to_extended_public_key();
         let private_key = extended_private_key.to_private_key();
         let public_key = extended_public_key.to_public_key();
-        let address = public_key.to_address(format)?;
+        let (diversifier_index, address) = to_address(&extended_public_key, format, diversifier_index)?;
         Ok(Self {
             path: Some(path.to_string()),
             extended_private_key: Some(extended_private_key.to_string()),
@@ -103,6 +176,7 @@ impl ZcashWallet {
             address: Some(address.to_string()),
             format: Some(address.format().to_string()),
             diversifier: address.to_diversifier(),
+            diversifier_index,
             network: Some(N::NAME.to_string()),
             ..Default::default()
         })
@@ -112,6 +186,7 @@ impl ZcashWallet {
         extended_private_key: &str,
         path: &Option<String>,
         format: &ZcashFormat,
+        diversifier_index: Option<u64>,
     ) -> Result<Self, CLIError> {
         let mut extended_private_key = ZcashExtendedPrivateKey::<N>::from_str(extended_private_key)?;
         if let Some(derivation_path) = path {
@@ -121,7 +196,7 @@ impl ZcashWallet {
         let extended_public_key = extended_private_key.to_extended_public_key();
         let private_key = extended_private_key.to_private_key();
         let public_key = extended_public_key.to_public_key();
-        let address = public_key.to_address(format)?;
+        let (diversifier_index, address) = to_address(&extended_public_key, format, diversifier_index)?;
         Ok(Self {
             path: path.clone(),
             extended_private_key: Some(extended_private_key.to_string()),
@@ -131,6 +206,7 @@ impl ZcashWallet {
             address: Some(address.to_string()),
             format: Some(address.format().to_string()),
             diversifier: address.to_diversifier(),
+            diversifier_index,
             network: Some(N::NAME.to_string()),
             ..Default::default()
         })
@@ -140,6 +216,7 @@ impl ZcashWallet {
         extended_public_key: &str,
         path: &Option<String>,
         format: &ZcashFormat,
+        diversifier_index: Option<u64>,
     ) -> Result<Self, CLIError> {
         let mut extended_public_key = ZcashExtendedPublicKey::<N>::from_str(extended_public_key)?;
         if let Some(derivation_path) = path {
@@ -147,7 +224,7 @@ impl ZcashWallet {
             extended_public_key = extended_public_key.derive(&derivation_path)?;
         }
         let public_key = extended_public_key.to_public_key();
-        let address = public_key.to_address(format)?;
+        let (diversifier_index, address) = to_address(&extended_public_key, format, diversifier_index)?;
         Ok(Self {
             path: path.clone(),
             extended_public_key: Some(extended_public_key.to_string()),
@@ -155,6 +232,25 @@ impl ZcashWallet {
             address: Some(address.to_string()),
             format: Some(address.format().to_string()),
             diversifier: address.to_diversifier(),
+            diversifier_index,
+            network: Some(N::NAME.to_string()),
+            ..Default::default()
+        })
+    }
+
+    pub fn to_extended_public_key<N: ZcashNetwork>(
+        extended_private_key: &str,
+        path: &Option<String>,
+    ) -> Result<Self, CLIError> {
+        let mut extended_private_key = ZcashExtendedPrivateKey::<N>::from_str(extended_private_key)?;
+        if let Some(derivation_path) = path {
+            let derivation_path = ZcashDerivationPath::from_str(derivation_path)?;
+            extended_private_key = extended_private_key.derive(&derivation_path)?;
+        }
+        let extended_public_key = extended_private_key.to_extended_public_key();
+        Ok(Self {
+            path: path.clone(),
+            extended_public_key: Some(extended_public_key.to_string()),
             network: Some(N::NAME.to_string()),
             ..Default::default()
         })
@@ -199,6 +295,53 @@ impl ZcashWallet {
         })
     }
 
+    /// Returns the note of a Sapling output decrypted with an incoming viewing key, given in hex or as
+    /// the incoming viewing key of an extended public key. The ephemeral public key and the note commitment
+    /// are in the reversed byte order of the Zcash RPC.
+    pub fn from_note_ciphertext<N: ZcashNetwork>(
+        ivk: &str,
+        ciphertext: &str,
+        epk: &str,
+        cmu: Option<&str>,
+    ) -> Result<Self, CLIError> {
+        let ivk = match ZcashExtendedPublicKey::<N>::from_str(ivk) {
+            Ok(extended_public_key) => extended_public_key.to_incoming_viewing_key()?,
+            Err(_) => to_key(ivk, false)?,
+        };
+        let cmu = match cmu {
+            Some(cmu) => Some(to_key(cmu, true)?),
+            None => None,
+        };
+
+        let note = ZcashSaplingNote::<N>::decrypt(
+            &ivk,
+            &to_key(epk, true)?,
+            &hex::decode(ciphertext.trim())?,
+            cmu.as_ref(),
+        )?;
+        let address = note.to_address()?;
+
+        // An empty memo is 0xF6 followed by zeros, and a memo that is not UTF-8 text is shown in hex
+        let memo = match note.to_memo_text() {
+            Some(text) => Some(text).filter(|text| !text.is_empty()),
+            None if note.to_memo()[1..].iter().all(|byte| *byte == 0) => None,
+            None => Some(hex::encode(note.to_memo())),
+        };
+        let mut note_commitment = note.to_cmu();
+        note_commitment.reverse();
+
+        Ok(Self {
+            address: Some(address.to_string()),
+            format: Some(address.format().to_string()),
+            diversifier: address.to_diversifier(),
+            amount: Some(note.to_amount()?.0 as u64),
+            memo,
+            note_commitment: Some(hex::encode(note_commitment)),
+            network: Some(N::NAME.to_string()),
+            ..Default::default()
+        })
+    }
+
     pub fn to_raw_transaction<N: ZcashNetwork>(
         inputs: &Vec<ZcashInput>,
         outputs: &Vec<&str>,
@@ -318,6 +461,37 @@ impl ZcashWallet {
     }
 }
 
+/// Returns the address of the extended public key, and its diversifier index if one is specified,
+/// in which case the address is of the first valid diversifier index at or after it.
+fn to_address<N: ZcashNetwork>(
+    extended_public_key: &ZcashExtendedPublicKey<N>,
+    format: &ZcashFormat,
+    diversifier_index: Option<u64>,
+) -> Result<(Option<u64>, ZcashAddress<N>), CLIError> {
+    match diversifier_index {
+        Some(index) => {
+            let (index, address) = extended_public_key.to_diversified_address(index)?;
+            Ok((Some(index), address))
+        }
+        None => Ok((None, extended_public_key.to_address(format)?)),
+    }
+}
+
+/// Returns the 32-byte key of the given hex string, reversed from the byte order of the Zcash RPC if specified.
+fn to_key(key: &str, reverse: bool) -> Result<[u8; 32], CLIError> {
+    let bytes = hex::decode(key.trim())?;
+    if bytes.len() != 32 {
+        return Err(CLIError::InvalidKeyLength(bytes.len()));
+    }
+
+    let mut key = [0u8; 32];
+    key.copy_from_slice(&bytes);
+    if reverse {
+        key.reverse();
+    }
+    Ok(key)
+}
+
 #[cfg_attr(tarpaulin, skip)]
 impl Display for ZcashWallet {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -362,6 +536,24 @@ impl Display for ZcashWallet {
                 Some(diversifier) => format!("      {}          {}\n", "Diversifier".cyan().bold(), diversifier),
                 _ => "".to_owned(),
             },
+            match &self.diversifier_index {
+                Some(index) => format!("      {}    {}\n", "Diversifier Index".cyan().bold(), index),
+                _ => "".to_owned(),
+            },
+            match &self.amount {
+                Some(amount) => format!("      {}               {}\n", "Amount".cyan().bold(), amount),
+                _ => "".to_owned(),
+            },
+            match &self.memo {
+                Some(memo) => format!("      {}                 {}\n", "Memo".cyan().bold(), memo),
+                _ => "".to_owned(),
+            },
+            match &self.note_commitment {
+                Some(note_commitment) => {
+                    format!("      {}      {}\n", "Note Commitment".cyan().bold(), note_commitment)
+                }
+                _ => "".to_owned(),
+            },
             match &self.network {
                 Some(network) => format!("      {}              {}\n", "Network".cyan().bold(), network),
                 _ => "".to_owned(),
@@ -410,19 +602,32 @@ pub struct ZcashInput {
 #[derive(Clone, Debug, Serialize)]
 pub struct ZcashOptions {
     // Standard command
+    clear_after: u64,
     count: usize,
+    copy: Option<String>,
+    encrypt: bool,
+    encrypt_to: Option<String>,
     diversifier: Option<String>,
+    diversifier_index: Option<u64>,
     format: ZcashFormat,
     json: bool,
+    manifest: Option<String>,
+    mask_secrets: bool,
     network: String,
+    reveal: bool,
+    save: Option<String>,
     subcommand: Option<String>,
     // HD and Import HD subcommands
     account: u32,
+    #[serde(skip)]
+    account_range: ChildNumberRange,
     chain: u32,
     derivation: String,
     extended_private_key: Option<String>,
     extended_public_key: Option<String>,
     index: u32,
+    #[serde(skip)]
+    index_range: ChildNumberRange,
     language: String,
     mnemonic: Option<String>,
     password: Option<String>,
@@ -430,9 +635,22 @@ pub struct ZcashOptions {
     word_count: u8,
     // Import subcommand
     address: Option<String>,
+    column: Option<String>,
+    file: Option<String>,
     private: Option<String>,
     public: Option<String>,
+    // Decrypt note subcommand
+    ciphertext: Option<String>,
+    cmu: Option<String>,
+    epk: Option<String>,
+    ivk: Option<String>,
+    // Sprout subcommand
+    destination: Option<String>,
+    dumpfile: Option<String>,
+    spending_key: Option<String>,
     // Transaction subcommand
+    allow_dust: bool,
+    dust_limit: u64,
     transaction_inputs: Option<String>,
     transaction_hex: Option<String>,
     transaction_outputs: Option<String>,
@@ -445,19 +663,30 @@ impl Default for ZcashOptions {
     fn default() -> Self {
         Self {
             // Standard command
+            clear_after: CLIPBOARD_CLEAR_AFTER,
             count: 1,
+            copy: None,
+            encrypt: false,
+            encrypt_to: None,
             diversifier: None,
+            diversifier_index: None,
             format: ZcashFormat::P2PKH,
             json: false,
+            manifest: None,
+            mask_secrets: false,
             network: "mainnet".into(),
+            reveal: false,
+            save: None,
             subcommand: None,
             // HD and Import HD subcommands
             account: 0,
+            account_range: ChildNumberRange::from(0),
             chain: 0,
             derivation: "bip32".into(),
             extended_private_key: None,
             extended_public_key: None,
             index: 0,
+            index_range: ChildNumberRange::from(0),
             language: "english".into(),
             mnemonic: None,
             password: None,
@@ -465,9 +694,22 @@ impl Default for ZcashOptions {
             word_count: 12,
             // Import subcommand
             address: None,
+            column: None,
+            file: None,
             private: None,
             public: None,
+            // Decrypt note subcommand
+            ciphertext: None,
+            cmu: None,
+            epk: None,
+            ivk: None,
+            // Sprout subcommand
+            destination: None,
+            dumpfile: None,
+            spending_key: None,
             // Transaction subcommand
+            allow_dust: false,
+            dust_limit: DUST_LIMIT,
             transaction_inputs: None,
             transaction_hex: None,
             transaction_outputs: None,
@@ -481,33 +723,67 @@ impl Default for ZcashOptions {
 impl ZcashOptions {
     fn parse(&mut self, arguments: &ArgMatches, options: &[&str]) {
         options.iter().for_each(|option| match *option {
-            "account" => self.account(clap::value_t!(arguments.value_of(*option), u32).ok()),
+            "account" => self.account(arguments.value_of(option)),
             "address" => self.address(arguments.value_of(option)),
+            "allow dust" => self.allow_dust(arguments.is_present(option)),
+            "clear after" => self.clear_after(clap::value_t!(arguments.value_of(*option), u64).ok()),
+            "ciphertext" => self.ciphertext(arguments.value_of(option)),
+            "cmu" => self.cmu(arguments.value_of(option)),
+            "column" => self.column(arguments.value_of(option)),
+            "copy" => self.copy(arguments.value_of(option)),
             "count" => self.count(clap::value_t!(arguments.value_of(*option), usize).ok()),
             "createrawtransaction" => self.create_raw_transaction(arguments.values_of(option)),
             "derivation" => self.derivation(arguments.value_of(option)),
+            "destination" => self.destination(arguments.value_of(option)),
+            "dumpfile" => self.dumpfile(arguments.value_of(option)),
+            "dust limit" => self.dust_limit(clap::value_t!(arguments.value_of(*option), u64).ok()),
+            "encrypt" => self.encrypt(arguments.is_present(option)),
+            "epk" => self.epk(arguments.value_of(option)),
+            "encrypt to" => self.encrypt_to(arguments.value_of(option)),
             "diversifier" => self.diversifier(arguments.value_of(option)),
+            "diversifier index" => self.diversifier_index(clap::value_t!(arguments.value_of(*option), u64).ok()),
             "expiry height" => self.expiry_height(clap::value_t!(arguments.value_of(*option), u32).ok()),
             "extended private" => self.extended_private(arguments.value_of(option)),
+            "extended private key" => self.extended_private(arguments.value_of(option)),
             "extended public" => self.extended_public(arguments.value_of(option)),
+            "file" => self.file(arguments.value_of(option)),
             "format" => self.format(arguments.value_of(option)),
-            "index" => self.index(clap::value_t!(arguments.value_of(*option), u32).ok()),
+            "index" => self.index(arguments.value_of(option)),
+            "ivk" => self.ivk(arguments.value_of(option)),
             "json" => self.json(arguments.is_present(option)),
             "lock time" => self.lock_time(clap::value_t!(arguments.value_of(*option), u32).ok()),
+            "manifest" => self.manifest(arguments.value_of(option)),
+            "mask secrets" => self.mask_secrets(arguments.is_present(option)),
             "network" => self.network(arguments.value_of(option)),
+            "path" => self.path(arguments.value_of(option)),
             "private" => self.private(arguments.value_of(option)),
             "public" => self.public(arguments.value_of(option)),
+            "reveal" => self.reveal(arguments.is_present(option)),
+            "save" => self.save(arguments.value_of(option)),
             "signrawtransaction" => self.sign_raw_transaction(arguments.values_of(option)),
+            "spending key" => self.spending_key(arguments.value_of(option)),
             "version" => self.version(arguments.value_of(option)),
             _ => (),
         });
     }
 
-    /// Sets `account` to the specified account index, overriding its previous state.
+    /// Sanitizes the key material of the options, with `--strict` rejecting key material in a non-canonical form.
+    fn sanitize_inputs(&mut self) -> Result<(), CLIError> {
+        sanitize_option("address", &mut self.address, InputKind::Text)?;
+        sanitize_option("extended private key", &mut self.extended_private_key, InputKind::Text)?;
+        sanitize_option("extended public key", &mut self.extended_public_key, InputKind::Text)?;
+        sanitize_option("mnemonic", &mut self.mnemonic, InputKind::Mnemonic)?;
+        sanitize_option("private key", &mut self.private, InputKind::Text)?;
+        sanitize_option("public key", &mut self.public, InputKind::Text)?;
+        sanitize_option("spending key", &mut self.spending_key, InputKind::Text)
+    }
+
+    /// Sets `account` and `account_range` to the specified account index or range, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
-    fn account(&mut self, argument: Option<u32>) {
-        if let Some(account) = argument {
-            self.account = account;
+    fn account(&mut self, argument: Option<&str>) {
+        if let Some(Ok(account)) = argument.map(ChildNumberRange::from_str) {
+            self.account = account.start();
+            self.account_range = account;
         }
     }
 
@@ -519,6 +795,53 @@ impl ZcashOptions {
         }
     }
 
+    /// Sets `allow_dust` to the specified boolean value, overriding its previous state.
+    fn allow_dust(&mut self, argument: bool) {
+        if argument {
+            self.allow_dust = true;
+        }
+    }
+
+    /// Sets `ciphertext` to the specified note ciphertext, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn ciphertext(&mut self, argument: Option<&str>) {
+        if let Some(ciphertext) = argument {
+            self.ciphertext = Some(ciphertext.to_string());
+        }
+    }
+
+    /// Sets `clear_after` to the specified number of seconds, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn clear_after(&mut self, argument: Option<u64>) {
+        if let Some(seconds) = argument {
+            self.clear_after = seconds;
+        }
+    }
+
+    /// Sets `cmu` to the specified note commitment, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn cmu(&mut self, argument: Option<&str>) {
+        if let Some(cmu) = argument {
+            self.cmu = Some(cmu.to_string());
+        }
+    }
+
+    /// Sets `column` to the specified CSV column of a batch file, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn column(&mut self, argument: Option<&str>) {
+        if let Some(column) = argument {
+            self.column = Some(column.to_string());
+        }
+    }
+
+    /// Sets `copy` to the specified wallet field, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn copy(&mut self, argument: Option<&str>) {
+        if let Some(field) = argument {
+            self.copy = Some(field.to_string());
+        }
+    }
+
     /// Sets `count` to the specified count, overriding its previous state.
     fn count(&mut self, argument: Option<usize>) {
         if let Some(count) = argument {
@@ -550,6 +873,14 @@ impl ZcashOptions {
         };
     }
 
+    /// Sets `destination` to the specified Sapling address of a Sprout migration, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn destination(&mut self, argument: Option<&str>) {
+        if let Some(address) = argument {
+            self.destination = Some(address.to_string());
+        }
+    }
+
     /// Sets `diversifier` to the specified diversifier and `format` to the updated Sapling format,
     /// overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
@@ -563,6 +894,55 @@ impl ZcashOptions {
         }
     }
 
+    /// Sets `diversifier_index` to the specified diversifier index and `format` to the Sapling format,
+    /// overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn diversifier_index(&mut self, argument: Option<u64>) {
+        if let Some(index) = argument {
+            self.diversifier_index = Some(index);
+            self.format = ZcashFormat::Sapling(None);
+        }
+    }
+
+    /// Sets `dumpfile` to the specified zcashd wallet dump, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn dumpfile(&mut self, argument: Option<&str>) {
+        if let Some(path) = argument {
+            self.dumpfile = Some(path.to_string());
+        }
+    }
+
+    /// Sets `dust_limit` to the specified amount in zatoshis, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn dust_limit(&mut self, argument: Option<u64>) {
+        if let Some(dust_limit) = argument {
+            self.dust_limit = dust_limit;
+        }
+    }
+
+    /// Sets `encrypt` to the specified boolean value, overriding its previous state.
+    fn encrypt(&mut self, argument: bool) {
+        if argument {
+            self.encrypt = true;
+        }
+    }
+
+    /// Sets `encrypt_to` to the specified recipients, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn encrypt_to(&mut self, argument: Option<&str>) {
+        if let Some(recipients) = argument {
+            self.encrypt_to = Some(recipients.to_string());
+        }
+    }
+
+    /// Sets `epk` to the specified ephemeral public key, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn epk(&mut self, argument: Option<&str>) {
+        if let Some(epk) = argument {
+            self.epk = Some(epk.to_string());
+        }
+    }
+
     /// Sets `expiry_height` to the specified transaction lock time, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
     fn expiry_height(&mut self, argument: Option<u32>) {
@@ -587,6 +967,14 @@ impl ZcashOptions {
         }
     }
 
+    /// Sets `file` to the specified batch file of keys or addresses, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn file(&mut self, argument: Option<&str>) {
+        if let Some(path) = argument {
+            self.file = Some(path.to_string());
+        }
+    }
+
     /// Sets `format` to the specified format, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
     fn format(&mut self, argument: Option<&str>) {
@@ -605,11 +993,20 @@ impl ZcashOptions {
         };
     }
 
-    /// Sets `index` to the specified index, overriding its previous state.
+    /// Sets `index` and `index_range` to the specified index or range, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
-    fn index(&mut self, argument: Option<u32>) {
-        if let Some(index) = argument {
-            self.index = index;
+    fn index(&mut self, argument: Option<&str>) {
+        if let Some(Ok(index)) = argument.map(ChildNumberRange::from_str) {
+            self.index = index.start();
+            self.index_range = index;
+        }
+    }
+
+    /// Sets `ivk` to the specified incoming viewing key, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn ivk(&mut self, argument: Option<&str>) {
+        if let Some(ivk) = argument {
+            self.ivk = Some(ivk.to_string());
         }
     }
 
@@ -626,6 +1023,19 @@ impl ZcashOptions {
         }
     }
 
+    /// Sets `manifest` to the specified manifest file, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn manifest(&mut self, argument: Option<&str>) {
+        if let Some(manifest) = argument {
+            self.manifest = Some(manifest.to_string());
+        }
+    }
+
+    /// Sets `mask_secrets` to the specified boolean value, overriding its previous state.
+    fn mask_secrets(&mut self, argument: bool) {
+        self.mask_secrets = argument;
+    }
+
     /// Sets `network` to the specified network, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
     fn network(&mut self, argument: Option<&str>) {
@@ -636,6 +1046,14 @@ impl ZcashOptions {
         };
     }
 
+    /// Sets `path` to the specified derivation path, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn path(&mut self, argument: Option<&str>) {
+        if let Some(path) = argument {
+            self.path = Some(path.to_string());
+        }
+    }
+
     /// Imports a wallet for the specified private key, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
     fn private(&mut self, argument: Option<&str>) {
@@ -652,6 +1070,19 @@ impl ZcashOptions {
         }
     }
 
+    /// Sets `reveal` to the specified boolean value, overriding its previous state.
+    fn reveal(&mut self, argument: bool) {
+        self.reveal = argument;
+    }
+
+    /// Sets `save` to the specified wallet file, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn save(&mut self, argument: Option<&str>) {
+        if let Some(save) = argument {
+            self.save = Some(save.to_string());
+        }
+    }
+
     /// Sets `transaction_hex` and `transaction_inputs` to the specified transaction values, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
     fn sign_raw_transaction(&mut self, argument: Option<Values>) {
@@ -662,6 +1093,14 @@ impl ZcashOptions {
         }
     }
 
+    /// Sets `spending_key` to the specified Sprout spending key, overriding its previous state.
+    /// If the specified argument is `None`, then no change occurs.
+    fn spending_key(&mut self, argument: Option<&str>) {
+        if let Some(spending_key) = argument {
+            self.spending_key = Some(spending_key.to_string());
+        }
+    }
+
     /// Returns the derivation path with the specified account, index, and path.
     /// If `default` is enabled, then return the default path if no derivation was provided.
     fn to_derivation_path(&self, default: bool) -> Option<String> {
@@ -683,6 +1122,22 @@ impl ZcashOptions {
         }
     }
 
+    /// Returns the derivation paths with the specified account and index ranges, if the path is a template.
+    /// Otherwise, returns the derivation path with the specified account, chain, derivation, index, and path.
+    fn to_derivation_paths(&self, default: bool) -> Result<Vec<Option<String>>, CLIError> {
+        match &self.path {
+            Some(path) if self.derivation == "custom" && DerivationPathTemplate::is_template(path) => {
+                let template = DerivationPathTemplate::from_str(path)?;
+                Ok(template
+                    .expand(&self.account_range, &self.index_range)
+                    .into_iter()
+                    .map(Some)
+                    .collect())
+            }
+            _ => Ok(vec![self.to_derivation_path(default)]),
+        }
+    }
+
     /// Sets `version` to the specified transaction version, overriding its previous state.
     /// If the specified argument is `None`, then no change occurs.
     fn version(&mut self, argument: Option<&str>) {
@@ -692,6 +1147,75 @@ impl ZcashOptions {
     }
 }
 
+/// Returns the parameters of the given network, with the address prefix of each format (the version bytes of a
+/// transparent or Sprout address, or the human-readable part of a Sapling address), and its key prefixes.
+fn to_network_info<N: ZcashNetwork>() -> serde_json::Value {
+    let options = ZcashOptions {
+        network: N::NAME.into(),
+        ..Default::default()
+    };
+    let formats = [
+        ZcashFormat::P2PKH,
+        ZcashFormat::P2SH,
+        ZcashFormat::Sprout,
+        ZcashFormat::Sapling(None),
+    ]
+    .iter()
+    .map(|format| {
+        let address_prefix = match format {
+            ZcashFormat::Sapling(_) => String::from_utf8_lossy(&N::to_address_prefix(format)).to_string(),
+            _ => hex::encode(N::to_address_prefix(format)),
+        };
+        (
+            format.to_string(),
+            serde_json::json!({ "address_prefix": address_prefix }),
+        )
+    })
+    .collect::<serde_json::Map<String, serde_json::Value>>();
+
+    serde_json::json!({
+        "network": N::NAME,
+        "coin_type": to_coin_type(N::HD_COIN_TYPE),
+        "default_derivation_path": options.to_derivation_path(true),
+        "private_key_prefix": hex::encode([N::to_wif_prefix()]),
+        "sprout_spending_key_prefix": hex::encode(N::to_sprout_spending_key_prefix()),
+        "sprout_viewing_key_prefix": hex::encode(N::to_sprout_viewing_key_prefix()),
+        "sapling_spending_key_prefix": N::to_sapling_spending_key_prefix(),
+        "sapling_viewing_key_prefix": N::to_sapling_viewing_key_prefix(),
+        "extended_private_key_prefix": N::to_extended_private_key_prefix(),
+        "extended_public_key_prefix": N::to_extended_public_key_prefix(),
+        "formats": formats,
+    })
+}
+
+/// Returns the mismatches between the stored fields of the given wallet and the fields re-derived from it,
+/// with its addresses re-derived in the given format (with the diversifier of a Sapling address).
+fn to_mismatches<N: ZcashNetwork>(wallet: &serde_json::Value, format: &ZcashFormat) -> Vec<Mismatch> {
+    let mut mismatches = Vec::new();
+    mismatches.extend(rederive(
+        wallet,
+        "extended_private_key",
+        &["extended_public_key", "private_key", "public_key", "address"],
+        |extended_private_key| ZcashWallet::from_extended_private_key::<N>(extended_private_key, &None, format, None),
+    ));
+    mismatches.extend(rederive(
+        wallet,
+        "extended_public_key",
+        &["public_key", "address"],
+        |extended_public_key| ZcashWallet::from_extended_public_key::<N>(extended_public_key, &None, format, None),
+    ));
+    mismatches.extend(rederive(
+        wallet,
+        "private_key",
+        &["public_key", "address"],
+        |private_key| ZcashWallet::from_private_key::<N>(private_key, format),
+    ));
+    mismatches.extend(rederive(wallet, "public_key", &["address"], |public_key| {
+        ZcashWallet::from_public_key::<N>(public_key, format)
+    }));
+    mismatches
+}
+
 pub struct ZcashCLI;
 
 impl CLI for ZcashCLI {
@@ -699,7 +1223,17 @@ impl CLI for ZcashCLI {
 
     const NAME: NameType = "zcash";
     const ABOUT: AboutType = "Generates a Zcash wallet (include -h for more options)";
-    const FLAGS: &'static [FlagType] = &[flag::JSON];
+    const FLAGS: &'static [FlagType] = &[
+        flag::CLEAR_AFTER,
+        flag::COPY,
+        flag::ENCRYPT,
+        flag::ENCRYPT_TO,
+        flag::JSON,
+        flag::MANIFEST,
+        flag::MASK_SECRETS,
+        flag::REVEAL,
+        flag::SAVE,
+    ];
     const OPTIONS: &'static [OptionType] = &[
         option::COUNT,
         option::DIVERSIFIER_ZCASH,
@@ -707,12 +1241,60 @@ impl CLI for ZcashCLI {
         option::NETWORK_ZCASH,
     ];
     const SUBCOMMANDS: &'static [SubCommandType] = &[
+        subcommand::DECRYPT_NOTE_ZCASH,
         subcommand::HD_ZCASH,
         subcommand::IMPORT_ZCASH,
         subcommand::IMPORT_HD_ZCASH,
+        subcommand::SPROUT_ZCASH,
         subcommand::TRANSACTION_ZCASH,
+        subcommand::XPUB,
     ];
 
+    /// Generate Zcash wallets, which are not derived from a BIP-39 mnemonic
+    #[cfg_attr(tarpaulin, skip)]
+    fn generate(
+        count: usize,
+        mnemonic: Option<&str>,
+        password: Option<&str>,
+    ) -> Result<Vec<serde_json::Value>, CLIError> {
+        if mnemonic.is_some() || password.is_some() {
+            return Err(CLIError::UnsupportedMnemonic(Self::NAME.to_string()));
+        }
+        (0..count)
+            .map(|_| {
+                let wallet = ZcashWallet::new::<ZcashMainnet, _>(&mut StdRng::from_entropy(), &ZcashFormat::P2PKH)?;
+                Ok(serde_json::to_value(wallet)?)
+            })
+            .collect()
+    }
+
+    /// Describe the parameters of the Zcash mainnet and testnet
+    #[cfg_attr(tarpaulin, skip)]
+    fn info() -> Result<serde_json::Value, CLIError> {
+        Ok(serde_json::json!({
+            "coin": Self::NAME,
+            "networks": [to_network_info::<ZcashMainnet>(), to_network_info::<ZcashTestnet>()],
+        }))
+    }
+
+    /// Suggest the derivation of Zcash HD wallets for `--derivation`
+    #[cfg_attr(tarpaulin, skip)]
+    fn value_hints() -> Result<Vec<ValueHint>, CLIError> {
+        Ok(vec![("derivation", vec!["zip32".to_string()])])
+    }
+
+    /// Verify a Zcash wallet, recognized by its mainnet or testnet address
+    fn verify(wallet: &serde_json::Value) -> Result<Option<Vec<Mismatch>>, CLIError> {
+        let address = wallet["address"].as_str().unwrap_or_default();
+        if let Ok(address) = ZcashAddress::<ZcashMainnet>::from_str(address) {
+            return Ok(Some(to_mismatches::<ZcashMainnet>(wallet, &address.format())));
+        }
+        if let Ok(address) = ZcashAddress::<ZcashTestnet>::from_str(address) {
+            return Ok(Some(to_mismatches::<ZcashTestnet>(wallet, &address.format())));
+        }
+        Ok(None)
+    }
+
     /// Handle all CLI arguments and flags for Zcash
     #[cfg_attr(tarpaulin, skip)]
     fn parse(arguments: &ArgMatches) -> Result<Self::Options, CLIError> {
@@ -720,30 +1302,49 @@ impl CLI for ZcashCLI {
         options.parse(arguments, &["count", "diversifier", "format", "json", "network"]);
 
         match arguments.subcommand() {
+            ("decrypt-note", Some(arguments)) => {
+                options.subcommand = Some("decrypt-note".into());
+                options.parse(arguments, &["json", "network"]);
+                options.parse(arguments, &["ciphertext", "cmu", "epk", "ivk"]);
+            }
             ("hd", Some(arguments)) => {
                 options.subcommand = Some("hd".into());
                 options.parse(arguments, &["count", "diversifier", "format", "json", "network"]);
-                options.parse(arguments, &["derivation"]);
+                options.parse(arguments, &["derivation", "diversifier index"]);
             }
             ("import", Some(arguments)) => {
                 options.subcommand = Some("import".into());
                 options.parse(arguments, &["diversifier", "format", "json", "network"]);
-                options.parse(arguments, &["address", "private", "public"]);
+                options.parse(arguments, &["address", "column", "file", "private", "public"]);
             }
             ("import-hd", Some(arguments)) => {
                 options.subcommand = Some("import-hd".into());
                 options.parse(arguments, &["diversifier", "format", "json", "network"]);
                 options.parse(
                     arguments,
-                    &["account", "derivation", "extended private", "extended public", "index"],
+                    &[
+                        "account",
+                        "derivation",
+                        "diversifier index",
+                        "extended private",
+                        "extended public",
+                        "index",
+                    ],
                 );
             }
+            ("sprout", Some(arguments)) => {
+                options.subcommand = Some("sprout".into());
+                options.parse(arguments, &["json", "network"]);
+                options.parse(arguments, &["destination", "dumpfile", "spending key"]);
+            }
             ("transaction", Some(arguments)) => {
                 options.subcommand = Some("transaction".into());
                 options.parse(
                     arguments,
                     &[
+                        "allow dust",
                         "createrawtransaction",
+                        "dust limit",
                         "expiry height",
                         "lock time",
                         "signrawtransaction",
@@ -751,9 +1352,39 @@ impl CLI for ZcashCLI {
                     ],
                 );
             }
+            ("xpub", Some(arguments)) => {
+                options.subcommand = Some("xpub".into());
+                options.parse(arguments, &["json"]);
+                options.parse(arguments, &["extended private key", "path"]);
+            }
             _ => {}
         };
 
+        // The save flags are global, and thus parsed from the innermost subcommand arguments
+        let arguments = arguments.subcommand().1.unwrap_or(arguments);
+        options.parse(
+            arguments,
+            &[
+                "clear after",
+                "copy",
+                "encrypt",
+                "encrypt to",
+                "manifest",
+                "mask secrets",
+                "reveal",
+                "save",
+            ],
+        );
+        check_clipboard_field(&options.copy)?;
+        if options.encrypt && options.save.is_none() {
+            return Err(CLIError::MissingSaveFile);
+        }
+        if options.encrypt && options.encrypt_to.is_some() {
+            return Err(CLIError::ConflictingEncryption);
+        }
+
+        options.sanitize_inputs()?;
+
         Ok(options)
     }
 
@@ -761,12 +1392,62 @@ impl CLI for ZcashCLI {
     #[cfg_attr(tarpaulin, skip)]
     fn print(options: Self::Options) -> Result<(), CLIError> {
         fn output<N: ZcashNetwork>(options: ZcashOptions) -> Result<(), CLIError> {
+            // Import each entry of a batch file as a private key, public key, or address
+            if let (Some("import"), Some(path)) = (options.subcommand.as_deref(), &options.file) {
+                let entries = read_batch_file(path, options.column.as_deref())?;
+                return import_batch(
+                    "zcash import",
+                    &entries,
+                    options.mask_secrets && !options.reveal,
+                    |entry| {
+                        ZcashWallet::from_private_key::<ZcashMainnet>(entry, &options.format)
+                            .or_else(|_| ZcashWallet::from_private_key::<ZcashTestnet>(entry, &options.format))
+                            .or_else(|_| ZcashWallet::from_public_key::<ZcashMainnet>(entry, &options.format))
+                            .or_else(|_| ZcashWallet::from_public_key::<ZcashTestnet>(entry, &options.format))
+                            .or_else(|_| ZcashWallet::from_address::<ZcashMainnet>(entry))
+                            .or_else(|_| ZcashWallet::from_address::<ZcashTestnet>(entry))
+                    },
+                );
+            }
+
+            // Derive the keys of a legacy Sprout backup, with the Sapling address that their funds migrate to
+            if let Some("sprout") = options.subcommand.as_deref() {
+                let destination = options.destination.as_deref();
+                let keys = match (&options.dumpfile, &options.spending_key) {
+                    (Some(path), _) => read_sprout_dump::<N>(path, destination)?,
+                    (None, Some(spending_key)) => vec![SproutKey::from_backup::<N>(spending_key, destination)?],
+                    (None, None) => vec![],
+                };
+                print_wallets(&keys, options.json, options.mask_secrets && !options.reveal)?;
+                return AuditLog::log_wallets("zcash sprout", &keys);
+            }
+
             let wallets =
                 match options.subcommand.as_ref().map(String::as_str) {
-                    Some("hd") => match options.to_derivation_path(true) {
+                    Some("decrypt-note") => match (&options.ivk, &options.ciphertext, &options.epk) {
+                        (Some(ivk), Some(ciphertext), Some(epk)) => {
+                            let cmu = options.cmu.as_deref();
+
+                            // An extended public key specifies its network, which overrides the network option
+                            vec![if ZcashExtendedPublicKey::<ZcashMainnet>::from_str(ivk).is_ok() {
+                                ZcashWallet::from_note_ciphertext::<ZcashMainnet>(ivk, ciphertext, epk, cmu)?
+                            } else if ZcashExtendedPublicKey::<ZcashTestnet>::from_str(ivk).is_ok() {
+                                ZcashWallet::from_note_ciphertext::<ZcashTestnet>(ivk, ciphertext, epk, cmu)?
+                            } else {
+                                ZcashWallet::from_note_ciphertext::<N>(ivk, ciphertext, epk, cmu)?
+                            }]
+                        }
+                        _ => vec![],
+                    },
+                    Some("hd") => match options.to_derivation_paths(true)?.remove(0) {
                         Some(path) => (0..options.count)
                             .flat_map(|_| {
-                                match ZcashWallet::new_hd::<N, _>(&mut StdRng::from_entropy(), &path, &options.format) {
+                                match ZcashWallet::new_hd::<N, _>(
+                                    &mut StdRng::from_entropy(),
+                                    &path,
+                                    &options.format,
+                                    options.diversifier_index,
+                                ) {
                                     Ok(wallet) => vec![wallet],
                                     _ => vec![],
                                 }
@@ -795,23 +1476,42 @@ impl CLI for ZcashCLI {
                         }
                     }
                     Some("import-hd") => {
+                        // Generate a wallet for each derivation path, from the expanded path template if specified
                         if let Some(extended_private_key) = options.extended_private_key.clone() {
                             let key = &extended_private_key;
-                            let path = &options.to_derivation_path(false);
                             let format = &options.format;
-
-                            vec![
-                                ZcashWallet::from_extended_private_key::<ZcashMainnet>(key, path, format).or(
-                                    ZcashWallet::from_extended_private_key::<ZcashTestnet>(key, path, format),
-                                )?,
-                            ]
+                            let index = options.diversifier_index;
+
+                            let mut wallets = vec![];
+                            for path in options.to_derivation_paths(false)?.iter() {
+                                wallets.push(
+                                    ZcashWallet::from_extended_private_key::<ZcashMainnet>(key, path, format, index)
+                                        .or(ZcashWallet::from_extended_private_key::<ZcashTestnet>(
+                                            key, path, format, index,
+                                        ))?,
+                                );
+                            }
+                            wallets
                         } else if let Some(extended_public_key) = options.extended_public_key.clone() {
                             let key = &extended_public_key;
-                            let path = &options.to_derivation_path(false);
                             let format = &options.format;
+                            let index = options.diversifier_index;
 
-                            vec![ZcashWallet::from_extended_public_key::<ZcashMainnet>(key, path, format)
-                                .or(ZcashWallet::from_extended_public_key::<ZcashTestnet>(key, path, format))?]
+                            let mut wallets = vec![];
+                            for path in options.to_derivation_paths(false)?.iter() {
+                                // Hardened paths are rejected before the network fallback can mask the error
+                                if let Some(path) = path {
+                                    ZcashDerivationPath::<ZcashMainnet>::from_str(path)?.non_hardened_only()?;
+                                }
+
+                                wallets.push(
+                                    ZcashWallet::from_extended_public_key::<ZcashMainnet>(key, path, format, index)
+                                        .or(ZcashWallet::from_extended_public_key::<ZcashTestnet>(
+                                            key, path, format, index,
+                                        ))?,
+                                );
+                            }
+                            wallets
                         } else {
                             vec![]
                         }
@@ -827,6 +1527,15 @@ impl CLI for ZcashCLI {
                             let lock_time = options.lock_time.unwrap_or(0);
                             let expiry_height = options.expiry_height.unwrap_or(0);
 
+                            // Shielded outputs do not reveal their amounts, and thus are not subject to the dust limit
+                            let transparent_outputs = outputs
+                                .iter()
+                                .filter(|output| output.starts_with('t'))
+                                .cloned()
+                                .collect::<Vec<_>>();
+                            check_dust(&transparent_outputs, options.dust_limit, options.allow_dust)?;
+                            check_fee(&inputs.iter().map(|input| input.amount).collect::<Vec<_>>(), outputs);
+
                             vec![ZcashWallet::to_raw_transaction::<ZcashMainnet>(
                                 inputs,
                                 outputs,
@@ -855,6 +1564,16 @@ impl CLI for ZcashCLI {
                             vec![]
                         }
                     }
+                    Some("xpub") => match options.extended_private_key.clone() {
+                        Some(extended_private_key) => {
+                            let key = &extended_private_key;
+                            let path = &options.path;
+
+                            vec![ZcashWallet::to_extended_public_key::<ZcashMainnet>(key, path)
+                                .or(ZcashWallet::to_extended_public_key::<ZcashTestnet>(key, path))?]
+                        }
+                        None => vec![],
+                    },
                     _ => (0..options.count)
                         .flat_map(
                             |_| match ZcashWallet::new::<N, _>(&mut StdRng::from_entropy(), &options.format) {
@@ -865,11 +1584,40 @@ impl CLI for ZcashCLI {
                         .collect(),
                 };
 
-            match options.json {
-                true => println!("{}\n", serde_json::to_string_pretty(&wallets)?),
-                false => wallets.iter().for_each(|wallet| println!("{}\n", wallet)),
+            match &options.encrypt_to {
+                Some(recipients) => {
+                    let recipients = Recipient::from_list(recipients)?;
+                    print!(
+                        "{}",
+                        encrypt_to(serde_json::to_string_pretty(&wallets)?.as_bytes(), &recipients)?
+                    );
+                    if let Some(path) = &options.save {
+                        let file = WalletFile::new(&wallets, "zcash")?;
+                        std::fs::write(
+                            path,
+                            encrypt_to(serde_json::to_string_pretty(&file)?.as_bytes(), &recipients)?,
+                        )?;
+                    }
+                }
+                None => {
+                    print_wallets(&wallets, options.json, options.mask_secrets && !options.reveal)?;
+                    if let Some(path) = &options.save {
+                        WalletFile::save(&wallets, "zcash", path, options.encrypt)?;
+                    }
+                }
             };
 
+            if let Some(path) = &options.manifest {
+                Manifest::new(&wallets, "zcash")?.write(path)?;
+            }
+
+            if let Some(field) = &options.copy {
+                copy_wallets(&wallets, field, options.clear_after)?;
+            }
+
+            let operation = format!("zcash {}", options.subcommand.as_deref().unwrap_or("generate"));
+            AuditLog::log_wallets(&operation, &wallets)?;
+
             Ok(())
         }
 
@@ -2,19 +2,43 @@
 //!
 //! A command-line tool to generate cryptocurrency wallets.
 
-use wagyu::cli::bitcoin::BitcoinCLI;
-use wagyu::cli::ethereum::EthereumCLI;
-use wagyu::cli::monero::MoneroCLI;
-use wagyu::cli::zcash::ZcashCLI;
-use wagyu::cli::{CLIError, CLI};
+use wagyu::bitcoin::{Mainnet as BitcoinMainnet, Testnet as BitcoinTestnet};
+#[cfg(feature = "serve")]
+use wagyu::cli::Server;
+use wagyu::cli::{
+    convert_key, decode, encode, generate, hash, localize, lock_memory, print_wallets, read_hash_input, sanitize,
+    schema, set_force, set_locale, set_strict, subcommand, to_subcommand, types::ValueHint, AuditLog, CLIError,
+    Ceremony, CeremonySummary, DerivedKey, DiceVerification, InheritancePackage, InheritanceRecovery, InheritanceShare,
+    InputKind, Locale, MnemonicEntropy, Portfolio, RedactionPolicy, Registry, SeedQr, SelfTest, Verification,
+    WalletFile, WordlistLookup,
+};
 
-use clap::{App, AppSettings};
+use clap::{App, AppSettings, Shell};
+use core::str::FromStr;
+use serde_json::json;
+use wagyu_memory::SecureAllocator;
 
+/// The allocator of the secrets of each coin, which zeroes each of their buffers when released,
+/// and locks them against swapping with `--lock-memory`
+#[global_allocator]
+static ALLOCATOR: SecureAllocator = SecureAllocator;
+
+/// Returns the command-line tool, with the subcommands of the coins of the given registry, suggesting
+/// the given value hints of each coin in shell completions.
 #[cfg_attr(tarpaulin, skip)]
-fn main() -> Result<(), CLIError> {
-    let arguments = App::new("wagyu")
+fn app<'b>(registry: &Registry, hints: &'b [Vec<ValueHint>]) -> App<'static, 'b> {
+    let mut subcommands = registry.subcommands(hints);
+    subcommands.extend(subcommand::COMMANDS.iter().map(|s| {
+        match s.0 {
+            "mnemonic" => to_subcommand(s, &[], &[])
+                .subcommands(subcommand::MNEMONIC_COMMANDS.iter().map(|s| to_subcommand(s, &[], &[]))),
+            _ => to_subcommand(s, &[], &[]),
+        }
+    }));
+
+    App::new("wagyu")
         .version("v0.6.3")
-        .about("Generate a wallet for Bitcoin, Ethereum, Monero, and Zcash")
+        .about("Generate a wallet for Bitcoin, Ethereum, Monero, Nostr, and Zcash")
         .author("Aleo <hello@aleo.org>")
         .settings(&[
             AppSettings::ColoredHelp,
@@ -22,20 +46,308 @@ fn main() -> Result<(), CLIError> {
             AppSettings::DisableVersion,
             AppSettings::SubcommandRequiredElseHelp,
         ])
-        .subcommands(vec![
-            BitcoinCLI::new(),
-            EthereumCLI::new(),
-            MoneroCLI::new(),
-            ZcashCLI::new(),
-        ])
+        .subcommands(subcommands)
         .set_term_width(0)
-        .get_matches();
+}
+
+/// Runs the command-line tool, printing an error in the language of the locale.
+#[cfg_attr(tarpaulin, skip)]
+fn main() {
+    if let Err(error) = run() {
+        match Locale::current() {
+            Locale::English => eprintln!("Error: {:?}", error),
+            _ => eprintln!("{}", localize(&format!("Error: {}", error))),
+        };
+        std::process::exit(1);
+    }
+}
+
+#[cfg_attr(tarpaulin, skip)]
+fn run() -> Result<(), CLIError> {
+    let registry = Registry::default();
+    let arguments = app(&registry, &[]).get_matches();
+
+    // The options of the process are options of the subcommands that use them, at any depth
+    let subcommands = || std::iter::successors(Some(&arguments), |arguments| arguments.subcommand().1);
+    let value_of = |name| subcommands().find_map(|arguments| arguments.value_of(name));
+    let is_present = |name| subcommands().any(|arguments| arguments.is_present(name));
+
+    // The memory is locked before any seed or private key is read or generated
+    if is_present("lock memory") {
+        lock_memory()?;
+    }
+
+    if let Some(path) = value_of("log file") {
+        AuditLog::set_log_file(path)?;
+    }
+    set_force(is_present("force"));
+    set_strict(is_present("strict"));
+    set_locale(value_of("locale"));
+    RedactionPolicy::set(value_of("redact"), value_of("redact mode"))?;
 
     match arguments.subcommand() {
-        ("bitcoin", Some(arguments)) => BitcoinCLI::print(BitcoinCLI::parse(arguments)?),
-        ("ethereum", Some(arguments)) => EthereumCLI::print(EthereumCLI::parse(arguments)?),
-        ("monero", Some(arguments)) => MoneroCLI::print(MoneroCLI::parse(arguments)?),
-        ("zcash", Some(arguments)) => ZcashCLI::print(ZcashCLI::parse(arguments)?),
+        ("ceremony", Some(arguments)) => {
+            if let Some(path) = arguments.value_of("verify") {
+                let summary: CeremonySummary = serde_json::from_str(&std::fs::read_to_string(path)?)?;
+                println!(
+                    "{}\n",
+                    serde_json::to_string_pretty(&json!({ "valid": summary.verify()? }))?
+                );
+                return Ok(());
+            }
+
+            let ceremony = Ceremony {
+                operators: arguments.value_of("operators").unwrap_or("2").parse()?,
+                word_count: arguments.value_of("words").unwrap_or("24").parse()?,
+                checks: arguments.value_of("checks").unwrap_or("3").parse()?,
+            };
+            let (_, summary) = ceremony.run()?;
+            AuditLog::log_wallets(
+                "ceremony",
+                &[json!({ "path": summary.path, "address": summary.address })],
+            )?;
+
+            let summary = serde_json::to_string_pretty(&summary)?;
+            match arguments.value_of("summary") {
+                Some(path) => std::fs::write(path, summary)?,
+                None => println!("{}\n", summary),
+            };
+            Ok(())
+        }
+        ("completions", Some(arguments)) => {
+            let shell = Shell::from_str(arguments.value_of("shell").unwrap_or_default())
+                .map_err(|error| CLIError::Crate("clap", error))?;
+            let hints = registry.value_hints()?;
+            app(&registry, &hints).gen_completions_to("wagyu", shell, &mut std::io::stdout());
+            Ok(())
+        }
+        ("convert-key", Some(arguments)) => {
+            let (from, to) = (arguments.value_of("from"), arguments.value_of("to"));
+            let (from, to) = (from.unwrap_or_default(), to.unwrap_or_default());
+            let input = arguments.value_of("input").unwrap_or_default();
+            let password = arguments.value_of("password");
+            let converted = match arguments.value_of("network") {
+                Some("testnet") => convert_key::<BitcoinTestnet>(from, to, input, password)?,
+                _ => convert_key::<BitcoinMainnet>(from, to, input, password)?,
+            };
+            println!("{}\n", converted);
+            Ok(())
+        }
+        ("decode", Some(arguments)) => {
+            let decoded = decode(
+                arguments.value_of("encoding").unwrap_or_default(),
+                arguments.value_of("input").unwrap_or_default(),
+            )?;
+            println!("{}\n", serde_json::to_string_pretty(&decoded)?);
+            Ok(())
+        }
+        ("derive", Some(arguments)) => {
+            let mnemonic = sanitize(
+                "mnemonic",
+                arguments.value_of("mnemonic").unwrap_or_default(),
+                InputKind::Mnemonic,
+            )?;
+            let keys = arguments
+                .values_of("keys")
+                .unwrap_or_default()
+                .map(|key| {
+                    // A TOTP secret is derived for a label, a password for a site, and the other keys for an identity
+                    let password = arguments.value_of("password");
+                    match key {
+                        "password" => DerivedKey::derive_password(
+                            &mnemonic,
+                            password,
+                            arguments.value_of("site").unwrap_or("wagyu"),
+                            arguments.value_of("counter").unwrap_or("1").parse()?,
+                            arguments.value_of("charset").unwrap_or("base64"),
+                            arguments.value_of("length").unwrap_or("20").parse()?,
+                        ),
+                        "totp" => {
+                            DerivedKey::derive(key, &mnemonic, password, arguments.value_of("label").unwrap_or("wagyu"))
+                        }
+                        _ => DerivedKey::derive(
+                            key,
+                            &mnemonic,
+                            password,
+                            arguments.value_of("identity").unwrap_or("wagyu"),
+                        ),
+                    }
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            AuditLog::log_wallets("derive", &keys)?;
+            print_wallets(&keys, arguments.is_present("json"), false)
+        }
+        ("encode", Some(arguments)) => {
+            let data = hex::decode(arguments.value_of("input").unwrap_or_default().trim_start_matches("0x"))?;
+            println!(
+                "{}\n",
+                encode(
+                    arguments.value_of("encoding").unwrap_or_default(),
+                    &data,
+                    arguments.value_of("prefix")
+                )?
+            );
+            Ok(())
+        }
+        ("generate", Some(arguments)) => {
+            let generated = generate(
+                &registry,
+                arguments.value_of("coins").unwrap_or_default(),
+                arguments.value_of("count").unwrap_or("1").parse()?,
+                arguments
+                    .value_of("mnemonic")
+                    .map(|mnemonic| sanitize("mnemonic", mnemonic, InputKind::Mnemonic))
+                    .transpose()?
+                    .as_deref(),
+                arguments.is_present("same mnemonic"),
+            )?;
+            println!("{}\n", serde_json::to_string_pretty(&generated)?);
+            Ok(())
+        }
+        ("hash", Some(arguments)) => {
+            let data = read_hash_input(arguments.value_of("input").unwrap_or_default())?;
+            println!("{}\n", hash(arguments.value_of("algo").unwrap_or_default(), &data)?);
+            Ok(())
+        }
+        ("inheritance", Some(arguments)) => {
+            if let Some(shares) = arguments.values_of("combine") {
+                let shares = shares.map(InheritanceShare::from_str).collect::<Result<Vec<_>, _>>()?;
+                let recovery = InheritanceRecovery::combine(&shares, arguments.value_of("password"))?;
+                AuditLog::log_wallets("inheritance combine", &[&recovery])?;
+                return print_wallets(&[recovery], arguments.is_present("json"), false);
+            }
+
+            let mnemonic = arguments
+                .value_of("mnemonic")
+                .ok_or(CLIError::MissingInheritanceMnemonic)?;
+            let shares = match (arguments.value_of("shares"), arguments.value_of("threshold")) {
+                (Some(count), Some(threshold)) => Some((count.parse()?, threshold.parse()?)),
+                _ => None,
+            };
+            let package = InheritancePackage::write(
+                arguments.value_of("output").unwrap_or("inheritance"),
+                &sanitize("mnemonic", mnemonic, InputKind::Mnemonic)?,
+                arguments.value_of("password"),
+                shares,
+                arguments.value_of("encrypt to"),
+                arguments.value_of("note"),
+            )?;
+            AuditLog::log_wallets("inheritance", &[&package])?;
+            print_wallets(&[package], arguments.is_present("json"), false)
+        }
+        ("mnemonic", Some(arguments)) => match arguments.subcommand() {
+            ("from-entropy", Some(arguments)) => {
+                let entropy = MnemonicEntropy::from_entropy(
+                    &sanitize(
+                        "entropy",
+                        arguments.value_of("entropy").unwrap_or_default(),
+                        InputKind::Entropy,
+                    )?,
+                    arguments.value_of("language").unwrap_or("english"),
+                )?;
+                print_wallets(&[entropy], arguments.is_present("json"), false)
+            }
+            ("to-entropy", Some(arguments)) => {
+                let entropy = MnemonicEntropy::from_mnemonic(&sanitize(
+                    "mnemonic",
+                    arguments.value_of("mnemonic").unwrap_or_default(),
+                    InputKind::Mnemonic,
+                )?)?;
+                print_wallets(&[entropy], arguments.is_present("json"), false)
+            }
+            ("wordlist", Some(arguments)) => {
+                let lookup = WordlistLookup::new(
+                    arguments.value_of("language").unwrap_or("english"),
+                    arguments.value_of("index").map(str::parse).transpose()?,
+                    arguments.value_of("prefix"),
+                )?;
+                print_wallets(&[lookup], arguments.is_present("json"), false)
+            }
+            _ => unreachable!(),
+        },
+        ("open", Some(arguments)) => {
+            let (_, wallets) = WalletFile::open(arguments.value_of("file").unwrap_or_default())?;
+            AuditLog::log_wallets("open", wallets.as_array().map(Vec::as_slice).unwrap_or_default())?;
+            println!("{}\n", serde_json::to_string_pretty(&wallets)?);
+            Ok(())
+        }
+        ("portfolio", Some(arguments)) => {
+            let portfolio = Portfolio::new(
+                &registry,
+                &sanitize(
+                    "mnemonic",
+                    arguments.value_of("mnemonic").unwrap_or_default(),
+                    InputKind::Mnemonic,
+                )?,
+                arguments.value_of("password"),
+            )?;
+            AuditLog::log_wallets("portfolio", &portfolio.addresses)?;
+            print_wallets(&[portfolio], arguments.is_present("json"), false)
+        }
+        ("schema", Some(arguments)) => {
+            println!(
+                "{}\n",
+                serde_json::to_string_pretty(&schema(arguments.value_of("output").unwrap_or_default())?)?
+            );
+            Ok(())
+        }
+        ("seedqr", Some(arguments)) => {
+            let seedqr = match arguments.value_of("payload") {
+                Some(payload) => SeedQr::from_payload(payload)?,
+                None => SeedQr::from_mnemonic(
+                    &sanitize(
+                        "mnemonic",
+                        arguments.value_of("mnemonic").unwrap_or_default(),
+                        InputKind::Mnemonic,
+                    )?,
+                    arguments.is_present("compact"),
+                )?,
+            };
+            AuditLog::log_wallets("seedqr", &[&seedqr])?;
+            print_wallets(&[seedqr], arguments.is_present("json"), false)
+        }
+        ("selftest", Some(arguments)) => {
+            let selftest = SelfTest::run();
+            print_wallets(std::slice::from_ref(&selftest), arguments.is_present("json"), false)?;
+            match selftest.failed {
+                0 => Ok(()),
+                failed => Err(CLIError::FailedSelfTest(failed, selftest.results.len())),
+            }
+        }
+        #[cfg(feature = "serve")]
+        ("serve", Some(arguments)) => Server::bind(
+            arguments.value_of("listen").unwrap_or("127.0.0.1:8080"),
+            arguments.value_of("token").map(String::from),
+            arguments.value_of("wallet file"),
+            arguments.value_of("audit log"),
+        )?
+        .run(),
+        ("verify", Some(arguments)) => {
+            let verification = Verification::from_file(&registry, arguments.value_of("input").unwrap_or_default())?;
+            print_wallets(std::slice::from_ref(&verification), arguments.is_present("json"), false)?;
+            match verification.failed {
+                0 => Ok(()),
+                failed => Err(CLIError::FailedVerification(failed, verification.wallets.len())),
+            }
+        }
+        ("verify-dice", Some(arguments)) => {
+            let verification = DiceVerification::new(
+                arguments.value_of("rolls").unwrap_or_default(),
+                &sanitize(
+                    "mnemonic",
+                    arguments.value_of("mnemonic").unwrap_or_default(),
+                    InputKind::Mnemonic,
+                )?,
+                arguments.value_of("method").unwrap_or("raw"),
+            )?;
+            AuditLog::log_wallets("verify-dice", &[&verification])?;
+            print_wallets(std::slice::from_ref(&verification), arguments.is_present("json"), false)?;
+            match verification.mismatched_words.len() {
+                0 => Ok(()),
+                mismatched => Err(CLIError::FailedDiceVerification(mismatched)),
+            }
+        }
+        (name, Some(arguments)) => registry.find(name)?.run(arguments),
         _ => unreachable!(),
     }
 }
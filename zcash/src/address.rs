@@ -4,6 +4,7 @@ use crate::librustzcash::JUBJUB;
 use crate::network::ZcashNetwork;
 use crate::private_key::ZcashPrivateKey;
 use crate::public_key::{P2PKHViewingKey, SaplingFullViewingKey, SproutViewingKey, ZcashPublicKey};
+use crate::transaction::Opcode;
 use wagyu_model::no_std::{vec, String, ToString, Vec};
 use wagyu_model::{
     crypto::{checksum, hash160},
@@ -18,6 +19,11 @@ use curve25519_dalek::scalar::Scalar;
 use rand::{rngs::StdRng, Rng};
 use rand_core::SeedableRng;
 
+/// The maximum number of public keys in a multisignature redeem script
+const MAX_MULTISIG_PUBLIC_KEYS: usize = 16;
+/// The maximum size of a P2SH redeem script in bytes
+const MAX_REDEEM_SCRIPT_SIZE: usize = 520;
+
 /// Represents a Zcash address
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
 pub struct ZcashAddress<N: ZcashNetwork> {
@@ -38,7 +44,7 @@ impl<N: ZcashNetwork> Address for ZcashAddress<N> {
     fn from_private_key(private_key: &Self::PrivateKey, format: &Self::Format) -> Result<Self, AddressError> {
         match private_key.to_public_key() {
             ZcashPublicKey::<N>::P2PKH(public_key) => Ok(Self::p2pkh(&public_key)),
-            ZcashPublicKey::<N>::P2SH(_) => Ok(Self::p2sh()),
+            ZcashPublicKey::<N>::P2SH(_) => Err(AddressError::Message("p2sh addresses require a redeem script".into())),
             ZcashPublicKey::<N>::Sprout(public_key) => Self::sprout(&public_key),
             ZcashPublicKey::<N>::Sapling(public_key) => Self::sapling(&public_key, format),
         }
@@ -48,7 +54,7 @@ impl<N: ZcashNetwork> Address for ZcashAddress<N> {
     fn from_public_key(public_key: &Self::PublicKey, format: &Self::Format) -> Result<Self, AddressError> {
         match public_key {
             ZcashPublicKey::<N>::P2PKH(public_key) => Ok(Self::p2pkh(&public_key)),
-            ZcashPublicKey::<N>::P2SH(_) => Ok(Self::p2sh()),
+            ZcashPublicKey::<N>::P2SH(_) => Err(AddressError::Message("p2sh addresses require a redeem script".into())),
             ZcashPublicKey::<N>::Sprout(public_key) => Self::sprout(&public_key),
             ZcashPublicKey::<N>::Sapling(public_key) => Self::sapling(&public_key, format),
         }
@@ -75,9 +81,65 @@ impl<N: ZcashNetwork> ZcashAddress<N> {
         }
     }
 
-    /// Returns a P2SH address.
-    pub fn p2sh() -> Self {
-        unimplemented!("p2sh addresses are unimplemented");
+    /// Returns a transparent P2SH address from a given redeem script.
+    pub fn p2sh(redeem_script: &[u8]) -> Self {
+        let mut address = [0u8; 26];
+        address[0..2].copy_from_slice(&N::to_address_prefix(&ZcashFormat::P2SH));
+        address[2..22].copy_from_slice(&hash160(redeem_script));
+
+        let sum = &checksum(&address[0..22])[0..4];
+        address[22..26].copy_from_slice(sum);
+
+        Self {
+            address: address.to_base58(),
+            format: ZcashFormat::P2SH,
+            _network: PhantomData,
+        }
+    }
+
+    /// Returns a transparent m-of-n multisignature P2SH address from a given threshold and Zcash public keys.
+    pub fn multisig(threshold: usize, public_keys: &[P2PKHViewingKey]) -> Result<Self, AddressError> {
+        Ok(Self::p2sh(&Self::create_multisig_redeem_script(
+            threshold,
+            public_keys,
+        )?))
+    }
+
+    /// Returns an m-of-n multisignature redeem script for a given threshold and Zcash public keys,
+    /// in the order of the given public keys.
+    pub fn create_multisig_redeem_script(
+        threshold: usize,
+        public_keys: &[P2PKHViewingKey],
+    ) -> Result<Vec<u8>, AddressError> {
+        if threshold == 0 || threshold > public_keys.len() || public_keys.len() > MAX_MULTISIG_PUBLIC_KEYS {
+            return Err(AddressError::Message(format!(
+                "invalid {}-of-{} multisignature, expected 1 <= m <= n <= {}",
+                threshold,
+                public_keys.len(),
+                MAX_MULTISIG_PUBLIC_KEYS
+            )));
+        }
+
+        let mut redeem_script = vec![Opcode::OP_1 as u8 + threshold as u8 - 1];
+        for public_key in public_keys {
+            let public_key = match public_key.compressed {
+                true => public_key.public_key.serialize_compressed().to_vec(),
+                false => public_key.public_key.serialize().to_vec(),
+            };
+            redeem_script.push(public_key.len() as u8);
+            redeem_script.extend(public_key);
+        }
+        redeem_script.push(Opcode::OP_1 as u8 + public_keys.len() as u8 - 1);
+        redeem_script.push(Opcode::OP_CHECKMULTISIG as u8);
+
+        match redeem_script.len() > MAX_REDEEM_SCRIPT_SIZE {
+            true => Err(AddressError::Message(format!(
+                "invalid redeem script size: {} bytes exceeds {} bytes",
+                redeem_script.len(),
+                MAX_REDEEM_SCRIPT_SIZE
+            ))),
+            false => Ok(redeem_script),
+        }
     }
 
     /// Returns a shielded address from a given Zcash public key.
@@ -121,16 +183,24 @@ impl<N: ZcashNetwork> ZcashAddress<N> {
             data = rng.gen();
         }
 
+        let mut pk_d = [0u8; 32];
+        address.pk_d.write(pk_d.as_mut())?;
+
+        Self::from_diversified_transmission_key(&diversifier, &pk_d)
+    }
+
+    /// Returns a shielded address from a given diversifier and diversified transmission key.
+    pub fn from_diversified_transmission_key(diversifier: &[u8; 11], pk_d: &[u8; 32]) -> Result<Self, AddressError> {
         let mut checked_data = vec![0; 43];
-        checked_data[..11].copy_from_slice(&diversifier);
-        address.pk_d.write(checked_data[11..].as_mut())?;
+        checked_data[..11].copy_from_slice(diversifier);
+        checked_data[11..].copy_from_slice(pk_d);
 
-        let format = ZcashFormat::Sapling(Some(diversifier));
+        let format = ZcashFormat::Sapling(Some(*diversifier));
         let prefix = N::to_address_prefix(&format);
 
         Ok(Self {
             address: Bech32::new(String::from(str::from_utf8(&prefix)?), checked_data.to_base32())?.to_string(),
-            format: ZcashFormat::Sapling(Some(diversifier)),
+            format,
             _network: PhantomData,
         })
     }
@@ -187,7 +257,7 @@ impl<N: ZcashNetwork> FromStr for ZcashAddress<N> {
         // Transparent
         if &address[0..=0] == "t" && address.len() < 40 {
             match &address[1..=1] {
-                "1" | "m" => {
+                "1" | "m" | "3" | "2" => {
                     let data = address.from_base58()?;
                     if data.len() != 26 {
                         return Err(AddressError::InvalidByteLength(data.len()));
@@ -203,9 +273,6 @@ impl<N: ZcashNetwork> FromStr for ZcashAddress<N> {
                         _network: PhantomData,
                     });
                 }
-                "3" | "2" => {
-                    unimplemented!("");
-                }
                 _ => return Err(AddressError::InvalidAddress(address.into())),
             }
         }
@@ -303,6 +370,16 @@ mod tests {
         assert_eq!(expected_address, address.to_string());
     }
 
+    fn test_public_keys<N: ZcashNetwork>(public_keys: &[&str]) -> Vec<P2PKHViewingKey> {
+        public_keys
+            .iter()
+            .map(|public_key| match ZcashPublicKey::<N>::from_str(public_key).unwrap() {
+                ZcashPublicKey::<N>::P2PKH(public_key) => public_key,
+                _ => panic!("expected a transparent public key"),
+            })
+            .collect()
+    }
+
     mod p2pkh_mainnet_compressed {
         use super::*;
 
@@ -547,6 +624,117 @@ mod tests {
         }
     }
 
+    mod p2sh_mainnet {
+        use super::*;
+
+        type N = Mainnet;
+
+        const PUBLIC_KEYS: [&str; 5] = [
+            "0310d63f8c2f0a6efd13ce8a77776de26eba1816f73aa73e73a4da3f2368fcc949",
+            "02f4bf56c9c8389b04752236a4f2419367e3a4e36fe80da6162a0b530ca91262b0",
+            "031347c183c608c629e8bc0ad76718cc9f2a1ee9e53d45862a1b9c8fad25f8ab5b",
+            "03a0d8ab54a080f6e085777c2f5432b22b3543ad421aecc3f2136bcd2e1e2a59e4",
+            "020ceda15424ec7159f7ac5f6ad2654c93ab4cae7f9419de7aae39967f97907fd7",
+        ];
+
+        const MULTISIGS: [(usize, usize, &str); 3] = [
+            (1, 1, "t3eVrZYUVQibAwiSnHxAE5RCaf6WikPb8Pr"),
+            (2, 3, "t3R3sayVBtCuMNWphckMWeFnmS6qPy5jE1E"),
+            (3, 5, "t3VRZ8bf1DorxHzV53ABbRMsJrmXpBz6q6L"),
+        ];
+
+        #[test]
+        fn multisig() {
+            MULTISIGS.iter().for_each(|(threshold, count, expected_address)| {
+                let public_keys = test_public_keys::<N>(&PUBLIC_KEYS[..*count]);
+                let address = ZcashAddress::<N>::multisig(*threshold, &public_keys).unwrap();
+                test_to_str(expected_address, &address);
+                assert_eq!(ZcashFormat::P2SH, address.format());
+            });
+        }
+
+        #[test]
+        fn from_str() {
+            MULTISIGS.iter().for_each(|(_, _, address)| {
+                test_from_str::<N>(address, &ZcashFormat::P2SH);
+            });
+        }
+
+        #[test]
+        fn to_str() {
+            MULTISIGS.iter().for_each(|(_, _, expected_address)| {
+                let address = ZcashAddress::<N>::from_str(expected_address).unwrap();
+                test_to_str(expected_address, &address);
+            });
+        }
+    }
+
+    mod p2sh_testnet {
+        use super::*;
+
+        type N = Testnet;
+
+        const PUBLIC_KEYS: [&str; 5] = [
+            "0310d63f8c2f0a6efd13ce8a77776de26eba1816f73aa73e73a4da3f2368fcc949",
+            "02f4bf56c9c8389b04752236a4f2419367e3a4e36fe80da6162a0b530ca91262b0",
+            "031347c183c608c629e8bc0ad76718cc9f2a1ee9e53d45862a1b9c8fad25f8ab5b",
+            "03a0d8ab54a080f6e085777c2f5432b22b3543ad421aecc3f2136bcd2e1e2a59e4",
+            "020ceda15424ec7159f7ac5f6ad2654c93ab4cae7f9419de7aae39967f97907fd7",
+        ];
+
+        const MULTISIGS: [(usize, usize, &str); 3] = [
+            (1, 1, "t2SV3cDadHBCYVR2XDhAGd3PDmajtb4EhjV"),
+            (2, 3, "t2D34debKkfWivDQSYVMZBsyQYb4ZoM6nbH"),
+            (3, 5, "t2HQkBGm96GUKqh4oxuBdxz3wyFkyyHru2o"),
+        ];
+
+        #[test]
+        fn multisig() {
+            MULTISIGS.iter().for_each(|(threshold, count, expected_address)| {
+                let public_keys = test_public_keys::<N>(&PUBLIC_KEYS[..*count]);
+                let address = ZcashAddress::<N>::multisig(*threshold, &public_keys).unwrap();
+                test_to_str(expected_address, &address);
+                assert_eq!(ZcashFormat::P2SH, address.format());
+            });
+        }
+
+        #[test]
+        fn from_str() {
+            MULTISIGS.iter().for_each(|(_, _, address)| {
+                test_from_str::<N>(address, &ZcashFormat::P2SH);
+            });
+        }
+
+        #[test]
+        fn to_str() {
+            MULTISIGS.iter().for_each(|(_, _, expected_address)| {
+                let address = ZcashAddress::<N>::from_str(expected_address).unwrap();
+                test_to_str(expected_address, &address);
+            });
+        }
+    }
+
+    #[test]
+    fn test_create_multisig_redeem_script() {
+        let public_keys = test_public_keys::<Mainnet>(&[
+            "0310d63f8c2f0a6efd13ce8a77776de26eba1816f73aa73e73a4da3f2368fcc949",
+            "02f4bf56c9c8389b04752236a4f2419367e3a4e36fe80da6162a0b530ca91262b0",
+            "031347c183c608c629e8bc0ad76718cc9f2a1ee9e53d45862a1b9c8fad25f8ab5b",
+        ]);
+        let redeem_script = ZcashAddress::<Mainnet>::create_multisig_redeem_script(2, &public_keys).unwrap();
+        assert_eq!(
+            "52210310d63f8c2f0a6efd13ce8a77776de26eba1816f73aa73e73a4da3f2368fcc9492102f4bf56c9c8389b04752236a4f2419367e3a4e36fe80da6162a0b530ca91262b021031347c183c608c629e8bc0ad76718cc9f2a1ee9e53d45862a1b9c8fad25f8ab5b53ae",
+            hex::encode(&redeem_script)
+        );
+
+        assert!(ZcashAddress::<Mainnet>::create_multisig_redeem_script(0, &public_keys).is_err());
+        assert!(ZcashAddress::<Mainnet>::create_multisig_redeem_script(4, &public_keys).is_err());
+        assert!(ZcashAddress::<Mainnet>::create_multisig_redeem_script(1, &[]).is_err());
+
+        let public_keys = public_keys.iter().cycle().take(16).cloned().collect::<Vec<_>>();
+        assert!(ZcashAddress::<Mainnet>::create_multisig_redeem_script(1, &public_keys).is_err());
+    }
+
     mod sprout_mainnet {
         use super::*;
 
@@ -8,8 +8,8 @@ use crate::private_key::{SaplingSpendingKey, ZcashPrivateKey};
 use crate::public_key::ZcashPublicKey;
 use wagyu_model::no_std::{vec, ToString, Vec};
 use wagyu_model::{
-    Address, AddressError, ChildIndex, DerivationPath, ExtendedPrivateKey, ExtendedPrivateKeyError, ExtendedPublicKey,
-    PublicKey,
+    crypto::constant_time_eq, Address, AddressError, ChildIndex, DerivationPath, ExtendedPrivateKey,
+    ExtendedPrivateKeyError, ExtendedPublicKey, PublicKey,
 };
 
 use bech32::{Bech32, FromBase32, ToBase32};
@@ -120,8 +120,16 @@ impl<N: ZcashNetwork> Display for ZcashExtendedPrivateKey<N> {
 }
 
 impl<N: ZcashNetwork> PartialEq for ZcashExtendedPrivateKey<N> {
+    /// Returns `true` if the extended private keys are equal, comparing their serializations in constant time.
     fn eq(&self, other: &Self) -> bool {
-        self.extended_spending_key == other.extended_spending_key
+        let (mut this, mut that) = (vec![], vec![]);
+        match (
+            self.extended_spending_key.write(&mut this),
+            other.extended_spending_key.write(&mut that),
+        ) {
+            (Ok(_), Ok(_)) => constant_time_eq(&this, &that),
+            _ => false,
+        }
     }
 }
 
@@ -2,12 +2,13 @@ use crate::address::ZcashAddress;
 use crate::derivation_path::ZcashDerivationPath;
 use crate::extended_private_key::ZcashExtendedPrivateKey;
 use crate::format::ZcashFormat;
-use crate::librustzcash::zip32::ExtendedFullViewingKey;
+use crate::librustzcash::algebra::field::{PrimeField, PrimeFieldRepr};
+use crate::librustzcash::zip32::{DiversifierIndex, ExtendedFullViewingKey};
 use crate::network::ZcashNetwork;
 use crate::public_key::ZcashPublicKey;
-use wagyu_model::no_std::{vec, ToString, Vec};
+use wagyu_model::no_std::{format, vec, ToString, Vec};
 use wagyu_model::{
-    Address, AddressError, ChildIndex, DerivationPath, DerivationPathError, ExtendedPublicKey, ExtendedPublicKeyError,
+    Address, AddressError, DerivationPath, DerivationPathError, ExtendedPublicKey, ExtendedPublicKeyError,
 };
 
 use bech32::{Bech32, FromBase32, ToBase32};
@@ -37,20 +38,13 @@ impl<N: ZcashNetwork> ExtendedPublicKey for ZcashExtendedPublicKey<N> {
     /// Returns the extended public key of the given derivation path.
     fn derive(&self, path: &Self::DerivationPath) -> Result<Self, ExtendedPublicKeyError> {
         let mut extended_public_key = self.clone();
-        for index in path.to_vec()?.into_iter() {
-            match index {
-                ChildIndex::Hardened(_) => return Err(DerivationPathError::ExpectedNormalPath.into()),
-                ChildIndex::Normal(number) => {
-                    extended_public_key = Self {
-                        extended_full_viewing_key: match extended_public_key
-                            .extended_full_viewing_key
-                            .derive_child(ChildIndex::Normal(number))
-                        {
-                            Ok(extended_full_viewing_key) => extended_full_viewing_key,
-                            _ => return Err(DerivationPathError::InvalidDerivationPath(path.to_string()).into()),
-                        },
-                    }
-                }
+        // Hardened child indices cannot be derived from an extended public key
+        for index in path.non_hardened_only()?.into_iter() {
+            extended_public_key = Self {
+                extended_full_viewing_key: match extended_public_key.extended_full_viewing_key.derive_child(index) {
+                    Ok(extended_full_viewing_key) => extended_full_viewing_key,
+                    _ => return Err(DerivationPathError::InvalidDerivationPath(path.to_string()).into()),
+                },
             }
         }
         Ok(extended_public_key)
@@ -72,6 +66,51 @@ impl<N: ZcashNetwork> ZcashExtendedPublicKey<N> {
     pub fn to_extended_full_viewing_key(&self) -> ExtendedFullViewingKey<N> {
         self.extended_full_viewing_key.clone()
     }
+
+    /// Returns the Sapling incoming viewing key of the Zcash extended public key.
+    pub fn to_incoming_viewing_key(&self) -> Result<[u8; 32], ExtendedPublicKeyError> {
+        let mut ivk = [0u8; 32];
+        self.extended_full_viewing_key
+            .fvk
+            .vk
+            .ivk()
+            .into_repr()
+            .write_le(&mut ivk[..])?;
+        Ok(ivk)
+    }
+
+    /// Returns the Sapling address of the first valid diversifier index at or after the given index,
+    /// along with that diversifier index. Index 0 returns the default address of the extended public key.
+    pub fn to_diversified_address(&self, index: u64) -> Result<(u64, ZcashAddress<N>), AddressError> {
+        let mut diversifier_index = DiversifierIndex::new();
+        diversifier_index.0[..8].copy_from_slice(&index.to_le_bytes());
+
+        let (diversifier_index, address) = match self.extended_full_viewing_key.address(diversifier_index) {
+            Ok(output) => output,
+            Err(_) => {
+                return Err(AddressError::Message(format!(
+                    "no valid diversifier from index {}",
+                    index
+                )))
+            }
+        };
+        if diversifier_index.0[8..].iter().any(|byte| *byte != 0) {
+            return Err(AddressError::Message(format!(
+                "no valid diversifier from index {}",
+                index
+            )));
+        }
+
+        let mut index = [0u8; 8];
+        index.copy_from_slice(&diversifier_index.0[..8]);
+        let mut pk_d = [0u8; 32];
+        address.pk_d.write(pk_d.as_mut())?;
+
+        Ok((
+            u64::from_le_bytes(index),
+            ZcashAddress::<N>::from_diversified_transmission_key(&address.diversifier.0, &pk_d)?,
+        ))
+    }
 }
 
 impl<N: ZcashNetwork> FromStr for ZcashExtendedPublicKey<N> {
@@ -153,6 +192,12 @@ mod tests {
         assert_eq!(expected_address, address.to_string());
     }
 
+    fn test_to_diversified_address<N: ZcashNetwork>(expected_address: &str, extended_public_key: &str) {
+        let extended_public_key = ZcashExtendedPublicKey::<N>::from_str(extended_public_key).unwrap();
+        let (_, address) = extended_public_key.to_diversified_address(0).unwrap();
+        assert_eq!(expected_address, address.to_string());
+    }
+
     fn test_from_str<N: ZcashNetwork>(expected_extended_public_key: &str) {
         let extended_public_key = ZcashExtendedPublicKey::<N>::from_str(&expected_extended_public_key).unwrap();
         assert_eq!(expected_extended_public_key, extended_public_key.to_string());
@@ -225,6 +270,13 @@ mod tests {
             });
         }
 
+        #[test]
+        fn to_diversified_address() {
+            KEYPAIRS.iter().for_each(|(_, _, _, extended_public_key, address)| {
+                test_to_diversified_address::<N>(address, extended_public_key);
+            });
+        }
+
         #[test]
         fn from_str() {
             KEYPAIRS.iter().for_each(|(_, _, _, extended_public_key, _)| {
@@ -298,6 +350,13 @@ mod tests {
             });
         }
 
+        #[test]
+        fn to_diversified_address() {
+            KEYPAIRS.iter().for_each(|(_, _, _, extended_public_key, address)| {
+                test_to_diversified_address::<N>(address, extended_public_key);
+            });
+        }
+
         #[test]
         fn from_str() {
             KEYPAIRS.iter().for_each(|(_, _, _, extended_public_key, _)| {
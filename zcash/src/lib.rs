@@ -46,6 +46,9 @@ pub mod librustzcash;
 pub mod network;
 pub use self::network::*;
 
+pub mod note;
+pub use self::note::*;
+
 pub mod private_key;
 pub use self::private_key::*;
 
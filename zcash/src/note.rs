@@ -0,0 +1,282 @@
+use crate::address::ZcashAddress;
+use crate::amount::ZcashAmount;
+use crate::network::ZcashNetwork;
+use wagyu_model::no_std::{format, String, ToString, Vec};
+use wagyu_model::TransactionError;
+
+use blake2b_simd::Params;
+use byteorder::{ByteOrder, LittleEndian};
+use core::marker::PhantomData;
+use crypto_api_chachapoly::ChachaPolyIetf;
+
+// librustzcash crates
+use ff::{PrimeField, PrimeFieldRepr};
+use pairing::bls12_381::{Bls12, FrRepr};
+use zcash_primitives::{
+    jubjub::{
+        edwards,
+        fs::{Fs, FsRepr},
+        ToUniform,
+    },
+    keys::prf_expand,
+    note_encryption::{sapling_ka_agree, KDF_SAPLING_PERSONALIZATION},
+    primitives::{Diversifier, PaymentAddress},
+    JUBJUB,
+};
+
+/// The size of a Sapling note plaintext without the memo
+const COMPACT_NOTE_SIZE: usize = 1 + 11 + 8 + 32;
+/// The size of a Sapling note plaintext
+const NOTE_PLAINTEXT_SIZE: usize = COMPACT_NOTE_SIZE + 512;
+/// The size of a Sapling note ciphertext, including the authentication tag
+const ENC_CIPHERTEXT_SIZE: usize = NOTE_PLAINTEXT_SIZE + 16;
+
+/// Represents a Zcash Sapling note decrypted with an incoming viewing key
+#[derive(Debug, Clone)]
+pub struct ZcashSaplingNote<N: ZcashNetwork> {
+    /// The diversifier of the recipient address
+    diversifier: [u8; 11],
+    /// The diversified transmission key of the recipient address
+    pk_d: [u8; 32],
+    /// The value of the note in zatoshis
+    value: u64,
+    /// The note commitment randomness
+    rcm: [u8; 32],
+    /// The note commitment
+    cmu: [u8; 32],
+    /// The memo of the note
+    memo: Vec<u8>,
+    /// The network on which this note is usable
+    _network: PhantomData<N>,
+}
+
+impl<N: ZcashNetwork> ZcashSaplingNote<N> {
+    /// Returns the note of a Sapling output decrypted with the given incoming viewing key,
+    /// ephemeral public key, and note ciphertext. If the note commitment of the output is given,
+    /// it is checked against the note commitment of the decrypted note.
+    ///
+    /// Both version 1 and version 2 (ZIP 212) note plaintexts are supported.
+    pub fn decrypt(
+        ivk: &[u8; 32],
+        epk: &[u8; 32],
+        enc_ciphertext: &[u8],
+        cmu: Option<&[u8; 32]>,
+    ) -> Result<Self, TransactionError> {
+        if enc_ciphertext.len() != ENC_CIPHERTEXT_SIZE {
+            return Err(TransactionError::Message(format!(
+                "invalid note ciphertext length: expected {} bytes, found {} bytes",
+                ENC_CIPHERTEXT_SIZE,
+                enc_ciphertext.len()
+            )));
+        }
+
+        let mut ivk_repr = FsRepr::default();
+        ivk_repr.read_le(&ivk[..])?;
+        let ivk = Fs::from_repr(ivk_repr)?;
+
+        let epk_point = match edwards::Point::<Bls12, _>::read(&epk[..], &JUBJUB)?.as_prime_order(&JUBJUB) {
+            Some(epk) => epk,
+            None => return Err(TransactionError::InvalidEphemeralKey(hex::encode(epk))),
+        };
+
+        // Sapling key agreement and KDF - section 5.4.4 of the Zcash Protocol Specification
+        let shared_secret = sapling_ka_agree(&ivk, &epk_point);
+        let mut kdf_input = [0u8; 64];
+        shared_secret.write(&mut kdf_input[0..32])?;
+        kdf_input[32..64].copy_from_slice(epk);
+        let key = Params::new()
+            .hash_length(32)
+            .personal(KDF_SAPLING_PERSONALIZATION)
+            .hash(&kdf_input);
+
+        let mut plaintext = [0u8; ENC_CIPHERTEXT_SIZE];
+        match ChachaPolyIetf::aead_cipher().open_to(&mut plaintext, enc_ciphertext, &[], key.as_bytes(), &[0u8; 12]) {
+            Ok(NOTE_PLAINTEXT_SIZE) => (),
+            _ => return Err(TransactionError::FailedNoteDecryption(hex::encode(enc_ciphertext))),
+        };
+
+        let mut diversifier = [0u8; 11];
+        diversifier.copy_from_slice(&plaintext[1..12]);
+        let value = LittleEndian::read_u64(&plaintext[12..20]);
+
+        // The note commitment randomness is derived from the seed of version 2 note plaintexts
+        let rcm = match plaintext[0] {
+            0x01 => {
+                let mut rcm = FsRepr::default();
+                rcm.read_le(&plaintext[20..COMPACT_NOTE_SIZE])?;
+                Fs::from_repr(rcm)?
+            }
+            0x02 => Fs::to_uniform(prf_expand(&plaintext[20..COMPACT_NOTE_SIZE], &[0x04]).as_bytes()),
+            version => {
+                return Err(TransactionError::Message(format!(
+                    "unsupported note plaintext version: {}",
+                    version
+                )))
+            }
+        };
+
+        let payment_address = match Diversifier(diversifier).g_d::<Bls12>(&JUBJUB) {
+            Some(g_d) => PaymentAddress::from_parts(Diversifier(diversifier), g_d.mul(ivk, &JUBJUB)),
+            None => None,
+        };
+        let payment_address = match payment_address {
+            Some(payment_address) => payment_address,
+            None => return Err(TransactionError::MissingDiversifier),
+        };
+        let note = match payment_address.create_note(value, rcm, &JUBJUB) {
+            Some(note) => note,
+            None => return Err(TransactionError::MissingDiversifier),
+        };
+
+        let mut note_cmu = [0u8; 32];
+        note.cm(&JUBJUB).into_repr().write_le(&mut note_cmu[..])?;
+        if let Some(cmu) = cmu {
+            let mut cmu_repr = FrRepr::default();
+            cmu_repr.read_le(&cmu[..])?;
+            if note.cm(&JUBJUB).into_repr() != cmu_repr {
+                return Err(TransactionError::Message(
+                    "invalid note commitment: the decrypted note does not match the output".into(),
+                ));
+            }
+        }
+
+        let mut pk_d = [0u8; 32];
+        payment_address.pk_d().write(&mut pk_d[..])?;
+        let mut rcm_bytes = [0u8; 32];
+        rcm.into_repr().write_le(&mut rcm_bytes[..])?;
+
+        Ok(Self {
+            diversifier,
+            pk_d,
+            value,
+            rcm: rcm_bytes,
+            cmu: note_cmu,
+            memo: plaintext[COMPACT_NOTE_SIZE..NOTE_PLAINTEXT_SIZE].to_vec(),
+            _network: PhantomData,
+        })
+    }
+
+    /// Returns the recipient address of the note.
+    pub fn to_address(&self) -> Result<ZcashAddress<N>, TransactionError> {
+        Ok(ZcashAddress::<N>::from_diversified_transmission_key(
+            &self.diversifier,
+            &self.pk_d,
+        )?)
+    }
+
+    /// Returns the value of the note.
+    pub fn to_amount(&self) -> Result<ZcashAmount, TransactionError> {
+        Ok(ZcashAmount::from_zatoshi(self.value as i64)?)
+    }
+
+    /// Returns the note commitment randomness of the note.
+    pub fn to_rcm(&self) -> [u8; 32] {
+        self.rcm
+    }
+
+    /// Returns the note commitment of the note.
+    pub fn to_cmu(&self) -> [u8; 32] {
+        self.cmu
+    }
+
+    /// Returns the memo of the note.
+    pub fn to_memo(&self) -> Vec<u8> {
+        self.memo.clone()
+    }
+
+    /// Returns the text of the memo, if the memo is a UTF-8 string - section 5.5 of the
+    /// Zcash Protocol Specification. Otherwise, returns `None`.
+    pub fn to_memo_text(&self) -> Option<String> {
+        if self.memo[0] > 0xF4 {
+            return None;
+        }
+
+        let length = self
+            .memo
+            .iter()
+            .rposition(|byte| *byte != 0)
+            .map_or(0, |index| index + 1);
+        core::str::from_utf8(&self.memo[..length])
+            .ok()
+            .map(|text| text.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::extended_private_key::ZcashExtendedPrivateKey;
+    use crate::network::*;
+    use core::str::FromStr;
+    use wagyu_model::ExtendedPrivateKey;
+
+    const EXTENDED_PRIVATE_KEY: &str = "secret-extended-key-test1qwq6zxvfqsqqpqzn4hxmcv7d9whwepfpx72aahddkf073x2cwr6fwar0p2ns4xkcu73xgs2pnxgux2nfx8a5nt2w7tm49ptnq9v3z4qncjlk8er7q27qewhcul9xtlxjqe56jyspamlhh8r4glmva2zxvkuejw8ypsfp5lsgc564r6g5w068kqlrcy0s0wnu0382tv2eqnlart5gjczwa0l72qgtaa794dqpva62206wwvemvath3t2f5j6x9nlvsgus0wavrvwavucculeth";
+    const CMU: &str = "40d9712ba3dd9787a0451462e4fdda07929305b06248dd8aaeabdce13985b576";
+    const EPK: &str = "8dca2796416d9ab8409e40e3b3839eb28b5765ba9b7dcfb2c267ac54b85fc6be";
+    const ENC_CIPHERTEXT: &str = "d259f0cb859e6bc1b92590600f2a5d9b3464c7c568c96926fb97b2ffcecd7f9c8bfb878e3650cf30378ec222797787ab2c354589cda6da227c9b72945751827857823848a03bddeef13ecc14570291ee6638da600e0f91ca0348a6146b9f176b60f053a7f4bd94f5d9c669e8958b3d03c2fd456caa4703ec1ffcf75759ffaf098502295c7eadbdab928e77740220339611c4c977b0185627f2ac6db5c0fca6c1d6a89f0ba6503f6d520e6814f0f592bc950023395b2907e39067242a87d74dc535a7decf37c4530b1b5f375cd588949cc9948c409ad3b7bf1bd6a307d076b34a1c93c330f7a42df419ef95965e747b43306f277255cc2fe4b7f4ec3e6ca06f7161ac4a89b703b2b99b201d3a2279b45d60b0f899931afc45a6be5496df192abade2039403132711d899bc8e02700d2f9cf225ca7de9b4c9e3899c9e63eb669e4b626006797ad61247bef423b27ad4e2d472c648420ac88d9f03abc9132d360b1e634684bb73eb251495f3c34a5ab6f73436b2b58e5fb89a5e41692ceea8f04d19b2dd76a796bda2d99f95b5aca03a750acb50f44924f7fea953fe33316255da6c5cadb80687875bc63b2865d79010a3d845d9bc1836a2726d3040ed05fe403f30a51597f8921e3c0c4544d1aab8d67a382410ce377654e79e27e9ff81bd8264d5fbd4e5915a7fa804424cc65ff19ca4d4dd0f3fb34585d18b75f6c39d99cc48e800bab6fa2324340922f62273fe371f2d725567fae988579a00bf7bc3b911cf03746cf47286f05d24c64c38f829d0d40a34799151176057cfe49d6b9fc6b9d983612d75d";
+
+    /// Returns the given hex in the reversed byte order of the Zcash RPC.
+    fn reversed(hex: &str) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        bytes.copy_from_slice(&hex::decode(hex).unwrap());
+        bytes.reverse();
+        bytes
+    }
+
+    fn ivk() -> [u8; 32] {
+        ZcashExtendedPrivateKey::<Testnet>::from_str(EXTENDED_PRIVATE_KEY)
+            .unwrap()
+            .to_extended_public_key()
+            .to_incoming_viewing_key()
+            .unwrap()
+    }
+
+    #[test]
+    fn decrypt() {
+        let enc_ciphertext = hex::decode(ENC_CIPHERTEXT).unwrap();
+        let note = ZcashSaplingNote::<Testnet>::decrypt(&ivk(), &reversed(EPK), &enc_ciphertext, Some(&reversed(CMU)))
+            .unwrap();
+
+        assert_eq!(
+            "ztestsapling1eay5laktfpn2qk6r78u2tdxvcg9t5uqlkdek3x8y6csnqzfql6ckq9csls6n3e4mpv6m2l80j2g",
+            note.to_address().unwrap().to_string()
+        );
+        assert_eq!(ZcashAmount::from_zatoshi(499970000).unwrap(), note.to_amount().unwrap());
+        assert_eq!(reversed(CMU), note.to_cmu());
+        assert_eq!(0xF6, note.to_memo()[0]);
+        assert_eq!(None, note.to_memo_text());
+
+        // The note commitment is optional
+        let unchecked = ZcashSaplingNote::<Testnet>::decrypt(&ivk(), &reversed(EPK), &enc_ciphertext, None).unwrap();
+        assert_eq!(note.to_rcm(), unchecked.to_rcm());
+        assert_eq!(note.to_cmu(), unchecked.to_cmu());
+    }
+
+    #[test]
+    fn decrypt_invalid() {
+        let enc_ciphertext = hex::decode(ENC_CIPHERTEXT).unwrap();
+        let (ivk, epk, cmu) = (ivk(), reversed(EPK), reversed(CMU));
+
+        // Invalid incoming viewing key
+        assert!(ZcashSaplingNote::<Testnet>::decrypt(&[1u8; 32], &epk, &enc_ciphertext, None).is_err());
+        // Invalid ephemeral public key
+        assert!(ZcashSaplingNote::<Testnet>::decrypt(&ivk, &[0u8; 32], &enc_ciphertext, None).is_err());
+        // Invalid note commitment
+        assert!(ZcashSaplingNote::<Testnet>::decrypt(&ivk, &epk, &enc_ciphertext, Some(&epk)).is_err());
+        // Invalid ciphertext
+        assert!(ZcashSaplingNote::<Testnet>::decrypt(&ivk, &epk, &enc_ciphertext[1..], Some(&cmu)).is_err());
+        assert!(ZcashSaplingNote::<Testnet>::decrypt(&ivk, &epk, &[0u8; ENC_CIPHERTEXT_SIZE], Some(&cmu)).is_err());
+    }
+
+    #[test]
+    fn to_memo_text() {
+        let enc_ciphertext = hex::decode(ENC_CIPHERTEXT).unwrap();
+        let mut note = ZcashSaplingNote::<Testnet>::decrypt(&ivk(), &reversed(EPK), &enc_ciphertext, None).unwrap();
+
+        note.memo = [&b"wagyu"[..], &[0u8; 507][..]].concat();
+        assert_eq!(Some("wagyu".to_string()), note.to_memo_text());
+
+        note.memo = [0u8; 512].to_vec();
+        assert_eq!(Some("".to_string()), note.to_memo_text());
+    }
+}
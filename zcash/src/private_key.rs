@@ -13,7 +13,10 @@ use wagyu_model::no_std::{
     io::{self, Read, Write},
     vec, String, ToString, Vec,
 };
-use wagyu_model::{crypto::checksum, Address, AddressError, PrivateKey, PrivateKeyError, PublicKey};
+use wagyu_model::{
+    crypto::{checksum, constant_time_eq},
+    Address, AddressError, PrivateKey, PrivateKeyError, PublicKey,
+};
 
 use base58::{FromBase58, ToBase58};
 use bech32::{Bech32, FromBase32, ToBase32};
@@ -27,7 +30,7 @@ use failure::AsFail;
 use rand::Rng;
 use secp256k1;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Eq)]
 pub struct P2PKHSpendingKey<N: ZcashNetwork> {
     /// The ECDSA private key
     pub(super) secret_key: secp256k1::SecretKey,
@@ -57,6 +60,14 @@ impl<N: ZcashNetwork> P2PKHSpendingKey<N> {
     }
 }
 
+impl<N: ZcashNetwork> PartialEq for P2PKHSpendingKey<N> {
+    /// Returns `true` if the spending keys are equal, comparing their secret keys in constant time.
+    fn eq(&self, other: &Self) -> bool {
+        constant_time_eq(&self.secret_key.serialize(), &other.secret_key.serialize())
+            && self.compressed == other.compressed
+    }
+}
+
 impl<N: ZcashNetwork> Display for P2PKHSpendingKey<N> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         /// Returns a WIF string given a secp256k1 secret key.
@@ -83,7 +94,7 @@ impl<N: ZcashNetwork> Display for P2PKHSpendingKey<N> {
 #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
 pub struct P2SHSpendingKey {}
 
-#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, Clone, Eq)]
 pub struct SproutSpendingKey<N: ZcashNetwork> {
     /// Raw encoding of (0000 || 252-bit a_sk)
     pub(super) spending_key: [u8; 32],
@@ -100,6 +111,13 @@ impl<N: ZcashNetwork> SproutSpendingKey<N> {
     }
 }
 
+impl<N: ZcashNetwork> PartialEq for SproutSpendingKey<N> {
+    /// Returns `true` if the spending keys are equal, comparing them in constant time.
+    fn eq(&self, other: &Self) -> bool {
+        constant_time_eq(&self.spending_key, &other.spending_key)
+    }
+}
+
 impl<N: ZcashNetwork> Display for SproutSpendingKey<N> {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         let mut spending_key = [0u8; 38];
@@ -223,22 +241,21 @@ impl<N: ZcashNetwork> Display for SaplingSpendingKey<N> {
 }
 
 impl<N: ZcashNetwork> PartialEq for SaplingSpendingKey<N> {
+    /// Returns `true` if the spending keys are equal, comparing their spending keys (if both are known)
+    /// and their expanded spending keys in constant time.
     fn eq(&self, other: &Self) -> bool {
-        if let Some(this) = self.spending_key {
-            if let Some(that) = other.spending_key {
-                if this != that {
-                    return false;
-                }
-            }
-        }
-        self.ask == other.ask && self.nsk == other.nsk && self.ovk == other.ovk
+        let spending_keys_eq = match (self.spending_key, other.spending_key) {
+            (Some(this), Some(that)) => constant_time_eq(&this, &that),
+            _ => true,
+        };
+        spending_keys_eq & constant_time_eq(&self.to_bytes(), &other.to_bytes())
     }
 }
 
 impl<N: ZcashNetwork> Eq for SaplingSpendingKey<N> {}
 
 /// Represents a Zcash private key
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Eq)]
 pub enum ZcashPrivateKey<N: ZcashNetwork> {
     /// P2PKH transparent spending key
     P2PKH(P2PKHSpendingKey<N>),
@@ -250,6 +267,20 @@ pub enum ZcashPrivateKey<N: ZcashNetwork> {
     Sapling(SaplingSpendingKey<N>),
 }
 
+impl<N: ZcashNetwork> PartialEq for ZcashPrivateKey<N> {
+    /// Returns `true` if the private keys are of the same kind and equal, comparing their spending keys
+    /// in constant time.
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (ZcashPrivateKey::P2PKH(this), ZcashPrivateKey::P2PKH(that)) => this == that,
+            (ZcashPrivateKey::P2SH(this), ZcashPrivateKey::P2SH(that)) => this == that,
+            (ZcashPrivateKey::Sprout(this), ZcashPrivateKey::Sprout(that)) => this == that,
+            (ZcashPrivateKey::Sapling(this), ZcashPrivateKey::Sapling(that)) => this == that,
+            _ => false,
+        }
+    }
+}
+
 impl<N: ZcashNetwork> PrivateKey for ZcashPrivateKey<N> {
     type Address = ZcashAddress<N>;
     type Format = ZcashFormat;
@@ -1010,6 +1041,22 @@ mod tests {
                 test_invalid_spending_key_length::<N>(private_key);
             });
         }
+
+        #[test]
+        fn eq() {
+            // The first two keys are the same key, as a spending key and as an expanded spending key
+            let private_keys = KEYPAIRS
+                .iter()
+                .map(|(private_key, _, _)| ZcashPrivateKey::<N>::from_str(private_key).unwrap())
+                .collect::<Vec<_>>();
+            assert_eq!(private_keys[0], private_keys[1]);
+            assert_ne!(private_keys[0], private_keys[2]);
+            assert_ne!(private_keys[1], private_keys[2]);
+            assert_ne!(
+                private_keys[0],
+                ZcashPrivateKey::<N>::from_str("L3a3yRcYATnZQt7ams14Pe5KCyRzrrCSejDyeQzHXGntToffVH4g").unwrap()
+            );
+        }
     }
 
     mod sapling_testnet {
@@ -212,6 +212,17 @@ pub fn create_script_pub_key<N: ZcashNetwork>(address: &ZcashAddress<N>) -> Resu
             script.push(Opcode::OP_CHECKSIG as u8);
             Ok(script)
         }
+        ZcashFormat::P2SH => {
+            let address_bytes = &address.to_string().from_base58()?;
+            let script_hash = address_bytes[2..(address_bytes.len() - 4)].to_vec();
+
+            let mut script = vec![];
+            script.push(Opcode::OP_HASH160 as u8);
+            script.extend(variable_length_integer(script_hash.len() as u64)?);
+            script.extend(script_hash);
+            script.push(Opcode::OP_EQUAL as u8);
+            Ok(script)
+        }
         _ => unreachable!(),
     }
 }
@@ -279,9 +290,11 @@ impl SignatureHash {
 #[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize)]
 #[allow(non_camel_case_types)]
 pub enum Opcode {
+    OP_1 = 0x51,
     OP_DUP = 0x76,
     OP_HASH160 = 0xa9,
     OP_CHECKSIG = 0xac,
+    OP_CHECKMULTISIG = 0xae,
     OP_EQUAL = 0x87,
     OP_EQUALVERIFY = 0x88,
 }
@@ -289,9 +302,11 @@ pub enum Opcode {
 impl fmt::Display for Opcode {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
+            Opcode::OP_1 => write!(f, "OP_1"),
             Opcode::OP_DUP => write!(f, "OP_DUP"),
             Opcode::OP_HASH160 => write!(f, "OP_HASH160"),
             Opcode::OP_CHECKSIG => write!(f, "OP_CHECKSIG"),
+            Opcode::OP_CHECKMULTISIG => write!(f, "OP_CHECKMULTISIG"),
             Opcode::OP_EQUAL => write!(f, "OP_EQUAL"),
             Opcode::OP_EQUALVERIFY => write!(f, "OP_EQUALVERIFY"),
         }